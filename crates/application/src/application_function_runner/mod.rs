@@ -75,6 +75,10 @@ use common::{
     RequestId,
 };
 use database::{
+    hybrid_search::{
+        HybridSearch,
+        HybridSearchResult,
+    },
     unauthorized_error,
     Database,
     Token,
@@ -110,6 +114,7 @@ use model::{
         module_loader::ModuleLoader,
         types::ModuleConfig,
     },
+    egress_policy::EgressPolicyModel,
     environment_variables::{
         types::{
             EnvVarName,
@@ -133,7 +138,20 @@ use model::{
         },
         ModuleModel,
     },
-    scheduled_jobs::VirtualSchedulerModel,
+    rate_limits::{
+        types::{
+            RateLimitRequest,
+            RateLimitResult,
+        },
+        RateLimitModel,
+    },
+    scheduled_jobs::{
+        types::{
+            ScheduledJobQueue,
+            ScheduledJobRetryPolicy,
+        },
+        VirtualSchedulerModel,
+    },
     session_requests::{
         types::{
             SessionRequestIdentifier,
@@ -169,6 +187,7 @@ use udf::{
     },
     ActionOutcome,
     EvaluateAppDefinitionsResult,
+    ExecutionRecording,
     FunctionOutcome,
     FunctionResult,
     HttpActionOutcome,
@@ -187,7 +206,9 @@ use value::{
 };
 use vector::{
     PublicVectorSearchQueryResult,
+    SparseVectorSearch,
     VectorSearch,
+    VectorSearchPage,
 };
 
 use self::metrics::{
@@ -299,6 +320,7 @@ impl<RT: Runtime> FunctionRouter<RT> {
                 Some(FunctionMetadata {
                     journal,
                     path_and_args,
+                    replay: None,
                 }),
                 None,
             )
@@ -308,6 +330,32 @@ impl<RT: Runtime> FunctionRouter<RT> {
         Ok((tx, outcome))
     }
 
+    #[fastrace::trace]
+    pub(crate) async fn replay_query_or_mutation(
+        &self,
+        tx: Transaction<RT>,
+        recording: ExecutionRecording,
+        context: ExecutionContext,
+    ) -> anyhow::Result<FunctionOutcome> {
+        let udf_type = recording.udf_type;
+        anyhow::ensure!(udf_type == UdfType::Query || udf_type == UdfType::Mutation);
+        let (_, outcome) = self
+            .function_runner_execute(
+                tx,
+                udf_type,
+                context,
+                None,
+                Some(FunctionMetadata {
+                    journal: QueryJournal::new(),
+                    path_and_args: recording.path_and_args.clone(),
+                    replay: Some(recording),
+                }),
+                None,
+            )
+            .await?;
+        Ok(outcome)
+    }
+
     #[fastrace::trace]
     pub(crate) async fn execute_action(
         &self,
@@ -325,6 +373,7 @@ impl<RT: Runtime> FunctionRouter<RT> {
                 Some(FunctionMetadata {
                     journal: QueryJournal::new(),
                     path_and_args,
+                    replay: None,
                 }),
                 None,
             )
@@ -421,7 +470,7 @@ impl<RT: Runtime> FunctionRouter<RT> {
                 user_tx_size,
                 system_tx_size,
             } = function_tx.reads;
-            let FunctionWrites { updates } = function_tx.writes;
+            let FunctionWrites { updates, merges } = function_tx.writes;
             tx.apply_function_runner_tx(
                 function_tx.begin_timestamp,
                 reads,
@@ -429,6 +478,7 @@ impl<RT: Runtime> FunctionRouter<RT> {
                 user_tx_size,
                 system_tx_size,
                 updates,
+                merges,
                 function_tx.rows_read_by_tablet,
             )?;
             Some(tx)
@@ -642,6 +692,37 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
         Ok(())
     }
 
+    /// Replays a query or mutation from a recording written while
+    /// `UDF_EXECUTION_RECORDING_DIR` was set, at the snapshot it was recorded
+    /// at. Nothing the replay writes is committed.
+    pub async fn replay_execution_recording(
+        &self,
+        request_id: RequestId,
+        identity: Identity,
+        encrypted_recording: &str,
+        caller: FunctionCaller,
+    ) -> anyhow::Result<(Result<JsonPackedValue, JsError>, LogLines)> {
+        if !(identity.is_admin() || identity.is_system()) {
+            anyhow::bail!(unauthorized_error("replay_execution_recording"));
+        }
+        let recording = ExecutionRecording::decrypt(&self.key_broker, encrypted_recording)?;
+        let tx = self
+            .database
+            .begin_with_ts(identity, recording.snapshot_ts, FunctionUsageTracker::new())
+            .await?;
+        let context = ExecutionContext::new(request_id, &caller);
+        let outcome = self
+            .isolate_functions
+            .replay_query_or_mutation(tx, recording, context)
+            .await?;
+        match outcome {
+            FunctionOutcome::Query(outcome) | FunctionOutcome::Mutation(outcome) => {
+                Ok((outcome.result, outcome.log_lines))
+            },
+            _ => anyhow::bail!("Replayed a function that isn't a query or mutation"),
+        }
+    }
+
     // Only used for running queries from REPLs.
     pub async fn run_query_without_caching(
         &self,
@@ -1258,8 +1339,21 @@ impl<RT: Runtime> ApplicationFunctionRunner<RT> {
         let (log_line_sender, log_line_receiver) = mpsc::unbounded_channel();
 
         let inert_identity = tx.inert_identity();
+        // Node actions make their own requests, so the egress policy can't be
+        // enforced on them. Refuse to run them instead of letting them get around
+        // it.
+        let node_egress_policy_configured = module.environment == ModuleEnvironment::Node
+            && !EgressPolicyModel::new(&mut tx).get().await?.is_empty();
         let timer = function_total_timer(module.environment, UdfType::Action);
         let completion_result = match module.environment {
+            ModuleEnvironment::Node if node_egress_policy_configured => {
+                Err(anyhow::anyhow!(ErrorMetadata::bad_request(
+                    "NodeActionsEgressPolicy",
+                    "Node actions can't run while this deployment has an egress policy, since the \
+                     policy can't be enforced on their requests. Move this action to the default \
+                     runtime or remove the egress policy."
+                )))
+            },
             ModuleEnvironment::Isolate => {
                 // TODO: This is the only use case of clone. We should get rid of clone,
                 // when we deprecate that codepath.
@@ -2079,6 +2173,8 @@ impl<RT: Runtime> ActionCallbacks for ApplicationFunctionRunner<RT> {
         udf_args: Vec<JsonValue>,
         scheduled_ts: UnixTimestamp,
         context: ExecutionContext,
+        retry_policy: Option<ScheduledJobRetryPolicy>,
+        queue: Option<ScheduledJobQueue>,
     ) -> anyhow::Result<DeveloperDocumentId> {
         let (_ts, virtual_id, _stats) = self
             .database
@@ -2090,6 +2186,8 @@ impl<RT: Runtime> ActionCallbacks for ApplicationFunctionRunner<RT> {
                     let path = scheduled_path.clone();
                     let args = udf_args.clone();
                     let context = context.clone();
+                    let retry_policy = retry_policy.clone();
+                    let queue = queue.clone();
                     async move {
                         let (path, udf_args) = validate_schedule_args(
                             path,
@@ -2103,7 +2201,14 @@ impl<RT: Runtime> ActionCallbacks for ApplicationFunctionRunner<RT> {
                         .await?;
                         let virtual_id =
                             VirtualSchedulerModel::new(tx, scheduling_component.into())
-                                .schedule(path, udf_args, scheduled_ts, context)
+                                .schedule(
+                                    path,
+                                    udf_args,
+                                    scheduled_ts,
+                                    context,
+                                    retry_policy,
+                                    queue,
+                                )
                                 .await?;
                         Ok(virtual_id)
                     }
@@ -2137,16 +2242,97 @@ impl<RT: Runtime> ActionCallbacks for ApplicationFunctionRunner<RT> {
         Ok(())
     }
 
+    async fn rate_limit(
+        &self,
+        identity: Identity,
+        component: ComponentId,
+        request: RateLimitRequest,
+    ) -> anyhow::Result<RateLimitResult> {
+        let (_ts, result, _stats) = self
+            .database
+            .execute_with_occ_retries(
+                identity,
+                FunctionUsageTracker::new(),
+                "app_funrun_rate_limit",
+                |tx| {
+                    let request = request.clone();
+                    async move {
+                        RateLimitModel::new(tx, component.into())
+                            .limit(request)
+                            .await
+                    }
+                    .into()
+                },
+            )
+            .await?;
+        Ok(result)
+    }
+
+    async fn reset_rate_limit(
+        &self,
+        identity: Identity,
+        component: ComponentId,
+        name: String,
+        key: Option<String>,
+    ) -> anyhow::Result<()> {
+        self.database
+            .execute_with_occ_retries(
+                identity,
+                FunctionUsageTracker::new(),
+                "app_funrun_reset_rate_limit",
+                |tx| {
+                    async {
+                        RateLimitModel::new(tx, component.into())
+                            .reset(&name, key.as_deref())
+                            .await
+                    }
+                    .into()
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
     async fn vector_search(
         &self,
         identity: Identity,
         query: JsonValue,
-    ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)> {
+    ) -> anyhow::Result<(VectorSearchPage, FunctionUsageStats)> {
         let query = VectorSearch::try_from(query).map_err(|e| {
             let message = e.to_string();
             e.context(ErrorMetadata::bad_request("InvalidVectorQuery", message))
         })?;
-        self.database.vector_search(identity, query).await
+        self.database.vector_search_page(identity, query).await
+    }
+
+    async fn hybrid_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<HybridSearchResult>, FunctionUsageStats)> {
+        let query = HybridSearch::try_from(query).map_err(|e| {
+            let message = e.to_string();
+            e.context(ErrorMetadata::bad_request(
+                "InvalidHybridSearchQuery",
+                message,
+            ))
+        })?;
+        self.database.hybrid_search(identity, query).await
+    }
+
+    async fn sparse_vector_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)> {
+        let query = SparseVectorSearch::try_from(query).map_err(|e| {
+            let message = e.to_string();
+            e.context(ErrorMetadata::bad_request(
+                "InvalidSparseVectorQuery",
+                message,
+            ))
+        })?;
+        self.database.sparse_vector_search(identity, query).await
     }
 
     async fn lookup_function_handle(
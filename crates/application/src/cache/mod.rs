@@ -27,8 +27,8 @@ use common::{
     execution_context::ExecutionContext,
     identity::IdentityCacheKey,
     knobs::{
+        DATABASE_UDF_MAX_USER_TIMEOUT,
         DATABASE_UDF_SYSTEM_TIMEOUT,
-        DATABASE_UDF_USER_TIMEOUT,
     },
     query_journal::QueryJournal,
     runtime::Runtime,
@@ -102,8 +102,9 @@ mod metrics;
 // Maximum age of results to tolerate if they're time-dependent.
 pub const MAX_CACHE_AGE: Duration = Duration::from_secs(5);
 
+// Queries can raise their own user timeout up to the deployment's maximum.
 static TOTAL_QUERY_TIMEOUT: LazyLock<Duration> =
-    LazyLock::new(|| *DATABASE_UDF_USER_TIMEOUT + *DATABASE_UDF_SYSTEM_TIMEOUT);
+    LazyLock::new(|| *DATABASE_UDF_MAX_USER_TIMEOUT + *DATABASE_UDF_SYSTEM_TIMEOUT);
 
 #[derive(Clone)]
 pub struct CacheManager<RT: Runtime> {
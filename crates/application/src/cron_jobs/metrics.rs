@@ -2,6 +2,7 @@ use std::time::Duration;
 
 use errors::ErrorMetadataAnyhowExt;
 use metrics::{
+    log_counter,
     log_counter_with_labels,
     log_distribution,
     register_convex_counter,
@@ -40,3 +41,11 @@ register_convex_histogram!(CRON_JOB_EXECUTION_LAG_SECONDS, "Cron job execution l
 pub fn log_cron_job_execution_lag(lag: Duration) {
     log_distribution(&CRON_JOB_EXECUTION_LAG_SECONDS, lag.as_secs_f64());
 }
+
+register_convex_counter!(
+    CRON_JOB_CATCH_UP_RUNS_TOTAL,
+    "Number of cron job runs made to catch up on missed scheduled runs"
+);
+pub fn log_cron_job_catch_up_run() {
+    log_counter(&CRON_JOB_CATCH_UP_RUNS_TOTAL, 1);
+}
@@ -31,7 +31,12 @@ use common::{
         SCHEDULED_JOB_EXECUTION_PARALLELISM,
         UDF_EXECUTOR_OCC_MAX_RETRIES,
     },
-    log_lines::LogLines,
+    log_lines::{
+        LogLevel,
+        LogLine,
+        LogLines,
+        SystemLogMetadata,
+    },
     runtime::Runtime,
     types::{
         FunctionCaller,
@@ -59,9 +64,17 @@ use keybroker::Identity;
 use model::{
     backend_state::BackendStateModel,
     cron_jobs::{
-        next_ts::compute_next_ts,
+        next_ts::{
+            compute_next_ts,
+            compute_ready_ts,
+            following_ts,
+            skip_missed_runs,
+            SkippedRuns,
+            MAX_SKIPPED_RUNS_COUNTED,
+        },
         stream_cron_jobs_to_run,
         types::{
+            CronCatchUpPolicy,
             CronJob,
             CronJobLogLines,
             CronJobResult,
@@ -118,6 +131,17 @@ pub struct CronJobContext<RT: Runtime> {
     function_log: FunctionExecutionLog<RT>,
 }
 
+// What happened to the runs a pending job missed while it was behind schedule.
+enum MissedRuns {
+    // The job is on schedule, or just running a little late.
+    None,
+    // The missed runs are being made up, and this run should include the log
+    // line.
+    CatchingUp(LogLine),
+    // The missed runs were skipped and the job was rescheduled.
+    Skipped,
+}
+
 impl<RT: Runtime> CronJobExecutor<RT> {
     pub async fn run(
         rt: RT,
@@ -212,19 +236,39 @@ impl<RT: Runtime> CronJobExecutor<RT> {
         tx: &mut Transaction<RT>,
     ) -> anyhow::Result<Option<Timestamp>> {
         let now = self.context.rt.generate_timestamp()?;
+        // The earliest ready time of jobs that are due but held back by jitter.
+        let mut next_jittered_ts: Option<Timestamp> = None;
         let mut job_stream = stream_cron_jobs_to_run(tx);
         while let Some(job) = job_stream.try_next().await? {
             let job_id = job.id;
             if self.running_job_ids.contains(&job_id) {
                 continue;
             }
-            let next_ts = job.next_ts;
+            // Jobs are sorted by `next_ts` and jitter only ever delays them, so
+            // no later job can be ready before a jittered one we've already seen.
+            if let Some(jittered_ts) = next_jittered_ts
+                && job.next_ts >= jittered_ts
+            {
+                return Ok(Some(jittered_ts));
+            }
+            // Paused crons are parked after every runnable cron.
+            if job.paused {
+                continue;
+            }
+            let ready_ts = compute_ready_ts(&job.cron_spec, job_id.developer_id, job.next_ts)?;
             // If we can't execute the job return the job's target timestamp. If we're
             // caught up, we can sleep until the timestamp. If we're behind and
             // at our concurrency limit, we can use the timestamp to log how far
             // behind we get.
-            if next_ts > now || self.running_job_ids.len() == *SCHEDULED_JOB_EXECUTION_PARALLELISM {
-                return Ok(Some(next_ts));
+            let earliest_ts = next_jittered_ts.map_or(ready_ts, |ts| ts.min(ready_ts));
+            if job.next_ts > now
+                || self.running_job_ids.len() == *SCHEDULED_JOB_EXECUTION_PARALLELISM
+            {
+                return Ok(Some(earliest_ts));
+            }
+            if ready_ts > now {
+                next_jittered_ts = Some(earliest_ts);
+                continue;
             }
             let root = get_sampled_span(
                 &self.instance_name,
@@ -253,7 +297,7 @@ impl<RT: Runtime> CronJobExecutor<RT> {
             );
             self.running_job_ids.insert(job_id);
         }
-        Ok(None)
+        Ok(next_jittered_ts)
     }
 }
 
@@ -329,13 +373,41 @@ impl<RT: Runtime> CronJobContext<RT> {
             })?
             .udf_type;
 
+        let catch_up_log_line = match job.state {
+            CronJobState::Pending => {
+                match self
+                    .apply_catch_up_policy(&mut tx, &job, udf_type, mutation_retry_count)
+                    .await?
+                {
+                    MissedRuns::None => None,
+                    MissedRuns::CatchingUp(log_line) => Some(log_line),
+                    MissedRuns::Skipped => {
+                        self.database
+                            .commit_with_write_source(tx, "cron_skip_missed_runs")
+                            .await?;
+                        return Ok(job.id);
+                    },
+                }
+            },
+            CronJobState::InProgress { .. } => None,
+        };
+
         let job_id = job.id;
         match udf_type {
             UdfType::Mutation => {
-                self.handle_mutation(tx, job, usage_tracker, mutation_retry_count)
+                self.handle_mutation(
+                    tx,
+                    job,
+                    usage_tracker,
+                    mutation_retry_count,
+                    catch_up_log_line,
+                )
+                .await?
+            },
+            UdfType::Action => {
+                self.handle_action(tx, job, usage_tracker, catch_up_log_line)
                     .await?
             },
-            UdfType::Action => self.handle_action(tx, job, usage_tracker).await?,
             udf_type => {
                 anyhow::bail!(
                     "Cron trying to execute {} which is a {} function. This should have been \
@@ -404,6 +476,7 @@ impl<RT: Runtime> CronJobContext<RT> {
         job: CronJob,
         usage_tracker: FunctionUsageTracker,
         mutation_retry_count: usize,
+        catch_up_log_line: Option<LogLine>,
     ) -> anyhow::Result<()> {
         let start = self.rt.monotonic_now();
         let identity = tx.inert_identity();
@@ -449,6 +522,9 @@ impl<RT: Runtime> CronJobContext<RT> {
         let stats = tx.take_stats();
         let execution_time = start.elapsed();
         let execution_time_f64 = execution_time.as_secs_f64();
+        if let Some(log_line) = catch_up_log_line {
+            outcome.log_lines.push(log_line);
+        }
         let truncated_log_lines = self.truncate_log_lines(outcome.log_lines.clone());
 
         let mut model = CronModel::new(&mut tx, component);
@@ -537,6 +613,7 @@ impl<RT: Runtime> CronJobContext<RT> {
         mut tx: Transaction<RT>,
         job: CronJob,
         usage_tracker: FunctionUsageTracker,
+        catch_up_log_line: Option<LogLine>,
     ) -> anyhow::Result<()> {
         let namespace = tx.table_mapping().tablet_namespace(job.id.tablet_id)?;
         let component = match namespace {
@@ -571,7 +648,7 @@ impl<RT: Runtime> CronJobContext<RT> {
                     component: component_path,
                     udf_path: job.cron_spec.udf_path.clone(),
                 };
-                let completion = self
+                let mut completion = self
                     .runner
                     .run_action_no_udf_log(
                         PublicFunctionPath::Component(path),
@@ -582,6 +659,9 @@ impl<RT: Runtime> CronJobContext<RT> {
                         context.clone(),
                     )
                     .await?;
+                if let Some(log_line) = catch_up_log_line {
+                    completion.log_lines.push(log_line);
+                }
                 let execution_time_f64 = completion.execution_time.as_secs_f64();
                 let truncated_log_lines = self.truncate_log_lines(completion.log_lines.clone());
 
@@ -756,97 +836,33 @@ impl<RT: Runtime> CronJobContext<RT> {
     ) -> anyhow::Result<()> {
         let now = self.rt.generate_timestamp()?;
         let prev_ts = job.next_ts;
-        let mut next_ts = compute_next_ts(&job.cron_spec, Some(prev_ts), now)?;
+        // With `RunAll`, missed runs execute back to back, so don't skip past
+        // them here. Runs beyond the policy's max are dropped on pickup instead.
+        let run_all = matches!(job.cron_spec.catch_up, CronCatchUpPolicy::RunAll { .. });
+        let mut next_ts = if run_all {
+            following_ts(&job.cron_spec, prev_ts)?
+        } else {
+            compute_next_ts(&job.cron_spec, Some(prev_ts), now)?
+        };
         let mut num_skipped = 0;
         let first_skipped_ts = next_ts;
-        let (component, component_path) = self.get_job_component(tx, job.id).await?;
-        let mut model = CronModel::new(tx, component);
-        while next_ts < now {
+        while !run_all && next_ts < now {
             num_skipped += 1;
             next_ts = compute_next_ts(&job.cron_spec, Some(next_ts), now)?;
         }
         if num_skipped > 0 {
-            let job_id = job.id.developer_id;
-            tracing::info!(
-                "Skipping {num_skipped} run(s) of job {job_id} because multiple scheduled runs \
-                 are in the past"
-            );
-            match udf_type {
-                // These aren't system errors in the sense that they represent an issue with Convex
-                // (e.g. they can occur due to the developer pausing their deployment)
-                // but they get logged similarly, since they shouldn't count towards usage and
-                // should appear as errors
-                UdfType::Mutation => {
-                    self.function_log
-                        .log_mutation_system_error(
-                            &anyhow::anyhow!(ErrorMetadata::bad_request(
-                                "SkippingPastScheduledRuns",
-                                format!(
-                                    "Skipping {num_skipped} run(s) of job {job_id} because \
-                                     multiple scheduled runs are in the past"
-                                )
-                            )),
-                            CanonicalizedComponentFunctionPath {
-                                component: component_path,
-                                udf_path: job.cron_spec.udf_path.clone(),
-                            },
-                            job.cron_spec.udf_args.clone(),
-                            identity,
-                            self.rt.monotonic_now(),
-                            FunctionCaller::Cron,
-                            context,
-                            None,
-                            mutation_retry_count
-                                .context("Mutations should have mutation_retry_count set")?,
-                        )
-                        .await?;
-                },
-                UdfType::Action => {
-                    anyhow::ensure!(
-                        mutation_retry_count.is_none(),
-                        "Actions should not have mutation_retry_count set"
-                    );
-                    let err = anyhow::anyhow!(ErrorMetadata::bad_request(
-                        "SkippingPastScheduledRuns",
-                        format!(
-                            "Skipping {num_skipped} run(s) of job {job_id} because multiple \
-                             scheduled runs are in the past"
-                        )
-                    ));
-                    self.function_log
-                        .log_action_system_error(
-                            &err,
-                            CanonicalizedComponentFunctionPath {
-                                component: component_path,
-                                udf_path: job.cron_spec.udf_path.clone(),
-                            },
-                            job.cron_spec.udf_args.clone(),
-                            identity,
-                            self.rt.monotonic_now(),
-                            FunctionCaller::Cron,
-                            vec![].into(),
-                            context,
-                        )
-                        .await?;
-                    tracing::error!("{err:#}");
-                },
-                UdfType::Query | UdfType::HttpAction => {
-                    anyhow::bail!("Executing unexpected function type as a cron")
-                },
-            }
-
-            let status = CronJobStatus::Canceled {
-                num_canceled: num_skipped,
-            };
-            let log_lines = CronJobLogLines {
-                log_lines: vec![].into(),
-                is_truncated: false,
-            };
             let mut canceled_job = job.clone();
             canceled_job.next_ts = first_skipped_ts;
-            model
-                .insert_cron_job_log(&canceled_job, status, log_lines, 0.0)
-                .await?;
+            self.log_skipped_runs(
+                identity,
+                tx,
+                &canceled_job,
+                num_skipped,
+                udf_type,
+                context,
+                mutation_retry_count,
+            )
+            .await?;
         }
 
         let next_run = CronNextRun {
@@ -855,7 +871,210 @@ impl<RT: Runtime> CronJobContext<RT> {
             prev_ts: Some(prev_ts),
             next_ts,
         };
-        model.update_job_state(next_run).await?;
+        let (component, _) = self.get_job_component(tx, job.id).await?;
+        CronModel::new(tx, component)
+            .update_job_state(next_run)
+            .await?;
+        Ok(())
+    }
+
+    // Applies the job's catch-up policy if it has fallen behind its schedule,
+    // e.g. after an outage. If missed runs were skipped, the job has been
+    // rescheduled and shouldn't run in this transaction.
+    async fn apply_catch_up_policy(
+        &self,
+        tx: &mut Transaction<RT>,
+        job: &CronJob,
+        udf_type: UdfType,
+        mutation_retry_count: usize,
+    ) -> anyhow::Result<MissedRuns> {
+        let max_runs = match job.cron_spec.catch_up {
+            // The one run covers everything that was missed, and the rest are
+            // skipped once it completes.
+            CronCatchUpPolicy::RunOnce => return Ok(MissedRuns::None),
+            CronCatchUpPolicy::Skip => 0,
+            CronCatchUpPolicy::RunAll { max } => max as usize,
+        };
+        let now = self.rt.generate_timestamp()?;
+        // Only count far enough to tell whether the job is over its budget, since
+        // a long outage can leave an arbitrary number of runs in the past.
+        let mut num_missed = 0;
+        let mut ts = job.next_ts;
+        while ts <= now && num_missed <= max_runs.max(1) {
+            num_missed += 1;
+            ts = following_ts(&job.cron_spec, ts)?;
+        }
+        // A single past run just means we're running a little late.
+        if num_missed <= 1 {
+            return Ok(MissedRuns::None);
+        }
+
+        let job_id = job.id.developer_id;
+        if num_missed <= max_runs {
+            // Catching up is the policy working as intended, so unlike skipped
+            // runs it's logged as info rather than as an error.
+            let message = format!(
+                "Catching up on missed runs of job {job_id}, {num_missed} run(s) behind schedule"
+            );
+            tracing::info!("{message}");
+            metrics::log_cron_job_catch_up_run();
+            let log_line = LogLine::new_system_log_line(
+                LogLevel::Info,
+                vec![message],
+                self.rt.unix_timestamp(),
+                SystemLogMetadata {
+                    code: "info:cronCatchUp".to_string(),
+                },
+            );
+            return Ok(MissedRuns::CatchingUp(log_line));
+        }
+
+        let identity = tx.inert_identity();
+        let context = ExecutionContext::new(RequestId::new(), &FunctionCaller::Cron);
+        let mutation_retry_count =
+            matches!(udf_type, UdfType::Mutation).then_some(mutation_retry_count);
+
+        // Skip the oldest runs, keeping the most recent `max_runs`.
+        let SkippedRuns {
+            num_skipped,
+            next_ts,
+        } = skip_missed_runs(&job.cron_spec, job.next_ts, now, max_runs as u64)?;
+        self.log_skipped_runs(
+            identity,
+            tx,
+            job,
+            num_skipped as i64,
+            udf_type,
+            context,
+            mutation_retry_count,
+        )
+        .await?;
+        let next_run = CronNextRun {
+            cron_job_id: job_id,
+            state: CronJobState::Pending,
+            prev_ts: job.prev_ts,
+            next_ts,
+        };
+        let (component, _) = self.get_job_component(tx, job.id).await?;
+        CronModel::new(tx, component)
+            .update_job_state(next_run)
+            .await?;
+        Ok(MissedRuns::Skipped)
+    }
+
+    // Records `num_skipped` runs, starting at `job.next_ts`, as canceled in both
+    // the function logs and the cron job logs.
+    async fn log_skipped_runs(
+        &self,
+        identity: InertIdentity,
+        tx: &mut Transaction<RT>,
+        job: &CronJob,
+        num_skipped: i64,
+        udf_type: UdfType,
+        context: ExecutionContext,
+        mutation_retry_count: Option<usize>,
+    ) -> anyhow::Result<()> {
+        let job_id = job.id.developer_id;
+        // Skipped runs of calendar schedules are only counted up to a limit.
+        let num_skipped_str = if num_skipped as u64 >= MAX_SKIPPED_RUNS_COUNTED {
+            format!("at least {num_skipped}")
+        } else {
+            num_skipped.to_string()
+        };
+        tracing::info!(
+            "Skipping {num_skipped_str} run(s) of job {job_id} because multiple scheduled runs \
+             are in the past"
+        );
+        let (component, component_path) = self.get_job_component(tx, job.id).await?;
+        let err = anyhow::anyhow!(ErrorMetadata::bad_request(
+            "SkippingPastScheduledRuns",
+            format!(
+                "Skipping {num_skipped_str} run(s) of job {job_id} because multiple scheduled \
+                 runs are in the past"
+            )
+        ));
+        self.log_schedule_event(
+            err,
+            identity,
+            component_path,
+            job,
+            udf_type,
+            context,
+            mutation_retry_count,
+        )
+        .await?;
+
+        let status = CronJobStatus::Canceled {
+            num_canceled: num_skipped,
+        };
+        let log_lines = CronJobLogLines {
+            log_lines: vec![].into(),
+            is_truncated: false,
+        };
+        CronModel::new(tx, component)
+            .insert_cron_job_log(job, status, log_lines, 0.0)
+            .await?;
+        Ok(())
+    }
+
+    async fn log_schedule_event(
+        &self,
+        err: anyhow::Error,
+        identity: InertIdentity,
+        component_path: ComponentPath,
+        job: &CronJob,
+        udf_type: UdfType,
+        context: ExecutionContext,
+        mutation_retry_count: Option<usize>,
+    ) -> anyhow::Result<()> {
+        let path = CanonicalizedComponentFunctionPath {
+            component: component_path,
+            udf_path: job.cron_spec.udf_path.clone(),
+        };
+        match udf_type {
+            // These aren't system errors in the sense that they represent an issue with Convex
+            // (e.g. they can occur due to the developer pausing their deployment)
+            // but they get logged similarly, since they shouldn't count towards usage and
+            // should appear as errors
+            UdfType::Mutation => {
+                self.function_log
+                    .log_mutation_system_error(
+                        &err,
+                        path,
+                        job.cron_spec.udf_args.clone(),
+                        identity,
+                        self.rt.monotonic_now(),
+                        FunctionCaller::Cron,
+                        context,
+                        None,
+                        mutation_retry_count
+                            .context("Mutations should have mutation_retry_count set")?,
+                    )
+                    .await?;
+            },
+            UdfType::Action => {
+                anyhow::ensure!(
+                    mutation_retry_count.is_none(),
+                    "Actions should not have mutation_retry_count set"
+                );
+                self.function_log
+                    .log_action_system_error(
+                        &err,
+                        path,
+                        job.cron_spec.udf_args.clone(),
+                        identity,
+                        self.rt.monotonic_now(),
+                        FunctionCaller::Cron,
+                        vec![].into(),
+                        context,
+                    )
+                    .await?;
+                tracing::error!("{err:#}");
+            },
+            UdfType::Query | UdfType::HttpAction => {
+                anyhow::bail!("Executing unexpected function type as a cron")
+            },
+        }
         Ok(())
     }
 }
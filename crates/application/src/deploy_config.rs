@@ -49,7 +49,10 @@ use database::{
     WriteSource,
     SCHEMAS_TABLE,
 };
-use errors::ErrorMetadata;
+use errors::{
+    ErrorMetadata,
+    ErrorMetadataAnyhowExt,
+};
 use fastrace::{
     future::FutureExt as _,
     Span,
@@ -94,10 +97,13 @@ use model::{
     },
     environment_variables::EnvironmentVariablesModel,
     external_packages::types::ExternalDepsPackageId,
-    modules::module_versions::{
-        AnalyzedModule,
-        ModuleSource,
-        SourceMap,
+    modules::{
+        module_versions::{
+            AnalyzedModule,
+            ModuleSource,
+            SourceMap,
+        },
+        wasm::decode_wasm_module,
     },
     source_packages::{
         types::SourcePackage,
@@ -995,15 +1001,49 @@ impl TryFrom<ModuleJson> for ModuleConfig {
             // Default to using the path for backwards compatibility
             None => deprecated_extract_environment_from_path(path.clone())?,
         };
+        let path = parse_module_path(&path)?;
+        let source = ModuleSource::new(&source);
+        if path.is_wasm() {
+            validate_wasm_module_config(&path, &source, source_map.as_ref(), environment)?;
+        }
         Ok(ModuleConfig {
-            path: parse_module_path(&path)?,
-            source: ModuleSource::new(&source),
+            path,
+            source,
             source_map,
             environment,
         })
     }
 }
 
+fn validate_wasm_module_config(
+    path: &ModulePath,
+    source: &ModuleSource,
+    source_map: Option<&SourceMap>,
+    environment: ModuleEnvironment,
+) -> anyhow::Result<()> {
+    if environment != ModuleEnvironment::Isolate {
+        anyhow::bail!(ErrorMetadata::bad_request(
+            "InvalidWasmModule",
+            format!(
+                "WebAssembly module {} can only be used outside of \"use node\" files.",
+                path.as_str()
+            ),
+        ));
+    }
+    if source_map.is_some() {
+        anyhow::bail!(ErrorMetadata::bad_request(
+            "InvalidWasmModule",
+            format!(
+                "WebAssembly module {} can't have a source map.",
+                path.as_str()
+            ),
+        ));
+    }
+    decode_wasm_module(source)
+        .map_err(|e| e.wrap_error_message(|msg| format!("{}: {msg}", path.as_str())))?;
+    Ok(())
+}
+
 pub fn parse_module_path(path: &str) -> anyhow::Result<ModulePath> {
     path.parse().map_err(|e: anyhow::Error| {
         let msg = format!("{path} is not a valid path to a Convex module. {e}");
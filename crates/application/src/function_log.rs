@@ -17,6 +17,7 @@ use anyhow::Context as _;
 use common::{
     components::{
         CanonicalizedComponentFunctionPath,
+        ComponentId,
         ComponentPath,
     },
     errors::{
@@ -1118,18 +1119,21 @@ impl<RT: Runtime> FunctionExecutionLog<RT> {
     }
 
     /// Indicates that as of now (`timestamp`), the next scheduled job is at
-    /// `next_job_ts` (None if there are no pending jobs)
+    /// `next_job_ts` (None if there are no pending jobs), and likewise for
+    /// each component's named queues in `queue_next_job_ts`.
     pub fn log_scheduled_job_stats(
         &self,
         next_job_ts: Option<SystemTime>,
         timestamp: SystemTime,
         num_running_jobs: u64,
+        queue_next_job_ts: BTreeMap<(ComponentId, String), Option<SystemTime>>,
     ) {
-        if let Err(mut e) =
-            self.inner
-                .lock()
-                .log_scheduled_job_stats(next_job_ts, timestamp, num_running_jobs)
-        {
+        if let Err(mut e) = self.inner.lock().log_scheduled_job_stats(
+            next_job_ts,
+            timestamp,
+            num_running_jobs,
+            queue_next_job_ts,
+        ) {
             report_error_sync(&mut e);
         }
     }
@@ -1453,13 +1457,23 @@ impl<RT: Runtime> FunctionExecutionLog<RT> {
         }
     }
 
-    pub fn scheduled_job_lag(&self, window: MetricsWindow) -> anyhow::Result<Timeseries> {
+    /// The scheduler's lag over `window`, either overall or for the named
+    /// `queue` in a component.
+    pub fn scheduled_job_lag(
+        &self,
+        window: MetricsWindow,
+        queue: Option<(ComponentId, &str)>,
+    ) -> anyhow::Result<Timeseries> {
         let metrics = {
             let inner = self.inner.lock();
             inner.metrics.clone()
         };
+        let name = match queue {
+            Some((component, queue)) => scheduled_job_queue_next_ts_metric(component, queue),
+            None => scheduled_job_next_ts_metric().to_string(),
+        };
         let buckets = metrics
-            .query_gauge(scheduled_job_next_ts_metric(), window.start..window.end)?
+            .query_gauge(&name, window.start..window.end)?
             .into_iter()
             .collect();
         let mut timeseries = window.resample_gauges(&metrics, buckets)?;
@@ -1688,7 +1702,15 @@ impl<RT: Runtime> Inner<RT> {
         next_job_ts: Option<SystemTime>,
         now: SystemTime,
         num_running_jobs: u64,
+        queue_next_job_ts: BTreeMap<(ComponentId, String), Option<SystemTime>>,
     ) -> anyhow::Result<()> {
+        for ((component, queue), queue_next_job_ts) in queue_next_job_ts {
+            self.add_scheduled_job_next_ts_gauge(
+                &scheduled_job_queue_next_ts_metric(component, &queue),
+                queue_next_job_ts,
+                now,
+            )?;
+        }
         let name = scheduled_job_next_ts_metric();
         // -Infinity means there is no scheduled job
         let value = next_job_ts.map_or(-f32::INFINITY, |ts| signed_duration_since(now, ts));
@@ -1709,6 +1731,17 @@ impl<RT: Runtime> Inner<RT> {
                 },
             }]);
         }
+        self.add_scheduled_job_next_ts_gauge(name, next_job_ts, now)
+    }
+
+    fn add_scheduled_job_next_ts_gauge(
+        &mut self,
+        name: &str,
+        next_job_ts: Option<SystemTime>,
+        now: SystemTime,
+    ) -> anyhow::Result<()> {
+        // -Infinity means there is no scheduled job
+        let value = next_job_ts.map_or(-f32::INFINITY, |ts| signed_duration_since(now, ts));
         match self.metrics.add_gauge(name, now, value) {
             Ok(()) => (),
             Err(UdfMetricsError::SamplePrecedesCutoff { ts: _, cutoff }) => {
@@ -1841,6 +1874,14 @@ fn scheduled_job_next_ts_metric() -> &'static str {
     "scheduled_jobs:next_ts"
 }
 
+// Queue names are only unique within a component.
+fn scheduled_job_queue_next_ts_metric(component: ComponentId, queue: &str) -> MetricName {
+    let component = component
+        .serialize_to_string()
+        .unwrap_or_else(|| "root".to_string());
+    format!("scheduled_jobs:queue:{component}:{queue}:next_ts")
+}
+
 fn udf_metric_name(identifier: &UdfIdentifier) -> String {
     let (component, id) = identifier.clone().into_component_and_udf_path();
     match component {
@@ -366,8 +366,8 @@ use value::{
     TableNamespace,
 };
 use vector::{
-    PublicVectorSearchQueryResult,
     VectorSearch,
+    VectorSearchPage,
 };
 
 use crate::{
@@ -956,8 +956,8 @@ impl<RT: Runtime> Application<RT> {
         &self,
         identity: Identity,
         query: VectorSearch,
-    ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)> {
-        self.database.vector_search(identity, query).await
+    ) -> anyhow::Result<(VectorSearchPage, FunctionUsageStats)> {
+        self.database.vector_search_page(identity, query).await
     }
 
     pub async fn get_source_code(
@@ -2453,6 +2453,30 @@ impl<RT: Runtime> Application<RT> {
         })
     }
 
+    /// Replays a recorded query or mutation at the snapshot it was recorded
+    /// at, without committing anything it writes. Only admins can replay
+    /// recordings, so logs and errors aren't redacted.
+    pub async fn replay_execution_recording(
+        &self,
+        request_id: RequestId,
+        identity: Identity,
+        encrypted_recording: &str,
+        caller: FunctionCaller,
+    ) -> anyhow::Result<Result<FunctionReturn, FunctionError>> {
+        let (result, log_lines) = self
+            .runner
+            .replay_execution_recording(request_id.clone(), identity, encrypted_recording, caller)
+            .await?;
+        let log_lines = RedactedLogLines::from_log_lines(log_lines, false);
+        Ok(match result {
+            Ok(value) => Ok(FunctionReturn { value, log_lines }),
+            Err(error) => Err(FunctionError {
+                error: RedactedJsError::from_js_error(error, false, request_id),
+                log_lines,
+            }),
+        })
+    }
+
     #[fastrace::trace]
     pub async fn build_external_node_deps(
         &self,
@@ -2762,6 +2786,20 @@ impl<RT: Runtime> Application<RT> {
                     .map(|auth_info| auth_info.into_value())
                     .collect();
 
+                // HS256 providers reference their shared secret by environment variable name.
+                let mut shared_secrets = BTreeMap::new();
+                for auth_info in &auth_info_values {
+                    if let AuthInfo::CustomJwt {
+                        secret_env_var: Some(name),
+                        ..
+                    } = auth_info
+                        && let Some(env_var) =
+                            EnvironmentVariablesModel::new(&mut tx).get(name).await?
+                    {
+                        shared_secrets.insert(name.clone(), env_var.into_value().into_value());
+                    }
+                }
+
                 let should_redact_errors = self
                     .log_visibility
                     .should_redact_logs_and_error(
@@ -2776,6 +2814,7 @@ impl<RT: Runtime> Application<RT> {
                     Auth0IdToken(id_token.clone()),
                     cached_http_client_for(ClientPurpose::ProviderMetadata),
                     auth_info_values.clone(),
+                    shared_secrets,
                     system_time,
                     should_redact_errors,
                 )
@@ -2914,11 +2953,17 @@ impl<RT: Runtime> Application<RT> {
         &self,
         identity: Identity,
         window: MetricsWindow,
+        queue: Option<(ComponentId, String)>,
     ) -> anyhow::Result<Timeseries> {
         if !(identity.is_admin() || identity.is_system()) {
             anyhow::bail!(unauthorized_error("scheduled_job_lag"));
         }
-        self.function_log.scheduled_job_lag(window)
+        self.function_log.scheduled_job_lag(
+            window,
+            queue
+                .as_ref()
+                .map(|(component, queue)| (*component, queue.as_str())),
+        )
     }
 
     pub async fn cancel_all_jobs(
@@ -2,7 +2,7 @@ use std::{
     cmp,
     collections::{
         BTreeMap,
-        HashSet,
+        HashMap,
     },
     sync::Arc,
     time::{
@@ -11,6 +11,7 @@ use std::{
     },
 };
 
+use anyhow::Context;
 use common::{
     backoff::Backoff,
     components::{
@@ -38,9 +39,11 @@ use common::{
         SCHEDULED_JOB_GARBAGE_COLLECTION_MAX_BACKOFF,
         SCHEDULED_JOB_INITIAL_BACKOFF,
         SCHEDULED_JOB_MAX_BACKOFF,
+        SCHEDULED_JOB_QUEUE_STARVATION_TIMEOUT,
         SCHEDULED_JOB_RETENTION,
         UDF_EXECUTOR_OCC_MAX_RETRIES,
     },
+    maybe_val,
     pause::Fault,
     query::{
         IndexRange,
@@ -54,12 +57,14 @@ use common::{
     },
     types::{
         FunctionCaller,
+        MaybeValue,
         UdfType,
     },
     RequestId,
 };
 use database::{
     Database,
+    IndexModel,
     ResolvedQuery,
     Transaction,
 };
@@ -73,9 +78,7 @@ use futures::{
     select_biased,
     Future,
     FutureExt,
-    TryStreamExt,
 };
-use futures_async_stream::try_stream;
 use keybroker::Identity;
 use model::{
     backend_state::BackendStateModel,
@@ -83,13 +86,17 @@ use model::{
     scheduled_jobs::{
         types::{
             ScheduledJob,
+            ScheduledJobQueue,
+            ScheduledJobRetryClass,
             ScheduledJobState,
         },
         SchedulerModel,
         COMPLETED_TS_FIELD,
         NEXT_TS_FIELD,
+        PENDING_QUEUE_FIELD,
         SCHEDULED_JOBS_INDEX,
         SCHEDULED_JOBS_INDEX_BY_COMPLETED_TS,
+        SCHEDULED_JOBS_INDEX_BY_QUEUE,
         SCHEDULED_JOBS_TABLE,
     },
 };
@@ -98,7 +105,11 @@ use sentry::SentryFutureExt;
 use sync_types::Timestamp;
 use tokio::sync::mpsc;
 use usage_tracking::FunctionUsageTracker;
-use value::ResolvedDocumentId;
+use value::{
+    ConvexValue,
+    ResolvedDocumentId,
+    TableNamespace,
+};
 
 use crate::{
     application_function_runner::ApplicationFunctionRunner,
@@ -149,12 +160,22 @@ impl ScheduledJobRunner {
     }
 }
 
+/// Identifies a queue of scheduled jobs. Queue names are scoped to the
+/// component that scheduled the job, and `None` is the default queue.
+type QueueKey = (TableNamespace, Option<String>);
+
 pub struct ScheduledJobExecutor<RT: Runtime> {
     context: ScheduledJobContext<RT>,
     instance_name: String,
-    running_job_ids: HashSet<ResolvedDocumentId>,
+    running_jobs: HashMap<ResolvedDocumentId, QueueKey>,
+    num_running_by_queue: HashMap<QueueKey, usize>,
     /// Some if there's at least one pending job. May be in the past!
     next_job_ready_time: Option<Timestamp>,
+    /// The next job ready time for each named queue with a pending job.
+    next_queue_ready_times: BTreeMap<(ComponentId, String), Timestamp>,
+    /// The named queues included in the last logged stats, so we can log when
+    /// they drain.
+    last_logged_queues: Vec<(ComponentId, String)>,
     job_finished_tx: mpsc::Sender<ResolvedDocumentId>,
     job_finished_rx: mpsc::Receiver<ResolvedDocumentId>,
     /// The last time we logged stats, used to rate limit logging
@@ -206,8 +227,11 @@ impl<RT: Runtime> ScheduledJobExecutor<RT> {
                 function_log,
             },
             instance_name,
-            running_job_ids: HashSet::new(),
+            running_jobs: HashMap::new(),
+            num_running_by_queue: HashMap::new(),
             next_job_ready_time: None,
+            next_queue_ready_times: BTreeMap::new(),
+            last_logged_queues: Vec::new(),
             job_finished_tx,
             job_finished_rx,
             last_stats_log: rt.system_time(),
@@ -240,8 +264,9 @@ impl<RT: Runtime> ScheduledJobExecutor<RT> {
         self.next_job_ready_time = if is_backend_stopped {
             // If the backend is stopped we shouldn't poll. Our subscription will notify us
             // when the backend is started again.
+            self.next_queue_ready_times.clear();
             None
-        } else if self.running_job_ids.len() == *SCHEDULED_JOB_EXECUTION_PARALLELISM {
+        } else if self.running_jobs.len() == *SCHEDULED_JOB_EXECUTION_PARALLELISM {
             // A scheduled job may have been added, but we can't do anything because we're
             // still running jobs at our concurrency limit.
             self.next_job_ready_time
@@ -297,7 +322,7 @@ impl<RT: Runtime> ScheduledJobExecutor<RT> {
                 if num_jobs > 0 {
                     for job_id in job_ids {
                         pause_client.wait(SCHEDULED_JOB_EXECUTED).await;
-                        self.running_job_ids.remove(&job_id);
+                        self.finish_job(job_id);
                     }
                 } else {
                     anyhow::bail!("Job results channel closed, this is unexpected!");
@@ -311,8 +336,12 @@ impl<RT: Runtime> ScheduledJobExecutor<RT> {
         Ok(())
     }
 
-    fn log_scheduled_job_stats(&self, next_job_ready_time: Option<SystemTime>, now: SystemTime) {
-        metrics::log_num_running_jobs(self.running_job_ids.len());
+    fn log_scheduled_job_stats(
+        &mut self,
+        next_job_ready_time: Option<SystemTime>,
+        now: SystemTime,
+    ) {
+        metrics::log_num_running_jobs(self.running_jobs.len());
         if let Some(next_job_ts) = next_job_ready_time {
             metrics::log_scheduled_job_execution_lag(
                 now.duration_since(next_job_ts).unwrap_or(Duration::ZERO),
@@ -320,16 +349,52 @@ impl<RT: Runtime> ScheduledJobExecutor<RT> {
         } else {
             metrics::log_scheduled_job_execution_lag(Duration::ZERO);
         }
+        // Queues that drained since we last logged are logged as having no
+        // pending jobs.
+        let mut queue_ready_times: BTreeMap<_, _> = self
+            .last_logged_queues
+            .drain(..)
+            .map(|queue| (queue, None))
+            .collect();
+        for (queue, ts) in &self.next_queue_ready_times {
+            queue_ready_times.insert(queue.clone(), Some(SystemTime::from(*ts)));
+        }
+        self.last_logged_queues = self.next_queue_ready_times.keys().cloned().collect();
         self.context.function_log.log_scheduled_job_stats(
             next_job_ready_time,
             now,
-            self.running_job_ids.len() as u64,
+            self.running_jobs.len() as u64,
+            queue_ready_times,
         );
     }
 
-    /// Reads through scheduled jobs in timestamp ascending order and starts any
-    /// that are allowed by our concurrency limit and the jobs' scheduled
-    /// time.
+    fn start_job(&mut self, job_id: ResolvedDocumentId, queue: QueueKey) {
+        *self.num_running_by_queue.entry(queue.clone()).or_default() += 1;
+        self.running_jobs.insert(job_id, queue);
+    }
+
+    fn finish_job(&mut self, job_id: ResolvedDocumentId) {
+        let Some(queue) = self.running_jobs.remove(&job_id) else {
+            return;
+        };
+        if let Some(num_running) = self.num_running_by_queue.get_mut(&queue) {
+            *num_running -= 1;
+            if *num_running == 0 {
+                self.num_running_by_queue.remove(&queue);
+            }
+        }
+    }
+
+    /// Reads through each queue of scheduled jobs in timestamp ascending order
+    /// and starts any that are allowed by our concurrency limit, the queue's
+    /// concurrency limit and the jobs' scheduled time.
+    ///
+    /// Ready jobs from queues with a higher priority start first, unless a job
+    /// has been ready for longer than `SCHEDULED_JOB_QUEUE_STARVATION_TIMEOUT`,
+    /// so busy high priority queues can't hold back the others forever.
+    /// Between queues of the same priority, the one with the fewest running
+    /// jobs goes first so that a burst of jobs in one queue can't starve the
+    /// others.
     ///
     /// Returns the time at which the next job in the queue will be ready to
     /// run. If the scheduler is behind, the returned time may be in the
@@ -339,103 +404,282 @@ impl<RT: Runtime> ScheduledJobExecutor<RT> {
         tx: &mut Transaction<RT>,
     ) -> anyhow::Result<Option<Timestamp>> {
         let now = self.context.rt.generate_timestamp()?;
-        let mut job_stream = self.context.stream_jobs_to_run(tx);
-        while let Some(job) = job_stream.try_next().await? {
-            let (job_id, job) = job.clone().into_id_and_value();
-            if self.running_job_ids.contains(&job_id) {
-                continue;
+        let starving_ts = now.sub(*SCHEDULED_JOB_QUEUE_STARVATION_TIMEOUT).ok();
+        let mut cursors = self.context.open_queue_cursors(tx).await?;
+        for cursor in cursors.iter_mut() {
+            cursor.skip_running(tx, &self.running_jobs).await?;
+        }
+        cursors.retain(|cursor| cursor.head.is_some());
+
+        while self.running_jobs.len() < *SCHEDULED_JOB_EXECUTION_PARALLELISM {
+            let next = cursors
+                .iter()
+                .enumerate()
+                .filter_map(|(i, cursor)| {
+                    let next_ts = cursor.head_next_ts()?;
+                    let num_running = self
+                        .num_running_by_queue
+                        .get(&cursor.queue)
+                        .copied()
+                        .unwrap_or(0);
+                    let priority = if starving_ts.is_some_and(|ts| next_ts <= ts) {
+                        u32::MAX
+                    } else {
+                        cursor.priority
+                    };
+                    (next_ts <= now && num_running < cursor.max_concurrency).then_some((
+                        cmp::Reverse(priority),
+                        num_running,
+                        next_ts,
+                        i,
+                    ))
+                })
+                .min();
+            let Some((.., i)) = next else {
+                break;
+            };
+            let cursor = &mut cursors[i];
+            let (job_id, job) = cursor
+                .head
+                .take()
+                .context("Missing scheduled job at head of queue")?
+                .into_id_and_value();
+            self.spawn_job(job, job_id);
+            self.start_job(job_id, cursor.queue.clone());
+            cursor.advance(tx).await?;
+            cursor.skip_running(tx, &self.running_jobs).await?;
+            if cursor.head.is_none() {
+                cursors.swap_remove(i);
             }
-            let next_ts = job
-                .next_ts
-                .ok_or_else(|| anyhow::anyhow!("Could not get next_ts to run scheduled job at"))?;
-            // If we can't execute the job return the job's target timestamp. If we're
-            // caught up, we can sleep until the timestamp. If we're behind and
-            // at our concurrency limit, we can use the timestamp to log how far
-            // behind we get.
-            if next_ts > now || self.running_job_ids.len() == *SCHEDULED_JOB_EXECUTION_PARALLELISM {
-                return Ok(Some(next_ts));
+        }
+
+        // If we can't execute the remaining jobs return the earliest target
+        // timestamp. If we're caught up, we can sleep until the timestamp. If
+        // we're behind and at a concurrency limit, we can use the timestamp to
+        // log how far behind we get.
+        self.next_queue_ready_times.clear();
+        let mut next_job_ready_time: Option<Timestamp> = None;
+        for cursor in &cursors {
+            let Some(next_ts) = cursor.head_next_ts() else {
+                continue;
+            };
+            next_job_ready_time = Some(next_job_ready_time.map_or(next_ts, |t| t.min(next_ts)));
+            if let (namespace, Some(name)) = &cursor.queue {
+                let ready_time = self
+                    .next_queue_ready_times
+                    .entry((ComponentId::from(*namespace), name.clone()))
+                    .or_insert(next_ts);
+                *ready_time = (*ready_time).min(next_ts);
             }
+        }
+        Ok(next_job_ready_time)
+    }
 
-            let context = self.context.clone();
-            let tx = self.job_finished_tx.clone();
+    fn spawn_job(&mut self, job: ScheduledJob, job_id: ResolvedDocumentId) {
+        let context = self.context.clone();
+        let tx = self.job_finished_tx.clone();
 
-            let root = get_sampled_span(
-                &self.instance_name,
-                "scheduler/execute_job",
-                &mut self.context.rt.rng(),
-                BTreeMap::new(),
-            );
-            let sentry_hub = sentry::Hub::with(|hub| sentry::Hub::new_from_top(hub));
-            self.context.rt.spawn_background(
-                "spawn_scheduled_job",
-                async move {
-                    select_biased! {
-                        _ = tx.closed().fuse() => {
-                            tracing::error!("Scheduled job receiver closed");
-                        },
-                        _ = context.execute_job(job, job_id).fuse() => {
-                            let _ = tx.send(job_id).await;
-                        },
-                    }
+        let root = get_sampled_span(
+            &self.instance_name,
+            "scheduler/execute_job",
+            &mut self.context.rt.rng(),
+            BTreeMap::new(),
+        );
+        let sentry_hub = sentry::Hub::with(|hub| sentry::Hub::new_from_top(hub));
+        self.context.rt.spawn_background(
+            "spawn_scheduled_job",
+            async move {
+                select_biased! {
+                    _ = tx.closed().fuse() => {
+                        tracing::error!("Scheduled job receiver closed");
+                    },
+                    _ = context.execute_job(job, job_id).fuse() => {
+                        let _ = tx.send(job_id).await;
+                    },
                 }
-                .in_span(root)
-                .bind_hub(sentry_hub),
-            );
+            }
+            .in_span(root)
+            .bind_hub(sentry_hub),
+        );
+    }
+}
 
-            self.running_job_ids.insert(job_id);
+/// Reads the pending jobs of one queue in `next_ts` order.
+struct QueueCursor<RT: Runtime> {
+    queue: QueueKey,
+    max_concurrency: usize,
+    priority: u32,
+    head: Option<ParsedDocument<ScheduledJob>>,
+    query: Query,
+    /// Opened on the first `advance`, so queues whose head never starts only
+    /// cost the read that found them.
+    stream: Option<ResolvedQuery<RT>>,
+}
 
-            // We might have hit the concurrency limit by adding the new job, so
-            // we could check and break immediately if we have.
-            // However we want to know the time the next job in the
-            // queue (if any) is due, so instead we continue the loop one more
-            // time.
+impl<RT: Runtime> QueueCursor<RT> {
+    /// A cursor over the default queue, which is only bounded by the
+    /// scheduler's parallelism.
+    async fn default_queue(
+        tx: &mut Transaction<RT>,
+        namespace: TableNamespace,
+        query: Query,
+    ) -> anyhow::Result<Self> {
+        let mut cursor = Self {
+            queue: (namespace, None),
+            max_concurrency: usize::MAX,
+            priority: 0,
+            head: None,
+            query,
+            stream: None,
+        };
+        cursor.advance(tx).await?;
+        Ok(cursor)
+    }
+
+    /// A cursor over a named queue whose first pending job is `head`.
+    fn named_queue(
+        namespace: TableNamespace,
+        settings: Option<ScheduledJobQueue>,
+        head: ParsedDocument<ScheduledJob>,
+        query: Query,
+    ) -> anyhow::Result<Self> {
+        let name = head
+            .queue
+            .clone()
+            .context("Scheduled job with a pending queue is missing its queue")?;
+        let (max_concurrency, priority) = match settings {
+            Some(settings) => (settings.max_concurrency as usize, settings.priority),
+            None => (usize::MAX, 0),
+        };
+        Ok(Self {
+            queue: (namespace, Some(name)),
+            max_concurrency,
+            priority,
+            head: Some(head),
+            query,
+            stream: None,
+        })
+    }
+
+    async fn advance(&mut self, tx: &mut Transaction<RT>) -> anyhow::Result<()> {
+        let stream = match self.stream.take() {
+            Some(stream) => stream,
+            None => {
+                let mut stream = ResolvedQuery::new(tx, self.queue.0, self.query.clone())?;
+                // A head found while listing queues is also the first job this
+                // query returns.
+                if self.head.is_some() {
+                    stream.next(tx, None).await?;
+                }
+                stream
+            },
+        };
+        let stream = self.stream.insert(stream);
+        self.head = match stream.next(tx, None).await? {
+            Some(doc) => Some(doc.parse()?),
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Jobs keep their `next_ts` while they run, so step past the ones we've
+    /// already started.
+    async fn skip_running(
+        &mut self,
+        tx: &mut Transaction<RT>,
+        running_jobs: &HashMap<ResolvedDocumentId, QueueKey>,
+    ) -> anyhow::Result<()> {
+        while let Some(head) = &self.head
+            && running_jobs.contains_key(&head.id())
+        {
+            self.advance(tx).await?;
         }
-        Ok(None)
+        Ok(())
+    }
+
+    fn head_next_ts(&self) -> Option<Timestamp> {
+        self.head.as_ref()?.next_ts
     }
 }
 
 impl<RT: Runtime> ScheduledJobContext<RT> {
-    #[try_stream(boxed, ok = ParsedDocument<ScheduledJob>, error = anyhow::Error)]
-    async fn stream_jobs_to_run<'a>(&'a self, tx: &'a mut Transaction<RT>) {
+    /// Opens a cursor over the pending jobs of each queue in each component.
+    /// Named queues without pending jobs aren't read at all.
+    async fn open_queue_cursors(
+        &self,
+        tx: &mut Transaction<RT>,
+    ) -> anyhow::Result<Vec<QueueCursor<RT>>> {
         let namespaces: Vec<_> = tx
             .table_mapping()
             .iter()
             .filter(|(_, _, _, name)| **name == *SCHEDULED_JOBS_TABLE)
             .map(|(_, namespace, ..)| namespace)
             .collect();
-        let index_query = Query::index_range(IndexRange {
-            index_name: SCHEDULED_JOBS_INDEX.name(),
-            range: vec![IndexRangeExpression::Gt(
-                NEXT_TS_FIELD.clone(),
-                value::ConvexValue::Null.into(),
-            )],
-            order: Order::Asc,
-        });
-        // Key is (next_ts, namespace), where next_ts is for sorting and namespace
-        // is for deduping.
-        // Value is (job, query) where job is the job to run and query will get
-        // the next job to run in that namespace.
-        let mut queries = BTreeMap::new();
+        let mut cursors = vec![];
         for namespace in namespaces {
-            let mut query = ResolvedQuery::new(tx, namespace, index_query.clone())?;
-            if let Some(doc) = query.next(tx, None).await? {
-                let job: ParsedDocument<ScheduledJob> = doc.parse()?;
-                let next_ts = job.next_ts.ok_or_else(|| {
-                    anyhow::anyhow!("Could not get next_ts to run scheduled job {}", job.id())
-                })?;
-                queries.insert((next_ts, namespace), (job, query));
+            // Until the by-queue index has backfilled, run all of the
+            // component's jobs from the default queue.
+            if IndexModel::new(tx)
+                .enabled_index_metadata(namespace, &SCHEDULED_JOBS_INDEX_BY_QUEUE.name())?
+                .is_none()
+            {
+                let query = Query::index_range(IndexRange {
+                    index_name: SCHEDULED_JOBS_INDEX.name(),
+                    range: vec![IndexRangeExpression::Gt(
+                        NEXT_TS_FIELD.clone(),
+                        ConvexValue::Null.into(),
+                    )],
+                    order: Order::Asc,
+                });
+                cursors.push(QueueCursor::default_queue(tx, namespace, query).await?);
+                continue;
             }
-        }
-        while let Some(((_min_next_ts, namespace), (min_job, mut query))) = queries.pop_first() {
-            yield min_job;
-            if let Some(doc) = query.next(tx, None).await? {
-                let job: ParsedDocument<ScheduledJob> = doc.parse()?;
-                let next_ts = job.next_ts.ok_or_else(|| {
-                    anyhow::anyhow!("Could not get next_ts to run scheduled job {}", job.id())
-                })?;
-                queries.insert((next_ts, namespace), (job, query));
+            let query = Self::queue_query(maybe_val!(undefined));
+            cursors.push(QueueCursor::default_queue(tx, namespace, query).await?);
+            // Skip through the named queues with pending jobs one at a time.
+            // The first job of each is the head of its queue. Named queues
+            // sort after jobs without one.
+            let mut after = ConvexValue::Null;
+            loop {
+                let query = Query::index_range(IndexRange {
+                    index_name: SCHEDULED_JOBS_INDEX_BY_QUEUE.name(),
+                    range: vec![IndexRangeExpression::Gt(
+                        PENDING_QUEUE_FIELD.clone(),
+                        after.into(),
+                    )],
+                    order: Order::Asc,
+                });
+                let mut query_stream = ResolvedQuery::new(tx, namespace, query)?;
+                let Some(doc) = query_stream.next(tx, Some(1)).await? else {
+                    break;
+                };
+                let head: ParsedDocument<ScheduledJob> = doc.parse()?;
+                let name = head
+                    .queue
+                    .clone()
+                    .context("Scheduled job with a pending queue is missing its queue")?;
+                let settings = SchedulerModel::new(tx, namespace)
+                    .get_queue(&name)
+                    .await?
+                    .map(|doc| doc.into_value());
+                after = ConvexValue::try_from(name.clone())?;
+                let query = Self::queue_query(after.clone().into());
+                cursors.push(QueueCursor::named_queue(namespace, settings, head, query)?);
             }
         }
+        Ok(cursors)
+    }
+
+    /// The pending jobs of one queue, where `undefined` is the default queue.
+    fn queue_query(queue: MaybeValue) -> Query {
+        Query::index_range(IndexRange {
+            index_name: SCHEDULED_JOBS_INDEX_BY_QUEUE.name(),
+            range: vec![
+                IndexRangeExpression::Eq(PENDING_QUEUE_FIELD.clone(), queue),
+                IndexRangeExpression::Gt(NEXT_TS_FIELD.clone(), ConvexValue::Null.into()),
+            ],
+            order: Order::Asc,
+        })
     }
 
     // This handles re-running the scheduled function on transient errors. It
@@ -710,9 +954,9 @@ impl<RT: Runtime> ScheduledJobContext<RT> {
         let stats = tx.take_stats();
         let execution_time = start.elapsed();
 
-        if outcome.result.is_ok() {
+        if let Ok(result) = &outcome.result {
             SchedulerModel::new(&mut tx, namespace)
-                .complete(job_id, ScheduledJobState::Success)
+                .complete_with_result(job_id, ScheduledJobState::Success, Some(result.unpack()))
                 .await?;
             if let Fault::Error(e) = pause_client.wait(SCHEDULED_JOB_COMMITTING).await {
                 tracing::info!("Injected error before committing mutation");
@@ -742,12 +986,14 @@ impl<RT: Runtime> ScheduledJobContext<RT> {
                 // Continue without updating since the job state has changed
                 return Ok(());
             }
-            SchedulerModel::new(&mut tx, namespace)
-                .complete(
-                    job_id,
-                    ScheduledJobState::Failed(outcome.result.clone().unwrap_err().to_string()),
-                )
-                .await?;
+            self.fail_or_retry(
+                &mut tx,
+                job_id,
+                job.clone(),
+                ScheduledJobRetryClass::DeveloperError,
+                outcome.result.clone().unwrap_err().to_string(),
+            )
+            .await?;
             // NOTE: We should not be getting developer errors here.
             self.database
                 .commit_with_write_source(tx, "scheduled_job_mutation_error")
@@ -813,9 +1059,9 @@ impl<RT: Runtime> ScheduledJobContext<RT> {
                         context.clone(),
                     )
                     .await?;
-                let state = match &completion.outcome.result {
-                    Ok(_) => ScheduledJobState::Success,
-                    Err(e) => ScheduledJobState::Failed(e.to_string()),
+                let (state, result) = match &completion.outcome.result {
+                    Ok(value) => (ScheduledJobState::Success, Some(value.unpack())),
+                    Err(e) => (ScheduledJobState::Failed(e.to_string()), None),
                 };
 
                 // Mark the job as completed. Keep trying until we succeed (or
@@ -824,7 +1070,13 @@ impl<RT: Runtime> ScheduledJobContext<RT> {
                 let mut backoff =
                     Backoff::new(*SCHEDULED_JOB_INITIAL_BACKOFF, *SCHEDULED_JOB_MAX_BACKOFF);
                 while let Err(mut err) = self
-                    .complete_action(job_id, &updated_job, usage_tracker.clone(), state.clone())
+                    .complete_action(
+                        job_id,
+                        &updated_job,
+                        usage_tracker.clone(),
+                        state.clone(),
+                        result.clone(),
+                    )
                     .await
                 {
                     let delay = backoff.fail(&mut self.rt.rng());
@@ -843,11 +1095,17 @@ impl<RT: Runtime> ScheduledJobContext<RT> {
                 // This case can happen if there is a system error while executing
                 // the action or if backend exits after executing the action but
                 // before updating the state. Since we execute actions at most once,
-                // complete this job and log the error.
+                // complete this job and log the error, unless its retry policy
+                // opted into rerunning interrupted actions.
                 let message = "Transient error while executing action".to_string();
-                SchedulerModel::new(&mut tx, namespace)
-                    .complete(job_id, ScheduledJobState::Failed(message.clone()))
-                    .await?;
+                self.fail_or_retry(
+                    &mut tx,
+                    job_id,
+                    job.clone(),
+                    ScheduledJobRetryClass::Interrupted,
+                    message.clone(),
+                )
+                .await?;
                 self.database
                     .commit_with_write_source(tx, "scheduled_job_action_error")
                     .await?;
@@ -907,13 +1165,15 @@ impl<RT: Runtime> ScheduledJobContext<RT> {
     }
 
     // Completes an action in separate transaction. Returns false if the action
-    // state has changed.
+    // state has changed. The action's return value is passed along so a
+    // workflow waiting on it can pick it up.
     async fn complete_action(
         &self,
         job_id: ResolvedDocumentId,
         expected_state: &ScheduledJob,
         usage_tracking: FunctionUsageTracker,
         job_state: ScheduledJobState,
+        result: Option<ConvexValue>,
     ) -> anyhow::Result<()> {
         let (success, mut tx) = self
             .new_transaction_for_job_state(job_id, expected_state, usage_tracking)
@@ -922,17 +1182,60 @@ impl<RT: Runtime> ScheduledJobContext<RT> {
             // Continue without updating since the job state has changed
             return Ok(());
         }
-        let namespace = tx.table_mapping().tablet_namespace(job_id.tablet_id)?;
-
-        // Remove from the scheduled jobs table
-        SchedulerModel::new(&mut tx, namespace)
-            .complete(job_id, job_state)
-            .await?;
+        match job_state {
+            ScheduledJobState::Failed(message) => {
+                self.fail_or_retry(
+                    &mut tx,
+                    job_id,
+                    expected_state.clone(),
+                    ScheduledJobRetryClass::DeveloperError,
+                    message,
+                )
+                .await?;
+            },
+            job_state => {
+                let namespace = tx.table_mapping().tablet_namespace(job_id.tablet_id)?;
+                // Remove from the scheduled jobs table
+                SchedulerModel::new(&mut tx, namespace)
+                    .complete_with_result(job_id, job_state, result)
+                    .await?;
+            },
+        }
         self.database
             .commit_with_write_source(tx, "scheduled_job_complete_action")
             .await?;
         Ok(())
     }
+
+    // Marks the job as failed, or reschedules it if its retry policy covers
+    // this kind of failure and it has attempts left.
+    async fn fail_or_retry(
+        &self,
+        tx: &mut Transaction<RT>,
+        job_id: ResolvedDocumentId,
+        mut job: ScheduledJob,
+        class: ScheduledJobRetryClass,
+        message: String,
+    ) -> anyhow::Result<()> {
+        let namespace = tx.table_mapping().tablet_namespace(job_id.tablet_id)?;
+        let Some(retry_policy) = job
+            .retry_policy
+            .as_ref()
+            .filter(|policy| policy.should_retry(class, job.attempts.retries))
+        else {
+            return SchedulerModel::new(tx, namespace)
+                .complete(job_id, ScheduledJobState::Failed(message))
+                .await;
+        };
+        let delay = retry_policy.retry_delay(job.attempts.retries, &mut self.rt.rng());
+        tracing::info!("Scheduled job {job_id} failed with {class:?}, retrying in {delay:?}");
+        job.state = ScheduledJobState::Pending;
+        job.attempts.retries += 1;
+        job.next_ts = Some(self.rt.generate_timestamp()?.add(delay)?);
+        SchedulerModel::new(tx, namespace)
+            .replace(job_id, job)
+            .await
+    }
 }
 
 pub struct ScheduledJobGarbageCollector<RT: Runtime> {
@@ -299,6 +299,7 @@ mod tests {
             staged_text_indexes: btreemap! {},
             vector_indexes: btreemap! {},
             staged_vector_indexes: btreemap! {},
+            sparse_vector_indexes: btreemap! {},
             document_type: Some(DocumentSchema::Any),
         };
         let db_schema = DatabaseSchema {
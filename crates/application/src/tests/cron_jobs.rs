@@ -24,6 +24,7 @@ use database::{
     TableModel,
     Transaction,
 };
+use errors::ErrorMetadataAnyhowExt;
 use keybroker::Identity;
 use model::{
     backend_state::{
@@ -32,6 +33,7 @@ use model::{
     },
     cron_jobs::{
         types::{
+            CronCatchUpPolicy,
             CronIdentifier,
             CronJob,
             CronSchedule,
@@ -40,11 +42,13 @@ use model::{
         CronModel,
         CRON_JOB_LOGS_INDEX_BY_NAME_TS,
         CRON_JOB_LOGS_NAME_FIELD,
+        PAUSED_NEXT_TS,
     },
 };
 use runtime::testing::TestRuntime;
 use serde_json::Value as JsonValue;
 use udf::helpers::parse_udf_args;
+use value::ConvexValue;
 
 use crate::{
     test_helpers::{
@@ -61,6 +65,13 @@ fn test_cron_identifier() -> CronIdentifier {
 
 async fn create_cron_job(
     tx: &mut Transaction<TestRuntime>,
+) -> anyhow::Result<(BTreeMap<CronIdentifier, CronJob>, CronModel<TestRuntime>)> {
+    create_cron_job_with_catch_up(tx, CronCatchUpPolicy::RunOnce).await
+}
+
+async fn create_cron_job_with_catch_up(
+    tx: &mut Transaction<TestRuntime>,
+    catch_up: CronCatchUpPolicy,
 ) -> anyhow::Result<(BTreeMap<CronIdentifier, CronJob>, CronModel<TestRuntime>)> {
     let mut cron_model = CronModel::new(tx, ComponentId::test_user());
     let mut map = serde_json::Map::new();
@@ -76,6 +87,8 @@ async fn create_cron_job(
         udf_path: path.udf_path.clone(),
         udf_args: parse_udf_args(&path.udf_path, vec![JsonValue::Object(map)])?,
         cron_schedule: CronSchedule::Interval { seconds: 60 },
+        catch_up,
+        jitter_seconds: None,
     };
     let original_jobs = cron_model.list().await?;
     let name = test_cron_identifier();
@@ -247,3 +260,137 @@ async fn test_cron_jobs_helper(rt: TestRuntime, backend_state: BackendState) ->
 
     Ok(())
 }
+
+#[convex_macro::test_runtime]
+async fn test_pause_and_resume_cron_job(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    application.load_udf_tests_modules().await?;
+    // udf-tests include crons, so we let them execute so that we can then add
+    // a new cron without hitting an OCC.
+    rt.wait(Duration::from_secs(100)).await;
+
+    let mut tx = application.begin(Identity::system()).await?;
+    let (_, mut cron_model) = create_cron_job(&mut tx).await?;
+    cron_model.set_paused(&test_cron_identifier(), true).await?;
+    let err = cron_model
+        .run_now(&test_cron_identifier())
+        .await
+        .unwrap_err();
+    assert_eq!(err.short_msg(), "CronJobPaused");
+    application.commit_test(tx).await?;
+
+    // The paused job shouldn't execute.
+    rt.wait(Duration::from_secs(100)).await;
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut table_model = TableModel::new(&mut tx);
+    assert!(
+        table_model
+            .table_is_empty(OBJECTS_TABLE_COMPONENT.into(), &OBJECTS_TABLE)
+            .await?
+    );
+    let mut cron_model = CronModel::new(&mut tx, ComponentId::test_user());
+    let job = cron_model
+        .get_by_name(&test_cron_identifier())
+        .await?
+        .unwrap();
+    assert!(job.paused);
+    // The paused job is parked after every runnable cron.
+    let next_run = cron_model
+        .next_run(job.id().developer_id)
+        .await?
+        .unwrap()
+        .into_value();
+    assert_eq!(next_run.next_ts, PAUSED_NEXT_TS);
+
+    // Resuming the job should make it execute.
+    cron_model
+        .set_paused(&test_cron_identifier(), false)
+        .await?;
+    let next_run = cron_model
+        .next_run(job.id().developer_id)
+        .await?
+        .unwrap()
+        .into_value();
+    let now = rt.generate_timestamp()?;
+    assert!(next_run.next_ts >= now);
+    assert!(next_run.next_ts <= now.add(Duration::from_secs(60))?);
+    application.commit_test(tx).await?;
+    rt.wait(Duration::from_secs(100)).await;
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut table_model = TableModel::new(&mut tx);
+    assert!(
+        !table_model
+            .table_is_empty(OBJECTS_TABLE_COMPONENT.into(), &OBJECTS_TABLE)
+            .await?
+    );
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_cron_job_catch_up_run_all(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    application.load_udf_tests_modules().await?;
+    // udf-tests include crons, so we let them execute so that we can then add
+    // a new cron without hitting an OCC.
+    rt.wait(Duration::from_secs(100)).await;
+
+    let mut tx = application.begin(Identity::system()).await?;
+    let (_, mut cron_model) =
+        create_cron_job_with_catch_up(&mut tx, CronCatchUpPolicy::RunAll { max: 3 }).await?;
+    // Pretend the job has been stuck for ten minutes, so it has missed at least
+    // ten of its minutely runs.
+    let job = cron_model
+        .list()
+        .await?
+        .remove(&test_cron_identifier())
+        .unwrap();
+    let mut next_run = job.cron_next_run();
+    next_run.next_ts = rt.generate_timestamp()?.sub(Duration::from_secs(10 * 60))?;
+    cron_model.update_job_state(next_run).await?;
+    application.commit_test(tx).await?;
+    rt.wait(Duration::from_secs(30)).await;
+
+    // The oldest missed runs are skipped and the three most recent run.
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut logs_query = cron_log_query(&mut tx, ComponentId::test_user())?;
+    let mut num_canceled = 0;
+    let mut num_succeeded = 0;
+    let mut num_caught_up = 0;
+    while let Some(doc) = logs_query.next(&mut tx, None).await? {
+        let Some(ConvexValue::Object(status)) = doc.value().get("status") else {
+            anyhow::bail!("Cron job log is missing its status");
+        };
+        match status.get("type") {
+            Some(t) if *t == ConvexValue::try_from("canceled")? => {
+                let Some(ConvexValue::Int64(n)) = status.get("num_canceled") else {
+                    anyhow::bail!("Canceled cron job log is missing num_canceled");
+                };
+                num_canceled += n;
+            },
+            Some(t) if *t == ConvexValue::try_from("success")? => {
+                num_succeeded += 1;
+                let Some(ConvexValue::Object(log_lines)) = doc.value().get("logLines") else {
+                    anyhow::bail!("Cron job log is missing its log lines");
+                };
+                let Some(ConvexValue::Array(log_lines)) = log_lines.get("logLines") else {
+                    anyhow::bail!("Cron job log lines are missing");
+                };
+                let is_catch_up = |line: &ConvexValue| match line {
+                    ConvexValue::String(s) => s.contains("Catching up on missed runs"),
+                    _ => false,
+                };
+                if log_lines.iter().any(is_catch_up) {
+                    num_caught_up += 1;
+                }
+            },
+            t => anyhow::bail!("Unexpected cron job status {t:?}"),
+        }
+    }
+    // Ten minutes at one run a minute is eleven runs, counting both ends.
+    assert_eq!(num_canceled, 8);
+    assert_eq!(num_succeeded, 3);
+    // Every run but the last is still behind schedule, and logs that it's
+    // catching up.
+    assert_eq!(num_caught_up, 2);
+    Ok(())
+}
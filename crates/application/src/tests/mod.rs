@@ -13,12 +13,15 @@ mod mutation;
 mod occ_retries;
 mod push;
 mod query_cache;
+mod rate_limits;
 mod returns_validation;
 mod scheduled_jobs;
 mod schema;
 mod source_package;
 mod storage;
 mod streaming_export;
+mod work_queues;
+mod workflows;
 
 const NODE_SOURCE: &str = r#"
 var nodeFunction = () => {};
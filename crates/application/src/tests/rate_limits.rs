@@ -0,0 +1,174 @@
+use errors::ErrorMetadataAnyhowExt;
+use keybroker::Identity;
+use model::rate_limits::{
+    types::{
+        RateLimitConfig,
+        RateLimitRequest,
+        RateLimitResult,
+    },
+    RateLimitModel,
+};
+use runtime::testing::TestRuntime;
+use value::TableNamespace;
+
+use crate::Application;
+
+fn request(config: RateLimitConfig, count: f64, consume: bool) -> RateLimitRequest {
+    RateLimitRequest {
+        name: "sendMessage".to_string(),
+        key: Some("user1".to_string()),
+        config,
+        count,
+        consume,
+    }
+}
+
+// Refills so slowly that the tests can ignore it.
+fn token_bucket(capacity: f64, shards: u32) -> RateLimitConfig {
+    RateLimitConfig::TokenBucket {
+        rate: 1.0,
+        period_ms: 3_600_000,
+        capacity,
+        shards,
+    }
+}
+
+async fn limit(
+    application: &Application<TestRuntime>,
+    request: RateLimitRequest,
+) -> anyhow::Result<RateLimitResult> {
+    let mut tx = application.begin(Identity::system()).await?;
+    let result = RateLimitModel::new(&mut tx, TableNamespace::test_user())
+        .limit(request)
+        .await?;
+    application.commit_test(tx).await?;
+    Ok(result)
+}
+
+async fn reset(application: &Application<TestRuntime>) -> anyhow::Result<()> {
+    let mut tx = application.begin(Identity::system()).await?;
+    RateLimitModel::new(&mut tx, TableNamespace::test_user())
+        .reset("sendMessage", Some("user1"))
+        .await?;
+    application.commit_test(tx).await?;
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_rate_limit_consumes_until_exhausted(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    let config = RateLimitConfig::FixedWindow {
+        rate: 2.0,
+        period_ms: 60_000,
+        shards: 1,
+    };
+
+    // Checking doesn't consume anything.
+    for _ in 0..3 {
+        assert!(limit(&application, request(config, 1.0, false)).await?.ok);
+    }
+    assert!(limit(&application, request(config, 1.0, true)).await?.ok);
+    assert!(limit(&application, request(config, 1.0, true)).await?.ok);
+    let result = limit(&application, request(config, 1.0, true)).await?;
+    assert!(!result.ok);
+    assert!(result.retry_after.unwrap() <= 60_000.0);
+
+    // Other keys have their own limit.
+    let mut other_key = request(config, 1.0, true);
+    other_key.key = Some("user2".to_string());
+    assert!(limit(&application, other_key).await?.ok);
+
+    reset(&application).await?;
+    assert!(limit(&application, request(config, 1.0, true)).await?.ok);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_rate_limit_within_transaction(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    let config = token_bucket(2.0, 1);
+    assert!(limit(&application, request(config, 1.0, true)).await?.ok);
+
+    // Consumption merged earlier in a transaction counts towards later calls.
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut model = RateLimitModel::new(&mut tx, TableNamespace::test_user());
+    assert!(model.limit(request(config, 1.0, true)).await?.ok);
+    assert!(!model.limit(request(config, 1.0, true)).await?.ok);
+    application.commit_test(tx).await?;
+    assert!(!limit(&application, request(config, 1.0, false)).await?.ok);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_sharded_rate_limit_allows_whole_capacity(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    let config = token_bucket(16.0, 16);
+    // Calls are only denied once every shard is exhausted.
+    for _ in 0..16 {
+        assert!(limit(&application, request(config, 1.0, true)).await?.ok);
+    }
+    let result = limit(&application, request(config, 1.0, true)).await?;
+    assert!(!result.ok);
+    assert!(result.retry_after.is_some());
+
+    // A single call can draw from several shards at once.
+    reset(&application).await?;
+    assert!(limit(&application, request(config, 5.0, true)).await?.ok);
+    assert!(limit(&application, request(config, 11.0, true)).await?.ok);
+    assert!(!limit(&application, request(config, 1.0, true)).await?.ok);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_concurrent_rate_limits_do_not_conflict(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    // A single shard, so every consumer updates the same document.
+    let config = token_bucket(4.0, 1);
+    assert!(limit(&application, request(config, 1.0, true)).await?.ok);
+
+    let mut txs = vec![];
+    for _ in 0..2 {
+        let mut tx = application.begin(Identity::system()).await?;
+        assert!(
+            RateLimitModel::new(&mut tx, TableNamespace::test_user())
+                .limit(request(config, 1.0, true))
+                .await?
+                .ok
+        );
+        let writes = tx.writes().as_flat()?;
+        assert_eq!(writes.merges().len(), 1);
+        assert_eq!(writes.coalesced_writes().count(), 0);
+        txs.push(tx);
+    }
+    // Both commit without an OCC, and both of their consumption is kept.
+    for tx in txs {
+        application.commit_test(tx).await?;
+    }
+    assert!(!limit(&application, request(config, 2.0, false)).await?.ok);
+    assert!(limit(&application, request(config, 1.0, false)).await?.ok);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_concurrent_rate_limits_do_not_overconsume(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    let config = token_bucket(2.0, 1);
+    assert!(limit(&application, request(config, 1.0, true)).await?.ok);
+
+    let mut tx1 = application.begin(Identity::system()).await?;
+    let mut tx2 = application.begin(Identity::system()).await?;
+    for tx in [&mut tx1, &mut tx2] {
+        assert!(
+            RateLimitModel::new(tx, TableNamespace::test_user())
+                .limit(request(config, 1.0, true))
+                .await?
+                .ok
+        );
+    }
+    // Only one of them fits once the other has committed, so the other is
+    // retried.
+    application.commit_test(tx1).await?;
+    let err = application.commit_test(tx2).await.unwrap_err();
+    assert!(err.is_occ());
+    Ok(())
+}
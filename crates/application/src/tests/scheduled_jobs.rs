@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeSet,
     str::FromStr,
     time::Duration,
 };
@@ -32,7 +33,12 @@ use model::{
         BackendStateModel,
     },
     scheduled_jobs::{
-        types::ScheduledJobState,
+        types::{
+            ScheduledJobQueue,
+            ScheduledJobRetryClass,
+            ScheduledJobRetryPolicy,
+            ScheduledJobState,
+        },
         SchedulerModel,
     },
 };
@@ -84,6 +90,8 @@ async fn create_scheduled_job<'a>(
             parse_udf_args(&path.udf_path, vec![JsonValue::Object(map)])?,
             rt.unix_timestamp(),
             ExecutionContext::new_for_test(),
+            None,
+            None,
         )
         .await?;
     let state = model.check_status(job_id).await?.unwrap();
@@ -398,3 +406,145 @@ async fn test_scheduled_job_retry(
     assert_eq!(state, ScheduledJobState::Success);
     Ok(())
 }
+
+#[convex_macro::test_runtime]
+async fn test_scheduled_job_retry_policy(
+    rt: TestRuntime,
+    pause_controller: PauseController,
+) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    application.load_udf_tests_modules().await?;
+
+    let first_execute = pause_controller.hold(SCHEDULED_JOB_EXECUTED);
+
+    let path = CanonicalizedComponentFunctionPath {
+        component: ComponentPath::test_user(),
+        udf_path: CanonicalizedUdfPath::from_str("custom_errors:mutationThrows")?,
+    };
+    let retry_policy = ScheduledJobRetryPolicy {
+        max_attempts: 2,
+        base_delay_ms: 0,
+        jitter: 0.0,
+        retry_on: BTreeSet::from([ScheduledJobRetryClass::DeveloperError]),
+    };
+    let mut tx = application.begin(Identity::system()).await?;
+    let job_id = SchedulerModel::new(&mut tx, TableNamespace::test_user())
+        .schedule(
+            path.clone(),
+            parse_udf_args(&path.udf_path, vec![])?,
+            rt.unix_timestamp(),
+            ExecutionContext::new_for_test(),
+            Some(retry_policy),
+            None,
+        )
+        .await?;
+    application.commit_test(tx).await?;
+
+    // The first attempt throws, so the job goes back to pending.
+    let pause_guard = first_execute.wait_for_blocked().await.unwrap();
+    let second_execute = pause_controller.hold(SCHEDULED_JOB_EXECUTED);
+    let mut tx = application.begin(Identity::system()).await?;
+    let job = SchedulerModel::new(&mut tx, TableNamespace::test_user())
+        .list()
+        .await?
+        .into_iter()
+        .find(|job| job.id() == job_id)
+        .unwrap();
+    assert_eq!(job.state, ScheduledJobState::Pending);
+    assert_eq!(job.attempts.retries, 1);
+    pause_guard.unpause();
+
+    // The second attempt throws too, which exhausts the policy.
+    wait_for_scheduled_job_execution(second_execute).await;
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut model = SchedulerModel::new(&mut tx, TableNamespace::test_user());
+    let state = model.check_status(job_id).await?.unwrap();
+    assert!(matches!(state, ScheduledJobState::Failed(_)));
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_scheduled_job_queue_concurrency(
+    rt: TestRuntime,
+    pause_controller: PauseController,
+) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    application.load_udf_tests_modules().await?;
+
+    let hold_guard = pause_controller.hold(SCHEDULED_JOB_EXECUTED);
+
+    let path = insert_object_path();
+    let queue = ScheduledJobQueue {
+        name: "serial".to_string(),
+        max_concurrency: 1,
+        priority: 0,
+    };
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut model = SchedulerModel::new(&mut tx, TableNamespace::test_user());
+    let mut job_ids = vec![];
+    for _ in 0..2 {
+        let job_id = model
+            .schedule(
+                path.clone(),
+                parse_udf_args(&path.udf_path, vec![JsonValue::Object(Default::default())])?,
+                rt.unix_timestamp(),
+                ExecutionContext::new_for_test(),
+                None,
+                Some(queue.clone()),
+            )
+            .await?;
+        job_ids.push(job_id);
+    }
+    application.commit_test(tx).await?;
+
+    // The executor is paused after the first job finishes, and the queue only
+    // allows one job at a time, so the second job can't have started yet.
+    let pause_guard = hold_guard.wait_for_blocked().await.unwrap();
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut model = SchedulerModel::new(&mut tx, TableNamespace::test_user());
+    let mut states = vec![];
+    for job_id in &job_ids {
+        states.push(model.check_status(*job_id).await?.unwrap());
+    }
+    states.sort_by_key(|state| state == &ScheduledJobState::Pending);
+    assert_eq!(
+        states,
+        vec![ScheduledJobState::Success, ScheduledJobState::Pending]
+    );
+    pause_guard.unpause();
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_scheduled_job_queue_settings_stored_once(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    application.load_udf_tests_modules().await?;
+
+    // Schedule far in the future so the jobs stay pending.
+    let path = insert_object_path();
+    let ts = rt.unix_timestamp() + Duration::from_secs(3600);
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut model = SchedulerModel::new(&mut tx, TableNamespace::test_user());
+    for max_concurrency in [1, 4] {
+        let queue = ScheduledJobQueue {
+            name: "emails".to_string(),
+            max_concurrency,
+            priority: 0,
+        };
+        model
+            .schedule(
+                path.clone(),
+                parse_udf_args(&path.udf_path, vec![JsonValue::Object(Default::default())])?,
+                ts,
+                ExecutionContext::new_for_test(),
+                None,
+                Some(queue),
+            )
+            .await?;
+    }
+    // The last job scheduled onto the queue sets its settings.
+    let queue = model.get_queue("emails").await?.unwrap();
+    assert_eq!(queue.max_concurrency, 4);
+    application.commit_test(tx).await?;
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use common::runtime::Runtime;
+use errors::ErrorMetadataAnyhowExt;
+use keybroker::Identity;
+use model::work_queues::WorkQueueModel;
+use runtime::testing::TestRuntime;
+use value::{
+    ConvexValue,
+    DeveloperDocumentId,
+    TableNamespace,
+};
+
+use crate::Application;
+
+const VISIBILITY_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[convex_macro::test_runtime]
+async fn test_dequeue_hides_leased_messages(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut model = WorkQueueModel::new(&mut tx, TableNamespace::test_user());
+    for i in 0..3 {
+        model
+            .enqueue(
+                "emails".to_string(),
+                ConvexValue::from(i as f64),
+                Duration::ZERO,
+                None,
+            )
+            .await?;
+    }
+    model
+        .enqueue(
+            "emails".to_string(),
+            ConvexValue::Null,
+            Duration::from_secs(60),
+            None,
+        )
+        .await?;
+
+    // The delayed message isn't visible yet.
+    let leases = model.dequeue("emails", 10, VISIBILITY_TIMEOUT).await?;
+    assert_eq!(leases.len(), 3);
+    assert!(leases.iter().all(|lease| lease.attempt == 1));
+    assert!(model
+        .dequeue("emails", 10, VISIBILITY_TIMEOUT)
+        .await?
+        .is_empty());
+
+    let id = DeveloperDocumentId::decode(&leases[0].id)?;
+    let err = model.ack(id, 2).await.unwrap_err();
+    assert_eq!(err.short_msg(), "WorkQueueLeaseLost");
+    model.ack(id, 1).await?;
+    let err = model.ack(id, 1).await.unwrap_err();
+    assert_eq!(err.short_msg(), "WorkQueueMessageNotFound");
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_expired_lease_is_dequeued_again(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut model = WorkQueueModel::new(&mut tx, TableNamespace::test_user());
+    model
+        .enqueue("jobs".to_string(), ConvexValue::Null, Duration::ZERO, None)
+        .await?;
+    let first = model.dequeue("jobs", 1, VISIBILITY_TIMEOUT).await?;
+    assert_eq!(first.len(), 1);
+
+    rt.wait(VISIBILITY_TIMEOUT * 2).await;
+    let second = model.dequeue("jobs", 1, VISIBILITY_TIMEOUT).await?;
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].id, first[0].id);
+    assert_eq!(second[0].attempt, 2);
+
+    // The first consumer's lease is gone.
+    let id = DeveloperDocumentId::decode(&first[0].id)?;
+    let err = model.ack(id, 1).await.unwrap_err();
+    assert_eq!(err.short_msg(), "WorkQueueLeaseLost");
+    model.ack(id, 2).await?;
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_dead_letter_and_redrive(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    let mut tx = application.begin(Identity::system()).await?;
+    let mut model = WorkQueueModel::new(&mut tx, TableNamespace::test_user());
+    let id = model
+        .enqueue(
+            "jobs".to_string(),
+            ConvexValue::Null,
+            Duration::ZERO,
+            Some(1),
+        )
+        .await?;
+    let leases = model.dequeue("jobs", 1, VISIBILITY_TIMEOUT).await?;
+    model
+        .nack(
+            id,
+            leases[0].attempt,
+            Duration::ZERO,
+            Some("boom".to_string()),
+        )
+        .await?;
+
+    assert!(model
+        .dequeue("jobs", 1, VISIBILITY_TIMEOUT)
+        .await?
+        .is_empty());
+    let dead_letters = model.dead_letters("jobs", 10).await?;
+    assert_eq!(dead_letters.len(), 1);
+    assert_eq!(dead_letters[0].last_error.as_deref(), Some("boom"));
+
+    model.redrive(id).await?;
+    assert!(model.dead_letters("jobs", 10).await?.is_empty());
+    let leases = model.dequeue("jobs", 1, VISIBILITY_TIMEOUT).await?;
+    assert_eq!(leases.len(), 1);
+    assert_eq!(leases[0].attempt, 1);
+    Ok(())
+}
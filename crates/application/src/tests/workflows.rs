@@ -0,0 +1,76 @@
+use std::{
+    str::FromStr,
+    time::Duration,
+};
+
+use common::{
+    components::{
+        CanonicalizedComponentFunctionPath,
+        ComponentPath,
+    },
+    runtime::Runtime,
+};
+use keybroker::Identity;
+use model::workflows::WorkflowModel;
+use runtime::testing::TestRuntime;
+use serde_json::json;
+use sync_types::CanonicalizedUdfPath;
+use value::{
+    assert_obj,
+    TableNamespace,
+};
+
+use crate::{
+    test_helpers::ApplicationTestExt,
+    Application,
+};
+
+#[convex_macro::test_runtime]
+async fn test_workflow_runs_through_scheduler(rt: TestRuntime) -> anyhow::Result<()> {
+    let application = Application::new_for_tests(&rt).await?;
+    application.load_udf_tests_modules().await?;
+
+    let mut tx = application.begin(Identity::system()).await?;
+    let workflow_id = WorkflowModel::new(&mut tx, TableNamespace::test_user())
+        .start(
+            CanonicalizedComponentFunctionPath {
+                component: ComponentPath::test_user(),
+                udf_path: CanonicalizedUdfPath::from_str("workflows:doubleThenIncrement")?,
+            },
+            assert_obj!("x" => 3.0),
+        )
+        .await?;
+    application.commit_test(tx).await?;
+
+    // The handler is replayed once to start, once after the action, once when
+    // the sleep ends and once after the mutation.
+    let mut status = None;
+    for _ in 0..10 {
+        rt.wait(Duration::from_secs(1)).await;
+        let mut tx = application.begin(Identity::system()).await?;
+        let current = WorkflowModel::new(&mut tx, TableNamespace::test_user())
+            .status(workflow_id)
+            .await?;
+        if current.state != "running" {
+            status = Some(current);
+            break;
+        }
+    }
+    let status = status.expect("workflow didn't finish");
+    assert_eq!(status.state, "completed");
+    assert_eq!(status.result, Some(json!(7.0)));
+    let steps: Vec<_> = status
+        .steps
+        .iter()
+        .map(|step| (step.kind, step.state, step.result.clone()))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![
+            ("action", "completed", Some(json!(6.0))),
+            ("sleep", "completed", Some(json!(null))),
+            ("mutation", "completed", Some(json!(7.0))),
+        ]
+    );
+    Ok(())
+}
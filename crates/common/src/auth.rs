@@ -12,6 +12,8 @@ use serde::{
     Serialize,
 };
 
+use crate::types::EnvVarName;
+
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum AuthInfo {
     Oidc {
@@ -27,10 +29,13 @@ pub enum AuthInfo {
         application_id: Option<String>,
         /// The issuer of the JWT auth provider (e.g. `https://auth.example.com`)
         issuer: IssuerUrl,
-        /// The URL to fetch the JWKS (e.g. `https://auth.example.com/.well-known/jwks.json`)
-        jwks: String,
-        /// The algorithm used to sign the JWT tokens. Convex currently only
-        /// supports RS256 and ES256.
+        /// The URL to fetch the JWKS (e.g. `https://auth.example.com/.well-known/jwks.json`).
+        /// Set for every algorithm except HS256.
+        jwks: Option<String>,
+        /// The environment variable holding the shared secret for HS256
+        /// tokens. The secret itself is never stored in the auth config.
+        secret_env_var: Option<EnvVarName>,
+        /// The algorithm used to sign the JWT tokens.
         algorithm: SignatureAlgorithm,
     },
 }
@@ -84,15 +89,51 @@ impl AuthInfo {
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub enum SignatureAlgorithm {
     RS256,
+    RS384,
+    RS512,
     ES256,
+    ES384,
+    /// ECDSA with P-521. Verified with aws-lc since neither biscuit nor the
+    /// OIDC library supports P-521.
+    ES512,
+    PS256,
+    PS384,
+    PS512,
+    /// Ed25519 signatures. Verified with the OIDC library since biscuit
+    /// doesn't support EdDSA.
+    EdDSA,
+    /// HMAC with SHA-256, keyed by a secret stored in an environment variable.
+    HS256,
 }
 
-impl From<SignatureAlgorithm> for biscuit::jwa::SignatureAlgorithm {
-    fn from(algorithm: SignatureAlgorithm) -> Self {
-        match algorithm {
+const SUPPORTED_SIGNATURE_ALGORITHMS: &str =
+    "RS256, RS384, RS512, ES256, ES384, ES512, PS256, PS384, PS512, EdDSA and HS256";
+
+impl SignatureAlgorithm {
+    /// Symmetric algorithms are keyed by a shared secret instead of a JWKS.
+    pub fn uses_shared_secret(&self) -> bool {
+        matches!(self, SignatureAlgorithm::HS256)
+    }
+}
+
+impl TryFrom<SignatureAlgorithm> for biscuit::jwa::SignatureAlgorithm {
+    type Error = anyhow::Error;
+
+    fn try_from(algorithm: SignatureAlgorithm) -> Result<Self, Self::Error> {
+        let result = match algorithm {
             SignatureAlgorithm::RS256 => biscuit::jwa::SignatureAlgorithm::RS256,
+            SignatureAlgorithm::RS384 => biscuit::jwa::SignatureAlgorithm::RS384,
+            SignatureAlgorithm::RS512 => biscuit::jwa::SignatureAlgorithm::RS512,
             SignatureAlgorithm::ES256 => biscuit::jwa::SignatureAlgorithm::ES256,
-        }
+            SignatureAlgorithm::ES384 => biscuit::jwa::SignatureAlgorithm::ES384,
+            SignatureAlgorithm::PS256 => biscuit::jwa::SignatureAlgorithm::PS256,
+            SignatureAlgorithm::PS384 => biscuit::jwa::SignatureAlgorithm::PS384,
+            SignatureAlgorithm::PS512 => biscuit::jwa::SignatureAlgorithm::PS512,
+            SignatureAlgorithm::HS256 => biscuit::jwa::SignatureAlgorithm::HS256,
+            SignatureAlgorithm::ES512 => anyhow::bail!("biscuit does not support ES512"),
+            SignatureAlgorithm::EdDSA => anyhow::bail!("biscuit does not support EdDSA"),
+        };
+        Ok(result)
     }
 }
 
@@ -110,7 +151,10 @@ impl FromStr for SignatureAlgorithm {
         serde_json::from_str(s).with_context(|| {
             ErrorMetadata::bad_request(
                 "InvalidSignatureAlgorithm",
-                format!("Invalid signature algorithm (only RS256 and ES256 are supported): {s}"),
+                format!(
+                    "Invalid signature algorithm (only {SUPPORTED_SIGNATURE_ALGORITHMS} are \
+                     supported): {s}"
+                ),
             )
         })
     }
@@ -124,7 +168,14 @@ pub enum SerializedAuthInfo {
         #[serde(rename = "applicationID")]
         application_id: Option<String>,
         issuer: String,
-        jwks: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        jwks: Option<String>,
+        #[serde(
+            rename = "secretEnvVar",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        secret_env_var: Option<String>,
         algorithm: SignatureAlgorithm,
     },
     #[serde(untagged)]
@@ -151,11 +202,13 @@ impl TryFrom<AuthInfo> for SerializedAuthInfo {
                 application_id,
                 issuer,
                 jwks,
+                secret_env_var,
                 algorithm,
             } => SerializedAuthInfo::CustomJwt {
                 application_id,
                 issuer: issuer.to_string(),
                 jwks,
+                secret_env_var: secret_env_var.map(String::from),
                 algorithm,
             },
         };
@@ -182,13 +235,19 @@ impl TryFrom<SerializedAuthInfo> for AuthInfo {
                 application_id,
                 issuer,
                 jwks,
+                secret_env_var,
                 algorithm,
             } => {
                 let issuer = deserialize_issuer_url(issuer)?;
+                let secret_env_var = secret_env_var
+                    .map(|name| name.parse::<EnvVarName>())
+                    .transpose()?;
+                validate_custom_jwt_keys(algorithm, jwks.as_deref(), secret_env_var.as_ref())?;
                 Self::CustomJwt {
                     application_id,
                     issuer,
                     jwks,
+                    secret_env_var,
                     algorithm,
                 }
             },
@@ -197,6 +256,41 @@ impl TryFrom<SerializedAuthInfo> for AuthInfo {
     }
 }
 
+/// HS256 providers are keyed by a secret in an environment variable, and every
+/// other algorithm by a JWKS. Reject configs that mix the two up.
+fn validate_custom_jwt_keys(
+    algorithm: SignatureAlgorithm,
+    jwks: Option<&str>,
+    secret_env_var: Option<&EnvVarName>,
+) -> anyhow::Result<()> {
+    let invalid = |msg: &str| {
+        ErrorMetadata::bad_request(
+            "InvalidCustomJwtProvider",
+            format!("Custom JWT provider with algorithm {algorithm:?} {msg}"),
+        )
+    };
+    if algorithm.uses_shared_secret() {
+        anyhow::ensure!(
+            secret_env_var.is_some(),
+            invalid(
+                "must set secretEnvVar to the name of the environment variable holding the shared \
+                 secret"
+            )
+        );
+        anyhow::ensure!(
+            jwks.is_none(),
+            invalid("is verified with a shared secret and must not set jwks")
+        );
+    } else {
+        anyhow::ensure!(jwks.is_some(), invalid("must set jwks"));
+        anyhow::ensure!(
+            secret_env_var.is_none(),
+            invalid("must not set secretEnvVar (only HS256 uses a shared secret)")
+        );
+    }
+    Ok(())
+}
+
 static PROTOCOL_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\w+://").unwrap());
 
 fn deserialize_issuer_url(original_url: String) -> anyhow::Result<IssuerUrl> {
@@ -278,16 +372,23 @@ impl proptest::arbitrary::Arbitrary for AuthInfo {
                 any::<Option<String>>(),              // application_id
                 any::<proptest_http::ArbitraryUri>(), // issuer
                 any::<proptest_http::ArbitraryUri>(), // jwks
+                any::<EnvVarName>(),                  // secret_env_var
                 any::<SignatureAlgorithm>(),          // algorithm
             )
                 .prop_filter_map(
                     "String and URIs weren't valid JWT AuthInfo",
-                    |(app_id, issuer_uri, jwks_uri, algorithm)| {
+                    |(app_id, issuer_uri, jwks_uri, secret_env_var, algorithm)| {
+                        let (jwks, secret_env_var) = if algorithm.uses_shared_secret() {
+                            (None, Some(secret_env_var))
+                        } else {
+                            (Some(jwks_uri.0.to_string()), None)
+                        };
                         IssuerUrl::new(format!("{}", issuer_uri.0))
                             .map(|issuer| Self::CustomJwt {
                                 application_id: app_id,
                                 issuer,
-                                jwks: jwks_uri.0.to_string(),
+                                jwks,
+                                secret_env_var,
                                 algorithm,
                             })
                             .ok()
@@ -345,6 +446,7 @@ mod tests {
     use crate::auth::{
         AuthInfo,
         SerializedAuthInfo,
+        SignatureAlgorithm,
     };
 
     #[test]
@@ -418,4 +520,53 @@ mod tests {
         AuthInfo::try_from(serialized).unwrap_err();
         Ok(())
     }
+
+    #[test]
+    fn test_custom_jwt_signature_algorithms() -> anyhow::Result<()> {
+        for algorithm in [
+            "RS384", "RS512", "ES384", "ES512", "PS256", "PS384", "PS512", "EdDSA",
+        ] {
+            let AuthInfo::CustomJwt {
+                algorithm: parsed, ..
+            } = serde_json::from_str::<SerializedAuthInfo>(&format!(
+                r#"{{"type": "customJwt", "issuer": "https://example.com", "jwks": "https://example.com/jwks.json", "algorithm": "{algorithm}"}}"#,
+            ))?
+            .try_into()?
+            else {
+                panic!("Expected CustomJwt AuthInfo");
+            };
+            assert_eq!(String::from(parsed), format!("\"{algorithm}\""));
+        }
+        assert!("\"ES999\"".parse::<SignatureAlgorithm>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_jwt_shared_secret() -> anyhow::Result<()> {
+        let AuthInfo::CustomJwt {
+            jwks,
+            secret_env_var,
+            ..
+        } = serde_json::from_str::<SerializedAuthInfo>(
+            r#"{"type": "customJwt", "issuer": "https://example.com", "secretEnvVar": "JWT_SECRET", "algorithm": "HS256"}"#,
+        )?
+        .try_into()?
+        else {
+            panic!("Expected CustomJwt AuthInfo");
+        };
+        assert_eq!(jwks, None);
+        assert_eq!(secret_env_var.unwrap().as_ref(), "JWT_SECRET");
+
+        // HS256 needs a secret and can't use a JWKS.
+        for config in [
+            r#"{"type": "customJwt", "issuer": "https://example.com", "algorithm": "HS256"}"#,
+            r#"{"type": "customJwt", "issuer": "https://example.com", "jwks": "https://example.com/jwks.json", "secretEnvVar": "JWT_SECRET", "algorithm": "HS256"}"#,
+            r#"{"type": "customJwt", "issuer": "https://example.com", "secretEnvVar": "JWT_SECRET", "algorithm": "RS256"}"#,
+            r#"{"type": "customJwt", "issuer": "https://example.com", "secretEnvVar": "not a name", "algorithm": "HS256"}"#,
+        ] {
+            let serialized = serde_json::from_str::<SerializedAuthInfo>(config)?;
+            AuthInfo::try_from(serialized).unwrap_err();
+        }
+        Ok(())
+    }
 }
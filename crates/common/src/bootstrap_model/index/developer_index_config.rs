@@ -11,6 +11,10 @@ use super::{
         DeveloperDatabaseIndexConfig,
         SerializedDeveloperDatabaseIndexConfig,
     },
+    sparse_vector_index::{
+        DeveloperSparseVectorIndexConfig,
+        SerializedDeveloperSparseVectorIndexConfig,
+    },
     text_index::{
         DeveloperTextIndexConfig,
         SerializedDeveloperTextIndexConfig,
@@ -33,6 +37,8 @@ pub enum DeveloperIndexConfig {
     Search(DeveloperTextIndexConfig),
 
     Vector(DeveloperVectorIndexConfig),
+
+    SparseVector(DeveloperSparseVectorIndexConfig),
 }
 
 impl From<IndexConfig> for DeveloperIndexConfig {
@@ -47,6 +53,9 @@ impl From<IndexConfig> for DeveloperIndexConfig {
             IndexConfig::Vector {
                 developer_config, ..
             } => DeveloperIndexConfig::Vector(developer_config),
+            IndexConfig::SparseVector {
+                developer_config, ..
+            } => DeveloperIndexConfig::SparseVector(developer_config),
         }
     }
 }
@@ -67,6 +76,7 @@ pub enum SerializedDeveloperIndexConfig {
     Database(SerializedDeveloperDatabaseIndexConfig),
     Search(SerializedDeveloperTextIndexConfig),
     Vector(SerializedDeveloperVectorIndexConfig),
+    SparseVector(SerializedDeveloperSparseVectorIndexConfig),
 }
 
 impl TryFrom<DeveloperIndexConfig> for SerializedDeveloperIndexConfig {
@@ -77,6 +87,7 @@ impl TryFrom<DeveloperIndexConfig> for SerializedDeveloperIndexConfig {
             DeveloperIndexConfig::Database(config) => Self::Database(config.try_into()?),
             DeveloperIndexConfig::Search(config) => Self::Search(config.try_into()?),
             DeveloperIndexConfig::Vector(config) => Self::Vector(config.try_into()?),
+            DeveloperIndexConfig::SparseVector(config) => Self::SparseVector(config.try_into()?),
         })
     }
 }
@@ -89,6 +100,9 @@ impl TryFrom<SerializedDeveloperIndexConfig> for DeveloperIndexConfig {
             SerializedDeveloperIndexConfig::Database(config) => Self::Database(config.try_into()?),
             SerializedDeveloperIndexConfig::Search(config) => Self::Search(config.try_into()?),
             SerializedDeveloperIndexConfig::Vector(config) => Self::Vector(config.try_into()?),
+            SerializedDeveloperIndexConfig::SparseVector(config) => {
+                Self::SparseVector(config.try_into()?)
+            },
         })
     }
 }
@@ -13,6 +13,10 @@ use super::{
         SerializedDatabaseIndexState,
         SerializedDeveloperDatabaseIndexConfig,
     },
+    sparse_vector_index::{
+        DeveloperSparseVectorIndexConfig,
+        SerializedDeveloperSparseVectorIndexConfig,
+    },
     text_index::{
         DeveloperTextIndexConfig,
         SerializedDeveloperTextIndexConfig,
@@ -52,6 +56,13 @@ pub enum IndexConfig {
         developer_config: DeveloperVectorIndexConfig,
         on_disk_state: VectorIndexState,
     },
+
+    /// Sparse vector index, stored in the same fragmented segments as dense
+    /// vector indexes.
+    SparseVector {
+        developer_config: DeveloperSparseVectorIndexConfig,
+        on_disk_state: VectorIndexState,
+    },
 }
 
 impl IndexConfig {
@@ -63,7 +74,8 @@ impl IndexConfig {
             IndexConfig::Text { on_disk_state, .. } => {
                 matches!(on_disk_state, TextIndexState::SnapshottedAt(_))
             },
-            IndexConfig::Vector { on_disk_state, .. } => {
+            IndexConfig::Vector { on_disk_state, .. }
+            | IndexConfig::SparseVector { on_disk_state, .. } => {
                 matches!(on_disk_state, VectorIndexState::SnapshottedAt(_))
             },
         }
@@ -77,7 +89,8 @@ impl IndexConfig {
             IndexConfig::Text { on_disk_state, .. } => {
                 matches!(on_disk_state, TextIndexState::Backfilling(_))
             },
-            IndexConfig::Vector { on_disk_state, .. } => {
+            IndexConfig::Vector { on_disk_state, .. }
+            | IndexConfig::SparseVector { on_disk_state, .. } => {
                 matches!(on_disk_state, VectorIndexState::Backfilling(_))
             },
         }
@@ -112,6 +125,15 @@ impl IndexConfig {
                     ..
                 },
             ) => developer_config == config_to_compare,
+            (
+                IndexConfig::SparseVector {
+                    developer_config, ..
+                },
+                IndexConfig::SparseVector {
+                    developer_config: config_to_compare,
+                    ..
+                },
+            ) => developer_config == config_to_compare,
             (..) => false,
         }
     }
@@ -129,7 +151,9 @@ impl IndexConfig {
     /// on other index types will panic.
     pub fn estimate_pricing_size_bytes(&self) -> anyhow::Result<u64> {
         match self {
-            IndexConfig::Database { .. } | IndexConfig::Text { .. } => {
+            IndexConfig::Database { .. }
+            | IndexConfig::Text { .. }
+            | IndexConfig::SparseVector { .. } => {
                 // TODO(sam): We should support this for all index types in the future. Right
                 // now search indexes are free and we estimate the size of
                 // database indexes. Both of those could instead track usage in their metadata,
@@ -174,6 +198,12 @@ pub enum SerializedIndexConfig {
         developer_config: SerializedDeveloperVectorIndexConfig,
         on_disk_state: SerializedVectorIndexState,
     },
+    #[serde(rename_all = "camelCase")]
+    SparseVector {
+        #[serde(flatten)]
+        developer_config: SerializedDeveloperSparseVectorIndexConfig,
+        on_disk_state: SerializedVectorIndexState,
+    },
 }
 
 impl TryFrom<IndexConfig> for SerializedIndexConfig {
@@ -202,6 +232,13 @@ impl TryFrom<IndexConfig> for SerializedIndexConfig {
                 developer_config: developer_config.try_into()?,
                 on_disk_state: on_disk_state.try_into()?,
             },
+            IndexConfig::SparseVector {
+                developer_config,
+                on_disk_state,
+            } => SerializedIndexConfig::SparseVector {
+                developer_config: developer_config.try_into()?,
+                on_disk_state: on_disk_state.try_into()?,
+            },
         })
     }
 }
@@ -232,6 +269,13 @@ impl TryFrom<SerializedIndexConfig> for IndexConfig {
                 developer_config: developer_config.try_into()?,
                 on_disk_state: on_disk_state.try_into()?,
             },
+            SerializedIndexConfig::SparseVector {
+                developer_config,
+                on_disk_state,
+            } => IndexConfig::SparseVector {
+                developer_config: developer_config.try_into()?,
+                on_disk_state: on_disk_state.try_into()?,
+            },
         })
     }
 }
@@ -250,6 +294,7 @@ mod tests {
         vector_index::{
             DeveloperVectorIndexConfig,
             FragmentedVectorSegment,
+            VectorDistanceMetric,
             VectorIndexBackfillState,
             VectorIndexState,
         },
@@ -287,6 +332,8 @@ mod tests {
                     dimensions: 1536.try_into()?,
                     vector_field: "embedding.field".parse()?,
                     filter_fields: btreeset! { "filter1".parse()?, "filter2".parse()? },
+                    distance_metric: VectorDistanceMetric::Cosine,
+                    quantization: None,
                 },
                 on_disk_state: VectorIndexState::Backfilling(VectorIndexBackfillState {
                     cursor: None,
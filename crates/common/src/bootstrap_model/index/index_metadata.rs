@@ -21,11 +21,14 @@ use super::{
         IndexedFields,
     },
     index_config::SerializedIndexConfig,
+    sparse_vector_index::DeveloperSparseVectorIndexConfig,
     vector_index::{
         DeveloperVectorIndexConfig,
         VectorDimensions,
+        VectorDistanceMetric,
         VectorIndexBackfillState,
         VectorIndexState,
+        VectorQuantization,
     },
     IndexConfig,
 };
@@ -84,12 +87,14 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
         name: GenericIndexName<T>,
         search_field: FieldPath,
         filter_fields: BTreeSet<FieldPath>,
+        synonyms: Vec<BTreeSet<String>>,
     ) -> Self {
         Self::new_text_index(
             name,
             DeveloperTextIndexConfig {
                 search_field,
                 filter_fields,
+                synonyms,
             },
             TextIndexState::Backfilling(TextIndexBackfillState::new(false)),
         )
@@ -100,6 +105,8 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
         vector_field: FieldPath,
         dimensions: VectorDimensions,
         filter_fields: BTreeSet<FieldPath>,
+        distance_metric: VectorDistanceMetric,
+        quantization: Option<VectorQuantization>,
     ) -> Self {
         Self {
             name,
@@ -108,6 +115,30 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
                     dimensions,
                     vector_field,
                     filter_fields,
+                    distance_metric,
+                    quantization,
+                },
+                on_disk_state: VectorIndexState::Backfilling(VectorIndexBackfillState {
+                    segments: vec![],
+                    cursor: None,
+                    backfill_snapshot_ts: None,
+                    staged: false,
+                }),
+            },
+        }
+    }
+
+    pub fn new_backfilling_sparse_vector_index(
+        name: GenericIndexName<T>,
+        vector_field: FieldPath,
+        filter_fields: BTreeSet<FieldPath>,
+    ) -> Self {
+        Self {
+            name,
+            config: IndexConfig::SparseVector {
+                developer_config: DeveloperSparseVectorIndexConfig {
+                    vector_field,
+                    filter_fields,
                 },
                 on_disk_state: VectorIndexState::Backfilling(VectorIndexBackfillState {
                     segments: vec![],
@@ -155,6 +186,10 @@ impl<T: IndexTableIdentifier> IndexMetadata<T> {
         matches!(self.config, IndexConfig::Vector { .. })
     }
 
+    pub fn is_sparse_vector_index(&self) -> bool {
+        matches!(self.config, IndexConfig::SparseVector { .. })
+    }
+
     pub fn map_table<U: IndexTableIdentifier>(
         self,
         f: &impl Fn(T) -> anyhow::Result<U>,
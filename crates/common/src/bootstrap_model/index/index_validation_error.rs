@@ -157,6 +157,21 @@ pub fn too_many_filter_fields(num_fields: usize) -> ErrorMetadata {
         format!("Search indexes may have up to {num_fields} filter fields."),
     )
 }
+pub fn too_many_synonym_sets(num_sets: usize) -> ErrorMetadata {
+    ErrorMetadata::bad_request(
+        "IndexTooManySynonymSets",
+        format!("Search indexes may have up to {num_sets} synonym sets."),
+    )
+}
+pub fn invalid_synonym_set(index: &IndexDescriptor, max_set_size: usize) -> ErrorMetadata {
+    ErrorMetadata::bad_request(
+        "IndexInvalidSynonymSet",
+        format!(
+            "Synonym sets for search index \"{index}\" must contain between 2 and {max_set_size} \
+             distinct, non-empty entries."
+        ),
+    )
+}
 pub fn too_many_indexes(table_name: &TableName, num_indexes: usize) -> ErrorMetadata {
     ErrorMetadata::bad_request(
         "TooManyIndexes",
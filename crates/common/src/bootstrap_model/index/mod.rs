@@ -3,6 +3,7 @@ mod developer_index_config;
 mod index_config;
 mod index_metadata;
 pub mod index_validation_error;
+pub mod sparse_vector_index;
 pub mod text_index;
 pub mod vector_index;
 
@@ -47,4 +48,6 @@ pub static INDEX_BY_TABLE_ID_VIRTUAL_INDEX_DESCRIPTOR: LazyLock<IndexDescriptor>
 
 pub const MAX_INDEX_FIELDS_SIZE: usize = 16;
 pub const MAX_TEXT_INDEX_FILTER_FIELDS_SIZE: usize = 16;
+pub const MAX_TEXT_INDEX_SYNONYM_SETS: usize = 256;
+pub const MAX_TEXT_INDEX_SYNONYM_SET_SIZE: usize = 16;
 pub const MAX_VECTOR_INDEX_FILTER_FIELDS_SIZE: usize = 16;
@@ -0,0 +1,68 @@
+use std::collections::BTreeSet;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use value::{
+    codegen_convex_serialization,
+    FieldPath,
+};
+
+/// The maximum number of nonzero entries in a sparse vector. Sparse vectors
+/// are stored as objects, so this matches the maximum number of object fields.
+pub const MAX_SPARSE_VECTOR_LENGTH: usize = 1024;
+
+/// A sparse vector index stores maps from dimension index to weight in an
+/// inverted index and scores them against a query by dot product.
+///
+/// Sparse indexes reuse `VectorIndexState` for their on disk state since
+/// their segments are fragmented in the same way as dense vector segments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct DeveloperSparseVectorIndexConfig {
+    /// The field to index for sparse vector search.
+    pub vector_field: FieldPath,
+
+    /// Other fields to index for equality filtering.
+    pub filter_fields: BTreeSet<FieldPath>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct SerializedDeveloperSparseVectorIndexConfig {
+    vector_field: String,
+    filter_fields: Vec<String>,
+}
+
+impl TryFrom<DeveloperSparseVectorIndexConfig> for SerializedDeveloperSparseVectorIndexConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(config: DeveloperSparseVectorIndexConfig) -> anyhow::Result<Self> {
+        Ok(Self {
+            vector_field: config.vector_field.into(),
+            filter_fields: config.filter_fields.into_iter().map(String::from).collect(),
+        })
+    }
+}
+
+impl TryFrom<SerializedDeveloperSparseVectorIndexConfig> for DeveloperSparseVectorIndexConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(config: SerializedDeveloperSparseVectorIndexConfig) -> anyhow::Result<Self> {
+        Ok(Self {
+            vector_field: config.vector_field.parse()?,
+            filter_fields: config
+                .filter_fields
+                .into_iter()
+                .map(|p| p.parse())
+                .collect::<anyhow::Result<BTreeSet<FieldPath>>>()?,
+        })
+    }
+}
+
+codegen_convex_serialization!(
+    DeveloperSparseVectorIndexConfig,
+    SerializedDeveloperSparseVectorIndexConfig
+);
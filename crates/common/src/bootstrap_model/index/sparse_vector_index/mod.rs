@@ -0,0 +1,7 @@
+mod index_config;
+
+pub use self::index_config::{
+    DeveloperSparseVectorIndexConfig,
+    SerializedDeveloperSparseVectorIndexConfig,
+    MAX_SPARSE_VECTOR_LENGTH,
+};
@@ -16,6 +16,20 @@ pub struct DeveloperTextIndexConfig {
 
     /// Other fields to index for equality filtering.
     pub filter_fields: BTreeSet<FieldPath>,
+
+    /// Groups of terms or phrases that search queries against this index
+    /// treat as equivalent.
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "synonym_sets_strategy()")
+    )]
+    pub synonyms: Vec<BTreeSet<String>>,
+}
+
+#[cfg(any(test, feature = "testing"))]
+pub fn synonym_sets_strategy() -> impl proptest::strategy::Strategy<Value = Vec<BTreeSet<String>>>
+{
+    proptest::collection::vec(proptest::collection::btree_set("[a-z]{1,8}", 2..4), 0..4)
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -24,6 +38,9 @@ pub struct DeveloperTextIndexConfig {
 pub struct SerializedDeveloperTextIndexConfig {
     search_field: String,
     filter_fields: Vec<String>,
+    // Omitted when empty so existing index metadata is unchanged.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    synonyms: Vec<Vec<String>>,
 }
 
 impl TryFrom<DeveloperTextIndexConfig> for SerializedDeveloperTextIndexConfig {
@@ -33,6 +50,11 @@ impl TryFrom<DeveloperTextIndexConfig> for SerializedDeveloperTextIndexConfig {
         Ok(Self {
             search_field: config.search_field.into(),
             filter_fields: config.filter_fields.into_iter().map(String::from).collect(),
+            synonyms: config
+                .synonyms
+                .into_iter()
+                .map(|synonym_set| synonym_set.into_iter().collect())
+                .collect(),
         })
     }
 }
@@ -48,6 +70,11 @@ impl TryFrom<SerializedDeveloperTextIndexConfig> for DeveloperTextIndexConfig {
                 .into_iter()
                 .map(|p| p.parse())
                 .collect::<anyhow::Result<BTreeSet<FieldPath>>>()?,
+            synonyms: config
+                .synonyms
+                .into_iter()
+                .map(|synonym_set| synonym_set.into_iter().collect())
+                .collect(),
         })
     }
 }
@@ -70,6 +97,11 @@ impl TryFrom<pb::searchlight::SearchIndexConfig> for DeveloperTextIndexConfig {
                 .collect::<Result<Vec<_>, _>>()?
                 .into_iter()
                 .collect(),
+            synonyms: proto
+                .synonyms
+                .into_iter()
+                .map(|synonym_set| synonym_set.entries.into_iter().collect())
+                .collect(),
         })
     }
 }
@@ -83,6 +115,13 @@ impl From<DeveloperTextIndexConfig> for pb::searchlight::SearchIndexConfig {
                 .into_iter()
                 .map(|f| f.into())
                 .collect::<Vec<_>>(),
+            synonyms: config
+                .synonyms
+                .into_iter()
+                .map(|synonym_set| pb::searchlight::SynonymSet {
+                    entries: synonym_set.into_iter().collect(),
+                })
+                .collect(),
         }
     }
 }
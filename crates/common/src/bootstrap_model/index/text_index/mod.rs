@@ -3,6 +3,8 @@ mod index_config;
 mod index_snapshot;
 mod index_state;
 
+#[cfg(any(test, feature = "testing"))]
+pub use self::index_config::synonym_sets_strategy;
 pub use self::{
     backfill_state::{
         TextBackfillCursor,
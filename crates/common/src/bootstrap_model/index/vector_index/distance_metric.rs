@@ -0,0 +1,76 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+use errors::ErrorMetadata;
+
+/// How vectors in a vector index are compared to a query vector.
+///
+/// Scores are always higher-is-better internally. For `Euclidean` the score
+/// exposed in search results is the distance itself, so lower is better.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum VectorDistanceMetric {
+    #[default]
+    Cosine,
+    DotProduct,
+    Euclidean,
+}
+
+impl VectorDistanceMetric {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for VectorDistanceMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Cosine => "cosine",
+            Self::DotProduct => "dotProduct",
+            Self::Euclidean => "euclidean",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for VectorDistanceMetric {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let metric = match s {
+            "cosine" => Self::Cosine,
+            "dotProduct" => Self::DotProduct,
+            "euclidean" => Self::Euclidean,
+            _ => anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidVectorDistanceMetric",
+                format!(
+                    "Unknown vector distance metric \"{s}\". Expected one of \"cosine\", \
+                     \"dotProduct\" or \"euclidean\"."
+                )
+            )),
+        };
+        Ok(metric)
+    }
+}
+
+impl From<VectorDistanceMetric> for pb::searchlight::VectorDistanceMetric {
+    fn from(metric: VectorDistanceMetric) -> Self {
+        match metric {
+            VectorDistanceMetric::Cosine => Self::Cosine,
+            VectorDistanceMetric::DotProduct => Self::DotProduct,
+            VectorDistanceMetric::Euclidean => Self::Euclidean,
+        }
+    }
+}
+
+impl From<pb::searchlight::VectorDistanceMetric> for VectorDistanceMetric {
+    fn from(metric: pb::searchlight::VectorDistanceMetric) -> Self {
+        match metric {
+            pb::searchlight::VectorDistanceMetric::Cosine => Self::Cosine,
+            pb::searchlight::VectorDistanceMetric::DotProduct => Self::DotProduct,
+            pb::searchlight::VectorDistanceMetric::Euclidean => Self::Euclidean,
+        }
+    }
+}
@@ -9,7 +9,12 @@ use value::{
     FieldPath,
 };
 
-use super::VectorDimensions;
+use super::{
+    SerializedVectorQuantization,
+    VectorDimensions,
+    VectorDistanceMetric,
+    VectorQuantization,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
@@ -22,6 +27,12 @@ pub struct DeveloperVectorIndexConfig {
 
     /// Other fields to index for equality filtering.
     pub filter_fields: BTreeSet<FieldPath>,
+
+    /// How vectors are scored against a query vector.
+    pub distance_metric: VectorDistanceMetric,
+
+    /// How vectors are compressed in disk segments, if at all.
+    pub quantization: Option<VectorQuantization>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -33,6 +44,11 @@ pub struct SerializedDeveloperVectorIndexConfig {
     dimensions: i64,
     vector_field: String,
     filter_fields: Vec<String>,
+    // Omitted for cosine so existing index metadata is unchanged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distance_metric: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quantization: Option<SerializedVectorQuantization>,
 }
 
 impl TryFrom<DeveloperVectorIndexConfig> for SerializedDeveloperVectorIndexConfig {
@@ -43,6 +59,9 @@ impl TryFrom<DeveloperVectorIndexConfig> for SerializedDeveloperVectorIndexConfi
             dimensions: u32::from(config.dimensions) as i64,
             vector_field: config.vector_field.into(),
             filter_fields: config.filter_fields.into_iter().map(String::from).collect(),
+            distance_metric: (!config.distance_metric.is_default())
+                .then(|| config.distance_metric.to_string()),
+            quantization: config.quantization.map(SerializedVectorQuantization::from),
         })
     }
 }
@@ -59,6 +78,15 @@ impl TryFrom<SerializedDeveloperVectorIndexConfig> for DeveloperVectorIndexConfi
                 .into_iter()
                 .map(|p| p.parse())
                 .collect::<anyhow::Result<BTreeSet<FieldPath>>>()?,
+            distance_metric: config
+                .distance_metric
+                .map(|m| m.parse())
+                .transpose()?
+                .unwrap_or_default(),
+            quantization: config
+                .quantization
+                .map(VectorQuantization::try_from)
+                .transpose()?,
         })
     }
 }
@@ -72,6 +100,8 @@ impl TryFrom<pb::searchlight::VectorIndexConfig> for DeveloperVectorIndexConfig
     type Error = anyhow::Error;
 
     fn try_from(proto: pb::searchlight::VectorIndexConfig) -> anyhow::Result<Self> {
+        let distance_metric = proto.distance_metric().into();
+        let quantization = proto.quantization.map(VectorQuantization::from);
         Ok(DeveloperVectorIndexConfig {
             dimensions: VectorDimensions::try_from(proto.dimension)?,
             vector_field: proto
@@ -85,6 +115,8 @@ impl TryFrom<pb::searchlight::VectorIndexConfig> for DeveloperVectorIndexConfig
                 .collect::<Result<Vec<_>, _>>()?
                 .into_iter()
                 .collect(),
+            distance_metric,
+            quantization,
         })
     }
 }
@@ -99,6 +131,10 @@ impl From<DeveloperVectorIndexConfig> for pb::searchlight::VectorIndexConfig {
                 .into_iter()
                 .map(|f| f.into())
                 .collect::<Vec<_>>(),
+            distance_metric: Some(pb::searchlight::VectorDistanceMetric::from(
+                config.distance_metric,
+            ) as i32),
+            quantization: config.quantization.map(|q| q.into()),
         }
     }
 }
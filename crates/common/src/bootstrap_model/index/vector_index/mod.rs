@@ -1,8 +1,10 @@
 mod backfill_state;
 mod dimensions;
+mod distance_metric;
 mod index_config;
 mod index_snapshot;
 mod index_state;
+mod quantization;
 mod segment;
 
 pub use self::{
@@ -12,6 +14,7 @@ pub use self::{
         MAX_VECTOR_DIMENSIONS,
         MIN_VECTOR_DIMENSIONS,
     },
+    distance_metric::VectorDistanceMetric,
     index_config::{
         DeveloperVectorIndexConfig,
         SerializedDeveloperVectorIndexConfig,
@@ -24,6 +27,11 @@ pub use self::{
         SerializedVectorIndexState,
         VectorIndexState,
     },
+    quantization::{
+        SerializedVectorQuantization,
+        VectorQuantization,
+        VectorQuantizationType,
+    },
     segment::FragmentedVectorSegment,
 };
 
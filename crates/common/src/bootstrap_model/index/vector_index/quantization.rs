@@ -0,0 +1,127 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+use errors::ErrorMetadata;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// Opt-in compression of the vectors in a vector index's disk segments.
+/// Quantized vectors are kept in memory for the approximate search, while the
+/// full precision vectors stay on disk.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct VectorQuantization {
+    pub quantization_type: VectorQuantizationType,
+    /// Whether to rescore the approximate results against the full precision
+    /// vectors. This costs disk reads but recovers most of the lost recall.
+    pub rescore: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum VectorQuantizationType {
+    /// Each f32 component is stored as an int8.
+    Scalar,
+    /// Each f32 component is stored as a single bit.
+    Binary,
+}
+
+impl VectorQuantizationType {
+    /// The size of one quantized vector, ignoring qdrant's per-segment
+    /// overhead.
+    pub fn vector_size_bytes(&self, dimensions: usize) -> usize {
+        match self {
+            Self::Scalar => dimensions,
+            Self::Binary => dimensions.div_ceil(8),
+        }
+    }
+}
+
+impl fmt::Display for VectorQuantizationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Scalar => "scalar",
+            Self::Binary => "binary",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for VectorQuantizationType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let quantization_type = match s {
+            "scalar" => Self::Scalar,
+            "binary" => Self::Binary,
+            _ => anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidVectorQuantization",
+                format!(
+                    "Unknown vector quantization type \"{s}\". Expected \"scalar\" or \"binary\"."
+                )
+            )),
+        };
+        Ok(quantization_type)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct SerializedVectorQuantization {
+    #[serde(rename = "type")]
+    pub quantization_type: String,
+    pub rescore: Option<bool>,
+}
+
+impl From<VectorQuantization> for SerializedVectorQuantization {
+    fn from(quantization: VectorQuantization) -> Self {
+        Self {
+            quantization_type: quantization.quantization_type.to_string(),
+            rescore: Some(quantization.rescore),
+        }
+    }
+}
+
+impl TryFrom<SerializedVectorQuantization> for VectorQuantization {
+    type Error = anyhow::Error;
+
+    fn try_from(quantization: SerializedVectorQuantization) -> anyhow::Result<Self> {
+        Ok(Self {
+            quantization_type: quantization.quantization_type.parse()?,
+            // Rescoring is cheap relative to the recall it recovers, so it's on
+            // unless developers opt out.
+            rescore: quantization.rescore.unwrap_or(true),
+        })
+    }
+}
+
+impl From<VectorQuantization> for pb::searchlight::VectorQuantization {
+    fn from(quantization: VectorQuantization) -> Self {
+        let quantization_type = match quantization.quantization_type {
+            VectorQuantizationType::Scalar => pb::searchlight::VectorQuantizationType::Scalar,
+            VectorQuantizationType::Binary => pb::searchlight::VectorQuantizationType::Binary,
+        };
+        Self {
+            quantization_type: Some(quantization_type as i32),
+            rescore: Some(quantization.rescore),
+        }
+    }
+}
+
+impl From<pb::searchlight::VectorQuantization> for VectorQuantization {
+    fn from(proto: pb::searchlight::VectorQuantization) -> Self {
+        let quantization_type = match proto.quantization_type() {
+            pb::searchlight::VectorQuantizationType::Scalar => VectorQuantizationType::Scalar,
+            pb::searchlight::VectorQuantizationType::Binary => VectorQuantizationType::Binary,
+        };
+        Self {
+            quantization_type,
+            rescore: proto.rescore.unwrap_or(true),
+        }
+    }
+}
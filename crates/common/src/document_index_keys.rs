@@ -109,8 +109,7 @@ impl HeapSize for DocumentIndexKeys {
 pub enum DocumentIndexKeyValue {
     Standard(IndexKeyBytes),
     Search(SearchIndexKeyValue),
-    // We don’t store index key values for vector indexes because they don’t
-    // support subscriptions.
+    Vector(VectorIndexKeyValue),
 }
 
 #[derive(Clone, Debug)]
@@ -123,6 +122,74 @@ pub struct SearchIndexKeyValue {
     pub search_field_value: Option<SearchValueTokens>,
 }
 
+#[derive(Clone, Debug)]
+#[cfg_attr(any(test, feature = "testing"), derive(PartialEq, Eq))]
+pub struct VectorIndexKeyValue {
+    /// These are values for the filter fields of the vector index.
+    pub filter_values: WithHeapSize<BTreeMap<FieldPath, SearchFilterValue>>,
+    /// The document's vector, if it has one with the index's dimensions.
+    pub vector: Option<CompactVector>,
+}
+
+/// A vector stored with one byte per dimension, so keeping it in the write log
+/// costs a quarter of the full vector. Each component is rounded to a multiple
+/// of `scale`, so it's within `scale / 2` of the original.
+#[derive(Clone, Debug)]
+pub struct CompactVector {
+    scale: f32,
+    components: Box<[i8]>,
+}
+
+impl CompactVector {
+    pub fn new(vector: &[f32]) -> Self {
+        let max_abs = vector.iter().fold(0f32, |max, x| max.max(x.abs()));
+        let scale = max_abs / i8::MAX as f32;
+        let components = vector
+            .iter()
+            .map(|x| {
+                if scale > 0. {
+                    (x / scale).round() as i8
+                } else {
+                    0
+                }
+            })
+            .collect();
+        Self { scale, components }
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// The approximate vector this was compressed from.
+    pub fn to_vec(&self) -> Vec<f32> {
+        self.components
+            .iter()
+            .map(|c| *c as f32 * self.scale)
+            .collect()
+    }
+
+    /// An upper bound on the Euclidean distance between `to_vec()` and the
+    /// original vector.
+    pub fn max_error(&self) -> f32 {
+        self.scale / 2. * (self.components.len() as f32).sqrt()
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl PartialEq for CompactVector {
+    fn eq(&self, other: &Self) -> bool {
+        self.scale.to_bits() == other.scale.to_bits() && self.components == other.components
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl Eq for CompactVector {}
+
 impl HeapSize for DocumentIndexKeyValue {
     fn heap_size(&self) -> usize {
         match self {
@@ -136,6 +203,12 @@ impl HeapSize for DocumentIndexKeyValue {
                     + search_field.heap_size()
                     + search_field_value.heap_size()
             },
+            DocumentIndexKeyValue::Vector(VectorIndexKeyValue {
+                filter_values,
+                vector,
+            }) => {
+                filter_values.heap_size() + vector.as_ref().map_or(0, |v| v.len())
+            },
         }
     }
 }
@@ -1,13 +1,21 @@
 use std::{
     collections::{
         BTreeMap,
+        BTreeSet,
         HashMap,
     },
+    fmt,
+    net::{
+        IpAddr,
+        SocketAddr,
+    },
+    str::FromStr,
     sync::{
         atomic::{
             AtomicU64,
             Ordering,
         },
+        Arc,
         LazyLock,
     },
 };
@@ -20,14 +28,27 @@ use futures::{
     StreamExt,
 };
 use futures_async_stream::try_stream;
-use http::StatusCode;
+use http::{
+    HeaderMap,
+    HeaderValue,
+    StatusCode,
+};
+use parking_lot::Mutex;
 use reqwest::{
+    dns::{
+        Addrs,
+        Name,
+        Resolve,
+        Resolving,
+    },
     redirect,
     Body,
     Proxy,
     Url,
 };
+use serde_json::json;
 use tokio::select;
+use url::Host;
 
 use crate::http::{
     HttpRequestStream,
@@ -39,6 +60,19 @@ use crate::http::{
 pub trait FetchClient: Send + Sync {
     async fn fetch(&self, request: HttpRequestStream) -> anyhow::Result<HttpResponseStream>;
 
+    /// Fetch on behalf of a deployment with an egress policy. Clients that
+    /// connect to the network must connect only to the addresses
+    /// [`EgressPolicy::check`] returns, since a hostname can resolve
+    /// differently by then.
+    async fn fetch_with_egress_policy(
+        &self,
+        request: HttpRequestStream,
+        egress_policy: &EgressPolicy,
+    ) -> anyhow::Result<HttpResponseStream> {
+        egress_policy.check(&request.url).await?;
+        self.fetch(request).await
+    }
+
     /// Unrestricted, unproxied fetch to be used for internal purposes only.
     /// Customer UDFs should never have access to this method. A `purpose`
     /// parameter is required (but not used) just to make it more obvious why
@@ -59,39 +93,135 @@ pub struct ProxiedFetchClient {
     http_client:
         LazyLock<reqwest::Client, Box<dyn FnOnce() -> reqwest::Client + Send + Sync + 'static>>,
     internal_http_client: reqwest::Client,
+    proxy: Option<(Url, String)>,
+    egress_clients: Mutex<EgressPolicyClients>,
+}
+
+/// Clients for fetching under `policy`, rebuilt when the policy changes.
+/// Without a proxy, there's a client per port since rules can be limited to
+/// some ports, and each connects only to addresses that were checked for
+/// that port. With a proxy, a single client sends the policy to the proxy.
+#[derive(Default)]
+struct EgressPolicyClients {
+    policy: EgressPolicy,
+    by_port: BTreeMap<u16, (reqwest::Client, Arc<EgressPolicyResolver>)>,
+    proxied: Option<reqwest::Client>,
+}
+
+/// Proxy header carrying the deployment's egress policy as JSON, so the proxy
+/// can check the addresses it connects to against CIDR rules.
+pub const EGRESS_POLICY_PROXY_HEADER: &str = "Convex-Egress-Policy";
+
+fn build_http_client(
+    proxy_url: Option<Url>,
+    client_id: String,
+    resolver: Option<Arc<EgressPolicyResolver>>,
+    egress_policy: Option<&EgressPolicy>,
+) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder().redirect(redirect::Policy::none());
+    // It's okay to panic on these errors, as they indicate a serious programming
+    // error -- building the reqwest client is expected to be infallible.
+    if let Some(proxy_url) = proxy_url {
+        let mut proxy = Proxy::all(proxy_url)
+            .expect("Infallible conversion from URL type to URL type")
+            .custom_http_auth(
+                client_id
+                    .try_into()
+                    .expect("Backend name is not valid ASCII?"),
+            );
+        if let Some(egress_policy) = egress_policy {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                EGRESS_POLICY_PROXY_HEADER,
+                HeaderValue::from_str(&egress_policy.to_proxy_header())
+                    .expect("Egress policy JSON is not valid ASCII?"),
+            );
+            proxy = proxy.headers(headers);
+        }
+        builder = builder.proxy(proxy);
+    }
+    if let Some(resolver) = resolver {
+        builder = builder.dns_resolver(resolver);
+    }
+    builder = builder.user_agent("Convex/1.0");
+    builder.build().expect("Failed to build reqwest client")
 }
 
 impl ProxiedFetchClient {
     pub fn new(proxy_url: Option<Url>, client_id: String) -> Self {
+        let proxy = proxy_url.map(|proxy_url| (proxy_url, client_id.clone()));
+        let http_client_proxy = proxy.clone();
         Self {
-            http_client: LazyLock::new(Box::new(move || {
-                let mut builder = reqwest::Client::builder().redirect(redirect::Policy::none());
-                // It's okay to panic on these errors, as they indicate a serious programming
-                // error -- building the reqwest client is expected to be infallible.
-                if let Some(proxy_url) = proxy_url {
-                    let proxy = Proxy::all(proxy_url)
-                        .expect("Infallible conversion from URL type to URL type")
-                        .custom_http_auth(
-                            client_id
-                                .try_into()
-                                .expect("Backend name is not valid ASCII?"),
-                        );
-                    builder = builder.proxy(proxy);
-                }
-                builder = builder.user_agent("Convex/1.0");
-                builder.build().expect("Failed to build reqwest client")
+            proxy,
+            http_client: LazyLock::new(Box::new(move || match http_client_proxy {
+                Some((proxy_url, client_id)) => {
+                    build_http_client(Some(proxy_url), client_id, None, None)
+                },
+                None => build_http_client(None, client_id, None, None),
             })),
             internal_http_client: INTERNAL_HTTP_CLIENT.clone(),
+            egress_clients: Mutex::new(EgressPolicyClients::default()),
         }
     }
-}
 
-#[async_trait]
-impl FetchClient for ProxiedFetchClient {
-    async fn fetch(&self, mut request: HttpRequestStream) -> anyhow::Result<HttpResponseStream> {
-        let mut request_builder = self
-            .http_client
-            .request(request.method, request.url.as_str());
+    fn egress_clients(
+        &self,
+        egress_policy: &EgressPolicy,
+    ) -> parking_lot::MutexGuard<'_, EgressPolicyClients> {
+        let mut clients = self.egress_clients.lock();
+        if clients.policy != *egress_policy {
+            *clients = EgressPolicyClients {
+                policy: egress_policy.clone(),
+                ..Default::default()
+            };
+        }
+        clients
+    }
+
+    /// A client that connects to `host` on `port` only at `addresses`, which
+    /// have been checked against the policy.
+    fn pinned_egress_policy_client(
+        &self,
+        egress_policy: &EgressPolicy,
+        host: &str,
+        port: u16,
+        addresses: Vec<SocketAddr>,
+    ) -> reqwest::Client {
+        let mut clients = self.egress_clients(egress_policy);
+        let (client, resolver) = clients.by_port.entry(port).or_insert_with(|| {
+            let resolver = Arc::new(EgressPolicyResolver::default());
+            let client = build_http_client(None, String::new(), Some(resolver.clone()), None);
+            (client, resolver)
+        });
+        resolver.pin(host, addresses);
+        client.clone()
+    }
+
+    fn proxied_egress_policy_client(
+        &self,
+        egress_policy: &EgressPolicy,
+        proxy_url: &Url,
+        client_id: &str,
+    ) -> reqwest::Client {
+        self.egress_clients(egress_policy)
+            .proxied
+            .get_or_insert_with(|| {
+                build_http_client(
+                    Some(proxy_url.clone()),
+                    client_id.to_string(),
+                    None,
+                    Some(egress_policy),
+                )
+            })
+            .clone()
+    }
+
+    async fn fetch_with_client(
+        &self,
+        http_client: &reqwest::Client,
+        mut request: HttpRequestStream,
+    ) -> anyhow::Result<HttpResponseStream> {
+        let mut request_builder = http_client.request(request.method, request.url.as_str());
         let body = Body::wrap_stream(request.body);
         request_builder = request_builder.body(body);
         for (name, value) in &request.headers {
@@ -99,7 +229,7 @@ impl FetchClient for ProxiedFetchClient {
         }
         let raw_request = request_builder.build()?;
         let raw_response = select! {
-            response = self.http_client.execute(raw_request) => {
+            response = http_client.execute(raw_request) => {
                 response?
             },
             _ = &mut request.signal => {
@@ -126,6 +256,39 @@ impl FetchClient for ProxiedFetchClient {
         };
         Ok(response)
     }
+}
+
+#[async_trait]
+impl FetchClient for ProxiedFetchClient {
+    async fn fetch(&self, request: HttpRequestStream) -> anyhow::Result<HttpResponseStream> {
+        self.fetch_with_client(&self.http_client, request).await
+    }
+
+    async fn fetch_with_egress_policy(
+        &self,
+        request: HttpRequestStream,
+        egress_policy: &EgressPolicy,
+    ) -> anyhow::Result<HttpResponseStream> {
+        let addresses = egress_policy.check(&request.url).await?;
+        // CIDR rules have to hold for the addresses we actually connect to, or
+        // DNS rebinding could get around them. With a proxy, the proxy
+        // resolves the hostname and connects, so it checks the policy we send
+        // it. Without one, connect only to the addresses we just checked.
+        let http_client = match (
+            &self.proxy,
+            request.url.domain(),
+            request.url.port_or_known_default(),
+        ) {
+            (Some((proxy_url, client_id)), ..) if egress_policy.has_cidr_rule() => {
+                self.proxied_egress_policy_client(egress_policy, proxy_url, client_id)
+            },
+            (None, Some(domain), Some(port)) if !addresses.is_empty() => {
+                self.pinned_egress_policy_client(egress_policy, domain, port, addresses)
+            },
+            _ => (*self.http_client).clone(),
+        };
+        self.fetch_with_client(&http_client, request).await
+    }
 
     async fn internal_fetch(
         &self,
@@ -263,6 +426,391 @@ pub enum InternalFetchPurpose {
     AccessTokenAuth,
 }
 
+pub const EGRESS_DENIED: &str = "EgressDenied";
+
+/// Deployment-level restrictions on where functions may `fetch`. A request is
+/// denied if it matches any `deny` rule, or if there are `allow` rules and it
+/// matches none of them.
+///
+/// Node actions make their own requests, so they can't run while a policy is
+/// configured.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct EgressPolicy {
+    pub allow: Vec<EgressRule>,
+    pub deny: Vec<EgressRule>,
+    /// Emit a system log line for every outbound request, so requests show up
+    /// in the function's logs and in log sinks.
+    pub log_requests: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct EgressRule {
+    pub destination: EgressDestination,
+    /// The rule applies to any port if this is empty.
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(
+            strategy = "proptest::collection::btree_set(proptest::prelude::any::<u16>(), 0..4)"
+        )
+    )]
+    pub ports: BTreeSet<u16>,
+}
+
+/// Either a host pattern like `api.example.com` or `*.example.com`, or a CIDR
+/// block like `10.0.0.0/8`. A bare IP address is a CIDR block with a single
+/// address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EgressDestination {
+    /// `*` matches every host, and `*.example.com` matches every subdomain of
+    /// `example.com` (but not `example.com` itself).
+    Host(String),
+    Cidr {
+        network: IpAddr,
+        prefix_len: u8,
+    },
+}
+
+impl EgressPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    pub fn has_cidr_rule(&self) -> bool {
+        self.allow
+            .iter()
+            .chain(&self.deny)
+            .any(|rule| matches!(rule.destination, EgressDestination::Cidr { .. }))
+    }
+
+    /// Check that `url` may be fetched under this policy. Hostnames are only
+    /// resolved if there's a CIDR rule to check them against, and a CIDR rule
+    /// matches if any resolved address is in range for `deny` rules, but only
+    /// if all of them are for `allow` rules.
+    ///
+    /// Returns the addresses the hostname resolved to, if it was resolved. The
+    /// fetch client must connect only to these, since DNS can answer
+    /// differently by the time it connects.
+    pub async fn check(&self, url: &Url) -> anyhow::Result<Vec<SocketAddr>> {
+        if self.is_empty() {
+            return Ok(vec![]);
+        }
+        let (Some(host), Some(port)) = (url.host(), url.port_or_known_default()) else {
+            // Let the fetch client reject URLs without a host.
+            return Ok(vec![]);
+        };
+        let resolved = match host {
+            Host::Domain(domain) if self.has_cidr_rule() => {
+                let resolved: Vec<SocketAddr> = tokio::net::lookup_host((domain, port))
+                    .await
+                    .map_err(|e| {
+                        anyhow::anyhow!(ErrorMetadata::bad_request(
+                            "FetchDnsLookupFailed",
+                            format!("Failed to resolve {domain}: {e}"),
+                        ))
+                    })?
+                    .collect();
+                anyhow::ensure!(
+                    !resolved.is_empty(),
+                    ErrorMetadata::bad_request(
+                        "FetchDnsLookupFailed",
+                        format!("Failed to resolve {domain}: no addresses found"),
+                    )
+                );
+                resolved
+            },
+            _ => vec![],
+        };
+        let addresses: Vec<IpAddr> = match host {
+            Host::Ipv4(addr) => vec![IpAddr::V4(addr)],
+            Host::Ipv6(addr) => vec![IpAddr::V6(addr)],
+            Host::Domain(_) => resolved.iter().map(|addr| addr.ip()).collect(),
+        };
+        let host_str = url.host_str().unwrap_or_default();
+        anyhow::ensure!(
+            self.allows(host_str, port, &addresses),
+            egress_denied(&url.origin().unicode_serialization())
+        );
+        Ok(resolved)
+    }
+
+    /// Like [`EgressPolicy::check`], but never resolves hostnames, for
+    /// requests that are answered without connecting anywhere, such as
+    /// replayed fetch fixtures. CIDR rules only see IP address hosts, so a
+    /// hostname is never denied by one but is allowed by any that covers its
+    /// port.
+    pub fn check_without_resolving(&self, url: &Url) -> anyhow::Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let (Some(host), Some(port)) = (url.host(), url.port_or_known_default()) else {
+            return Ok(());
+        };
+        let host_str = url.host_str().unwrap_or_default();
+        let allowed = match host {
+            Host::Ipv4(addr) => self.allows(host_str, port, &[IpAddr::V4(addr)]),
+            Host::Ipv6(addr) => self.allows(host_str, port, &[IpAddr::V6(addr)]),
+            Host::Domain(_) => self.allows_unresolved(host_str, port),
+        };
+        anyhow::ensure!(
+            allowed,
+            egress_denied(&url.origin().unicode_serialization())
+        );
+        Ok(())
+    }
+
+    /// The policy as sent to a fetch proxy in [`EGRESS_POLICY_PROXY_HEADER`].
+    pub fn to_proxy_header(&self) -> String {
+        let rules = |rules: &[EgressRule]| {
+            rules
+                .iter()
+                .map(|rule| {
+                    json!({
+                        "destination": rule.destination.to_string(),
+                        "ports": rule.ports,
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+        json!({
+            "allow": rules(&self.allow),
+            "deny": rules(&self.deny),
+        })
+        .to_string()
+    }
+
+    fn allows(&self, host: &str, port: u16, addresses: &[IpAddr]) -> bool {
+        let host = normalize_host(host);
+        let denied = self
+            .deny
+            .iter()
+            .any(|rule| rule.matches(&host, port, addresses, false));
+        let allowed = self.allow.is_empty()
+            || self
+                .allow
+                .iter()
+                .any(|rule| rule.matches(&host, port, addresses, true));
+        !denied && allowed
+    }
+
+    fn allows_unresolved(&self, host: &str, port: u16) -> bool {
+        let host = normalize_host(host);
+        let denied = self
+            .deny
+            .iter()
+            .any(|rule| rule.matches(&host, port, &[], false));
+        let allowed = self.allow.is_empty()
+            || self.allow.iter().any(|rule| match rule.destination {
+                EgressDestination::Host(_) => rule.matches(&host, port, &[], true),
+                EgressDestination::Cidr { .. } => {
+                    rule.ports.is_empty() || rule.ports.contains(&port)
+                },
+            });
+        !denied && allowed
+    }
+}
+
+fn egress_denied(destination: &str) -> ErrorMetadata {
+    ErrorMetadata::bad_request(
+        EGRESS_DENIED,
+        format!("Request to {destination} is not allowed by this deployment's egress policy"),
+    )
+}
+
+/// Normalizes a hostname or host pattern so equivalent names compare equal:
+/// lowercase, converted to ASCII with IDNA, and without the trailing dot of a
+/// fully qualified name.
+fn normalize_host(host: &str) -> String {
+    let host = host.strip_suffix('.').unwrap_or(host);
+    match Host::parse(host) {
+        Ok(Host::Domain(domain)) => domain,
+        // Leave IP addresses and anything invalid alone. Invalid hosts can't
+        // match a valid pattern.
+        _ => host.to_ascii_lowercase(),
+    }
+}
+
+/// Only this many hostnames are pinned per client before they're forgotten.
+const MAX_PINNED_HOSTS: usize = 1024;
+
+/// Resolves hostnames for a client that fetches under an egress policy to the
+/// addresses [`EgressPolicy::check`] resolved and checked them to, without
+/// resolving them again. This way a DNS server that answers differently by
+/// the time the client connects can't get around CIDR rules. Hostnames that
+/// weren't checked don't resolve at all.
+#[derive(Default)]
+struct EgressPolicyResolver {
+    pinned: Mutex<BTreeMap<String, Vec<SocketAddr>>>,
+}
+
+impl EgressPolicyResolver {
+    fn pin(&self, host: &str, addresses: Vec<SocketAddr>) {
+        let mut pinned = self.pinned.lock();
+        if pinned.len() >= MAX_PINNED_HOSTS && !pinned.contains_key(host) {
+            pinned.clear();
+        }
+        pinned.insert(host.to_string(), addresses);
+    }
+}
+
+impl Resolve for EgressPolicyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let addresses = self.pinned.lock().get(name.as_str()).cloned();
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addresses = addresses.ok_or_else(|| {
+                anyhow::anyhow!(egress_denied(&format!(
+                    "{host} (not checked against the egress policy)"
+                )))
+            })?;
+            let addrs: Addrs = Box::new(addresses.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+impl EgressRule {
+    fn matches(&self, host: &str, port: u16, addresses: &[IpAddr], all_addresses: bool) -> bool {
+        if !self.ports.is_empty() && !self.ports.contains(&port) {
+            return false;
+        }
+        match &self.destination {
+            EgressDestination::Host(pattern) => {
+                if pattern == "*" {
+                    return true;
+                }
+                match pattern.strip_prefix("*.") {
+                    Some(suffix) => host
+                        .strip_suffix(suffix)
+                        .is_some_and(|subdomain| subdomain.ends_with('.') && subdomain.len() > 1),
+                    None => host.eq_ignore_ascii_case(pattern),
+                }
+            },
+            EgressDestination::Cidr { .. } if addresses.is_empty() => false,
+            EgressDestination::Cidr { .. } if all_addresses => {
+                addresses.iter().all(|addr| self.destination.contains(addr))
+            },
+            EgressDestination::Cidr { .. } => {
+                addresses.iter().any(|addr| self.destination.contains(addr))
+            },
+        }
+    }
+}
+
+impl EgressDestination {
+    fn contains(&self, addr: &IpAddr) -> bool {
+        let Self::Cidr {
+            network,
+            prefix_len,
+        } = self
+        else {
+            return false;
+        };
+        match (network, addr.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(addr)) => {
+                let mask = u32::MAX.checked_shl(32 - *prefix_len as u32).unwrap_or(0);
+                u32::from(*network) & mask == u32::from(addr) & mask
+            },
+            (IpAddr::V6(network), IpAddr::V6(addr)) => {
+                let mask = u128::MAX.checked_shl(128 - *prefix_len as u32).unwrap_or(0);
+                u128::from(*network) & mask == u128::from(addr) & mask
+            },
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for EgressDestination {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let invalid = || {
+            ErrorMetadata::bad_request(
+                "InvalidEgressRule",
+                format!(
+                    "Invalid egress destination {s:?}: expected a host like \"api.example.com\" \
+                     or \"*.example.com\", or a CIDR block like \"10.0.0.0/8\""
+                ),
+            )
+        };
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (s, None),
+        };
+        if let Ok(network) = addr.parse::<IpAddr>() {
+            let max_prefix_len = if network.is_ipv4() { 32 } else { 128 };
+            let prefix_len = match prefix_len {
+                Some(prefix_len) => prefix_len
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|prefix_len| *prefix_len <= max_prefix_len)
+                    .ok_or_else(invalid)?,
+                None => max_prefix_len,
+            };
+            return Ok(Self::Cidr {
+                network,
+                prefix_len,
+            });
+        }
+        anyhow::ensure!(prefix_len.is_none(), invalid());
+        let pattern = match s.strip_prefix("*.") {
+            Some(domain) => format!("*.{}", normalize_host(domain)),
+            None => normalize_host(s),
+        };
+        let domain = pattern.strip_prefix("*.").unwrap_or(&pattern);
+        let valid = pattern == "*"
+            || (!domain.is_empty()
+                && domain.split('.').all(|label| {
+                    !label.is_empty()
+                        && label
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                }));
+        anyhow::ensure!(valid, invalid());
+        Ok(Self::Host(pattern))
+    }
+}
+
+impl fmt::Display for EgressDestination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Host(pattern) => write!(f, "{pattern}"),
+            Self::Cidr {
+                network,
+                prefix_len,
+            } => write!(f, "{network}/{prefix_len}"),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl proptest::arbitrary::Arbitrary for EgressDestination {
+    type Parameters = ();
+
+    type Strategy = impl proptest::strategy::Strategy<Value = EgressDestination>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+        prop_oneof![
+            "(\\*|(\\*\\.)?[a-z0-9-]{1,10}(\\.[a-z0-9-]{1,10}){0,3})"
+                .prop_map(EgressDestination::Host),
+            (any::<std::net::Ipv4Addr>(), 0..=32u8).prop_map(|(addr, prefix_len)| {
+                EgressDestination::Cidr {
+                    network: IpAddr::V4(addr),
+                    prefix_len,
+                }
+            }),
+            (any::<std::net::Ipv6Addr>(), 0..=128u8).prop_map(|(addr, prefix_len)| {
+                EgressDestination::Cidr {
+                    network: IpAddr::V6(addr),
+                    prefix_len,
+                }
+            }),
+        ]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use errors::ErrorMetadataAnyhowExt;
@@ -272,8 +820,16 @@ mod tests {
         Method,
         StatusCode,
     };
+    use reqwest::dns::Resolve;
 
-    use super::ProxiedFetchClient;
+    use super::{
+        EgressDestination,
+        EgressPolicy,
+        EgressPolicyResolver,
+        EgressRule,
+        ProxiedFetchClient,
+        EGRESS_DENIED,
+    };
     use crate::http::{
         categorize_http_response_stream,
         fetch::{
@@ -380,4 +936,223 @@ mod tests {
             "success"
         );
     }
+
+    fn rule(destination: &str, ports: &[u16]) -> anyhow::Result<EgressRule> {
+        Ok(EgressRule {
+            destination: destination.parse()?,
+            ports: ports.iter().copied().collect(),
+        })
+    }
+
+    async fn is_allowed(policy: &EgressPolicy, url: &str) -> anyhow::Result<bool> {
+        match policy.check(&url.parse()?).await {
+            Ok(_) => Ok(true),
+            Err(e) if e.short_msg() == EGRESS_DENIED => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[test]
+    fn test_parse_egress_destination() -> anyhow::Result<()> {
+        for valid in [
+            "*",
+            "*.Example.com",
+            "api.example.com",
+            "10.0.0.0/8",
+            "::1",
+            "fd00::/8",
+        ] {
+            let destination: EgressDestination = valid.parse()?;
+            assert_eq!(
+                destination.to_string().parse::<EgressDestination>()?,
+                destination
+            );
+        }
+        assert_eq!(
+            "10.1.2.3".parse::<EgressDestination>()?.to_string(),
+            "10.1.2.3/32"
+        );
+        // Patterns are normalized like hostnames in URLs.
+        assert_eq!(
+            "*.Example.com.".parse::<EgressDestination>()?.to_string(),
+            "*.example.com"
+        );
+        assert_eq!(
+            "Bücher.example".parse::<EgressDestination>()?.to_string(),
+            "xn--bcher-kva.example"
+        );
+        for invalid in [
+            "",
+            "*.",
+            "a..b",
+            "example.com/8",
+            "10.0.0.0/33",
+            "https://example.com",
+        ] {
+            let err = invalid.parse::<EgressDestination>().unwrap_err();
+            assert_eq!(err.short_msg(), "InvalidEgressRule", "{invalid}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_egress_policy_host_rules() -> anyhow::Result<()> {
+        let policy = EgressPolicy {
+            allow: vec![rule("*.example.com", &[443])?, rule("api.convex.dev", &[])?],
+            deny: vec![rule("internal.example.com", &[])?],
+            log_requests: false,
+        };
+        assert!(is_allowed(&policy, "https://a.example.com/path?q=1").await?);
+        assert!(is_allowed(&policy, "https://b.a.example.com").await?);
+        assert!(is_allowed(&policy, "http://api.convex.dev:8080").await?);
+        // Subdomain patterns don't match the domain itself.
+        assert!(!is_allowed(&policy, "https://example.com").await?);
+        assert!(!is_allowed(&policy, "https://notexample.com").await?);
+        assert!(!is_allowed(&policy, "http://a.example.com").await?);
+        assert!(!is_allowed(&policy, "https://internal.example.com").await?);
+        assert!(!is_allowed(&policy, "https://google.com").await?);
+
+        // Without allow rules, anything not denied is allowed.
+        let policy = EgressPolicy {
+            allow: vec![],
+            deny: vec![rule("*", &[25])?],
+            log_requests: false,
+        };
+        assert!(is_allowed(&policy, "https://google.com").await?);
+        assert!(!is_allowed(&policy, "http://mail.google.com:25").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_egress_policy_normalizes_hosts() -> anyhow::Result<()> {
+        let policy = EgressPolicy {
+            allow: vec![],
+            deny: vec![
+                rule("internal.example.com", &[])?,
+                rule("*.corp.example.com", &[])?,
+                rule("bücher.example", &[])?,
+            ],
+            log_requests: false,
+        };
+        // Fully qualified names with a trailing dot are the same host.
+        assert!(!is_allowed(&policy, "https://internal.example.com./").await?);
+        assert!(!is_allowed(&policy, "https://a.corp.example.com.").await?);
+        // So are names that differ in case or in how they're encoded.
+        assert!(!is_allowed(&policy, "https://INTERNAL.Example.COM").await?);
+        assert!(!is_allowed(&policy, "https://BÜCHER.example").await?);
+        assert!(!is_allowed(&policy, "https://xn--bcher-kva.example").await?);
+        assert!(is_allowed(&policy, "https://external.example.com.").await?);
+
+        let policy = EgressPolicy {
+            allow: vec![rule("*.example.com", &[])?],
+            deny: vec![],
+            log_requests: false,
+        };
+        assert!(is_allowed(&policy, "https://api.example.com.").await?);
+        assert!(!is_allowed(&policy, "https://api.example.com.evil.com").await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_egress_policy_checks_connected_addresses() -> anyhow::Result<()> {
+        let policy = EgressPolicy {
+            allow: vec![],
+            deny: vec![rule("10.0.0.0/8", &[])?],
+            log_requests: false,
+        };
+        // A hostname that resolved to a public address when the request was
+        // checked could resolve to a private one when the client connects, so
+        // the client connects to the addresses that were checked instead.
+        let addresses = policy.check(&"http://localhost:8080".parse()?).await?;
+        assert!(!addresses.is_empty());
+        for address in &addresses {
+            assert!(address.ip().is_loopback(), "{address}");
+            assert_eq!(address.port(), 8080);
+        }
+        // IP address hosts aren't resolved, so there's nothing to pin.
+        assert!(policy
+            .check(&"http://127.0.0.1:8080".parse()?)
+            .await?
+            .is_empty());
+        let resolver = EgressPolicyResolver::default();
+        resolver.pin("localhost", addresses.clone());
+        let resolved: Vec<_> = resolver
+            .resolve("localhost".parse().unwrap())
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?
+            .collect();
+        assert_eq!(resolved, addresses);
+
+        // Hostnames that weren't checked don't resolve.
+        let Err(err) = resolver.resolve("rebind.example.com".parse().unwrap()).await else {
+            panic!("Resolved an unchecked hostname");
+        };
+        assert!(
+            err.to_string()
+                .contains("not allowed by this deployment's egress policy"),
+            "{err}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_egress_policy_dns_failure_is_user_error() -> anyhow::Result<()> {
+        let policy = EgressPolicy {
+            allow: vec![],
+            deny: vec![rule("10.0.0.0/8", &[])?],
+            log_requests: false,
+        };
+        let err = policy
+            .check(&"https://does-not-exist.invalid".parse()?)
+            .await
+            .unwrap_err();
+        assert!(err.is_bad_request());
+        assert_eq!(err.short_msg(), "FetchDnsLookupFailed");
+        Ok(())
+    }
+
+    #[test]
+    fn test_egress_policy_proxy_header() -> anyhow::Result<()> {
+        let policy = EgressPolicy {
+            allow: vec![rule("*.example.com", &[443])?],
+            deny: vec![rule("10.0.0.0/8", &[])?],
+            log_requests: true,
+        };
+        let header: serde_json::Value = serde_json::from_str(&policy.to_proxy_header())?;
+        assert_eq!(
+            header,
+            serde_json::json!({
+                "allow": [{ "destination": "*.example.com", "ports": [443] }],
+                "deny": [{ "destination": "10.0.0.0/8", "ports": [] }],
+            })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_egress_policy_cidr_rules() -> anyhow::Result<()> {
+        let policy = EgressPolicy {
+            allow: vec![],
+            deny: vec![rule("10.0.0.0/8", &[])?, rule("fd00::/8", &[])?],
+            log_requests: false,
+        };
+        assert!(!is_allowed(&policy, "http://10.1.2.3").await?);
+        assert!(!is_allowed(&policy, "http://[fd12::1]:8080").await?);
+        // IPv4-mapped IPv6 addresses are checked as IPv4.
+        assert!(!is_allowed(&policy, "http://[::ffff:10.0.0.1]").await?);
+        assert!(is_allowed(&policy, "http://11.0.0.1").await?);
+        assert!(is_allowed(&policy, "http://[fe80::1]").await?);
+
+        let policy = EgressPolicy {
+            allow: vec![rule("192.168.1.0/24", &[443])?],
+            deny: vec![],
+            log_requests: false,
+        };
+        assert!(is_allowed(&policy, "https://192.168.1.20").await?);
+        assert!(!is_allowed(&policy, "http://192.168.1.20").await?);
+        assert!(!is_allowed(&policy, "https://192.168.2.20").await?);
+        // Resolves to 127.0.0.1 without any DNS lookups over the network.
+        assert!(!is_allowed(&policy, "https://localhost").await?);
+        Ok(())
+    }
 }
@@ -0,0 +1,797 @@
+//! A record/replay store for outbound `fetch` requests, so integration tests
+//! against a local backend don't depend on third-party APIs.
+//!
+//! In record mode, requests go to the network and each request/response pair
+//! is appended to a cassette. In replay mode, requests are served from the
+//! cassette and never reach the network. Requests without a recorded response
+//! fail and are kept so the test harness can report them.
+//!
+//! Cassette files have one JSON interaction per line, so recording appends
+//! to them instead of rewriting them. Request headers are recorded and
+//! matched on, except for ones that usually carry credentials.
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{
+        Path,
+        PathBuf,
+    },
+    str::FromStr,
+    sync::Arc,
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use bytes::Bytes;
+use errors::ErrorMetadata;
+use futures::{
+    stream,
+    TryStreamExt,
+};
+use http::{
+    HeaderMap,
+    HeaderName,
+    HeaderValue,
+    StatusCode,
+};
+use parking_lot::Mutex;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use tokio::io::AsyncWriteExt;
+
+use crate::http::{
+    fetch::{
+        EgressPolicy,
+        FetchClient,
+        InternalFetchPurpose,
+    },
+    HttpRequestStream,
+    HttpResponse,
+    HttpResponseStream,
+};
+
+pub const FETCH_FIXTURE_NOT_FOUND: &str = "FetchFixtureNotFound";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FetchFixtureMode {
+    #[default]
+    Off,
+    Record,
+    Replay,
+}
+
+impl FromStr for FetchFixtureMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "off" => Ok(Self::Off),
+            "record" => Ok(Self::Record),
+            "replay" => Ok(Self::Replay),
+            _ => anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidFetchFixtureMode",
+                format!("Invalid fetch fixture mode {s:?}: expected off, record or replay"),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for FetchFixtureMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Record => write!(f, "record"),
+            Self::Replay => write!(f, "replay"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    /// Lowercase names, sorted, without [`REDACTED_HEADERS`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    #[serde(flatten)]
+    pub body: RecordedBody,
+}
+
+/// Request headers that are never recorded or matched on since they usually
+/// carry credentials, along with any header whose name mentions a key, token
+/// or secret.
+const REDACTED_HEADERS: &[&str] = &["authorization", "cookie", "proxy-authorization"];
+
+impl RecordedRequest {
+    fn new(request: &HttpRequestStream, body: &[u8]) -> Self {
+        let mut headers: Vec<_> = request
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+            .filter(|(name, _)| {
+                !REDACTED_HEADERS.contains(name)
+                    && !["key", "token", "secret"]
+                        .iter()
+                        .any(|word| name.contains(word))
+            })
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+        headers.sort();
+        Self {
+            method: request.method.to_string(),
+            url: request.url.to_string(),
+            headers,
+            body: RecordedBody::new(body),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    #[serde(flatten)]
+    pub body: RecordedBody,
+}
+
+/// Bodies are stored as text when they're UTF-8 so cassettes are easy to read
+/// and edit by hand, and as base64 otherwise.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedBody {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_base64: Option<String>,
+}
+
+impl RecordedBody {
+    fn new(bytes: &[u8]) -> Self {
+        if bytes.is_empty() {
+            return Self::default();
+        }
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Self {
+                body: Some(text.to_string()),
+                body_base64: None,
+            },
+            Err(_) => Self {
+                body: None,
+                body_base64: Some(base64::encode(bytes)),
+            },
+        }
+    }
+
+    fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        Ok(match (&self.body, &self.body_base64) {
+            (Some(text), _) => text.as_bytes().to_vec(),
+            (None, Some(encoded)) => base64::decode(encoded)?,
+            (None, None) => vec![],
+        })
+    }
+}
+
+/// Snapshot of the store for reporting back to tests.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchFixturesStatus {
+    pub mode: FetchFixtureMode,
+    pub cassette: Cassette,
+    pub unmatched: Vec<RecordedRequest>,
+}
+
+struct FixtureState {
+    mode: FetchFixtureMode,
+    cassette: Cassette,
+    /// Whether the next recorded interaction starts a new cassette file
+    /// instead of being appended to it.
+    truncate_file: bool,
+    /// How many times each request has been replayed. Repeated requests are
+    /// served the recorded responses in order, and then the last one again.
+    replayed: BTreeMap<RecordedRequest, usize>,
+    unmatched: Vec<RecordedRequest>,
+}
+
+/// A `FetchClient` that records or replays requests sent through `inner`.
+/// Internal fetches always go to `inner` directly.
+pub struct FetchFixtureStore {
+    inner: Arc<dyn FetchClient>,
+    path: Option<PathBuf>,
+    state: Mutex<FixtureState>,
+    /// Held while appending to `path`, so interactions are written one at a
+    /// time.
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl FetchFixtureStore {
+    pub fn new(
+        inner: Arc<dyn FetchClient>,
+        mode: FetchFixtureMode,
+        path: Option<PathBuf>,
+    ) -> anyhow::Result<Self> {
+        let cassette = match (&path, mode) {
+            (Some(path), FetchFixtureMode::Replay) => {
+                let contents = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read fetch fixtures from {path:?}"))?;
+                let interactions = contents
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(serde_json::from_str)
+                    .collect::<Result<_, _>>()
+                    .with_context(|| format!("Invalid fetch fixtures in {path:?}"))?;
+                Cassette { interactions }
+            },
+            _ => Cassette::default(),
+        };
+        Ok(Self {
+            inner,
+            path,
+            state: Mutex::new(FixtureState {
+                mode,
+                cassette,
+                truncate_file: true,
+                replayed: BTreeMap::new(),
+                unmatched: vec![],
+            }),
+            write_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Switches modes, replacing the cassette and clearing unmatched requests.
+    pub fn set(&self, mode: FetchFixtureMode, cassette: Cassette) {
+        *self.state.lock() = FixtureState {
+            mode,
+            cassette,
+            truncate_file: true,
+            replayed: BTreeMap::new(),
+            unmatched: vec![],
+        };
+    }
+
+    pub fn status(&self) -> FetchFixturesStatus {
+        let state = self.state.lock();
+        FetchFixturesStatus {
+            mode: state.mode,
+            cassette: state.cassette.clone(),
+            unmatched: state.unmatched.clone(),
+        }
+    }
+
+    fn mode(&self) -> FetchFixtureMode {
+        self.state.lock().mode
+    }
+
+    async fn record(
+        &self,
+        mut request: HttpRequestStream,
+        egress_policy: &EgressPolicy,
+    ) -> anyhow::Result<HttpResponseStream> {
+        let body = read_body(&mut request).await?;
+        let recorded_request = RecordedRequest::new(&request, &body);
+        let response = self
+            .inner
+            .fetch_with_egress_policy(with_body(request, body), egress_policy)
+            .await?
+            .into_http_response()
+            .await?;
+        let recorded_response = RecordedResponse {
+            status: response.status.as_u16(),
+            headers: response
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: RecordedBody::new(response.body.as_deref().unwrap_or_default()),
+        };
+        let interaction = Interaction {
+            request: recorded_request,
+            response: recorded_response,
+        };
+        // Append each interaction as it's recorded so the file is complete
+        // even if the backend is killed at the end of a test run.
+        if let Some(path) = &self.path {
+            self.append_to_file(path, &interaction).await?;
+        }
+        self.state.lock().cassette.interactions.push(interaction);
+        Ok(response.into())
+    }
+
+    /// Appends one interaction to the cassette file at `path` as a line of
+    /// JSON, starting a new file for the first one after switching modes.
+    async fn append_to_file(&self, path: &Path, interaction: &Interaction) -> anyhow::Result<()> {
+        let mut line = serde_json::to_vec(interaction)?;
+        line.push(b'\n');
+        let _write_guard = self.write_lock.lock().await;
+        let truncate = std::mem::take(&mut self.state.lock().truncate_file);
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(!truncate)
+            .truncate(truncate)
+            .open(path)
+            .await
+            .with_context(|| format!("Failed to open fetch fixtures file {path:?}"))?;
+        file.write_all(&line)
+            .await
+            .with_context(|| format!("Failed to write fetch fixtures to {path:?}"))?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    async fn replay(&self, mut request: HttpRequestStream) -> anyhow::Result<HttpResponseStream> {
+        let body = read_body(&mut request).await?;
+        let recorded_request = RecordedRequest::new(&request, &body);
+        let recorded_response = {
+            let mut state = self.state.lock();
+            let matches: Vec<_> = state
+                .cassette
+                .interactions
+                .iter()
+                .filter(|interaction| interaction.request == recorded_request)
+                .map(|interaction| interaction.response.clone())
+                .collect();
+            if matches.is_empty() {
+                tracing::warn!(
+                    "No fetch fixture for {} {}",
+                    recorded_request.method,
+                    recorded_request.url
+                );
+                state.unmatched.push(recorded_request.clone());
+                anyhow::bail!(ErrorMetadata::bad_request(
+                    FETCH_FIXTURE_NOT_FOUND,
+                    format!(
+                        "No recorded response for {} {}",
+                        recorded_request.method, recorded_request.url
+                    ),
+                ));
+            }
+            let replayed = state.replayed.entry(recorded_request).or_default();
+            let response = matches[(*replayed).min(matches.len() - 1)].clone();
+            *replayed += 1;
+            response
+        };
+        let mut headers = HeaderMap::new();
+        for (name, value) in &recorded_response.headers {
+            headers.append(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+        Ok(HttpResponse::new(
+            StatusCode::from_u16(recorded_response.status)?,
+            headers,
+            Some(recorded_response.body.to_bytes()?),
+            Some(request.url),
+        )
+        .into())
+    }
+}
+
+#[async_trait]
+impl FetchClient for FetchFixtureStore {
+    async fn fetch(&self, request: HttpRequestStream) -> anyhow::Result<HttpResponseStream> {
+        match self.mode() {
+            FetchFixtureMode::Off => self.inner.fetch(request).await,
+            FetchFixtureMode::Record => self.record(request, &EgressPolicy::default()).await,
+            FetchFixtureMode::Replay => self.replay(request).await,
+        }
+    }
+
+    async fn fetch_with_egress_policy(
+        &self,
+        request: HttpRequestStream,
+        egress_policy: &EgressPolicy,
+    ) -> anyhow::Result<HttpResponseStream> {
+        match self.mode() {
+            FetchFixtureMode::Off => {
+                self.inner
+                    .fetch_with_egress_policy(request, egress_policy)
+                    .await
+            },
+            FetchFixtureMode::Record => self.record(request, egress_policy).await,
+            FetchFixtureMode::Replay => {
+                // Replays never connect anywhere, so there's nothing to
+                // resolve hostnames for.
+                egress_policy.check_without_resolving(&request.url)?;
+                self.replay(request).await
+            },
+        }
+    }
+
+    async fn internal_fetch(
+        &self,
+        request: HttpRequestStream,
+        purpose: InternalFetchPurpose,
+    ) -> anyhow::Result<HttpResponseStream> {
+        self.inner.internal_fetch(request, purpose).await
+    }
+}
+
+async fn read_body(request: &mut HttpRequestStream) -> anyhow::Result<Vec<u8>> {
+    let mut body = vec![];
+    while let Some(chunk) = request.body.try_next().await? {
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Puts a body that was read with `read_body` back, keeping the request's
+/// abort signal.
+fn with_body(mut request: HttpRequestStream, body: Vec<u8>) -> HttpRequestStream {
+    request.body = Box::pin(stream::once(async move {
+        Ok::<_, anyhow::Error>(Bytes::from(body))
+    }));
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeSet,
+        sync::Arc,
+    };
+
+    use errors::ErrorMetadataAnyhowExt;
+    use futures::FutureExt;
+    use http::{
+        HeaderMap,
+        Method,
+        StatusCode,
+    };
+
+    use super::{
+        FetchFixtureMode,
+        FetchFixtureStore,
+        FETCH_FIXTURE_NOT_FOUND,
+    };
+    use crate::http::{
+        fetch::{
+            EgressPolicy,
+            EgressRule,
+            FetchClient,
+            StaticFetchClient,
+            EGRESS_DENIED,
+        },
+        HttpRequest,
+        HttpRequestStream,
+        HttpResponse,
+        HttpResponseStream,
+    };
+
+    async fn fetch(
+        client: &FetchFixtureStore,
+        method: Method,
+        body: Option<&str>,
+    ) -> anyhow::Result<(StatusCode, String)> {
+        fetch_with_headers(client, method, body, HeaderMap::new()).await
+    }
+
+    async fn fetch_with_headers(
+        client: &FetchFixtureStore,
+        method: Method,
+        body: Option<&str>,
+        headers: HeaderMap,
+    ) -> anyhow::Result<(StatusCode, String)> {
+        let response = client
+            .fetch(
+                HttpRequest {
+                    headers,
+                    url: "https://api.example.com/items".parse()?,
+                    method,
+                    body: body.map(|b| b.to_string().into()),
+                }
+                .into(),
+            )
+            .await?
+            .into_http_response()
+            .await?;
+        Ok((
+            response.status,
+            String::from_utf8(response.body.unwrap_or_default())?,
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() -> anyhow::Result<()> {
+        let url: url::Url = "https://api.example.com/items".parse()?;
+        let mut upstream = StaticFetchClient::new();
+        upstream.register_http_route(url.clone(), Method::GET, |_: HttpRequestStream| {
+            async {
+                Ok(HttpResponseStream::from(HttpResponse::new(
+                    StatusCode::OK,
+                    HeaderMap::new(),
+                    Some(b"[1, 2]".to_vec()),
+                    None,
+                )))
+            }
+            .boxed()
+        });
+        upstream.register_http_route(url, Method::POST, |request: HttpRequestStream| {
+            async {
+                let request = request.into_http_request().await?;
+                Ok(HttpResponseStream::from(HttpResponse::new(
+                    StatusCode::CREATED,
+                    HeaderMap::new(),
+                    request.body.map(|b| b.to_vec()),
+                    None,
+                )))
+            }
+            .boxed()
+        });
+        let upstream = Arc::new(upstream);
+
+        let recorder = FetchFixtureStore::new(upstream.clone(), FetchFixtureMode::Record, None)?;
+        assert_eq!(
+            fetch(&recorder, Method::GET, None).await?,
+            (StatusCode::OK, "[1, 2]".to_string())
+        );
+        assert_eq!(
+            fetch(&recorder, Method::POST, Some("3")).await?,
+            (StatusCode::CREATED, "3".to_string())
+        );
+        let cassette = recorder.status().cassette;
+        assert_eq!(cassette.interactions.len(), 2);
+        assert_eq!(upstream.num_calls(), 2);
+
+        let replayer = FetchFixtureStore::new(upstream.clone(), FetchFixtureMode::Off, None)?;
+        replayer.set(FetchFixtureMode::Replay, cassette);
+        assert_eq!(
+            fetch(&replayer, Method::POST, Some("3")).await?,
+            (StatusCode::CREATED, "3".to_string())
+        );
+        assert_eq!(
+            fetch(&replayer, Method::GET, None).await?,
+            (StatusCode::OK, "[1, 2]".to_string())
+        );
+        // Replays never reach the network.
+        assert_eq!(upstream.num_calls(), 2);
+
+        // A different body doesn't match, and is reported.
+        let err = fetch(&replayer, Method::POST, Some("4")).await.unwrap_err();
+        assert_eq!(err.short_msg(), FETCH_FIXTURE_NOT_FOUND);
+        let unmatched = replayer.status().unmatched;
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].method, "POST");
+        assert_eq!(unmatched[0].body.body.as_deref(), Some("4"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_replay_matches_headers() -> anyhow::Result<()> {
+        let url: url::Url = "https://api.example.com/items".parse()?;
+        let mut upstream = StaticFetchClient::new();
+        upstream.register_http_route(url, Method::GET, |request: HttpRequestStream| {
+            async move {
+                let accept = request
+                    .headers
+                    .get("accept")
+                    .map(|v| v.to_str().unwrap().to_string())
+                    .unwrap_or_default();
+                Ok(HttpResponseStream::from(HttpResponse::new(
+                    StatusCode::OK,
+                    HeaderMap::new(),
+                    Some(accept.into_bytes()),
+                    None,
+                )))
+            }
+            .boxed()
+        });
+        let upstream = Arc::new(upstream);
+        let headers = |accept: &str, token: &str| -> anyhow::Result<HeaderMap> {
+            let mut headers = HeaderMap::new();
+            headers.insert("accept", accept.parse()?);
+            headers.insert("authorization", token.parse()?);
+            headers.insert("x-api-key", token.parse()?);
+            Ok(headers)
+        };
+
+        let recorder = FetchFixtureStore::new(upstream.clone(), FetchFixtureMode::Record, None)?;
+        for accept in ["text/plain", "application/json"] {
+            fetch_with_headers(&recorder, Method::GET, None, headers(accept, "secret1")?).await?;
+        }
+        let cassette = recorder.status().cassette;
+        // Credentials aren't recorded.
+        assert_eq!(
+            cassette.interactions[0].request.headers,
+            vec![("accept".to_string(), "text/plain".to_string())]
+        );
+
+        let replayer = FetchFixtureStore::new(upstream, FetchFixtureMode::Off, None)?;
+        replayer.set(FetchFixtureMode::Replay, cassette);
+        // Requests that only differ in their headers get their own responses,
+        // and credentials don't need to match.
+        assert_eq!(
+            fetch_with_headers(
+                &replayer,
+                Method::GET,
+                None,
+                headers("application/json", "secret2")?
+            )
+            .await?,
+            (StatusCode::OK, "application/json".to_string())
+        );
+        assert_eq!(
+            fetch_with_headers(
+                &replayer,
+                Method::GET,
+                None,
+                headers("text/plain", "secret2")?
+            )
+            .await?,
+            (StatusCode::OK, "text/plain".to_string())
+        );
+        let err = fetch_with_headers(&replayer, Method::GET, None, headers("text/html", "")?)
+            .await
+            .unwrap_err();
+        assert_eq!(err.short_msg(), FETCH_FIXTURE_NOT_FOUND);
+        Ok(())
+    }
+
+    async fn fetch_with_egress_policy(
+        client: &FetchFixtureStore,
+        url: &str,
+        egress_policy: &EgressPolicy,
+    ) -> anyhow::Result<StatusCode> {
+        let response = client
+            .fetch_with_egress_policy(
+                HttpRequest {
+                    headers: HeaderMap::new(),
+                    url: url.parse()?,
+                    method: Method::GET,
+                    body: None,
+                }
+                .into(),
+                egress_policy,
+            )
+            .await?;
+        Ok(response.status)
+    }
+
+    #[tokio::test]
+    async fn test_replay_checks_egress_policy_without_resolving() -> anyhow::Result<()> {
+        let url: url::Url = "https://api.example.com/items".parse()?;
+        let mut upstream = StaticFetchClient::new();
+        upstream.register_http_route(url, Method::GET, |_: HttpRequestStream| {
+            async {
+                Ok(HttpResponseStream::from(HttpResponse::new(
+                    StatusCode::OK,
+                    HeaderMap::new(),
+                    Some(b"[1, 2]".to_vec()),
+                    None,
+                )))
+            }
+            .boxed()
+        });
+        let upstream = Arc::new(upstream);
+        let recorder = FetchFixtureStore::new(upstream.clone(), FetchFixtureMode::Record, None)?;
+        fetch(&recorder, Method::GET, None).await?;
+        let replayer = FetchFixtureStore::new(upstream, FetchFixtureMode::Off, None)?;
+        replayer.set(FetchFixtureMode::Replay, recorder.status().cassette);
+
+        let rule = |destination: &str| -> anyhow::Result<EgressRule> {
+            Ok(EgressRule {
+                destination: destination.parse()?,
+                ports: BTreeSet::new(),
+            })
+        };
+        let policy = EgressPolicy {
+            allow: vec![rule("93.184.0.0/16")?, rule("10.0.0.0/8")?],
+            deny: vec![rule("10.1.0.0/16")?],
+            log_requests: false,
+        };
+        // The hostname isn't resolved, so CIDR rules can't deny it.
+        assert_eq!(
+            fetch_with_egress_policy(&replayer, "https://api.example.com/items", &policy).await?,
+            StatusCode::OK
+        );
+        // IP addresses are still checked against them.
+        let err = fetch_with_egress_policy(&replayer, "https://10.1.0.1/items", &policy)
+            .await
+            .unwrap_err();
+        assert_eq!(err.short_msg(), EGRESS_DENIED);
+
+        // Host rules apply as usual.
+        let policy = EgressPolicy {
+            allow: vec![],
+            deny: vec![rule("api.example.com")?],
+            log_requests: false,
+        };
+        let err = fetch_with_egress_policy(&replayer, "https://api.example.com/items", &policy)
+            .await
+            .unwrap_err();
+        assert_eq!(err.short_msg(), EGRESS_DENIED);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_record_to_file() -> anyhow::Result<()> {
+        let url: url::Url = "https://api.example.com/items".parse()?;
+        let mut upstream = StaticFetchClient::new();
+        upstream.register_http_route(url, Method::GET, |_: HttpRequestStream| {
+            async {
+                Ok(HttpResponseStream::from(HttpResponse::new(
+                    StatusCode::OK,
+                    HeaderMap::new(),
+                    Some(b"[1, 2]".to_vec()),
+                    None,
+                )))
+            }
+            .boxed()
+        });
+        let upstream = Arc::new(upstream);
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("cassette.jsonl");
+
+        let recorder = FetchFixtureStore::new(
+            upstream.clone(),
+            FetchFixtureMode::Record,
+            Some(path.clone()),
+        )?;
+        futures::future::try_join_all((0..4).map(|_| fetch(&recorder, Method::GET, None))).await?;
+
+        // Every interaction made it to the file, one per line.
+        let contents = tokio::fs::read_to_string(&path).await?;
+        assert_eq!(contents.lines().count(), 4);
+        let replayer = FetchFixtureStore::new(
+            upstream.clone(),
+            FetchFixtureMode::Replay,
+            Some(path.clone()),
+        )?;
+        assert_eq!(replayer.status().cassette.interactions.len(), 4);
+
+        // Recording again starts a new file.
+        let recorder =
+            FetchFixtureStore::new(upstream, FetchFixtureMode::Record, Some(path.clone()))?;
+        fetch(&recorder, Method::GET, None).await?;
+        let contents = tokio::fs::read_to_string(&path).await?;
+        assert_eq!(contents.lines().count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cassette_format() -> anyhow::Result<()> {
+        let cassette: super::Cassette = serde_json::from_value(serde_json::json!({
+            "interactions": [{
+                "request": { "method": "GET", "url": "https://api.example.com/" },
+                "response": {
+                    "status": 200,
+                    "headers": [["content-type", "application/octet-stream"]],
+                    "bodyBase64": "AAEC",
+                },
+            }],
+        }))?;
+        let response = &cassette.interactions[0].response;
+        assert_eq!(response.body.to_bytes()?, vec![0, 1, 2]);
+        assert_eq!(
+            serde_json::to_value(&cassette.interactions[0].request)?,
+            serde_json::json!({ "method": "GET", "url": "https://api.example.com/" })
+        );
+        Ok(())
+    }
+}
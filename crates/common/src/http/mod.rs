@@ -119,6 +119,7 @@ use crate::{
 
 pub mod extract;
 pub mod fetch;
+pub mod fetch_fixtures;
 pub mod fork_of_axum_serve;
 
 const MAX_HTTP2_STREAMS: u32 = 1024;
@@ -243,24 +243,41 @@ impl TryFrom<JsonQuerySource> for QuerySource {
                     order: try_order_from_string(json_index_range.order)?,
                 })
             },
-            JsonQuerySource::Search(json_search) => {
-                let filter_expressions: Vec<SearchFilterExpression> = json_search
-                    .filters
-                    .into_iter()
-                    .map(|json_filter_expression| json_filter_expression.try_into())
-                    .collect::<anyhow::Result<Vec<_>>>()?;
+            JsonQuerySource::Search(json_search) => QuerySource::Search(json_search.try_into()?),
+        })
+    }
+}
 
-                let index_name = IndexName::from_str(&json_search.index_name)?;
-                QuerySource::Search(Search {
-                    table: index_name.table().clone(),
-                    index_name,
-                    filters: filter_expressions,
-                })
-            },
+impl TryFrom<JsonSearch> for Search {
+    type Error = anyhow::Error;
+
+    fn try_from(json_search: JsonSearch) -> Result<Self> {
+        let filter_expressions: Vec<SearchFilterExpression> = json_search
+            .filters
+            .into_iter()
+            .map(|json_filter_expression| json_filter_expression.try_into())
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let index_name = IndexName::from_str(&json_search.index_name)?;
+        Ok(Search {
+            table: index_name.table().clone(),
+            index_name,
+            filters: filter_expressions,
         })
     }
 }
 
+/// Parses a search index query without any operators, e.g. the text half of a
+/// hybrid search.
+impl TryFrom<JsonValue> for Search {
+    type Error = anyhow::Error;
+
+    fn try_from(value: JsonValue) -> Result<Self> {
+        let json_search: JsonSearch = serde_json::from_value(value)?;
+        json_search.try_into()
+    }
+}
+
 impl From<QuerySource> for JsonQuerySource {
     fn from(query_source: QuerySource) -> Self {
         match query_source {
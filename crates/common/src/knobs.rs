@@ -17,13 +17,17 @@ use std::{
         NonZeroU32,
         NonZeroUsize,
     },
+    path::PathBuf,
     sync::LazyLock,
     time::Duration,
 };
 
 use cmd_util::env::env_config;
 
-use crate::fastrace_helpers::SamplingConfig;
+use crate::{
+    fastrace_helpers::SamplingConfig,
+    http::fetch_fixtures::FetchFixtureMode,
+};
 
 /// This exists solely to allow knobs to have separate defaults for local
 /// execution and prod (running in Nomad). Don't export this outside of
@@ -116,6 +120,14 @@ pub static HEAP_WORKER_REPORT_INTERVAL_SECONDS: LazyLock<Duration> =
 pub static ACTION_USER_TIMEOUT: LazyLock<Duration> =
     LazyLock::new(|| Duration::from_secs(env_config("ACTIONS_USER_TIMEOUT_SECS", 600)));
 
+/// The longest timeout an action can set in its `limits` config.
+pub static ACTION_MAX_USER_TIMEOUT: LazyLock<Duration> = LazyLock::new(|| {
+    Duration::from_secs(env_config(
+        "ACTIONS_MAX_USER_TIMEOUT_SECS",
+        ACTION_USER_TIMEOUT.as_secs(),
+    ))
+});
+
 /// Max number of rows we will read when calculating document deltas.
 pub static DOCUMENT_DELTAS_LIMIT: LazyLock<usize> =
     LazyLock::new(|| env_config("DOCUMENT_DELTAS_LIMIT", 128));
@@ -201,6 +213,25 @@ pub static TRANSACTION_MAX_USER_WRITE_SIZE_BYTES: LazyLock<usize> = LazyLock::ne
     env_config("TRANSACTION_MAX_USER_WRITE_SIZE_BYTES", 1 << 24) // 16 MiB
 });
 
+/// The most user writes a function can allow itself in its `limits` config.
+/// Make sure to also increase `MAX_INSERT_SIZE` in mysql/src/lib.rs and
+/// postgres/src/lib.rs.
+pub static FUNCTION_MAX_NUM_USER_WRITES: LazyLock<usize> = LazyLock::new(|| {
+    env_config(
+        "FUNCTION_MAX_NUM_USER_WRITES",
+        *TRANSACTION_MAX_NUM_USER_WRITES,
+    )
+});
+
+/// The most bytes of user writes a function can allow itself in its `limits`
+/// config.
+pub static FUNCTION_MAX_USER_WRITE_SIZE_BYTES: LazyLock<usize> = LazyLock::new(|| {
+    env_config(
+        "FUNCTION_MAX_USER_WRITE_SIZE_BYTES",
+        *TRANSACTION_MAX_USER_WRITE_SIZE_BYTES,
+    )
+});
+
 /// SnapshotManager maintains a bounded time range of versions,
 /// determined by `MAX_TRANSACTION_WINDOW`, allowing the `Database` layer to
 /// begin a transaction in any timestamp within that range.
@@ -262,6 +293,12 @@ pub static TRANSACTION_MAX_SCHEDULED_TOTAL_ARGUMENT_SIZE_BYTES: LazyLock<usize>
 pub static SCHEDULED_JOB_EXECUTION_PARALLELISM: LazyLock<usize> =
     LazyLock::new(|| env_config("SCHEDULED_JOB_EXECUTION_PARALLELISM", 10));
 
+/// How long a job from a lower priority scheduler queue can be ready to run
+/// before it goes ahead of jobs from higher priority queues.
+pub static SCHEDULED_JOB_QUEUE_STARVATION_TIMEOUT: LazyLock<Duration> = LazyLock::new(|| {
+    Duration::from_secs(env_config("SCHEDULED_JOB_QUEUE_STARVATION_TIMEOUT_SECS", 60))
+});
+
 /// Initial backoff in milliseconds on a system error from a scheduled job.
 pub static SCHEDULED_JOB_INITIAL_BACKOFF: LazyLock<Duration> =
     LazyLock::new(|| Duration::from_millis(env_config("SCHEDULED_JOB_INITIAL_BACKOFF_MS", 500)));
@@ -337,6 +374,22 @@ pub static TRANSACTION_MAX_READ_SIZE_BYTES: LazyLock<usize> = LazyLock::new(|| {
     env_config("TRANSACTION_MAX_READ_SIZE_BYTES", 1 << 24) // 16 MiB
 });
 
+/// The most rows a function can allow itself to read in its `limits` config.
+pub static FUNCTION_MAX_READ_SIZE_ROWS: LazyLock<usize> = LazyLock::new(|| {
+    env_config(
+        "FUNCTION_MAX_READ_SIZE_ROWS",
+        *TRANSACTION_MAX_READ_SIZE_ROWS,
+    )
+});
+
+/// The most bytes a function can allow itself to read in its `limits` config.
+pub static FUNCTION_MAX_READ_SIZE_BYTES: LazyLock<usize> = LazyLock::new(|| {
+    env_config(
+        "FUNCTION_MAX_READ_SIZE_BYTES",
+        *TRANSACTION_MAX_READ_SIZE_BYTES,
+    )
+});
+
 /// Maximum number of intervals that can be read in a transaction.
 pub static TRANSACTION_MAX_READ_SET_INTERVALS: LazyLock<usize> =
     LazyLock::new(|| env_config("TRANSACTION_MAX_READ_SET_INTERVALS", 4096));
@@ -619,6 +672,12 @@ pub static VECTOR_INDEX_SIZE_SOFT_LIMIT: LazyLock<usize> =
 pub static VECTOR_INDEX_THREADS: LazyLock<usize> =
     LazyLock::new(|| env_config("VECTOR_INDEX_THREADS", 4));
 
+/// Fraction of searches against quantized vector segments that also run an
+/// exact search to measure recall. The exact search is expensive, so keep this
+/// low.
+pub static VECTOR_QUANTIZATION_RECALL_SAMPLE_RATE: LazyLock<f64> =
+    LazyLock::new(|| env_config("VECTOR_QUANTIZATION_RECALL_SAMPLE_RATE", 0.001));
+
 /// Configures the vector and search index workers' rate limit on pages
 /// processed per second. This is the default rate limit for anything a user
 /// might be waiting on. It's initialized high enough that it effectively does
@@ -658,6 +717,15 @@ pub static VECTOR_INDEX_WORKER_PAGE_SIZE: LazyLock<usize> =
 pub static DATABASE_UDF_USER_TIMEOUT: LazyLock<Duration> =
     LazyLock::new(|| Duration::from_secs(env_config("DATABASE_UDF_USER_TIMEOUT_SECONDS", 1)));
 
+/// The longest user timeout a query or mutation can set in its `limits`
+/// config.
+pub static DATABASE_UDF_MAX_USER_TIMEOUT: LazyLock<Duration> = LazyLock::new(|| {
+    Duration::from_secs(env_config(
+        "DATABASE_UDF_MAX_USER_TIMEOUT_SECONDS",
+        DATABASE_UDF_USER_TIMEOUT.as_secs(),
+    ))
+});
+
 /// Timeout on the "system time" during a UDF -- i.e. syscalls.
 // The user limits are not very tight, which requires us to have a high
 // syscall timeout. When the database is healthy, we should never have UDF
@@ -669,6 +737,77 @@ pub static DATABASE_UDF_USER_TIMEOUT: LazyLock<Duration> =
 pub static DATABASE_UDF_SYSTEM_TIMEOUT: LazyLock<Duration> =
     LazyLock::new(|| Duration::from_secs(env_config("DATABASE_UDF_SYSTEM_TIMEOUT_SECONDS", 15)));
 
+/// Directory to write recordings of the queries and mutations in
+/// `UDF_EXECUTION_RECORDING_FUNCTIONS` to, for replaying them with the
+/// `/api/replay_execution_recording` endpoint. Recording is off when this is
+/// empty. Recordings are encrypted with the instance secret.
+pub static UDF_EXECUTION_RECORDING_DIR: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
+    let dir: String = env_config("UDF_EXECUTION_RECORDING_DIR", String::new());
+    if !dir.is_empty() {
+        Some(PathBuf::from(dir))
+    } else {
+        None
+    }
+});
+
+/// Comma-separated function paths (e.g. `messages.js:send`) to record every
+/// execution of when `UDF_EXECUTION_RECORDING_DIR` is set.
+pub static UDF_EXECUTION_RECORDING_FUNCTIONS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    let functions: String = env_config("UDF_EXECUTION_RECORDING_FUNCTIONS", String::new());
+    functions
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+});
+
+/// Comma-separated function paths (e.g. `messages.js:list`) to CPU profile on
+/// every execution at startup. Each instance can change this at runtime through
+/// the `/api/update_cpu_profiling` admin endpoint.
+pub static ISOLATE_CPU_PROFILE_FUNCTIONS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    let functions: String = env_config("ISOLATE_CPU_PROFILE_FUNCTIONS", String::new());
+    functions
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+});
+
+/// CPU profile one out of every this many function executions at startup. 0
+/// only profiles the functions in `ISOLATE_CPU_PROFILE_FUNCTIONS`. Each instance
+/// can change this at runtime through the `/api/update_cpu_profiling` admin
+/// endpoint.
+pub static ISOLATE_CPU_PROFILE_SAMPLE_RATE: LazyLock<u64> =
+    LazyLock::new(|| env_config("ISOLATE_CPU_PROFILE_SAMPLE_RATE", 0));
+
+/// How often to check whether a function with a memory limit in its `limits`
+/// config has gone over it while its JavaScript is running.
+pub static ISOLATE_MEMORY_LIMIT_CHECK_INTERVAL: LazyLock<Duration> = LazyLock::new(|| {
+    Duration::from_millis(env_config("ISOLATE_MEMORY_LIMIT_CHECK_INTERVAL_MS", 10))
+});
+
+/// How often the CPU profiler samples the JavaScript stack.
+pub static ISOLATE_CPU_PROFILE_INTERVAL: LazyLock<Duration> =
+    LazyLock::new(|| Duration::from_micros(env_config("ISOLATE_CPU_PROFILE_INTERVAL_US", 1000)));
+
+/// Serve action `fetch` requests from a fixture store instead of the network,
+/// for integration tests: "off", "record" or "replay". This can also be
+/// changed at runtime through `/api/update_fetch_fixtures`.
+pub static FETCH_FIXTURES_MODE: LazyLock<FetchFixtureMode> =
+    LazyLock::new(|| env_config("FETCH_FIXTURES_MODE", FetchFixtureMode::Off));
+
+/// Cassette file for `FETCH_FIXTURES_MODE`, with one JSON interaction per
+/// line. Replay mode loads fixtures from it, and record mode appends every
+/// recorded request to it.
+pub static FETCH_FIXTURES_PATH: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
+    let path: String = env_config("FETCH_FIXTURES_PATH", String::new());
+    if !path.is_empty() {
+        Some(PathBuf::from(path))
+    } else {
+        None
+    }
+});
+
 /// Timeout on the time it takes to analyze code during a push.
 pub static ISOLATE_ANALYZE_USER_TIMEOUT: LazyLock<Duration> =
     LazyLock::new(|| Duration::from_secs(env_config("ISOLATE_ANALYZE_USER_TIMEOUT_SECONDS", 2)));
@@ -698,6 +837,12 @@ pub static V8_THREADS: LazyLock<u32> = LazyLock::new(|| env_config("V8_THREADS",
 /// heap.
 pub static REUSE_ISOLATES: LazyLock<bool> = LazyLock::new(|| env_config("REUSE_ISOLATES", true));
 
+/// If true, build a V8 startup snapshot with every module already evaluated
+/// for each pushed source package, and create isolates from it so queries and
+/// mutations skip evaluating modules on a cold start.
+pub static ISOLATE_DEPLOYMENT_SNAPSHOTS: LazyLock<bool> =
+    LazyLock::new(|| env_config("ISOLATE_DEPLOYMENT_SNAPSHOTS", false));
+
 /// Duration in seconds before an idle isolate is recreated
 pub static ISOLATE_IDLE_TIMEOUT: LazyLock<Duration> =
     LazyLock::new(|| Duration::from_secs(env_config("ISOLATE_IDLE_TIMEOUT_SECONDS", 600)));
@@ -987,6 +1132,10 @@ pub static FUNRUN_MODULE_MAX_CONCURRENCY: LazyLock<usize> =
 pub static FUNRUN_CODE_CACHE_SIZE: LazyLock<u64> =
     LazyLock::new(|| env_config("FUNRUN_CODE_CACHE_SIZE", 500_000_000));
 
+/// The maximum size of the deployment snapshot cache in Funrun in bytes.
+pub static FUNRUN_SNAPSHOT_CACHE_SIZE: LazyLock<u64> =
+    LazyLock::new(|| env_config("FUNRUN_SNAPSHOT_CACHE_SIZE", 500_000_000));
+
 /// The maximum number of fetch clients Funrun would create.
 pub static FUNRUN_FETCH_CLIENT_CACHE_SIZE: LazyLock<usize> =
     LazyLock::new(|| env_config("FUNRUN_FETCH_CLIENT_CACHE_SIZE", 100));
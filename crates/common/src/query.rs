@@ -557,6 +557,60 @@ impl InternalSearch {
     }
 }
 
+/// A request for completions of the last token in `prefix`, drawn from the
+/// term dictionary of a search index's `searchField`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchSuggest {
+    /// The search index being queried.
+    pub index_name: IndexName,
+    pub table: TableName,
+
+    /// The partially typed text. All tokens but the last are kept as-is, and
+    /// the last one is completed.
+    pub prefix: String,
+    /// The maximum number of completions to return.
+    pub limit: usize,
+}
+
+impl SearchSuggest {
+    pub fn to_internal(self, tablet_index_name: TabletIndexName) -> InternalSearchSuggest {
+        InternalSearchSuggest {
+            index_name: tablet_index_name,
+            table_name: self.table,
+            prefix: self.prefix,
+            limit: self.limit,
+        }
+    }
+}
+
+/// The `TableId` counterpart of `SearchSuggest`, like `InternalSearch`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InternalSearchSuggest {
+    pub index_name: GenericIndexName<TabletId>,
+    pub table_name: TableName,
+    pub prefix: String,
+    pub limit: usize,
+}
+
+impl InternalSearchSuggest {
+    pub fn printable_index_name(&self) -> anyhow::Result<IndexName> {
+        IndexName::new(
+            self.table_name.clone(),
+            self.index_name.descriptor().clone(),
+        )
+    }
+}
+
+/// The filter fields to count values for across all of a search query's
+/// matches, for building facet sidebars next to the search results.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchFacets {
+    /// Each field must be one of the search index's `filterFields`.
+    pub fields: Vec<FieldPath>,
+    /// The maximum number of distinct values to return counts for per field.
+    pub limit: usize,
+}
+
 /// Filter field values under this size are stored as bytes. Otherwise
 /// we hash them down to 32 bytes.
 const MAX_FILTER_FIELD_LENGTH: usize = 32;
@@ -33,6 +33,7 @@ use super::{
     DatabaseSchema,
     DocumentSchema,
     IndexSchema,
+    SparseVectorIndexSchema,
     VectorIndexSchema,
 };
 use crate::{
@@ -43,7 +44,12 @@ use crate::{
             search_field_not_unique,
             vector_field_not_unique,
         },
-        vector_index::VectorDimensions,
+        vector_index::{
+            SerializedVectorQuantization,
+            VectorDimensions,
+            VectorDistanceMetric,
+            VectorQuantization,
+        },
     },
     json::JsonSerializable,
     schemas::{
@@ -121,6 +127,8 @@ pub struct TableDefinitionJson {
     staged_search_indexes: Option<Vec<TextIndexSchemaJson>>,
     vector_indexes: Option<Vec<VectorIndexSchemaJson>>,
     staged_vector_indexes: Option<Vec<VectorIndexSchemaJson>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sparse_vector_indexes: Option<Vec<SparseVectorIndexSchemaJson>>,
     document_type: Option<ValidatorJson>,
 }
 
@@ -172,6 +180,7 @@ impl TryFrom<TableDefinitionJson> for TableDefinition {
         let staged_text_indexes = j.staged_search_indexes.unwrap_or_default();
         let vector_indexes = j.vector_indexes.unwrap_or_default();
         let staged_vector_indexes = j.staged_vector_indexes.unwrap_or_default();
+        let sparse_vector_indexes = j.sparse_vector_indexes.unwrap_or_default();
 
         let document_type = j.document_type.map(|t| t.try_into()).transpose()?;
 
@@ -190,6 +199,7 @@ impl TryFrom<TableDefinitionJson> for TableDefinition {
             + staged_db_indexes.len()
             + staged_text_indexes.len()
             + staged_vector_indexes.len()
+            + sparse_vector_indexes.len()
             > MAX_INDEXES_PER_TABLE
         {
             anyhow::bail!(index_validation_error::too_many_indexes(
@@ -260,6 +270,18 @@ impl TryFrom<TableDefinitionJson> for TableDefinition {
             |index1, index2| vector_field_not_unique(&table_name, index1, index2),
         )?;
 
+        let (sparse_vector_index_names, sparse_vector_indexes): (Vec<_>, BTreeMap<_, _>) =
+            parse_names_and_indexes(
+                &table_name,
+                sparse_vector_indexes,
+                |idx: &SparseVectorIndexSchema| &idx.index_descriptor,
+            )?;
+        validate_unique_index_fields(
+            sparse_vector_indexes.iter(),
+            |idx: &SparseVectorIndexSchema| idx.vector_field.clone(),
+            |index1, index2| vector_field_not_unique(&table_name, index1, index2),
+        )?;
+
         let all_index_names: Vec<_> = index_names
             .into_iter()
             .chain(staged_db_index_names)
@@ -267,6 +289,7 @@ impl TryFrom<TableDefinitionJson> for TableDefinition {
             .chain(staged_text_index_names)
             .chain(staged_vector_index_names)
             .chain(vector_index_names)
+            .chain(sparse_vector_index_names)
             .collect();
 
         let mut seen: HashSet<_> = HashSet::new();
@@ -295,6 +318,7 @@ impl TryFrom<TableDefinitionJson> for TableDefinition {
             staged_text_indexes,
             vector_indexes,
             staged_vector_indexes,
+            sparse_vector_indexes,
             document_type,
         })
     }
@@ -312,6 +336,7 @@ impl TryFrom<TableDefinition> for TableDefinitionJson {
             staged_text_indexes: staged_search_indexes,
             vector_indexes,
             staged_vector_indexes,
+            sparse_vector_indexes,
             document_type,
         }: TableDefinition,
     ) -> anyhow::Result<Self> {
@@ -351,6 +376,16 @@ impl TryFrom<TableDefinition> for TableDefinitionJson {
                 .map(VectorIndexSchemaJson::try_from)
                 .collect::<anyhow::Result<Vec<_>>>()?,
         );
+        // Only include sparse vector indexes when there are some so that
+        // schemas without them serialize the same way as before.
+        let sparse_vector_indexes = (!sparse_vector_indexes.is_empty())
+            .then(|| {
+                sparse_vector_indexes
+                    .into_values()
+                    .map(SparseVectorIndexSchemaJson::try_from)
+                    .collect::<anyhow::Result<Vec<_>>>()
+            })
+            .transpose()?;
         Ok(TableDefinitionJson {
             table_name,
             indexes,
@@ -359,6 +394,7 @@ impl TryFrom<TableDefinition> for TableDefinitionJson {
             staged_search_indexes,
             vector_indexes,
             staged_vector_indexes,
+            sparse_vector_indexes,
             document_type,
         })
     }
@@ -427,6 +463,10 @@ pub struct VectorIndexSchemaJson {
     dimensions: Option<u32>,
     dimension: Option<u32>,
     filter_fields: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distance_metric: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quantization: Option<SerializedVectorQuantization>,
 }
 
 impl JsonSerializable for VectorIndexSchema {
@@ -458,7 +498,22 @@ impl TryFrom<VectorIndexSchemaJson> for VectorIndexSchema {
                 None => anyhow::bail!("Missing dimensions field"),
             },
         };
-        Self::new(index_descriptor, vector_field, dimension, filter_fields)
+        let distance_metric = match j.distance_metric {
+            Some(m) => m.parse::<VectorDistanceMetric>()?,
+            None => VectorDistanceMetric::default(),
+        };
+        let quantization = j
+            .quantization
+            .map(VectorQuantization::try_from)
+            .transpose()?;
+        Self::new(
+            index_descriptor,
+            vector_field,
+            dimension,
+            filter_fields,
+            distance_metric,
+            quantization,
+        )
     }
 }
 
@@ -471,6 +526,8 @@ impl TryFrom<VectorIndexSchema> for VectorIndexSchemaJson {
             vector_field,
             dimension,
             filter_fields,
+            distance_metric,
+            quantization,
             ..
         }: VectorIndexSchema,
     ) -> anyhow::Result<Self> {
@@ -483,6 +540,63 @@ impl TryFrom<VectorIndexSchema> for VectorIndexSchemaJson {
                 .into_iter()
                 .map(String::from)
                 .collect::<Vec<_>>(),
+            distance_metric: (!distance_metric.is_default()).then(|| distance_metric.to_string()),
+            quantization: quantization.map(SerializedVectorQuantization::from),
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SparseVectorIndexSchemaJson {
+    index_descriptor: String,
+    vector_field: String,
+    filter_fields: Vec<String>,
+}
+
+impl JsonSerializable for SparseVectorIndexSchema {
+    type Json = SparseVectorIndexSchemaJson;
+}
+
+impl TryFrom<SparseVectorIndexSchemaJson> for SparseVectorIndexSchema {
+    type Error = anyhow::Error;
+
+    fn try_from(j: SparseVectorIndexSchemaJson) -> Result<Self, Self::Error> {
+        let index_descriptor = IndexDescriptor::new(j.index_descriptor)?;
+        let vector_field = j.vector_field.parse().with_context(|| {
+            index_validation_error::invalid_index_field(&index_descriptor, &j.vector_field)
+        })?;
+        let filter_fields = j
+            .filter_fields
+            .into_iter()
+            .map(|f| {
+                f.parse().with_context(|| {
+                    index_validation_error::invalid_index_field(&index_descriptor, &f)
+                })
+            })
+            .collect::<anyhow::Result<BTreeSet<_>>>()?;
+        Self::new(index_descriptor, vector_field, filter_fields)
+    }
+}
+
+impl TryFrom<SparseVectorIndexSchema> for SparseVectorIndexSchemaJson {
+    type Error = anyhow::Error;
+
+    fn try_from(
+        SparseVectorIndexSchema {
+            index_descriptor,
+            vector_field,
+            filter_fields,
+            ..
+        }: SparseVectorIndexSchema,
+    ) -> anyhow::Result<Self> {
+        Ok(SparseVectorIndexSchemaJson {
+            index_descriptor: String::from(index_descriptor),
+            vector_field: String::from(vector_field),
+            filter_fields: filter_fields
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>(),
         })
     }
 }
@@ -493,6 +607,8 @@ pub struct TextIndexSchemaJson {
     index_descriptor: String,
     search_field: String,
     filter_fields: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    synonyms: Vec<BTreeSet<String>>,
 }
 
 impl JsonSerializable for TextIndexSchema {
@@ -517,7 +633,7 @@ impl TryFrom<TextIndexSchemaJson> for TextIndexSchema {
             })
             .collect::<anyhow::Result<BTreeSet<_>>>()?;
 
-        Self::new(index_descriptor, search_field, filter_fields)
+        Self::new(index_descriptor, search_field, filter_fields, j.synonyms)
     }
 }
 
@@ -529,6 +645,7 @@ impl TryFrom<TextIndexSchema> for TextIndexSchemaJson {
             index_descriptor,
             search_field,
             filter_fields,
+            synonyms,
             ..
         }: TextIndexSchema,
     ) -> anyhow::Result<Self> {
@@ -539,6 +656,7 @@ impl TryFrom<TextIndexSchema> for TextIndexSchemaJson {
                 .into_iter()
                 .map(String::from)
                 .collect::<BTreeSet<_>>(),
+            synonyms,
         })
     }
 }
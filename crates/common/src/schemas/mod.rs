@@ -41,8 +41,14 @@ use crate::{
     bootstrap_model::index::{
         database_index::IndexedFields,
         index_validation_error,
-        vector_index::VectorDimensions,
+        vector_index::{
+            VectorDimensions,
+            VectorDistanceMetric,
+            VectorQuantization,
+        },
         MAX_TEXT_INDEX_FILTER_FIELDS_SIZE,
+        MAX_TEXT_INDEX_SYNONYM_SETS,
+        MAX_TEXT_INDEX_SYNONYM_SET_SIZE,
         MAX_VECTOR_INDEX_FILTER_FIELDS_SIZE,
     },
     document::ResolvedDocument,
@@ -174,6 +180,7 @@ macro_rules! db_schema {
                         staged_text_indexes: Default::default(),
                         vector_indexes: Default::default(),
                         staged_vector_indexes: Default::default(),
+                        sparse_vector_indexes: Default::default(),
                         document_type: Some($document_schema),
                     };
                     tables.insert(table_name, table_def);
@@ -208,6 +215,7 @@ macro_rules! db_schema_not_validated {
                         staged_text_indexes: Default::default(),
                         vector_indexes: Default::default(),
                         staged_vector_indexes: Default::default(),
+                        sparse_vector_indexes: Default::default(),
                         document_type: Some($document_schema),
                     };
                     tables.insert(table_name, table_def);
@@ -253,6 +261,8 @@ macro_rules! db_schema_with_vector_indexes {
                                 value::FieldPath::from_str($vector_field)?,
                                 1536u32.try_into()?,
                                 Default::default(),
+                                Default::default(),
+                                None,
                             )?,
                         );
                     )*
@@ -264,6 +274,7 @@ macro_rules! db_schema_with_vector_indexes {
                         staged_text_indexes: Default::default(),
                         vector_indexes,
                         staged_vector_indexes: Default::default(),
+                        sparse_vector_indexes: Default::default(),
                         document_type: Some($document_schema),
                     };
                     tables.insert(table_name, table_def);
@@ -557,6 +568,7 @@ pub struct TableDefinition {
     pub staged_text_indexes: BTreeMap<IndexDescriptor, TextIndexSchema>,
     pub vector_indexes: BTreeMap<IndexDescriptor, VectorIndexSchema>,
     pub staged_vector_indexes: BTreeMap<IndexDescriptor, VectorIndexSchema>,
+    pub sparse_vector_indexes: BTreeMap<IndexDescriptor, SparseVectorIndexSchema>,
     pub document_type: Option<DocumentSchema>, /* FIXME: `Option` could be removed here, since
                                                 * `None` is handled the same way as
                                                 * `Some(DocumentSchema::Any)`. */
@@ -598,10 +610,17 @@ impl TableDefinition {
 
         let vector_index_fields = self.vector_fields();
 
+        let sparse_vector_index_fields = self.sparse_vector_indexes.iter().map(
+            |(index_descriptor, sparse_vector_index_schema)| {
+                (index_descriptor, &sparse_vector_index_schema.vector_field)
+            },
+        );
+
         index_fields
             .chain(text_index_fields)
             .chain(text_index_filter_fields)
             .chain(vector_index_fields)
+            .chain(sparse_vector_index_fields)
     }
 
     pub fn vector_fields(&self) -> impl Iterator<Item = (&IndexDescriptor, &FieldPath)> {
@@ -630,6 +649,7 @@ impl proptest::arbitrary::Arbitrary for TableDefinition {
             prop::collection::vec(any::<TextIndexSchema>(), 0..3),
             prop::collection::vec(any::<VectorIndexSchema>(), 0..3),
             prop::collection::vec(any::<VectorIndexSchema>(), 0..3),
+            prop::collection::vec(any::<SparseVectorIndexSchema>(), 0..3),
             any_with::<Option<DocumentSchema>>((
                 prop::option::Probability::default(),
                 all_table_names,
@@ -644,6 +664,7 @@ impl proptest::arbitrary::Arbitrary for TableDefinition {
                     staged_search_indexes,
                     vector_indexes,
                     staged_vector_indexes,
+                    sparse_vector_indexes,
                     document_type,
                 )| {
                     let index_descriptors: BTreeSet<_> = indexes
@@ -654,13 +675,15 @@ impl proptest::arbitrary::Arbitrary for TableDefinition {
                         .chain(staged_search_indexes.iter().map(|i| &i.index_descriptor))
                         .chain(vector_indexes.iter().map(|i| &i.index_descriptor))
                         .chain(staged_vector_indexes.iter().map(|i| &i.index_descriptor))
+                        .chain(sparse_vector_indexes.iter().map(|i| &i.index_descriptor))
                         .collect();
                     let expected = indexes.len()
                         + staged_db_indexes.len()
                         + search_indexes.len()
                         + staged_search_indexes.len()
                         + vector_indexes.len()
-                        + staged_vector_indexes.len();
+                        + staged_vector_indexes.len()
+                        + sparse_vector_indexes.len();
                     assert!(index_descriptors.len() <= expected);
                     if index_descriptors.len() == expected {
                         Some(Self {
@@ -689,6 +712,10 @@ impl proptest::arbitrary::Arbitrary for TableDefinition {
                                 .into_iter()
                                 .map(|i| (i.index_descriptor.clone(), i))
                                 .collect(),
+                            sparse_vector_indexes: sparse_vector_indexes
+                                .into_iter()
+                                .map(|i| (i.index_descriptor.clone(), i))
+                                .collect(),
                             document_type,
                         })
                     } else {
@@ -722,6 +749,14 @@ pub struct TextIndexSchema {
         proptest(strategy = "prop::collection::btree_set(any::<FieldPath>(), 0..8)")
     )]
     pub filter_fields: BTreeSet<FieldPath>,
+    /// Groups of terms or phrases that search queries against this index treat
+    /// as equivalent. These are stored in the index's
+    /// `DeveloperTextIndexConfig`, so changing them rebuilds the index.
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "crate::bootstrap_model::index::text_index::synonym_sets_strategy()")
+    )]
+    pub synonyms: Vec<BTreeSet<String>>,
 
     // Private field to force all creations to go through the constructor.
     _pd: PhantomData<()>,
@@ -732,16 +767,34 @@ impl TextIndexSchema {
         index_descriptor: IndexDescriptor,
         search_field: FieldPath,
         filter_fields: BTreeSet<FieldPath>,
+        synonyms: Vec<BTreeSet<String>>,
     ) -> anyhow::Result<Self> {
         if filter_fields.len() > MAX_TEXT_INDEX_FILTER_FIELDS_SIZE {
             anyhow::bail!(index_validation_error::too_many_filter_fields(
                 MAX_TEXT_INDEX_FILTER_FIELDS_SIZE
             ));
         }
+        if synonyms.len() > MAX_TEXT_INDEX_SYNONYM_SETS {
+            anyhow::bail!(index_validation_error::too_many_synonym_sets(
+                MAX_TEXT_INDEX_SYNONYM_SETS
+            ));
+        }
+        for synonym_set in &synonyms {
+            if synonym_set.len() < 2
+                || synonym_set.len() > MAX_TEXT_INDEX_SYNONYM_SET_SIZE
+                || synonym_set.iter().any(|entry| entry.trim().is_empty())
+            {
+                anyhow::bail!(index_validation_error::invalid_synonym_set(
+                    &index_descriptor,
+                    MAX_TEXT_INDEX_SYNONYM_SET_SIZE
+                ));
+            }
+        }
         Ok(Self {
             index_descriptor,
             search_field,
             filter_fields,
+            synonyms,
             _pd: PhantomData,
         })
     }
@@ -758,6 +811,8 @@ pub struct VectorIndexSchema {
         proptest(strategy = "prop::collection::btree_set(any::<FieldPath>(), 0..8)")
     )]
     pub filter_fields: BTreeSet<FieldPath>,
+    pub distance_metric: VectorDistanceMetric,
+    pub quantization: Option<VectorQuantization>,
 
     // Private field to force all creations to go through the constructor.
     _pd: PhantomData<()>,
@@ -769,6 +824,8 @@ impl VectorIndexSchema {
         vector_field: FieldPath,
         dimension: VectorDimensions,
         filter_fields: BTreeSet<FieldPath>,
+        distance_metric: VectorDistanceMetric,
+        quantization: Option<VectorQuantization>,
     ) -> anyhow::Result<Self> {
         if filter_fields.len() > MAX_VECTOR_INDEX_FILTER_FIELDS_SIZE {
             anyhow::bail!(index_validation_error::too_many_filter_fields(
@@ -780,6 +837,43 @@ impl VectorIndexSchema {
             vector_field,
             dimension,
             filter_fields,
+            distance_metric,
+            quantization,
+            _pd: PhantomData,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct SparseVectorIndexSchema {
+    pub index_descriptor: IndexDescriptor,
+    pub vector_field: FieldPath,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "prop::collection::btree_set(any::<FieldPath>(), 0..8)")
+    )]
+    pub filter_fields: BTreeSet<FieldPath>,
+
+    // Private field to force all creations to go through the constructor.
+    _pd: PhantomData<()>,
+}
+
+impl SparseVectorIndexSchema {
+    pub fn new(
+        index_descriptor: IndexDescriptor,
+        vector_field: FieldPath,
+        filter_fields: BTreeSet<FieldPath>,
+    ) -> anyhow::Result<Self> {
+        if filter_fields.len() > MAX_VECTOR_INDEX_FILTER_FIELDS_SIZE {
+            anyhow::bail!(index_validation_error::too_many_filter_fields(
+                MAX_VECTOR_INDEX_FILTER_FIELDS_SIZE
+            ));
+        }
+        Ok(Self {
+            index_descriptor,
+            vector_field,
+            filter_fields,
             _pd: PhantomData,
         })
     }
@@ -655,6 +655,61 @@ fn test_json_backwards_compatibility() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_sparse_vector_indexes() -> anyhow::Result<()> {
+    let schema_json = json!({
+        "tables": [
+            {
+                "tableName": "testTable",
+                "indexes": [],
+                "sparseVectorIndexes": [
+                    {
+                        "indexDescriptor": "by_embedding",
+                        "vectorField": "embedding",
+                        "filterFields": ["genre"],
+                    },
+                ],
+            },
+        ],
+        "schemaValidation": true,
+    });
+    let schema = DatabaseSchema::json_deserialize_value(schema_json)?;
+    let table = &schema.tables[&"testTable".parse()?];
+    let index = &table.sparse_vector_indexes[&"by_embedding".parse()?];
+    assert_eq!(index.vector_field, "embedding".parse()?);
+    assert_eq!(
+        index
+            .filter_fields
+            .iter()
+            .cloned()
+            .map(String::from)
+            .collect::<Vec<_>>(),
+        vec!["genre".to_string()]
+    );
+    assert_roundtrips::<DatabaseSchema, DatabaseSchemaJson>(schema);
+
+    // Sparse vector index names share a namespace with the other indexes.
+    let schema_json = json!({
+        "tables": [
+            {
+                "tableName": "testTable",
+                "indexes": [{ "indexDescriptor": "by_embedding", "fields": ["genre"] }],
+                "sparseVectorIndexes": [
+                    {
+                        "indexDescriptor": "by_embedding",
+                        "vectorField": "embedding",
+                        "filterFields": [],
+                    },
+                ],
+            },
+        ],
+        "schemaValidation": true,
+    });
+    let error = DatabaseSchema::json_deserialize_value(schema_json).unwrap_err();
+    assert!(error.to_string().contains("by_embedding"), "{error}");
+    Ok(())
+}
+
 fn empty_table_mapping() -> NamespacedTableMapping {
     TableMapping::new().namespace(TableNamespace::test_user())
 }
@@ -815,6 +815,30 @@ fn test_too_many_search_filter_fields() {
     );
 }
 
+#[test]
+fn test_invalid_search_synonym_set() {
+    let value = json!({
+        "tables": [
+            {
+                "tableName": "test",
+                "indexes": [],
+                "searchIndexes": [{
+                    "indexDescriptor": "search_index",
+                    "searchField": "fieldName",
+                    "filterFields": [],
+                    "synonyms": [["tv", "television"], ["lonely"]],
+                }]
+            },
+        ],
+        "schemaValidation": true,
+    });
+    let err = index_validation_test(value);
+    assert_eq!(
+        err.short_msg, "IndexInvalidSynonymSet",
+        "<{err}> does not match expected error type"
+    );
+}
+
 #[test]
 fn test_too_many_indexes() {
     let value = json!({
@@ -24,6 +24,15 @@ use crate::{
         CanonicalizedComponentFunctionPath,
         ComponentId,
     },
+    knobs::{
+        ACTION_MAX_USER_TIMEOUT,
+        DATABASE_UDF_MAX_USER_TIMEOUT,
+        FUNCTION_MAX_NUM_USER_WRITES,
+        FUNCTION_MAX_READ_SIZE_BYTES,
+        FUNCTION_MAX_READ_SIZE_ROWS,
+        FUNCTION_MAX_USER_WRITE_SIZE_BYTES,
+        ISOLATE_MAX_USER_HEAP_SIZE,
+    },
     version::ClientVersion,
 };
 
@@ -361,6 +370,133 @@ impl TryFrom<pb::common::FunctionCaller> for FunctionCaller {
     }
 }
 
+/// Resource limits declared in a function's config. Unset limits use the
+/// deployment's defaults. Declared limits can be tighter or looser than the
+/// defaults, but never above the deployment's maximums.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct FunctionLimits {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_documents_read: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes_read: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_documents_written: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes_written: Option<u64>,
+}
+
+impl FunctionLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Checks that every declared limit is positive and within the
+    /// deployment's maximum for a function of type `udf_type`.
+    pub fn validate(&self, udf_type: UdfType) -> anyhow::Result<()> {
+        let max_timeout = match udf_type {
+            UdfType::Query | UdfType::Mutation => *DATABASE_UDF_MAX_USER_TIMEOUT,
+            UdfType::Action | UdfType::HttpAction => *ACTION_MAX_USER_TIMEOUT,
+        };
+        let limits = [
+            ("timeoutMs", self.timeout_ms, max_timeout.as_millis() as u64),
+            (
+                "memoryMb",
+                self.memory_mb,
+                (*ISOLATE_MAX_USER_HEAP_SIZE >> 20) as u64,
+            ),
+            (
+                "maxDocumentsRead",
+                self.max_documents_read,
+                *FUNCTION_MAX_READ_SIZE_ROWS as u64,
+            ),
+            (
+                "maxBytesRead",
+                self.max_bytes_read,
+                *FUNCTION_MAX_READ_SIZE_BYTES as u64,
+            ),
+            (
+                "maxDocumentsWritten",
+                self.max_documents_written,
+                *FUNCTION_MAX_NUM_USER_WRITES as u64,
+            ),
+            (
+                "maxBytesWritten",
+                self.max_bytes_written,
+                *FUNCTION_MAX_USER_WRITE_SIZE_BYTES as u64,
+            ),
+        ];
+        for (name, limit, max) in limits {
+            let Some(limit) = limit else {
+                continue;
+            };
+            anyhow::ensure!(
+                limit > 0 && limit <= max,
+                "`{name}` must be between 1 and {max}, but is {limit}"
+            );
+        }
+        if matches!(udf_type, UdfType::Action | UdfType::HttpAction) {
+            anyhow::ensure!(
+                self.max_documents_read.is_none()
+                    && self.max_bytes_read.is_none()
+                    && self.max_documents_written.is_none()
+                    && self.max_bytes_written.is_none(),
+                "Read and write limits can only be set on queries and mutations"
+            );
+        }
+        Ok(())
+    }
+}
+
+impl From<FunctionLimits> for pb::common::FunctionLimits {
+    fn from(
+        FunctionLimits {
+            timeout_ms,
+            memory_mb,
+            max_documents_read,
+            max_bytes_read,
+            max_documents_written,
+            max_bytes_written,
+        }: FunctionLimits,
+    ) -> Self {
+        Self {
+            timeout_ms,
+            memory_mb,
+            max_documents_read,
+            max_bytes_read,
+            max_documents_written,
+            max_bytes_written,
+        }
+    }
+}
+
+impl From<pb::common::FunctionLimits> for FunctionLimits {
+    fn from(
+        pb::common::FunctionLimits {
+            timeout_ms,
+            memory_mb,
+            max_documents_read,
+            max_bytes_read,
+            max_documents_written,
+            max_bytes_written,
+        }: pb::common::FunctionLimits,
+    ) -> Self {
+        Self {
+            timeout_ms,
+            memory_mb,
+            max_documents_read,
+            max_bytes_read,
+            max_documents_written,
+            max_bytes_written,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
@@ -415,6 +551,7 @@ mod tests {
     use sync_types::testing::assert_roundtrips;
 
     use super::{
+        FunctionLimits,
         UdfType,
         UdfTypeProto,
     };
@@ -434,5 +571,10 @@ mod tests {
         fn test_function_caller_roundtrips(u in any::<FunctionCaller>()) {
             assert_roundtrips::<FunctionCaller, pb::common::FunctionCaller>(u);
         }
+
+        #[test]
+        fn test_function_limits_roundtrips(u in any::<FunctionLimits>()) {
+            assert_roundtrips::<FunctionLimits, pb::common::FunctionLimits>(u);
+        }
     }
 }
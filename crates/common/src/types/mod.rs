@@ -65,6 +65,7 @@ pub use file_storage::StorageUuid;
 pub use functions::{
     AllowedVisibility,
     FunctionCaller,
+    FunctionLimits,
     ModuleEnvironment,
     UdfIdentifier,
     UdfType,
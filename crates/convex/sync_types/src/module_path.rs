@@ -20,6 +20,7 @@ pub const DEPS_DIR: &str = "_deps";
 pub const ACTIONS_DIR: &str = "actions";
 pub const HTTP_PATH: &str = "http.js";
 pub const CRON_PATH: &str = "crons.js";
+pub const WASM_EXTENSION: &str = "wasm";
 
 /// User-specified path to a loaded module.
 #[derive(Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -100,6 +101,13 @@ impl ModulePath {
         self.is_cron
     }
 
+    /// Is this module a WebAssembly binary rather than JavaScript?
+    pub fn is_wasm(&self) -> bool {
+        self.path
+            .extension()
+            .is_some_and(|ext| ext == WASM_EXTENSION)
+    }
+
     pub fn canonicalize(self) -> CanonicalizedModulePath {
         let Self {
             path,
@@ -129,7 +137,10 @@ impl ModulePath {
         let ext = path
             .extension()
             .ok_or_else(|| anyhow::anyhow!("Path {path:?} doesn't have an extension."))?;
-        anyhow::ensure!(ext == "js", "Path {path:?} doesn't have a '.js' extension.");
+        anyhow::ensure!(
+            ext == "js" || ext == WASM_EXTENSION,
+            "Path {path:?} doesn't have a '.js' or '.wasm' extension."
+        );
         Ok(CanonicalizedModulePath {
             path,
             is_system,
@@ -157,8 +168,11 @@ impl FromStr for ModulePath {
             anyhow::bail!("Module path {p} doesn't have a filename.");
         }
         if let Some(ext) = path.extension() {
-            if ext != "js" {
-                anyhow::bail!("Module path ({}) has an extension that isn't 'js'.", p);
+            if ext != "js" && ext != WASM_EXTENSION {
+                anyhow::bail!(
+                    "Module path ({}) has an extension that isn't 'js' or 'wasm'.",
+                    p
+                );
             }
         }
 
@@ -323,6 +337,12 @@ impl CanonicalizedModulePath {
         self.is_cron
     }
 
+    pub fn is_wasm(&self) -> bool {
+        self.path
+            .extension()
+            .is_some_and(|ext| ext == WASM_EXTENSION)
+    }
+
     pub fn strip(self) -> ModulePath {
         let Self {
             mut path,
@@ -437,6 +457,11 @@ mod tests {
         for p in not_http_paths {
             assert!(!ModulePath::from_str(p)?.is_http());
         }
+        let wasm_path = ModulePath::from_str("lib/parser.wasm")?;
+        assert!(wasm_path.is_wasm());
+        // WebAssembly modules keep their extension when canonicalized.
+        assert_eq!(wasm_path.canonicalize().as_str(), "lib/parser.wasm");
+        assert!(!ModulePath::from_str("parser")?.is_wasm());
         Ok(())
     }
 }
@@ -13,6 +13,7 @@ use common::{
             IndexedFields,
         },
         index_validation_error,
+        sparse_vector_index::DeveloperSparseVectorIndexConfig,
         text_index::{
             DeveloperTextIndexConfig,
             TextIndexState,
@@ -234,6 +235,10 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
             IndexConfig::Vector {
                 ref mut on_disk_state,
                 ..
+            }
+            | IndexConfig::SparseVector {
+                ref mut on_disk_state,
+                ..
             } => match on_disk_state {
                 VectorIndexState::Backfilled { snapshot, .. } => {
                     *on_disk_state = VectorIndexState::SnapshottedAt(snapshot.clone());
@@ -424,6 +429,7 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
                     index_name.clone(),
                     index_schema.search_field.clone(),
                     index_schema.filter_fields.clone(),
+                    index_schema.synonyms.clone(),
                 ))
             }
             for (index_descriptor, index_schema) in &table_schema.vector_indexes {
@@ -433,6 +439,16 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
                     index_schema.vector_field.clone(),
                     index_schema.dimension,
                     index_schema.filter_fields.clone(),
+                    index_schema.distance_metric,
+                    index_schema.quantization,
+                ));
+            }
+            for (index_descriptor, index_schema) in &table_schema.sparse_vector_indexes {
+                let index_name = IndexName::new(table_name.clone(), index_descriptor.clone())?;
+                indexes_in_schema.push(IndexMetadata::new_backfilling_sparse_vector_index(
+                    index_name.clone(),
+                    index_schema.vector_field.clone(),
+                    index_schema.filter_fields.clone(),
                 ));
             }
         }
@@ -815,7 +831,9 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
         let mut non_empty_indexes = vec![];
         for index in all_indexes {
             match index.config {
-                IndexConfig::Text { .. } | IndexConfig::Vector { .. } => (),
+                IndexConfig::Text { .. }
+                | IndexConfig::Vector { .. }
+                | IndexConfig::SparseVector { .. } => (),
                 IndexConfig::Database { .. } => continue,
             };
             let table = *index.name.table();
@@ -969,12 +987,14 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
                         DeveloperTextIndexConfig {
                             search_field,
                             filter_fields,
+                            synonyms,
                         },
                     ..
                 } => IndexMetadata::new_backfilling_text_index(
                     index_name,
                     search_field,
                     filter_fields,
+                    synonyms,
                 ),
                 IndexConfig::Vector {
                     developer_config:
@@ -982,6 +1002,8 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
                             dimensions,
                             vector_field,
                             filter_fields,
+                            distance_metric,
+                            quantization,
                         },
                     ..
                 } => IndexMetadata::new_backfilling_vector_index(
@@ -989,6 +1011,20 @@ impl<'a, RT: Runtime> IndexModel<'a, RT> {
                     vector_field,
                     dimensions,
                     filter_fields,
+                    distance_metric,
+                    quantization,
+                ),
+                IndexConfig::SparseVector {
+                    developer_config:
+                        DeveloperSparseVectorIndexConfig {
+                            vector_field,
+                            filter_fields,
+                        },
+                    ..
+                } => IndexMetadata::new_backfilling_sparse_vector_index(
+                    index_name,
+                    vector_field,
+                    filter_fields,
                 ),
             };
             SystemMetadataModel::new_global(self.tx)
@@ -1,6 +1,9 @@
 use std::{
     cmp,
-    collections::BTreeSet,
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
     ops::Bound,
     sync::Arc,
     time::Duration,
@@ -337,6 +340,7 @@ impl<RT: Runtime> Committer<RT> {
                             queue_timer,
                             transaction,
                             result,
+                            stale_merges,
                             write_source,
                             parent_trace,
                         }) => {
@@ -353,6 +357,7 @@ impl<RT: Runtime> Committer<RT> {
                             drop(queue_timer);
                             if let Some(persistence_write_future) = self.start_commit(transaction,
                                 result,
+                                stale_merges,
                                 write_source,
                                 parent_trace,
                                 commit_id,
@@ -682,7 +687,12 @@ impl<RT: Runtime> Committer<RT> {
         }
         timer.finish();
 
-        let updates: Vec<_> = transaction.writes.coalesced_writes().collect();
+        let merged_updates = self.apply_merges(&transaction, commit_ts)?;
+        let updates: Vec<_> = transaction
+            .writes
+            .coalesced_writes()
+            .chain(merged_updates.iter().map(|update| (&update.id, update)))
+            .collect();
         // The updates are ordered using table_dependency_sort_key,
         // which is the same order they should be applied to database metadata
         // and index data structures
@@ -723,6 +733,45 @@ impl<RT: Runtime> Committer<RT> {
         })
     }
 
+    /// Applies the transaction's merges to the latest revision of each document
+    /// it merges into: the latest pending write to the document if there is
+    /// one, and otherwise the base the client read. `start_commit` has already
+    /// checked that nothing wrote to the documents after the bases were read.
+    fn apply_merges(
+        &self,
+        transaction: &FinalTransaction,
+        commit_ts: Timestamp,
+    ) -> anyhow::Result<Vec<DocumentUpdateWithPrevTs>> {
+        let Some(merge_bases) = &transaction.merge_bases else {
+            return Ok(vec![]);
+        };
+        let mut latest = merge_bases.documents.clone();
+        for (ts, writes, _) in self.pending_writes.iter(merge_bases.ts.succ()?, commit_ts) {
+            for (id, update) in writes {
+                if let Some(revision) = latest.get_mut(id) {
+                    *revision = update.unpack().new_document.map(|document| (document, *ts));
+                }
+            }
+        }
+        let mut updates = vec![];
+        for (id, merges) in transaction.writes.merges() {
+            // The document was deleted after the transaction merged into it.
+            let Some(Some((old_document, old_ts))) = latest.remove(id) else {
+                anyhow::bail!(ErrorMetadata::system_occ());
+            };
+            let mut new_document = old_document.clone();
+            for merge in merges {
+                new_document = merge.apply(&new_document)?;
+            }
+            updates.push(DocumentUpdateWithPrevTs {
+                id: *id,
+                old_document: Some((old_document, old_ts)),
+                new_document: Some(new_document),
+            });
+        }
+        Ok(updates)
+    }
+
     #[fastrace::trace]
     fn compute_writes(
         &self,
@@ -869,6 +918,7 @@ impl<RT: Runtime> Committer<RT> {
         &mut self,
         transaction: FinalTransaction,
         result: oneshot::Sender<anyhow::Result<Timestamp>>,
+        stale_merges: oneshot::Sender<FinalTransaction>,
         write_source: WriteSource,
         parent_trace: EncodedSpan,
         commit_id: usize,
@@ -879,6 +929,22 @@ impl<RT: Runtime> Committer<RT> {
             let _ = result.send(Ok(*transaction.begin_timestamp));
             return None;
         }
+        // Send the transaction back to the client to read the bases of its
+        // merges again if any of them have been overwritten since.
+        if let Some(merge_bases_ts) = transaction.merge_bases.as_ref().map(|bases| bases.ts) {
+            let merged_ids: BTreeSet<_> = transaction.writes.merges().keys().copied().collect();
+            match self.log.has_writes_to(&merged_ids, merge_bases_ts) {
+                Ok(false) => {},
+                Ok(true) => {
+                    let _ = stale_merges.send(transaction);
+                    return None;
+                },
+                Err(e) => {
+                    let _ = result.send(Err(e));
+                    return None;
+                },
+            }
+        }
         let commit_timer = metrics::commit_timer();
         metrics::log_write_tx(&transaction);
 
@@ -1045,6 +1111,17 @@ impl<RT: Runtime> Committer<RT> {
     }
 }
 
+/// How many times `CommitterClient` reads the bases of a transaction's merges
+/// before giving up with an OCC error.
+const MAX_MERGE_ATTEMPTS: usize = 4;
+
+/// The latest revisions as of `ts` of the documents a transaction merges into,
+/// or `None` for documents that don't exist.
+pub(crate) struct MergeBases {
+    ts: Timestamp,
+    documents: BTreeMap<ResolvedDocumentId, Option<(ResolvedDocument, Timestamp)>>,
+}
+
 struct ValidatedDocumentWrite {
     commit_ts: Timestamp,
     id: InternalDocumentId,
@@ -1126,28 +1203,76 @@ impl CommitterClient {
         self.check_generated_ids(&transaction).await?;
 
         // Finish reading everything from persistence.
-        let transaction = transaction.finalize(self.snapshot_reader.clone()).await?;
+        let mut transaction = transaction.finalize(self.snapshot_reader.clone()).await?;
 
-        let queue_timer = metrics::commit_queue_timer();
-        let (tx, rx) = oneshot::channel();
-        let message = CommitterMessage::Commit {
-            queue_timer,
-            transaction,
-            result: tx,
-            write_source,
-            parent_trace: EncodedSpan::from_parent(),
-        };
-        self.sender.try_send(message).map_err(|e| match e {
-            TrySendError::Full(..) => metrics::committer_full_error().into(),
-            TrySendError::Closed(..) => metrics::shutdown_error(),
-        })?;
-        let Ok(result) = rx.await else {
-            anyhow::bail!(metrics::shutdown_error());
-        };
-        if let Err(e) = result {
-            return Err(recapture_stacktrace(e).await);
+        let mut merge_attempts = 0;
+        loop {
+            if !transaction.writes.merges().is_empty() {
+                transaction.merge_bases = Some(self.read_merge_bases(&transaction).await?);
+            }
+            let queue_timer = metrics::commit_queue_timer();
+            let (tx, rx) = oneshot::channel();
+            let (stale_merges_tx, stale_merges_rx) = oneshot::channel();
+            let message = CommitterMessage::Commit {
+                queue_timer,
+                transaction,
+                result: tx,
+                stale_merges: stale_merges_tx,
+                write_source: write_source.clone(),
+                parent_trace: EncodedSpan::from_parent(),
+            };
+            self.sender.try_send(message).map_err(|e| match e {
+                TrySendError::Full(..) => metrics::committer_full_error().into(),
+                TrySendError::Closed(..) => metrics::shutdown_error(),
+            })?;
+            let Ok(result) = rx.await else {
+                // The committer sends the transaction back instead of a result
+                // if the documents it merges into changed after we read them.
+                let Ok(stale) = stale_merges_rx.await else {
+                    anyhow::bail!(metrics::shutdown_error());
+                };
+                merge_attempts += 1;
+                if merge_attempts >= MAX_MERGE_ATTEMPTS {
+                    anyhow::bail!(ErrorMetadata::system_occ());
+                }
+                transaction = stale;
+                continue;
+            };
+            if let Err(e) = result {
+                return Err(recapture_stacktrace(e).await);
+            }
+            return result;
         }
-        result
+    }
+
+    /// Reads the latest revisions of the documents `transaction` merges into.
+    async fn read_merge_bases(&self, transaction: &FinalTransaction) -> anyhow::Result<MergeBases> {
+        let ts = self.snapshot_reader.lock().latest_ts();
+        let repeatable_persistence = RepeatablePersistence::new(
+            self.persistence_reader.clone(),
+            ts,
+            self.retention_validator.clone(),
+        );
+        let next_ts = ts.succ()?;
+        let ids: BTreeSet<_> = transaction
+            .writes
+            .merges()
+            .keys()
+            .map(|id| (InternalDocumentId::from(*id), next_ts))
+            .collect();
+        let mut revisions = repeatable_persistence.previous_revisions(ids).await?;
+        let documents = transaction
+            .writes
+            .merges()
+            .keys()
+            .map(|id| {
+                let revision = revisions
+                    .remove(&(InternalDocumentId::from(*id), next_ts))
+                    .and_then(|entry| entry.value.map(|document| (document, entry.ts)));
+                (*id, revision)
+            })
+            .collect();
+        Ok(MergeBases { ts: *ts, documents })
     }
 
     pub fn shutdown(&self) {
@@ -1229,6 +1354,7 @@ enum CommitterMessage {
         queue_timer: Timer<VMHistogram>,
         transaction: FinalTransaction,
         result: oneshot::Sender<anyhow::Result<Timestamp>>,
+        stale_merges: oneshot::Sender<FinalTransaction>,
         write_source: WriteSource,
         parent_trace: EncodedSpan,
     },
@@ -31,6 +31,8 @@ use common::{
         components::ComponentMetadata,
         index::{
             database_index::IndexedFields,
+            vector_index::VectorDistanceMetric,
+            IndexConfig,
             IndexMetadata,
             TabletIndexMetadata,
             INDEX_TABLE,
@@ -58,6 +60,7 @@ use common::{
     knobs::{
         DEFAULT_DOCUMENTS_PAGE_SIZE,
         LIST_SNAPSHOT_MAX_AGE_SECS,
+        MAX_TRANSACTION_WINDOW,
     },
     persistence::{
         new_idle_repeatable_ts,
@@ -75,7 +78,10 @@ use common::{
         RetentionValidator,
         TimestampRange,
     },
-    query::Order,
+    query::{
+        Order,
+        SearchVersion,
+    },
     runtime::{
         RateLimiter,
         Runtime,
@@ -150,8 +156,12 @@ use value::{
 };
 use vector::{
     PublicVectorSearchQueryResult,
+    SparseVectorSearch,
     VectorIndexManager,
     VectorSearch,
+    VectorSearchCursor,
+    VectorSearchPage,
+    DEFAULT_VECTOR_LIMIT,
 };
 
 use crate::{
@@ -167,12 +177,17 @@ use crate::{
         bootstrap_system_tables,
         DEFAULT_BOOTSTRAP_TABLE_NUMBERS,
     },
+    hybrid_search::{
+        HybridSearch,
+        HybridSearchResult,
+    },
     metrics::{
         self,
         load_indexes_into_memory_timer,
         vector::vector_search_with_retries_timer,
         verify_invariants_timer,
     },
+    query::TableFilter,
     retention::LeaderRetentionManager,
     schema_registry::SchemaRegistry,
     search_index_bootstrap::SearchIndexBootstrapWorker,
@@ -202,6 +217,7 @@ use crate::{
     transaction_index::{
         TextIndexManagerSnapshot,
         TransactionIndex,
+        VectorIndexManagerSnapshot,
     },
     write_log::{
         new_write_log,
@@ -212,11 +228,14 @@ use crate::{
     ComponentRegistry,
     ComponentsTable,
     FollowerRetentionManager,
+    IndexModel,
     SchemasTable,
     TableIterator,
     Transaction,
     TransactionReadSet,
     TransactionTextSnapshot,
+    TransactionVectorSnapshot,
+    UserFacingModel,
     COMPONENTS_TABLE,
     SCHEMAS_TABLE,
 };
@@ -872,6 +891,7 @@ impl<RT: Runtime> DatabaseSnapshot<RT> {
         &self,
         identity: Identity,
         text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+        vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
         usage_tracker: FunctionUsageTracker,
         virtual_system_mapping: VirtualSystemMapping,
     ) -> anyhow::Result<Transaction<RT>> {
@@ -889,6 +909,7 @@ impl<RT: Runtime> DatabaseSnapshot<RT> {
             self.snapshot.index_registry.clone(),
             database_index_snapshot,
             text_index_snapshot,
+            vector_index_snapshot,
         );
         Ok(Transaction::new(
             identity,
@@ -1670,11 +1691,17 @@ impl<RT: Runtime> Database<RT> {
                 .read_snapshot(repeatable_ts)?,
             ),
             Arc::new(TextIndexManagerSnapshot::new(
-                snapshot.index_registry,
+                snapshot.index_registry.clone(),
                 snapshot.text_indexes,
                 self.searcher.clone(),
                 self.search_storage.clone(),
             )),
+            Arc::new(VectorIndexManagerSnapshot::new(
+                snapshot.index_registry,
+                snapshot.vector_indexes,
+                self.searcher.clone(),
+                self.search_storage.clone(),
+            )),
         );
         let count_snapshot = Arc::new(snapshot.table_summaries);
         let tx = Transaction::new(
@@ -2119,15 +2146,46 @@ impl<RT: Runtime> Database<RT> {
 
     pub async fn vector_search(
         &self,
-        _identity: Identity,
+        identity: Identity,
         query: VectorSearch,
     ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)> {
+        let (page, usage) = self.vector_search_page(identity, query).await?;
+        Ok((page.results, usage))
+    }
+
+    /// Run one page of a vector search. Searches continuing from a cursor are
+    /// read at the cursor's timestamp so every page sees the same snapshot.
+    pub async fn vector_search_page(
+        &self,
+        _identity: Identity,
+        query: VectorSearch,
+    ) -> anyhow::Result<(VectorSearchPage, FunctionUsageStats)> {
         let mut last_error = None;
         let mut backoff = Backoff::new(INITIAL_VECTOR_BACKOFF, MAX_VECTOR_BACKOFF);
         let timer = vector_search_with_retries_timer();
         while backoff.failures() < MAX_VECTOR_ATTEMPTS {
-            let ts = self.now_ts_for_reads();
-            match self.vector_search_at_ts(query.clone(), ts).await {
+            let now = self.now_ts_for_reads();
+            let ts = match query.cursor {
+                Some(ref cursor) => now.prior_ts(cursor.ts).context(ErrorMetadata::bad_request(
+                    "InvalidVectorSearchCursor",
+                    "Vector search cursor is from the future.",
+                ))?,
+                None => now,
+            };
+            match self.vector_search_page_at_ts(query.clone(), ts).await {
+                // Every page is read at the first page's snapshot, which is only
+                // retained for `MAX_TRANSACTION_WINDOW`.
+                Err(e) if query.cursor.is_some() && e.is_out_of_retention() => {
+                    timer.finish(false);
+                    anyhow::bail!(ErrorMetadata::bad_request(
+                        "VectorSearchCursorExpired",
+                        format!(
+                            "Vector search cursor expired. Cursors are valid for {} seconds after \
+                             the first page; restart the search without a cursor.",
+                            MAX_TRANSACTION_WINDOW.as_secs(),
+                        ),
+                    ));
+                },
                 Err(e) => {
                     // If backend hasn't loaded the in-memory index yet, it returns
                     // overloaded. We want to retry those.
@@ -2154,7 +2212,7 @@ impl<RT: Runtime> Database<RT> {
                 },
             }
         }
-        let last_error = last_error.expect("Exited vector_search() loop without any failure");
+        let last_error = last_error.expect("Exited vector_search_page() loop without any failure");
         timer.finish(false);
         Err(last_error)
     }
@@ -2164,6 +2222,15 @@ impl<RT: Runtime> Database<RT> {
         query: VectorSearch,
         ts: RepeatableTimestamp,
     ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)> {
+        let (page, usage) = self.vector_search_page_at_ts(query, ts).await?;
+        Ok((page.results, usage))
+    }
+
+    pub async fn vector_search_page_at_ts(
+        &self,
+        query: VectorSearch,
+        ts: RepeatableTimestamp,
+    ) -> anyhow::Result<(VectorSearchPage, FunctionUsageStats)> {
         let timer = metrics::vector::vector_search_timer();
         let usage = FunctionUsageTracker::new();
         let snapshot = self.snapshot(ts)?;
@@ -2172,7 +2239,11 @@ impl<RT: Runtime> Database<RT> {
             .table_mapping()
             .namespace(TableNamespace::from(component_id));
         if !table_mapping.name_exists(query.index_name.table()) {
-            return Ok((vec![], usage.gather_user_stats()));
+            let page = VectorSearchPage {
+                results: vec![],
+                continue_cursor: None,
+            };
+            return Ok((page, usage.gather_user_stats()));
         }
         let table_number = table_mapping.id(query.index_name.table())?.table_number;
         let index_name = query
@@ -2182,9 +2253,19 @@ impl<RT: Runtime> Database<RT> {
         let index = snapshot
             .index_registry
             .require_enabled(&index_name, &query.index_name)?;
+        let IndexConfig::Vector {
+            ref developer_config,
+            ..
+        } = index.metadata.config
+        else {
+            anyhow::bail!("Vector search against non-vector index {index_name}");
+        };
+        let distance_metric = developer_config.distance_metric;
+        let page_limit = query.limit.unwrap_or(DEFAULT_VECTOR_LIMIT) as usize;
+        let offset = query.cursor.map_or(0, |c| c.offset);
         let resolved: vector::InternalVectorSearch = query.resolve(&table_mapping)?;
         let search_storage = self.search_storage();
-        let results: Vec<_> = snapshot
+        let internal_results = snapshot
             .vector_indexes
             .vector_search(
                 &index,
@@ -2192,9 +2273,18 @@ impl<RT: Runtime> Database<RT> {
                 self.searcher.clone(),
                 search_storage.clone(),
             )
-            .await?
+            .await?;
+        // A short page means there's nothing left past it.
+        let continue_cursor =
+            match internal_results.last() {
+                Some(last) if internal_results.len() >= page_limit => Some(
+                    VectorSearchCursor::after(*ts, offset + internal_results.len() as u32, last),
+                ),
+                _ => None,
+            };
+        let results: Vec<_> = internal_results
             .into_iter()
-            .map(|r| r.to_public(table_number))
+            .map(|r| r.to_public(table_number, distance_metric))
             .collect();
         let size: u64 = results.iter().map(|row| row.size() as u64).sum();
         let component_path = snapshot
@@ -2208,6 +2298,182 @@ impl<RT: Runtime> Database<RT> {
             false,
         );
         timer.finish();
+        let page = VectorSearchPage {
+            results,
+            continue_cursor,
+        };
+        Ok((page, usage.gather_user_stats()))
+    }
+
+    pub async fn sparse_vector_search(
+        &self,
+        _identity: Identity,
+        query: SparseVectorSearch,
+    ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)> {
+        let mut backoff = Backoff::new(INITIAL_VECTOR_BACKOFF, MAX_VECTOR_BACKOFF);
+        loop {
+            let ts = self.now_ts_for_reads();
+            match self.sparse_vector_search_at_ts(query.clone(), ts).await {
+                // Like vector search, retry while the in-memory indexes are loading.
+                Err(e) if e.is_overloaded() && backoff.failures() + 1 < MAX_VECTOR_ATTEMPTS => {
+                    let delay = backoff.fail(&mut self.runtime.rng());
+                    tracing::warn!("Retrying sparse vector search error: {e}");
+                    self.runtime.wait(delay).await;
+                },
+                result => return result,
+            }
+        }
+    }
+
+    /// Run a sparse vector search, scoring documents by the dot product of
+    /// their sparse vector with the query's.
+    pub async fn sparse_vector_search_at_ts(
+        &self,
+        query: SparseVectorSearch,
+        ts: RepeatableTimestamp,
+    ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)> {
+        let usage = FunctionUsageTracker::new();
+        let snapshot = self.snapshot(ts)?;
+        let component_id = query.component_id;
+        let table_mapping = snapshot
+            .table_mapping()
+            .namespace(TableNamespace::from(component_id));
+        if !table_mapping.name_exists(query.index_name.table()) {
+            return Ok((vec![], usage.gather_user_stats()));
+        }
+        let table_number = table_mapping.id(query.index_name.table())?.table_number;
+        let index_name = query
+            .index_name
+            .clone()
+            .to_resolved(table_mapping.name_to_tablet())?;
+        let index = snapshot
+            .index_registry
+            .require_enabled(&index_name, &query.index_name)?;
+        let resolved = query.resolve(&table_mapping)?;
+        let internal_results = snapshot
+            .vector_indexes
+            .sparse_vector_search(
+                &index,
+                resolved,
+                self.searcher.clone(),
+                self.search_storage(),
+            )
+            .await?;
+        let results: Vec<_> = internal_results
+            .into_iter()
+            .map(|r| r.to_public(table_number, VectorDistanceMetric::DotProduct))
+            .collect();
+        let size: u64 = results.iter().map(|row| row.size() as u64).sum();
+        let component_path = snapshot
+            .component_registry
+            .must_component_path(component_id, &mut TransactionReadSet::new())?;
+        usage.track_vector_egress_size(
+            component_path,
+            table_mapping.tablet_name(*index_name.table())?.to_string(),
+            size,
+            false,
+        );
+        Ok((results, usage.gather_user_stats()))
+    }
+
+    pub async fn hybrid_search(
+        &self,
+        identity: Identity,
+        query: HybridSearch,
+    ) -> anyhow::Result<(Vec<HybridSearchResult>, FunctionUsageStats)> {
+        let mut backoff = Backoff::new(INITIAL_VECTOR_BACKOFF, MAX_VECTOR_BACKOFF);
+        loop {
+            let ts = self.now_ts_for_reads();
+            match self
+                .hybrid_search_at_ts(identity.clone(), query.clone(), ts)
+                .await
+            {
+                // Like vector search, retry while the in-memory indexes are loading.
+                Err(e) if e.is_overloaded() && backoff.failures() + 1 < MAX_VECTOR_ATTEMPTS => {
+                    let delay = backoff.fail(&mut self.runtime.rng());
+                    tracing::warn!("Retrying hybrid search error: {e}");
+                    self.runtime.wait(delay).await;
+                },
+                result => return result,
+            }
+        }
+    }
+
+    /// Run the text and vector halves of a hybrid search at the same
+    /// timestamp, fuse their rankings, and load the top documents.
+    pub async fn hybrid_search_at_ts(
+        &self,
+        identity: Identity,
+        query: HybridSearch,
+        ts: RepeatableTimestamp,
+    ) -> anyhow::Result<(Vec<HybridSearchResult>, FunctionUsageStats)> {
+        let timer = metrics::search::hybrid_search_timer();
+        let HybridSearch {
+            text,
+            vector,
+            fusion,
+            limit,
+        } = query;
+        if text.index_name.table() != vector.index_name.table() {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "HybridSearchTableMismatch",
+                format!(
+                    "Hybrid search text index {} and vector index {} must be on the same table.",
+                    text.index_name, vector.index_name,
+                ),
+            ));
+        }
+        let namespace = TableNamespace::from(vector.component_id);
+        let usage = FunctionUsageTracker::new();
+        let (vector_results, vector_usage) = self.vector_search_at_ts(vector, ts).await?;
+        usage.add(vector_usage);
+
+        let mut tx = self
+            .begin_with_repeatable_ts(identity, ts, usage.clone())
+            .await?;
+        let stable_index_name = IndexModel::new(&mut tx).stable_index_name(
+            namespace,
+            &text.index_name,
+            TableFilter::ExcludePrivateSystemTables,
+        )?;
+        let Some(tablet_index_name) = stable_index_name.tablet_index_name() else {
+            timer.finish();
+            return Ok((vec![], usage.gather_user_stats()));
+        };
+        let table_number = tx
+            .table_mapping()
+            .tablet_number(*tablet_index_name.table())?;
+        let text_results = tx
+            .search(&stable_index_name, &text, SearchVersion::V2)
+            .await?
+            .into_iter()
+            .map(|(candidate, _)| {
+                (
+                    DeveloperDocumentId::new(table_number, candidate.id),
+                    candidate.score,
+                )
+            });
+        let vector_results = vector_results
+            .into_iter()
+            .map(|result| (result.id, result.score));
+
+        let mut results = vec![];
+        for fused in fusion.fuse(text_results, vector_results, limit) {
+            let Some(document) = UserFacingModel::new(&mut tx, namespace)
+                .get(fused.id, None)
+                .await?
+            else {
+                continue;
+            };
+            results.push(HybridSearchResult {
+                id: fused.id,
+                score: fused.score,
+                text: fused.text,
+                vector: fused.vector,
+                document,
+            });
+        }
+        timer.finish();
         Ok((results, usage.gather_user_stats()))
     }
 
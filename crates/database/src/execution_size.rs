@@ -1,3 +1,13 @@
+use common::{
+    knobs::{
+        FUNCTION_MAX_READ_SIZE_BYTES,
+        FUNCTION_MAX_READ_SIZE_ROWS,
+        TRANSACTION_MAX_READ_SIZE_BYTES,
+        TRANSACTION_MAX_READ_SIZE_ROWS,
+    },
+    types::FunctionLimits,
+};
+
 use crate::{
     TransactionReadSize,
     TransactionWriteSize,
@@ -10,3 +20,38 @@ pub struct FunctionExecutionSize {
     pub write_size: TransactionWriteSize,
     pub scheduled_size: TransactionWriteSize,
 }
+
+/// How much a function execution can read from user tables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReadLimits {
+    pub max_documents: usize,
+    pub max_bytes: usize,
+    /// Set if the limits came from the function's config rather than the
+    /// deployment defaults, so errors can say where the limit came from.
+    pub from_function_config: bool,
+}
+
+impl ReadLimits {
+    pub fn new(limits: &FunctionLimits) -> Self {
+        Self {
+            max_documents: limits
+                .max_documents_read
+                .map_or(*TRANSACTION_MAX_READ_SIZE_ROWS, |n| {
+                    (n as usize).min(*FUNCTION_MAX_READ_SIZE_ROWS)
+                }),
+            max_bytes: limits
+                .max_bytes_read
+                .map_or(*TRANSACTION_MAX_READ_SIZE_BYTES, |n| {
+                    (n as usize).min(*FUNCTION_MAX_READ_SIZE_BYTES)
+                }),
+            from_function_config: limits.max_documents_read.is_some()
+                || limits.max_bytes_read.is_some(),
+        }
+    }
+}
+
+impl Default for ReadLimits {
+    fn default() -> Self {
+        Self::new(&FunctionLimits::default())
+    }
+}
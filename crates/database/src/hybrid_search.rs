@@ -0,0 +1,129 @@
+//! Hybrid search runs a text query and a vector query against the same table
+//! and fuses their results with reciprocal rank fusion.
+
+use common::{
+    components::ComponentId,
+    document::DeveloperDocument,
+    query::Search,
+};
+use errors::ErrorMetadata;
+use search::{
+    hybrid::{
+        ComponentScore,
+        ReciprocalRankFusion,
+    },
+    DEFAULT_RRF_K,
+    MAX_HYBRID_SEARCH_RESULTS,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    json,
+    Value as JsonValue,
+};
+use value::id_v6::DeveloperDocumentId;
+use vector::{
+    VectorSearch,
+    VectorSearchJson,
+};
+
+/// The default number of fused results to return.
+const DEFAULT_HYBRID_SEARCH_LIMIT: usize = 10;
+
+#[derive(Clone, Debug)]
+pub struct HybridSearch {
+    /// A text query against a search index on the same table as `vector`.
+    pub text: Search,
+    pub vector: VectorSearch,
+    pub fusion: ReciprocalRankFusion,
+    /// The maximum number of fused results to return.
+    pub limit: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HybridSearchJson {
+    text: JsonValue,
+    vector: VectorSearchJson,
+    fusion: Option<HybridSearchFusionJson>,
+    limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HybridSearchFusionJson {
+    k: Option<f64>,
+    text_weight: Option<f64>,
+    vector_weight: Option<f64>,
+}
+
+impl HybridSearchJson {
+    /// See [`VectorSearchJson::insert_component_id`]. The text query runs in
+    /// the same component as the vector query.
+    pub fn insert_component_id(&mut self, component_id: ComponentId) {
+        self.vector.insert_component_id(component_id);
+    }
+}
+
+impl TryFrom<JsonValue> for HybridSearch {
+    type Error = anyhow::Error;
+
+    fn try_from(value: JsonValue) -> anyhow::Result<Self> {
+        let json: HybridSearchJson = serde_json::from_value(value)?;
+        let fusion = match json.fusion {
+            None => ReciprocalRankFusion::default(),
+            Some(fusion) => ReciprocalRankFusion::new(
+                fusion.k.unwrap_or(DEFAULT_RRF_K),
+                fusion.text_weight.unwrap_or(1.0),
+                fusion.vector_weight.unwrap_or(1.0),
+            )?,
+        };
+        let limit = match json.limit {
+            None => DEFAULT_HYBRID_SEARCH_LIMIT,
+            Some(limit) => limit as usize,
+        };
+        if limit == 0 || limit > MAX_HYBRID_SEARCH_RESULTS {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidHybridSearchLimit",
+                format!(
+                    "Hybrid search limit must be between 1 and {MAX_HYBRID_SEARCH_RESULTS}. \
+                     Actual: {limit}"
+                ),
+            ));
+        }
+        Ok(Self {
+            text: Search::try_from(json.text)?,
+            vector: VectorSearch::try_from(json.vector)?,
+            fusion,
+            limit,
+        })
+    }
+}
+
+/// A document from a hybrid search along with its fused score and its rank
+/// and score in each of the text and vector results it appeared in.
+#[derive(Clone, Debug)]
+pub struct HybridSearchResult {
+    pub id: DeveloperDocumentId,
+    pub score: f64,
+    pub text: Option<ComponentScore>,
+    pub vector: Option<ComponentScore>,
+    pub document: DeveloperDocument,
+}
+
+impl From<HybridSearchResult> for JsonValue {
+    fn from(result: HybridSearchResult) -> Self {
+        let component_json = |component: Option<ComponentScore>| {
+            component.map(|c| json!({ "rank": c.rank as f64, "score": c.score }))
+        };
+        json!({
+            "_id": result.id.encode(),
+            "_score": result.score,
+            "textScore": component_json(result.text),
+            "vectorScore": component_json(result.vector),
+            "document": result.document.to_internal_json(),
+        })
+    }
+}
@@ -166,6 +166,7 @@ impl<RT: Runtime, T: SearchIndex + 'static> SearchFlusher<RT, T> {
     fn index_type_name(&self) -> &'static str {
         match Self::search_type() {
             SearchType::Vector => "vector",
+            SearchType::SparseVector => "sparse_vector",
             SearchType::Text => "text",
         }
     }
@@ -45,6 +45,17 @@ use crate::{
         writer::SearchIndexMetadataWriter,
         FlusherType,
     },
+    sparse_vector_index_worker::{
+        compactor::{
+            new_sparse_vector_compactor,
+            SparseVectorIndexCompactor,
+        },
+        flusher::{
+            new_sparse_vector_flusher,
+            SparseVectorIndexFlusher,
+        },
+        BuildSparseVectorIndexArgs,
+    },
     text_index_worker::{
         compactor::{
             new_text_compactor,
@@ -70,7 +81,7 @@ use crate::{
     VectorIndexFlusher,
 };
 
-/// Builds and compacts text/vector search indexes.
+/// Builds and compacts text, vector and sparse vector search indexes.
 pub struct SearchIndexWorkers {
     handles: Vec<Box<dyn SpawnHandle>>,
 }
@@ -78,6 +89,8 @@ pub struct SearchIndexWorkers {
 enum SearchIndexWorker<RT: Runtime> {
     VectorFlusher(VectorIndexFlusher<RT>),
     VectorCompactor(VectorIndexCompactor<RT>),
+    SparseVectorFlusher(SparseVectorIndexFlusher<RT>),
+    SparseVectorCompactor(SparseVectorIndexCompactor<RT>),
     TextFlusher(TextIndexFlusher<RT>),
     TextCompactor(TextIndexCompactor<RT>),
 }
@@ -113,6 +126,13 @@ impl SearchIndexWorkers {
                 full_scan_threshold_bytes: *MULTI_SEGMENT_FULL_SCAN_THRESHOLD_KB,
             },
         );
+        let sparse_vector_index_metadata_writer = SearchIndexMetadataWriter::new(
+            runtime.clone(),
+            database.clone(),
+            reader.clone(),
+            search_storage.clone(),
+            BuildSparseVectorIndexArgs,
+        );
         let text_index_metadata_writer = TextIndexMetadataWriter::new(
             runtime.clone(),
             database.clone(),
@@ -172,6 +192,54 @@ impl SearchIndexWorkers {
                 vector_index_metadata_writer,
             )),
         );
+        let sparse_vector_live_flush = retry_loop_expect_occs_and_overloaded(
+            "SparseVectorLiveFlusher",
+            runtime.clone(),
+            database.clone(),
+            // Sparse indexes bootstrap along with dense vector indexes.
+            Duration::from_secs(5),
+            *INDEX_WORKERS_INITIAL_BACKOFF,
+            *SEARCH_INDEX_FLUSHER_MAX_BACKOFF,
+            SearchIndexWorker::SparseVectorFlusher(new_sparse_vector_flusher(
+                runtime.clone(),
+                database.clone(),
+                reader.clone(),
+                search_storage.clone(),
+                sparse_vector_index_metadata_writer.clone(),
+                FlusherType::LiveFlush,
+            )),
+        );
+        let sparse_vector_backfill_flush = retry_loop_expect_occs_and_overloaded(
+            "SparseVectorBackfillFlusher",
+            runtime.clone(),
+            database.clone(),
+            Duration::from_secs(5),
+            *INDEX_WORKERS_INITIAL_BACKOFF,
+            *SEARCH_INDEX_FLUSHER_MAX_BACKOFF,
+            SearchIndexWorker::SparseVectorFlusher(new_sparse_vector_flusher(
+                runtime.clone(),
+                database.clone(),
+                reader.clone(),
+                search_storage.clone(),
+                sparse_vector_index_metadata_writer.clone(),
+                FlusherType::Backfill,
+            )),
+        );
+        let sparse_vector_compact = retry_loop_expect_occs_and_overloaded(
+            "SparseVectorCompactor",
+            runtime.clone(),
+            database.clone(),
+            Duration::ZERO,
+            *SEARCH_COMPACTOR_INITIAL_BACKOFF,
+            *SEARCH_COMPACTOR_MAX_BACKOFF,
+            SearchIndexWorker::SparseVectorCompactor(new_sparse_vector_compactor(
+                database.clone(),
+                searcher.clone(),
+                search_storage.clone(),
+                CompactionConfig::default(),
+                sparse_vector_index_metadata_writer,
+            )),
+        );
         let text_live_flusher = SearchIndexWorker::TextFlusher(new_text_flusher(
             runtime.clone(),
             database.clone(),
@@ -229,6 +297,12 @@ impl SearchIndexWorkers {
             runtime.spawn("vector_backfill_flush", vector_backfill_flush);
         let vector_live_flush_handle = runtime.spawn("vector_live_flush", vector_live_flush);
         let vector_compact_handle = runtime.spawn("vector_compact", vector_compact);
+        let sparse_vector_backfill_flush_handle =
+            runtime.spawn("sparse_vector_backfill_flush", sparse_vector_backfill_flush);
+        let sparse_vector_live_flush_handle =
+            runtime.spawn("sparse_vector_live_flush", sparse_vector_live_flush);
+        let sparse_vector_compact_handle =
+            runtime.spawn("sparse_vector_compact", sparse_vector_compact);
         let text_live_flush_handle = runtime.spawn("text_live_flush", text_live_flush);
         let text_backfill_flush_handle = runtime.spawn("text_backfill_flush", text_backfill_flush);
         let text_compact_handle = runtime.spawn("text_compact", text_compact);
@@ -237,6 +311,9 @@ impl SearchIndexWorkers {
                 vector_backfill_flush_handle,
                 vector_live_flush_handle,
                 vector_compact_handle,
+                sparse_vector_backfill_flush_handle,
+                sparse_vector_live_flush_handle,
+                sparse_vector_compact_handle,
                 text_live_flush_handle,
                 text_backfill_flush_handle,
                 text_compact_handle,
@@ -256,6 +333,8 @@ impl<RT: Runtime> SearchIndexWorker<RT> {
         match self {
             Self::VectorFlusher(flusher) => flusher.step().boxed(),
             Self::VectorCompactor(compactor) => compactor.step().boxed(),
+            Self::SparseVectorFlusher(flusher) => flusher.step().boxed(),
+            Self::SparseVectorCompactor(compactor) => compactor.step().boxed(),
             Self::TextFlusher(flusher) => flusher.step().boxed(),
             Self::TextCompactor(compactor) => compactor.step().boxed(),
         }
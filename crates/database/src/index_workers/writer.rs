@@ -114,6 +114,7 @@ impl<RT: Runtime, T: SearchIndex> SearchIndexMetadataWriter<RT, T> {
                     1,
                     match T::search_type() {
                         SearchType::Vector => "vector_writer",
+                        SearchType::SparseVector => "sparse_vector_writer",
                         SearchType::Text => "text_writer",
                     },
                 ),
@@ -369,6 +370,7 @@ impl<RT: Runtime, T: SearchIndex> Inner<RT, T> {
                 tx,
                 match T::search_type() {
                     SearchType::Vector => "search_index_metadata_writer_write_vector",
+                    SearchType::SparseVector => "search_index_metadata_writer_write_sparse_vector",
                     SearchType::Text => "search_index_metadata_writer_write_text",
                 },
             )
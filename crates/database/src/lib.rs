@@ -17,6 +17,7 @@ mod bootstrap_model;
 mod committer;
 mod database;
 mod execution_size;
+pub mod hybrid_search;
 mod index_worker;
 mod index_workers;
 mod metrics;
@@ -28,6 +29,7 @@ pub mod reads;
 mod retention;
 mod search_index_bootstrap;
 mod snapshot_manager;
+pub mod sparse_vector_index_worker;
 mod stack_traces;
 pub mod streaming_export_selection;
 pub mod subscription;
@@ -58,7 +60,10 @@ pub mod test_helpers;
 pub mod tests;
 pub mod text_index_worker;
 pub use component_registry::ComponentRegistry;
-pub use execution_size::FunctionExecutionSize;
+pub use execution_size::{
+    FunctionExecutionSize,
+    ReadLimits,
+};
 pub use index_worker::IndexWorker;
 pub use patch::PatchValue;
 pub use preloaded::PreloadedIndexRange;
@@ -82,15 +87,23 @@ pub use transaction_index::{
     SearchNotEnabled,
     TextIndexManagerSnapshot,
     TransactionTextSnapshot,
+    TransactionVectorSnapshot,
+    VectorIndexManagerSnapshot,
 };
 pub use vector_index_worker::flusher::VectorIndexFlusher;
-pub use write_limits::BiggestDocumentWrites;
+pub use write_limits::{
+    BiggestDocumentWrites,
+    WriteLimits,
+};
 pub use write_log::{
     LogReader,
     WriteSource,
 };
 pub use writes::{
+    DocumentMerge,
+    DocumentMerges,
     DocumentWrite,
+    PendingMerge,
     TransactionWriteSize,
     Writes,
 };
@@ -299,6 +299,16 @@ pub fn log_missing_search_index_key_staleness() {
     log_counter(&DATABASE_MISSING_SEARCH_INDEX_KEY_STALENESS_TOTAL, 1);
 }
 
+register_convex_counter!(
+    DATABASE_MISSING_VECTOR_INDEX_KEY_STALENESS_TOTAL,
+    "Number of times a vector index was not found in DocumentIndexKeys when determining commit \
+     staleness"
+);
+pub fn log_missing_vector_index_key_staleness() {
+    // See comment in log_missing_index_key_staleness
+    log_counter(&DATABASE_MISSING_VECTOR_INDEX_KEY_STALENESS_TOTAL, 1);
+}
+
 register_convex_counter!(
     DATABASE_MISSING_INDEX_KEY_SUBSCRIPTIONS_TOTAL,
     "Number of times a database index was not found in DocumentIndexKeys when updating \
@@ -1061,6 +1071,16 @@ pub mod search {
     pub fn iterator_next_timer() -> StatusTimer {
         StatusTimer::new(&DATABASE_SEARCH_ITERATOR_NEXT_SECONDS)
     }
+
+    register_convex_histogram!(
+        DATABASE_HYBRID_SEARCH_QUERY_SECONDS,
+        "Time to run a single hybrid text and vector search, not including retries due to \
+         bootstrapping",
+        &STATUS_LABEL
+    );
+    pub fn hybrid_search_timer() -> StatusTimer {
+        StatusTimer::new(&DATABASE_HYBRID_SEARCH_QUERY_SECONDS)
+    }
 }
 
 pub mod vector {
@@ -56,4 +56,10 @@ impl PreloadedIndexRange {
         }
         Ok(result)
     }
+
+    /// Returns every document in the range without recording any reads, so
+    /// callers must not let the result affect the transaction's outcome.
+    pub fn documents_unrecorded(&self) -> impl Iterator<Item = &ResolvedDocument> {
+        self.range.values()
+    }
 }
@@ -68,6 +68,10 @@ mod limit;
 mod search_query;
 
 pub use index_range::soft_data_limit;
+pub use search_query::{
+    search_with_facets,
+    SearchWithFacets,
+};
 
 // Even in the presence of large prefetch hints, we should never fetch too much
 // data at once.
@@ -1,11 +1,16 @@
+use std::collections::BTreeSet;
+
+use anyhow::Context;
 use async_trait::async_trait;
 use common::{
+    bootstrap_model::index::IndexConfig,
     document::DeveloperDocument,
     index::IndexKeyBytes,
     knobs::TRANSACTION_MAX_READ_SIZE_BYTES,
     query::{
         CursorPosition,
         Search,
+        SearchFacets,
         SearchVersion,
     },
     runtime::Runtime,
@@ -24,7 +29,11 @@ use errors::ErrorMetadata;
 use indexing::index_registry::index_not_found_error;
 use search::{
     CandidateRevision,
+    FacetCountAggregator,
+    FacetCounts,
     MAX_CANDIDATE_REVISIONS,
+    MAX_FACET_FIELDS,
+    MAX_FACET_VALUES,
 };
 use tokio::task;
 use value::{
@@ -41,9 +50,11 @@ use super::{
     DeveloperIndexRangeResponse,
     QueryStream,
     QueryStreamNext,
+    TableFilter,
 };
 use crate::{
     metrics,
+    IndexModel,
     Transaction,
     UserFacingModel,
 };
@@ -150,6 +161,143 @@ impl SearchQuery {
     }
 }
 
+/// The most relevant results of a search query along with facet counts over
+/// all of its matches.
+#[derive(Debug)]
+pub struct SearchWithFacets {
+    /// The first `num_results` documents matching the search, in relevance
+    /// order.
+    pub results: Vec<DeveloperDocument>,
+    pub facets: Vec<FacetCounts>,
+    /// The search index only returns the `MAX_CANDIDATE_REVISIONS` most
+    /// relevant matches, so the facet counts don't cover every matching
+    /// document when this is set.
+    pub is_truncated: bool,
+}
+
+/// Run `search`, returning its first `num_results` results and the counts of
+/// the `facets.limit` most common values of each of `facets.fields` across
+/// every matching document.
+///
+/// The counts are computed from the search candidates themselves, so they're
+/// invalidated by the same reads as running the search and loading all of its
+/// results. Searches with more than `MAX_CANDIDATE_REVISIONS` matches are
+/// reported as truncated rather than failing.
+pub async fn search_with_facets<RT: Runtime>(
+    tx: &mut Transaction<RT>,
+    namespace: TableNamespace,
+    search: Search,
+    facets: &SearchFacets,
+    num_results: usize,
+    version: Option<Version>,
+    table_filter: TableFilter,
+) -> anyhow::Result<SearchWithFacets> {
+    if facets.fields.is_empty() || facets.fields.len() > MAX_FACET_FIELDS {
+        anyhow::bail!(ErrorMetadata::bad_request(
+            "InvalidFacetFieldsError",
+            format!(
+                "Search query against {} must facet between 1 and {} fields. Actual: {}",
+                search.index_name,
+                MAX_FACET_FIELDS,
+                facets.fields.len(),
+            )
+        ));
+    }
+    if facets.limit == 0 || facets.limit > MAX_FACET_VALUES {
+        anyhow::bail!(ErrorMetadata::bad_request(
+            "InvalidFacetLimitError",
+            format!(
+                "Search query against {} must request between 1 and {} facet values. Actual: {}",
+                search.index_name, MAX_FACET_VALUES, facets.limit,
+            )
+        ));
+    }
+    let unique_fields: BTreeSet<_> = facets.fields.iter().collect();
+    if unique_fields.len() != facets.fields.len() {
+        anyhow::bail!(ErrorMetadata::bad_request(
+            "DuplicateFacetFieldError",
+            format!(
+                "Search query against {} faceted the same field more than once",
+                search.index_name,
+            )
+        ));
+    }
+
+    let stable_index_name =
+        IndexModel::new(tx).stable_index_name(namespace, &search.index_name, table_filter)?;
+    if let Some(tablet_index_name) = stable_index_name.tablet_index_name() {
+        let index =
+            tx.index
+                .require_enabled(&mut tx.reads, tablet_index_name, &search.index_name)?;
+        if let IndexConfig::Text {
+            ref developer_config,
+            ..
+        } = index.metadata().config
+        {
+            for field_path in &facets.fields {
+                if !developer_config.filter_fields.contains(field_path) {
+                    anyhow::bail!(ErrorMetadata::bad_request(
+                        "FacetFieldNotFilterFieldError",
+                        format!(
+                            "Search query against {} can only facet filter fields of the index. \
+                             {} is not one of {:?}.",
+                            search.index_name,
+                            String::from(field_path.clone()),
+                            developer_config
+                                .filter_fields
+                                .iter()
+                                .map(|field| String::from(field.clone()))
+                                .collect::<Vec<_>>(),
+                        )
+                    ));
+                }
+            }
+        }
+    }
+
+    let query = SearchQuery::new(
+        stable_index_name,
+        search,
+        CursorInterval {
+            curr_exclusive: None,
+            end_inclusive: None,
+        },
+        version,
+    );
+    let mut candidates = query.search(tx).await?;
+    let is_truncated = candidates.candidates.len() >= MAX_CANDIDATE_REVISIONS;
+    let mut results = vec![];
+    let mut aggregators = facets
+        .fields
+        .iter()
+        .cloned()
+        .map(FacetCountAggregator::new)
+        .collect::<Vec<_>>();
+    // NB: Stop as soon as we run out of candidates rather than waiting for `next`
+    // to return `None`, since it errors after `MAX_CANDIDATE_REVISIONS` results.
+    while candidates.next_index < candidates.candidates.len() {
+        let (document, ..) = candidates
+            .next(tx)
+            .await?
+            .context("Search result iterator ended early")?;
+        for aggregator in &mut aggregators {
+            let value = document.value().get_path(aggregator.field_path());
+            aggregator.insert(value);
+        }
+        if results.len() < num_results {
+            results.push(document);
+        }
+    }
+    Ok(SearchWithFacets {
+        results,
+        facets: aggregators
+            .into_iter()
+            .map(|aggregator| aggregator.into_results(facets.limit))
+            .collect(),
+        is_truncated,
+    })
+}
+
 #[async_trait]
 impl QueryStream for SearchQuery {
     fn cursor_position(&self) -> &Option<CursorPosition> {
@@ -20,11 +20,7 @@ use common::{
         Interval,
         IntervalSet,
     },
-    knobs::{
-        TRANSACTION_MAX_READ_SET_INTERVALS,
-        TRANSACTION_MAX_READ_SIZE_BYTES,
-        TRANSACTION_MAX_READ_SIZE_ROWS,
-    },
+    knobs::TRANSACTION_MAX_READ_SET_INTERVALS,
     static_span,
     types::{
         PersistenceVersion,
@@ -44,6 +40,7 @@ use value::{
     TableName,
     TabletId,
 };
+use vector::VectorQueryReads;
 
 #[cfg(doc)]
 use crate::Transaction;
@@ -52,6 +49,7 @@ use crate::{
         ConflictingRead,
         ConflictingReadWithWriteSource,
     },
+    execution_size::ReadLimits,
     metrics,
     stack_traces::StackTrace,
     write_log::{
@@ -65,6 +63,9 @@ pub const OVER_LIMIT_HELP: &str = "Consider using smaller limits in your queries
                                    queries, or using indexed queries with a selective index range \
                                    expressions.";
 
+/// Appended to the limit in errors when a function set the limit itself.
+pub(crate) const FUNCTION_CONFIG_LIMIT_SOURCE: &str = ", set in the function's `limits` config";
+
 /// If set to 'true', then collect backtraces of every database read in order
 /// to help debug OCC errors. Collecting stack traces is expensive and should
 /// only be used in development.
@@ -90,11 +91,12 @@ impl HeapSize for IndexReads {
 pub struct ReadSet {
     indexed: WithHeapSize<BTreeMap<TabletIndexName, IndexReads>>,
     search: WithHeapSize<BTreeMap<TabletIndexName, SearchQueryReads>>,
+    vector: WithHeapSize<BTreeMap<TabletIndexName, VectorQueryReads>>,
 }
 
 impl HeapSize for ReadSet {
     fn heap_size(&self) -> usize {
-        self.indexed.heap_size() + self.search.heap_size()
+        self.indexed.heap_size() + self.search.heap_size() + self.vector.heap_size()
     }
 }
 
@@ -103,16 +105,19 @@ impl ReadSet {
         Self {
             indexed: WithHeapSize::default(),
             search: WithHeapSize::default(),
+            vector: WithHeapSize::default(),
         }
     }
 
     pub fn new(
         indexed: BTreeMap<TabletIndexName, IndexReads>,
         search: BTreeMap<TabletIndexName, SearchQueryReads>,
+        vector: BTreeMap<TabletIndexName, VectorQueryReads>,
     ) -> Self {
         Self {
             indexed: indexed.into(),
             search: search.into(),
+            vector: vector.into(),
         }
     }
 
@@ -133,13 +138,22 @@ impl ReadSet {
         self.search.iter()
     }
 
+    pub fn iter_vector(&self) -> impl Iterator<Item = (&TabletIndexName, &VectorQueryReads)> {
+        self.vector.iter()
+    }
+
     pub fn consume(
         self,
     ) -> (
         impl Iterator<Item = (TabletIndexName, IndexReads)>,
         impl Iterator<Item = (TabletIndexName, SearchQueryReads)>,
+        impl Iterator<Item = (TabletIndexName, VectorQueryReads)>,
     ) {
-        (self.indexed.into_iter(), self.search.into_iter())
+        (
+            self.indexed.into_iter(),
+            self.search.into_iter(),
+            self.vector.into_iter(),
+        )
     }
 
     /// Determine whether a mutation to a document overlaps with the read set.
@@ -191,6 +205,16 @@ impl ReadSet {
                 });
             }
         }
+
+        for (index, vector_reads) in iter_indexes_for_table(&self.vector, document.id().tablet_id) {
+            if vector_reads.overlaps_document(document) {
+                return Some(ConflictingRead {
+                    index: index.clone(),
+                    id: document.id(),
+                    stack_traces: None,
+                });
+            }
+        }
         None
     }
 
@@ -262,6 +286,22 @@ impl ReadSet {
             }
         }
 
+        // Vector indexes
+        for (index, vector_reads) in iter_indexes_for_table(&self.vector, id.tablet_id) {
+            let Some(DocumentIndexKeyValue::Vector(value)) = index_keys.get(index) else {
+                metrics::log_missing_vector_index_key_staleness();
+                continue;
+            };
+
+            if vector_reads.overlaps_vector_index_key_value(value) {
+                return Some(ConflictingRead {
+                    index: index.clone(),
+                    id,
+                    stack_traces: None,
+                });
+            }
+        }
+
         None
     }
 
@@ -378,6 +418,8 @@ pub struct TransactionReadSet {
 
     user_tx_size: TransactionReadSize,
     system_tx_size: TransactionReadSize,
+
+    limits: ReadLimits,
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -407,9 +449,14 @@ impl TransactionReadSet {
             num_intervals: 0,
             user_tx_size: TransactionReadSize::default(),
             system_tx_size: TransactionReadSize::default(),
+            limits: ReadLimits::default(),
         }
     }
 
+    pub fn set_limits(&mut self, limits: ReadLimits) {
+        self.limits = limits;
+    }
+
     pub fn into_read_set(self) -> ReadSet {
         self.read_set
     }
@@ -479,13 +526,16 @@ impl TransactionReadSet {
         user_tx_size: TransactionReadSize,
         system_tx_size: TransactionReadSize,
     ) {
-        let (index_reads, search_reads) = reads.consume();
+        let (index_reads, search_reads, vector_reads) = reads.consume();
         for (index_name, index_reads) in index_reads {
             self._record_indexed(index_name, index_reads.fields, index_reads.intervals.iter());
         }
         for (index_name, search_reads) in search_reads {
             self.record_search(index_name, search_reads);
         }
+        for (index_name, vector_reads) in vector_reads {
+            self.record_vector_search(index_name, vector_reads);
+        }
         self.num_intervals += num_intervals;
         self.user_tx_size += user_tx_size;
         self.system_tx_size += system_tx_size;
@@ -527,25 +577,31 @@ impl TransactionReadSet {
         tx_size.total_document_size += document_size;
 
         if !is_system_table {
+            let limits = &self.limits;
+            let limit_source = if limits.from_function_config {
+                FUNCTION_CONFIG_LIMIT_SOURCE
+            } else {
+                ""
+            };
             anyhow::ensure!(
-                tx_size.total_document_count <= *TRANSACTION_MAX_READ_SIZE_ROWS,
+                tx_size.total_document_count <= limits.max_documents,
                 ErrorMetadata::pagination_limit(
                     "TooManyDocumentsRead",
                     format!(
-                        "Too many documents read in a single function execution (limit: {}). \
-                         {OVER_LIMIT_HELP}",
-                        *TRANSACTION_MAX_READ_SIZE_ROWS,
+                        "Too many documents read in a single function execution (limit: \
+                         {}{limit_source}). {OVER_LIMIT_HELP}",
+                        limits.max_documents,
                     )
                 ),
             );
             anyhow::ensure!(
-                tx_size.total_document_size <= *TRANSACTION_MAX_READ_SIZE_BYTES,
+                tx_size.total_document_size <= limits.max_bytes,
                 ErrorMetadata::pagination_limit(
                     "TooManyBytesRead",
                     format!(
-                        "Too many bytes read in a single function execution (limit: {} bytes). \
-                         {OVER_LIMIT_HELP}",
-                        *TRANSACTION_MAX_READ_SIZE_BYTES,
+                        "Too many bytes read in a single function execution (limit: {} \
+                         bytes{limit_source}). {OVER_LIMIT_HELP}",
+                        limits.max_bytes,
                     )
                 ),
             );
@@ -613,6 +669,18 @@ impl TransactionReadSet {
         );
     }
 
+    pub fn record_vector_search(
+        &mut self,
+        index_name: TabletIndexName,
+        vector_reads: VectorQueryReads,
+    ) {
+        self.read_set.vector.mutate_entry_or_insert_with(
+            index_name,
+            VectorQueryReads::empty,
+            |existing_reads| existing_reads.merge(vector_reads),
+        );
+    }
+
     pub fn num_intervals(&self) -> usize {
         self.num_intervals
     }
@@ -669,6 +737,7 @@ impl proptest::arbitrary::Arbitrary for ReadSet {
             Self {
                 indexed: indexed.into(),
                 search: search.into(),
+                vector: WithHeapSize::default(),
             }
         })
     }
@@ -680,6 +749,7 @@ mod tests {
 
     use common::{
         assert_obj,
+        bootstrap_model::index::vector_index::VectorDistanceMetric,
         document::{
             CreationTime,
             PackedDocument,
@@ -708,6 +778,10 @@ mod tests {
         TextQueryTermRead,
     };
     use value::val;
+    use vector::{
+        VectorQueryRead,
+        VectorQueryReads,
+    };
 
     use super::TransactionReadSet;
     use crate::ReadSet;
@@ -1089,6 +1163,61 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_vector_search_reads() -> anyhow::Result<()> {
+        let mut reads = TransactionReadSet::new();
+        let mut id_generator = TestIdGenerator::new();
+        let table_name = "mytable".parse()?;
+        let table_id = id_generator.user_table_id(&table_name);
+        let index_name =
+            TabletIndexName::new(table_id.tablet_id, IndexDescriptor::new("vector_index")?)?;
+
+        reads.record_vector_search(
+            index_name.clone(),
+            VectorQueryReads::new(VectorQueryRead {
+                vector_field: FieldPath::from_str("embedding")?,
+                distance_metric: VectorDistanceMetric::Cosine,
+                vector: vec![1.0, 0.0].into(),
+                filter_conditions: vec![],
+                min_score: Some(0.5),
+            }),
+        );
+        let read_set = reads.into_read_set();
+
+        // A vector scoring above the last result could enter the results.
+        let close_doc = create_document_with_one_field(
+            id_generator.user_generate(&table_name),
+            "embedding",
+            val!([1.0, 0.2]),
+        )?;
+        assert_eq!(
+            read_set
+                .overlaps_document_for_test(
+                    &PackedDocument::pack(&close_doc),
+                    PersistenceVersion::default()
+                )
+                .unwrap()
+                .index,
+            index_name
+        );
+
+        // A vector scoring below it could not.
+        let far_doc = create_document_with_one_field(
+            id_generator.user_generate(&table_name),
+            "embedding",
+            val!([-1.0, 0.2]),
+        )?;
+        assert_eq!(
+            read_set.overlaps_document_for_test(
+                &PackedDocument::pack(&far_doc),
+                PersistenceVersion::default()
+            ),
+            None
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_search_filter_reads_empty_query() -> anyhow::Result<()> {
         let mut reads = TransactionReadSet::new();
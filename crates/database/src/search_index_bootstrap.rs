@@ -70,8 +70,11 @@ use value::{
 };
 use vector::{
     IndexState,
+    MemoryIndex,
+    MemorySparseVectorIndex,
     MemoryVectorIndex,
     QdrantSchema,
+    SparseVectorSchema,
     VectorIndexManager,
 };
 
@@ -98,6 +101,7 @@ const MAX_BACKOFF: Duration = Duration::from_secs(5);
 struct IndexesToBootstrap {
     table_to_text_indexes: BTreeMap<TabletId, Vec<TextIndexBootstrapData>>,
     table_to_vector_indexes: BTreeMap<TabletId, Vec<VectorIndexBootstrapData>>,
+    table_to_sparse_vector_indexes: BTreeMap<TabletId, Vec<SparseVectorIndexBootstrapData>>,
     /// Timestamp to walk the document log from to get all of the revisions
     /// since the last write to disk.
     oldest_index_ts: Timestamp,
@@ -115,6 +119,7 @@ impl IndexesToBootstrap {
         indexes_with_fast_forward_ts: Vec<(ParsedDocument<TabletIndexMetadata>, Option<Timestamp>)>,
     ) -> anyhow::Result<Self> {
         let mut table_to_vector_indexes: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        let mut table_to_sparse_vector_indexes: BTreeMap<_, Vec<_>> = BTreeMap::new();
         let mut table_to_text_indexes: BTreeMap<_, Vec<_>> = BTreeMap::new();
         // We keep track of latest ts we can bootstrap from for each vector index.
         let mut oldest_index_ts = *upper_bound;
@@ -129,24 +134,19 @@ impl IndexesToBootstrap {
                     ..
                 } => {
                     let qdrant_schema = QdrantSchema::new(developer_config);
-                    let ts = match on_disk_state {
-                        VectorIndexState::Backfilled {
-                            snapshot: ref snapshot_info,
-                            ..
-                        }
-                        | VectorIndexState::SnapshottedAt(ref snapshot_info) => {
-                            // Use fast forward ts instead of snapshot ts.
-                            let current_index_ts =
-                                max(fast_forward_ts.unwrap_or_default(), snapshot_info.ts);
-                            oldest_index_ts = min(oldest_index_ts, current_index_ts);
-                            snapshot_info.ts
-                        },
-                        VectorIndexState::Backfilling(_) => upper_bound.succ()?,
-                    };
+                    let ts = vector_memory_index_ts(
+                        &on_disk_state,
+                        fast_forward_ts,
+                        upper_bound,
+                        &mut oldest_index_ts,
+                    )?;
                     let vector_index_bootstrap_data = VectorIndexBootstrapData {
                         index_id: index_id.internal_id(),
                         on_disk_state,
-                        memory_index: MemoryVectorIndex::new(WriteTimestamp::Committed(ts.succ()?)),
+                        memory_index: MemoryVectorIndex::new(
+                            WriteTimestamp::Committed(ts.succ()?),
+                            developer_config.distance_metric,
+                        ),
                         qdrant_schema,
                     };
                     if let Some(vector_indexes) =
@@ -160,6 +160,28 @@ impl IndexesToBootstrap {
                         );
                     }
                 },
+                IndexConfig::SparseVector {
+                    on_disk_state,
+                    ref developer_config,
+                } => {
+                    let ts = vector_memory_index_ts(
+                        &on_disk_state,
+                        fast_forward_ts,
+                        upper_bound,
+                        &mut oldest_index_ts,
+                    )?;
+                    table_to_sparse_vector_indexes
+                        .entry(*index_metadata.name.table())
+                        .or_default()
+                        .push(SparseVectorIndexBootstrapData {
+                            index_id: index_id.internal_id(),
+                            on_disk_state,
+                            memory_index: MemorySparseVectorIndex::new(WriteTimestamp::Committed(
+                                ts.succ()?,
+                            )),
+                            schema: SparseVectorSchema::new(developer_config),
+                        });
+                },
                 IndexConfig::Text {
                     ref developer_config,
                     on_disk_state,
@@ -232,6 +254,7 @@ impl IndexesToBootstrap {
         Ok(Self {
             table_to_text_indexes,
             table_to_vector_indexes,
+            table_to_sparse_vector_indexes,
             oldest_index_ts,
         })
     }
@@ -240,6 +263,7 @@ impl IndexesToBootstrap {
         self.table_to_text_indexes
             .keys()
             .chain(self.table_to_vector_indexes.keys())
+            .chain(self.table_to_sparse_vector_indexes.keys())
             .copied()
             .collect()
     }
@@ -278,6 +302,14 @@ impl IndexesToBootstrap {
                     vector_index.update(&revision_pair)?;
                 }
             }
+            if let Some(sparse_vector_indexes_to_update) = self
+                .table_to_sparse_vector_indexes
+                .get_mut(&revision_pair.id.table())
+            {
+                for sparse_vector_index in sparse_vector_indexes_to_update {
+                    sparse_vector_index.update(&revision_pair)?;
+                }
+            }
             if let Some(text_indexes_to_update) = self
                 .table_to_text_indexes
                 .get_mut(&revision_pair.id.table())
@@ -343,11 +375,26 @@ impl IndexesToBootstrap {
                                  memory_index,
                                  qdrant_schema: _,
                              }| {
-                                (index_id, (on_disk_state, memory_index))
+                                (index_id, (on_disk_state, MemoryIndex::from(memory_index)))
                             },
                         )
                         .collect::<Vec<_>>()
                 })
+                .chain(
+                    self.table_to_sparse_vector_indexes
+                        .into_values()
+                        .flatten()
+                        .map(
+                            |SparseVectorIndexBootstrapData {
+                                 index_id,
+                                 on_disk_state,
+                                 memory_index,
+                                 schema: _,
+                             }| {
+                                (index_id, (on_disk_state, MemoryIndex::from(memory_index)))
+                            },
+                        ),
+                )
                 .collect(),
         );
         let vector_index_manager = VectorIndexManager { indexes };
@@ -435,6 +482,65 @@ impl VectorIndexBootstrapData {
     }
 }
 
+#[derive(Clone)]
+struct SparseVectorIndexBootstrapData {
+    index_id: IndexId,
+    on_disk_state: VectorIndexState,
+    memory_index: MemorySparseVectorIndex,
+    schema: SparseVectorSchema,
+}
+
+impl SparseVectorIndexBootstrapData {
+    fn update(&mut self, revision_pair: &RevisionPair) -> anyhow::Result<()> {
+        match self.memory_index.min_ts() {
+            WriteTimestamp::Pending => {
+                anyhow::bail!(
+                    "Found a pending write timestamp for sparse vector memory index created \
+                     during bootstrapping. This should always be a committed timestamp."
+                )
+            },
+            WriteTimestamp::Committed(ts) => {
+                // Skip updates for revision pairs that have already been written to disk.
+                if ts > revision_pair.ts() {
+                    return Ok(());
+                }
+            },
+        }
+        self.memory_index.update(
+            revision_pair.id.internal_id(),
+            WriteTimestamp::Committed(revision_pair.ts()),
+            revision_pair
+                .prev_document()
+                .and_then(|d| self.schema.index(d)),
+            revision_pair.document().and_then(|d| self.schema.index(d)),
+        )
+    }
+}
+
+/// Returns the timestamp a dense or sparse vector index's memory index starts
+/// after, lowering `oldest_index_ts` to cover the revisions it's missing.
+fn vector_memory_index_ts(
+    on_disk_state: &VectorIndexState,
+    fast_forward_ts: Option<Timestamp>,
+    upper_bound: RepeatableTimestamp,
+    oldest_index_ts: &mut Timestamp,
+) -> anyhow::Result<Timestamp> {
+    let ts = match on_disk_state {
+        VectorIndexState::Backfilled {
+            snapshot: snapshot_info,
+            ..
+        }
+        | VectorIndexState::SnapshottedAt(snapshot_info) => {
+            // Use fast forward ts instead of snapshot ts.
+            let current_index_ts = max(fast_forward_ts.unwrap_or_default(), snapshot_info.ts);
+            *oldest_index_ts = min(*oldest_index_ts, current_index_ts);
+            snapshot_info.ts
+        },
+        VectorIndexState::Backfilling(_) => upper_bound.succ()?,
+    };
+    Ok(ts)
+}
+
 /// Streams revision pairs for documents in the indexed tables.
 pub fn stream_revision_pairs_for_indexes<'a>(
     tables_with_indexes: &'a BTreeSet<TabletId>,
@@ -901,6 +1007,8 @@ mod tests {
             vector: vec![0.; 2],
             limit: None,
             expressions: btreeset![],
+            cursor: None,
+            score_threshold: None,
         };
         let (results, _usage_stats) = db.vector_search(Identity::system(), query).await?;
         Ok(results)
@@ -950,6 +1058,8 @@ mod tests {
             vector_field,
             (2u32).try_into()?,
             btreeset![filter_field],
+            Default::default(),
+            None,
         );
         Ok(metadata)
     }
@@ -1181,6 +1291,7 @@ mod tests {
             "test.by_text".parse()?,
             "searchField".parse()?,
             btreeset! {"filterField".parse()?},
+            vec![],
         );
         IndexModel::new(&mut tx)
             .add_application_index(TableNamespace::test_user(), index)
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+#[cfg(any(test, feature = "testing"))]
+use common::persistence::PersistenceReader;
+use common::runtime::Runtime;
+use search::searcher::Searcher;
+use storage::Storage;
+
+use crate::{
+    index_workers::{
+        search_compactor::{
+            CompactionConfig,
+            SearchIndexCompactor,
+        },
+        writer::SearchIndexMetadataWriter,
+    },
+    sparse_vector_index_worker::sparse_vector_meta::SparseVectorSearchIndex,
+    Database,
+};
+
+pub type SparseVectorIndexCompactor<RT> = SearchIndexCompactor<RT, SparseVectorSearchIndex>;
+
+pub(crate) fn new_sparse_vector_compactor<RT: Runtime>(
+    database: Database<RT>,
+    searcher: Arc<dyn Searcher>,
+    search_storage: Arc<dyn Storage>,
+    config: CompactionConfig,
+    writer: SearchIndexMetadataWriter<RT, SparseVectorSearchIndex>,
+) -> SparseVectorIndexCompactor<RT> {
+    SparseVectorIndexCompactor::new(database, searcher, search_storage, config, writer)
+}
+
+#[cfg(any(test, feature = "testing"))]
+pub async fn compact_sparse_vector_indexes_in_test<RT: Runtime>(
+    runtime: RT,
+    database: Database<RT>,
+    reader: Arc<dyn PersistenceReader>,
+    search_storage: Arc<dyn Storage>,
+    searcher: Arc<dyn Searcher>,
+) -> anyhow::Result<()> {
+    use super::BuildSparseVectorIndexArgs;
+
+    let writer = SearchIndexMetadataWriter::new(
+        runtime,
+        database.clone(),
+        reader,
+        search_storage.clone(),
+        BuildSparseVectorIndexArgs,
+    );
+    let compactor = new_sparse_vector_compactor(
+        database,
+        searcher,
+        search_storage,
+        CompactionConfig::default(),
+        writer,
+    );
+    compactor.step().await?;
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use common::{
+    knobs::VECTOR_INDEX_SIZE_SOFT_LIMIT,
+    persistence::PersistenceReader,
+    runtime::Runtime,
+};
+use storage::Storage;
+
+use super::sparse_vector_meta::BuildSparseVectorIndexArgs;
+use crate::{
+    index_workers::{
+        search_flusher::{
+            SearchFlusher,
+            SearchIndexLimits,
+        },
+        writer::SearchIndexMetadataWriter,
+        FlusherType,
+    },
+    sparse_vector_index_worker::sparse_vector_meta::SparseVectorSearchIndex,
+    Database,
+};
+
+pub type SparseVectorIndexFlusher<RT> = SearchFlusher<RT, SparseVectorSearchIndex>;
+
+/// Backfills all sparse vector indexes that are in a "backfilling" state.
+#[cfg(any(test, feature = "testing"))]
+pub async fn backfill_sparse_vector_indexes<RT: Runtime>(
+    runtime: RT,
+    database: Database<RT>,
+    reader: Arc<dyn PersistenceReader>,
+    storage: Arc<dyn Storage>,
+) -> anyhow::Result<()> {
+    for flusher_type in [FlusherType::Backfill, FlusherType::LiveFlush] {
+        let writer = SearchIndexMetadataWriter::new(
+            runtime.clone(),
+            database.clone(),
+            reader.clone(),
+            storage.clone(),
+            BuildSparseVectorIndexArgs,
+        );
+        let flusher = SearchFlusher::new(
+            runtime.clone(),
+            database.clone(),
+            reader.clone(),
+            storage.clone(),
+            SearchIndexLimits {
+                index_size_soft_limit: 0,
+                incremental_multipart_threshold_bytes: *VECTOR_INDEX_SIZE_SOFT_LIMIT,
+            },
+            writer,
+            BuildSparseVectorIndexArgs,
+            flusher_type,
+        );
+        flusher.step().await?;
+    }
+    Ok(())
+}
+
+pub(crate) fn new_sparse_vector_flusher<RT: Runtime>(
+    runtime: RT,
+    database: Database<RT>,
+    reader: Arc<dyn PersistenceReader>,
+    storage: Arc<dyn Storage>,
+    writer: SearchIndexMetadataWriter<RT, SparseVectorSearchIndex>,
+    flusher_type: FlusherType,
+) -> SparseVectorIndexFlusher<RT> {
+    SearchFlusher::new(
+        runtime,
+        database,
+        reader,
+        storage,
+        SearchIndexLimits {
+            index_size_soft_limit: *VECTOR_INDEX_SIZE_SOFT_LIMIT,
+            incremental_multipart_threshold_bytes: *VECTOR_INDEX_SIZE_SOFT_LIMIT,
+        },
+        writer,
+        BuildSparseVectorIndexArgs,
+        flusher_type,
+    )
+}
@@ -0,0 +1,8 @@
+pub mod compactor;
+pub mod flusher;
+mod sparse_vector_meta;
+
+pub use sparse_vector_meta::{
+    BuildSparseVectorIndexArgs,
+    SparseVectorSearchIndex,
+};
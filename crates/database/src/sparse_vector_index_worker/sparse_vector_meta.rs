@@ -0,0 +1,334 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::Arc,
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use common::{
+    bootstrap_model::index::{
+        sparse_vector_index::DeveloperSparseVectorIndexConfig,
+        vector_index::{
+            FragmentedVectorSegment,
+            VectorIndexBackfillState,
+            VectorIndexSnapshot,
+            VectorIndexState,
+        },
+        IndexConfig,
+        TabletIndexMetadata,
+    },
+    document::{
+        ParsedDocument,
+        ResolvedDocument,
+    },
+    persistence::{
+        DocumentStream,
+        RepeatablePersistence,
+    },
+    runtime::{
+        try_join_buffer_unordered,
+        Runtime,
+    },
+    types::IndexId,
+};
+use futures::TryStreamExt;
+use search::{
+    disk_index::upload_sparse_vector_segment,
+    fragmented_segment::{
+        MutableFragmentedSegmentMetadata,
+        PreviousVectorSegments,
+    },
+    metrics::SearchType,
+    Searcher,
+};
+use storage::Storage;
+use sync_types::Timestamp;
+use vector::{
+    qdrant_segments::VectorDiskSegmentValues,
+    SparseVectorSchema,
+};
+
+use crate::{
+    index_workers::{
+        index_meta::{
+            BackfillState,
+            SearchIndex,
+            SearchIndexConfig,
+            SearchOnDiskState,
+            SearchSnapshot,
+            SegmentType,
+            SnapshotData,
+        },
+        search_flusher::MultipartBuildType,
+    },
+    vector_index_worker::VectorStatistics,
+    Snapshot,
+};
+
+/// Sparse segments don't record how many entries their vectors have, so size
+/// estimates assume a typical number of (u32, f32) entries per vector.
+const ESTIMATED_ENTRIES_PER_VECTOR: u64 = 64;
+
+impl From<VectorIndexState> for SearchOnDiskState<SparseVectorSearchIndex> {
+    fn from(value: VectorIndexState) -> Self {
+        match value {
+            VectorIndexState::Backfilling(backfill_state) => {
+                SearchOnDiskState::Backfilling(backfill_state.into())
+            },
+            VectorIndexState::Backfilled { snapshot, staged } => SearchOnDiskState::Backfilled {
+                snapshot: snapshot.into(),
+                staged,
+            },
+            VectorIndexState::SnapshottedAt(snapshot) => {
+                SearchOnDiskState::SnapshottedAt(snapshot.into())
+            },
+        }
+    }
+}
+
+impl TryFrom<SearchOnDiskState<SparseVectorSearchIndex>> for VectorIndexState {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SearchOnDiskState<SparseVectorSearchIndex>) -> anyhow::Result<Self> {
+        Ok(match value {
+            SearchOnDiskState::Backfilling(state) => Self::Backfilling(state.into()),
+            SearchOnDiskState::Backfilled { snapshot, staged } => Self::Backfilled {
+                snapshot: snapshot.try_into()?,
+                staged,
+            },
+            SearchOnDiskState::SnapshottedAt(snapshot) => Self::SnapshottedAt(snapshot.try_into()?),
+        })
+    }
+}
+
+impl SegmentType<SparseVectorSearchIndex> for FragmentedVectorSegment {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn statistics(&self) -> anyhow::Result<VectorStatistics> {
+        let non_deleted_vectors = self.non_deleted_vectors()?;
+        Ok(VectorStatistics {
+            non_deleted_vectors,
+            num_vectors: self.num_vectors,
+        })
+    }
+
+    fn total_size_bytes(&self, _config: &DeveloperSparseVectorIndexConfig) -> anyhow::Result<u64> {
+        (self.num_vectors as u64)
+            .checked_mul(ESTIMATED_ENTRIES_PER_VECTOR)
+            .and_then(|value| value.checked_mul(8))
+            .context("Overflowed size calculation!")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseVectorSearchIndex;
+
+/// Sparse segments are always searched by walking their posting lists, so
+/// there's nothing to configure when building them.
+#[derive(Clone)]
+pub struct BuildSparseVectorIndexArgs;
+
+#[async_trait]
+impl SearchIndex for SparseVectorSearchIndex {
+    type BuildIndexArgs = BuildSparseVectorIndexArgs;
+    type DeveloperConfig = DeveloperSparseVectorIndexConfig;
+    type NewSegment = VectorDiskSegmentValues;
+    type PreviousSegments = PreviousVectorSegments;
+    type Schema = SparseVectorSchema;
+    type Segment = FragmentedVectorSegment;
+    type Statistics = VectorStatistics;
+
+    fn get_config(config: IndexConfig) -> Option<SearchIndexConfig<Self>> {
+        let IndexConfig::SparseVector {
+            on_disk_state,
+            developer_config,
+        } = config
+        else {
+            return None;
+        };
+        Some(SearchIndexConfig {
+            developer_config,
+            on_disk_state: SearchOnDiskState::from(on_disk_state),
+        })
+    }
+
+    fn get_index_sizes(snapshot: Snapshot) -> anyhow::Result<BTreeMap<IndexId, usize>> {
+        // Sparse indexes live in the vector index manager alongside dense ones.
+        Ok(snapshot
+            .vector_indexes
+            .backfilled_and_enabled_index_sizes()?
+            .collect())
+    }
+
+    fn is_version_current(snapshot: &SearchSnapshot<Self>) -> bool {
+        snapshot.data.is_version_current()
+    }
+
+    fn new_schema(config: &Self::DeveloperConfig) -> Self::Schema {
+        SparseVectorSchema::new(config)
+    }
+
+    async fn download_previous_segments(
+        storage: Arc<dyn Storage>,
+        segments: Vec<Self::Segment>,
+    ) -> anyhow::Result<Self::PreviousSegments> {
+        let segments = try_join_buffer_unordered(
+            "download_sparse_vector_metadata",
+            segments.into_iter().map(move |segment| {
+                MutableFragmentedSegmentMetadata::download(segment, storage.clone())
+            }),
+        )
+        .await?;
+        Ok(PreviousVectorSegments(segments))
+    }
+
+    async fn upload_previous_segments(
+        storage: Arc<dyn Storage>,
+        segments: Self::PreviousSegments,
+    ) -> anyhow::Result<Vec<Self::Segment>> {
+        try_join_buffer_unordered(
+            "upload_sparse_vector_metadata",
+            segments
+                .0
+                .into_iter()
+                .map(move |segment| segment.upload_deleted_bitset(storage.clone())),
+        )
+        .await
+    }
+
+    fn estimate_document_size(schema: &Self::Schema, doc: &ResolvedDocument) -> u64 {
+        schema.estimate_document_size(doc) as u64
+    }
+
+    async fn build_disk_index(
+        schema: &Self::Schema,
+        index_path: &PathBuf,
+        documents: DocumentStream<'_>,
+        _reader: RepeatablePersistence,
+        previous_segments: &mut Self::PreviousSegments,
+        _document_log_lower_bound: Option<Timestamp>,
+        _build_index_args: Self::BuildIndexArgs,
+        _multipart_build_type: MultipartBuildType,
+    ) -> anyhow::Result<Option<Self::NewSegment>> {
+        schema
+            .build_disk_index(index_path, documents, previous_segments)
+            .await
+    }
+
+    async fn upload_new_segment<RT: Runtime>(
+        rt: &RT,
+        storage: Arc<dyn Storage>,
+        new_segment: Self::NewSegment,
+    ) -> anyhow::Result<Self::Segment> {
+        upload_sparse_vector_segment(rt, storage, new_segment).await
+    }
+
+    fn extract_metadata(
+        metadata: ParsedDocument<TabletIndexMetadata>,
+    ) -> anyhow::Result<(Self::DeveloperConfig, SearchOnDiskState<Self>)> {
+        let (on_disk_state, developer_config) = match metadata.into_value().config {
+            IndexConfig::Database { .. }
+            | IndexConfig::Text { .. }
+            | IndexConfig::Vector { .. } => {
+                anyhow::bail!("Index type changed!");
+            },
+            IndexConfig::SparseVector {
+                on_disk_state,
+                developer_config,
+            } => (on_disk_state, developer_config),
+        };
+
+        Ok((developer_config, SearchOnDiskState::from(on_disk_state)))
+    }
+
+    fn new_index_config(
+        developer_config: Self::DeveloperConfig,
+        new_state: SearchOnDiskState<Self>,
+    ) -> anyhow::Result<IndexConfig> {
+        let on_disk_state = VectorIndexState::try_from(new_state)?;
+        Ok(IndexConfig::SparseVector {
+            on_disk_state,
+            developer_config,
+        })
+    }
+
+    fn search_type() -> SearchType {
+        SearchType::SparseVector
+    }
+
+    async fn execute_compaction(
+        searcher: Arc<dyn Searcher>,
+        search_storage: Arc<dyn Storage>,
+        _config: &Self::DeveloperConfig,
+        segments: Vec<Self::Segment>,
+    ) -> anyhow::Result<Self::Segment> {
+        let protos: Vec<pb::searchlight::FragmentedVectorSegmentPaths> = segments
+            .into_iter()
+            .map(|segment| segment.to_paths_proto())
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        searcher
+            .execute_sparse_vector_compaction(search_storage, protos)
+            .await
+    }
+
+    async fn merge_deletes(
+        previous_segments: &mut Self::PreviousSegments,
+        mut documents: DocumentStream<'_>,
+        _repeatable_persistence: &RepeatablePersistence,
+        _build_index_args: Self::BuildIndexArgs,
+        _schema: Self::Schema,
+        _document_log_lower_bound: Timestamp,
+    ) -> anyhow::Result<()> {
+        while let Some(entry) = documents.try_next().await? {
+            if entry.value.is_none() {
+                previous_segments.maybe_delete_convex(entry.id.internal_id())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<VectorIndexBackfillState> for BackfillState<SparseVectorSearchIndex> {
+    fn from(value: VectorIndexBackfillState) -> Self {
+        Self {
+            segments: value.segments,
+            cursor: value.cursor,
+            backfill_snapshot_ts: value.backfill_snapshot_ts,
+            staged: value.staged,
+        }
+    }
+}
+
+impl From<BackfillState<SparseVectorSearchIndex>> for VectorIndexBackfillState {
+    fn from(value: BackfillState<SparseVectorSearchIndex>) -> Self {
+        Self {
+            segments: value.segments,
+            cursor: value.cursor,
+            backfill_snapshot_ts: value.backfill_snapshot_ts,
+            staged: value.staged,
+        }
+    }
+}
+
+impl From<VectorIndexSnapshot> for SearchSnapshot<SparseVectorSearchIndex> {
+    fn from(snapshot: VectorIndexSnapshot) -> Self {
+        Self {
+            ts: snapshot.ts,
+            data: SnapshotData::from(snapshot.data),
+        }
+    }
+}
+
+impl TryFrom<SearchSnapshot<SparseVectorSearchIndex>> for VectorIndexSnapshot {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SearchSnapshot<SparseVectorSearchIndex>) -> anyhow::Result<Self> {
+        Ok(VectorIndexSnapshot {
+            data: value.data.try_into()?,
+            ts: value.ts,
+        })
+    }
+}
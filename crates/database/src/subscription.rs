@@ -64,6 +64,7 @@ use tokio::sync::{
     watch,
 };
 use value::ResolvedDocumentId;
+use vector::VectorSearchSubscriptions;
 
 use crate::{
     metrics::{
@@ -395,13 +396,15 @@ impl SubscriptionManager {
                     let total_subscribers: usize = subscribers_by_index.values().sum();
                     let search_len = self.subscriptions.search.filter_len();
                     let fuzzy_len = self.subscriptions.search.fuzzy_len();
+                    let vector_len = self.subscriptions.vector.len();
                     tracing::info!(
                         "[{next_ts} advance_log] Duration {}ms, indexes: {}, search filters: {}, \
-                         fuzzy search: {}",
+                         fuzzy search: {}, vector search: {}",
                         _timer.elapsed().as_millis(),
                         self.subscriptions.indexed.len(),
                         search_len,
-                        fuzzy_len
+                        fuzzy_len,
+                        vector_len
                     );
                     tracing::info!(
                         "`[{next_ts} advance_log] Subscription map size: {total_subscribers}"
@@ -478,6 +481,9 @@ impl SubscriptionManager {
         self.subscriptions
             .search
             .add_matches(document_id, document_index_keys, notify);
+        self.subscriptions
+            .vector
+            .add_matches(document_id, document_index_keys, notify);
     }
 
     fn get_subscriber(&self, key: SubscriptionKey) -> Option<&Subscriber> {
@@ -625,6 +631,7 @@ struct SubscriptionMap {
     // TODO: remove nesting, merge all IntervalMaps into one big data structure
     indexed: BTreeMap<TabletIndexName, (IndexedFields, IntervalMap)>,
     search: TextSearchSubscriptions,
+    vector: VectorSearchSubscriptions,
 }
 
 impl SubscriptionMap {
@@ -632,6 +639,7 @@ impl SubscriptionMap {
         Self {
             indexed: BTreeMap::new(),
             search: TextSearchSubscriptions::new(),
+            vector: VectorSearchSubscriptions::new(),
         }
     }
 
@@ -648,6 +656,9 @@ impl SubscriptionMap {
         for (index, reads) in reads.iter_search() {
             self.search.insert(id, index, reads);
         }
+        for (index, reads) in reads.iter_vector() {
+            self.vector.insert(id, index, reads);
+        }
     }
 
     fn remove(&mut self, id: SubscriberId, reads: &ReadSet) {
@@ -664,6 +675,9 @@ impl SubscriptionMap {
         for (index, reads) in reads.iter_search() {
             self.search.remove(id, index, reads);
         }
+        for (index, _) in reads.iter_vector() {
+            self.vector.remove(id, index);
+        }
     }
 }
 
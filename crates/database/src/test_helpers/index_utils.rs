@@ -85,7 +85,8 @@ pub fn assert_backfilling(
         IndexConfig::Text { on_disk_state, .. } => {
             assert_matches!(on_disk_state, TextIndexState::Backfilling(_))
         },
-        IndexConfig::Vector { on_disk_state, .. } => {
+        IndexConfig::Vector { on_disk_state, .. }
+        | IndexConfig::SparseVector { on_disk_state, .. } => {
             assert_matches!(on_disk_state, VectorIndexState::Backfilling(_))
         },
     }
@@ -107,7 +108,8 @@ pub async fn assert_backfilled(
         IndexConfig::Text { on_disk_state, .. } => {
             assert_matches!(on_disk_state, TextIndexState::Backfilled { .. })
         },
-        IndexConfig::Vector { on_disk_state, .. } => {
+        IndexConfig::Vector { on_disk_state, .. }
+        | IndexConfig::SparseVector { on_disk_state, .. } => {
             assert_matches!(on_disk_state, VectorIndexState::Backfilled { .. })
         },
     }
@@ -129,7 +131,8 @@ pub async fn assert_enabled(
         IndexConfig::Text { on_disk_state, .. } => {
             assert_matches!(on_disk_state, TextIndexState::SnapshottedAt(_))
         },
-        IndexConfig::Vector { on_disk_state, .. } => {
+        IndexConfig::Vector { on_disk_state, .. }
+        | IndexConfig::SparseVector { on_disk_state, .. } => {
             assert_matches!(on_disk_state, VectorIndexState::SnapshottedAt(_))
         },
     }
@@ -211,6 +214,14 @@ pub fn get_index_fields<T: IndexTableIdentifier>(index_metadata: IndexMetadata<T
             .iter()
             .map(|field| field.to_string())
             .collect(),
+        IndexConfig::SparseVector {
+            developer_config, ..
+        } => developer_config
+            .vector_field
+            .fields()
+            .iter()
+            .map(|field| field.to_string())
+            .collect(),
     }
 }
 
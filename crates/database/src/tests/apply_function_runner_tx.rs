@@ -81,6 +81,7 @@ async fn test_apply_function_runner_tx_new_table(rt: TestRuntime) -> anyhow::Res
         .map(|(table, stats)| (*table, stats.rows_read))
         .collect();
     let updates = function_runner_tx.writes.as_flat()?.clone().into_updates();
+    let merges = function_runner_tx.writes.as_flat()?.merges().clone();
     backend_tx.apply_function_runner_tx(
         *begin_timestamp,
         reads,
@@ -88,6 +89,7 @@ async fn test_apply_function_runner_tx_new_table(rt: TestRuntime) -> anyhow::Res
         user_tx_size,
         system_tx_size,
         updates,
+        merges,
         rows_read_by_tablet,
     )?;
     assert_eq!(
@@ -134,6 +136,7 @@ async fn test_apply_function_runner_tx_read_only(rt: TestRuntime) -> anyhow::Res
         .map(|(table, stats)| (*table, stats.rows_read))
         .collect();
     let updates = function_runner_tx.writes.as_flat()?.clone().into_updates();
+    let merges = function_runner_tx.writes.as_flat()?.merges().clone();
     backend_tx.apply_function_runner_tx(
         *begin_timestamp,
         reads,
@@ -141,6 +144,7 @@ async fn test_apply_function_runner_tx_read_only(rt: TestRuntime) -> anyhow::Res
         user_tx_size,
         system_tx_size,
         updates,
+        merges,
         rows_read_by_tablet,
     )?;
 
@@ -184,6 +188,7 @@ async fn test_apply_function_runner_tx_replace(rt: TestRuntime) -> anyhow::Resul
         .map(|(table, stats)| (*table, stats.rows_read))
         .collect();
     let updates = function_runner_tx.writes.as_flat()?.clone().into_updates();
+    let merges = function_runner_tx.writes.as_flat()?.merges().clone();
     backend_tx.apply_function_runner_tx(
         *begin_timestamp,
         reads,
@@ -191,6 +196,7 @@ async fn test_apply_function_runner_tx_replace(rt: TestRuntime) -> anyhow::Resul
         user_tx_size,
         system_tx_size,
         updates,
+        merges,
         rows_read_by_tablet,
     )?;
 
@@ -219,7 +225,8 @@ async fn test_apply_function_runner_tx_merge_existing_writes(
         )
         .await?;
     let updates = backend_tx.writes().as_flat()?.clone().into_updates();
-    function_runner_tx.merge_writes(updates)?;
+    let merges = backend_tx.writes().as_flat()?.merges().clone();
+    function_runner_tx.merge_writes(updates, merges)?;
 
     // Perform writes as if in funrun
     UserFacingModel::new_root_for_test(&mut function_runner_tx)
@@ -237,6 +244,7 @@ async fn test_apply_function_runner_tx_merge_existing_writes(
         .map(|(table, stats)| (*table, stats.rows_read))
         .collect();
     let updates = function_runner_tx.writes.as_flat()?.clone().into_updates();
+    let merges = function_runner_tx.writes.as_flat()?.merges().clone();
     backend_tx.apply_function_runner_tx(
         *begin_timestamp,
         reads,
@@ -244,6 +252,7 @@ async fn test_apply_function_runner_tx_merge_existing_writes(
         user_tx_size,
         system_tx_size,
         updates,
+        merges,
         rows_read_by_tablet,
     )?;
 
@@ -289,6 +298,7 @@ async fn test_apply_function_runner_tx_merge_existing_writes_bad(
         .map(|(table, stats)| (*table, stats.rows_read))
         .collect();
     let updates = function_runner_tx.writes.as_flat()?.clone().into_updates();
+    let merges = function_runner_tx.writes.as_flat()?.merges().clone();
     assert!(backend_tx
         .apply_function_runner_tx(
             *begin_timestamp,
@@ -297,6 +307,7 @@ async fn test_apply_function_runner_tx_merge_existing_writes_bad(
             user_tx_size,
             system_tx_size,
             updates,
+            merges,
             rows_read_by_tablet,
         )
         .is_err());
@@ -45,6 +45,9 @@ use common::{
         Query,
         QueryOperator,
         QuerySource,
+        Search,
+        SearchFacets,
+        SearchFilterExpression,
     },
     runtime::Runtime,
     schemas::{
@@ -71,6 +74,7 @@ use common::{
         ConvexObject,
         ConvexValue,
     },
+    version::MIN_NPM_VERSION_FOR_FUZZY_SEARCH,
     virtual_system_mapping::{
         all_tables_name_to_number,
         all_tables_number_to_name,
@@ -88,6 +92,7 @@ use must_let::must_let;
 use pretty_assertions::assert_eq;
 use proptest::prelude::*;
 use runtime::testing::TestRuntime;
+use search::FacetCount;
 use sync_types::backoff::Backoff;
 use value::{
     array,
@@ -107,8 +112,10 @@ use crate::{
         IndexWriter,
     },
     query::{
+        search_with_facets,
         PaginationOptions,
         ResolvedQuery,
+        SearchWithFacets,
         TableFilter,
     },
     table_summary::{
@@ -121,6 +128,10 @@ use crate::{
         DbFixtures,
         DbFixturesArgs,
     },
+    tests::text_test_utils::{
+        IndexData,
+        TextFixtures,
+    },
     write_log::WriteSource,
     Database,
     DatabaseSnapshot,
@@ -286,6 +297,7 @@ async fn test_build_indexes(rt: TestRuntime) -> anyhow::Result<()> {
             staged_text_indexes: BTreeMap::new(),
             vector_indexes: BTreeMap::new(),
             staged_vector_indexes: BTreeMap::new(),
+            sparse_vector_indexes: BTreeMap::new(),
             document_type: None,
         },
     );
@@ -345,6 +357,7 @@ async fn test_build_indexes(rt: TestRuntime) -> anyhow::Result<()> {
             staged_text_indexes: BTreeMap::new(),
             vector_indexes: BTreeMap::new(),
             staged_vector_indexes: BTreeMap::new(),
+            sparse_vector_indexes: BTreeMap::new(),
             document_type: None,
         },
     );
@@ -2525,3 +2538,98 @@ async fn test_schema_registry_takes_read_dependency(rt: TestRuntime) -> anyhow::
     );
     Ok(())
 }
+
+#[convex_macro::test_runtime]
+async fn test_search_with_facets(rt: TestRuntime) -> anyhow::Result<()> {
+    let fixtures = TextFixtures::new(rt).await?;
+    let IndexData {
+        index_name,
+        namespace,
+        ..
+    } = fixtures.enabled_text_index().await?;
+    let table_name = index_name.table().clone();
+
+    let mut tx = fixtures.db.begin_system().await?;
+    for (text, channel) in [
+        ("cat", "#general"),
+        ("cat food", "#general"),
+        ("cat toy", "#random"),
+        ("dog", "#random"),
+    ] {
+        TestFacingModel::new(&mut tx)
+            .insert(
+                &table_name,
+                assert_obj!("text" => text, "channel" => channel),
+            )
+            .await?;
+    }
+    // Documents missing the field are counted under a `None` value.
+    TestFacingModel::new(&mut tx)
+        .insert(&table_name, assert_obj!("text" => "cat nap"))
+        .await?;
+    fixtures.db.commit(tx).await?;
+
+    let search_cats = |facet_limit| {
+        let db = fixtures.db.clone();
+        let table_name = table_name.clone();
+        let index_name = index_name.clone();
+        async move {
+            let mut tx = db.begin_system().await?;
+            let search = Search {
+                table: table_name,
+                index_name,
+                filters: vec![SearchFilterExpression::Search(
+                    "text".parse()?,
+                    "cat".into(),
+                )],
+            };
+            let facets = SearchFacets {
+                fields: vec!["channel".parse()?],
+                limit: facet_limit,
+            };
+            search_with_facets(
+                &mut tx,
+                namespace,
+                search,
+                &facets,
+                2,
+                Some(MIN_NPM_VERSION_FOR_FUZZY_SEARCH.clone()),
+                TableFilter::IncludePrivateSystemTables,
+            )
+            .await
+        }
+    };
+
+    let SearchWithFacets {
+        results,
+        facets,
+        is_truncated,
+    } = search_cats(10).await?;
+    // Only `num_results` documents are loaded, but all four matches are counted.
+    assert_eq!(results.len(), 2);
+    assert!(!is_truncated);
+    assert_eq!(facets.len(), 1);
+    assert_eq!(
+        facets[0].counts,
+        vec![
+            FacetCount {
+                value: Some(val!("#general")),
+                count: 2,
+            },
+            FacetCount {
+                value: None,
+                count: 1,
+            },
+            FacetCount {
+                value: Some(val!("#random")),
+                count: 1,
+            },
+        ]
+    );
+    assert_eq!(facets[0].num_omitted_values, 0);
+
+    let SearchWithFacets { facets, .. } = search_cats(1).await?;
+    assert_eq!(facets[0].counts.len(), 1);
+    assert_eq!(facets[0].num_omitted_values, 2);
+    Ok(())
+}
@@ -74,6 +74,7 @@ use search::{
         PostingListMatch,
         PostingListQuery,
         Term,
+        TermSuggestion,
         TokenMatch,
         TokenQuery,
     },
@@ -87,8 +88,10 @@ use value::{
     TableNamespace,
 };
 use vector::{
+    CompiledSparseVectorSearch,
     CompiledVectorSearch,
     QdrantSchema,
+    SparseVectorSchema,
     VectorSearchQueryResult,
     VectorSearcher,
 };
@@ -175,6 +178,7 @@ impl Scenario {
             "test.by_text".parse()?,
             "searchField".parse()?,
             btreeset! {"filterField".parse()?},
+            vec![],
         );
         let index_id = IndexModel::new(&mut tx)
             .add_application_index(namespace, index)
@@ -1115,6 +1119,25 @@ impl VectorSearcher for BrokenSearcher {
     ) -> anyhow::Result<FragmentedVectorSegment> {
         anyhow::bail!("不");
     }
+
+    async fn execute_multi_segment_sparse_vector_query(
+        &self,
+        _: Arc<dyn Storage>,
+        _: Vec<FragmentedVectorSegmentPaths>,
+        _: SparseVectorSchema,
+        _: CompiledSparseVectorSearch,
+        _: u32,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        anyhow::bail!("我");
+    }
+
+    async fn execute_sparse_vector_compaction(
+        &self,
+        _: Arc<dyn Storage>,
+        _: Vec<FragmentedVectorSegmentPaths>,
+    ) -> anyhow::Result<FragmentedVectorSegment> {
+        anyhow::bail!("不");
+    }
 }
 
 #[async_trait]
@@ -1129,6 +1152,16 @@ impl Searcher for BrokenSearcher {
         anyhow::bail!("recherche")
     }
 
+    async fn query_term_suggestions(
+        &self,
+        _: Arc<dyn Storage>,
+        _: FragmentedTextStorageKeys,
+        _: Term,
+        _: usize,
+    ) -> anyhow::Result<Vec<TermSuggestion>> {
+        anyhow::bail!("suggestion")
+    }
+
     async fn query_bm25_stats(
         &self,
         _: Arc<dyn Storage>,
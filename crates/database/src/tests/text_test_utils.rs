@@ -412,6 +412,7 @@ pub fn backfilling_text_index() -> anyhow::Result<IndexMetadata<TableName>> {
         index_name,
         search_field,
         btreeset![filter_field],
+        vec![],
     );
     Ok(metadata)
 }
@@ -243,6 +243,8 @@ async fn vector_query_counts_bandwidth(rt: TestRuntime) -> anyhow::Result<()> {
                 limit: Some(10),
                 vector: vec![0.; 2],
                 expressions: btreeset![],
+                cursor: None,
+                score_threshold: None,
             },
         )
         .await?;
@@ -54,6 +54,7 @@ use search::{
         PostingListQuery,
         Searcher,
         Term,
+        TermSuggestion,
         TokenMatch,
         TokenQuery,
     },
@@ -74,8 +75,10 @@ use vector::{
         unsafe_load_disk_segment,
         VectorDiskSegmentPaths,
     },
+    CompiledSparseVectorSearch,
     CompiledVectorSearch,
     QdrantSchema,
+    SparseVectorSchema,
     VectorSearchQueryResult,
     VectorSearcher,
 };
@@ -459,6 +462,8 @@ fn new_backfilling_vector_index() -> anyhow::Result<IndexMetadata<TableName>> {
         vector_field,
         (2u32).try_into()?,
         btreeset![filter_field],
+        Default::default(),
+        None,
     );
     Ok(metadata)
 }
@@ -572,6 +577,18 @@ impl<RT: Runtime> Searcher for DeleteOnCompactSearchlight<RT> {
             .await
     }
 
+    async fn query_term_suggestions(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        storage_keys: FragmentedTextStorageKeys,
+        prefix: Term,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<TermSuggestion>> {
+        self.searcher
+            .query_term_suggestions(search_storage, storage_keys, prefix, max_results)
+            .await
+    }
+
     async fn query_bm25_stats(
         &self,
         search_storage: Arc<dyn Storage>,
@@ -649,4 +666,33 @@ impl<RT: Runtime> VectorSearcher for DeleteOnCompactSearchlight<RT> {
             .execute_vector_compaction(search_storage, segments, dimension)
             .await
     }
+
+    async fn execute_multi_segment_sparse_vector_query(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        segments: Vec<FragmentedVectorSegmentPaths>,
+        schema: SparseVectorSchema,
+        search: CompiledSparseVectorSearch,
+        overfetch_delta: u32,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        self.searcher
+            .execute_multi_segment_sparse_vector_query(
+                search_storage,
+                segments,
+                schema,
+                search,
+                overfetch_delta,
+            )
+            .await
+    }
+
+    async fn execute_sparse_vector_compaction(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        segments: Vec<FragmentedVectorSegmentPaths>,
+    ) -> anyhow::Result<FragmentedVectorSegment> {
+        self.searcher
+            .execute_sparse_vector_compaction(search_storage, segments)
+            .await
+    }
 }
@@ -21,10 +21,15 @@ use common::{
     },
     components::ComponentId,
     knobs::{
+        MAX_TRANSACTION_WINDOW,
         MULTI_SEGMENT_FULL_SCAN_THRESHOLD_KB,
         VECTOR_INDEX_SIZE_SOFT_LIMIT,
     },
     persistence::PersistenceReader,
+    query::{
+        Search,
+        SearchFilterExpression,
+    },
     runtime::Runtime,
     types::{
         unchecked_repeatable_ts,
@@ -32,6 +37,7 @@ use common::{
         IndexName,
     },
 };
+use errors::ErrorMetadataAnyhowExt;
 use itertools::Itertools;
 use keybroker::Identity;
 use maplit::{
@@ -49,9 +55,12 @@ use runtime::{
         TestRuntime,
     },
 };
-use search::searcher::{
-    InProcessSearcher,
-    Searcher,
+use search::{
+    hybrid::ReciprocalRankFusion,
+    searcher::{
+        InProcessSearcher,
+        Searcher,
+    },
 };
 use storage::Storage;
 use value::{
@@ -66,10 +75,13 @@ use vector::{
     cosine_similarity,
     PublicVectorSearchQueryResult,
     VectorSearch,
+    VectorSearchCursor,
     VectorSearchExpression,
+    MAX_VECTOR_PAGINATION_DEPTH,
 };
 
 use crate::{
+    hybrid_search::HybridSearch,
     index_workers::FlusherType,
     test_helpers::{
         vector_utils::{
@@ -85,6 +97,7 @@ use crate::{
         IndexData,
         VectorFixtures,
     },
+    text_index_worker::flusher::backfill_text_indexes,
     vector_index_worker::{
         compactor::compact_vector_indexes_in_test,
         flusher::{
@@ -163,6 +176,8 @@ impl<RT: Runtime> Scenario<RT> {
             INDEXED_FIELD.parse()?,
             DIMENSIONS.try_into()?,
             FILTER_FIELDS.iter().map(|f| f.parse()).try_collect()?,
+            Default::default(),
+            None,
         );
         IndexModel::new(&mut tx)
             .add_application_index(namespace, index)
@@ -253,6 +268,8 @@ impl<RT: Runtime> Scenario<RT> {
                     vector,
                     limit,
                     expressions: filter_expressions,
+                    cursor: None,
+                    score_threshold: None,
                 },
             )
             .await?;
@@ -431,6 +448,8 @@ impl<RT: Runtime> RandomizedTest<RT> {
                     vector: test_query.vector.clone(),
                     limit: Some(test_query.limit),
                     expressions,
+                    cursor: None,
+                    score_threshold: None,
                 };
                 let (returned_results, _usage_stats) = self
                     .scenario
@@ -567,6 +586,273 @@ async fn test_vector_search(rt: TestRuntime) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[convex_macro::test_runtime]
+async fn test_vector_search_pagination(rt: TestRuntime) -> anyhow::Result<()> {
+    let scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
+
+    let mut tx = scenario.database.begin(Identity::system()).await?;
+    for _ in 0..7 {
+        let obj = assert_obj!(INDEXED_FIELD => random_vector_value(&mut rt.rng()));
+        UserFacingModel::new_root_for_test(&mut tx)
+            .insert(TABLE_NAME.parse()?, obj)
+            .await?;
+    }
+    scenario.database.commit(tx).await?;
+    scenario.backfill().await?;
+
+    let query = |cursor| VectorSearch {
+        index_name: INDEX_NAME.parse().unwrap(),
+        component_id: ComponentId::Root,
+        vector: vec![1., 0., 0., 0.],
+        limit: Some(3),
+        expressions: btreeset![],
+        cursor,
+        score_threshold: None,
+    };
+    let (all_results, _) = scenario
+        .database
+        .vector_search(
+            Identity::system(),
+            VectorSearch {
+                limit: Some(7),
+                ..query(None)
+            },
+        )
+        .await?;
+
+    let (first, _) = scenario
+        .database
+        .vector_search_page(Identity::system(), query(None))
+        .await?;
+    let first_cursor = first.continue_cursor.expect("Expected a second page");
+
+    // Writes after the first page don't show up in later pages.
+    let mut tx = scenario.database.begin(Identity::system()).await?;
+    let obj = assert_obj!(INDEXED_FIELD => vector_to_value(vec![1., 0., 0., 0.]));
+    UserFacingModel::new_root_for_test(&mut tx)
+        .insert(TABLE_NAME.parse()?, obj)
+        .await?;
+    scenario.database.commit(tx).await?;
+
+    let (second, _) = scenario
+        .database
+        .vector_search_page(Identity::system(), query(Some(first_cursor)))
+        .await?;
+    let (third, _) = scenario
+        .database
+        .vector_search_page(Identity::system(), query(second.continue_cursor))
+        .await?;
+    assert_eq!(third.results.len(), 1);
+    assert!(third.continue_cursor.is_none());
+
+    let paginated: Vec<_> = [first, second, third]
+        .into_iter()
+        .flat_map(|page| page.results)
+        .collect();
+    assert_eq!(paginated, all_results);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_vector_search_cursor_expires(rt: TestRuntime) -> anyhow::Result<()> {
+    let scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
+    scenario.seed_table_with_vector_data(4).await?;
+
+    let query = |cursor| VectorSearch {
+        index_name: INDEX_NAME.parse().unwrap(),
+        component_id: ComponentId::Root,
+        vector: vec![1., 0., 0., 0.],
+        limit: Some(2),
+        expressions: btreeset![],
+        cursor,
+        score_threshold: None,
+    };
+    let (first, _) = scenario
+        .database
+        .vector_search_page(Identity::system(), query(None))
+        .await?;
+    let cursor = first.continue_cursor.expect("Expected a second page");
+
+    // Once the first page's snapshot falls out of the transaction window, the
+    // cursor can't be continued.
+    rt.advance_time(*MAX_TRANSACTION_WINDOW * 2).await;
+    scenario.seed_table_with_vector_data(1).await?;
+    let err = scenario
+        .database
+        .vector_search_page(Identity::system(), query(Some(cursor)))
+        .await
+        .unwrap_err();
+    assert!(err.is_bad_request());
+    assert_eq!(err.short_msg(), "VectorSearchCursorExpired");
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_vector_search_pagination_too_deep(rt: TestRuntime) -> anyhow::Result<()> {
+    let scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
+    scenario.seed_table_with_vector_data(4).await?;
+
+    let query = |cursor| VectorSearch {
+        index_name: INDEX_NAME.parse().unwrap(),
+        component_id: ComponentId::Root,
+        vector: vec![1., 0., 0., 0.],
+        limit: Some(2),
+        expressions: btreeset![],
+        cursor,
+        score_threshold: None,
+    };
+    let (first, _) = scenario
+        .database
+        .vector_search_page(Identity::system(), query(None))
+        .await?;
+    let cursor = first.continue_cursor.expect("Expected a second page");
+
+    // Paginating past the maximum depth is an error rather than an empty page
+    // that looks like the end of the results.
+    let deep_cursor = VectorSearchCursor {
+        offset: (MAX_VECTOR_PAGINATION_DEPTH - 1) as u32,
+        ..cursor
+    };
+    let err = scenario
+        .database
+        .vector_search_page(Identity::system(), query(Some(deep_cursor)))
+        .await
+        .unwrap_err();
+    assert!(err.is_bad_request());
+    assert_eq!(err.short_msg(), "VectorPaginationTooDeepError");
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_vector_search_score_threshold(rt: TestRuntime) -> anyhow::Result<()> {
+    let scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
+
+    let mut tx = scenario.database.begin(Identity::system()).await?;
+    let close = assert_obj!(INDEXED_FIELD => vector_to_value(vec![1., 0.1, 0., 0.]));
+    let close_id = UserFacingModel::new_root_for_test(&mut tx)
+        .insert(TABLE_NAME.parse()?, close)
+        .await?;
+    let far = assert_obj!(INDEXED_FIELD => vector_to_value(vec![0., 1., 0., 0.]));
+    UserFacingModel::new_root_for_test(&mut tx)
+        .insert(TABLE_NAME.parse()?, far)
+        .await?;
+    scenario.database.commit(tx).await?;
+
+    for _ in 0..2 {
+        let (results, _) = scenario
+            .database
+            .vector_search(
+                Identity::system(),
+                VectorSearch {
+                    index_name: INDEX_NAME.parse()?,
+                    component_id: ComponentId::Root,
+                    vector: vec![1., 0., 0., 0.],
+                    limit: None,
+                    expressions: btreeset![],
+                    cursor: None,
+                    score_threshold: Some(0.9),
+                },
+            )
+            .await?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id.internal_id(), close_id.internal_id());
+
+        // Backfill and repeat once to check the disk index.
+        scenario.backfill().await?;
+    }
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_hybrid_search(rt: TestRuntime) -> anyhow::Result<()> {
+    let scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
+    let text_index_name: IndexName = "test.by_text".parse()?;
+
+    let mut tx = scenario.database.begin_system().await?;
+    IndexModel::new(&mut tx)
+        .add_application_index(
+            TABLE_NAMESPACE,
+            IndexMetadata::new_backfilling_text_index(
+                text_index_name.clone(),
+                "text".parse()?,
+                btreeset![],
+                vec![],
+            ),
+        )
+        .await?;
+    scenario.database.commit(tx).await?;
+    backfill_text_indexes(
+        rt.clone(),
+        scenario.database.clone(),
+        scenario.reader.clone(),
+        scenario.search_storage.clone(),
+        Arc::new(InProcessSearcher::new(rt.clone()).await?),
+    )
+    .await?;
+    let mut tx = scenario.database.begin_system().await?;
+    IndexModel::new(&mut tx)
+        .enable_index_for_testing(TABLE_NAMESPACE, &text_index_name)
+        .await?;
+    scenario.database.commit(tx).await?;
+
+    let mut tx = scenario.database.begin(Identity::system()).await?;
+    let mut ids = vec![];
+    for (text, vector) in [
+        ("apple", vec![1., 0., 0., 0.]),
+        ("red car", vec![0., 1., 0., 0.]),
+        ("green apple pie", vec![0.9, 0.1, 0., 0.]),
+    ] {
+        let obj = assert_obj!("text" => text, INDEXED_FIELD => vector_to_value(vector));
+        ids.push(
+            UserFacingModel::new_root_for_test(&mut tx)
+                .insert(TABLE_NAME.parse()?, obj)
+                .await?,
+        );
+    }
+    scenario.database.commit(tx).await?;
+    let (apple, car, pie) = (ids[0], ids[1], ids[2]);
+
+    let (results, _) = scenario
+        .database
+        .hybrid_search(
+            Identity::system(),
+            HybridSearch {
+                text: Search {
+                    index_name: text_index_name.clone(),
+                    table: TABLE_NAME.parse()?,
+                    filters: vec![SearchFilterExpression::Search(
+                        "text".parse()?,
+                        "apple".to_string(),
+                    )],
+                },
+                vector: VectorSearch {
+                    index_name: INDEX_NAME.parse()?,
+                    component_id: ComponentId::Root,
+                    vector: vec![1., 0., 0., 0.],
+                    limit: None,
+                    expressions: btreeset![],
+                    cursor: None,
+                    score_threshold: None,
+                },
+                fusion: ReciprocalRankFusion::default(),
+                limit: 10,
+            },
+        )
+        .await?;
+    let ids: Vec<_> = results.iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![apple, pie, car]);
+
+    // Documents matching both halves carry both component scores, while the
+    // car only appears in the vector results.
+    assert_eq!(results[0].text.as_ref().map(|s| s.rank), Some(1));
+    assert_eq!(results[0].vector.as_ref().map(|s| s.rank), Some(1));
+    assert!(results[2].text.is_none());
+    assert_eq!(results[2].vector.as_ref().map(|s| s.rank), Some(3));
+    assert!(results[0].score > results[1].score);
+    assert!(results[1].score > results[2].score);
+    Ok(())
+}
+
 #[convex_macro::test_runtime]
 async fn test_vector_search_compaction(rt: TestRuntime) -> anyhow::Result<()> {
     let mut scenario = Scenario::new(rt.clone(), ScenarioIndexState::Some).await?;
@@ -680,6 +966,8 @@ async fn test_concurrent_index_version_searches(rt: ProdRuntime) -> anyhow::Resu
                         limit: Some(10),
                         vector: vec![0.; 4],
                         expressions: btreeset![],
+                        cursor: None,
+                        score_threshold: None,
                     },
                     unchecked_repeatable_ts(timestamp),
                 )
@@ -1023,6 +1311,8 @@ async fn test_multi_segment_search_obeys_sorted_order(rt: TestRuntime) -> anyhow
                 vector: [6f64, 7f64].into_iter().map(|value| value as f32).collect(),
                 limit: Some(3),
                 expressions: btreeset![],
+                cursor: None,
+                score_threshold: None,
             },
         )
         .await?;
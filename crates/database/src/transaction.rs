@@ -16,6 +16,7 @@ use common::{
     bootstrap_model::{
         index::{
             database_index::IndexedFields,
+            IndexConfig,
             IndexMetadata,
             INDEX_TABLE,
         },
@@ -49,12 +50,14 @@ use common::{
         CursorPosition,
         Order,
         Search,
+        SearchSuggest,
         SearchVersion,
     },
     runtime::Runtime,
     schemas::DatabaseSchema,
     sync::split_rw_lock::Reader,
     types::{
+        FunctionLimits,
         GenericIndexName,
         IndexId,
         IndexName,
@@ -86,6 +89,7 @@ use keybroker::{
 use search::{
     metrics::SearchType,
     CandidateRevision,
+    SearchSuggestion,
 };
 use sync_types::{
     AuthenticationToken,
@@ -98,6 +102,10 @@ use value::{
     TableNumber,
     TabletId,
 };
+use vector::{
+    PublicVectorSearchQueryResult,
+    VectorSearch,
+};
 
 use crate::{
     bootstrap_model::{
@@ -107,8 +115,14 @@ use crate::{
             NUM_RESERVED_SYSTEM_TABLE_NUMBERS,
         },
     },
-    committer::table_dependency_sort_key,
-    execution_size::FunctionExecutionSize,
+    committer::{
+        table_dependency_sort_key,
+        MergeBases,
+    },
+    execution_size::{
+        FunctionExecutionSize,
+        ReadLimits,
+    },
     metrics::{
         self,
         log_index_too_large_blocking_writes,
@@ -129,10 +143,16 @@ use crate::{
     token::Token,
     transaction_id_generator::TransactionIdGenerator,
     transaction_index::TransactionIndex,
-    write_limits::BiggestDocumentWrites,
+    write_limits::{
+        BiggestDocumentWrites,
+        WriteLimits,
+    },
     writes::{
+        DocumentMerge,
+        DocumentMerges,
         NestedWriteToken,
         NestedWrites,
+        PendingMerge,
         TransactionWriteSize,
         Writes,
     },
@@ -385,6 +405,13 @@ impl<RT: Runtime> Transaction<RT> {
         &self.writes
     }
 
+    /// Applies the read and write limits from a function's config to the rest
+    /// of this transaction.
+    pub fn apply_function_limits(&mut self, limits: &FunctionLimits) {
+        self.reads.set_limits(ReadLimits::new(limits));
+        self.writes.set_limits(WriteLimits::new(limits));
+    }
+
     pub fn into_reads_and_writes(self) -> (TransactionReadSet, NestedWrites<Writes>) {
         (self.reads, self.writes)
     }
@@ -441,6 +468,7 @@ impl<RT: Runtime> Transaction<RT> {
         user_tx_size: crate::reads::TransactionReadSize,
         system_tx_size: crate::reads::TransactionReadSize,
         updates: OrdMap<ResolvedDocumentId, DocumentUpdateWithPrevTs>,
+        merges: DocumentMerges,
         rows_read_by_tablet: BTreeMap<TabletId, u64>,
     ) -> anyhow::Result<()> {
         anyhow::ensure!(
@@ -451,7 +479,7 @@ impl<RT: Runtime> Transaction<RT> {
         self.reads
             .merge(reads, num_intervals, user_tx_size, system_tx_size);
 
-        self.merge_writes(updates)?;
+        self.merge_writes(updates, merges)?;
 
         for (tablet_id, rows_read) in rows_read_by_tablet {
             self.stats.entry(tablet_id).or_default().rows_read += rows_read;
@@ -461,14 +489,16 @@ impl<RT: Runtime> Transaction<RT> {
     }
 
     // Checks that if this transaction already has some writes, they are included
-    // in the given `updates`. This means the passed in `updates` are a superset
-    // of the existing `updates` on this transaction, and that the merged-in
-    // writes cannot modify documents already written to in this transaction.
-    // In most scenarios this transaction will have no writes.
+    // in the given `updates` and `merges`. This means the passed in writes are a
+    // superset of the existing writes on this transaction, and that the
+    // merged-in writes cannot modify documents already written to in this
+    // transaction. In most scenarios this transaction will have no writes.
     pub fn merge_writes(
         &mut self,
         updates: OrdMap<ResolvedDocumentId, DocumentUpdateWithPrevTs>,
+        merges: DocumentMerges,
     ) -> anyhow::Result<()> {
+        let existing_merges = self.writes().as_flat()?.merges().clone();
         let existing_updates = self.writes().as_flat()?.clone().into_updates();
 
         let mut updates = updates.into_iter().collect::<Vec<_>>();
@@ -515,6 +545,22 @@ impl<RT: Runtime> Transaction<RT> {
             "Existing write was not preserved"
         );
 
+        // Existing merges may have been dropped by deleting the document above,
+        // but otherwise the merged-in merges must extend them.
+        for (id, merges) in merges {
+            let existing = existing_merges
+                .get(&id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            anyhow::ensure!(
+                merges.starts_with(existing),
+                "Conflicting merges for document {id}"
+            );
+            for merge in merges.into_iter().skip(existing.len()) {
+                self.apply_merge(id, merge)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -1032,6 +1078,32 @@ impl<RT: Runtime> Transaction<RT> {
         Ok(())
     }
 
+    /// Merges into the document with the given `id` when this transaction
+    /// commits, without taking a read dependency on the document. See
+    /// [`DocumentMerge`]. The merge isn't visible to reads in this
+    /// transaction, except for [`Transaction::index_range_for_merge`].
+    pub fn merge_document(
+        &mut self,
+        id: ResolvedDocumentId,
+        merge: Arc<dyn DocumentMerge>,
+    ) -> anyhow::Result<()> {
+        self.apply_merge(id, PendingMerge::new(merge))
+    }
+
+    fn apply_merge(&mut self, id: ResolvedDocumentId, merge: PendingMerge) -> anyhow::Result<()> {
+        let is_system_document = self.table_mapping().is_system_tablet(id.tablet_id);
+        let bootstrap_tables = self.bootstrap_tables();
+        self.writes.merge(
+            bootstrap_tables,
+            is_system_document,
+            &mut self.reads,
+            id,
+            merge,
+        )?;
+        self.stats.entry(id.tablet_id).or_default().rows_written += 1;
+        Ok(())
+    }
+
     pub(crate) async fn insert_document(
         &mut self,
         document: ResolvedDocument,
@@ -1060,6 +1132,79 @@ impl<RT: Runtime> Transaction<RT> {
             .await
     }
 
+    pub async fn search_suggest(
+        &mut self,
+        stable_index_name: &StableIndexName,
+        suggest: &SearchSuggest,
+    ) -> anyhow::Result<Vec<SearchSuggestion>> {
+        let Some(tablet_index_name) = stable_index_name.tablet_index_name() else {
+            return Ok(vec![]);
+        };
+        let suggest = suggest.clone().to_internal(tablet_index_name.clone());
+        self.index
+            .suggest(&mut self.reads, &suggest, tablet_index_name.clone())
+            .await
+    }
+
+    /// Run a vector search at this transaction's snapshot, including its
+    /// pending writes. The results are recorded in the read set, so a write
+    /// that could change the top results invalidates the transaction.
+    pub async fn vector_search(
+        &mut self,
+        stable_index_name: &StableIndexName,
+        query: VectorSearch,
+    ) -> anyhow::Result<Vec<PublicVectorSearchQueryResult>> {
+        // Cursors pin a snapshot, which a transaction already has.
+        anyhow::ensure!(
+            query.cursor.is_none(),
+            ErrorMetadata::bad_request(
+                "VectorSearchCursorInTransaction",
+                "Vector search cursors can only be used from actions."
+            )
+        );
+        let Some(tablet_index_name) = stable_index_name.tablet_index_name() else {
+            return Ok(vec![]);
+        };
+        let component_id = query.component_id;
+        let tablet_id = *tablet_index_name.table();
+        let table_number = self.table_mapping().tablet_number(tablet_id)?;
+        let table_name = self.table_mapping().tablet_name(tablet_id)?;
+        let query = query.resolve(
+            &self
+                .table_mapping()
+                .namespace(TableNamespace::from(component_id)),
+        )?;
+        let index = self
+            .index
+            .index_registry()
+            .require_enabled(tablet_index_name, &query.printable_index_name()?)?;
+        let IndexConfig::Vector {
+            ref developer_config,
+            ..
+        } = index.metadata.config
+        else {
+            anyhow::bail!("Vector search against non-vector index {tablet_index_name}");
+        };
+        let distance_metric = developer_config.distance_metric;
+        let results: Vec<_> = self
+            .index
+            .vector_search(&mut self.reads, query, tablet_index_name.clone())
+            .await?
+            .into_iter()
+            .map(|r| r.to_public(table_number, distance_metric))
+            .collect();
+        let size: u64 = results.iter().map(|row| row.size() as u64).sum();
+        let component_path = self.must_component_path(component_id)?;
+        self.usage_tracker.track_vector_egress_size(
+            component_path,
+            table_name.to_string(),
+            size,
+            // We don't have system owned vector indexes.
+            false,
+        );
+        Ok(results)
+    }
+
     // TODO(lee) Make this private.
     // We ideally want the transaction to call this internally so caller doesn't
     // have to call this. However, this is currently hard since the query layer
@@ -1113,6 +1258,64 @@ impl<RT: Runtime> Transaction<RT> {
             .await
     }
 
+    /// Reads an index range as of this transaction, with this transaction's
+    /// pending merges applied, without recording a read dependency on the
+    /// range. Since the result isn't checked for conflicts at commit time, it
+    /// should only be used to decide which documents to merge into.
+    pub async fn index_range_for_merge(
+        &mut self,
+        namespace: TableNamespace,
+        index_name: &IndexName,
+        interval: &Interval,
+    ) -> anyhow::Result<Vec<(ResolvedDocument, WriteTimestamp)>> {
+        let stable_index_name = IndexModel::new(self).stable_index_name(
+            namespace,
+            index_name,
+            TableFilter::IncludePrivateSystemTables,
+        )?;
+        let tablet_index_name = match stable_index_name {
+            StableIndexName::Physical(tablet_index_name) => tablet_index_name,
+            StableIndexName::Missing(_) => return Ok(vec![]),
+            StableIndexName::Virtual(..) => {
+                anyhow::bail!("Can only merge into documents in physical tables: {index_name}")
+            },
+        };
+        self.index
+            .require_enabled(&mut self.reads, &tablet_index_name, index_name)?;
+        let mut remaining_interval = interval.clone();
+        let mut documents = vec![];
+        while !remaining_interval.is_empty() {
+            let [result] = self
+                .index
+                .range_no_deps(&[&RangeRequest {
+                    index_name: tablet_index_name.clone(),
+                    printable_index_name: index_name.clone(),
+                    interval: remaining_interval,
+                    order: Order::Asc,
+                    max_size: DEFAULT_PAGE_SIZE,
+                }])
+                .await
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("wrong number of results"))?;
+            let (page, cursor) = result?;
+            (_, remaining_interval) = interval.split(cursor, Order::Asc);
+            for (_, document, ts) in page {
+                let mut document = document.unpack();
+                for merge in self
+                    .writes
+                    .merges()
+                    .get(&document.id())
+                    .into_iter()
+                    .flatten()
+                {
+                    document = merge.apply(&document)?;
+                }
+                documents.push((document, ts));
+            }
+        }
+        Ok(documents)
+    }
+
     #[cfg(any(test, feature = "testing"))]
     pub fn set_index_size_hard_limit(&mut self, size: usize) {
         self.index_size_override = Some(size);
@@ -1146,6 +1349,9 @@ pub struct FinalTransaction {
 
     pub(crate) reads: TransactionReadSet,
     pub(crate) writes: Writes,
+    // The revisions that `writes.merges()` apply to, read by `CommitterClient`
+    // right before committing.
+    pub(crate) merge_bases: Option<MergeBases>,
 
     pub(crate) usage_tracker: FunctionUsageTracker,
 }
@@ -1176,6 +1382,7 @@ impl FinalTransaction {
             component_registry,
             reads: transaction.reads,
             writes: transaction.writes.into_flat()?,
+            merge_bases: None,
             usage_tracker: transaction.usage_tracker.clone(),
         })
     }
@@ -30,6 +30,7 @@ use common::{
     query::{
         CursorPosition,
         InternalSearch,
+        InternalSearchSuggest,
         Order,
         SearchVersion,
     },
@@ -59,7 +60,9 @@ use search::{
     query::RevisionWithKeys,
     CandidateRevision,
     QueryResults,
+    SearchSuggestion,
     Searcher,
+    SuggestResults,
     TextIndexManager,
 };
 use storage::Storage;
@@ -68,6 +71,13 @@ use value::{
     DeveloperDocumentId,
     FieldPath,
 };
+use vector::{
+    InternalVectorSearch,
+    VectorIndexManager,
+    VectorQueryRead,
+    VectorQueryReads,
+    VectorSearchQueryResult,
+};
 
 use crate::{
     preloaded::PreloadedIndexRange,
@@ -100,6 +110,11 @@ pub struct TransactionIndex {
     // on top of the transaction base snapshot.
     text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
     text_index_updates: OrdMap<IndexId, Vec<DocumentUpdate>>,
+
+    // Vector searches also apply pending updates on top of the base snapshot, for the same
+    // determinism reasons as text search.
+    vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
+    vector_index_updates: OrdMap<IndexId, Vec<DocumentUpdate>>,
 }
 
 impl PendingWrites for TransactionIndex {}
@@ -109,6 +124,7 @@ impl TransactionIndex {
         index_registry: IndexRegistry,
         database_index_snapshot: DatabaseIndexSnapshot,
         text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+        vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
     ) -> Self {
         Self {
             index_registry,
@@ -117,6 +133,8 @@ impl TransactionIndex {
             database_index_updates: OrdMap::new(),
             text_index_snapshot,
             text_index_updates: OrdMap::new(),
+            vector_index_snapshot,
+            vector_index_updates: OrdMap::new(),
         }
     }
 
@@ -294,6 +312,74 @@ impl TransactionIndex {
         Ok(results.revisions_with_keys)
     }
 
+    #[fastrace::trace]
+    pub async fn suggest(
+        &mut self,
+        reads: &mut TransactionReadSet,
+        query: &InternalSearchSuggest,
+        index_name: TabletIndexName,
+    ) -> anyhow::Result<Vec<SearchSuggestion>> {
+        // See `search` for why we don't allow index registry updates or record a
+        // read of the index metadata here.
+        anyhow::ensure!(
+            !self.index_registry_updated,
+            "Text search and index registry update not allowed in the same transaction"
+        );
+        let index = self
+            .index_registry
+            .require_enabled(&index_name, &query.printable_index_name()?)?;
+        let empty = vec![];
+        let pending_updates = self.text_index_updates.get(&index.id).unwrap_or(&empty);
+        let results = self
+            .text_index_snapshot
+            .suggest(&index, query, pending_updates)
+            .await?;
+        reads.record_search(index_name.clone(), results.reads);
+
+        Ok(results.suggestions)
+    }
+
+    #[fastrace::trace]
+    pub async fn vector_search(
+        &mut self,
+        reads: &mut TransactionReadSet,
+        query: InternalVectorSearch,
+        index_name: TabletIndexName,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        // See `search` for why we don't allow index registry updates or record a
+        // read of the index metadata here.
+        anyhow::ensure!(
+            !self.index_registry_updated,
+            "Vector search and index registry update not allowed in the same transaction"
+        );
+        let index = self
+            .index_registry
+            .require_enabled(&index_name, &query.printable_index_name()?)?;
+        let empty = vec![];
+        let pending_updates = self.vector_index_updates.get(&index.id).unwrap_or(&empty);
+        let results = self
+            .vector_index_snapshot
+            .vector_search(&index, query.clone(), pending_updates)
+            .await?;
+        let IndexConfig::Vector {
+            ref developer_config,
+            ..
+        } = index.metadata.config
+        else {
+            anyhow::bail!("Vector search returned results for non-vector index {index_name}");
+        };
+        reads.record_vector_search(
+            index_name,
+            VectorQueryReads::new(VectorQueryRead::new(
+                developer_config.vector_field.clone(),
+                developer_config.distance_metric,
+                &query,
+                &results,
+            )),
+        );
+        Ok(results)
+    }
+
     /// Fetch a batch of index ranges. This method does not update the read set,
     /// since we might be fetching more documents than the caller actually needs
     /// due to filtering.
@@ -509,11 +595,18 @@ impl TransactionIndex {
                         new_document: new_document.clone(),
                     });
             }
+            for index in self.index_registry.vector_indexes_by_table(id.tablet_id) {
+                self.vector_index_updates
+                    .entry(index.id)
+                    .or_default()
+                    .push(DocumentUpdate {
+                        id,
+                        old_document: old_document.clone(),
+                        new_document: new_document.clone(),
+                    });
+            }
         }
 
-        // Note that we do not update the vector index and we always read at the
-        // base snapshot.
-
         updates
     }
 
@@ -662,6 +755,28 @@ pub trait TransactionTextSnapshot: Send + Sync + 'static {
         // statistics anyway.
         pending_updates: &Vec<DocumentUpdate>,
     ) -> anyhow::Result<QueryResults>;
+
+    // Suggest completions at the given snapshot after applying the given writes.
+    // Writes are sent for the same determinism reasons as `search`.
+    async fn suggest(
+        &self,
+        index: &Index,
+        suggest: &InternalSearchSuggest,
+        pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<SuggestResults>;
+}
+
+#[async_trait]
+pub trait TransactionVectorSnapshot: Send + Sync + 'static {
+    // Vector search at the given snapshot after applying the given writes.
+    // Writes are sent for the same determinism reasons as
+    // `TransactionTextSnapshot::search`.
+    async fn vector_search(
+        &self,
+        index: &Index,
+        query: InternalVectorSearch,
+        pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>>;
 }
 
 #[derive(Clone)]
@@ -759,6 +874,87 @@ impl TransactionTextSnapshot for TextIndexManagerSnapshot {
             )
             .await
     }
+
+    async fn suggest(
+        &self,
+        index: &Index,
+        suggest: &InternalSearchSuggest,
+        pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<SuggestResults> {
+        let text_indexes_snapshot = self.snapshot_with_updates(pending_updates)?;
+        text_indexes_snapshot
+            .suggest(index, suggest, self.searcher.clone(), self.search_storage())
+            .await
+    }
+}
+
+#[derive(Clone)]
+pub struct VectorIndexManagerSnapshot {
+    index_registry: IndexRegistry,
+    vector_indexes: VectorIndexManager,
+
+    searcher: Arc<dyn Searcher>,
+    search_storage: Arc<OnceLock<Arc<dyn Storage>>>,
+}
+
+impl VectorIndexManagerSnapshot {
+    pub fn new(
+        index_registry: IndexRegistry,
+        vector_indexes: VectorIndexManager,
+        searcher: Arc<dyn Searcher>,
+        search_storage: Arc<OnceLock<Arc<dyn Storage>>>,
+    ) -> Self {
+        Self {
+            index_registry,
+            vector_indexes,
+            searcher,
+            search_storage,
+        }
+    }
+
+    // Applies the writes to the base snapshot and returns the new snapshot.
+    fn snapshot_with_updates(
+        &self,
+        pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<VectorIndexManager> {
+        let mut vector_indexes = self.vector_indexes.clone();
+        for DocumentUpdate {
+            id: _,
+            old_document,
+            new_document,
+        } in pending_updates
+        {
+            vector_indexes.update(
+                &self.index_registry,
+                old_document.as_ref(),
+                new_document.as_ref(),
+                WriteTimestamp::Pending,
+            )?;
+        }
+        Ok(vector_indexes)
+    }
+
+    fn search_storage(&self) -> Arc<dyn Storage> {
+        self.search_storage
+            .get()
+            .expect("search_storage not initialized")
+            .clone()
+    }
+}
+
+#[async_trait]
+impl TransactionVectorSnapshot for VectorIndexManagerSnapshot {
+    async fn vector_search(
+        &self,
+        index: &Index,
+        query: InternalVectorSearch,
+        pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        let vector_indexes_snapshot = self.snapshot_with_updates(pending_updates)?;
+        vector_indexes_snapshot
+            .vector_search(index, query, self.searcher.clone(), self.search_storage())
+            .await
+    }
 }
 
 pub struct SearchNotEnabled;
@@ -774,6 +970,27 @@ impl TransactionTextSnapshot for SearchNotEnabled {
     ) -> anyhow::Result<QueryResults> {
         anyhow::bail!("search not implemented in db-info")
     }
+
+    async fn suggest(
+        &self,
+        _index: &Index,
+        _suggest: &InternalSearchSuggest,
+        _pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<SuggestResults> {
+        anyhow::bail!("search not implemented in db-info")
+    }
+}
+
+#[async_trait]
+impl TransactionVectorSnapshot for SearchNotEnabled {
+    async fn vector_search(
+        &self,
+        _index: &Index,
+        _query: InternalVectorSearch,
+        _pending_updates: &Vec<DocumentUpdate>,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        anyhow::bail!("search not implemented in db-info")
+    }
 }
 
 #[cfg(test)]
@@ -848,7 +1065,10 @@ mod tests {
     };
     use value::assert_obj;
 
-    use super::TextIndexManagerSnapshot;
+    use super::{
+        SearchNotEnabled,
+        TextIndexManagerSnapshot,
+    };
     use crate::{
         query::IndexRangeResponse,
         transaction_index::TransactionIndex,
@@ -956,6 +1176,7 @@ mod tests {
                 searcher.clone(),
                 Arc::new(OnceLock::from(search_storage as Arc<dyn Storage>)),
             )),
+            Arc::new(SearchNotEnabled),
         );
 
         // Query the missing index. It should return an error because index is missing.
@@ -1056,6 +1277,7 @@ mod tests {
                 searcher.clone(),
                 Arc::new(OnceLock::from(search_storage as Arc<dyn Storage>)),
             )),
+            Arc::new(SearchNotEnabled),
         );
 
         // Query the missing table using table scan index. It should return no results.
@@ -1260,6 +1482,7 @@ mod tests {
                 searcher.clone(),
                 Arc::new(OnceLock::from(search_storage as Arc<dyn Storage>)),
             )),
+            Arc::new(SearchNotEnabled),
         );
         let david = ResolvedDocument::new(
             next_document_id(&mut id_generator, "users")?,
@@ -599,6 +599,8 @@ mod tests {
                     vector: vec![0f32, 0f32],
                     limit: Some(10),
                     expressions: btreeset![],
+                    cursor: None,
+                    score_threshold: None,
                 },
             )
             .await?;
@@ -1097,6 +1097,8 @@ mod tests {
                     vector: vector.into_iter().map(|value| value as f32).collect(),
                     limit: Some(1),
                     expressions: btreeset![],
+                    cursor: None,
+                    score_threshold: None,
                 },
             )
             .await?
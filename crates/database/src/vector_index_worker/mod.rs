@@ -4,3 +4,4 @@ pub mod flusher;
 mod vector_meta;
 
 pub use vector_meta::BuildVectorIndexArgs;
+pub(crate) use vector_meta::VectorStatistics;
@@ -1,3 +1,12 @@
+use common::{
+    knobs::{
+        FUNCTION_MAX_NUM_USER_WRITES,
+        FUNCTION_MAX_USER_WRITE_SIZE_BYTES,
+        TRANSACTION_MAX_NUM_USER_WRITES,
+        TRANSACTION_MAX_USER_WRITE_SIZE_BYTES,
+    },
+    types::FunctionLimits,
+};
 use value::DeveloperDocumentId;
 
 /// Metrics related to document writes
@@ -5,3 +14,38 @@ pub struct BiggestDocumentWrites {
     pub max_size: (DeveloperDocumentId, usize),
     pub max_nesting: (DeveloperDocumentId, usize),
 }
+
+/// How much a function execution can write to user tables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WriteLimits {
+    pub max_writes: usize,
+    pub max_bytes: usize,
+    /// Set if the limits came from the function's config rather than the
+    /// deployment defaults, so errors can say where the limit came from.
+    pub from_function_config: bool,
+}
+
+impl WriteLimits {
+    pub fn new(limits: &FunctionLimits) -> Self {
+        Self {
+            max_writes: limits
+                .max_documents_written
+                .map_or(*TRANSACTION_MAX_NUM_USER_WRITES, |n| {
+                    (n as usize).min(*FUNCTION_MAX_NUM_USER_WRITES)
+                }),
+            max_bytes: limits
+                .max_bytes_written
+                .map_or(*TRANSACTION_MAX_USER_WRITE_SIZE_BYTES, |n| {
+                    (n as usize).min(*FUNCTION_MAX_USER_WRITE_SIZE_BYTES)
+                }),
+            from_function_config: limits.max_documents_written.is_some()
+                || limits.max_bytes_written.is_some(),
+        }
+    }
+}
+
+impl Default for WriteLimits {
+    fn default() -> Self {
+        Self::new(&FunctionLimits::default())
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     borrow::Cow,
     collections::{
         BTreeMap,
+        BTreeSet,
         VecDeque,
     },
     sync::Arc,
@@ -484,6 +485,19 @@ impl LogWriter {
         let snapshot = { self.inner.lock().log.clone() };
         block_in_place(|| snapshot.is_stale(reads, reads_ts, ts))
     }
+
+    /// Returns whether any of `ids` were written after `ts`.
+    pub fn has_writes_to(
+        &self,
+        ids: &BTreeSet<ResolvedDocumentId>,
+        ts: Timestamp,
+    ) -> anyhow::Result<bool> {
+        let snapshot = { self.inner.lock().log.clone() };
+        block_in_place(|| {
+            let mut log_range = snapshot.iter(ts.succ()?, Timestamp::MAX)?;
+            Ok(log_range.any(|(_, mut writes, _)| writes.any(|(id, _)| ids.contains(id))))
+        })
+    }
 }
 
 /// Pending writes are used by the committer to detect conflicts between a new
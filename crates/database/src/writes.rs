@@ -1,10 +1,12 @@
 //! Write set tracking for an active transaction
 use std::{
     collections::BTreeSet,
+    fmt::Debug,
     ops::{
         Deref,
         DerefMut,
     },
+    sync::Arc,
 };
 
 use anyhow::Context;
@@ -25,16 +27,15 @@ use common::{
         Interval,
     },
     knobs::{
-        TRANSACTION_MAX_NUM_USER_WRITES,
         TRANSACTION_MAX_SYSTEM_NUM_WRITES,
         TRANSACTION_MAX_SYSTEM_WRITE_SIZE_BYTES,
-        TRANSACTION_MAX_USER_WRITE_SIZE_BYTES,
     },
     types::{
         TabletIndexName,
         WriteTimestamp,
     },
     value::{
+        ConvexObject,
         ResolvedDocumentId,
         Size,
     },
@@ -49,8 +50,12 @@ use value::{
 
 use crate::{
     bootstrap_model::defaults::BootstrapTableIds,
-    reads::TransactionReadSet,
+    reads::{
+        TransactionReadSet,
+        FUNCTION_CONFIG_LIMIT_SOURCE,
+    },
     schema_registry::SchemaRegistry,
+    write_limits::WriteLimits,
     ComponentRegistry,
     TableRegistry,
 };
@@ -62,6 +67,38 @@ pub struct DocumentWrite {
     pub document: Option<ResolvedDocument>,
 }
 
+/// An update to a document that is computed from the latest revision of the
+/// document when the transaction commits, instead of from the revision the
+/// transaction read. Transactions that only merge into a document don't read
+/// it, so concurrent merges into the same document don't conflict.
+pub trait DocumentMerge: Debug + Send + Sync {
+    /// Computes the new value of the document from its latest revision. An
+    /// error aborts the commit.
+    fn merge(&self, latest: &ResolvedDocument) -> anyhow::Result<ConvexObject>;
+}
+
+#[derive(Clone, Debug)]
+pub struct PendingMerge(Arc<dyn DocumentMerge>);
+
+impl PendingMerge {
+    pub fn new(merge: Arc<dyn DocumentMerge>) -> Self {
+        Self(merge)
+    }
+
+    pub fn apply(&self, latest: &ResolvedDocument) -> anyhow::Result<ResolvedDocument> {
+        latest.replace_value(self.0.merge(latest)?)
+    }
+}
+
+impl PartialEq for PendingMerge {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(Arc::as_ptr(&self.0), Arc::as_ptr(&other.0))
+    }
+}
+
+/// Merges to apply at commit time, in the order they were made.
+pub type DocumentMerges = OrdMap<ResolvedDocumentId, Vec<PendingMerge>>;
+
 pub trait PendingWrites: Clone {}
 
 impl PendingWrites for Writes {}
@@ -169,6 +206,8 @@ impl<W: PendingWrites> NestedWrites<W> {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Writes {
     updates: OrdMap<ResolvedDocumentId, DocumentUpdateWithPrevTs>,
+    // Documents that are never in `updates`.
+    merges: DocumentMerges,
 
     // Fields below can be recomputed from `updates`.
 
@@ -176,6 +215,8 @@ pub struct Writes {
     user_tx_size: TransactionWriteSize,
     // Size of writes to system tables
     system_tx_size: TransactionWriteSize,
+
+    limits: WriteLimits,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -192,14 +233,20 @@ impl Writes {
     pub fn new() -> Self {
         Self {
             updates: OrdMap::new(),
+            merges: OrdMap::new(),
             user_tx_size: TransactionWriteSize::default(),
             system_tx_size: TransactionWriteSize::default(),
+            limits: WriteLimits::default(),
         }
     }
 
+    pub fn set_limits(&mut self, limits: WriteLimits) {
+        self.limits = limits;
+    }
+
     /// Are there any writes in the active transaction?
     pub fn is_empty(&self) -> bool {
-        self.updates.is_empty()
+        self.updates.is_empty() && self.merges.is_empty()
     }
 
     pub fn update(
@@ -215,11 +262,80 @@ impl Writes {
             anyhow::ensure!(!self.updates.contains_key(&document_id), "Duplicate insert");
             self.register_new_id(reads, document_id)?;
         }
+        if self.merges.contains_key(&document_id) {
+            // Deleting a document drops the merges into it, but any other write
+            // would be based on a revision that doesn't include them.
+            anyhow::ensure!(
+                new_document.is_none(),
+                "Cannot write to document {document_id} with pending merges"
+            );
+            self.merges.remove(&document_id);
+        }
         Self::record_reads_for_write(bootstrap_tables, reads, document_id.tablet_id)?;
 
-        let id_size = document_id.size();
         let value_size = new_document.as_ref().map(|d| d.value().size()).unwrap_or(0);
+        self.record_write_size(is_system_document, document_id.size() + value_size)?;
+
+        if let Some(old_update) = self.updates.get_mut(&document_id) {
+            let (old_document, old_document_ts) = old_document.unzip();
+            anyhow::ensure!(
+                old_update.new_document == old_document,
+                "Inconsistent update: The old update's new document does not match the new \
+                 document's old update"
+            );
+            anyhow::ensure!(
+                [None, Some(WriteTimestamp::Pending)].contains(&old_document_ts),
+                "Inconsistent update: The new document's old update timestamp should be Pending \
+                 but is {:?}",
+                old_document_ts
+            );
+            old_update.new_document = new_document;
+        } else {
+            self.updates.insert(
+                document_id,
+                DocumentUpdateWithPrevTs {
+                    id: document_id,
+                    old_document: match old_document {
+                        Some((d, ts)) => Some((
+                            d,
+                            match ts {
+                                WriteTimestamp::Committed(ts) => ts,
+                                WriteTimestamp::Pending => anyhow::bail!(
+                                    "Old document timestamp is Pending, but there is no pending \
+                                     write"
+                                ),
+                            },
+                        )),
+                        None => None,
+                    },
+                    new_document,
+                },
+            );
+        }
 
+        Ok(())
+    }
+
+    /// Merge into a document at commit time. See [`DocumentMerge`].
+    pub fn merge(
+        &mut self,
+        bootstrap_tables: BootstrapTableIds,
+        is_system_document: bool,
+        reads: &mut TransactionReadSet,
+        document_id: ResolvedDocumentId,
+        merge: PendingMerge,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !self.updates.contains_key(&document_id),
+            "Cannot merge into document {document_id} that was written in this transaction"
+        );
+        Self::record_reads_for_write(bootstrap_tables, reads, document_id.tablet_id)?;
+        self.record_write_size(is_system_document, document_id.size())?;
+        self.merges.entry(document_id).or_default().push(merge);
+        Ok(())
+    }
+
+    fn record_write_size(&mut self, is_system_document: bool, size: usize) -> anyhow::Result<()> {
         let tx_size = if is_system_document {
             &mut self.system_tx_size
         } else {
@@ -230,7 +346,7 @@ impl Writes {
         // we want the size to reflect the write, so that
         // we can tell that we threw and not issue a warning.
         tx_size.num_writes += 1;
-        tx_size.size += id_size + value_size;
+        tx_size.size += size;
 
         if is_system_document {
             let tx_size = &self.system_tx_size;
@@ -250,66 +366,35 @@ impl Writes {
             tx_size
         } else {
             let tx_size = &self.user_tx_size;
+            let limits = &self.limits;
+            let limit_source = if limits.from_function_config {
+                FUNCTION_CONFIG_LIMIT_SOURCE
+            } else {
+                ""
+            };
             anyhow::ensure!(
-                tx_size.num_writes <= *TRANSACTION_MAX_NUM_USER_WRITES,
+                tx_size.num_writes <= limits.max_writes,
                 ErrorMetadata::pagination_limit(
                     "TooManyWrites",
                     format!(
-                        "Too many writes in a single function execution (limit: {})",
-                        *TRANSACTION_MAX_NUM_USER_WRITES,
+                        "Too many writes in a single function execution (limit: {}{limit_source})",
+                        limits.max_writes,
                     )
                 ),
             );
             anyhow::ensure!(
-                tx_size.size <= *TRANSACTION_MAX_USER_WRITE_SIZE_BYTES,
+                tx_size.size <= limits.max_bytes,
                 ErrorMetadata::pagination_limit(
                     "TooManyBytesWritten",
                     format!(
-                        "Too many bytes written in a single function execution (limit: {} bytes)",
-                        *TRANSACTION_MAX_USER_WRITE_SIZE_BYTES,
+                        "Too many bytes written in a single function execution (limit: {} \
+                         bytes{limit_source})",
+                        limits.max_bytes,
                     )
                 ),
             );
             tx_size
         };
-
-        if let Some(old_update) = self.updates.get_mut(&document_id) {
-            let (old_document, old_document_ts) = old_document.unzip();
-            anyhow::ensure!(
-                old_update.new_document == old_document,
-                "Inconsistent update: The old update's new document does not match the new \
-                 document's old update"
-            );
-            anyhow::ensure!(
-                [None, Some(WriteTimestamp::Pending)].contains(&old_document_ts),
-                "Inconsistent update: The new document's old update timestamp should be Pending \
-                 but is {:?}",
-                old_document_ts
-            );
-            old_update.new_document = new_document;
-        } else {
-            self.updates.insert(
-                document_id,
-                DocumentUpdateWithPrevTs {
-                    id: document_id,
-                    old_document: match old_document {
-                        Some((d, ts)) => Some((
-                            d,
-                            match ts {
-                                WriteTimestamp::Committed(ts) => ts,
-                                WriteTimestamp::Pending => anyhow::bail!(
-                                    "Old document timestamp is Pending, but there is no pending \
-                                     write"
-                                ),
-                            },
-                        )),
-                        None => None,
-                    },
-                    new_document,
-                },
-            );
-        }
-
         Ok(())
     }
 
@@ -420,6 +505,10 @@ impl Writes {
         self.updates
     }
 
+    pub fn merges(&self) -> &DocumentMerges {
+        &self.merges
+    }
+
     pub fn generated_ids(&self) -> BTreeSet<ResolvedDocumentId> {
         self.updates
             .iter()
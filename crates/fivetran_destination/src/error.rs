@@ -382,6 +382,7 @@ mod tests {
             staged_text_indexes: Default::default(),
             vector_indexes: Default::default(),
             staged_vector_indexes: Default::default(),
+            sparse_vector_indexes: Default::default(),
         };
 
         assert_eq!(
@@ -212,6 +212,7 @@ impl FivetranTableSchema {
             staged_text_indexes: Default::default(),
             vector_indexes: Default::default(),
             staged_vector_indexes: Default::default(),
+            sparse_vector_indexes: Default::default(),
         })
     }
 
@@ -669,6 +670,7 @@ impl FivetranTableSchema {
             staged_text_indexes: BTreeMap::new(),
             vector_indexes: BTreeMap::new(),
             staged_vector_indexes: BTreeMap::new(),
+            sparse_vector_indexes: BTreeMap::new(),
             document_type: Some(document_schema),
         })
     }
@@ -1084,6 +1086,7 @@ mod tests {
             staged_text_indexes: Default::default(),
             vector_indexes: Default::default(),
             staged_vector_indexes: Default::default(),
+            sparse_vector_indexes: Default::default(),
             document_type: Some(DocumentSchema::Union(vec![ObjectValidator(
                 fields
                     .into_iter()
@@ -1908,6 +1911,7 @@ mod tests {
                 staged_db_indexes: btreemap! {},
                 staged_text_indexes: btreemap! {},
                 staged_vector_indexes: btreemap! {},
+                sparse_vector_indexes: btreemap! {},
                 document_type: Some(DocumentSchema::Union(vec![object_validator!(
                     "name" => FieldValidator::required_field_type(Validator::Union(vec![
                         Validator::String,
@@ -52,6 +52,7 @@ use database::{
     TransactionIndex,
     TransactionReadSet,
     TransactionTextSnapshot,
+    TransactionVectorSnapshot,
     COMPONENTS_TABLE,
     SCHEMAS_TABLE,
 };
@@ -101,6 +102,7 @@ fn make_transaction<RT: Runtime>(
     table_count_snapshot: Arc<dyn TableCountSnapshot>,
     database_index_snapshot: DatabaseIndexSnapshot,
     text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+    vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
     retention_validator: Arc<dyn RetentionValidator>,
     virtual_system_mapping: VirtualSystemMapping,
     usage_tracker: FunctionUsageTracker,
@@ -109,8 +111,12 @@ fn make_transaction<RT: Runtime>(
     // The transaction timestamp might be few minutes behind if the backend
     // has been idle. Make sure creation time is always recent.
     let creation_time = CreationTime::try_from(cmp::max(*ts, rt.generate_timestamp()?))?;
-    let transaction_index =
-        TransactionIndex::new(index_registry, database_index_snapshot, text_index_snapshot);
+    let transaction_index = TransactionIndex::new(
+        index_registry,
+        database_index_snapshot,
+        text_index_snapshot,
+        vector_index_snapshot,
+    );
     Ok(Transaction::new(
         identity,
         id_generator,
@@ -607,6 +613,7 @@ impl<RT: Runtime> InMemoryIndexCache<RT> {
         bootstrap_metadata: BootstrapMetadata,
         table_count_snapshot: Arc<dyn TableCountSnapshot>,
         text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+        vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
         usage_tracker: FunctionUsageTracker,
         retention_validator: Arc<dyn RetentionValidator>,
     ) -> anyhow::Result<Transaction<RT>> {
@@ -652,11 +659,12 @@ impl<RT: Runtime> InMemoryIndexCache<RT> {
             table_count_snapshot,
             database_index_snapshot,
             text_index_snapshot,
+            vector_index_snapshot,
             retention_validator,
             virtual_system_mapping().clone(),
             usage_tracker,
         )?;
-        tx.merge_writes(existing_writes.updates)?;
+        tx.merge_writes(existing_writes.updates, existing_writes.merges)?;
         Ok(tx)
     }
 }
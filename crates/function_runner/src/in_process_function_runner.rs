@@ -40,6 +40,7 @@ use database::{
     shutdown_error,
     Database,
     TextIndexManagerSnapshot,
+    VectorIndexManagerSnapshot,
 };
 use errors::ErrorMetadata;
 use futures::{
@@ -224,11 +225,17 @@ impl<RT: Runtime> FunctionRunner<RT> for InProcessFunctionRunner<RT> {
         let snapshot = self.database.snapshot(ts)?;
         let table_count_snapshot = Arc::new(snapshot.table_summaries);
         let text_index_snapshot = Arc::new(TextIndexManagerSnapshot::new(
-            snapshot.index_registry,
+            snapshot.index_registry.clone(),
             snapshot.text_indexes,
             self.database.searcher.clone(),
             self.database.search_storage.clone(),
         ));
+        let vector_index_snapshot = Arc::new(VectorIndexManagerSnapshot::new(
+            snapshot.index_registry,
+            snapshot.vector_indexes,
+            self.database.searcher.clone(),
+            self.database.search_storage.clone(),
+        ));
         let action_callbacks = self
             .action_callbacks
             .read()
@@ -245,6 +252,7 @@ impl<RT: Runtime> FunctionRunner<RT> for InProcessFunctionRunner<RT> {
             bootstrap_metadata: self.database.bootstrap_metadata.clone(),
             table_count_snapshot,
             text_index_snapshot,
+            vector_index_snapshot,
             action_callbacks,
             fetch_client: self.fetch_client.clone(),
             log_line_sender,
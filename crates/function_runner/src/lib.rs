@@ -34,6 +34,7 @@ use common::{
     },
 };
 use database::{
+    DocumentMerges,
     ReadSet,
     Transaction,
     TransactionReadSet,
@@ -217,6 +218,7 @@ impl From<TransactionReadSet> for FunctionReads {
 #[derive(Clone, Default)]
 pub struct FunctionWrites {
     pub updates: OrdMap<ResolvedDocumentId, DocumentUpdateWithPrevTs>,
+    pub merges: DocumentMerges,
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -229,6 +231,7 @@ impl proptest::arbitrary::Arbitrary for FunctionWrites {
         proptest::collection::vec(proptest::prelude::any::<DocumentUpdateWithPrevTs>(), 0..4)
             .prop_map(|updates| Self {
                 updates: updates.into_iter().map(|u| (u.id, u)).collect(),
+                merges: DocumentMerges::new(),
             })
             .boxed()
     }
@@ -237,6 +240,7 @@ impl proptest::arbitrary::Arbitrary for FunctionWrites {
 impl From<Writes> for FunctionWrites {
     fn from(writes: Writes) -> Self {
         Self {
+            merges: writes.merges().clone(),
             updates: writes.into_updates(),
         }
     }
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+};
 
 use async_lru::async_lru::AsyncLru;
 use async_trait::async_trait;
@@ -8,20 +11,43 @@ use common::{
         FUNRUN_CODE_CACHE_SIZE,
         FUNRUN_MODULE_CACHE_SIZE,
         FUNRUN_MODULE_MAX_CONCURRENCY,
+        FUNRUN_SNAPSHOT_CACHE_SIZE,
+    },
+    runtime::{
+        Runtime,
+        SpawnHandle,
     },
-    runtime::Runtime,
+    sha256::Sha256,
+    types::ModuleEnvironment,
 };
 use futures::FutureExt;
-use isolate::environment::helpers::module_loader::get_module_and_prefetch;
+use isolate::{
+    environment::helpers::module_loader::{
+        get_module_and_prefetch,
+        module_specifier_from_path,
+    },
+    metrics::create_deployment_snapshot_timer,
+    snapshot::{
+        create_deployment_snapshot,
+        DeploymentSnapshot,
+    },
+};
 use model::{
     config::module_loader::ModuleLoader,
+    environment_variables::types::{
+        EnvVarName,
+        EnvVarValue,
+    },
     modules::{
         module_versions::FullModuleSource,
         types::ModuleMetadata,
     },
-    source_packages::types::{
-        SourcePackage,
-        SourcePackageId,
+    source_packages::{
+        types::{
+            SourcePackage,
+            SourcePackageId,
+        },
+        upload_download::download_package,
     },
 };
 use moka::sync::Cache;
@@ -64,9 +90,109 @@ impl CodeCache {
     }
 }
 
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct SnapshotAttemptKey {
+    instance_name: String,
+    source_package_id: SourcePackageId,
+    environment_variables_digest: String,
+}
+
+/// The latest deployment snapshot for each instance. Snapshots are built on
+/// a dedicated thread, and each combination of source package and
+/// environment variables is only attempted once so deployments whose modules
+/// can't be snapshotted don't retry on every request.
+#[derive(Clone)]
+pub(crate) struct SnapshotCache<RT: Runtime> {
+    rt: RT,
+    snapshots: Arc<Cache<String, Arc<DeploymentSnapshot>>>,
+    attempts: Arc<Cache<SnapshotAttemptKey, ()>>,
+}
+
+impl<RT: Runtime> SnapshotCache<RT> {
+    pub(crate) fn new(rt: RT) -> Self {
+        Self {
+            rt,
+            snapshots: Arc::new(
+                Cache::builder()
+                    .max_capacity(*FUNRUN_SNAPSHOT_CACHE_SIZE)
+                    .weigher(|_, snapshot: &Arc<DeploymentSnapshot>| {
+                        u32::try_from(snapshot.blob().len()).unwrap_or(u32::MAX)
+                    })
+                    .build(),
+            ),
+            attempts: Arc::new(Cache::new(10_000)),
+        }
+    }
+
+    fn build(
+        &self,
+        instance_name: String,
+        modules_storage: Arc<dyn Storage>,
+        source_package: ParsedDocument<SourcePackage>,
+        environment_variables: BTreeMap<EnvVarName, EnvVarValue>,
+    ) {
+        let source_package_id: SourcePackageId = source_package.developer_id().into();
+        let mut hasher = Sha256::new();
+        for (name, value) in &environment_variables {
+            hasher.update(name.as_ref().as_bytes());
+            hasher.update(&[0]);
+            hasher.update(value.as_ref().as_bytes());
+            hasher.update(&[0]);
+        }
+        let key = SnapshotAttemptKey {
+            instance_name: instance_name.clone(),
+            source_package_id,
+            environment_variables_digest: hasher.finalize().as_hex(),
+        };
+        if self.attempts.contains_key(&key) {
+            return;
+        }
+        self.attempts.insert(key, ());
+        let snapshots = self.snapshots.clone();
+        self.rt
+            .spawn_thread("deployment_snapshot", move || async move {
+                let result: anyhow::Result<DeploymentSnapshot> = try {
+                    let package = download_package(
+                        modules_storage,
+                        source_package.storage_key.clone(),
+                        source_package.sha256.clone(),
+                    )
+                    .await?;
+                    let mut modules = vec![];
+                    for (path, module_config) in package {
+                        if module_config.environment != ModuleEnvironment::Isolate {
+                            continue;
+                        }
+                        let source = FullModuleSource {
+                            source: module_config.source,
+                            source_map: module_config.source_map,
+                        };
+                        modules.push((module_specifier_from_path(&path)?, Arc::new(source)));
+                    }
+                    let timer = create_deployment_snapshot_timer();
+                    let snapshot = create_deployment_snapshot(
+                        source_package_id,
+                        modules,
+                        environment_variables,
+                    )?;
+                    timer.finish();
+                    snapshot
+                };
+                match result {
+                    Ok(snapshot) => snapshots.insert(instance_name, Arc::new(snapshot)),
+                    Err(e) => tracing::warn!(
+                        "Failed to create deployment snapshot for {instance_name}: {e:#}"
+                    ),
+                }
+            })
+            .detach();
+    }
+}
+
 pub(crate) struct FunctionRunnerModuleLoader<RT: Runtime> {
     pub cache: ModuleCache<RT>,
     pub code_cache: CodeCache,
+    pub snapshot_cache: SnapshotCache<RT>,
     pub instance_name: String,
     pub modules_storage: Arc<dyn Storage>,
 }
@@ -137,4 +263,21 @@ impl<RT: Runtime> isolate::module_cache::ModuleCache<RT> for FunctionRunnerModul
     fn get_cached_code(&self, module_metadata: &ModuleMetadata) -> Option<Arc<[u8]>> {
         self.code_cache.0.get(&self.cache_key(module_metadata))
     }
+
+    fn get_deployment_snapshot(&self) -> Option<Arc<DeploymentSnapshot>> {
+        self.snapshot_cache.snapshots.get(&self.instance_name)
+    }
+
+    fn build_deployment_snapshot(
+        &self,
+        source_package: ParsedDocument<SourcePackage>,
+        environment_variables: BTreeMap<EnvVarName, EnvVarValue>,
+    ) {
+        self.snapshot_cache.build(
+            self.instance_name.clone(),
+            self.modules_storage.clone(),
+            source_package,
+            environment_variables,
+        );
+    }
 }
@@ -49,6 +49,7 @@ use database::{
     TableCountSnapshot,
     Transaction,
     TransactionTextSnapshot,
+    TransactionVectorSnapshot,
 };
 use file_storage::TransactionalFileStorage;
 use futures::FutureExt;
@@ -93,6 +94,7 @@ use udf::{
         ValidatedPathAndArgs,
     },
     EvaluateAppDefinitionsResult,
+    ExecutionRecording,
     FunctionOutcome,
     HttpActionRequest as HttpActionRequestInner,
     HttpActionResponseStreamer,
@@ -109,6 +111,7 @@ use crate::{
         CodeCache,
         FunctionRunnerModuleLoader,
         ModuleCache,
+        SnapshotCache,
     },
     FunctionFinalTransaction,
     FunctionWrites,
@@ -124,6 +127,7 @@ pub struct RunRequestArgs {
     pub bootstrap_metadata: BootstrapMetadata,
     pub table_count_snapshot: Arc<dyn TableCountSnapshot>,
     pub text_index_snapshot: Arc<dyn TransactionTextSnapshot>,
+    pub vector_index_snapshot: Arc<dyn TransactionVectorSnapshot>,
     pub action_callbacks: Arc<dyn ActionCallbacks>,
     pub fetch_client: Arc<dyn FetchClient>,
     pub log_line_sender: Option<mpsc::UnboundedSender<LogLine>>,
@@ -141,6 +145,9 @@ pub struct RunRequestArgs {
 pub struct FunctionMetadata {
     pub path_and_args: ValidatedPathAndArgs,
     pub journal: QueryJournal,
+    /// Replay this recording of a query or mutation instead of running it
+    /// against the database. No transaction is returned for replays.
+    pub replay: Option<ExecutionRecording>,
 }
 
 pub struct HttpActionMetadata {
@@ -189,6 +196,7 @@ pub struct FunctionRunnerCore<RT: Runtime, S: StorageForInstance<RT>> {
     index_cache: InMemoryIndexCache<RT>,
     module_cache: ModuleCache<RT>,
     code_cache: CodeCache,
+    snapshot_cache: SnapshotCache<RT>,
     isolate_client: IsolateClient<RT>,
 }
 
@@ -200,6 +208,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> Clone for FunctionRunnerCore<RT, S>
             index_cache: self.index_cache.clone(),
             module_cache: self.module_cache.clone(),
             code_cache: self.code_cache.clone(),
+            snapshot_cache: self.snapshot_cache.clone(),
             isolate_client: self.isolate_client.clone(),
         }
     }
@@ -245,6 +254,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
         let index_cache = InMemoryIndexCache::new(rt.clone());
         let module_cache = ModuleCache::new(rt.clone());
         let code_cache = CodeCache::new();
+        let snapshot_cache = SnapshotCache::new(rt.clone());
 
         Ok(Self {
             rt,
@@ -252,6 +262,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
             index_cache,
             module_cache,
             code_cache,
+            snapshot_cache,
             isolate_client,
         })
     }
@@ -295,6 +306,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
             bootstrap_metadata,
             table_count_snapshot,
             text_index_snapshot,
+            vector_index_snapshot,
             action_callbacks,
             fetch_client,
             log_line_sender,
@@ -341,6 +353,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
                 bootstrap_metadata,
                 table_count_snapshot,
                 text_index_snapshot,
+                vector_index_snapshot,
                 usage_tracker.clone(),
                 retention_validator,
             )
@@ -364,6 +377,7 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
                 instance_name: instance_name.clone(),
                 cache: self.module_cache.clone(),
                 code_cache: self.code_cache.clone(),
+                snapshot_cache: self.snapshot_cache.clone(),
                 modules_storage,
             }),
         };
@@ -373,7 +387,21 @@ impl<RT: Runtime, S: StorageForInstance<RT>> FunctionRunnerCore<RT, S> {
                 let FunctionMetadata {
                     path_and_args,
                     journal,
+                    replay,
                 } = function_metadata.context("Missing function metadata for query or mutation")?;
+                if let Some(recording) = replay {
+                    let outcome = self
+                        .isolate_client
+                        .replay_udf(
+                            recording,
+                            transaction,
+                            context,
+                            environment_data,
+                            instance_name,
+                        )
+                        .await?;
+                    return Ok((None, outcome, usage_tracker.gather_user_stats()));
+                }
                 let (tx, outcome) = self
                     .isolate_client
                     .execute_udf(
@@ -177,7 +177,9 @@ impl BackendInMemoryIndexes {
                             index_metadata.name
                         )
                     },
-                    IndexConfig::Text { .. } | IndexConfig::Vector { .. } => {
+                    IndexConfig::Text { .. }
+                    | IndexConfig::Vector { .. }
+                    | IndexConfig::SparseVector { .. } => {
                         // We do not load search or vector indexes into memory.
                         continue;
                     },
@@ -15,6 +15,7 @@ use common::{
             IndexedFields,
         },
         text_index::DeveloperTextIndexConfig,
+        vector_index::DeveloperVectorIndexConfig,
         DeveloperIndexConfig,
         IndexConfig,
         TabletIndexMetadata,
@@ -33,10 +34,12 @@ use common::{
         ResolvedDocument,
     },
     document_index_keys::{
+        CompactVector,
         DocumentIndexKeyValue,
         DocumentIndexKeys,
         SearchIndexKeyValue,
         SearchValueTokens,
+        VectorIndexKeyValue,
     },
     index::{
         IndexKey,
@@ -271,6 +274,7 @@ impl IndexRegistry {
                             DeveloperTextIndexConfig {
                                 search_field,
                                 filter_fields,
+                                ..
                             },
                         ..
                     } => {
@@ -294,7 +298,51 @@ impl IndexRegistry {
                             search_field_value,
                         }))
                     },
-                    IndexConfig::Vector { .. } => None,
+                    IndexConfig::Vector {
+                        developer_config:
+                            DeveloperVectorIndexConfig {
+                                dimensions,
+                                vector_field,
+                                filter_fields,
+                                ..
+                            },
+                        ..
+                    } => {
+                        let filter_values = filter_fields
+                            .iter()
+                            .map(|field| {
+                                let value = document.value().get_path(field);
+                                let bytes = SearchFilterValue::from_search_value(value.as_ref());
+                                (field.clone(), bytes)
+                            })
+                            .collect();
+
+                        // Match the vector index: only arrays of floats with the
+                        // index's dimensions are indexed.
+                        let vector = match document.value().get_path(vector_field) {
+                            Some(ConvexValue::Array(array))
+                                if array.len() == usize::from(*dimensions) =>
+                            {
+                                array
+                                    .iter()
+                                    .map(|value| match value {
+                                        ConvexValue::Float64(f) => Some(*f as f32),
+                                        _ => None,
+                                    })
+                                    .collect::<Option<Vec<f32>>>()
+                                    .map(|vector| CompactVector::new(&vector))
+                            },
+                            _ => None,
+                        };
+
+                        Some(DocumentIndexKeyValue::Vector(VectorIndexKeyValue {
+                            filter_values,
+                            vector,
+                        }))
+                    },
+                    // Sparse vector indexes aren't readable from queries, so
+                    // there are no subscriptions for them to invalidate.
+                    IndexConfig::SparseVector { .. } => None,
                 };
 
                 key.map(|key| {
@@ -499,9 +547,18 @@ impl IndexRegistry {
             .collect()
     }
 
+    pub fn all_sparse_vector_indexes(&self) -> Vec<ParsedDocument<TabletIndexMetadata>> {
+        self.all_indexes()
+            .filter(|index| index.is_sparse_vector_index())
+            .cloned()
+            .collect()
+    }
+
     pub fn all_search_and_vector_indexes(&self) -> Vec<ParsedDocument<TabletIndexMetadata>> {
         self.all_indexes()
-            .filter(|index| index.is_text_index() || index.is_vector_index())
+            .filter(|index| {
+                index.is_text_index() || index.is_vector_index() || index.is_sparse_vector_index()
+            })
             .cloned()
             .collect()
     }
@@ -530,7 +587,9 @@ impl IndexRegistry {
                     IndexConfig::Database {
                         developer_config, ..
                     } => Some((index_id, (index_name, developer_config.fields.clone()))),
-                    IndexConfig::Text { .. } | IndexConfig::Vector { .. } => None,
+                    IndexConfig::Text { .. }
+                    | IndexConfig::Vector { .. }
+                    | IndexConfig::SparseVector { .. } => None,
                 }
             })
             .collect()
@@ -587,6 +646,14 @@ impl IndexRegistry {
             .filter(|index| index.metadata.is_vector_index())
     }
 
+    pub fn sparse_vector_indexes_by_table(
+        &self,
+        tablet_id: TabletId,
+    ) -> impl Iterator<Item = &'_ Index> + '_ {
+        self.indexes_by_table(tablet_id)
+            .filter(|index| index.metadata.is_sparse_vector_index())
+    }
+
     /// Returns both enabled and pending indexes for the given table.
     ///
     /// Multiple Indexes with a given name will be returned if an index is
@@ -848,6 +915,7 @@ mod tests {
                 DeveloperTextIndexConfig {
                     search_field: FieldPath::from_str("content")?,
                     filter_fields: vec![FieldPath::from_str("author")?].into_iter().collect(),
+                    synonyms: vec![],
                 },
                 TextIndexState::SnapshottedAt(TextIndexSnapshot {
                     data: TextIndexSnapshotData::MultiSegment(vec![]),
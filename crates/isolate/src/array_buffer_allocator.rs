@@ -19,6 +19,7 @@ use std::{
 use deno_core::v8::{
     new_rust_allocator,
     Allocator,
+    HeapStatistics,
     RustAllocatorVtable,
     UniqueRef,
 };
@@ -37,6 +38,15 @@ impl ArrayBufferMemoryLimit {
             .saturating_sub(self.available.load(Ordering::Relaxed))
     }
 
+    /// Returns the amount of memory used by ArrayBuffers, including
+    /// WebAssembly memories. V8 allocates wasm memories itself instead of
+    /// going through this allocator and counts them in the isolate's external
+    /// memory. That may count some ArrayBuffers twice, but it never lets a
+    /// function use both budgets in full.
+    pub fn used_including_wasm(&self, stats: &HeapStatistics) -> usize {
+        self.used().saturating_add(stats.external_memory())
+    }
+
     fn consume(&self, amount: usize) -> bool {
         let mut limit = self.available.load(Ordering::Relaxed);
         loop {
@@ -6,6 +6,7 @@ use std::{
         VecDeque,
     },
     env,
+    path::PathBuf,
     sync::{
         Arc,
         Once,
@@ -54,10 +55,13 @@ use common::{
     knobs::{
         FUNRUN_ISOLATE_ACTIVE_THREADS,
         HEAP_WORKER_REPORT_INTERVAL_SECONDS,
+        ISOLATE_DEPLOYMENT_SNAPSHOTS,
         ISOLATE_IDLE_TIMEOUT,
         ISOLATE_MAX_LIFETIME,
         ISOLATE_QUEUE_SIZE,
         REUSE_ISOLATES,
+        UDF_EXECUTION_RECORDING_DIR,
+        UDF_EXECUTION_RECORDING_FUNCTIONS,
         V8_THREADS,
     },
     log_lines::LogLine,
@@ -77,6 +81,7 @@ use common::{
     utils::ensure_utc,
 };
 use database::{
+    hybrid_search::HybridSearchResult,
     shutdown_error,
     Transaction,
 };
@@ -114,10 +119,21 @@ use model::{
         types::FileStorageEntry,
         FileStorageId,
     },
-    modules::module_versions::{
-        AnalyzedModule,
-        ModuleSource,
-        SourceMap,
+    modules::{
+        module_versions::{
+            AnalyzedModule,
+            ModuleSource,
+            SourceMap,
+        },
+        wasm::max_wasm_memory_pages,
+    },
+    rate_limits::types::{
+        RateLimitRequest,
+        RateLimitResult,
+    },
+    scheduled_jobs::types::{
+        ScheduledJobQueue,
+        ScheduledJobRetryPolicy,
     },
     udf_config::types::UdfConfig,
 };
@@ -137,6 +153,7 @@ use udf::{
     },
     ActionOutcome,
     EvaluateAppDefinitionsResult,
+    ExecutionRecording,
     FunctionOutcome,
     FunctionResult,
     HttpActionOutcome,
@@ -147,7 +164,10 @@ use value::{
     id_v6::DeveloperDocumentId,
     identifier::Identifier,
 };
-use vector::PublicVectorSearchQueryResult;
+use vector::{
+    PublicVectorSearchQueryResult,
+    VectorSearchPage,
+};
 
 use crate::{
     concurrency_limiter::ConcurrencyLimiter,
@@ -166,6 +186,7 @@ use crate::{
         queue_timer,
     },
     module_cache::ModuleCache,
+    snapshot::DeploymentSnapshot,
 };
 
 // We gather prometheus stats every 30 seconds, so we should make sure we log
@@ -289,6 +310,8 @@ pub trait ActionCallbacks: Send + Sync {
         udf_args: Vec<JsonValue>,
         scheduled_ts: UnixTimestamp,
         context: ExecutionContext,
+        retry_policy: Option<ScheduledJobRetryPolicy>,
+        queue: Option<ScheduledJobQueue>,
     ) -> anyhow::Result<DeveloperDocumentId>;
 
     async fn cancel_job(
@@ -297,11 +320,41 @@ pub trait ActionCallbacks: Send + Sync {
         virtual_id: DeveloperDocumentId,
     ) -> anyhow::Result<()>;
 
+    // Rate limits
+    async fn rate_limit(
+        &self,
+        identity: Identity,
+        component: ComponentId,
+        request: RateLimitRequest,
+    ) -> anyhow::Result<RateLimitResult>;
+
+    async fn reset_rate_limit(
+        &self,
+        identity: Identity,
+        component: ComponentId,
+        name: String,
+        key: Option<String>,
+    ) -> anyhow::Result<()>;
+
     // Vector Search
     async fn vector_search(
         &self,
         identity: Identity,
         query: JsonValue,
+    ) -> anyhow::Result<(VectorSearchPage, FunctionUsageStats)>;
+
+    // Hybrid Search
+    async fn hybrid_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<HybridSearchResult>, FunctionUsageStats)>;
+
+    // Sparse Vector Search
+    async fn sparse_vector_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
     ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)>;
 
     // Components
@@ -323,6 +376,17 @@ pub struct UdfRequest<RT: Runtime> {
     pub transaction: Transaction<RT>,
     pub journal: QueryJournal,
     pub context: ExecutionContext,
+    pub recording: ExecutionRecordingMode,
+}
+
+/// Whether to record a query or mutation's execution, or to replay an
+/// earlier recording of it.
+pub enum ExecutionRecordingMode {
+    Disabled,
+    /// Record the execution and write it to a new file in this directory.
+    Record(PathBuf),
+    /// Serve syscalls from an earlier recording instead of running them.
+    Replay(ExecutionRecording),
 }
 
 pub struct HttpActionRequest<RT: Runtime> {
@@ -441,6 +505,22 @@ pub enum RequestType<RT: Runtime> {
     },
 }
 
+impl<RT: Runtime> RequestType<RT> {
+    /// The deployment snapshot the request would like its isolate to be
+    /// created from, if any. Only queries and mutations use snapshots.
+    fn deployment_snapshot(&self) -> Option<Arc<DeploymentSnapshot>> {
+        if !*ISOLATE_DEPLOYMENT_SNAPSHOTS {
+            return None;
+        }
+        match self {
+            RequestType::Udf {
+                environment_data, ..
+            } => environment_data.module_loader.get_deployment_snapshot(),
+            _ => None,
+        }
+    }
+}
+
 #[async_trait]
 pub trait UdfCallback<RT: Runtime>: Send + Sync {
     async fn execute_udf(
@@ -582,6 +662,14 @@ pub fn initialize_v8() {
             // tell V8 it can use up to 2MiB of stack space itself. The
             // default is 1MiB. Note that the flag is in KiB (https://github.com/v8/v8/blob/master/src/flags/flag-definitions.h#L1594).
             "--stack-size=2048".to_string(),
+            // WebAssembly memories aren't allocated through our ArrayBuffer
+            // allocator, so cap each one at the ArrayBuffer limit. This also
+            // makes `memory.grow` fail deterministically in queries and
+            // mutations.
+            format!("--wasm-max-mem-pages={}", max_wasm_memory_pages()),
+            // Relaxed SIMD instructions may give different results on
+            // different CPUs, which queries and mutations can't allow.
+            "--no-experimental-wasm-relaxed-simd".to_string(),
         ];
         if let Ok(flags) = env::var("ISOLATE_V8_FLAGS") {
             argv.extend(
@@ -687,6 +775,76 @@ impl<RT: Runtime> IsolateClient<RT> {
         reactor_depth: usize,
         instance_name: String,
         function_started_sender: Option<oneshot::Sender<()>>,
+    ) -> anyhow::Result<(Transaction<RT>, FunctionOutcome)> {
+        let udf_path = path_and_args.path().udf_path.to_string();
+        let recording = match &*UDF_EXECUTION_RECORDING_DIR {
+            Some(dir) if UDF_EXECUTION_RECORDING_FUNCTIONS.contains(&udf_path) => {
+                ExecutionRecordingMode::Record(dir.clone())
+            },
+            _ => ExecutionRecordingMode::Disabled,
+        };
+        self.execute_udf_with_recording(
+            udf_type,
+            path_and_args,
+            transaction,
+            journal,
+            context,
+            environment_data,
+            reactor_depth,
+            instance_name,
+            function_started_sender,
+            recording,
+        )
+        .await
+    }
+
+    /// Re-executes a query or mutation from a recording written while
+    /// `UDF_EXECUTION_RECORDING_DIR` was set. The function gets the recorded
+    /// arguments, random seed and time, and every syscall returns what it
+    /// returned when recorded, so the execution is deterministic. Fails if
+    /// any module it loads has changed since it was recorded.
+    ///
+    /// `transaction` is only used to load the function's modules and
+    /// environment variables, so it should be at the recording's
+    /// `snapshot_ts`. Nothing written during the replay is committed.
+    #[fastrace::trace]
+    pub async fn replay_udf(
+        &self,
+        recording: ExecutionRecording,
+        transaction: Transaction<RT>,
+        context: ExecutionContext,
+        environment_data: EnvironmentData<RT>,
+        instance_name: String,
+    ) -> anyhow::Result<FunctionOutcome> {
+        let (_, outcome) = self
+            .execute_udf_with_recording(
+                recording.udf_type,
+                recording.path_and_args.clone(),
+                transaction,
+                QueryJournal::new(),
+                context,
+                environment_data,
+                0,
+                instance_name,
+                None,
+                ExecutionRecordingMode::Replay(recording),
+            )
+            .await?;
+        Ok(outcome)
+    }
+
+    pub(crate) async fn execute_udf_with_recording(
+        &self,
+        udf_type: UdfType,
+        path_and_args: ValidatedPathAndArgs,
+        transaction: Transaction<RT>,
+        journal: QueryJournal,
+        context: ExecutionContext,
+        environment_data: EnvironmentData<RT>,
+        reactor_depth: usize,
+        instance_name: String,
+        function_started_sender: Option<oneshot::Sender<()>>,
+        recording: ExecutionRecordingMode,
     ) -> anyhow::Result<(Transaction<RT>, FunctionOutcome)> {
         let (tx, rx) = oneshot::channel();
         let request = RequestType::Udf {
@@ -696,6 +854,7 @@ impl<RT: Runtime> IsolateClient<RT> {
                 transaction,
                 journal,
                 context,
+                recording,
             },
             environment_data,
             response: tx,
@@ -1353,10 +1512,16 @@ pub trait IsolateWorker<RT: Runtime>: Clone + Send + 'static {
         } = self.config();
         let mut reqs = std::pin::pin!(ReceiverStream::new(reqs).peekable());
         let mut ready: Option<(oneshot::Sender<_>, _)> = None;
+        let mut deployment_snapshot: Option<Arc<DeploymentSnapshot>> = None;
         'recreate_isolate: loop {
             let mut last_client_id: Option<String> = None;
             let mut last_request: Option<String> = None;
-            let mut isolate = Isolate::new(self.rt(), *max_user_timeout, limiter.clone());
+            let mut isolate = Isolate::new_with_deployment_snapshot(
+                self.rt(),
+                *max_user_timeout,
+                limiter.clone(),
+                deployment_snapshot.clone(),
+            );
             heap_stats.store(isolate.heap_stats());
             loop {
                 let v8_context = {
@@ -1404,6 +1569,18 @@ pub trait IsolateWorker<RT: Runtime>: Clone + Send + 'static {
                         } else if reused {
                             tracing::debug!("Reusing isolate for client {}", req.client_id);
                         }
+                        // Requests without a snapshot can run in any isolate, but requests
+                        // with one need an isolate created from it.
+                        if let Some(snapshot) = req.inner.deployment_snapshot()
+                            && !isolate
+                                .deployment_snapshot()
+                                .is_some_and(|existing| Arc::ptr_eq(existing, &snapshot))
+                        {
+                            tracing::debug!("Restarting isolate for a new deployment snapshot");
+                            metrics::log_recreate_isolate("deployment_snapshot");
+                            deployment_snapshot = Some(snapshot);
+                            continue 'recreate_isolate;
+                        }
                         // Ok, we're ready to accept the request for real.
                         let Some((req, done, done_token)) = reqs.next().await else { return };
                         // Note that we won't reply to `done` until the next
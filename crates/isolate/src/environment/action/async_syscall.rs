@@ -12,6 +12,7 @@ use common::{
         UnixTimestamp,
     },
 };
+use database::hybrid_search::HybridSearchJson;
 use errors::{
     ErrorMetadata,
     ErrorMetadataAnyhowExt,
@@ -19,6 +20,16 @@ use errors::{
 use model::{
     components::auth::propagate_component_auth,
     file_storage::FileStorageId,
+    rate_limits::types::{
+        RateLimitRequestJson,
+        ResetRateLimitJson,
+    },
+    scheduled_jobs::types::{
+        ScheduledJobQueue,
+        ScheduledJobQueueJson,
+        ScheduledJobRetryPolicy,
+        ScheduledJobRetryPolicyJson,
+    },
 };
 use serde::{
     Deserialize,
@@ -33,6 +44,7 @@ use value::{
     JsonPackedValue,
 };
 use vector::{
+    SparseVectorSearchJson,
     VectorSearchJson,
     VectorSearchRequest,
 };
@@ -66,7 +78,15 @@ impl<RT: Runtime> TaskExecutor<RT> {
                 "1.0/actions/action" => self.async_syscall_actions_runAction(args).await?.into(),
                 "1.0/actions/schedule" => self.async_syscall_schedule(args).await?.into(),
                 "1.0/actions/cancel_job" => self.async_syscall_cancel_job(args).await?.into(),
+                "1.0/actions/rateLimit" => self.async_syscall_rateLimit(args).await?.into(),
+                "1.0/actions/resetRateLimit" => {
+                    self.async_syscall_resetRateLimit(args).await?.into()
+                },
                 "1.0/actions/vectorSearch" => self.async_syscall_vectorSearch(args).await?.into(),
+                "1.0/actions/hybridSearch" => self.async_syscall_hybridSearch(args).await?.into(),
+                "1.0/actions/sparseVectorSearch" => {
+                    self.async_syscall_sparseVectorSearch(args).await?.into()
+                },
                 "1.0/getUserIdentity" => self.async_syscall_getUserIdentity(args).await?.into(),
                 "1.0/storageDelete" => self.async_syscall_storageDelete(args).await?.into(),
                 "1.0/storageGetMetadata" => {
@@ -269,6 +289,8 @@ impl<RT: Runtime> TaskExecutor<RT> {
             function_handle: Option<String>,
             ts: f64,
             args: UdfArgsJson,
+            retry: Option<ScheduledJobRetryPolicyJson>,
+            queue: Option<ScheduledJobQueueJson>,
         }
 
         let ScheduleArgs {
@@ -277,7 +299,11 @@ impl<RT: Runtime> TaskExecutor<RT> {
             function_handle,
             ts,
             args,
+            retry,
+            queue,
         }: ScheduleArgs = with_argument_error("scheduler", || Ok(serde_json::from_value(args)?))?;
+        let retry_policy = retry.map(ScheduledJobRetryPolicy::try_from).transpose()?;
+        let queue = queue.map(ScheduledJobQueue::try_from).transpose()?;
         let path = match function_handle {
             Some(h) => {
                 let handle: FunctionHandle = with_argument_error("scheduler", || h.parse())?;
@@ -300,6 +326,8 @@ impl<RT: Runtime> TaskExecutor<RT> {
                 args.into_arg_vec(),
                 scheduled_ts,
                 self.context.clone(),
+                retry_policy,
+                queue,
             )
             .await?;
 
@@ -325,6 +353,31 @@ impl<RT: Runtime> TaskExecutor<RT> {
         Ok(JsonValue::Null)
     }
 
+    #[convex_macro::instrument_future]
+    async fn async_syscall_rateLimit(&self, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let request: RateLimitRequestJson =
+            with_argument_error("rateLimit", || Ok(serde_json::from_value(args)?))?;
+        let result = self
+            .action_callbacks
+            .rate_limit(
+                self.identity.clone(),
+                self.component_id(),
+                request.try_into()?,
+            )
+            .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn async_syscall_resetRateLimit(&self, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let ResetRateLimitJson { name, key } =
+            with_argument_error("resetRateLimit", || Ok(serde_json::from_value(args)?))?;
+        self.action_callbacks
+            .reset_rate_limit(self.identity.clone(), self.component_id(), name, key)
+            .await?;
+        Ok(JsonValue::Null)
+    }
+
     #[convex_macro::instrument_future]
     async fn async_syscall_vectorSearch(&self, args: JsonValue) -> anyhow::Result<JsonValue> {
         let VectorSearchRequest { query } = serde_json::from_value(args)?;
@@ -332,7 +385,7 @@ impl<RT: Runtime> TaskExecutor<RT> {
         let mut vector_search_query: VectorSearchJson = serde_json::from_value(query)?;
         vector_search_query.insert_component_id(component_id);
 
-        let (results, usage_stats) = self
+        let (page, usage_stats) = self
             .action_callbacks
             .vector_search(
                 self.identity.clone(),
@@ -340,6 +393,43 @@ impl<RT: Runtime> TaskExecutor<RT> {
             )
             .await?;
         self.usage_tracker.add(usage_stats);
+        Ok(JsonValue::from(page))
+    }
+
+    #[convex_macro::instrument_future]
+    async fn async_syscall_hybridSearch(&self, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let VectorSearchRequest { query } = serde_json::from_value(args)?;
+        let component_id = self.component_id();
+        let mut hybrid_search_query: HybridSearchJson = serde_json::from_value(query)?;
+        hybrid_search_query.insert_component_id(component_id);
+
+        let (results, usage_stats) = self
+            .action_callbacks
+            .hybrid_search(
+                self.identity.clone(),
+                serde_json::to_value(hybrid_search_query)?,
+            )
+            .await?;
+        self.usage_tracker.add(usage_stats);
+        let results: Vec<_> = results.into_iter().map(JsonValue::from).collect();
+        Ok(json!({ "results": results }))
+    }
+
+    #[convex_macro::instrument_future]
+    async fn async_syscall_sparseVectorSearch(&self, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let VectorSearchRequest { query } = serde_json::from_value(args)?;
+        let component_id = self.component_id();
+        let mut sparse_vector_search_query: SparseVectorSearchJson = serde_json::from_value(query)?;
+        sparse_vector_search_query.insert_component_id(component_id);
+
+        let (results, usage_stats) = self
+            .action_callbacks
+            .sparse_vector_search(
+                self.identity.clone(),
+                serde_json::to_value(sparse_vector_search_query)?,
+            )
+            .await?;
+        self.usage_tracker.add(usage_stats);
         let results: Vec<_> = results.into_iter().map(JsonValue::from).collect();
         Ok(json!({ "results": results }))
     }
@@ -3,12 +3,26 @@ use std::time::Duration;
 use ::metrics::StatusTimer;
 use common::{
     http::{
+        fetch::EGRESS_DENIED,
+        fetch_fixtures::FETCH_FIXTURE_NOT_FOUND,
         HttpRequestStream,
         HttpResponseStream,
     },
+    log_lines::{
+        LogLevel,
+        LogLine,
+        SystemLogMetadata,
+    },
     runtime::Runtime,
 };
-use errors::ErrorMetadata;
+use errors::{
+    ErrorMetadata,
+    ErrorMetadataAnyhowExt,
+};
+use http::{
+    Method,
+    StatusCode,
+};
 
 use super::task_executor::TaskExecutor;
 use crate::{
@@ -32,22 +46,33 @@ impl<RT: Runtime> TaskExecutor<RT> {
         let t = metrics::udf_fetch_timer();
         // Only log origin because query params might contain some PII.
         let origin = request.url.origin().unicode_serialization();
+        let method = request.method.clone();
         let result = self.run_fetch_inner(request).await;
         let initial_response_time = t.elapsed();
+        self.log_outbound_request(
+            &method,
+            &origin,
+            result.as_ref().map(|response| response.status),
+            initial_response_time,
+        );
         let (body, response) = match result
             .and_then(|response| HttpResponseV8::from_response_stream(response, stream_id))
         {
             Ok(parts) => parts,
             Err(e) => {
                 // All fetch errors are treated as developer errors since we have little
-                // control of what they request.
-                _ =
-                    self.task_retval_sender.send(TaskResponse::TaskDone {
-                        task_id,
-                        variant: Err(
-                            ErrorMetadata::bad_request("FetchFailed", format!("{e:#}")).into()
-                        ),
-                    });
+                // control of what they request. Egress policy violations and missing
+                // fetch fixtures keep their own error codes.
+                let error =
+                    if e.short_msg() == EGRESS_DENIED || e.short_msg() == FETCH_FIXTURE_NOT_FOUND {
+                        e
+                    } else {
+                        ErrorMetadata::bad_request("FetchFailed", format!("{e:#}")).into()
+                    };
+                _ = self.task_retval_sender.send(TaskResponse::TaskDone {
+                    task_id,
+                    variant: Err(error),
+                });
                 Self::log_fetch_request(t, origin, Err(()), initial_response_time);
                 return;
             },
@@ -66,7 +91,50 @@ impl<RT: Runtime> TaskExecutor<RT> {
         &self,
         request: HttpRequestStream,
     ) -> anyhow::Result<HttpResponseStream> {
-        self.fetch_client.fetch(request).await
+        // Redirects are followed in JS, so each hop is checked separately.
+        let egress_policy = self.egress_policy.lock().clone();
+        self.fetch_client
+            .fetch_with_egress_policy(request, &egress_policy)
+            .await
+    }
+
+    /// Records the request in the action's logs if the deployment's egress
+    /// policy asks for it. Like `log_fetch_request`, this only includes the
+    /// origin since the rest of the URL might contain PII.
+    fn log_outbound_request(
+        &self,
+        method: &Method,
+        origin: &str,
+        result: Result<StatusCode, &anyhow::Error>,
+        initial_response_time: Duration,
+    ) {
+        if !self.egress_policy.lock().log_requests {
+            return;
+        }
+        let (level, code, outcome) = match result {
+            Ok(status) => (
+                LogLevel::Info,
+                "OutboundRequest",
+                status.as_u16().to_string(),
+            ),
+            Err(e) if e.short_msg() == EGRESS_DENIED => (
+                LogLevel::Warn,
+                EGRESS_DENIED,
+                "denied by egress policy".to_string(),
+            ),
+            Err(_) => (LogLevel::Warn, "OutboundRequest", "failed".to_string()),
+        };
+        _ = self.log_line_sender.send(LogLine::new_system_log_line(
+            level,
+            vec![format!(
+                "{method} {origin} {outcome} ({}ms)",
+                initial_response_time.as_millis()
+            )],
+            self.rt.unix_timestamp(),
+            SystemLogMetadata {
+                code: code.to_string(),
+            },
+        ));
     }
 
     fn log_fetch_request(
@@ -21,13 +21,18 @@ use common::{
     execution_context::ExecutionContext,
     fastrace_helpers::EncodedSpan,
     http::{
-        fetch::FetchClient,
+        fetch::{
+            EgressPolicy,
+            FetchClient,
+        },
         RoutedHttpPath,
     },
     knobs::{
+        ACTION_MAX_USER_TIMEOUT,
         ACTION_USER_TIMEOUT,
         FUNCTION_MAX_ARGS_SIZE,
         FUNCTION_MAX_RESULT_SIZE,
+        ISOLATE_MAX_USER_HEAP_SIZE,
         V8_ACTION_SYSTEM_TIMEOUT,
     },
     log_lines::{
@@ -42,6 +47,7 @@ use common::{
     },
     sync::spsc,
     types::{
+        FunctionLimits,
         HttpActionRoute,
         UdfType,
     },
@@ -230,6 +236,8 @@ pub struct ActionEnvironment<RT: Runtime> {
     phase: ActionPhase<RT>,
     syscall_trace: Arc<Mutex<SyscallTrace>>,
     heap_stats: SharedIsolateHeapStats,
+    /// Limits from the action's config. HTTP actions always use the defaults.
+    limits: FunctionLimits,
 }
 
 impl<RT: Runtime> Drop for ActionEnvironment<RT> {
@@ -264,6 +272,7 @@ impl<RT: Runtime> ActionEnvironment<RT> {
         let (task_retval_sender, task_responses) = mpsc::unbounded_channel();
         let resources = Arc::new(Mutex::new(BTreeMap::new()));
         let convex_origin_override = Arc::new(Mutex::new(None));
+        let egress_policy = Arc::new(Mutex::new(EgressPolicy::default()));
         let task_executor = TaskExecutor {
             rt: rt.clone(),
             identity: identity.clone(),
@@ -280,6 +289,8 @@ impl<RT: Runtime> ActionEnvironment<RT> {
             resources: resources.clone(),
             component_id: component,
             convex_origin_override: convex_origin_override.clone(),
+            egress_policy: egress_policy.clone(),
+            log_line_sender: log_line_sender.clone(),
         };
         let (pending_task_sender, pending_task_receiver) = spsc::unbounded_channel();
         let running_tasks = rt.spawn("task_executor", task_executor.go(pending_task_receiver));
@@ -303,9 +314,11 @@ impl<RT: Runtime> ActionEnvironment<RT> {
                 default_system_env_vars,
                 resources,
                 convex_origin_override,
+                egress_policy,
             ),
             syscall_trace,
             heap_stats,
+            limits: FunctionLimits::default(),
         }
     }
 
@@ -661,6 +674,7 @@ impl<RT: Runtime> ActionEnvironment<RT> {
     ) -> anyhow::Result<ActionOutcome> {
         let start_unix_timestamp = self.rt.unix_timestamp();
         let heap_stats = self.heap_stats.clone();
+        self.limits = *request_params.path_and_args.limits();
 
         // See Isolate::with_context for an explanation of this setup code. We can't use
         // that method directly since we want an `await` below, and passing in a
@@ -1417,10 +1431,18 @@ impl<RT: Runtime> IsolateEnvironment<RT> for ActionEnvironment<RT> {
     }
 
     fn user_timeout(&self) -> std::time::Duration {
-        *ACTION_USER_TIMEOUT
+        self.limits.timeout_ms.map_or(*ACTION_USER_TIMEOUT, |ms| {
+            std::time::Duration::from_millis(ms).min(*ACTION_MAX_USER_TIMEOUT)
+        })
     }
 
     fn system_timeout(&self) -> std::time::Duration {
         *V8_ACTION_SYSTEM_TIMEOUT
     }
+
+    fn memory_limit(&self) -> Option<usize> {
+        self.limits
+            .memory_mb
+            .map(|mb| ((mb as usize) << 20).min(*ISOLATE_MAX_USER_HEAP_SIZE))
+    }
 }
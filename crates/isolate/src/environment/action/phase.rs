@@ -11,7 +11,10 @@ use common::{
         Reference,
         Resource,
     },
-    http::RequestDestination,
+    http::{
+        fetch::EgressPolicy,
+        RequestDestination,
+    },
     runtime::{
         Runtime,
         UnixTimestamp,
@@ -29,6 +32,7 @@ use errors::ErrorMetadata;
 use model::{
     canonical_urls::CanonicalUrlsModel,
     components::ComponentsModel,
+    egress_policy::EgressPolicyModel,
     environment_variables::{
         types::{
             EnvVarName,
@@ -95,6 +99,7 @@ enum ActionPreloaded<RT: Runtime> {
         default_system_env_vars: BTreeMap<EnvVarName, EnvVarValue>,
         resources: Arc<Mutex<BTreeMap<Reference, Resource>>>,
         convex_origin_override: Arc<Mutex<Option<ConvexOrigin>>>,
+        egress_policy: Arc<Mutex<EgressPolicy>>,
     },
     Preloading,
     Ready {
@@ -116,6 +121,7 @@ impl<RT: Runtime> ActionPhase<RT> {
         default_system_env_vars: BTreeMap<EnvVarName, EnvVarValue>,
         resources: Arc<Mutex<BTreeMap<Reference, Resource>>>,
         convex_origin_override: Arc<Mutex<Option<ConvexOrigin>>>,
+        egress_policy: Arc<Mutex<EgressPolicy>>,
     ) -> Self {
         Self {
             component,
@@ -127,6 +133,7 @@ impl<RT: Runtime> ActionPhase<RT> {
                 default_system_env_vars,
                 resources,
                 convex_origin_override,
+                egress_policy,
             },
         }
     }
@@ -146,6 +153,7 @@ impl<RT: Runtime> ActionPhase<RT> {
             default_system_env_vars,
             resources,
             convex_origin_override,
+            egress_policy,
         } = preloaded
         else {
             anyhow::bail!("ActionPhase initialized twice");
@@ -211,6 +219,11 @@ impl<RT: Runtime> ActionPhase<RT> {
         if let Some(cloud_url) = canonical_urls.get(&RequestDestination::ConvexCloud) {
             *convex_origin_override.lock() = Some(ConvexOrigin::from(&cloud_url.url));
         }
+        // The policy is loaded before any `fetch` can start, since those are only
+        // allowed once the action is executing.
+        *egress_policy.lock() =
+            with_release_permit(timeout, permit_slot, EgressPolicyModel::new(&mut tx).get())
+                .await?;
         // Environment variables are not accessible in component functions.
         let env_vars = if self.component.is_root() {
             let mut env_vars = default_system_env_vars;
@@ -13,8 +13,12 @@ use common::{
     },
     execution_context::ExecutionContext,
     fastrace_helpers::initialize_root_from_parent,
-    http::fetch::FetchClient,
+    http::fetch::{
+        EgressPolicy,
+        FetchClient,
+    },
     knobs::MAX_CONCURRENT_ACTION_OPS,
+    log_lines::LogLine,
     runtime::{
         Runtime,
         UnixTimestamp,
@@ -80,6 +84,8 @@ pub struct TaskExecutor<RT: Runtime> {
     pub resources: Arc<Mutex<BTreeMap<Reference, Resource>>>,
     pub component_id: ComponentId,
     pub convex_origin_override: Arc<Mutex<Option<ConvexOrigin>>>,
+    pub egress_policy: Arc<Mutex<EgressPolicy>>,
+    pub log_line_sender: mpsc::UnboundedSender<LogLine>,
 }
 
 impl<RT: Runtime> TaskExecutor<RT> {
@@ -26,6 +26,7 @@ use common::{
         UnixTimestamp,
     },
     types::{
+        FunctionLimits,
         HttpActionRoute,
         ModuleEnvironment,
         RoutableMethod,
@@ -260,7 +261,7 @@ impl AnalyzeEnvironment {
     ) -> anyhow::Result<Result<BTreeMap<CanonicalizedModulePath, AnalyzedModule>, JsError>> {
         let to_analyze = modules
             .keys()
-            .filter(|p| !p.is_deps())
+            .filter(|p| !p.is_deps() && !p.is_wasm())
             .cloned()
             .collect::<Vec<_>>();
         anyhow::ensure!(
@@ -578,6 +579,65 @@ fn parse_returns_validator<'s, RT: Runtime>(
     };
     Ok(Ok(returns))
 }
+
+#[fastrace::trace]
+fn parse_function_limits<'s, RT: Runtime>(
+    scope: &mut ExecutionScope<RT, AnalyzeEnvironment>,
+    function: v8::Local<v8::Object>,
+    udf_type: UdfType,
+    function_identifier_for_error: String,
+) -> anyhow::Result<Result<FunctionLimits, JsError>> {
+    // Call `exportLimits` to get the function's resource limits.
+    let export_limits = strings::exportLimits.create(scope)?;
+    let limits = match function.get(scope, export_limits.into()) {
+        Some(export_limits_value) if export_limits_value.is_function() => {
+            let export_limits_function: v8::Local<v8::Function> = export_limits_value.try_into()?;
+            let result_v8 = scope
+                .with_try_catch(|s| export_limits_function.call(s, function.into(), &[]))??
+                .context("Missing return value from successful function call")?;
+            let result_v8_str = match v8::Local::<v8::String>::try_from(result_v8) {
+                Ok(s) => s,
+                Err(_) => {
+                    let message = format!(
+                        "Invalid exportLimits return value: \
+                         {function_identifier_for_error}.exportLimits() didn't return a string."
+                    );
+                    return Ok(Err(JsError::from_message(message)));
+                },
+            };
+            let result_str = helpers::to_rust_string(scope, &result_v8_str)?;
+            let limits = match serde_json::from_str::<Option<FunctionLimits>>(&result_str) {
+                Ok(limits) => limits.unwrap_or_default(),
+                Err(parse_error) => {
+                    let message = format!(
+                        "Invalid JSON returned from \
+                         {function_identifier_for_error}.exportLimits(): {parse_error}"
+                    );
+                    return Ok(Err(JsError::from_message(message)));
+                },
+            };
+            if let Err(e) = limits.validate(udf_type) {
+                let message = format!("Invalid limits for {function_identifier_for_error}: {e}");
+                return Ok(Err(JsError::from_message(message)));
+            }
+            limits
+        },
+        // `exportLimits` is undefined for npm package versions that don't
+        // support limits and for functions without any.
+        Some(export_limits_value) if export_limits_value.is_undefined() => {
+            FunctionLimits::default()
+        },
+        Some(_) => {
+            let message = format!(
+                "{function_identifier_for_error}.exportLimits is not a function or `undefined`."
+            );
+            return Ok(Err(JsError::from_message(message)));
+        },
+        None => FunctionLimits::default(),
+    };
+    Ok(Ok(limits))
+}
+
 #[fastrace::trace]
 fn udf_analyze<RT: Runtime>(
     scope: &mut ExecutionScope<RT, AnalyzeEnvironment>,
@@ -644,6 +704,13 @@ fn udf_analyze<RT: Runtime>(
         let returns =
             parse_returns_validator(scope, function, format!("{module_path:?}:{property_name}"))??;
 
+        let limits = parse_function_limits(
+            scope,
+            function,
+            udf_type,
+            format!("{module_path:?}:{property_name}"),
+        )??;
+
         let visibility = match (is_public, is_internal) {
             (true, false) => Some(Visibility::Public),
             (false, true) => Some(Visibility::Internal),
@@ -710,28 +777,34 @@ fn udf_analyze<RT: Runtime>(
             && fn_canon_path.as_str() == module_path.as_str()
         {
             // Source map is valid; proceed with mapping in original source map
-            functions.push(AnalyzedFunction::new(
-                canonicalized_name.clone(),
-                Some(AnalyzedSourcePosition {
-                    path: fn_canon_path,
-                    start_lineno: token.get_src_line(),
-                    start_col: token.get_src_col(),
-                }),
-                udf_type,
-                visibility.clone(),
-                args.clone(),
-                returns.clone(),
-            )?);
+            functions.push(AnalyzedFunction {
+                limits,
+                ..AnalyzedFunction::new(
+                    canonicalized_name.clone(),
+                    Some(AnalyzedSourcePosition {
+                        path: fn_canon_path,
+                        start_lineno: token.get_src_line(),
+                        start_col: token.get_src_col(),
+                    }),
+                    udf_type,
+                    visibility.clone(),
+                    args.clone(),
+                    returns.clone(),
+                )?
+            });
         } else {
             // If there is no valid source map, push a function without a position
-            functions.push(AnalyzedFunction::new(
-                canonicalized_name.clone(),
-                None,
-                udf_type,
-                visibility.clone(),
-                args.clone(),
-                returns.clone(),
-            )?);
+            functions.push(AnalyzedFunction {
+                limits,
+                ..AnalyzedFunction::new(
+                    canonicalized_name.clone(),
+                    None,
+                    udf_type,
+                    visibility.clone(),
+                    args.clone(),
+                    returns.clone(),
+                )?
+            });
 
             // Log reason for fallback
             if fn_canon_path.as_str() != module_path.as_str() {
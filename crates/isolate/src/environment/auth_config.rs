@@ -315,6 +315,18 @@ fn check_for_common_confusions(config_str: &str) -> anyhow::Result<()> {
                         });
                     }
 
+                    if type_value == "customJwt" && obj.contains_key("secret") {
+                        anyhow::bail!(AuthConfigNotMatchingSchemaError {
+                            error: format!(
+                                "Provider at index {} has a 'secret' specified. Shared secrets \
+                                 can't be stored in the auth config: set the secret as an \
+                                 environment variable and reference it by name with \
+                                 'secretEnvVar'.",
+                                index,
+                            ),
+                        });
+                    }
+
                     let is_oidc = type_value == "oidc" || type_value == "unknown";
                     if is_oidc && has_issuer {
                         anyhow::bail!(AuthConfigNotMatchingSchemaError {
@@ -434,6 +446,16 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_custom_jwt_inline_secret() -> anyhow::Result<()> {
+        let invalid_config = r#"{ "providers": [ { "type": "customJwt", "issuer": "https://your.issuer.url.com", "secret": "hunter2", "algorithm": "HS256" }]}"#;
+        check_for_common_confusions(invalid_config).unwrap_err();
+
+        let valid_config = r#"{ "providers": [ { "type": "customJwt", "issuer": "https://your.issuer.url.com", "secretEnvVar": "JWT_SECRET", "algorithm": "HS256" }]}"#;
+        check_for_common_confusions(valid_config)?;
+        Ok(())
+    }
+
     #[test]
     fn test_valid_custom_jwt_without_application() -> anyhow::Result<()> {
         let valid_config = r#"{ "providers": [ { "type": "customJwt", "issuer": "https://your.issuer.url.com", "jwks": "https://your.issuer.url.com/.well-known/jwks.json", "algorithm": "RS256" }]}"#;
@@ -106,3 +106,8 @@ pub fn path_from_module_specifier(
         .transpose()?
         .ok_or_else(|| anyhow!("module specifier did not start with {}", prefix))
 }
+
+/// Is the module at `spec` a WebAssembly binary rather than JavaScript?
+pub fn is_wasm_module_specifier(spec: &ModuleSpecifier) -> bool {
+    path_from_module_specifier(spec).is_ok_and(|path| path.is_wasm())
+}
@@ -97,6 +97,12 @@ pub trait IsolateEnvironment<RT: Runtime>: 'static {
 
     fn user_timeout(&self) -> Duration;
     fn system_timeout(&self) -> Duration;
+    /// A limit on JavaScript memory that's lower than the isolate's heap
+    /// limit. It's checked periodically while JavaScript is running and
+    /// whenever the function yields to the event loop.
+    fn memory_limit(&self) -> Option<usize> {
+        None
+    }
     fn is_nested_function(&self) -> bool {
         false
     }
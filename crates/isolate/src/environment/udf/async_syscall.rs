@@ -27,6 +27,9 @@ use common::{
         Cursor,
         CursorPosition,
         Query,
+        QuerySource,
+        SearchFacets,
+        SearchSuggest,
     },
     query_journal::QueryJournal,
     runtime::{
@@ -35,6 +38,7 @@ use common::{
     },
     types::{
         AllowedVisibility,
+        IndexName,
         PersistenceVersion,
         UdfType,
     },
@@ -44,6 +48,7 @@ use common::{
 use database::{
     query::{
         query_batch_next,
+        search_with_facets,
         PaginationOptions,
         TableFilter,
     },
@@ -51,6 +56,7 @@ use database::{
     table_summary::table_summary_bootstrapping_error,
     BootstrapComponentsModel,
     DeveloperQuery,
+    IndexModel,
     PatchValue,
     Transaction,
     UserFacingModel,
@@ -72,8 +78,28 @@ use model::{
         BatchKey,
         FileStorageId,
     },
-    scheduled_jobs::VirtualSchedulerModel,
+    rate_limits::{
+        types::{
+            RateLimitRequestJson,
+            ResetRateLimitJson,
+        },
+        RateLimitModel,
+    },
+    scheduled_jobs::{
+        types::{
+            ScheduledJobQueue,
+            ScheduledJobQueueJson,
+            ScheduledJobRetryPolicy,
+            ScheduledJobRetryPolicyJson,
+        },
+        VirtualSchedulerModel,
+    },
     virtual_system_mapping,
+    work_queues::WorkQueueModel,
+    workflows::{
+        WorkflowModel,
+        WorkflowStepRequest,
+    },
 };
 use serde::{
     Deserialize,
@@ -96,8 +122,14 @@ use value::{
     id_v6::DeveloperDocumentId,
     ConvexArray,
     ConvexObject,
+    FieldPath,
     TableName,
 };
+use vector::{
+    VectorSearch,
+    VectorSearchJson,
+    VectorSearchRequest,
+};
 
 use super::DatabaseUdfEnvironment;
 use crate::{
@@ -150,6 +182,33 @@ pub fn system_table_guard(name: &TableName, expect_system_table: bool) -> anyhow
     Ok(())
 }
 
+fn parse_ms(syscall: &str, arg: &'static str, ms: Option<f64>) -> anyhow::Result<Duration> {
+    let ms = ms.unwrap_or(0.0);
+    with_argument_error(syscall, || {
+        Duration::try_from_secs_f64(ms / 1000.0)
+            .map_err(|_| anyhow::anyhow!("{ms} is not a valid number of milliseconds"))
+            .context(ArgName(arg))
+    })
+}
+
+fn parse_message_id(syscall: &str, id: &str) -> anyhow::Result<DeveloperDocumentId> {
+    with_argument_error(syscall, || {
+        DeveloperDocumentId::decode(id).context(ArgName("id"))
+    })
+}
+
+fn parse_workflow_id(syscall: &str, args: JsonValue) -> anyhow::Result<DeveloperDocumentId> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct WorkflowIdArgs {
+        workflow_id: String,
+    }
+    with_argument_error(syscall, || {
+        let args: WorkflowIdArgs = serde_json::from_value(args)?;
+        DeveloperDocumentId::decode(&args.workflow_id).context(ArgName("workflowId"))
+    })
+}
+
 /// A batch of async syscalls that can run "in parallel", where they actually
 /// execute in a batch for determinism, but as far as the js promises are
 /// concerned, they're running in parallel.
@@ -687,6 +746,11 @@ impl<RT: Runtime, P: AsyncSyscallProvider<RT>> DatabaseSyscallsV1<RT, P> {
                 let result = match &name[..] {
                     // Database
                     "1.0/count" => Box::pin(Self::count(provider, args)).await,
+                    "1.0/searchSuggest" => Box::pin(Self::search_suggest(provider, args)).await,
+                    "1.0/searchWithFacets" => {
+                        Box::pin(Self::search_with_facets(provider, args)).await
+                    },
+                    "1.0/vectorSearch" => Box::pin(Self::vector_search(provider, args)).await,
                     "1.0/insert" => Box::pin(Self::insert(provider, args)).await,
                     "1.0/shallowMerge" => Box::pin(Self::shallow_merge(provider, args)).await,
                     "1.0/replace" => Box::pin(Self::replace(provider, args)).await,
@@ -708,6 +772,31 @@ impl<RT: Runtime, P: AsyncSyscallProvider<RT>> DatabaseSyscallsV1<RT, P> {
                     "1.0/schedule" => Box::pin(Self::schedule(provider, args)).await,
                     "1.0/cancel_job" => Box::pin(Self::cancel_job(provider, args)).await,
 
+                    // Workflows
+                    "1.0/workflow/start" => Box::pin(Self::start_workflow(provider, args)).await,
+                    "1.0/workflow/load" => Box::pin(Self::load_workflow(provider, args)).await,
+                    "1.0/workflow/step" => {
+                        Box::pin(Self::record_workflow_step(provider, args)).await
+                    },
+                    "1.0/workflow/finish" => Box::pin(Self::finish_workflow(provider, args)).await,
+                    "1.0/workflow/sendEvent" => {
+                        Box::pin(Self::send_workflow_event(provider, args)).await
+                    },
+                    "1.0/workflow/status" => Box::pin(Self::workflow_status(provider, args)).await,
+                    "1.0/workflow/cancel" => Box::pin(Self::cancel_workflow(provider, args)).await,
+
+                    // Work queues
+                    "1.0/queue/enqueue" => Box::pin(Self::enqueue(provider, args)).await,
+                    "1.0/queue/dequeue" => Box::pin(Self::dequeue(provider, args)).await,
+                    "1.0/queue/ack" => Box::pin(Self::ack_message(provider, args)).await,
+                    "1.0/queue/nack" => Box::pin(Self::nack_message(provider, args)).await,
+                    "1.0/queue/deadLetters" => Box::pin(Self::dead_letters(provider, args)).await,
+                    "1.0/queue/redrive" => Box::pin(Self::redrive_message(provider, args)).await,
+
+                    // Rate limits
+                    "1.0/rateLimit" => Box::pin(Self::rate_limit(provider, args)).await,
+                    "1.0/rateLimit/reset" => Box::pin(Self::reset_rate_limit(provider, args)).await,
+
                     // Components
                     "1.0/runUdf" => Box::pin(Self::run_udf(provider, args)).await,
                     "1.0/createFunctionHandle" => {
@@ -772,6 +861,175 @@ impl<RT: Runtime, P: AsyncSyscallProvider<RT>> DatabaseSyscallsV1<RT, P> {
         Ok(ConvexValue::from(result).to_internal_json())
     }
 
+    #[convex_macro::instrument_future]
+    async fn search_suggest(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SearchSuggestArgs {
+            index_name: String,
+            prefix: String,
+            limit: usize,
+        }
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SearchSuggestionJson {
+            text: String,
+            doc_frequency: f64,
+        }
+        let (index_name, prefix, limit) = with_argument_error("db.suggest", || {
+            let args: SearchSuggestArgs = serde_json::from_value(args)?;
+            let index_name: IndexName = args.index_name.parse().context(ArgName("indexName"))?;
+            Ok((index_name, args.prefix, args.limit))
+        })?;
+        let table_filter = provider.table_filter();
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let stable_index_name =
+            IndexModel::new(tx).stable_index_name(component.into(), &index_name, table_filter)?;
+        let suggest = SearchSuggest {
+            table: index_name.table().clone(),
+            index_name,
+            prefix,
+            limit,
+        };
+        let suggestions = tx
+            .search_suggest(&stable_index_name, &suggest)
+            .await?
+            .into_iter()
+            .map(|suggestion| SearchSuggestionJson {
+                text: suggestion.text,
+                doc_frequency: suggestion.doc_frequency as f64,
+            })
+            .collect_vec();
+        Ok(serde_json::to_value(suggestions)?)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn vector_search(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let component = provider.component()?;
+        let query = with_argument_error("vectorSearch", || {
+            let VectorSearchRequest { query } = serde_json::from_value(args)?;
+            let mut query: VectorSearchJson =
+                serde_json::from_value(query).context(ArgName("query"))?;
+            query.insert_component_id(component);
+            VectorSearch::try_from(query).map_err(|e| {
+                let message = e.to_string();
+                e.context(ErrorMetadata::bad_request("InvalidVectorQuery", message))
+            })
+        })?;
+        let table_filter = provider.table_filter();
+        let tx = provider.tx()?;
+        let stable_index_name = IndexModel::new(tx).stable_index_name(
+            component.into(),
+            &query.index_name,
+            table_filter,
+        )?;
+        let results: Vec<_> = tx
+            .vector_search(&stable_index_name, query)
+            .await?
+            .into_iter()
+            .map(JsonValue::from)
+            .collect();
+        Ok(json!({ "results": results }))
+    }
+
+    #[convex_macro::instrument_future]
+    async fn search_with_facets(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SearchWithFacetsArgs {
+            query: JsonValue,
+            fields: Vec<String>,
+            limit: usize,
+            num_results: usize,
+            #[serde(default)]
+            version: Option<String>,
+        }
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct FacetCountJson {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            value: Option<JsonValue>,
+            count: f64,
+        }
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct FacetCountsJson {
+            field: String,
+            counts: Vec<FacetCountJson>,
+            num_omitted_values: f64,
+        }
+        let args: SearchWithFacetsArgs =
+            with_argument_error("searchWithFacets", || Ok(serde_json::from_value(args)?))?;
+        let (parsed_query, fields) = with_argument_error("searchWithFacets", || {
+            let parsed_query = Query::try_from(args.query).context(ArgName("query"))?;
+            let fields = args
+                .fields
+                .into_iter()
+                .map(|field| field.parse())
+                .collect::<anyhow::Result<Vec<FieldPath>>>()
+                .context(ArgName("fields"))?;
+            Ok((parsed_query, fields))
+        })?;
+        let QuerySource::Search(search) = parsed_query.source else {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "FacetsRequireSearchQuery",
+                "Facet counts can only be computed for queries using a search index",
+            ));
+        };
+        if !parsed_query.operators.is_empty() {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "FacetsRequireSearchQuery",
+                "Facet counts are computed over all search results and can't be combined with \
+                 filters or limits",
+            ));
+        }
+        let version = parse_version(args.version)?;
+        let facets = SearchFacets {
+            fields,
+            limit: args.limit,
+        };
+        let table_filter = provider.table_filter();
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let response = search_with_facets(
+            tx,
+            component.into(),
+            search,
+            &facets,
+            args.num_results,
+            version,
+            table_filter,
+        )
+        .await?;
+        let results = response
+            .results
+            .into_iter()
+            .map(|document| document.to_internal_json())
+            .collect_vec();
+        let facet_counts = response
+            .facets
+            .into_iter()
+            .map(|facet_counts| FacetCountsJson {
+                field: String::from(facet_counts.field_path),
+                counts: facet_counts
+                    .counts
+                    .into_iter()
+                    .map(|facet_count| FacetCountJson {
+                        value: facet_count.value.map(|value| value.to_internal_json()),
+                        count: facet_count.count as f64,
+                    })
+                    .collect(),
+                num_omitted_values: facet_counts.num_omitted_values as f64,
+            })
+            .collect_vec();
+        Ok(json!({
+            "results": results,
+            "facets": serde_json::to_value(facet_counts)?,
+            "isTruncated": response.is_truncated,
+        }))
+    }
+
     #[convex_macro::instrument_future]
     async fn get_user_identity(provider: &mut P, _args: JsonValue) -> anyhow::Result<JsonValue> {
         provider.observe_identity()?;
@@ -902,6 +1160,8 @@ impl<RT: Runtime, P: AsyncSyscallProvider<RT>> DatabaseSyscallsV1<RT, P> {
             function_handle: Option<String>,
             ts: f64,
             args: UdfArgsJson,
+            retry: Option<ScheduledJobRetryPolicyJson>,
+            queue: Option<ScheduledJobQueueJson>,
         }
 
         let ScheduleArgs {
@@ -910,15 +1170,51 @@ impl<RT: Runtime, P: AsyncSyscallProvider<RT>> DatabaseSyscallsV1<RT, P> {
             function_handle,
             ts,
             args,
+            retry,
+            queue,
         }: ScheduleArgs = with_argument_error("scheduler", || Ok(serde_json::from_value(args)?))?;
+        let retry_policy = retry.map(ScheduledJobRetryPolicy::try_from).transpose()?;
+        let queue = queue.map(ScheduledJobQueue::try_from).transpose()?;
+
+        let path = Self::resolve_schedulable_function(
+            provider,
+            "scheduler",
+            name,
+            reference,
+            function_handle,
+        )
+        .await?;
 
+        let scheduling_component = provider.component()?;
+
+        let scheduled_ts = with_argument_error("ts", || UnixTimestamp::from_secs_f64(ts))?;
+        let (path, udf_args) = provider
+            .validate_schedule_args(path, args.into_arg_vec(), scheduled_ts)
+            .await?;
+
+        let context = provider.context().clone();
+        let tx = provider.tx()?;
+        let virtual_id = VirtualSchedulerModel::new(tx, scheduling_component.into())
+            .schedule(path, udf_args, scheduled_ts, context, retry_policy, queue)
+            .await?;
+
+        Ok(JsonValue::from(virtual_id))
+    }
+
+    async fn resolve_schedulable_function(
+        provider: &mut P,
+        syscall: &str,
+        name: Option<String>,
+        reference: Option<String>,
+        function_handle: Option<String>,
+    ) -> anyhow::Result<CanonicalizedComponentFunctionPath> {
         let path = match function_handle {
             Some(h) => {
-                let handle: FunctionHandle = with_argument_error("scheduler", || h.parse())?;
+                let handle: FunctionHandle = with_argument_error(syscall, || h.parse())?;
                 provider.lookup_function_handle(handle).await?
             },
             None => {
-                let reference = parse_name_or_reference("scheduler", name, reference)?;
+                let reference = parse_name_or_reference(syscall, name, reference)?;
                 match provider.resolve(reference).await? {
                     Resource::Value(v) => {
                         anyhow::bail!(ErrorMetadata::bad_request(
@@ -936,21 +1232,7 @@ impl<RT: Runtime, P: AsyncSyscallProvider<RT>> DatabaseSyscallsV1<RT, P> {
                 }
             },
         };
-
-        let scheduling_component = provider.component()?;
-
-        let scheduled_ts = with_argument_error("ts", || UnixTimestamp::from_secs_f64(ts))?;
-        let (path, udf_args) = provider
-            .validate_schedule_args(path, args.into_arg_vec(), scheduled_ts)
-            .await?;
-
-        let context = provider.context().clone();
-        let tx = provider.tx()?;
-        let virtual_id = VirtualSchedulerModel::new(tx, scheduling_component.into())
-            .schedule(path, udf_args, scheduled_ts, context)
-            .await?;
-
-        Ok(JsonValue::from(virtual_id))
+        Ok(path)
     }
 
     #[convex_macro::instrument_future]
@@ -976,6 +1258,409 @@ impl<RT: Runtime, P: AsyncSyscallProvider<RT>> DatabaseSyscallsV1<RT, P> {
         Ok(JsonValue::Null)
     }
 
+    #[convex_macro::instrument_future]
+    async fn start_workflow(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct StartWorkflowArgs {
+            name: Option<String>,
+            reference: Option<String>,
+            function_handle: Option<String>,
+            args: JsonValue,
+        }
+        let StartWorkflowArgs {
+            name,
+            reference,
+            function_handle,
+            args,
+        }: StartWorkflowArgs =
+            with_argument_error("startWorkflow", || Ok(serde_json::from_value(args)?))?;
+        let workflow_args: ConvexObject = with_argument_error("startWorkflow", || {
+            ConvexValue::try_from(args.clone())
+                .context(ArgName("args"))?
+                .try_into()
+                .context(ArgName("args"))
+        })?;
+        let path = Self::resolve_schedulable_function(
+            provider,
+            "startWorkflow",
+            name,
+            reference,
+            function_handle,
+        )
+        .await?;
+
+        // The workflow's journal lives alongside its handler, so the handler
+        // has to be in the component starting it.
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let (_, handler_component) =
+            BootstrapComponentsModel::new(tx).must_component_path_to_ids(&path.component)?;
+        anyhow::ensure!(
+            handler_component == component,
+            ErrorMetadata::bad_request(
+                "InvalidWorkflowHandler",
+                "Workflows can only be started from the component that defines them",
+            )
+        );
+        let now = provider.rt().unix_timestamp();
+        let (path, _) = provider
+            .validate_schedule_args(path, vec![args], now)
+            .await?;
+
+        let tx = provider.tx()?;
+        let workflow_id = WorkflowModel::new(tx, component.into())
+            .start(path, workflow_args)
+            .await?;
+        Ok(JsonValue::from(workflow_id))
+    }
+
+    #[convex_macro::instrument_future]
+    async fn load_workflow(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let workflow_id = parse_workflow_id("workflow.load", args)?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let journal = WorkflowModel::new(tx, component.into())
+            .load(workflow_id)
+            .await?;
+        Ok(serde_json::to_value(journal)?)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn record_workflow_step(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RecordStepArgs {
+            workflow_id: String,
+            step_number: u32,
+            name: String,
+            kind: StepKindArgs,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase", tag = "type")]
+        enum StepKindArgs {
+            Query {
+                result: Option<JsonValue>,
+                error: Option<String>,
+            },
+            Mutation {
+                result: Option<JsonValue>,
+                error: Option<String>,
+            },
+            #[serde(rename_all = "camelCase")]
+            Action {
+                name: Option<String>,
+                reference: Option<String>,
+                function_handle: Option<String>,
+                args: UdfArgsJson,
+            },
+            Sleep {
+                ms: f64,
+            },
+            WaitForEvent {
+                event: String,
+            },
+        }
+        fn outcome(
+            result: Option<JsonValue>,
+            error: Option<String>,
+        ) -> anyhow::Result<Result<ConvexValue, String>> {
+            Ok(match error {
+                Some(error) => Err(error),
+                None => Ok(ConvexValue::try_from(result.unwrap_or(JsonValue::Null))
+                    .context(ArgName("result"))?),
+            })
+        }
+
+        let RecordStepArgs {
+            workflow_id,
+            step_number,
+            name,
+            kind,
+        }: RecordStepArgs =
+            with_argument_error("workflow.step", || Ok(serde_json::from_value(args)?))?;
+        let workflow_id = with_argument_error("workflow.step", || {
+            DeveloperDocumentId::decode(&workflow_id).context(ArgName("workflowId"))
+        })?;
+        let request = match kind {
+            StepKindArgs::Query { result, error } => {
+                WorkflowStepRequest::Query(with_argument_error("workflow.step", || {
+                    outcome(result, error)
+                })?)
+            },
+            StepKindArgs::Mutation { result, error } => {
+                WorkflowStepRequest::Mutation(with_argument_error("workflow.step", || {
+                    outcome(result, error)
+                })?)
+            },
+            StepKindArgs::Action {
+                name,
+                reference,
+                function_handle,
+                args,
+            } => {
+                let path = Self::resolve_schedulable_function(
+                    provider,
+                    "workflow.step",
+                    name,
+                    reference,
+                    function_handle,
+                )
+                .await?;
+                let now = provider.rt().unix_timestamp();
+                let (path, args) = provider
+                    .validate_schedule_args(path, args.into_arg_vec(), now)
+                    .await?;
+                WorkflowStepRequest::Action { path, args }
+            },
+            StepKindArgs::Sleep { ms } => {
+                let duration = Duration::try_from_secs_f64(ms / 1000.0).map_err(|_| {
+                    ErrorMetadata::bad_request(
+                        "InvalidWorkflowSleep",
+                        format!("Cannot sleep for {ms} milliseconds"),
+                    )
+                })?;
+                WorkflowStepRequest::Sleep(duration)
+            },
+            StepKindArgs::WaitForEvent { event } => WorkflowStepRequest::WaitForEvent(event),
+        };
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let step = WorkflowModel::new(tx, component.into())
+            .record_step(workflow_id, step_number, name, request)
+            .await?;
+        Ok(serde_json::to_value(step)?)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn finish_workflow(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct FinishWorkflowArgs {
+            workflow_id: String,
+            result: Option<JsonValue>,
+            error: Option<String>,
+        }
+        let (workflow_id, result) = with_argument_error("workflow.finish", || {
+            let args: FinishWorkflowArgs = serde_json::from_value(args)?;
+            let workflow_id =
+                DeveloperDocumentId::decode(&args.workflow_id).context(ArgName("workflowId"))?;
+            let result = match args.error {
+                Some(error) => Err(error),
+                None => Ok(
+                    ConvexValue::try_from(args.result.unwrap_or(JsonValue::Null))
+                        .context(ArgName("result"))?,
+                ),
+            };
+            Ok((workflow_id, result))
+        })?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        WorkflowModel::new(tx, component.into())
+            .finish(workflow_id, result)
+            .await?;
+        Ok(JsonValue::Null)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn send_workflow_event(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SendEventArgs {
+            workflow_id: String,
+            event: String,
+            value: Option<JsonValue>,
+        }
+        let (workflow_id, event, value) = with_argument_error("sendWorkflowEvent", || {
+            let args: SendEventArgs = serde_json::from_value(args)?;
+            let workflow_id =
+                DeveloperDocumentId::decode(&args.workflow_id).context(ArgName("workflowId"))?;
+            let value = ConvexValue::try_from(args.value.unwrap_or(JsonValue::Null))
+                .context(ArgName("value"))?;
+            Ok((workflow_id, args.event, value))
+        })?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        WorkflowModel::new(tx, component.into())
+            .send_event(workflow_id, event, value)
+            .await?;
+        Ok(JsonValue::Null)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn workflow_status(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let workflow_id = parse_workflow_id("getWorkflowStatus", args)?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let status = WorkflowModel::new(tx, component.into())
+            .status(workflow_id)
+            .await?;
+        Ok(serde_json::to_value(status)?)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn cancel_workflow(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let workflow_id = parse_workflow_id("cancelWorkflow", args)?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        WorkflowModel::new(tx, component.into())
+            .cancel(workflow_id)
+            .await?;
+        Ok(JsonValue::Null)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn enqueue(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct EnqueueArgs {
+            queue: String,
+            body: JsonValue,
+            delay_ms: Option<f64>,
+            max_attempts: Option<u32>,
+        }
+        let args: EnqueueArgs =
+            with_argument_error("queue.enqueue", || Ok(serde_json::from_value(args)?))?;
+        let body = with_argument_error("queue.enqueue", || {
+            ConvexValue::try_from(args.body).context(ArgName("body"))
+        })?;
+        let delay = parse_ms("queue.enqueue", "delayMs", args.delay_ms)?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let id = WorkQueueModel::new(tx, component.into())
+            .enqueue(args.queue, body, delay, args.max_attempts)
+            .await?;
+        Ok(JsonValue::from(id))
+    }
+
+    #[convex_macro::instrument_future]
+    async fn dequeue(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct DequeueArgs {
+            queue: String,
+            max_messages: Option<usize>,
+            visibility_timeout_ms: f64,
+        }
+        let args: DequeueArgs =
+            with_argument_error("queue.dequeue", || Ok(serde_json::from_value(args)?))?;
+        let visibility_timeout = parse_ms(
+            "queue.dequeue",
+            "visibilityTimeoutMs",
+            Some(args.visibility_timeout_ms),
+        )?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let leases = WorkQueueModel::new(tx, component.into())
+            .dequeue(
+                &args.queue,
+                args.max_messages.unwrap_or(1),
+                visibility_timeout,
+            )
+            .await?;
+        Ok(serde_json::to_value(leases)?)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn ack_message(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct AckArgs {
+            id: String,
+            attempt: u32,
+        }
+        let args: AckArgs = with_argument_error("queue.ack", || Ok(serde_json::from_value(args)?))?;
+        let id = parse_message_id("queue.ack", &args.id)?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        WorkQueueModel::new(tx, component.into())
+            .ack(id, args.attempt)
+            .await?;
+        Ok(JsonValue::Null)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn nack_message(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct NackArgs {
+            id: String,
+            attempt: u32,
+            delay_ms: Option<f64>,
+            error: Option<String>,
+        }
+        let args: NackArgs =
+            with_argument_error("queue.nack", || Ok(serde_json::from_value(args)?))?;
+        let id = parse_message_id("queue.nack", &args.id)?;
+        let delay = parse_ms("queue.nack", "delayMs", args.delay_ms)?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        WorkQueueModel::new(tx, component.into())
+            .nack(id, args.attempt, delay, args.error)
+            .await?;
+        Ok(JsonValue::Null)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn dead_letters(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct DeadLettersArgs {
+            queue: String,
+            limit: Option<usize>,
+        }
+        let args: DeadLettersArgs =
+            with_argument_error("queue.deadLetters", || Ok(serde_json::from_value(args)?))?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let messages = WorkQueueModel::new(tx, component.into())
+            .dead_letters(&args.queue, args.limit.unwrap_or(100))
+            .await?;
+        Ok(serde_json::to_value(messages)?)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn redrive_message(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RedriveArgs {
+            id: String,
+        }
+        let args: RedriveArgs =
+            with_argument_error("queue.redrive", || Ok(serde_json::from_value(args)?))?;
+        let id = parse_message_id("queue.redrive", &args.id)?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        WorkQueueModel::new(tx, component.into())
+            .redrive(id)
+            .await?;
+        Ok(JsonValue::Null)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn rate_limit(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let request: RateLimitRequestJson =
+            with_argument_error("rateLimit", || Ok(serde_json::from_value(args)?))?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        let result = RateLimitModel::new(tx, component.into())
+            .limit(request.try_into()?)
+            .await?;
+        Ok(serde_json::to_value(result)?)
+    }
+
+    #[convex_macro::instrument_future]
+    async fn reset_rate_limit(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
+        let ResetRateLimitJson { name, key } =
+            with_argument_error("resetRateLimit", || Ok(serde_json::from_value(args)?))?;
+        let component = provider.component()?;
+        let tx = provider.tx()?;
+        RateLimitModel::new(tx, component.into())
+            .reset(&name, key.as_deref())
+            .await?;
+        Ok(JsonValue::Null)
+    }
+
     #[fastrace::trace]
     #[convex_macro::instrument_future]
     async fn insert(provider: &mut P, args: JsonValue) -> anyhow::Result<JsonValue> {
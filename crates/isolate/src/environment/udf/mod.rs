@@ -16,6 +16,7 @@ use model::{
         EnvVarValue,
     },
     modules::{
+        hash_module_source,
         module_versions::FullModuleSource,
         user_error::FunctionNotFoundError,
     },
@@ -23,7 +24,11 @@ use model::{
 use tokio::sync::oneshot;
 use udf::{
     helpers::serialize_udf_args,
+    validation::ValidatedPathAndArgs,
+    ExecutionRecording,
     FunctionOutcome,
+    RecordedSyscall,
+    RecordedSyscallError,
     SyscallTrace,
 };
 pub mod async_syscall;
@@ -32,7 +37,11 @@ mod phase;
 pub mod syscall;
 use std::{
     cmp::Ordering,
-    collections::VecDeque,
+    collections::{
+        BTreeMap,
+        VecDeque,
+    },
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -41,17 +50,16 @@ use common::{
     errors::JsError,
     identity::InertIdentity,
     knobs::{
+        DATABASE_UDF_MAX_USER_TIMEOUT,
         DATABASE_UDF_SYSTEM_TIMEOUT,
         DATABASE_UDF_USER_TIMEOUT,
         FUNCTION_MAX_ARGS_SIZE,
         FUNCTION_MAX_RESULT_SIZE,
+        ISOLATE_DEPLOYMENT_SNAPSHOTS,
+        ISOLATE_MAX_USER_HEAP_SIZE,
         TRANSACTION_MAX_NUM_SCHEDULED,
-        TRANSACTION_MAX_NUM_USER_WRITES,
         TRANSACTION_MAX_READ_SET_INTERVALS,
-        TRANSACTION_MAX_READ_SIZE_BYTES,
-        TRANSACTION_MAX_READ_SIZE_ROWS,
         TRANSACTION_MAX_SCHEDULED_TOTAL_ARGUMENT_SIZE_BYTES,
-        TRANSACTION_MAX_USER_WRITE_SIZE_BYTES,
     },
     log_lines::{
         LogLevel,
@@ -64,7 +72,9 @@ use common::{
         UnixTimestamp,
     },
     types::{
+        FunctionLimits,
         PersistenceVersion,
+        Timestamp,
         UdfType,
     },
     value::{
@@ -75,7 +85,9 @@ use common::{
 use database::{
     BiggestDocumentWrites,
     FunctionExecutionSize,
+    ReadLimits,
     Transaction,
+    WriteLimits,
     OVER_LIMIT_HELP,
 };
 use deno_core::{
@@ -94,6 +106,7 @@ use value::{
         HeapSize,
         WithHeapSize,
     },
+    sha256::Sha256Digest,
     JsonPackedValue,
     NamespacedTableMapping,
     Size,
@@ -123,6 +136,7 @@ use super::{
 use crate::{
     client::{
         EnvironmentData,
+        ExecutionRecordingMode,
         SharedIsolateHeapStats,
         UdfCallback,
         UdfRequest,
@@ -149,9 +163,15 @@ use crate::{
     },
     metrics::{
         self,
+        log_deployment_snapshot_hit,
         log_isolate_request_cancelled,
     },
     request_scope::RequestScope,
+    snapshot::{
+        restore_modules,
+        DeploymentSnapshot,
+        DEPLOYMENT_SNAPSHOT_CONTEXT_INDEX,
+    },
     strings,
     termination::TerminationReason,
     timeout::{
@@ -168,6 +188,7 @@ pub struct DatabaseUdfEnvironment<RT: Runtime> {
     arguments: ConvexArray,
     identity: InertIdentity,
     udf_server_version: Option<semver::Version>,
+    limits: FunctionLimits,
     client_id: String,
 
     phase: UdfPhase<RT>,
@@ -190,6 +211,9 @@ pub struct DatabaseUdfEnvironment<RT: Runtime> {
 
     syscall_trace: SyscallTrace,
 
+    /// Set when recording this execution or replaying an earlier recording.
+    recording: Option<SyscallRecording>,
+
     heap_stats: SharedIsolateHeapStats,
 
     context: ExecutionContext,
@@ -198,6 +222,79 @@ pub struct DatabaseUdfEnvironment<RT: Runtime> {
     udf_callback: Box<dyn UdfCallback<RT>>,
 }
 
+enum SyscallRecording {
+    Record {
+        dir: PathBuf,
+        path_and_args: ValidatedPathAndArgs,
+        snapshot_ts: Timestamp,
+        syscalls: Vec<RecordedSyscall>,
+        modules: BTreeMap<String, Sha256Digest>,
+    },
+    Replay {
+        rng_seed: [u8; 32],
+        unix_timestamp: UnixTimestamp,
+        syscalls: VecDeque<RecordedSyscall>,
+        modules: BTreeMap<String, Sha256Digest>,
+    },
+}
+
+impl SyscallRecording {
+    /// Records the hash of a loaded module, or checks that it matches the
+    /// recorded one when replaying.
+    fn module_loaded(&mut self, path: &str, source: &FullModuleSource) -> anyhow::Result<()> {
+        let sha256 = hash_module_source(&source.source, source.source_map.as_ref());
+        match self {
+            SyscallRecording::Record { modules, .. } => {
+                modules.insert(path.to_string(), sha256);
+            },
+            SyscallRecording::Replay { modules, .. } => {
+                anyhow::ensure!(
+                    modules.get(path) == Some(&sha256),
+                    "Replay diverged: module {path} isn't the one that was recorded"
+                );
+            },
+        }
+        Ok(())
+    }
+
+    fn record_syscall(
+        &mut self,
+        name: &str,
+        args: JsonValue,
+        result: Result<String, &anyhow::Error>,
+    ) {
+        if let SyscallRecording::Record { syscalls, .. } = self {
+            syscalls.push(RecordedSyscall {
+                name: name.to_string(),
+                args,
+                result: match result {
+                    Ok(value) => Ok(value),
+                    Err(e) => Err(RecordedSyscallError::from_error(e)),
+                },
+            });
+        }
+    }
+
+    /// Returns the next recorded result, checking that the function is making
+    /// the same syscall it made when it was recorded.
+    fn replay_syscall(
+        syscalls: &mut VecDeque<RecordedSyscall>,
+        name: &str,
+        args: &JsonValue,
+    ) -> anyhow::Result<String> {
+        let Some(syscall) = syscalls.pop_front() else {
+            anyhow::bail!("Replay diverged: {name} was called after the last recorded syscall");
+        };
+        anyhow::ensure!(
+            syscall.name == name && syscall.args == *args,
+            "Replay diverged: expected {} with {}, but {name} was called with {args}",
+            syscall.name,
+            syscall.args,
+        );
+        syscall.result.map_err(RecordedSyscallError::into_error)
+    }
+}
+
 fn not_allowed_in_udf(name: &str, description: &str) -> ErrorMetadata {
     ErrorMetadata::bad_request(
         format!("No{name}InQueriesOrMutations"),
@@ -256,11 +353,27 @@ impl<RT: Runtime> IsolateEnvironment<RT> for DatabaseUdfEnvironment<RT> {
             .phase
             .get_module(&user_module_path, timeout, permit)
             .await?;
+        if let (Some(recording), Some((source, _))) = (&mut self.recording, &result) {
+            recording.module_loaded(path, source)?;
+        }
         Ok(result)
     }
 
     fn syscall(&mut self, name: &str, args: JsonValue) -> anyhow::Result<JsonValue> {
-        syscall_impl(self, name, args)
+        match &mut self.recording {
+            None => syscall_impl(self, name, args),
+            Some(SyscallRecording::Replay { syscalls, .. }) => {
+                let result = SyscallRecording::replay_syscall(syscalls, name, &args)?;
+                Ok(serde_json::from_str(&result)?)
+            },
+            Some(SyscallRecording::Record { .. }) => {
+                let result = syscall_impl(self, name, args.clone());
+                if let Some(recording) = &mut self.recording {
+                    recording.record_syscall(name, args, result.as_ref().map(JsonValue::to_string));
+                }
+                result
+            },
+        }
     }
 
     fn start_async_syscall(
@@ -296,13 +409,23 @@ impl<RT: Runtime> IsolateEnvironment<RT> for DatabaseUdfEnvironment<RT> {
     }
 
     fn user_timeout(&self) -> std::time::Duration {
-        *DATABASE_UDF_USER_TIMEOUT
+        self.limits
+            .timeout_ms
+            .map_or(*DATABASE_UDF_USER_TIMEOUT, |ms| {
+                std::time::Duration::from_millis(ms).min(*DATABASE_UDF_MAX_USER_TIMEOUT)
+            })
     }
 
     fn system_timeout(&self) -> std::time::Duration {
         *DATABASE_UDF_SYSTEM_TIMEOUT
     }
 
+    fn memory_limit(&self) -> Option<usize> {
+        self.limits
+            .memory_mb
+            .map(|mb| ((mb as usize) << 20).min(*ISOLATE_MAX_USER_HEAP_SIZE))
+    }
+
     fn is_nested_function(&self) -> bool {
         self.reactor_depth > 0
     }
@@ -321,15 +444,42 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
         UdfRequest {
             path_and_args,
             udf_type,
-            transaction,
+            mut transaction,
             journal,
             context,
+            recording,
         }: UdfRequest<RT>,
         reactor_depth: usize,
         udf_callback: Box<dyn UdfCallback<RT>>,
         client_id: String,
     ) -> Self {
         let persistence_version = transaction.persistence_version();
+        let mut identity = transaction.inert_identity();
+        let recording = match recording {
+            ExecutionRecordingMode::Disabled => None,
+            ExecutionRecordingMode::Record(dir) => Some(SyscallRecording::Record {
+                dir,
+                path_and_args: path_and_args.clone(),
+                snapshot_ts: *transaction.begin_timestamp(),
+                syscalls: vec![],
+                modules: BTreeMap::new(),
+            }),
+            ExecutionRecordingMode::Replay(recording) => {
+                identity = recording.identity;
+                Some(SyscallRecording::Replay {
+                    rng_seed: recording.rng_seed,
+                    unix_timestamp: recording.unix_timestamp,
+                    syscalls: recording.syscalls.into(),
+                    modules: recording.modules,
+                })
+            },
+        };
+        let limits = *path_and_args.limits();
+        // Nested functions share their caller's transaction, so only the
+        // outermost function's read and write limits apply.
+        if reactor_depth == 0 {
+            transaction.apply_function_limits(&limits);
+        }
         let (path, arguments, udf_server_version) = path_and_args.consume();
         let component = path.component;
         Self {
@@ -337,8 +487,9 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
             udf_type,
             path,
             arguments,
-            identity: transaction.inert_identity(),
+            identity,
             udf_server_version,
+            limits,
 
             phase: UdfPhase::new(
                 transaction,
@@ -359,6 +510,7 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
 
             pending_syscalls: WithHeapSize::default(),
             syscall_trace: SyscallTrace::new(),
+            recording,
             heap_stats,
             context,
 
@@ -380,9 +532,15 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
     ) -> anyhow::Result<(Transaction<RT>, FunctionOutcome)> {
         // Initialize the UDF's RNG from some high-quality entropy. As with
         // `unix_timestamp` below, the UDF is only deterministic modulo this
-        // system-generated input.
-        let rng_seed = self.rt.rng().random();
-        let unix_timestamp = self.rt.unix_timestamp();
+        // system-generated input, so replays reuse the recorded values.
+        let (rng_seed, unix_timestamp) = match &self.recording {
+            Some(SyscallRecording::Replay {
+                rng_seed,
+                unix_timestamp,
+                ..
+            }) => (*rng_seed, *unix_timestamp),
+            _ => (self.rt.rng().random(), self.rt.unix_timestamp()),
+        };
         let heap_stats = self.heap_stats.clone();
 
         // See Isolate::with_context for an explanation of this setup code. We can't use
@@ -390,18 +548,52 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
         // generic async closure to `Isolate` is currently difficult.
         let client_id = Arc::new(client_id);
         let path = self.path.clone();
-        let (handle, state) = isolate.start_request(client_id, self).await?;
+        let deployment_snapshot = isolate.deployment_snapshot().cloned();
+        let (handle, mut state) = isolate.start_request(client_id, self).await?;
         if let Some(tx) = function_started {
             // At this point we have acquired a permit and aren't going to
             // reject the function for capacity reasons.
             _ = tx.send(());
         }
+        // Initialize the environment, preloading the UDF config, before executing any
+        // JS. This happens before entering a context since the preloaded state decides
+        // whether we can start from the isolate's deployment snapshot.
+        state
+            .environment
+            .phase
+            .initialize(&mut state.timeout, &mut state.permit)
+            .await?;
+        let deployment_snapshot = if *ISOLATE_DEPLOYMENT_SNAPSHOTS {
+            let snapshot = state
+                .environment
+                .select_deployment_snapshot(
+                    deployment_snapshot,
+                    &mut state.timeout,
+                    &mut state.permit,
+                )
+                .await?;
+            log_deployment_snapshot_hit(snapshot.is_some());
+            snapshot
+        } else {
+            None
+        };
         let mut handle_scope = isolate.handle_scope();
-        let v8_context = v8::Local::new(&mut handle_scope, v8_context);
+        let v8_context = match deployment_snapshot {
+            Some(_) => v8::Context::from_snapshot(
+                &mut handle_scope,
+                DEPLOYMENT_SNAPSHOT_CONTEXT_INDEX,
+                Default::default(),
+            )
+            .ok_or_else(|| anyhow!("Failed to create context from deployment snapshot"))?,
+            None => v8::Local::new(&mut handle_scope, v8_context),
+        };
         let mut context_scope = v8::ContextScope::new(&mut handle_scope, v8_context);
 
         let mut isolate_context =
             RequestScope::new(&mut context_scope, handle.clone(), state, false).await?;
+        if let Some(snapshot) = &deployment_snapshot {
+            restore_modules(&mut isolate_context.scope(), snapshot)?;
+        }
         let mut result =
             Self::run_inner(&mut isolate_context, cancellation, rng_seed, unix_timestamp).await;
 
@@ -438,6 +630,7 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
         Self::add_warnings_to_log_lines(
             &self.path.clone().for_logging(),
             &self.arguments,
+            &self.limits,
             execution_time,
             self.phase.execution_size()?,
             self.phase.biggest_document_writes()?,
@@ -453,6 +646,54 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
                 ));
             },
         )?;
+        match self.recording.take() {
+            Some(SyscallRecording::Record {
+                dir,
+                path_and_args,
+                snapshot_ts,
+                syscalls,
+                modules,
+            }) => {
+                let recording = ExecutionRecording {
+                    udf_type: self.udf_type,
+                    path_and_args,
+                    identity: self.identity.clone(),
+                    snapshot_ts,
+                    rng_seed,
+                    unix_timestamp,
+                    syscalls,
+                    modules,
+                };
+                let name = format!("{snapshot_ts}-{:016x}", self.rt.rng().random::<u64>());
+                let key_broker = self.key_broker.clone();
+                let path = self.path.clone().for_logging();
+                // Encrypt and write the recording off of the isolate thread.
+                // It's written under a temporary name and renamed once it's
+                // complete, so readers never see a partial recording.
+                self.rt
+                    .spawn_background("write_execution_recording", async move {
+                        let result = async {
+                            let encrypted = recording.encrypt(&key_broker)?;
+                            let tmp_path = dir.join(format!("{name}.tmp"));
+                            tokio::fs::write(&tmp_path, encrypted).await?;
+                            tokio::fs::rename(&tmp_path, dir.join(format!("{name}.recording")))
+                                .await?;
+                            anyhow::Ok(())
+                        };
+                        if let Err(e) = result.await {
+                            tracing::warn!("Failed to record execution of {path:?}: {e:?}");
+                        }
+                    });
+            },
+            Some(SyscallRecording::Replay { syscalls, .. }) => {
+                anyhow::ensure!(
+                    syscalls.is_empty(),
+                    "Replay diverged: {} recorded syscalls were never called",
+                    syscalls.len()
+                );
+            },
+            None => (),
+        }
         let outcome = match self.udf_type {
             UdfType::Query => FunctionOutcome::Query(UdfOutcome {
                 path: self.path.for_logging(),
@@ -497,6 +738,51 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
         Ok((self.phase.into_transaction()?, outcome))
     }
 
+    /// Returns `snapshot` if it was built from the source package and
+    /// environment variables this request sees. Otherwise, asks the module
+    /// loader to build one for later requests.
+    async fn select_deployment_snapshot(
+        &mut self,
+        snapshot: Option<Arc<DeploymentSnapshot>>,
+        timeout: &mut Timeout<RT>,
+        permit: &mut Option<ConcurrencyPermit>,
+    ) -> anyhow::Result<Option<Arc<DeploymentSnapshot>>> {
+        // Components and system modules aren't in the root source package.
+        let module_path = self.path.udf_path.module().clone();
+        if !self.path.component.is_root() || module_path.is_system() {
+            return Ok(None);
+        }
+        // Every push rewrites all modules' metadata, so reading the entry
+        // module's metadata is enough to invalidate the function if any
+        // module in the snapshot changes.
+        let Some((module_metadata, source_package)) = self
+            .phase
+            .module_metadata_and_package(module_path, timeout, permit)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if let Some(snapshot) = snapshot
+            && snapshot.source_package_id == module_metadata.source_package_id
+        {
+            let mut environment_matches = true;
+            for (name, value) in &snapshot.environment_variables {
+                if self.phase.get_environment_variable(name.clone())? != *value {
+                    environment_matches = false;
+                    break;
+                }
+            }
+            if environment_matches {
+                return Ok(Some(snapshot));
+            }
+        }
+        let environment_variables = self.phase.environment_variables_unrecorded()?;
+        self.phase
+            .module_loader()
+            .build_deployment_snapshot(source_package, environment_variables);
+        Ok(None)
+    }
+
     #[convex_macro::instrument_future]
     #[fastrace::trace]
     async fn run_inner(
@@ -510,17 +796,6 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
 
         let mut scope = RequestScope::<RT, Self>::enter(&mut v8_scope);
 
-        // Initialize the environment, preloading the UDF config, before executing any
-        // JS.
-        {
-            let state = scope.state_mut()?;
-            state
-                .environment
-                .phase
-                .initialize(&mut state.timeout, &mut state.permit)
-                .await?;
-        }
-
         let (udf_type, path, udf_args) = {
             let state = scope.state()?;
             let environment = &state.environment;
@@ -704,6 +979,12 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
                     // No syscalls or javascript to run, so we're done.
                     break;
                 };
+                // Keep a copy of each syscall's name and arguments if we're
+                // recording or replaying.
+                let mut batch_syscalls = vec![];
+                if state.environment.recording.is_some() {
+                    batch_syscalls.push((p.name.clone(), p.args.clone()));
+                }
                 let mut batch = AsyncSyscallBatch::new(p.name, p.args);
                 let mut resolvers = vec![p.resolver];
                 while let Some(p) = state.environment.pending_syscalls.front()
@@ -714,6 +995,9 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
                         .pending_syscalls
                         .pop_front()
                         .expect("should have a syscall");
+                    if state.environment.recording.is_some() {
+                        batch_syscalls.push((p.name.clone(), p.args.clone()));
+                    }
                     batch.push(p.name, p.args)?;
                     resolvers.push(p.resolver);
                 }
@@ -728,19 +1012,33 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
                 // Even though the future would be blocking on the database most of the
                 // time it still does some processing that might result in oversubscribing
                 // the CPU threads dedicated to v8.
-                let results = select_biased! {
-                    _ = cancellation => {
-                        log_isolate_request_cancelled();
-                        anyhow::bail!("Cancelled");
-                    },
-                    results = with_release_permit(
-                        &mut state.timeout,
-                        &mut state.permit,
-                        DatabaseSyscallsV1::run_async_syscall_batch(
-                            &mut state.environment, batch,
-                        ).map(Ok),
-                    ).fuse() => results?,
+                let results: Vec<_> = if let Some(SyscallRecording::Replay { syscalls, .. }) =
+                    &mut state.environment.recording
+                {
+                    batch_syscalls
+                        .iter()
+                        .map(|(name, args)| SyscallRecording::replay_syscall(syscalls, name, args))
+                        .collect()
+                } else {
+                    select_biased! {
+                        _ = cancellation => {
+                            log_isolate_request_cancelled();
+                            anyhow::bail!("Cancelled");
+                        },
+                        results = with_release_permit(
+                            &mut state.timeout,
+                            &mut state.permit,
+                            DatabaseSyscallsV1::run_async_syscall_batch(
+                                &mut state.environment, batch,
+                            ).map(Ok),
+                        ).fuse() => results?,
+                    }
                 };
+                if let Some(recording) = &mut state.environment.recording {
+                    for ((name, args), result) in batch_syscalls.into_iter().zip(&results) {
+                        recording.record_syscall(&name, args, result.as_ref().cloned());
+                    }
+                }
                 (resolvers, results)
             };
             // Every syscall must have a result (which could be an error or None).
@@ -842,6 +1140,7 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
     pub fn add_warnings_to_log_lines(
         path: &CanonicalizedComponentFunctionPath,
         arguments: &ConvexArray,
+        limits: &FunctionLimits,
         execution_time: FunctionExecutionTime,
         execution_size: FunctionExecutionSize,
         biggest_writes: Option<BiggestDocumentWrites>,
@@ -856,6 +1155,8 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
         } else {
             None
         };
+        let read_limits = ReadLimits::new(limits);
+        let write_limits = WriteLimits::new(limits);
         if let Some(warning) = approaching_limit_warning(
             arguments.size(),
             *FUNCTION_MAX_ARGS_SIZE,
@@ -869,7 +1170,7 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
         }
         if let Some(warning) = approaching_limit_warning(
             execution_size.read_size.total_document_count,
-            *TRANSACTION_MAX_READ_SIZE_ROWS,
+            read_limits.max_documents,
             "TooManyDocumentsRead",
             || "Many documents read in a single function execution".to_string(),
             Some(OVER_LIMIT_HELP),
@@ -891,7 +1192,7 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
         }
         if let Some(warning) = approaching_limit_warning(
             execution_size.read_size.total_document_size,
-            *TRANSACTION_MAX_READ_SIZE_BYTES,
+            read_limits.max_bytes,
             "TooManyBytesRead",
             || "Many bytes read in a single function execution".to_string(),
             Some(OVER_LIMIT_HELP),
@@ -902,7 +1203,7 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
         }
         if let Some(warning) = approaching_limit_warning(
             execution_size.write_size.num_writes,
-            *TRANSACTION_MAX_NUM_USER_WRITES,
+            write_limits.max_writes,
             "TooManyWrites",
             || "Many writes in a single function execution".to_string(),
             None,
@@ -913,7 +1214,7 @@ impl<RT: Runtime> DatabaseUdfEnvironment<RT> {
         }
         if let Some(warning) = approaching_limit_warning(
             execution_size.write_size.size,
-            *TRANSACTION_MAX_USER_WRITE_SIZE_BYTES,
+            write_limits.max_bytes,
             "TooManyBytesWritten",
             || "Many bytes written in a single function execution".to_string(),
             None,
@@ -9,6 +9,7 @@ use common::{
         CanonicalizedComponentModulePath,
         ComponentId,
     },
+    document::ParsedDocument,
     runtime::{
         Runtime,
         UnixTimestamp,
@@ -33,14 +34,21 @@ use model::{
     },
     modules::{
         module_versions::FullModuleSource,
+        types::ModuleMetadata,
         ModuleModel,
     },
-    source_packages::SourcePackageModel,
+    source_packages::{
+        types::SourcePackage,
+        SourcePackageModel,
+    },
     udf_config::UdfConfigModel,
 };
 use rand::SeedableRng;
 use rand_chacha::ChaCha12Rng;
-use sync_types::ModulePath;
+use sync_types::{
+    CanonicalizedModulePath,
+    ModulePath,
+};
 use udf::environment::system_env_vars;
 use value::{
     identifier::Identifier,
@@ -240,30 +248,8 @@ impl<RT: Runtime> UdfPhase<RT> {
                 format!("Can't dynamically import {module_path:?} in a query or mutation")
             ));
         }
-        let UdfPreloaded::Ready { component, .. } = &self.preloaded else {
-            anyhow::bail!("Phase not initialized");
-        };
-        let component = *component;
-        let path = CanonicalizedComponentModulePath {
-            component,
-            module_path: module_path.clone().canonicalize(),
-        };
-        let Some((module_metadata, source_package)) =
-            with_release_permit(timeout, permit_slot, async {
-                match ModuleModel::new(self.tx_mut()?)
-                    .get_metadata(path.clone())
-                    .await?
-                {
-                    None => anyhow::Ok(None),
-                    Some(module_metadata) => {
-                        let source_package =
-                            SourcePackageModel::new(self.tx_mut()?, component.into())
-                                .get(module_metadata.source_package_id)
-                                .await?;
-                        anyhow::Ok(Some((module_metadata, source_package)))
-                    },
-                }
-            })
+        let Some((module_metadata, source_package)) = self
+            .module_metadata_and_package(module_path.clone().canonicalize(), timeout, permit_slot)
             .await?
         else {
             return Ok(None);
@@ -287,6 +273,46 @@ impl<RT: Runtime> UdfPhase<RT> {
         Ok(Some((module_source, code_cache_result)))
     }
 
+    pub async fn module_metadata_and_package(
+        &mut self,
+        module_path: CanonicalizedModulePath,
+        timeout: &mut Timeout<RT>,
+        permit_slot: &mut Option<ConcurrencyPermit>,
+    ) -> anyhow::Result<
+        Option<(
+            ParsedDocument<ModuleMetadata>,
+            ParsedDocument<SourcePackage>,
+        )>,
+    > {
+        let UdfPreloaded::Ready { component, .. } = &self.preloaded else {
+            anyhow::bail!("Phase not initialized");
+        };
+        let component = *component;
+        let path = CanonicalizedComponentModulePath {
+            component,
+            module_path,
+        };
+        with_release_permit(timeout, permit_slot, async {
+            match ModuleModel::new(self.tx_mut()?)
+                .get_metadata(path.clone())
+                .await?
+            {
+                None => anyhow::Ok(None),
+                Some(module_metadata) => {
+                    let source_package = SourcePackageModel::new(self.tx_mut()?, component.into())
+                        .get(module_metadata.source_package_id)
+                        .await?;
+                    anyhow::Ok(Some((module_metadata, source_package)))
+                },
+            }
+        })
+        .await
+    }
+
+    pub fn module_loader(&self) -> &Arc<dyn ModuleCache<RT>> {
+        &self.module_loader
+    }
+
     pub fn tx(&mut self) -> anyhow::Result<&mut Transaction<RT>> {
         if self.phase != Phase::Executing {
             anyhow::bail!(ErrorMetadata::bad_request(
@@ -381,6 +407,27 @@ impl<RT: Runtime> UdfPhase<RT> {
         Ok(system_env_vars.get(&name).cloned())
     }
 
+    /// All environment variables visible to the function, without recording
+    /// reads of them in the transaction. Only used to build deployment
+    /// snapshots, which record the variables they read themselves.
+    pub fn environment_variables_unrecorded(
+        &self,
+    ) -> anyhow::Result<BTreeMap<EnvVarName, EnvVarValue>> {
+        let UdfPreloaded::Ready {
+            ref env_vars,
+            ref system_env_vars,
+            ..
+        } = self.preloaded
+        else {
+            anyhow::bail!("Phase not initialized");
+        };
+        let mut environment_variables = system_env_vars.clone();
+        if let Some(env_vars) = env_vars {
+            environment_variables.extend(env_vars.all_unrecorded()?);
+        }
+        Ok(environment_variables)
+    }
+
     pub fn rng(&mut self) -> anyhow::Result<&mut ChaCha12Rng> {
         let UdfPreloaded::Ready {
             ref mut rng,
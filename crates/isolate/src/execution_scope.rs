@@ -39,6 +39,7 @@ use model::modules::{
         ModuleNotFoundError,
         SystemModuleNotFoundError,
     },
+    wasm::decode_wasm_module,
 };
 use serde_json::Value as JsonValue;
 use value::heap_size::HeapSize;
@@ -47,6 +48,7 @@ use crate::{
     array_buffer_allocator::ArrayBufferMemoryLimit,
     bundled_js::system_udf_file,
     environment::{
+        helpers::module_loader::is_wasm_module_specifier,
         IsolateEnvironment,
         ModuleCodeCacheResult,
     },
@@ -63,8 +65,12 @@ use crate::{
         ModuleId,
         ModuleMap,
     },
+    ops::compile_validated_wasm_module,
     request_scope::RequestState,
-    termination::IsolateHandle,
+    termination::{
+        IsolateHandle,
+        TerminationReason,
+    },
     IsolateHeapStats,
 };
 
@@ -212,8 +218,9 @@ impl<'a, 'b: 'a, RT: Runtime, E: IsolateEnvironment<RT>> ExecutionScope<'a, 'b,
         let array_buffer_size = self
             .get_slot::<Arc<ArrayBufferMemoryLimit>>()
             .context("missing ArrayBufferMemoryLimit?")?
-            .used();
-        self.with_state_mut(|state| {
+            .used_including_wasm(&stats);
+        let used_memory = stats.used_heap_size() + array_buffer_size;
+        let memory_limit = self.with_state_mut(|state| {
             let blobs_heap_size = state.blob_parts.heap_size();
             let streams_heap_size = state.streams.heap_size() + state.stream_listeners.heap_size();
             state.environment.record_heap_stats(IsolateHeapStats::new(
@@ -222,7 +229,16 @@ impl<'a, 'b: 'a, RT: Runtime, E: IsolateEnvironment<RT>> ExecutionScope<'a, 'b,
                 streams_heap_size,
                 array_buffer_size,
             ));
-        })
+            state.environment.memory_limit()
+        })?;
+        if let Some(memory_limit) = memory_limit
+            && used_memory > memory_limit
+            && self.handle().is_not_clean().is_none()
+        {
+            self.handle()
+                .terminate(TerminationReason::MemoryLimit(memory_limit));
+        }
+        Ok(())
     }
 
     pub fn module_map(&mut self) -> &ModuleMap {
@@ -334,6 +350,9 @@ impl<'a, 'b: 'a, RT: Runtime, E: IsolateEnvironment<RT>> ExecutionScope<'a, 'b,
                 return Ok(id);
             }
         }
+        if is_wasm_module_specifier(name) {
+            return self.register_wasm_module(name).await;
+        }
         let (id, import_specifiers) = {
             let (module_source, code_cache) = self.lookup_source(name).await?;
 
@@ -436,6 +455,40 @@ impl<'a, 'b: 'a, RT: Runtime, E: IsolateEnvironment<RT>> ExecutionScope<'a, 'b,
         Ok(id)
     }
 
+    /// `.wasm` modules are registered as synthetic modules whose default
+    /// export is the compiled `WebAssembly.Module`. They don't have any
+    /// imports, so instantiating one is up to the importing module.
+    async fn register_wasm_module(&mut self, name: &ModuleSpecifier) -> anyhow::Result<ModuleId> {
+        let _s = static_span!();
+        // WebAssembly modules don't use the code cache.
+        let (module_source, _) = self.lookup_source(name).await?;
+        let bytes = decode_wasm_module(&module_source.source)?;
+
+        let timer = metrics::compile_module_timer(false);
+        let mut scope = v8::HandleScope::new(&mut **self);
+        let mut scope = ExecutionScope::<RT, E>::new(&mut scope);
+        let wasm_module = scope
+            .with_try_catch(|s| compile_validated_wasm_module(s, &bytes))???
+            .ok_or_else(|| anyhow!("Unexpected WebAssembly compilation error"))?;
+        let name_str = v8::String::new(&mut scope, name.as_str())
+            .ok_or_else(|| anyhow!("Failed to create name string"))?;
+        let export_name = v8::String::new(&mut scope, "default")
+            .ok_or_else(|| anyhow!("Failed to create export name string"))?;
+        let module = v8::Module::create_synthetic_module(
+            &mut scope,
+            name_str,
+            &[export_name],
+            Self::wasm_module_evaluation_steps,
+        );
+        timer.finish();
+
+        let module_v8 = v8::Global::new(&mut scope, module);
+        let wasm_module: v8::Local<v8::Value> = wasm_module.into();
+        let wasm_module = v8::Global::new(&mut scope, wasm_module);
+        let module_map = scope.module_map_mut();
+        Ok(module_map.register_wasm(name, module_v8, module_source, wasm_module))
+    }
+
     async fn lookup_source(
         &mut self,
         module_specifier: &ModuleSpecifier,
@@ -605,6 +658,46 @@ impl<'a, 'b: 'a, RT: Runtime, E: IsolateEnvironment<RT>> ExecutionScope<'a, 'b,
         Ok(v8::Local::new(&mut scope, handle))
     }
 
+    fn wasm_module_evaluation_steps<'c>(
+        context: v8::Local<'c, v8::Context>,
+        module: v8::Local<'c, v8::Module>,
+    ) -> Option<v8::Local<'c, v8::Value>> {
+        let scope = &mut unsafe { v8::CallbackScope::new(context) };
+        match Self::_wasm_module_evaluation_steps(scope, module) {
+            Ok(promise) => Some(promise),
+            Err(e) => {
+                helpers::throw_type_error(scope, format!("{:?}", e));
+                None
+            },
+        }
+    }
+
+    fn _wasm_module_evaluation_steps<'c>(
+        scope: &mut v8::CallbackScope<'c>,
+        module: v8::Local<'c, v8::Module>,
+    ) -> anyhow::Result<v8::Local<'c, v8::Value>> {
+        let mut scope = ExecutionScope::<RT, E>::new(scope);
+        let module_global = v8::Global::new(&mut scope, module);
+        let wasm_module = scope
+            .module_map()
+            .wasm_module_by_handle(&module_global)
+            .ok_or_else(|| anyhow!("Couldn't find WebAssembly module"))?;
+        let wasm_module = v8::Local::new(&mut scope, wasm_module);
+        let export_name = v8::String::new(&mut scope, "default")
+            .ok_or_else(|| anyhow!("Failed to create export name string"))?;
+        module
+            .set_synthetic_module_export(&mut scope, export_name, wasm_module)
+            .ok_or_else(|| anyhow!("Failed to set WebAssembly module export"))?;
+
+        // Synthetic modules evaluate to a promise like any other module with
+        // top-level await enabled.
+        let resolver = v8::PromiseResolver::new(&mut scope)
+            .ok_or_else(|| anyhow!("Failed to create PromiseResolver"))?;
+        let undefined = v8::undefined(&mut scope);
+        resolver.resolve(&mut scope, undefined.into());
+        Ok(resolver.get_promise(&mut scope).into())
+    }
+
     pub fn syscall(
         &mut self,
         args: v8::FunctionCallbackArguments,
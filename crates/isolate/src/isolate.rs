@@ -36,11 +36,14 @@ use crate::{
     concurrency_limiter::ConcurrencyLimiter,
     environment::IsolateEnvironment,
     helpers::pump_message_loop,
+    memory_limit::MemoryLimitWatcher,
     metrics::{
         create_isolate_timer,
         log_heap_statistics,
     },
+    ops::allow_wasm_code_generation,
     request_scope::RequestState,
+    snapshot::DeploymentSnapshot,
     strings,
     termination::{
         IsolateHandle,
@@ -67,6 +70,7 @@ pub struct Isolate<RT: Runtime> {
     heap_ctx_ptr: *mut HeapContext,
     limiter: ConcurrencyLimiter,
     array_buffer_memory_limit: Arc<ArrayBufferMemoryLimit>,
+    deployment_snapshot: Option<Arc<DeploymentSnapshot>>,
 
     created: tokio::time::Instant,
 }
@@ -158,14 +162,30 @@ impl IsolateHeapStats {
 
 impl<RT: Runtime> Isolate<RT> {
     pub fn new(rt: RT, max_user_timeout: Option<Duration>, limiter: ConcurrencyLimiter) -> Self {
+        Self::new_with_deployment_snapshot(rt, max_user_timeout, limiter, None)
+    }
+
+    /// Creates an isolate that can also create contexts with a deployment's
+    /// modules already evaluated from `deployment_snapshot`.
+    pub fn new_with_deployment_snapshot(
+        rt: RT,
+        max_user_timeout: Option<Duration>,
+        limiter: ConcurrencyLimiter,
+        deployment_snapshot: Option<Arc<DeploymentSnapshot>>,
+    ) -> Self {
         let _timer = create_isolate_timer();
         let (array_buffer_memory_limit, array_buffer_allocator) =
             crate::array_buffer_allocator::limited_array_buffer_allocator(
                 *ISOLATE_MAX_ARRAY_BUFFER_TOTAL_SIZE,
             );
-        let mut v8_isolate = crate::udf_runtime::create_isolate_with_udf_runtime(
-            v8::CreateParams::default().array_buffer_allocator(array_buffer_allocator),
-        );
+        let create_params =
+            v8::CreateParams::default().array_buffer_allocator(array_buffer_allocator);
+        let mut v8_isolate = match &deployment_snapshot {
+            Some(snapshot) => {
+                crate::udf_runtime::create_isolate_with_deployment_snapshot(create_params, snapshot)
+            },
+            None => crate::udf_runtime::create_isolate_with_udf_runtime(create_params),
+        };
 
         // Tells V8 to capture current stack trace when uncaught exception occurs and
         // report it to the message listeners. The option is off by default.
@@ -185,6 +205,9 @@ impl<RT: Runtime> Isolate<RT> {
         // Disallow synchronous `Atomics.wait`.
         v8_isolate.set_allow_atomics_wait(false);
 
+        // Only compile WebAssembly modules we've validated.
+        v8_isolate.set_allow_wasm_code_generation_callback(allow_wasm_code_generation);
+
         v8_isolate.set_microtasks_policy(v8::MicrotasksPolicy::Explicit);
 
         let handle = IsolateHandle::new(v8_isolate.thread_safe_handle());
@@ -212,9 +235,14 @@ impl<RT: Runtime> Isolate<RT> {
             max_user_timeout,
             limiter,
             array_buffer_memory_limit,
+            deployment_snapshot,
         }
     }
 
+    pub fn deployment_snapshot(&self) -> Option<&Arc<DeploymentSnapshot>> {
+        self.deployment_snapshot.as_ref()
+    }
+
     extern "C" fn import_meta_callback(
         context: v8::Local<v8::Context>,
         _module: v8::Local<v8::Module>,
@@ -249,7 +277,8 @@ impl<RT: Runtime> Isolate<RT> {
     // Heap stats for an isolate that has no associated state or environment.
     pub fn heap_stats(&mut self) -> IsolateHeapStats {
         let stats = self.v8_isolate.get_heap_statistics();
-        IsolateHeapStats::new(stats, 0, 0, self.array_buffer_memory_limit.used())
+        let array_buffer_size = self.array_buffer_memory_limit.used_including_wasm(&stats);
+        IsolateHeapStats::new(stats, 0, 0, array_buffer_size)
     }
 
     pub fn check_isolate_clean(&mut self) -> Result<(), IsolateNotClean> {
@@ -322,9 +351,14 @@ impl<RT: Runtime> Isolate<RT> {
             Some(user_timeout),
             Some(environment.system_timeout()),
         );
+        let memory_limit_watcher = environment
+            .memory_limit()
+            .map(|limit| MemoryLimitWatcher::start(self.handle.clone(), limit))
+            .transpose()?;
         let state = RequestState {
             rt: self.rt.clone(),
             environment,
+            memory_limit_watcher,
             timeout,
             permit: Some(permit),
             blob_parts: WithHeapSize::default(),
@@ -337,6 +371,10 @@ impl<RT: Runtime> Isolate<RT> {
         Ok((self.handle.clone(), state))
     }
 
+    pub fn handle(&self) -> IsolateHandle {
+        self.handle.clone()
+    }
+
     pub fn handle_scope(&mut self) -> v8::HandleScope<()> {
         v8::HandleScope::new(&mut self.v8_isolate)
     }
@@ -33,7 +33,10 @@ use super::{
 use crate::{
     deserialize_udf_result,
     environment::helpers::{
-        module_loader::module_specifier_from_path,
+        module_loader::{
+            is_wasm_module_specifier,
+            module_specifier_from_path,
+        },
         resolve_promise,
     },
     error::extract_source_mapped_error,
@@ -158,6 +161,10 @@ impl<'enter, 'scope: 'enter> EnteredContext<'enter, 'scope> {
                 "Module already registered"
             );
         }
+        anyhow::ensure!(
+            !is_wasm_module_specifier(url),
+            "WebAssembly module {url} isn't supported by this isolate"
+        );
         let name_str = v8::String::new(self.scope, url.as_str())
             .ok_or_else(|| anyhow!("Failed to create name string"))?;
         let source_str = v8::String::new(self.scope, source)
@@ -486,6 +486,7 @@ async fn run_request<RT: Runtime>(
     execution_context: ExecutionContext,
     query_journal: QueryJournal,
 ) -> anyhow::Result<UdfOutcome> {
+    let limits = *path_and_args.limits();
     let (path, arguments, udf_server_version) = path_and_args.consume();
     anyhow::ensure!(
         path.component.is_root(),
@@ -667,6 +668,7 @@ async fn run_request<RT: Runtime>(
     DatabaseUdfEnvironment::<RT>::add_warnings_to_log_lines(
         &path.clone().for_logging(),
         &arguments,
+        &limits,
         client.execution_time()?,
         provider.tx.execution_size(),
         provider.tx.biggest_document_writes(),
@@ -1,6 +1,9 @@
 use deno_core::v8;
 
-use crate::strings;
+use crate::{
+    ops::allow_wasm_code_generation,
+    strings,
+};
 
 pub struct Thread {
     pub isolate: v8::OwnedIsolate,
@@ -24,6 +27,9 @@ impl Thread {
         // Disallow synchronous `Atomics.wait`.
         isolate.set_allow_atomics_wait(false);
 
+        // Only compile WebAssembly modules we've validated.
+        isolate.set_allow_wasm_code_generation_callback(allow_wasm_code_generation);
+
         isolate.set_microtasks_policy(v8::MicrotasksPolicy::Explicit);
 
         Self { isolate }
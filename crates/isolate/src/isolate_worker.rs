@@ -1,5 +1,7 @@
 use async_trait::async_trait;
 use common::{
+    components::ComponentId,
+    knobs::ISOLATE_CPU_PROFILE_INTERVAL,
     runtime::Runtime,
     types::UdfType,
 };
@@ -36,8 +38,13 @@ use crate::{
         service_request_timer,
         RequestStatus,
     },
+    profiler::{
+        CpuProfiler,
+        CPU_PROFILES,
+    },
     IsolateConfig,
 };
+
 #[derive(Clone)]
 pub(crate) struct FunctionRunnerIsolateWorker<RT: Runtime> {
     rt: RT,
@@ -49,6 +56,41 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
         Self { rt, isolate_config }
     }
 
+    fn start_cpu_profiler(
+        isolate: &Isolate<RT>,
+        instance_name: &str,
+        udf_path: &CanonicalizedUdfPath,
+    ) -> Option<CpuProfiler> {
+        if !CPU_PROFILES.should_profile(instance_name, &udf_path.to_string()) {
+            return None;
+        }
+        match CpuProfiler::start(isolate.handle(), *ISOLATE_CPU_PROFILE_INTERVAL) {
+            Ok(profiler) => Some(profiler),
+            Err(e) => {
+                tracing::warn!("Failed to start CPU profiler for {udf_path:?}: {e:?}");
+                None
+            },
+        }
+    }
+
+    fn finish_cpu_profile(
+        &self,
+        profiler: Option<CpuProfiler>,
+        instance_name: &str,
+        component: ComponentId,
+        udf_path: &CanonicalizedUdfPath,
+    ) {
+        let Some(profiler) = profiler else {
+            return;
+        };
+        CPU_PROFILES.insert(
+            instance_name,
+            component,
+            udf_path.to_string(),
+            profiler.stop(),
+        );
+    }
+
     async fn handle_request_inner(
         &self,
         isolate: &mut Isolate<RT>,
@@ -76,6 +118,8 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
                 let timer = service_request_timer(&request.udf_type);
                 record_component_function_path(request.path_and_args.path());
                 let udf_path = request.path_and_args.path().udf_path.to_owned();
+                let component = request.path_and_args.path().component;
+                let instance_name = environment_data.key_broker.instance_name().to_owned();
                 let environment = DatabaseUdfEnvironment::new(
                     self.rt.clone(),
                     environment_data,
@@ -85,6 +129,7 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
                     udf_callback,
                     client_id.clone(),
                 );
+                let profiler = Self::start_cpu_profiler(isolate, &instance_name, &udf_path);
                 let r = environment
                     .run(
                         client_id,
@@ -95,6 +140,7 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
                         function_started_sender,
                     )
                     .await;
+                self.finish_cpu_profile(profiler, &instance_name, component, &udf_path);
                 let status = match &r {
                     Ok((_tx, outcome)) => {
                         if is_developer_ok(outcome) {
@@ -125,6 +171,7 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
                 record_component_function_path(path);
                 let udf_path = path.udf_path.to_owned();
                 let component = path.component.to_owned();
+                let instance_name = environment_data.key_broker.instance_name().to_owned();
                 let environment = ActionEnvironment::new(
                     self.rt.clone(),
                     component,
@@ -138,6 +185,7 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
                     heap_stats.clone(),
                     request.context,
                 );
+                let profiler = Self::start_cpu_profiler(isolate, &instance_name, &udf_path);
                 let r = environment
                     .run_action(
                         client_id,
@@ -149,6 +197,7 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
                         function_started_sender,
                     )
                     .await;
+                self.finish_cpu_profile(profiler, &instance_name, component, &udf_path);
 
                 let status = match &r {
                     Ok(outcome) => {
@@ -199,9 +248,11 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
                 let udf_path: CanonicalizedUdfPath =
                     request.http_module_path.path().udf_path.clone();
                 record_component_function_path(request.http_module_path.path());
+                let component = request.http_module_path.path().component;
+                let instance_name = environment_data.key_broker.instance_name().to_owned();
                 let environment = ActionEnvironment::new(
                     self.rt.clone(),
-                    request.http_module_path.path().component,
+                    component,
                     environment_data,
                     request.identity,
                     request.transaction,
@@ -212,6 +263,7 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
                     heap_stats.clone(),
                     request.context,
                 );
+                let profiler = Self::start_cpu_profiler(isolate, &instance_name, &udf_path);
                 let r = environment
                     .run_http_action(
                         client_id,
@@ -224,6 +276,7 @@ impl<RT: Runtime> FunctionRunnerIsolateWorker<RT> {
                         function_started_sender,
                     )
                     .await;
+                self.finish_cpu_profile(profiler, &instance_name, component, &udf_path);
                 let status = match &r {
                     Ok(outcome) => match outcome.result {
                         // Note that the stream could potentially encounter errors later
@@ -23,11 +23,14 @@ mod is_instance_of_error;
 pub mod isolate;
 pub mod isolate2;
 pub mod isolate_worker;
+mod memory_limit;
 pub mod metrics;
 pub mod module_cache;
 pub mod module_map;
 mod ops;
+pub mod profiler;
 mod request_scope;
+pub mod snapshot;
 pub mod strings;
 mod termination;
 #[cfg(test)]
@@ -42,6 +45,7 @@ pub use self::{
         ActionCallbacks,
         ActionRequest,
         ActionRequestParams,
+        ExecutionRecordingMode,
         IsolateClient,
         IsolateConfig,
         UdfCallback,
@@ -0,0 +1,126 @@
+//! Enforces a function's memory limit while its JavaScript is running.
+//!
+//! A function's `limits` config can set a memory limit below the isolate's
+//! heap limit, which V8 can't lower for a single request. Instead a single
+//! background thread interrupts every isolate running a request with a memory
+//! limit once per check interval, and the interrupt callback terminates the
+//! isolate once it's using more than the limit, so a function can't get past
+//! its limit by never yielding to the event loop.
+use std::{
+    ffi,
+    sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+        Arc,
+    },
+    thread,
+};
+
+use common::knobs::ISOLATE_MEMORY_LIMIT_CHECK_INTERVAL;
+use deno_core::v8;
+use parking_lot::Mutex;
+
+use crate::{
+    array_buffer_allocator::ArrayBufferMemoryLimit,
+    termination::{
+        IsolateHandle,
+        TerminationReason,
+    },
+};
+
+/// The requests currently being watched, or `None` until the watcher thread
+/// has been started.
+static WATCHERS: Mutex<Option<Vec<Arc<WatcherState>>>> = Mutex::new(None);
+
+struct WatcherState {
+    handle: IsolateHandle,
+    limit: usize,
+    stopped: AtomicBool,
+    /// Set while a check is queued on the isolate, so an isolate that's stuck
+    /// running JavaScript doesn't pile up interrupts.
+    interrupt_pending: AtomicBool,
+}
+
+pub struct MemoryLimitWatcher {
+    state: Arc<WatcherState>,
+}
+
+impl MemoryLimitWatcher {
+    pub fn start(handle: IsolateHandle, limit: usize) -> anyhow::Result<Self> {
+        let state = Arc::new(WatcherState {
+            handle,
+            limit,
+            stopped: AtomicBool::new(false),
+            interrupt_pending: AtomicBool::new(false),
+        });
+        let mut watchers = WATCHERS.lock();
+        if watchers.is_none() {
+            // The thread is shared by every isolate in the process and runs for
+            // as long as it does.
+            thread::Builder::new()
+                .name("memory-limit".to_string())
+                .spawn(watch_memory)?;
+        }
+        watchers.get_or_insert_with(Vec::new).push(state.clone());
+        Ok(Self { state })
+    }
+}
+
+impl Drop for MemoryLimitWatcher {
+    /// This runs on the isolate's thread when the request finishes, so an
+    /// interrupt that's still pending sees the watcher as stopped and can't
+    /// affect the next request. The watcher thread forgets about the request
+    /// on its next check.
+    fn drop(&mut self) {
+        self.state.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+fn watch_memory() {
+    loop {
+        thread::sleep(*ISOLATE_MEMORY_LIMIT_CHECK_INTERVAL);
+        if let Some(watchers) = WATCHERS.lock().as_mut() {
+            watchers.retain(|state| !state.stopped.load(Ordering::Relaxed) && request_check(state));
+        }
+    }
+}
+
+/// Queues a memory check on the request's isolate unless one is already
+/// queued. Returns false if the isolate has been disposed.
+fn request_check(state: &Arc<WatcherState>) -> bool {
+    if state.interrupt_pending.swap(true, Ordering::Relaxed) {
+        return true;
+    }
+    // The callback takes ownership of this reference, so the state stays alive
+    // even if the interrupt runs after the watcher is dropped.
+    let data = Arc::into_raw(state.clone()) as *mut ffi::c_void;
+    if !state.handle.request_interrupt(check_memory, data) {
+        // The callback will never run.
+        drop(unsafe { Arc::from_raw(data as *const WatcherState) });
+        return false;
+    }
+    true
+}
+
+extern "C" fn check_memory(isolate: &mut v8::Isolate, data: *mut ffi::c_void) {
+    let state = unsafe { Arc::from_raw(data as *const WatcherState) };
+    state.interrupt_pending.store(false, Ordering::Relaxed);
+    if state.stopped.load(Ordering::Relaxed) {
+        return;
+    }
+    let stats = isolate.get_heap_statistics();
+    let array_buffer_size = isolate
+        .get_slot::<Arc<ArrayBufferMemoryLimit>>()
+        .map_or(0, |limit| limit.used_including_wasm(&stats));
+    if stats.used_heap_size() + array_buffer_size <= state.limit {
+        return;
+    }
+    let Some(handle) = isolate.get_slot::<IsolateHandle>() else {
+        return;
+    };
+    if handle.is_not_clean().is_none() {
+        handle.terminate(TerminationReason::MemoryLimit(state.limit));
+    }
+}
@@ -568,6 +568,28 @@ pub fn create_code_cache_timer() -> StatusTimer {
     StatusTimer::new(&CREATE_CODE_CACHE_SECONDS)
 }
 
+register_convex_histogram!(
+    CREATE_DEPLOYMENT_SNAPSHOT_SECONDS,
+    "Time to evaluate a deployment's modules and create a V8 startup snapshot from them",
+    &STATUS_LABEL
+);
+pub fn create_deployment_snapshot_timer() -> StatusTimer {
+    StatusTimer::new(&CREATE_DEPLOYMENT_SNAPSHOT_SECONDS)
+}
+
+register_convex_counter!(
+    DEPLOYMENT_SNAPSHOT_TOTAL,
+    "Number of queries and mutations that did or didn't start from a deployment snapshot",
+    &["hit"]
+);
+pub fn log_deployment_snapshot_hit(hit: bool) {
+    log_counter_with_labels(
+        &DEPLOYMENT_SNAPSHOT_TOTAL,
+        1,
+        vec![StaticMetricLabel::new("hit", hit.as_label())],
+    )
+}
+
 register_convex_histogram!(
     CONCURRENCY_PERMIT_ACQUIRE_SECONDS,
     "Time to acquire a concurrency permit. High latency indicate that isolate threads are \
@@ -1,17 +1,42 @@
-use std::sync::Arc;
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+};
 
-use common::runtime::Runtime;
+use common::{
+    document::ParsedDocument,
+    runtime::Runtime,
+};
 use model::{
     config::module_loader::ModuleLoader,
+    environment_variables::types::{
+        EnvVarName,
+        EnvVarValue,
+    },
     modules::types::ModuleMetadata,
+    source_packages::types::SourcePackage,
 };
 
-use crate::environment::ModuleCodeCacheResult;
+use crate::{
+    environment::ModuleCodeCacheResult,
+    snapshot::DeploymentSnapshot,
+};
 
 /// A `ModuleLoader` that also has the ability to store V8 code caches.
 pub trait ModuleCache<RT: Runtime>: ModuleLoader<RT> {
     fn put_cached_code(&self, module_metadata: &ModuleMetadata, cached_data: Arc<[u8]>);
     fn get_cached_code(&self, module_metadata: &ModuleMetadata) -> Option<Arc<[u8]>>;
+
+    /// The most recently built deployment snapshot, which may be for an older
+    /// push or older environment variables.
+    fn get_deployment_snapshot(&self) -> Option<Arc<DeploymentSnapshot>>;
+    /// Builds a deployment snapshot for `source_package` in the background.
+    /// Repeated calls with the same arguments are no-ops.
+    fn build_deployment_snapshot(
+        &self,
+        source_package: ParsedDocument<SourcePackage>,
+        environment_variables: BTreeMap<EnvVarName, EnvVarValue>,
+    );
 }
 
 impl<RT: Runtime> dyn ModuleCache<RT> {
@@ -40,5 +65,16 @@ mod test_helpers {
         fn get_cached_code(&self, _module_metadata: &ModuleMetadata) -> Option<Arc<[u8]>> {
             None
         }
+
+        fn get_deployment_snapshot(&self) -> Option<Arc<DeploymentSnapshot>> {
+            None
+        }
+
+        fn build_deployment_snapshot(
+            &self,
+            _source_package: ParsedDocument<SourcePackage>,
+            _environment_variables: BTreeMap<EnvVarName, EnvVarValue>,
+        ) {
+        }
     }
 }
@@ -19,6 +19,9 @@ pub struct ModuleMap {
     modules: Vec<ModuleInfo>,
     by_name: HashMap<ModuleSpecifier, ModuleId>,
     by_handle: HashMap<v8::Global<v8::Module>, ModuleId>,
+    /// Compiled `WebAssembly.Module`s exported by `.wasm` modules, which are
+    /// set as the module's default export when it's evaluated.
+    wasm_modules: HashMap<ModuleId, v8::Global<v8::Value>>,
 }
 
 struct ModuleInfo {
@@ -33,6 +36,7 @@ impl ModuleMap {
             modules: vec![],
             by_name: HashMap::new(),
             by_handle: HashMap::new(),
+            wasm_modules: HashMap::new(),
         }
     }
 
@@ -50,6 +54,14 @@ impl ModuleMap {
         self.by_name.get(specifier).cloned()
     }
 
+    pub fn wasm_module_by_handle(
+        &self,
+        handle: &v8::Global<v8::Module>,
+    ) -> Option<v8::Global<v8::Value>> {
+        let id = self.by_handle.get(handle)?;
+        self.wasm_modules.get(id).cloned()
+    }
+
     pub fn source_map(&self, id: ModuleId) -> Option<&SourceMap> {
         self.modules[id].module_source.source_map.as_ref()
     }
@@ -73,4 +85,16 @@ impl ModuleMap {
 
         id
     }
+
+    pub fn register_wasm(
+        &mut self,
+        name: &ModuleSpecifier,
+        handle: v8::Global<v8::Module>,
+        module_source: Arc<FullModuleSource>,
+        wasm_module: v8::Global<v8::Value>,
+    ) -> ModuleId {
+        let id = self.register(name, handle, module_source);
+        self.wasm_modules.insert(id, wasm_module);
+        id
+    }
 }
@@ -18,6 +18,7 @@ mod text;
 mod time;
 mod validate_args;
 mod validate_returns;
+mod wasm;
 
 use std::{
     collections::BTreeMap,
@@ -135,10 +136,15 @@ use self::{
         op_now,
     },
     validate_args::op_validate_args,
+    wasm::op_wasm_compile,
 };
 pub use self::{
     crypto::CryptoOps,
     random::op_random,
+    wasm::{
+        allow_wasm_code_generation,
+        compile_validated_wasm_module,
+    },
 };
 use crate::{
     environment::{
@@ -402,6 +408,7 @@ pub fn run_op<'b, P: OpProvider<'b>>(
         "getTableMapping" => op_get_table_mapping(provider, args, rv)?,
         "validateArgs" => op_validate_args(provider, args, rv)?,
         "validateReturns" => op_validate_returns(provider, args, rv)?,
+        "wasm/compile" => op_wasm_compile(provider, args.get(1), rv)?,
 
         "crypto/randomUUID" => op_crypto_random_uuid(provider, args, rv)?,
         "crypto/getRandomValues" => op_crypto_get_random_values(provider, args, rv)?,
@@ -0,0 +1,55 @@
+use anyhow::Context;
+use deno_core::v8;
+use model::modules::wasm::validate_wasm_module;
+
+use super::OpProvider;
+
+/// Isolate slot that's only set while we're compiling a WebAssembly module
+/// we've already validated.
+struct AllowWasmCodeGeneration(bool);
+
+/// Passed to `set_allow_wasm_code_generation_callback` so V8 refuses to compile
+/// WebAssembly that didn't go through [`compile_validated_wasm_module`]. This
+/// covers `WebAssembly.compile`, `WebAssembly.instantiate` and
+/// `new WebAssembly.Module` on bytes, which the UDF runtime replaces with
+/// versions that call `wasm/compile`, in case user code gets at the originals.
+pub extern "C" fn allow_wasm_code_generation(
+    context: v8::Local<v8::Context>,
+    _source: v8::Local<v8::String>,
+) -> bool {
+    let scope = &mut unsafe { v8::CallbackScope::new(context) };
+    scope
+        .get_slot::<AllowWasmCodeGeneration>()
+        .is_some_and(|allow| allow.0)
+}
+
+/// Validates and compiles a WebAssembly binary. Like
+/// `v8::WasmModuleObject::compile`, this returns `None` with an exception
+/// thrown on `scope` if V8 can't compile it.
+pub fn compile_validated_wasm_module<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    bytes: &[u8],
+) -> anyhow::Result<Option<v8::Local<'s, v8::WasmModuleObject>>> {
+    validate_wasm_module(bytes)?;
+    scope.set_slot(AllowWasmCodeGeneration(true));
+    let module = v8::WasmModuleObject::compile(scope, bytes);
+    scope.set_slot(AllowWasmCodeGeneration(false));
+    Ok(module)
+}
+
+// NOTE: not using `v8_op` macro because we return a `WebAssembly.Module`.
+pub fn op_wasm_compile<'b, P: OpProvider<'b>>(
+    provider: &mut P,
+    source: v8::Local<v8::Value>,
+    mut rv: v8::ReturnValue,
+) -> anyhow::Result<()> {
+    let source: v8::Local<v8::ArrayBufferView> = source
+        .try_into()
+        .context("wasm/compile expects a Uint8Array")?;
+    let mut bytes = vec![0; source.byte_length()];
+    source.copy_contents(&mut bytes);
+    if let Some(module) = compile_validated_wasm_module(provider.scope(), &bytes)? {
+        rv.set(module.into());
+    }
+    Ok(())
+}
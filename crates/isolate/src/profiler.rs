@@ -0,0 +1,512 @@
+//! A sampling CPU profiler for JavaScript running in an isolate.
+//!
+//! While a profiler is running, a background thread interrupts the isolate
+//! every sampling interval and the interrupt callback records the current
+//! JavaScript stack. Interrupts are only serviced while JavaScript is
+//! executing, so time spent waiting on syscalls doesn't show up in profiles.
+//!
+//! Finished profiles are kept in memory in [`CPU_PROFILES`], separately for
+//! each instance, which admin endpoints use to change what's profiled and to
+//! download profiles.
+use std::{
+    collections::{
+        BTreeMap,
+        VecDeque,
+    },
+    ffi,
+    sync::{
+        atomic::{
+            AtomicBool,
+            AtomicU64,
+            Ordering,
+        },
+        Arc,
+        LazyLock,
+    },
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use common::{
+    components::ComponentId,
+    knobs::{
+        ISOLATE_CPU_PROFILE_FUNCTIONS,
+        ISOLATE_CPU_PROFILE_SAMPLE_RATE,
+    },
+};
+use deno_core::v8;
+use parking_lot::{
+    Mutex,
+    RwLock,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    json,
+    Value as JsonValue,
+};
+
+use crate::termination::IsolateHandle;
+
+/// Deeper frames are dropped from samples.
+const MAX_STACK_DEPTH: usize = 128;
+
+/// Stop recording samples after this many so a long-running action can't
+/// grow a profile without bound.
+const MAX_SAMPLES: usize = 100_000;
+
+/// Only this many of the most recent profiles are kept for each instance.
+const MAX_STORED_PROFILES: usize = 32;
+
+/// Profiles of recent function executions, shared by every isolate in the
+/// process but kept apart by instance. Each instance starts out configured
+/// from `ISOLATE_CPU_PROFILE_FUNCTIONS` and `ISOLATE_CPU_PROFILE_SAMPLE_RATE`.
+pub static CPU_PROFILES: LazyLock<CpuProfileStore> = LazyLock::new(|| {
+    CpuProfileStore::new(CpuProfilingConfig {
+        functions: ISOLATE_CPU_PROFILE_FUNCTIONS.clone(),
+        sample_rate: *ISOLATE_CPU_PROFILE_SAMPLE_RATE,
+    })
+});
+
+/// Which function executions to profile.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuProfilingConfig {
+    /// Function paths (e.g. `messages.js:list`) to profile on every execution.
+    #[serde(default)]
+    pub functions: Vec<String>,
+    /// Profile one out of every this many executions. 0 only profiles
+    /// `functions`.
+    #[serde(default)]
+    pub sample_rate: u64,
+}
+
+pub struct StoredCpuProfile {
+    pub id: u64,
+    pub component: ComponentId,
+    pub udf_path: String,
+    pub profile: CpuProfile,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuProfileSummary {
+    pub id: u64,
+    /// Unset for the root component.
+    pub component_id: Option<String>,
+    pub udf_path: String,
+    pub duration_ms: u64,
+    pub num_samples: usize,
+}
+
+/// One instance's profiling config and profiles.
+struct InstanceCpuProfiles {
+    config: RwLock<CpuProfilingConfig>,
+    executions: AtomicU64,
+    profiles: Mutex<VecDeque<Arc<StoredCpuProfile>>>,
+}
+
+pub struct CpuProfileStore {
+    default_config: CpuProfilingConfig,
+    instances: RwLock<BTreeMap<String, Arc<InstanceCpuProfiles>>>,
+    next_id: AtomicU64,
+}
+
+impl CpuProfileStore {
+    fn new(default_config: CpuProfilingConfig) -> Self {
+        Self {
+            default_config,
+            instances: RwLock::new(BTreeMap::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    fn instance(&self, instance_name: &str) -> Arc<InstanceCpuProfiles> {
+        if let Some(instance) = self.instances.read().get(instance_name) {
+            return instance.clone();
+        }
+        self.instances
+            .write()
+            .entry(instance_name.to_string())
+            .or_insert_with(|| {
+                Arc::new(InstanceCpuProfiles {
+                    config: RwLock::new(self.default_config.clone()),
+                    executions: AtomicU64::new(0),
+                    profiles: Mutex::new(VecDeque::new()),
+                })
+            })
+            .clone()
+    }
+
+    pub fn config(&self, instance_name: &str) -> CpuProfilingConfig {
+        self.instance(instance_name).config.read().clone()
+    }
+
+    /// Changes what's profiled for one instance. Other instances in the
+    /// process are unaffected.
+    pub fn set_config(&self, instance_name: &str, config: CpuProfilingConfig) {
+        *self.instance(instance_name).config.write() = config;
+    }
+
+    /// Whether to profile this execution of `udf_path`. Functions in the
+    /// config match executions in any of the instance's components.
+    pub fn should_profile(&self, instance_name: &str, udf_path: &str) -> bool {
+        let instance = self.instance(instance_name);
+        let config = instance.config.read();
+        if config.functions.iter().any(|f| f == udf_path) {
+            return true;
+        }
+        config.sample_rate > 0
+            && instance.executions.fetch_add(1, Ordering::Relaxed) % config.sample_rate == 0
+    }
+
+    /// Keeps a finished profile, dropping the instance's oldest one if it has
+    /// too many. Serializing profiles is left to whoever downloads them, so
+    /// this is cheap enough to call when a function finishes.
+    pub fn insert(
+        &self,
+        instance_name: &str,
+        component: ComponentId,
+        udf_path: String,
+        profile: CpuProfile,
+    ) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let instance = self.instance(instance_name);
+        let mut profiles = instance.profiles.lock();
+        if profiles.len() >= MAX_STORED_PROFILES {
+            profiles.pop_front();
+        }
+        profiles.push_back(Arc::new(StoredCpuProfile {
+            id,
+            component,
+            udf_path,
+            profile,
+        }));
+    }
+
+    /// Summaries of the instance's stored profiles, most recent first.
+    pub fn list(&self, instance_name: &str) -> Vec<CpuProfileSummary> {
+        self.instance(instance_name)
+            .profiles
+            .lock()
+            .iter()
+            .rev()
+            .map(|stored| CpuProfileSummary {
+                id: stored.id,
+                component_id: stored.component.serialize_to_string(),
+                udf_path: stored.udf_path.clone(),
+                duration_ms: stored.profile.duration.as_millis() as u64,
+                num_samples: stored.profile.samples.len(),
+            })
+            .collect()
+    }
+
+    /// Looks up one of the instance's profiles. Profiles from other instances
+    /// are never returned.
+    pub fn get(&self, instance_name: &str, id: u64) -> Option<Arc<StoredCpuProfile>> {
+        self.instance(instance_name)
+            .profiles
+            .lock()
+            .iter()
+            .find(|stored| stored.id == id)
+            .cloned()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProfileFrame {
+    pub function_name: String,
+    pub url: String,
+    pub script_id: usize,
+    /// 1-based, as reported by V8's stack traces.
+    pub line_number: usize,
+    pub column_number: usize,
+}
+
+impl ProfileFrame {
+    fn label(&self) -> String {
+        let function_name = if self.function_name.is_empty() {
+            "(anonymous)"
+        } else {
+            &self.function_name
+        };
+        // `;` separates frames in the folded format.
+        format!("{function_name} {}:{}", self.url, self.line_number).replace(';', ":")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileSample {
+    /// Outermost frame first.
+    pub stack: Vec<ProfileFrame>,
+    /// Time since the profile started.
+    pub offset: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpuProfile {
+    pub duration: Duration,
+    pub samples: Vec<ProfileSample>,
+}
+
+impl CpuProfile {
+    /// Serializes the profile in the `.cpuprofile` format that Chrome
+    /// DevTools, VS Code and speedscope can open.
+    pub fn to_cpuprofile(&self) -> JsonValue {
+        struct Node<'a> {
+            frame: Option<&'a ProfileFrame>,
+            hit_count: u64,
+            children: Vec<usize>,
+        }
+        let mut nodes = vec![Node {
+            frame: None,
+            hit_count: 0,
+            children: vec![],
+        }];
+        let mut node_ids: BTreeMap<(usize, &ProfileFrame), usize> = BTreeMap::new();
+        let mut sample_ids = Vec::with_capacity(self.samples.len());
+        let mut time_deltas = Vec::with_capacity(self.samples.len());
+        let mut last_offset = Duration::ZERO;
+        for sample in &self.samples {
+            let mut node_id = 0;
+            for frame in &sample.stack {
+                let parent_id = node_id;
+                node_id = *node_ids.entry((parent_id, frame)).or_insert_with(|| {
+                    nodes.push(Node {
+                        frame: Some(frame),
+                        hit_count: 0,
+                        children: vec![],
+                    });
+                    let child_id = nodes.len() - 1;
+                    nodes[parent_id].children.push(child_id);
+                    child_id
+                });
+            }
+            nodes[node_id].hit_count += 1;
+            // Node ids in the format are 1-based.
+            sample_ids.push(node_id + 1);
+            time_deltas.push(sample.offset.saturating_sub(last_offset).as_micros() as u64);
+            last_offset = sample.offset;
+        }
+        let nodes: Vec<_> = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let call_frame = match node.frame {
+                    Some(frame) => json!({
+                        "functionName": frame.function_name,
+                        "scriptId": frame.script_id.to_string(),
+                        "url": frame.url,
+                        // The format's line and column numbers are 0-based.
+                        "lineNumber": frame.line_number as i64 - 1,
+                        "columnNumber": frame.column_number as i64 - 1,
+                    }),
+                    None => json!({
+                        "functionName": "(root)",
+                        "scriptId": "0",
+                        "url": "",
+                        "lineNumber": -1,
+                        "columnNumber": -1,
+                    }),
+                };
+                json!({
+                    "id": i + 1,
+                    "callFrame": call_frame,
+                    "hitCount": node.hit_count,
+                    "children": node.children.iter().map(|c| c + 1).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        json!({
+            "nodes": nodes,
+            "startTime": 0,
+            "endTime": self.duration.as_micros() as u64,
+            "samples": sample_ids,
+            "timeDeltas": time_deltas,
+        })
+    }
+
+    /// Serializes the profile as folded stacks, one line per unique stack
+    /// with its number of samples, for `flamegraph.pl` and `inferno`.
+    pub fn to_folded(&self) -> String {
+        let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+        for sample in &self.samples {
+            let stack = sample
+                .stack
+                .iter()
+                .map(ProfileFrame::label)
+                .collect::<Vec<_>>()
+                .join(";");
+            *counts.entry(stack).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(stack, count)| format!("{stack} {count}\n"))
+            .collect()
+    }
+}
+
+struct SamplerState {
+    start: Instant,
+    stopped: AtomicBool,
+    samples: Mutex<Vec<ProfileSample>>,
+}
+
+pub struct CpuProfiler {
+    state: Arc<SamplerState>,
+}
+
+impl CpuProfiler {
+    pub fn start(handle: IsolateHandle, sampling_interval: Duration) -> anyhow::Result<Self> {
+        let state = Arc::new(SamplerState {
+            start: Instant::now(),
+            stopped: AtomicBool::new(false),
+            samples: Mutex::new(vec![]),
+        });
+        let thread_state = state.clone();
+        // The sampler thread exits on its own within one interval of the
+        // profiler stopping, so it's never joined.
+        thread::Builder::new()
+            .name("cpu-profiler".to_string())
+            .spawn(move || {
+                while !thread_state.stopped.load(Ordering::Relaxed) {
+                    thread::sleep(sampling_interval);
+                    // The callback takes ownership of this reference, so the state stays
+                    // alive even if the interrupt runs after the profiler is stopped.
+                    let data = Arc::into_raw(thread_state.clone()) as *mut ffi::c_void;
+                    if !handle.request_interrupt(sample_stack, data) {
+                        // The isolate has been disposed, so the callback will never run.
+                        drop(unsafe { Arc::from_raw(data as *const SamplerState) });
+                        break;
+                    }
+                }
+            })?;
+        Ok(Self { state })
+    }
+
+    /// Stops sampling. This must be called on the isolate's thread, where
+    /// samples are taken, so no sample can be added after it returns.
+    pub fn stop(self) -> CpuProfile {
+        self.state.stopped.store(true, Ordering::Relaxed);
+        CpuProfile {
+            duration: self.state.start.elapsed(),
+            samples: std::mem::take(&mut *self.state.samples.lock()),
+        }
+    }
+}
+
+impl Drop for CpuProfiler {
+    fn drop(&mut self) {
+        self.state.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+extern "C" fn sample_stack(isolate: &mut v8::Isolate, data: *mut ffi::c_void) {
+    let state = unsafe { Arc::from_raw(data as *const SamplerState) };
+    if state.stopped.load(Ordering::Relaxed) || state.samples.lock().len() >= MAX_SAMPLES {
+        return;
+    }
+    let offset = state.start.elapsed();
+    let scope = &mut unsafe { v8::CallbackScope::new(isolate) };
+    let scope = &mut v8::HandleScope::new(scope);
+    let context = scope.get_current_context();
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let Some(stack_trace) = v8::StackTrace::current_stack_trace(scope, MAX_STACK_DEPTH) else {
+        return;
+    };
+    let mut stack = Vec::with_capacity(stack_trace.get_frame_count());
+    for i in 0..stack_trace.get_frame_count() {
+        let Some(frame) = stack_trace.get_frame(scope, i) else {
+            continue;
+        };
+        stack.push(ProfileFrame {
+            function_name: frame
+                .get_function_name(scope)
+                .map(|name| name.to_rust_string_lossy(scope))
+                .unwrap_or_default(),
+            url: frame
+                .get_script_name(scope)
+                .map(|name| name.to_rust_string_lossy(scope))
+                .unwrap_or_default(),
+            script_id: frame.get_script_id(),
+            line_number: frame.get_line_number(),
+            column_number: frame.get_column(),
+        });
+    }
+    if stack.is_empty() {
+        return;
+    }
+    stack.reverse();
+    state.samples.lock().push(ProfileSample { stack, offset });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::{
+        CpuProfile,
+        ProfileFrame,
+        ProfileSample,
+    };
+
+    fn frame(function_name: &str, line_number: usize) -> ProfileFrame {
+        ProfileFrame {
+            function_name: function_name.to_string(),
+            url: "convex:/user/messages.js".to_string(),
+            script_id: 7,
+            line_number,
+            column_number: 1,
+        }
+    }
+
+    fn profile() -> CpuProfile {
+        let sample = |stack: Vec<ProfileFrame>, offset_ms| ProfileSample {
+            stack,
+            offset: Duration::from_millis(offset_ms),
+        };
+        CpuProfile {
+            duration: Duration::from_millis(4),
+            samples: vec![
+                sample(vec![frame("list", 3), frame("format", 10)], 1),
+                sample(vec![frame("list", 3), frame("format", 10)], 2),
+                sample(vec![frame("list", 3)], 3),
+                sample(vec![frame("", 20)], 4),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_folded() {
+        assert_eq!(
+            profile().to_folded(),
+            "(anonymous) convex:/user/messages.js:20 1\nlist convex:/user/messages.js:3 1\nlist \
+             convex:/user/messages.js:3;format convex:/user/messages.js:10 2\n"
+        );
+    }
+
+    #[test]
+    fn test_cpuprofile() {
+        let cpuprofile = profile().to_cpuprofile();
+        assert_eq!(cpuprofile["samples"], json!([3, 3, 2, 4]));
+        assert_eq!(cpuprofile["timeDeltas"], json!([1000, 1000, 1000, 1000]));
+        assert_eq!(cpuprofile["endTime"], json!(4000));
+        let nodes = cpuprofile["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 4);
+        assert_eq!(nodes[0]["callFrame"]["functionName"], "(root)");
+        assert_eq!(nodes[0]["children"], json!([2, 4]));
+        assert_eq!(nodes[1]["callFrame"]["functionName"], "list");
+        assert_eq!(nodes[1]["callFrame"]["lineNumber"], 2);
+        assert_eq!(nodes[1]["hitCount"], 1);
+        assert_eq!(nodes[1]["children"], json!([3]));
+        assert_eq!(nodes[2]["callFrame"]["functionName"], "format");
+        assert_eq!(nodes[2]["hitCount"], 2);
+    }
+}
@@ -47,6 +47,7 @@ use crate::{
         pump_message_loop,
     },
     isolate::Isolate,
+    memory_limit::MemoryLimitWatcher,
     metrics::{
         context_build_timer,
         log_promise_handler_added_after_reject,
@@ -91,6 +92,9 @@ pub struct RequestState<RT: Runtime, E: IsolateEnvironment<RT>> {
     pub timeout: Timeout<RT>,
     pub permit: Option<ConcurrencyPermit>,
     pub environment: E,
+    /// Set if the environment has a memory limit, and stops checking it when
+    /// the request's state is dropped.
+    pub memory_limit_watcher: Option<MemoryLimitWatcher>,
 
     pub blob_parts: WithHeapSize<BTreeMap<uuid::Uuid, bytes::Bytes>>,
     pub streams: WithHeapSize<BTreeMap<uuid::Uuid, anyhow::Result<ReadableStream>>>,
@@ -0,0 +1,369 @@
+//! Startup snapshots of a deployment's modules.
+//!
+//! Evaluating a deployment's modules dominates cold start latency for large
+//! apps, so once a push is live we evaluate its modules once in a snapshot
+//! creator isolate and serialize the resulting heap. Isolates created from
+//! the snapshot can then create a context with every module already evaluated
+//! instead of compiling and evaluating them on each request.
+//!
+//! Module evaluation at import time can only read environment variables.
+//! Any other op or syscall fails the snapshot, and the deployment keeps
+//! evaluating its modules on every request. Since the values of environment
+//! variables are baked into the heap, the snapshot records the ones it read
+//! and is only used while they're unchanged.
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    sync::Arc,
+};
+
+use anyhow::Context as _;
+use deno_core::{
+    v8::{
+        self,
+        MapFnTo,
+    },
+    ModuleSpecifier,
+};
+use model::{
+    environment_variables::types::{
+        EnvVarName,
+        EnvVarValue,
+    },
+    modules::module_versions::FullModuleSource,
+    source_packages::types::SourcePackageId,
+};
+
+use crate::{
+    environment::helpers::module_loader::is_wasm_module_specifier,
+    helpers,
+    module_map::ModuleMap,
+    ops::allow_wasm_code_generation,
+    strings,
+    udf_runtime::create_runtime_context,
+};
+
+/// The index of the context with the deployment's modules, to be passed to
+/// `v8::Context::from_snapshot`.
+pub const DEPLOYMENT_SNAPSHOT_CONTEXT_INDEX: usize = 0;
+
+pub struct DeploymentSnapshot {
+    pub source_package_id: SourcePackageId,
+    /// The environment variables read while evaluating the modules and the
+    /// values they had.
+    pub environment_variables: BTreeMap<EnvVarName, Option<EnvVarValue>>,
+    modules: Vec<SnapshotModule>,
+    blob: Arc<[u8]>,
+}
+
+struct SnapshotModule {
+    specifier: ModuleSpecifier,
+    source: Arc<FullModuleSource>,
+    context_data_index: usize,
+}
+
+impl DeploymentSnapshot {
+    pub fn blob(&self) -> &[u8] {
+        &self.blob
+    }
+}
+
+/// Functions referenced from deployment snapshots. Isolates restored from a
+/// snapshot must be created with the same list.
+pub(crate) fn external_references() -> Cow<'static, [v8::ExternalReference]> {
+    Cow::Owned(vec![
+        v8::ExternalReference {
+            function: snapshot_op.map_fn_to(),
+        },
+        v8::ExternalReference {
+            function: unsupported_syscall.map_fn_to(),
+        },
+    ])
+}
+
+/// Isolate slot for the state of a snapshot being created.
+struct SnapshotEnvironment {
+    environment_variables: BTreeMap<EnvVarName, EnvVarValue>,
+    reads: BTreeMap<EnvVarName, Option<EnvVarValue>>,
+    error: Option<anyhow::Error>,
+}
+
+/// Evaluates `modules` and serializes the resulting heap. Fails if any module
+/// uses something other than environment variables at import time, uses a
+/// top-level await, or throws, or if any module is WebAssembly.
+pub fn create_deployment_snapshot(
+    source_package_id: SourcePackageId,
+    modules: Vec<(ModuleSpecifier, Arc<FullModuleSource>)>,
+    environment_variables: BTreeMap<EnvVarName, EnvVarValue>,
+) -> anyhow::Result<DeploymentSnapshot> {
+    // Compiled WebAssembly modules can't be serialized into snapshots.
+    if let Some((specifier, _)) = modules
+        .iter()
+        .find(|(specifier, _)| is_wasm_module_specifier(specifier))
+    {
+        anyhow::bail!("Can't snapshot WebAssembly module {specifier}");
+    }
+    let mut isolate = v8::Isolate::snapshot_creator(Some(external_references()), None);
+    isolate.set_microtasks_policy(v8::MicrotasksPolicy::Explicit);
+    isolate.set_allow_wasm_code_generation_callback(allow_wasm_code_generation);
+    assert!(isolate.set_slot(SnapshotEnvironment {
+        environment_variables,
+        reads: BTreeMap::new(),
+        error: None,
+    }));
+    assert!(isolate.set_slot(ModuleMap::new()));
+
+    let result = evaluate_modules(&mut isolate, &modules);
+    let environment = isolate
+        .remove_slot::<SnapshotEnvironment>()
+        .context("SnapshotEnvironment disappeared?")?;
+    // The module map holds global handles, which must be released before
+    // creating the blob.
+    isolate
+        .remove_slot::<ModuleMap>()
+        .context("ModuleMap disappeared?")?;
+    if let Some(e) = environment.error {
+        return Err(e);
+    }
+    let context_data_indexes = result?;
+
+    let blob = isolate
+        .create_blob(v8::FunctionCodeHandling::Keep)
+        .context("Failed to create snapshot")?;
+    let modules = modules
+        .into_iter()
+        .zip(context_data_indexes)
+        .map(|((specifier, source), context_data_index)| SnapshotModule {
+            specifier,
+            source,
+            context_data_index,
+        })
+        .collect();
+    Ok(DeploymentSnapshot {
+        source_package_id,
+        environment_variables: environment.reads,
+        modules,
+        blob: blob.to_vec().into(),
+    })
+}
+
+/// Returns the context data index of each module.
+fn evaluate_modules(
+    isolate: &mut v8::OwnedIsolate,
+    modules: &[(ModuleSpecifier, Arc<FullModuleSource>)],
+) -> anyhow::Result<Vec<usize>> {
+    let mut scope = v8::HandleScope::new(isolate);
+
+    // New contexts in restored isolates only have the UDF runtime, like
+    // isolates created from the base snapshot.
+    let default_context = create_runtime_context(&mut scope)?;
+    scope.set_default_context(default_context);
+
+    let context = create_runtime_context(&mut scope)?;
+    let handles = {
+        let mut scope = v8::ContextScope::new(&mut scope, context);
+        let convex = install_snapshot_ops(&mut scope)?;
+
+        let mut handles = Vec::with_capacity(modules.len());
+        for (specifier, source) in modules {
+            let name_str = v8::String::new(&mut scope, specifier.as_str())
+                .context("Failed to create name string")?;
+            // Unlike in `ExecutionScope`, copy the source onto the V8 heap
+            // since external strings can't be serialized.
+            let source_str = v8::String::new(&mut scope, &source.source)
+                .context("Failed to create source string")?;
+            let origin = helpers::module_origin(&mut scope, name_str);
+            let mut v8_source = v8::script_compiler::Source::new(source_str, Some(&origin));
+            let module = v8::script_compiler::compile_module(&mut scope, &mut v8_source)
+                .with_context(|| format!("Failed to compile {specifier}"))?;
+            let handle = v8::Global::new(&mut scope, module);
+            scope
+                .get_slot_mut::<ModuleMap>()
+                .context("ModuleMap disappeared?")?
+                .register(specifier, handle.clone(), source.clone());
+            handles.push(handle);
+        }
+
+        for ((specifier, _), handle) in modules.iter().zip(&handles) {
+            let module = v8::Local::new(&mut scope, handle);
+            if module.get_status() == v8::ModuleStatus::Evaluated {
+                continue;
+            }
+            anyhow::ensure!(
+                module.instantiate_module(&mut scope, module_resolve_callback) == Some(true),
+                "Failed to instantiate {specifier}"
+            );
+            let result = module
+                .evaluate(&mut scope)
+                .with_context(|| format!("Failed to evaluate {specifier}"))?;
+            let promise = v8::Local::<v8::Promise>::try_from(result)
+                .context("Module evaluation did not return a promise")?;
+            // Like `ExecutionScope`, a pending promise means the module used a
+            // top-level await.
+            anyhow::ensure!(
+                promise.state() == v8::PromiseState::Fulfilled,
+                "Evaluating {specifier} failed with promise state {:?}",
+                promise.state()
+            );
+        }
+
+        // Restored contexts get their ops from `RequestScope`.
+        for key in [
+            strings::op.create(&mut scope)?,
+            strings::asyncOp.create(&mut scope)?,
+            strings::syscall.create(&mut scope)?,
+            strings::asyncSyscall.create(&mut scope)?,
+        ] {
+            convex.delete(&mut scope, key.into());
+        }
+        handles
+    };
+
+    anyhow::ensure!(scope.add_context(context) == DEPLOYMENT_SNAPSHOT_CONTEXT_INDEX);
+    let mut context_data_indexes = Vec::with_capacity(handles.len());
+    for handle in handles {
+        let module = v8::Local::new(&mut scope, handle);
+        context_data_indexes.push(scope.add_context_data(context, module));
+    }
+    Ok(context_data_indexes)
+}
+
+fn install_snapshot_ops<'s>(
+    scope: &mut v8::HandleScope<'s>,
+) -> anyhow::Result<v8::Local<'s, v8::Object>> {
+    let global = scope.get_current_context().global(scope);
+    let convex_key = strings::Convex.create(scope)?;
+    let convex: v8::Local<v8::Object> = global
+        .get(scope, convex_key.into())
+        .context("Missing global.Convex")?
+        .try_into()
+        .context("Wrong type of global.Convex")?;
+
+    let op = v8::Function::new(scope, snapshot_op).context("Failed to create op")?;
+    let op_key = strings::op.create(scope)?;
+    convex.set(scope, op_key.into(), op.into());
+    for key in [
+        strings::asyncOp.create(scope)?,
+        strings::syscall.create(scope)?,
+        strings::asyncSyscall.create(scope)?,
+    ] {
+        let unsupported =
+            v8::Function::new(scope, unsupported_syscall).context("Failed to create syscall")?;
+        convex.set(scope, key.into(), unsupported.into());
+    }
+    Ok(convex)
+}
+
+fn snapshot_op(
+    scope: &mut v8::HandleScope,
+    args: v8::FunctionCallbackArguments,
+    mut rv: v8::ReturnValue,
+) {
+    match get_environment_variable(scope, &args) {
+        Ok(Some(value)) => match v8::String::new(scope, &value.to_string()) {
+            Some(value) => rv.set(value.into()),
+            None => abort_snapshot(scope, anyhow::anyhow!("Failed to create string")),
+        },
+        Ok(None) => rv.set_null(),
+        Err(e) => abort_snapshot(scope, e),
+    }
+}
+
+fn get_environment_variable(
+    scope: &mut v8::HandleScope,
+    args: &v8::FunctionCallbackArguments,
+) -> anyhow::Result<Option<EnvVarValue>> {
+    let op_name: v8::Local<v8::String> = args.get(0).try_into()?;
+    let op_name = helpers::to_rust_string(scope, &op_name)?;
+    anyhow::ensure!(
+        op_name == "environmentVariables/get",
+        "Unsupported op {op_name} while creating a snapshot"
+    );
+    let name: v8::Local<v8::String> = args.get(1).try_into()?;
+    let name: EnvVarName = helpers::to_rust_string(scope, &name)?.parse()?;
+    let environment = scope
+        .get_slot_mut::<SnapshotEnvironment>()
+        .context("SnapshotEnvironment disappeared?")?;
+    let value = environment.environment_variables.get(&name).cloned();
+    environment.reads.insert(name, value.clone());
+    Ok(value)
+}
+
+fn unsupported_syscall(
+    scope: &mut v8::HandleScope,
+    _args: v8::FunctionCallbackArguments,
+    _rv: v8::ReturnValue,
+) {
+    abort_snapshot(
+        scope,
+        anyhow::anyhow!("Syscalls are unsupported while creating a snapshot"),
+    );
+}
+
+/// Terminates execution so user code can't catch the failure.
+fn abort_snapshot(scope: &mut v8::HandleScope, error: anyhow::Error) {
+    if let Some(environment) = scope.get_slot_mut::<SnapshotEnvironment>()
+        && environment.error.is_none()
+    {
+        environment.error = Some(error);
+    }
+    scope.terminate_execution();
+}
+
+fn module_resolve_callback<'c>(
+    context: v8::Local<'c, v8::Context>,
+    specifier: v8::Local<'c, v8::String>,
+    _import_assertions: v8::Local<'c, v8::FixedArray>,
+    referrer: v8::Local<'c, v8::Module>,
+) -> Option<v8::Local<'c, v8::Module>> {
+    let scope = &mut unsafe { v8::CallbackScope::new(context) };
+    match resolve_module(scope, referrer, specifier) {
+        Ok(m) => Some(m),
+        Err(e) => {
+            helpers::throw_type_error(scope, format!("{:?}", e));
+            None
+        },
+    }
+}
+
+fn resolve_module<'c>(
+    scope: &mut v8::CallbackScope<'c>,
+    referrer: v8::Local<'c, v8::Module>,
+    specifier: v8::Local<'c, v8::String>,
+) -> anyhow::Result<v8::Local<'c, v8::Module>> {
+    let referrer_global = v8::Global::new(scope, referrer);
+    let specifier_str = helpers::to_rust_string(scope, &specifier)?;
+    let module_map = scope
+        .get_slot::<ModuleMap>()
+        .context("ModuleMap disappeared?")?;
+    let referrer_name = module_map
+        .name_by_handle(&referrer_global)
+        .context("Couldn't find referring module")?
+        .to_string();
+    let resolved_specifier = deno_core::resolve_import(&specifier_str, &referrer_name)?;
+    let handle = module_map
+        .get_by_name(&resolved_specifier)
+        .and_then(|id| module_map.handle_by_id(id))
+        .with_context(|| format!("Couldn't find {specifier_str} in {referrer_name}"))?;
+    Ok(v8::Local::new(scope, handle))
+}
+
+/// Registers the modules evaluated in the snapshot with the current context's
+/// module map. The current context must have been created from
+/// `DEPLOYMENT_SNAPSHOT_CONTEXT_INDEX`.
+pub(crate) fn restore_modules(
+    scope: &mut v8::HandleScope,
+    snapshot: &DeploymentSnapshot,
+) -> anyhow::Result<()> {
+    for module in &snapshot.modules {
+        let handle = scope
+            .get_context_data_from_snapshot_once::<v8::Module>(module.context_data_index)
+            .with_context(|| format!("Missing {} in snapshot", module.specifier))?;
+        let handle = v8::Global::new(scope, handle);
+        scope
+            .get_slot_mut::<ModuleMap>()
+            .context("ModuleMap disappeared?")?
+            .register(&module.specifier, handle, module.source.clone());
+    }
+    Ok(())
+}
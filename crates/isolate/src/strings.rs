@@ -66,6 +66,7 @@ declare_strings!(
     empty => "",
     export,
     exportArgs,
+    exportLimits,
     exportReturns,
     import_meta_unsupported => "import.meta unsupported",
     internal_error => "Convex encountered an internal error",
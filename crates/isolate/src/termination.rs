@@ -27,6 +27,8 @@ pub enum TerminationReason {
     UserTimeout(Duration),
     SystemTimeout(Duration),
     OutOfMemory,
+    /// The function went over the memory limit in its config, in bytes.
+    MemoryLimit(usize),
 }
 
 impl TerminationReason {
@@ -38,6 +40,7 @@ impl TerminationReason {
             Self::UserTimeout(d) => Self::UserTimeout(*d),
             Self::SystemTimeout(d) => Self::SystemTimeout(*d),
             Self::OutOfMemory => Self::OutOfMemory,
+            Self::MemoryLimit(limit) => Self::MemoryLimit(*limit),
         }
     }
 
@@ -48,7 +51,7 @@ impl TerminationReason {
             Self::UnhandledPromiseRejection(_) => IsolateNotClean::UnhandledPromiseRejection,
             Self::UserTimeout(_) => IsolateNotClean::UserTimeout,
             Self::SystemTimeout(_) => IsolateNotClean::SystemTimeout,
-            Self::OutOfMemory => IsolateNotClean::OutOfMemory,
+            Self::OutOfMemory | Self::MemoryLimit(_) => IsolateNotClean::OutOfMemory,
         }
     }
 }
@@ -88,6 +91,16 @@ impl IsolateHandle {
         inner.request_stream_bytes = Some(request_stream_bytes)
     }
 
+    /// Asks V8 to call `callback` on the isolate's thread the next time it's
+    /// running JavaScript. Returns false if the isolate has been disposed.
+    pub fn request_interrupt(
+        &self,
+        callback: v8::InterruptCallback,
+        data: *mut std::ffi::c_void,
+    ) -> bool {
+        self.v8_handle.request_interrupt(callback, data)
+    }
+
     pub fn terminate(&self, reason: TerminationReason) {
         self.v8_handle.terminate_execution();
         let mut inner = self.inner.lock();
@@ -177,6 +190,9 @@ impl IsolateHandle {
                             };
                         Ok(Err(JsError::from_message(error_message)))
                     },
+                    TerminationReason::MemoryLimit(limit) => Ok(Err(JsError::from_message(
+                        format!("{}", MemoryLimitError(limit)),
+                    ))),
                     TerminationReason::UncatchableDeveloperError(e) => Ok(Err(e)),
                 }
             },
@@ -220,3 +236,11 @@ pub struct OutOfMemoryError;
 #[derive(Error, Debug)]
 #[error("Function execution timed out (maximum duration: {0:?})")]
 pub struct UserTimeoutError(Duration);
+
+#[derive(Error, Debug)]
+#[error(
+    "JavaScript execution ran out of memory (maximum memory usage: {} MB, set in the function's \
+     `limits` config)",
+    .0 >> 20
+)]
+pub struct MemoryLimitError(usize);
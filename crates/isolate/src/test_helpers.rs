@@ -54,6 +54,10 @@ use common::{
     version::Version,
 };
 use database::{
+    hybrid_search::{
+        HybridSearch,
+        HybridSearchResult,
+    },
     test_helpers::{
         DbFixtures,
         DbFixturesArgs,
@@ -99,7 +103,20 @@ use model::{
         FileStorageId,
     },
     modules::module_versions::ModuleSource,
-    scheduled_jobs::VirtualSchedulerModel,
+    rate_limits::{
+        types::{
+            RateLimitRequest,
+            RateLimitResult,
+        },
+        RateLimitModel,
+    },
+    scheduled_jobs::{
+        types::{
+            ScheduledJobQueue,
+            ScheduledJobRetryPolicy,
+        },
+        VirtualSchedulerModel,
+    },
     source_packages::{
         types::SourcePackage,
         upload_download::upload_package,
@@ -136,6 +153,7 @@ use udf::{
         ValidatedPathAndArgs,
     },
     ActionOutcome,
+    ExecutionRecording,
     FunctionOutcome,
     FunctionResult,
     HttpActionRequest,
@@ -145,7 +163,10 @@ use udf::{
     HttpActionResult,
     UdfOutcome,
 };
-use usage_tracking::FunctionUsageStats;
+use usage_tracking::{
+    FunctionUsageStats,
+    FunctionUsageTracker,
+};
 use value::{
     id_v6::DeveloperDocumentId,
     ConvexArray,
@@ -155,7 +176,9 @@ use value::{
 };
 use vector::{
     PublicVectorSearchQueryResult,
+    SparseVectorSearch,
     VectorSearch,
+    VectorSearchPage,
 };
 
 use crate::{
@@ -163,6 +186,7 @@ use crate::{
     client::{
         initialize_v8,
         EnvironmentData,
+        ExecutionRecordingMode,
         IsolateWorker,
         Request,
         RequestType,
@@ -534,6 +558,17 @@ impl<RT: Runtime, P: Persistence> UdfTest<RT, P> {
         udf_path: &str,
         args: Vec<ConvexValue>,
         identity: Identity,
+    ) -> anyhow::Result<UdfOutcome> {
+        self.raw_mutation_with_recording(udf_path, args, identity, ExecutionRecordingMode::Disabled)
+            .await
+    }
+
+    pub async fn raw_mutation_with_recording(
+        &self,
+        udf_path: &str,
+        args: Vec<ConvexValue>,
+        identity: Identity,
+        recording: ExecutionRecordingMode,
     ) -> anyhow::Result<UdfOutcome> {
         // Bump time before running a mutation so we have a higher creation time than
         // previous mutations.
@@ -597,7 +632,7 @@ impl<RT: Runtime, P: Persistence> UdfTest<RT, P> {
         } else {
             let (tx, outcome) = self
                 .isolate
-                .execute_udf(
+                .execute_udf_with_recording(
                     UdfType::Mutation,
                     path_and_args,
                     tx,
@@ -607,6 +642,7 @@ impl<RT: Runtime, P: Persistence> UdfTest<RT, P> {
                     0,
                     DEV_INSTANCE_NAME.to_string(),
                     None,
+                    recording,
                 )
                 .await?;
             let FunctionOutcome::Mutation(outcome) = outcome else {
@@ -620,6 +656,32 @@ impl<RT: Runtime, P: Persistence> UdfTest<RT, P> {
         }
     }
 
+    /// Replay a recorded query or mutation at the recording's snapshot.
+    pub async fn replay(&self, recording: ExecutionRecording) -> anyhow::Result<UdfOutcome> {
+        let tx = self
+            .database
+            .begin_with_ts(
+                Identity::system(),
+                recording.snapshot_ts,
+                FunctionUsageTracker::new(),
+            )
+            .await?;
+        let outcome = self
+            .isolate
+            .replay_udf(
+                recording,
+                tx,
+                ExecutionContext::new_for_test(),
+                self.environment_data.clone(),
+                DEV_INSTANCE_NAME.to_string(),
+            )
+            .await?;
+        match outcome {
+            FunctionOutcome::Query(outcome) | FunctionOutcome::Mutation(outcome) => Ok(outcome),
+            _ => anyhow::bail!("Replayed a function that isn't a query or mutation"),
+        }
+    }
+
     pub async fn query(&self, udf_path: &str, args: ConvexObject) -> anyhow::Result<ConvexValue> {
         self.query_with_identity(udf_path, args, Identity::system())
             .await
@@ -1382,6 +1444,8 @@ impl<RT: Runtime, P: Persistence> ActionCallbacks for UdfTest<RT, P> {
         udf_args: Vec<JsonValue>,
         scheduled_ts: UnixTimestamp,
         context: ExecutionContext,
+        retry_policy: Option<ScheduledJobRetryPolicy>,
+        queue: Option<ScheduledJobQueue>,
     ) -> anyhow::Result<DeveloperDocumentId> {
         let mut tx: database::Transaction<RT> = self.database.begin(identity).await?;
         let (scheduled_path, udf_args) = validate_schedule_args(
@@ -1396,7 +1460,14 @@ impl<RT: Runtime, P: Persistence> ActionCallbacks for UdfTest<RT, P> {
         .await?;
 
         let virtual_id = VirtualSchedulerModel::new(&mut tx, scheduling_component.into())
-            .schedule(scheduled_path, udf_args, scheduled_ts, context)
+            .schedule(
+                scheduled_path,
+                udf_args,
+                scheduled_ts,
+                context,
+                retry_policy,
+                queue,
+            )
             .await?;
         self.database.commit(tx).await?;
 
@@ -1416,13 +1487,60 @@ impl<RT: Runtime, P: Persistence> ActionCallbacks for UdfTest<RT, P> {
         Ok(())
     }
 
+    async fn rate_limit(
+        &self,
+        identity: Identity,
+        component: ComponentId,
+        request: RateLimitRequest,
+    ) -> anyhow::Result<RateLimitResult> {
+        let mut tx = self.database.begin(identity).await?;
+        let result = RateLimitModel::new(&mut tx, component.into())
+            .limit(request)
+            .await?;
+        self.database.commit(tx).await?;
+        Ok(result)
+    }
+
+    async fn reset_rate_limit(
+        &self,
+        identity: Identity,
+        component: ComponentId,
+        name: String,
+        key: Option<String>,
+    ) -> anyhow::Result<()> {
+        let mut tx = self.database.begin(identity).await?;
+        RateLimitModel::new(&mut tx, component.into())
+            .reset(&name, key.as_deref())
+            .await?;
+        self.database.commit(tx).await?;
+        Ok(())
+    }
+
     async fn vector_search(
         &self,
         identity: Identity,
         query: JsonValue,
-    ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)> {
+    ) -> anyhow::Result<(VectorSearchPage, FunctionUsageStats)> {
         let query = VectorSearch::try_from(query)?;
-        self.database.vector_search(identity, query).await
+        self.database.vector_search_page(identity, query).await
+    }
+
+    async fn hybrid_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<HybridSearchResult>, FunctionUsageStats)> {
+        let query = HybridSearch::try_from(query)?;
+        self.database.hybrid_search(identity, query).await
+    }
+
+    async fn sparse_vector_search(
+        &self,
+        identity: Identity,
+        query: JsonValue,
+    ) -> anyhow::Result<(Vec<PublicVectorSearchQueryResult>, FunctionUsageStats)> {
+        let query = SparseVectorSearch::try_from(query)?;
+        self.database.sparse_vector_search(identity, query).await
     }
 
     async fn lookup_function_handle(
@@ -1464,6 +1582,7 @@ pub async fn bogus_udf_request<RT: Runtime>(
         transaction: tx,
         journal: QueryJournal::new(),
         context: ExecutionContext::new_for_test(),
+        recording: ExecutionRecordingMode::Disabled,
     };
     let inner = RequestType::Udf {
         request,
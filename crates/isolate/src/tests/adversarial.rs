@@ -136,6 +136,21 @@ async fn test_read_too_many_documents(rt: TestRuntime) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[convex_macro::test_runtime]
+async fn test_read_too_many_documents_function_limit(rt: TestRuntime) -> anyhow::Result<()> {
+    let t = UdfTest::default(rt).await?;
+    t.mutation("adversarial:populate", assert_obj!()).await?;
+    let e = t
+        .query_js_error("adversarial:queryWithReadLimit", assert_obj!())
+        .await?;
+    assert_contains(
+        &e,
+        "Too many documents read in a single function execution (limit: 10, set in the function's \
+         `limits` config)",
+    );
+    Ok(())
+}
+
 #[convex_macro::test_runtime]
 async fn test_read_many_documents(rt: TestRuntime) -> anyhow::Result<()> {
     let t = UdfTest::default(rt).await?;
@@ -479,6 +494,16 @@ async fn test_oom(rt: TestRuntime) -> anyhow::Result<()> {
     .await
 }
 
+#[convex_macro::test_runtime]
+async fn test_memory_limit_without_yielding(rt: TestRuntime) -> anyhow::Result<()> {
+    let t = UdfTest::default(rt).await?;
+    let e = t
+        .query_js_error("adversarial:memoryLimitWithoutYielding", assert_obj!())
+        .await?;
+    assert_contains(&e, "maximum memory usage: 16 MB");
+    Ok(())
+}
+
 #[convex_macro::test_runtime]
 async fn test_writes_too_many(rt: TestRuntime) -> anyhow::Result<()> {
     let t = UdfTest::default(rt).await?;
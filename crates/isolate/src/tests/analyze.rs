@@ -20,6 +20,7 @@ use maplit::btreemap;
 use model::{
     config::types::ModuleConfig,
     cron_jobs::types::{
+        CronCatchUpPolicy,
         CronIdentifier,
         CronSchedule,
         CronSpec,
@@ -166,20 +167,37 @@ async fn test_analyze_module(rt: TestRuntime) -> anyhow::Result<()> {
     let args: ConvexArray = vec![ConvexValue::Object(arg)].try_into()?;
     assert_eq!(
         module.cron_specs,
-        Some(btreemap!(
-        CronIdentifier::from_str("weekly re-engagement email")? => CronSpec {
-            udf_path: "crons.js:addOne".parse()?,
-            udf_args: args.clone(),
-            cron_schedule: CronSchedule::Weekly { day_of_week: 2, hour_utc: 17, minute_utc: 30 }},
-        CronIdentifier::from_str("add one every hour")? => CronSpec {
-            udf_path: "crons.js:addOne".parse()?,
-            udf_args: args.clone(),
-            cron_schedule: CronSchedule::Interval{ seconds: 3600 * 24 * 7 } },
-        CronIdentifier::from_str("clear presence data")? => CronSpec {
-            udf_path: "crons.js:addOne".parse()?,
-            udf_args: args,
-            cron_schedule: CronSchedule::Interval{ seconds: 300} },
-        ).into()),
+        Some(
+            btreemap!(
+            CronIdentifier::from_str("weekly re-engagement email")? => CronSpec {
+                udf_path: "crons.js:addOne".parse()?,
+                udf_args: args.clone(),
+                cron_schedule: CronSchedule::Weekly {
+                    day_of_week: 2,
+                    hour_utc: 17,
+                    minute_utc: 30,
+                    timezone: None,
+                },
+                catch_up: CronCatchUpPolicy::RunOnce,
+                jitter_seconds: None,
+            },
+            CronIdentifier::from_str("add one every hour")? => CronSpec {
+                udf_path: "crons.js:addOne".parse()?,
+                udf_args: args.clone(),
+                cron_schedule: CronSchedule::Interval{ seconds: 3600 * 24 * 7 },
+                catch_up: CronCatchUpPolicy::RunOnce,
+                jitter_seconds: None,
+            },
+            CronIdentifier::from_str("clear presence data")? => CronSpec {
+                udf_path: "crons.js:addOne".parse()?,
+                udf_args: args,
+                cron_schedule: CronSchedule::Interval{ seconds: 300},
+                catch_up: CronCatchUpPolicy::RunOnce,
+                jitter_seconds: None,
+            },
+            )
+            .into()
+        ),
     );
 
     Ok(())
@@ -18,6 +18,10 @@ use axum::{
 use common::{
     assert_obj,
     http::{
+        fetch::{
+            EgressPolicy,
+            EgressRule,
+        },
         ConvexHttpService,
         NoopRouteMapper,
     },
@@ -36,6 +40,7 @@ use http::{
 use http_body_util::BodyExt;
 use itertools::Itertools;
 use keybroker::Identity;
+use model::egress_policy::EgressPolicyModel;
 use must_let::must_let;
 use runtime::{
     prod::ProdRuntime,
@@ -380,6 +385,66 @@ async fn test_fetch_abort(rt: ProdRuntime) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[convex_macro::prod_rt_test]
+async fn test_fetch_egress_policy(rt: ProdRuntime) -> anyhow::Result<()> {
+    let router = Router::new().route("/hello", get(|| async { "hello" }));
+    let _router = rt.spawn("test_router", serve(router, 4549));
+    let t = UdfTest::default(rt).await?;
+
+    // Without a policy, requests aren't restricted or logged.
+    let (result, log_lines) = fetch_url(&t, "http://127.0.0.1:4549/hello").await?;
+    assert_eq!(result, "200 hello");
+    assert!(log_lines.is_empty());
+
+    let mut tx = t.database.begin(Identity::system()).await?;
+    EgressPolicyModel::new(&mut tx)
+        .set(EgressPolicy {
+            allow: vec![EgressRule {
+                destination: "127.0.0.0/8".parse()?,
+                ports: [4549].into(),
+            }],
+            deny: vec![],
+            log_requests: true,
+        })
+        .await?;
+    t.database.commit(tx).await?;
+
+    let (result, log_lines) = fetch_url(&t, "http://127.0.0.1:4549/hello").await?;
+    assert_eq!(result, "200 hello");
+    assert_eq!(log_lines.len(), 1);
+    assert_contains(&log_lines[0], "[INFO] GET http://127.0.0.1:4549 200");
+
+    let (result, log_lines) = fetch_url(&t, "http://127.0.0.1:4545/hello").await?;
+    assert_contains(
+        &result,
+        "Request to http://127.0.0.1:4545 is not allowed by this deployment's egress policy",
+    );
+    assert_eq!(log_lines.len(), 1);
+    assert_contains(
+        &log_lines[0],
+        "[WARN] GET http://127.0.0.1:4545 denied by egress policy",
+    );
+    Ok(())
+}
+
+async fn fetch_url(
+    t: &UdfTest<ProdRuntime, TestPersistence>,
+    url: &str,
+) -> anyhow::Result<(String, Vec<String>)> {
+    must_let!(let (ConvexValue::String(result), _outcome, log_lines) = t
+        .action_outcome_and_log_lines(
+            "fetch:fetchUrl",
+            assert_obj!("url" => url),
+            Identity::system(),
+        )
+        .await?);
+    let log_lines = log_lines
+        .into_iter()
+        .map(|l| l.to_pretty_string_test_only())
+        .collect_vec();
+    Ok((String::from(result), log_lines))
+}
+
 async fn trigger_abort(t: &UdfTest<ProdRuntime, TestPersistence>) -> anyhow::Result<()> {
     let mut tx = t.database.begin(Identity::system()).await?;
     // NOTE: you can't run a mutation in prod_rt_test, because time is paused but
@@ -19,12 +19,15 @@ mod internal;
 mod js_builtins;
 mod logging;
 mod module_loader;
+mod profiler;
 mod query;
+mod replay;
 mod scheduler;
 mod schema;
 mod search;
 mod shapes;
 mod size_errors;
+mod snapshot;
 mod source_maps;
 mod storage;
 mod system_udfs;
@@ -32,3 +35,4 @@ mod unicode;
 mod user_error;
 mod values;
 mod vector_search;
+mod wasm;
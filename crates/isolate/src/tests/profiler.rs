@@ -0,0 +1,46 @@
+use common::assert_obj;
+use keybroker::DEV_INSTANCE_NAME;
+use runtime::testing::TestRuntime;
+
+use crate::{
+    profiler::{
+        CpuProfilingConfig,
+        CPU_PROFILES,
+    },
+    test_helpers::UdfTest,
+};
+
+#[convex_macro::test_runtime]
+async fn test_profile_busy_function(rt: TestRuntime) -> anyhow::Result<()> {
+    let t = UdfTest::default(rt).await?;
+    CPU_PROFILES.set_config(
+        DEV_INSTANCE_NAME,
+        CpuProfilingConfig {
+            functions: vec!["profiling.js:busy".to_string()],
+            sample_rate: 0,
+        },
+    );
+    t.query("profiling:busy", assert_obj!()).await?;
+
+    let summary = CPU_PROFILES
+        .list(DEV_INSTANCE_NAME)
+        .into_iter()
+        .find(|summary| summary.udf_path == "profiling.js:busy")
+        .expect("Busy function wasn't profiled");
+    assert!(summary.num_samples > 0);
+    let stored = CPU_PROFILES.get(DEV_INSTANCE_NAME, summary.id).unwrap();
+    assert!(stored.profile.samples.iter().any(|sample| sample
+        .stack
+        .iter()
+        .any(|frame| frame.function_name == "spin")));
+    assert!(stored.profile.to_folded().contains("spin"));
+
+    // Other instances in the process can't see the profile or its config.
+    assert!(CPU_PROFILES.get("other-instance", summary.id).is_none());
+    assert!(CPU_PROFILES.list("other-instance").is_empty());
+    assert_ne!(
+        CPU_PROFILES.config("other-instance"),
+        CPU_PROFILES.config(DEV_INSTANCE_NAME)
+    );
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use std::{
+    path::PathBuf,
+    time::Duration,
+};
+
+use common::assert_obj;
+use errors::ErrorMetadataAnyhowExt;
+use keybroker::Identity;
+use runtime::testing::TestRuntime;
+use tempfile::TempDir;
+use udf::{
+    ExecutionRecording,
+    UdfOutcome,
+};
+use value::sha256::Sha256;
+
+use crate::{
+    client::ExecutionRecordingMode,
+    test_helpers::{
+        UdfTest,
+        UdfTestType,
+    },
+};
+
+/// Recordings are written in the background, so wait for one to show up.
+async fn wait_for_recording(dir: &TempDir) -> anyhow::Result<PathBuf> {
+    // The test runtime's clock is paused, so bound the wait in real time.
+    let deadline = std::time::Instant::now() + Duration::from_secs(10);
+    loop {
+        let mut entries = tokio::fs::read_dir(dir.path()).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == "recording")
+            {
+                return Ok(entry.path());
+            }
+        }
+        anyhow::ensure!(
+            std::time::Instant::now() < deadline,
+            "Recording was never written"
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+async fn record_insert(
+    t: &UdfTestType,
+    dir: &TempDir,
+) -> anyhow::Result<(UdfOutcome, ExecutionRecording)> {
+    let outcome = t
+        .raw_mutation_with_recording(
+            "replay:insertRandomObject",
+            vec![assert_obj!().into()],
+            Identity::system(),
+            ExecutionRecordingMode::Record(dir.path().to_path_buf()),
+        )
+        .await?;
+    let path = wait_for_recording(dir).await?;
+    let encrypted = tokio::fs::read_to_string(&path).await?;
+    // Recordings hold arguments and every value read, so they're never
+    // written in plaintext.
+    assert!(!encrypted.contains("insertRandomObject"));
+    let recording = ExecutionRecording::decrypt(&t.key_broker, &encrypted)?;
+    Ok((outcome, recording))
+}
+
+#[convex_macro::test_runtime]
+async fn test_replay_mutation(rt: TestRuntime) -> anyhow::Result<()> {
+    let t = UdfTest::default(rt).await?;
+    t.mutation("basic:insertObject", assert_obj!("foo" => 1.0))
+        .await?;
+    let dir = tempfile::tempdir()?;
+    let (outcome, recording) = record_insert(&t, &dir).await?;
+    assert_eq!(recording.rng_seed, outcome.rng_seed);
+    assert_eq!(recording.unix_timestamp, outcome.unix_timestamp);
+    // Sync syscalls are recorded along with async ones, as is every module
+    // the function loaded.
+    assert!(recording
+        .syscalls
+        .iter()
+        .any(|syscall| syscall.name == "1.0/queryStream"));
+    assert!(recording
+        .modules
+        .keys()
+        .any(|path| path.starts_with("replay")));
+
+    // Replaying later, after the table has changed, sees the same reads, time
+    // and randomness as the original execution.
+    t.rt.advance_time(Duration::from_secs(60)).await;
+    t.mutation("basic:insertObject", assert_obj!("foo" => 2.0))
+        .await?;
+    let replayed = t.replay(recording).await?;
+    assert_eq!(replayed.result, outcome.result);
+    assert_eq!(replayed.rng_seed, outcome.rng_seed);
+    assert_eq!(replayed.unix_timestamp, outcome.unix_timestamp);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_replay_detects_divergence(rt: TestRuntime) -> anyhow::Result<()> {
+    let t = UdfTest::default(rt).await?;
+    let dir = tempfile::tempdir()?;
+    let (_, mut recording) = record_insert(&t, &dir).await?;
+    recording.syscalls.truncate(1);
+    let err = t.replay(recording).await.unwrap_err();
+    assert!(format!("{err:?}").contains("Replay diverged"), "{err:?}");
+    assert!(!err.is_deterministic_user_error());
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_replay_detects_changed_module(rt: TestRuntime) -> anyhow::Result<()> {
+    let t = UdfTest::default(rt).await?;
+    let dir = tempfile::tempdir()?;
+    let (_, mut recording) = record_insert(&t, &dir).await?;
+    for sha256 in recording.modules.values_mut() {
+        *sha256 = Sha256::hash(b"some other source");
+    }
+    let err = t.replay(recording).await.unwrap_err();
+    assert!(format!("{err:?}").contains("Replay diverged"), "{err:?}");
+    Ok(())
+}
@@ -253,6 +253,7 @@ async fn test_eval_schema(rt: TestRuntime) -> anyhow::Result<()> {
                 staged_text_indexes: btreemap!(),
                 vector_indexes: btreemap!(),
                 staged_vector_indexes: btreemap!(),
+                sparse_vector_indexes: btreemap!(),
                 document_type: Some(DocumentSchema::Union(vec![
                   object_validator!(
                     "ref" => FieldValidator::required_field_type(Validator::Id("twoIndexTable".parse()?)),
@@ -292,6 +293,7 @@ async fn test_eval_schema(rt: TestRuntime) -> anyhow::Result<()> {
                 staged_text_indexes: btreemap!(),
                 vector_indexes: btreemap!(),
                 staged_vector_indexes: btreemap!(),
+                sparse_vector_indexes: btreemap!(),
                 document_type: None,
             },
             name3.clone() => TableDefinition {
@@ -302,12 +304,14 @@ async fn test_eval_schema(rt: TestRuntime) -> anyhow::Result<()> {
                 search_index.clone() => TextIndexSchema::new(
                   search_index,
                   "title".parse()?,
-                  btreeset!{"is_deleted".parse()?, "workspace_id".parse()?}
+                  btreeset!{"is_deleted".parse()?, "workspace_id".parse()?},
+                  vec![],
                 )?
                },
                staged_text_indexes: btreemap!(),
                vector_indexes: btreemap!(),
                staged_vector_indexes: btreemap!(),
+               sparse_vector_indexes: btreemap!(),
                document_type: None,
 
           }
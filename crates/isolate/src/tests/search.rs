@@ -40,6 +40,7 @@ async fn add_text_index(t: &UdfTest<TestRuntime, TestPersistence>) -> anyhow::Re
         "messages.by_body".parse()?,
         "body".parse()?,
         btreeset! { "filterField".parse()?},
+        vec![],
     ))
     .await
 }
@@ -408,3 +409,50 @@ async fn test_search_query_scanned_too_many_documents(rt: TestRuntime) -> anyhow
         Ok(())
     }).await
 }
+
+fn suggestion_texts(results: ConvexValue) -> Vec<(String, f64)> {
+    must_let!(let ConvexValue::Array(results) = results);
+    results
+        .iter()
+        .map(|v| {
+            must_let!(let ConvexValue::Object(o) = v);
+            must_let!(let Some(ConvexValue::String(text)) = o.get("text"));
+            must_let!(let Some(ConvexValue::Float64(doc_frequency)) = o.get("docFrequency"));
+            (text.to_string(), *doc_frequency)
+        })
+        .collect()
+}
+
+#[convex_macro::test_runtime]
+async fn test_search_suggest(rt: TestRuntime) -> anyhow::Result<()> {
+    UdfTest::run_test_with_isolate2(rt, async move |t: UdfTestType| {
+        // Put half of the documents on disk and the rest in the memory index.
+        t.mutation("search:populateSuggest", assert_obj!()).await?;
+        add_and_backfill_text_index(&t).await?;
+        t.mutation("search:populateSuggest", assert_obj!()).await?;
+
+        let results = t
+            .query("search:suggest", assert_obj!("prefix" => "ap"))
+            .await?;
+        assert_eq!(
+            suggestion_texts(results),
+            vec![("apple".to_string(), 4.0), ("apricot".to_string(), 2.0)]
+        );
+
+        // Earlier words are kept and only the last one is completed.
+        let results = t
+            .query("search:suggest", assert_obj!("prefix" => "fresh ban"))
+            .await?;
+        assert_eq!(
+            suggestion_texts(results),
+            vec![("fresh banana".to_string(), 2.0)]
+        );
+
+        let results = t
+            .query("search:suggest", assert_obj!("prefix" => "zz"))
+            .await?;
+        assert_eq!(suggestion_texts(results), vec![]);
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,104 @@
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+};
+
+use deno_core::{
+    v8,
+    ModuleSpecifier,
+};
+use model::{
+    modules::module_versions::FullModuleSource,
+    source_packages::types::SourcePackageId,
+};
+use value::DeveloperDocumentId;
+
+use crate::{
+    client::initialize_v8,
+    module_map::ModuleMap,
+    snapshot::{
+        create_deployment_snapshot,
+        restore_modules,
+        DEPLOYMENT_SNAPSHOT_CONTEXT_INDEX,
+    },
+    udf_runtime::create_isolate_with_deployment_snapshot,
+};
+
+fn module(path: &str, source: &str) -> (ModuleSpecifier, Arc<FullModuleSource>) {
+    (
+        ModuleSpecifier::parse(&format!("convex:/{path}")).unwrap(),
+        Arc::new(FullModuleSource {
+            source: source.into(),
+            source_map: None,
+        }),
+    )
+}
+
+fn source_package_id() -> SourcePackageId {
+    DeveloperDocumentId::MIN.into()
+}
+
+#[test]
+fn test_restore_deployment_snapshot() -> anyhow::Result<()> {
+    initialize_v8();
+    let modules = vec![
+        module("config.js", "export const name = process.env.NAME;"),
+        module(
+            "messages.js",
+            "import { name } from './config.js'; export const greeting = `hello ${name}`;",
+        ),
+    ];
+    let environment_variables = BTreeMap::from([
+        ("NAME".parse()?, "convex".parse()?),
+        ("UNUSED".parse()?, "value".parse()?),
+    ]);
+    let snapshot = create_deployment_snapshot(source_package_id(), modules, environment_variables)?;
+    // Only the variables read during evaluation are recorded.
+    assert_eq!(
+        snapshot.environment_variables,
+        BTreeMap::from([("NAME".parse()?, Some("convex".parse()?))])
+    );
+
+    let mut isolate = create_isolate_with_deployment_snapshot(Default::default(), &snapshot);
+    assert!(isolate.set_slot(ModuleMap::new()));
+    let mut scope = v8::HandleScope::new(&mut isolate);
+    let context = v8::Context::from_snapshot(
+        &mut scope,
+        DEPLOYMENT_SNAPSHOT_CONTEXT_INDEX,
+        Default::default(),
+    )
+    .unwrap();
+    let mut scope = v8::ContextScope::new(&mut scope, context);
+    restore_modules(&mut scope, &snapshot)?;
+
+    let specifier = ModuleSpecifier::parse("convex:/messages.js")?;
+    let module_map = scope.get_slot::<ModuleMap>().unwrap();
+    let handle = module_map
+        .handle_by_id(module_map.get_by_name(&specifier).unwrap())
+        .unwrap();
+    let module = v8::Local::new(&mut scope, handle);
+    assert_eq!(module.get_status(), v8::ModuleStatus::Evaluated);
+    let namespace = module.get_module_namespace().to_object(&mut scope).unwrap();
+    let key = v8::String::new(&mut scope, "greeting").unwrap();
+    let greeting = namespace.get(&mut scope, key.into()).unwrap();
+    assert_eq!(greeting.to_rust_string_lossy(&mut scope), "hello convex");
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_fails_on_unsupported_imports() -> anyhow::Result<()> {
+    initialize_v8();
+    for source in [
+        "throw new Error('boom');",
+        "await Promise.resolve();",
+        "export const now = Date.now();",
+    ] {
+        let result = create_deployment_snapshot(
+            source_package_id(),
+            vec![module("bad.js", source)],
+            BTreeMap::new(),
+        );
+        assert!(result.is_err(), "{source} should fail to snapshot");
+    }
+    Ok(())
+}
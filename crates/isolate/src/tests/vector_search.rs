@@ -15,6 +15,7 @@ use maplit::btreeset;
 use must_let::must_let;
 use runtime::testing::TestRuntime;
 use value::{
+    assert_val,
     ConvexValue,
     TableNamespace,
 };
@@ -31,6 +32,8 @@ async fn add_vector_index(t: &UdfTest<TestRuntime, TestPersistence>) -> anyhow::
         "vector".parse()?,
         VectorDimensions::try_from(4)?,
         btreeset! { "filterA".parse()?, "filterB".parse()? },
+        Default::default(),
+        None,
     );
     IndexModel::new(&mut tx)
         .add_application_index(TableNamespace::test_user(), index)
@@ -121,3 +124,60 @@ async fn test_no_filter(rt: TestRuntime) -> anyhow::Result<()> {
     assert_eq!(String::from(r), "success".to_string());
     Ok(())
 }
+
+#[convex_macro::test_runtime]
+async fn test_query_vector_search_invalidation(rt: TestRuntime) -> anyhow::Result<()> {
+    let t = action_udf_test(rt).await?;
+
+    add_and_backfill_vector_index(&t).await?;
+    t.mutation("vector_search:populate", assert_obj!()).await?;
+
+    let args = assert_val!({ "vector" => [1., 0., 0., 0.], "limit" => 1. });
+    let (outcome, token) = t
+        .raw_query(
+            "vector_search:nearestIds",
+            vec![args.clone()],
+            Identity::system(),
+            None,
+        )
+        .await?;
+    let ids = outcome.result.unwrap().json_value();
+    assert_eq!(ids.as_array().map(|ids| ids.len()), Some(1));
+
+    // A vector further from the query than the current top result can't
+    // change the results.
+    t.mutation(
+        "vector_search:insertVector",
+        assert_obj!("vector" => [-1., 0., 0., 0.]),
+    )
+    .await?;
+    let ts = t.database.now_ts_for_reads();
+    let token = t
+        .database
+        .log()
+        .refresh_token(token, *ts)?
+        .expect("Should not be invalidated by a vector outside the top results");
+
+    // A closer one replaces the top result.
+    t.mutation(
+        "vector_search:insertVector",
+        assert_obj!("vector" => [1., 0., 0., 0.]),
+    )
+    .await?;
+    let ts = t.database.now_ts_for_reads();
+    assert!(
+        t.database.log().refresh_token(token, *ts)?.is_err(),
+        "Should be invalidated by a vector closer than the top result"
+    );
+
+    let (outcome, _) = t
+        .raw_query(
+            "vector_search:nearestIds",
+            vec![args],
+            Identity::system(),
+            None,
+        )
+        .await?;
+    assert_ne!(outcome.result.unwrap().json_value(), ids);
+    Ok(())
+}
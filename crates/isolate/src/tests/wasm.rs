@@ -0,0 +1,212 @@
+use common::{
+    assert_obj,
+    types::ModuleEnvironment,
+    value::ConvexValue,
+};
+use errors::ErrorMetadataAnyhowExt;
+use model::{
+    config::types::ModuleConfig,
+    modules::{
+        module_versions::ModuleSource,
+        wasm::max_wasm_memory_pages,
+    },
+};
+use must_let::must_let;
+use runtime::testing::TestRuntime;
+use value::assert_val;
+
+use crate::test_helpers::UdfTest;
+
+/// `(func (export "add") (param i32 i32) (result i32) local.get 0 local.get 1
+/// i32.add)`
+const ADD_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x07, 0x01, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, // type section
+    0x03, 0x02, 0x01, 0x00, // function section
+    0x07, 0x07, 0x01, 0x03, 0x61, 0x64, 0x64, 0x00, 0x00, // export section
+    0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6a, 0x0b, // code section
+];
+
+/// `(func v128.const i32x4 0 0 0 0 i32x4.relaxed_trunc_f32x4_s drop)`
+const RELAXED_SIMD_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section
+    0x03, 0x02, 0x01, 0x00, // function section
+    0x0a, 0x1a, 0x01, 0x18, 0x00, // code section
+    0xfd, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, // v128.const
+    0xfd, 0x81, 0x02, // i32x4.relaxed_trunc_f32x4_s
+    0x1a, 0x0b, // drop, end
+];
+
+/// A module with a shared memory, which we don't allow.
+const SHARED_MEMORY_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x05, 0x04, 0x01, 0x03, 0x01, 0x01, // memory section
+];
+
+fn module(path: &str, source: &str) -> anyhow::Result<ModuleConfig> {
+    Ok(ModuleConfig {
+        path: path.parse()?,
+        source: ModuleSource::new(source),
+        source_map: None,
+        environment: ModuleEnvironment::Isolate,
+    })
+}
+
+/// Exports `handler` as a public query without depending on `convex/server`.
+fn query_module(path: &str, imports: &str, handler: &str) -> anyhow::Result<ModuleConfig> {
+    let source = format!(
+        r#"{imports}
+        function query() {{}}
+        query.isQuery = true;
+        query.isPublic = true;
+        query.invokeQuery = async (argsStr) => {{
+            const handler = {handler};
+            const result = await handler(JSON.parse(argsStr)[0]);
+            return JSON.stringify(result);
+        }};
+        export {{ query }};
+        "#
+    );
+    module(path, &source)
+}
+
+#[convex_macro::test_runtime]
+async fn test_wasm_module(rt: TestRuntime) -> anyhow::Result<()> {
+    let modules = vec![
+        module("lib/add.wasm", &base64::encode(ADD_WASM))?,
+        query_module(
+            "math.js",
+            r#"import addModule from "./lib/add.wasm";"#,
+            r#"async ({ a, b }) => {
+                const instance = await WebAssembly.instantiate(addModule);
+                return instance.exports.add(a, b);
+            }"#,
+        )?,
+    ];
+    let t = UdfTest::default_with_modules(modules, rt)
+        .await?
+        .expect("Unexpected JsError");
+    must_let!(let ConvexValue::Float64(r) = t.query("math:query", assert_obj!("a" => 2., "b" => 3.)).await?);
+    assert_eq!(r, 5.);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_wasm_memory_limit(rt: TestRuntime) -> anyhow::Result<()> {
+    let handler = format!(
+        r#"async () => {{
+            try {{
+                new WebAssembly.Memory({{ initial: {} }});
+                return "allocated";
+            }} catch (e) {{
+                return e.name;
+            }}
+        }}"#,
+        max_wasm_memory_pages() + 1
+    );
+    let modules = vec![query_module("memory.js", "", &handler)?];
+    let t = UdfTest::default_with_modules(modules, rt)
+        .await?
+        .expect("Unexpected JsError");
+    let result = t.query("memory:query", assert_obj!()).await?;
+    assert_eq!(result, assert_val!("RangeError"));
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_invalid_wasm_module(rt: TestRuntime) -> anyhow::Result<()> {
+    let modules = vec![
+        module("broken.wasm", &base64::encode("not wasm"))?,
+        query_module(
+            "broken.js",
+            r#"import broken from "./broken.wasm";"#,
+            "async () => typeof broken",
+        )?,
+    ];
+    let err = match UdfTest::default_with_modules(modules, rt).await {
+        Ok(Err(js_error)) => js_error.to_string(),
+        Err(e) if e.is_bad_request() => e.to_string(),
+        _ => anyhow::bail!("No error raised for invalid WebAssembly module"),
+    };
+    assert!(
+        err.contains("not a WebAssembly binary"),
+        "Unexpected error: {err}"
+    );
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_runtime_wasm_compilation_is_validated(rt: TestRuntime) -> anyhow::Result<()> {
+    let handler = format!(
+        r#"async () => {{
+            const errorMessage = async (f) => {{
+                try {{
+                    await f();
+                    return "compiled";
+                }} catch (e) {{
+                    return e.message;
+                }}
+            }};
+            const addModule = await WebAssembly.compile(new Uint8Array({ADD_WASM:?}));
+            const {{ instance }} = await WebAssembly.instantiate(new Uint8Array({ADD_WASM:?}));
+            return {{
+                sum: instance.exports.add(2, 3),
+                isModule: addModule instanceof WebAssembly.Module,
+                shared: await errorMessage(() =>
+                    WebAssembly.compile(new Uint8Array({SHARED_MEMORY_WASM:?}))
+                ),
+                sharedConstructor: await errorMessage(() =>
+                    new WebAssembly.Module(new Uint8Array({SHARED_MEMORY_WASM:?}))
+                ),
+                // The unpatched constructor is still reachable from a module,
+                // but V8 won't compile with it.
+                bypass: await errorMessage(() =>
+                    new addModule.constructor(new Uint8Array({SHARED_MEMORY_WASM:?}))
+                ),
+            }};
+        }}"#
+    );
+    let modules = vec![query_module("compile.js", "", &handler)?];
+    let t = UdfTest::default_with_modules(modules, rt)
+        .await?
+        .expect("Unexpected JsError");
+    must_let!(let ConvexValue::Object(result) = t.query("compile:query", assert_obj!()).await?);
+    assert_eq!(result.get("sum"), Some(&ConvexValue::Float64(5.)));
+    assert_eq!(result.get("isModule"), Some(&ConvexValue::Boolean(true)));
+    for field in ["shared", "sharedConstructor"] {
+        must_let!(let Some(ConvexValue::String(message)) = result.get(field));
+        assert!(
+            message.contains("Shared WebAssembly memories are not supported"),
+            "Unexpected {field} error: {message}"
+        );
+    }
+    must_let!(let Some(ConvexValue::String(message)) = result.get("bypass"));
+    assert!(
+        message.contains("disallowed by embedder"),
+        "Unexpected bypass error: {message}"
+    );
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_wasm_relaxed_simd_disabled(rt: TestRuntime) -> anyhow::Result<()> {
+    let handler = format!(
+        r#"async () => {{
+            try {{
+                await WebAssembly.compile(new Uint8Array({RELAXED_SIMD_WASM:?}));
+                return "compiled";
+            }} catch (e) {{
+                return e.name;
+            }}
+        }}"#
+    );
+    let modules = vec![query_module("simd.js", "", &handler)?];
+    let t = UdfTest::default_with_modules(modules, rt)
+        .await?
+        .expect("Unexpected JsError");
+    let result = t.query("simd:query", assert_obj!()).await?;
+    assert_eq!(result, assert_val!("CompileError"));
+    Ok(())
+}
@@ -17,6 +17,10 @@ use crate::{
     bundled_js::system_udf_file,
     helpers,
     isolate::SETUP_URL,
+    snapshot::{
+        external_references,
+        DeploymentSnapshot,
+    },
     strings,
 };
 
@@ -54,6 +58,26 @@ pub(crate) fn create_isolate_with_udf_runtime(create_params: v8::CreateParams) -
     )
 }
 
+/// Creates a new V8 isolate from a deployment snapshot. Contexts created with
+/// `v8::Context::new` only have the UDF runtime loaded, like
+/// `create_isolate_with_udf_runtime`, while contexts created from the
+/// snapshot's deployment context also have the deployment's modules
+/// evaluated.
+pub(crate) fn create_isolate_with_deployment_snapshot(
+    create_params: v8::CreateParams,
+    snapshot: &DeploymentSnapshot,
+) -> v8::OwnedIsolate {
+    v8::Isolate::new(
+        create_params
+            .heap_limits(
+                INITIAL_HEAP_SIZE,
+                *ISOLATE_MAX_USER_HEAP_SIZE + *ISOLATE_MAX_HEAP_EXTRA_SIZE,
+            )
+            .external_references(external_references())
+            .snapshot_blob(Cow::<'static, [u8]>::Owned(snapshot.blob().to_vec()).into()),
+    )
+}
+
 fn create_base_snapshot() -> anyhow::Result<v8::StartupData> {
     // TODO: set external references. For now we:
     // 1. do not reuse snapshot blobs across processes,
@@ -63,18 +87,7 @@ fn create_base_snapshot() -> anyhow::Result<v8::StartupData> {
 
     let mut scope = v8::HandleScope::new(&mut isolate);
 
-    let context = v8::Context::new(&mut scope, v8::ContextOptions::default());
-    let mut context_scope = v8::ContextScope::new(&mut scope, context);
-
-    // Create `global.Convex`, so that `setup.js` can populate `Convex.jsSyscall`
-    let convex_value = v8::Object::new(&mut context_scope);
-    let convex_key = strings::Convex.create(&mut context_scope)?;
-    let global = context.global(&mut context_scope);
-    global.set(&mut context_scope, convex_key.into(), convex_value.into());
-
-    run_setup_module(&mut context_scope)?;
-
-    drop(context_scope);
+    let context = create_runtime_context(&mut scope)?;
     // Mark the context we created as the "default context", so that every new
     // context created from the snapshot will include this runtime.
     scope.set_default_context(context);
@@ -87,6 +100,23 @@ fn create_base_snapshot() -> anyhow::Result<v8::StartupData> {
     Ok(data)
 }
 
+/// Creates a context with the UDF runtime loaded, for snapshotting.
+pub(crate) fn create_runtime_context<'s>(
+    scope: &mut v8::HandleScope<'s, ()>,
+) -> anyhow::Result<v8::Local<'s, v8::Context>> {
+    let context = v8::Context::new(scope, v8::ContextOptions::default());
+    let mut context_scope = v8::ContextScope::new(scope, context);
+
+    // Create `global.Convex`, so that `setup.js` can populate `Convex.jsSyscall`
+    let convex_value = v8::Object::new(&mut context_scope);
+    let convex_key = strings::Convex.create(&mut context_scope)?;
+    let global = context.global(&mut context_scope);
+    global.set(&mut context_scope, convex_key.into(), convex_value.into());
+
+    run_setup_module(&mut context_scope)?;
+    Ok(context)
+}
+
 /// Go through all the V8 boilerplate to compile, instantiate, evaluate, and run
 /// the setup code. This is all inlined to avoid any dependencies on context
 /// state that isn't set up in the snapshot creation code path.
@@ -77,6 +77,7 @@ use pb::{
         StorageToken as StorageTokenProto,
     },
     convex_query_journal::InstanceQueryJournal as InstanceQueryJournalProto,
+    outcome::ExecutionRecording as ExecutionRecordingProto,
 };
 #[cfg(any(test, feature = "testing"))]
 use proptest::prelude::{
@@ -115,6 +116,7 @@ const ADMIN_KEY_VERSION: u8 = 1;
 const CURSOR_VERSION: u8 = 7;
 const STORE_FILE_AUTHZ_VERSION: u8 = 1;
 const QUERY_JOURNAL_VERSION: u8 = 7;
+const EXECUTION_RECORDING_VERSION: u8 = 1;
 
 // Max delay from transaction start time -> key being issued that is tolerable.
 const MAX_TS_DELAY: Duration = Duration::from_secs(15);
@@ -128,6 +130,7 @@ pub struct KeyBroker {
     cursor_encryptor: DeterministicEncryptor,
     journal_encryptor: RandomEncryptor,
     store_file_encryptor: RandomEncryptor,
+    recording_encryptor: RandomEncryptor,
 }
 
 // This enum encodes a successful authentication decision, and its nontrivial
@@ -801,6 +804,10 @@ impl KeyBroker {
                 &instance_secret,
                 Purpose::STORE_FILE_AUTHORIZATION,
             )?,
+            recording_encryptor: RandomEncryptor::derive_from_secret(
+                &instance_secret,
+                Purpose::EXECUTION_RECORDING,
+            )?,
         })
     }
 
@@ -1097,6 +1104,25 @@ impl KeyBroker {
         }
     }
 
+    /// Encrypts a recording of a function execution, since it contains the
+    /// function's arguments, identity and every value it read.
+    pub fn encrypt_execution_recording(&self, recording: &ExecutionRecordingProto) -> String {
+        self.recording_encryptor
+            .encrypt_proto(EXECUTION_RECORDING_VERSION, recording)
+    }
+
+    pub fn decrypt_execution_recording(
+        &self,
+        encrypted: &str,
+    ) -> anyhow::Result<ExecutionRecordingProto> {
+        self.recording_encryptor
+            .decrypt_proto(EXECUTION_RECORDING_VERSION, encrypted)
+            .context(ErrorMetadata::bad_request(
+                "InvalidExecutionRecording",
+                "Failed to decrypt execution recording",
+            ))
+    }
+
     pub fn issue_action_token(&self, component_id: ComponentId) -> ActionCallbackToken {
         let now = SystemTime::now();
         let since_epoch = now
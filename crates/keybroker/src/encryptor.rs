@@ -42,6 +42,7 @@ impl Purpose {
     /// we want them to be deterministic to avoid breaking caching.
     /// These do not need to be secret in the first place - only tamper-proof.
     pub const CURSOR: DeterministicPurpose = Purpose("cursor");
+    pub const EXECUTION_RECORDING: Purpose = Purpose("execution recording");
     pub const QUERY_JOURNAL: Purpose = Purpose("query journal");
     pub const STORE_FILE_AUTHORIZATION: Purpose = Purpose("store file authorization");
 }
@@ -5,6 +5,7 @@ use axum::{
 use common::{
     components::{
         ComponentFunctionPath,
+        ComponentId,
         ComponentPath,
     },
     http::{
@@ -220,8 +221,13 @@ fn parse_udf_identifier(
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub(crate) struct ScheduledJobLagArgs {
     window: String,
+    /// Only report lag for this scheduler queue.
+    queue: Option<String>,
+    /// The component that owns `queue`, or the root component if unset.
+    component_id: Option<String>,
 }
 pub(crate) async fn scheduled_job_lag(
     State(st): State<LocalAppState>,
@@ -231,6 +237,16 @@ pub(crate) async fn scheduled_job_lag(
     let window_json: serde_json::Value =
         serde_json::from_str(&query_args.window).map_err(anyhow::Error::new)?;
     let window = window_json.try_into()?;
-    let timeseries = st.application.scheduled_job_lag(identity, window).await?;
+    let queue = match query_args.queue {
+        Some(queue) => Some((
+            ComponentId::deserialize_from_string(query_args.component_id.as_deref())?,
+            queue,
+        )),
+        None => None,
+    };
+    let timeseries = st
+        .application
+        .scheduled_job_lag(identity, window, queue)
+        .await?;
     Ok(Json(timeseries))
 }
@@ -0,0 +1,152 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use common::http::{
+    extract::{
+        Json,
+        Path,
+        Query,
+    },
+    HttpResponseError,
+};
+use errors::ErrorMetadata;
+use http::StatusCode;
+use isolate::profiler::{
+    CpuProfileSummary,
+    CpuProfilingConfig,
+    CPU_PROFILES,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    admin::{
+        must_be_admin,
+        must_be_admin_with_write_access,
+    },
+    authentication::ExtractIdentity,
+    LocalAppState,
+};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListCpuProfilesResponse {
+    config: CpuProfilingConfig,
+    profiles: Vec<CpuProfileSummary>,
+}
+
+#[derive(Deserialize)]
+pub struct CpuProfilePathArgs {
+    id: u64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum CpuProfileFormat {
+    /// Chrome DevTools `.cpuprofile` JSON.
+    #[default]
+    Cpuprofile,
+    /// Folded stacks, for flamegraph tools.
+    Folded,
+}
+
+#[derive(Deserialize)]
+pub struct CpuProfileQueryArgs {
+    #[serde(default)]
+    format: CpuProfileFormat,
+}
+
+/// Returns which of this instance's functions are being profiled and its most
+/// recent profiles.
+pub async fn list_cpu_profiles(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    Ok(Json(ListCpuProfilesResponse {
+        config: CPU_PROFILES.config(&st.instance_name),
+        profiles: CPU_PROFILES.list(&st.instance_name),
+    }))
+}
+
+/// Changes which of this instance's function executions are profiled. This
+/// takes effect for executions that start after it returns.
+pub async fn update_cpu_profiling(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(config): Json<CpuProfilingConfig>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    CPU_PROFILES.set_config(&st.instance_name, config);
+    Ok(StatusCode::OK)
+}
+
+pub async fn get_cpu_profile(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Path(CpuProfilePathArgs { id }): Path<CpuProfilePathArgs>,
+    Query(CpuProfileQueryArgs { format }): Query<CpuProfileQueryArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    let stored = CPU_PROFILES.get(&st.instance_name, id).ok_or_else(|| {
+        anyhow::anyhow!(ErrorMetadata::not_found(
+            "CpuProfileNotFound",
+            format!("CPU profile {id} not found. Only the most recent profiles are kept."),
+        ))
+    })?;
+    let body = match format {
+        CpuProfileFormat::Cpuprofile => stored.profile.to_cpuprofile().to_string(),
+        CpuProfileFormat::Folded => stored.profile.to_folded(),
+    };
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum_extra::headers::authorization::Credentials;
+    use http::{
+        Request,
+        StatusCode,
+    };
+    use runtime::prod::ProdRuntime;
+    use serde_json::{
+        json,
+        Value as JsonValue,
+    };
+
+    use crate::test_helpers::setup_backend_for_test;
+
+    #[convex_macro::prod_rt_test]
+    async fn test_update_cpu_profiling(rt: ProdRuntime) -> anyhow::Result<()> {
+        let backend = setup_backend_for_test(rt).await?;
+        let config = json!({ "functions": ["messages.js:list"], "sampleRate": 0 });
+        let req = Request::builder()
+            .uri("/api/update_cpu_profiling")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Authorization", backend.admin_auth_header.0.encode())
+            .body(axum::body::Body::from(serde_json::to_vec(&config)?))?;
+        let () = backend.expect_success(req).await?;
+
+        let req = Request::builder()
+            .uri("/api/cpu_profiles")
+            .method("GET")
+            .header("Authorization", backend.admin_auth_header.0.encode())
+            .body(axum::body::Body::empty())?;
+        let response: JsonValue = backend.expect_success(req).await?;
+        assert_eq!(response["config"], config);
+
+        let req = Request::builder()
+            .uri(format!("/api/cpu_profiles/{}", u64::MAX))
+            .method("GET")
+            .header("Authorization", backend.admin_auth_header.0.encode())
+            .body(axum::body::Body::empty())?;
+        backend
+            .expect_error(req, StatusCode::NOT_FOUND, "CpuProfileNotFound")
+            .await?;
+        Ok(())
+    }
+}
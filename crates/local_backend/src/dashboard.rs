@@ -39,6 +39,7 @@ use serde::{
 };
 use serde_json::json;
 use value::{
+    export::ValueFormat,
     TableName,
     TableNamespace,
 };
@@ -265,3 +266,73 @@ pub async fn run_test_function(
     };
     Ok(Json(response))
 }
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayExecutionRecordingArgs {
+    /// The contents of a recording written to `UDF_EXECUTION_RECORDING_DIR`.
+    recording: String,
+    format: Option<String>,
+}
+
+/// Replays a recorded query or mutation at the snapshot it was recorded at,
+/// returning its result and logs. Nothing it writes is committed.
+pub async fn replay_execution_recording(
+    State(st): State<LocalAppState>,
+    ExtractRequestId(request_id): ExtractRequestId,
+    ExtractClientVersion(client_version): ExtractClientVersion,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(req): Json<ReplayExecutionRecordingArgs>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    let udf_return = st
+        .application
+        .replay_execution_recording(
+            request_id,
+            identity,
+            &req.recording,
+            FunctionCaller::Tester(client_version.clone()),
+        )
+        .await?;
+    let value_format: Option<ValueFormat> = req.format.as_deref().map(str::parse).transpose()?;
+    let response = match udf_return {
+        Ok(result) => UdfResponse::Success {
+            value: export_value(result.value.unpack(), value_format, client_version)?,
+            log_lines: result.log_lines,
+        },
+        Err(error) => {
+            UdfResponse::error(error.error, error.log_lines, value_format, client_version)?
+        },
+    };
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum_extra::headers::authorization::Credentials;
+    use http::{
+        Request,
+        StatusCode,
+    };
+    use runtime::prod::ProdRuntime;
+    use serde_json::json;
+
+    use crate::test_helpers::setup_backend_for_test;
+
+    #[convex_macro::prod_rt_test]
+    async fn test_replay_rejects_invalid_recording(rt: ProdRuntime) -> anyhow::Result<()> {
+        let backend = setup_backend_for_test(rt).await?;
+        let req = Request::builder()
+            .uri("/api/replay_execution_recording")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Authorization", backend.admin_auth_header.0.encode())
+            .body(axum::body::Body::from(serde_json::to_vec(
+                &json!({ "recording": "01deadbeef" }),
+            )?))?;
+        backend
+            .expect_error(req, StatusCode::BAD_REQUEST, "InvalidExecutionRecording")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use common::http::{
+    extract::Json,
+    fetch::{
+        EgressPolicy,
+        EgressRule,
+    },
+    HttpResponseError,
+};
+use http::StatusCode;
+use model::{
+    deployment_audit_log::types::DeploymentAuditLogEvent,
+    egress_policy::EgressPolicyModel,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    admin::{
+        must_be_admin,
+        must_be_admin_with_write_access,
+    },
+    authentication::ExtractIdentity,
+    LocalAppState,
+};
+
+/// An egress policy for outbound `fetch` requests from actions. Each rule's
+/// `destination` is a host pattern like `*.example.com` or a CIDR block like
+/// `10.0.0.0/8`, and rules without `ports` apply to every port.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EgressPolicyJson {
+    #[serde(default)]
+    allow: Vec<EgressRuleJson>,
+    #[serde(default)]
+    deny: Vec<EgressRuleJson>,
+    #[serde(default)]
+    log_requests: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EgressRuleJson {
+    destination: String,
+    #[serde(default)]
+    ports: Vec<u16>,
+}
+
+impl TryFrom<EgressRuleJson> for EgressRule {
+    type Error = anyhow::Error;
+
+    fn try_from(rule: EgressRuleJson) -> anyhow::Result<Self> {
+        Ok(Self {
+            destination: rule.destination.parse()?,
+            ports: rule.ports.into_iter().collect(),
+        })
+    }
+}
+
+impl From<EgressRule> for EgressRuleJson {
+    fn from(rule: EgressRule) -> Self {
+        Self {
+            destination: rule.destination.to_string(),
+            ports: rule.ports.into_iter().collect(),
+        }
+    }
+}
+
+impl TryFrom<EgressPolicyJson> for EgressPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(policy: EgressPolicyJson) -> anyhow::Result<Self> {
+        Ok(Self {
+            allow: policy
+                .allow
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<anyhow::Result<_>>()?,
+            deny: policy
+                .deny
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<anyhow::Result<_>>()?,
+            log_requests: policy.log_requests,
+        })
+    }
+}
+
+impl From<EgressPolicy> for EgressPolicyJson {
+    fn from(policy: EgressPolicy) -> Self {
+        Self {
+            allow: policy.allow.into_iter().map(Into::into).collect(),
+            deny: policy.deny.into_iter().map(Into::into).collect(),
+            log_requests: policy.log_requests,
+        }
+    }
+}
+
+pub async fn get_egress_policy(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    let mut tx = st.application.begin(identity).await?;
+    let policy = EgressPolicyModel::new(&mut tx).get().await?;
+    Ok(Json(EgressPolicyJson::from(policy)))
+}
+
+/// Replaces the deployment's egress policy. Sending an empty policy allows
+/// all outbound requests again. Node actions fail with a
+/// `NodeActionsEgressPolicy` error while a policy is configured, since their
+/// requests can't be checked.
+pub async fn update_egress_policy(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(request): Json<EgressPolicyJson>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let policy = EgressPolicy::try_from(request)?;
+
+    let mut tx = st.application.begin(identity).await?;
+    EgressPolicyModel::new(&mut tx).set(policy.clone()).await?;
+    st.application
+        .commit_with_audit_log_events(
+            tx,
+            vec![DeploymentAuditLogEvent::UpdateEgressPolicy { policy }],
+            "update_egress_policy",
+        )
+        .await?;
+
+    Ok(StatusCode::OK)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum_extra::headers::authorization::Credentials;
+    use common::http::fetch::EgressDestination;
+    use http::{
+        Request,
+        StatusCode,
+    };
+    use keybroker::Identity;
+    use model::egress_policy::EgressPolicyModel;
+    use runtime::prod::ProdRuntime;
+    use serde_json::{
+        json,
+        Value as JsonValue,
+    };
+
+    use crate::test_helpers::{
+        setup_backend_for_test,
+        TestLocalBackend,
+    };
+
+    fn update_request(
+        backend: &TestLocalBackend,
+        body: JsonValue,
+    ) -> anyhow::Result<Request<axum::body::Body>> {
+        Ok(Request::builder()
+            .uri("/api/update_egress_policy")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Authorization", backend.admin_auth_header.0.encode())
+            .body(axum::body::Body::from(serde_json::to_vec(&body)?))?)
+    }
+
+    #[convex_macro::prod_rt_test]
+    async fn test_update_egress_policy(rt: ProdRuntime) -> anyhow::Result<()> {
+        let backend = setup_backend_for_test(rt).await?;
+        let policy = json!({
+            "allow": [{ "destination": "*.example.com", "ports": [443] }],
+            "deny": [{ "destination": "10.0.0.0/8" }],
+            "logRequests": true,
+        });
+        let () = backend
+            .expect_success(update_request(&backend, policy)?)
+            .await?;
+
+        let mut tx = backend.st.application.begin(Identity::system()).await?;
+        let stored = EgressPolicyModel::new(&mut tx).get().await?;
+        assert_eq!(stored.allow.len(), 1);
+        assert_eq!(
+            stored.allow[0].destination,
+            EgressDestination::Host("*.example.com".to_string())
+        );
+        assert_eq!(stored.allow[0].ports.iter().collect::<Vec<_>>(), [&443]);
+        assert_eq!(stored.deny[0].destination.to_string(), "10.0.0.0/8");
+        assert!(stored.deny[0].ports.is_empty());
+        assert!(stored.log_requests);
+
+        let req = Request::builder()
+            .uri("/api/get_egress_policy")
+            .method("GET")
+            .header("Authorization", backend.admin_auth_header.0.encode())
+            .body(axum::body::Body::empty())?;
+        let response: JsonValue = backend.expect_success(req).await?;
+        assert_eq!(
+            response,
+            json!({
+                "allow": [{ "destination": "*.example.com", "ports": [443] }],
+                "deny": [{ "destination": "10.0.0.0/8", "ports": [] }],
+                "logRequests": true,
+            })
+        );
+
+        // Clearing the policy allows everything again.
+        let () = backend
+            .expect_success(update_request(&backend, json!({}))?)
+            .await?;
+        let mut tx = backend.st.application.begin(Identity::system()).await?;
+        assert!(EgressPolicyModel::new(&mut tx).get().await?.is_empty());
+        Ok(())
+    }
+
+    #[convex_macro::prod_rt_test]
+    async fn test_invalid_egress_policy(rt: ProdRuntime) -> anyhow::Result<()> {
+        let backend = setup_backend_for_test(rt).await?;
+        let policy = json!({ "allow": [{ "destination": "https://example.com" }] });
+        backend
+            .expect_error(
+                update_request(&backend, policy)?,
+                StatusCode::BAD_REQUEST,
+                "InvalidEgressRule",
+            )
+            .await?;
+        let mut tx = backend.st.application.begin(Identity::system()).await?;
+        assert!(EgressPolicyModel::new(&mut tx).get().await?.is_empty());
+        Ok(())
+    }
+}
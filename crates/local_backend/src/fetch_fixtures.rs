@@ -0,0 +1,109 @@
+use axum::{
+    extract::State,
+    response::IntoResponse,
+};
+use common::http::{
+    extract::Json,
+    fetch_fixtures::{
+        Cassette,
+        FetchFixtureMode,
+    },
+    HttpResponseError,
+};
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::{
+    admin::{
+        must_be_admin,
+        must_be_admin_with_write_access,
+    },
+    authentication::ExtractIdentity,
+    LocalAppState,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFetchFixturesRequest {
+    mode: FetchFixtureMode,
+    /// Fixtures to replay. Recording always starts from an empty cassette.
+    #[serde(default)]
+    cassette: Cassette,
+}
+
+/// Returns the current mode, the recorded (or loaded) cassette, and any
+/// requests that had no recorded response while replaying.
+pub async fn get_fetch_fixtures(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    Ok(Json(st.fetch_fixtures.status()))
+}
+
+pub async fn update_fetch_fixtures(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(UpdateFetchFixturesRequest { mode, cassette }): Json<UpdateFetchFixturesRequest>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let cassette = match mode {
+        FetchFixtureMode::Replay => cassette,
+        FetchFixtureMode::Off | FetchFixtureMode::Record => Cassette::default(),
+    };
+    st.fetch_fixtures.set(mode, cassette);
+    Ok(StatusCode::OK)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum_extra::headers::authorization::Credentials;
+    use http::Request;
+    use runtime::prod::ProdRuntime;
+    use serde_json::{
+        json,
+        Value as JsonValue,
+    };
+
+    use crate::test_helpers::setup_backend_for_test;
+
+    #[convex_macro::prod_rt_test]
+    async fn test_update_fetch_fixtures(rt: ProdRuntime) -> anyhow::Result<()> {
+        let backend = setup_backend_for_test(rt).await?;
+        let get_fixtures = || {
+            Request::builder()
+                .uri("/api/fetch_fixtures")
+                .method("GET")
+                .header("Authorization", backend.admin_auth_header.0.encode())
+                .body(axum::body::Body::empty())
+        };
+        let status: JsonValue = backend.expect_success(get_fixtures()?).await?;
+        assert_eq!(
+            status,
+            json!({ "mode": "off", "cassette": { "interactions": [] }, "unmatched": [] })
+        );
+
+        let cassette = json!({
+            "interactions": [{
+                "request": { "method": "GET", "url": "https://api.example.com/" },
+                "response": { "status": 200, "headers": [], "body": "hello" },
+            }],
+        });
+        let req = Request::builder()
+            .uri("/api/update_fetch_fixtures")
+            .method("POST")
+            .header("Content-Type", "application/json")
+            .header("Authorization", backend.admin_auth_header.0.encode())
+            .body(axum::body::Body::from(serde_json::to_vec(
+                &json!({ "mode": "replay", "cassette": cassette }),
+            )?))?;
+        let () = backend.expect_success(req).await?;
+
+        let status: JsonValue = backend.expect_success(get_fixtures()?).await?;
+        assert_eq!(
+            status,
+            json!({ "mode": "replay", "cassette": cassette, "unmatched": [] })
+        );
+        Ok(())
+    }
+}
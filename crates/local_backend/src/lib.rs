@@ -25,11 +25,14 @@ use common::{
     self,
     http::{
         fetch::ProxiedFetchClient,
+        fetch_fixtures::FetchFixtureStore,
         RouteMapper,
     },
     knobs::{
         ACTION_USER_TIMEOUT,
         DOCUMENT_RETENTION_RATE_LIMIT,
+        FETCH_FIXTURES_MODE,
+        FETCH_FIXTURES_PATH,
         UDF_CACHE_MAX_SIZE,
     },
     persistence::Persistence,
@@ -79,11 +82,14 @@ pub mod authentication;
 pub mod beacon;
 pub mod canonical_urls;
 pub mod config;
+pub mod cpu_profiles;
 pub mod custom_headers;
 pub mod dashboard;
 pub mod deploy_config;
 pub mod deploy_config2;
+pub mod egress_policy;
 pub mod environment_variables;
+pub mod fetch_fixtures;
 pub mod http_actions;
 pub mod log_sinks;
 pub mod logs;
@@ -115,6 +121,7 @@ pub struct LocalAppState {
     pub instance_name: String,
     pub application: Application<ProdRuntime>,
     pub zombify_rx: async_broadcast::Receiver<()>,
+    pub fetch_fixtures: Arc<FetchFixtureStore>,
 }
 
 impl LocalAppState {
@@ -196,10 +203,17 @@ pub async fn make_app(
             "Running without a proxy in release mode -- UDF `fetch` requests are unrestricted!"
         );
     }
-    let fetch_client = Arc::new(ProxiedFetchClient::new(
-        config.convex_http_proxy.clone(),
-        config.name(),
-    ));
+    // Fixtures are off unless enabled with FETCH_FIXTURES_MODE or through
+    // `/api/update_fetch_fixtures`, in which case this passes requests through.
+    let fetch_fixtures = Arc::new(FetchFixtureStore::new(
+        Arc::new(ProxiedFetchClient::new(
+            config.convex_http_proxy.clone(),
+            config.name(),
+        )),
+        *FETCH_FIXTURES_MODE,
+        FETCH_FIXTURES_PATH.clone(),
+    )?);
+    let fetch_client = fetch_fixtures.clone();
     let function_runner: Arc<dyn FunctionRunner<ProdRuntime>> = Arc::new(
         InProcessFunctionRunner::new(
             config.name().clone(),
@@ -263,6 +277,7 @@ pub async fn make_app(
         instance_name,
         application,
         zombify_rx,
+        fetch_fixtures,
     };
 
     Ok(app_state)
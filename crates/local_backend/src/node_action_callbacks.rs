@@ -49,6 +49,18 @@ use isolate::{
     UdfArgsJson,
 };
 use keybroker::Identity;
+use model::{
+    rate_limits::types::{
+        RateLimitRequestJson,
+        ResetRateLimitJson,
+    },
+    scheduled_jobs::types::{
+        ScheduledJobQueue,
+        ScheduledJobQueueJson,
+        ScheduledJobRetryPolicy,
+        ScheduledJobRetryPolicyJson,
+    },
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -269,6 +281,8 @@ pub struct ScheduleJobRequest {
     udf_path: Option<String>,
     udf_args: UdfArgsJson,
     scheduled_ts: f64,
+    retry: Option<ScheduledJobRetryPolicyJson>,
+    queue: Option<ScheduledJobQueueJson>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -305,6 +319,11 @@ pub async fn schedule_job(
             anyhow::anyhow!(ErrorMetadata::bad_request("InvalidUdfPath", e.to_string()))
         })?;
     let udf_args = req.udf_args.into_arg_vec();
+    let retry_policy = req
+        .retry
+        .map(ScheduledJobRetryPolicy::try_from)
+        .transpose()?;
+    let queue = req.queue.map(ScheduledJobQueue::try_from).transpose()?;
     let job_id = st
         .application
         .runner()
@@ -315,6 +334,8 @@ pub async fn schedule_job(
             udf_args,
             scheduled_ts,
             context,
+            retry_policy,
+            queue,
         )
         .await?;
     Ok(Json(ScheduleJobResponse {
@@ -348,6 +369,39 @@ pub async fn cancel_developer_job(
     Ok(Json(json!(null)))
 }
 
+#[debug_handler]
+pub async fn rate_limit(
+    State(st): State<LocalAppState>,
+    ExtractActionIdentity {
+        identity,
+        component_id,
+    }: ExtractActionIdentity,
+    Json(req): Json<RateLimitRequestJson>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    let result = st
+        .application
+        .runner()
+        .rate_limit(identity, component_id, req.try_into()?)
+        .await?;
+    Ok(Json(result))
+}
+
+#[debug_handler]
+pub async fn reset_rate_limit(
+    State(st): State<LocalAppState>,
+    ExtractActionIdentity {
+        identity,
+        component_id,
+    }: ExtractActionIdentity,
+    Json(ResetRateLimitJson { name, key }): Json<ResetRateLimitJson>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    st.application
+        .runner()
+        .reset_rate_limit(identity, component_id, name, key)
+        .await?;
+    Ok(Json(json!(null)))
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateFunctionHandleRequest {
@@ -409,7 +463,7 @@ pub async fn vector_search(
         let message = e.to_string();
         e.context(ErrorMetadata::bad_request("InvalidVectorQuery", message))
     })?;
-    let (results, usage_stats) = st
+    let (page, usage_stats) = st
         .application
         .vector_search(identity.clone(), query)
         .await?;
@@ -448,8 +502,7 @@ pub async fn vector_search(
             .await;
     }
 
-    let results: Vec<_> = results.into_iter().map(JsonValue::from).collect();
-    Ok(Json(json!({ "results": results })))
+    Ok(Json(JsonValue::from(page)))
 }
 
 #[debug_handler]
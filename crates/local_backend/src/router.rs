@@ -56,12 +56,18 @@ use crate::{
         udf_rate,
     },
     canonical_urls::update_canonical_url,
+    cpu_profiles::{
+        get_cpu_profile,
+        list_cpu_profiles,
+        update_cpu_profiling,
+    },
     dashboard::{
         check_admin_key,
         delete_component,
         delete_tables,
         get_indexes,
         get_source_code,
+        replay_execution_recording,
         run_test_function,
         shapes2,
     },
@@ -71,7 +77,15 @@ use crate::{
         push_config,
     },
     deploy_config2,
+    egress_policy::{
+        get_egress_policy,
+        update_egress_policy,
+    },
     environment_variables::update_environment_variables,
+    fetch_fixtures::{
+        get_fetch_fixtures,
+        update_fetch_fixtures,
+    },
     http_actions::http_action_handler,
     log_sinks::{
         add_axiom_sink,
@@ -91,6 +105,8 @@ use crate::{
         internal_action_post,
         internal_mutation_post,
         internal_query_post,
+        rate_limit,
+        reset_rate_limit,
         schedule_job,
         storage_delete,
         storage_generate_upload_url,
@@ -112,6 +128,11 @@ use crate::{
     scheduling::{
         cancel_all_jobs,
         cancel_job,
+        cancel_workflow,
+        pause_cron_job,
+        resume_cron_job,
+        run_cron_job,
+        workflow_status,
     },
     schema::{
         prepare_schema,
@@ -182,10 +203,27 @@ pub fn router(st: LocalAppState) -> Router {
         // Scheduled jobs routes
         .route("/cancel_all_jobs", post(cancel_all_jobs))
         .route("/cancel_job", post(cancel_job))
+        // Cron job routes
+        .route("/pause_cron_job", post(pause_cron_job))
+        .route("/resume_cron_job", post(resume_cron_job))
+        .route("/run_cron_job", post(run_cron_job))
+        // Workflow routes
+        .route("/workflow_status", post(workflow_status))
+        .route("/cancel_workflow", post(cancel_workflow))
         // Environment variable routes
         .route("/update_environment_variables", post(update_environment_variables))
         // Canonical URL routes
         .route("/update_canonical_url", post(update_canonical_url))
+        // Egress policy routes
+        .route("/get_egress_policy", get(get_egress_policy))
+        .route("/update_egress_policy", post(update_egress_policy))
+        // Fetch fixture routes, for tests
+        .route("/fetch_fixtures", get(get_fetch_fixtures))
+        .route("/update_fetch_fixtures", post(update_fetch_fixtures))
+        // CPU profiling routes
+        .route("/cpu_profiles", get(list_cpu_profiles))
+        .route("/cpu_profiles/{id}", get(get_cpu_profile))
+        .route("/update_cpu_profiling", post(update_cpu_profiling))
         // Local-only route to check if the admin key is valid
         .route("/check_admin_key", get(check_admin_key))
         .layer(ServiceBuilder::new());
@@ -195,6 +233,10 @@ pub fn router(st: LocalAppState) -> Router {
         .route("/prepare_schema", post(prepare_schema))
         .route("/deploy2/start_push", post(deploy_config2::start_push))
         .route("/run_test_function", post(run_test_function))
+        .route(
+            "/replay_execution_recording",
+            post(replay_execution_recording),
+        )
         .route(
             "/deploy2/wait_for_schema",
             post(deploy_config2::wait_for_schema),
@@ -304,6 +346,8 @@ where
         .route("/schedule_job", post(schedule_job))
         .route("/vector_search", post(vector_search))
         .route("/cancel_job", post(cancel_developer_job))
+        .route("/rate_limit", post(rate_limit))
+        .route("/reset_rate_limit", post(reset_rate_limit))
         .route("/create_function_handle", post(create_function_handle))
         // file storage endpoints
         .route("/storage_generate_upload_url", post(storage_generate_upload_url))
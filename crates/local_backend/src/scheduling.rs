@@ -15,21 +15,37 @@ use common::{
         HttpResponseError,
     },
 };
+use database::BootstrapComponentsModel;
 use errors::ErrorMetadata;
 use http::StatusCode;
-use model::scheduled_jobs::{
-    SchedulerModel,
-    SCHEDULED_JOBS_TABLE,
+use keybroker::Identity;
+use model::{
+    cron_jobs::{
+        types::CronIdentifier,
+        CronModel,
+    },
+    deployment_audit_log::types::DeploymentAuditLogEvent,
+    scheduled_jobs::{
+        SchedulerModel,
+        SCHEDULED_JOBS_TABLE,
+    },
+    workflows::WorkflowModel,
 };
 use serde::{
     Deserialize,
     Serialize,
 };
 use sync_types::Timestamp;
-use value::TableNamespace;
+use value::{
+    DeveloperDocumentId,
+    TableNamespace,
+};
 
 use crate::{
-    admin::must_be_admin_with_write_access,
+    admin::{
+        must_be_admin,
+        must_be_admin_with_write_access,
+    },
     authentication::ExtractIdentity,
     parse::parse_document_id,
     LocalAppState,
@@ -140,3 +156,148 @@ pub async fn cancel_job(
 
     Ok(StatusCode::OK)
 }
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CronJobRequest {
+    pub name: String,
+    pub component_id: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+enum CronJobAction {
+    Pause,
+    Resume,
+    Run,
+}
+
+async fn update_cron_job(
+    st: LocalAppState,
+    identity: Identity,
+    request: CronJobRequest,
+    action: CronJobAction,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let component_id = ComponentId::deserialize_from_string(request.component_id.as_deref())?;
+    let name: CronIdentifier = request.name.parse().context(ErrorMetadata::bad_request(
+        "InvalidCronIdentifier",
+        format!("Invalid cron job name {}", request.name),
+    ))?;
+    st.application
+        .execute_with_audit_log_events_and_occ_retries(identity, "update_cron_job", |tx| {
+            let name = name.clone();
+            async move {
+                let component =
+                    BootstrapComponentsModel::new(tx).must_component_path(component_id)?;
+                let mut model = CronModel::new(tx, component_id);
+                let event = match action {
+                    CronJobAction::Pause => {
+                        model.set_paused(&name, true).await?;
+                        DeploymentAuditLogEvent::PauseCronJob { component, name }
+                    },
+                    CronJobAction::Resume => {
+                        model.set_paused(&name, false).await?;
+                        DeploymentAuditLogEvent::ResumeCronJob { component, name }
+                    },
+                    CronJobAction::Run => {
+                        model.run_now(&name).await?;
+                        DeploymentAuditLogEvent::RunCronJob { component, name }
+                    },
+                };
+                Ok(((), vec![event]))
+            }
+            .into()
+        })
+        .await?;
+
+    Ok(StatusCode::OK)
+}
+
+#[debug_handler]
+pub async fn pause_cron_job(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(request): Json<CronJobRequest>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    update_cron_job(st, identity, request, CronJobAction::Pause).await
+}
+
+#[debug_handler]
+pub async fn resume_cron_job(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(request): Json<CronJobRequest>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    update_cron_job(st, identity, request, CronJobAction::Resume).await
+}
+
+#[debug_handler]
+pub async fn run_cron_job(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(request): Json<CronJobRequest>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    update_cron_job(st, identity, request, CronJobAction::Run).await
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowRequest {
+    pub workflow_id: String,
+    pub component_id: Option<String>,
+}
+
+fn parse_workflow_id(workflow_id: &str) -> anyhow::Result<DeveloperDocumentId> {
+    workflow_id.parse().context(ErrorMetadata::bad_request(
+        "InvalidWorkflowId",
+        format!("Invalid workflow id {workflow_id}"),
+    ))
+}
+
+#[debug_handler]
+pub async fn workflow_status(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(request): Json<WorkflowRequest>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin(&identity)?;
+    let component_id = ComponentId::deserialize_from_string(request.component_id.as_deref())?;
+    let workflow_id = parse_workflow_id(&request.workflow_id)?;
+    let mut tx = st.application.begin(identity).await?;
+    let status = WorkflowModel::new(&mut tx, component_id.into())
+        .status(workflow_id)
+        .await?;
+    Ok(Json(status))
+}
+
+#[debug_handler]
+pub async fn cancel_workflow(
+    State(st): State<LocalAppState>,
+    ExtractIdentity(identity): ExtractIdentity,
+    Json(request): Json<WorkflowRequest>,
+) -> Result<impl IntoResponse, HttpResponseError> {
+    must_be_admin_with_write_access(&identity)?;
+    let component_id = ComponentId::deserialize_from_string(request.component_id.as_deref())?;
+    let workflow_id = parse_workflow_id(&request.workflow_id)?;
+    st.application
+        .execute_with_audit_log_events_and_occ_retries(identity, "cancel_workflow", |tx| {
+            async move {
+                let component =
+                    BootstrapComponentsModel::new(tx).must_component_path(component_id)?;
+                WorkflowModel::new(tx, component_id.into())
+                    .cancel(workflow_id)
+                    .await?;
+                Ok((
+                    (),
+                    vec![DeploymentAuditLogEvent::CancelWorkflow {
+                        component,
+                        workflow_id,
+                    }],
+                ))
+            }
+            .into()
+        })
+        .await?;
+
+    Ok(StatusCode::OK)
+}
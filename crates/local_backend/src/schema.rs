@@ -12,6 +12,7 @@ use common::{
                 DatabaseIndexState,
                 DeveloperDatabaseIndexConfig,
             },
+            sparse_vector_index::DeveloperSparseVectorIndexConfig,
             text_index::{
                 DeveloperTextIndexConfig,
                 TextIndexState,
@@ -123,6 +124,7 @@ impl TryFrom<IndexMetadata<TableName>> for IndexMetadataResponse {
                     DeveloperTextIndexConfig {
                         search_field,
                         filter_fields,
+                        synonyms,
                     },
             } => {
                 let backfill_state = match on_disk_state {
@@ -140,7 +142,8 @@ impl TryFrom<IndexMetadata<TableName>> for IndexMetadataResponse {
                     name,
                     fields: json!({
                         "searchField":  String::from(search_field),
-                        "filterFields": filter_fields.into_iter().map(String::from).collect::<Vec<_>>()
+                        "filterFields": filter_fields.into_iter().map(String::from).collect::<Vec<_>>(),
+                        "synonyms": synonyms,
                     }),
                     backfill: BackfillResponse {
                         state: backfill_state,
@@ -153,6 +156,8 @@ impl TryFrom<IndexMetadata<TableName>> for IndexMetadataResponse {
                         dimensions,
                         vector_field,
                         filter_fields,
+                        distance_metric,
+                        quantization,
                     },
                 on_disk_state,
             } => {
@@ -168,7 +173,35 @@ impl TryFrom<IndexMetadata<TableName>> for IndexMetadataResponse {
                     fields: json!({
                         "dimensions": u32::from(dimensions),
                         "vectorField": String::from(vector_field),
-                        "filterFields": filter_fields.into_iter().map(String::from).collect::<Vec<_>>()
+                        "filterFields": filter_fields.into_iter().map(String::from).collect::<Vec<_>>(),
+                        "distanceMetric": distance_metric.to_string(),
+                        "quantization": quantization.map(|q| q.quantization_type.to_string()),
+                    }),
+                    backfill: BackfillResponse {
+                        state: backfill_state,
+                    },
+                }
+            },
+            IndexConfig::SparseVector {
+                developer_config:
+                    DeveloperSparseVectorIndexConfig {
+                        vector_field,
+                        filter_fields,
+                    },
+                on_disk_state,
+            } => {
+                let backfill_state = match on_disk_state {
+                    VectorIndexState::Backfilling(_) => "in_progress".to_string(),
+                    VectorIndexState::Backfilled { .. } | VectorIndexState::SnapshottedAt(_) => {
+                        "done".to_string()
+                    },
+                };
+                IndexMetadataResponse {
+                    table,
+                    name,
+                    fields: json!({
+                        "vectorField": String::from(vector_field),
+                        "filterFields": filter_fields.into_iter().map(String::from).collect::<Vec<_>>(),
                     }),
                     backfill: BackfillResponse {
                         state: backfill_state,
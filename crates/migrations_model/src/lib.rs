@@ -30,7 +30,7 @@ pub type DatabaseVersion = i64;
 // migrations unless explicitly dropping support.
 // Add a user name next to the version when you make a change to highlight merge
 // conflicts.
-pub const DATABASE_VERSION: DatabaseVersion = 121; // nipunn
+pub const DATABASE_VERSION: DatabaseVersion = 127; // nipunn
 
 pub struct MigrationExecutor<RT: Runtime> {
     pub db: Database<RT>,
@@ -76,6 +76,38 @@ impl<RT: Runtime> MigrationExecutor<RT> {
                     .await?;
                 MigrationCompletionCriterion::MigrationComplete(to_version)
             },
+            122 => {
+                // This is an empty migration because we added a new system
+                // index, _scheduled_jobs.by_queue_and_next_ts. It was replaced
+                // by _scheduled_jobs.by_pending_queue_and_next_ts in 127.
+                MigrationCompletionCriterion::MigrationComplete(to_version)
+            },
+            123 => {
+                // This is an empty migration because we added new system
+                // tables, _workflows, _workflow_steps and _workflow_events
+                MigrationCompletionCriterion::MigrationComplete(to_version)
+            },
+            124 => {
+                // This is an empty migration because we added a new system
+                // table, _work_queue_messages
+                MigrationCompletionCriterion::MigrationComplete(to_version)
+            },
+            125 => {
+                // This is an empty migration because we added a new system
+                // table, _rate_limits
+                MigrationCompletionCriterion::MigrationComplete(to_version)
+            },
+            126 => {
+                // This is an empty migration because we added a new system
+                // table, _egress_policy
+                MigrationCompletionCriterion::MigrationComplete(to_version)
+            },
+            127 => {
+                // This is an empty migration because we added a new system
+                // table, _scheduled_job_queues, and a new system index,
+                // _scheduled_jobs.by_pending_queue_and_next_ts
+                MigrationCompletionCriterion::MigrationComplete(to_version)
+            },
             // NOTE: Make sure to increase DATABASE_VERSION when adding new migrations.
             _ => anyhow::bail!("Version did not define a migration! {}", to_version),
         };
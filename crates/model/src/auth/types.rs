@@ -58,8 +58,14 @@ impl TryFrom<ConvexObject> for AuthInfoPersisted {
                 _ => anyhow::bail!("Missing or invalid issuer field for AuthInfo"),
             };
             let jwks = match fields.remove("jwks") {
-                Some(ConvexValue::String(s)) => s.into(),
-                _ => anyhow::bail!("Missing or invalid jwks field for AuthInfo"),
+                Some(ConvexValue::String(s)) => Some(s.into()),
+                Some(ConvexValue::Null) | None => None,
+                v => anyhow::bail!("Invalid jwks field for AuthInfo: {v:?}"),
+            };
+            let secret_env_var = match fields.remove("secretEnvVar") {
+                Some(ConvexValue::String(s)) => Some(s.parse()?),
+                Some(ConvexValue::Null) | None => None,
+                v => anyhow::bail!("Invalid secretEnvVar field for AuthInfo: {v:?}"),
             };
             let algorithm = match fields.remove("algorithm") {
                 Some(ConvexValue::String(s)) => s.parse()?,
@@ -69,6 +75,7 @@ impl TryFrom<ConvexObject> for AuthInfoPersisted {
                 application_id,
                 issuer,
                 jwks,
+                secret_env_var,
                 algorithm,
             }
         };
@@ -92,12 +99,14 @@ impl TryFrom<AuthInfoPersisted> for ConvexObject {
                 application_id,
                 issuer,
                 jwks,
+                secret_env_var,
                 algorithm,
             } => obj!(
                 "type" => "customJwt",
                 "applicationID" => application_id,
                 "issuer" => issuer.to_string(),
                 "jwks" => jwks,
+                "secretEnvVar" => secret_env_var.map(String::from),
                 "algorithm" => String::from(algorithm),
             )?,
         };
@@ -105,6 +105,7 @@ macro_rules! db_schema_with_indexes {
                         staged_text_indexes: Default::default(),
                         vector_indexes: Default::default(),
                         staged_vector_indexes: Default::default(),
+                        sparse_vector_indexes: Default::default(),
                         document_type: None,
                     };
                     tables.insert(table_name, table_def);
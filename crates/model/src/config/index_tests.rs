@@ -22,6 +22,7 @@ use common::{
         },
         DatabaseSchema,
         DocumentSchema,
+        SparseVectorIndexSchema,
         TableDefinition,
         TextIndexSchema,
     },
@@ -82,6 +83,7 @@ macro_rules! db_schema_with_search_indexes {
                                 index_name.descriptor().clone(),
                                 field_path.try_into()?,
                                 BTreeSet::new(),
+                                vec![],
                             )?,
                         );
                     )*
@@ -93,6 +95,7 @@ macro_rules! db_schema_with_search_indexes {
                         staged_text_indexes: Default::default(),
                         vector_indexes: Default::default(),
                         staged_vector_indexes: Default::default(),
+                        sparse_vector_indexes: Default::default(),
                         document_type: None,
                     };
                     tables.insert(table_name, table_def);
@@ -188,6 +191,45 @@ async fn prepare_new_mutated_indexes_with_new_index_marks_index_backfilling_and_
     .await
 }
 
+#[convex_macro::test_runtime]
+async fn prepare_new_mutated_indexes_with_new_sparse_vector_index_marks_it_backfilling(
+    rt: TestRuntime,
+) -> anyhow::Result<()> {
+    let table_name: TableName = TABLE_NAME.parse()?;
+    let index_descriptor = new_index_descriptor(TABLE_NAME, INDEX_NAME)?;
+    let schema = DatabaseSchema {
+        tables: BTreeMap::from([(
+            table_name.clone(),
+            TableDefinition {
+                table_name,
+                indexes: BTreeMap::new(),
+                staged_db_indexes: Default::default(),
+                text_indexes: Default::default(),
+                staged_text_indexes: Default::default(),
+                vector_indexes: Default::default(),
+                staged_vector_indexes: Default::default(),
+                sparse_vector_indexes: BTreeMap::from([(
+                    index_descriptor.clone(),
+                    SparseVectorIndexSchema::new(
+                        index_descriptor,
+                        "embedding".parse()?,
+                        BTreeSet::new(),
+                    )?,
+                )]),
+                document_type: None,
+            },
+        )]),
+        schema_validation: true,
+    };
+    let mut tx = new_tx(rt).await?;
+    let result = IndexModel::new(&mut tx)
+        .prepare_new_and_mutated_indexes(TableNamespace::test_user(), &schema)
+        .await?;
+
+    expect_diff!(result ; added:[(TABLE_NAME, INDEX_NAME, vec!["embedding"])], dropped:[]);
+    assert_backfilling(tx, TABLE_NAME, INDEX_NAME)
+}
+
 // We expect the index to be returned because it's used by the CLI to tell the
 // user what indexes will be impacted by their push.
 #[convex_macro::test_runtime]
@@ -1135,6 +1177,17 @@ fn assert_index_data(actual: Vec<IndexConfig>, expected: Vec<TestIndexConfig>) {
                 };
                 TestIndexConfig(developer_config.vector_field.to_string(), vector_state)
             },
+            IndexConfig::SparseVector {
+                developer_config,
+                on_disk_state,
+            } => {
+                let vector_state = match on_disk_state {
+                    VectorIndexState::Backfilling(_) => TestIndexState::Backfilling,
+                    VectorIndexState::Backfilled { .. } => TestIndexState::Backfilled,
+                    VectorIndexState::SnapshottedAt(_) => TestIndexState::Enabled,
+                };
+                TestIndexConfig(developer_config.vector_field.to_string(), vector_state)
+            },
         })
         .collect();
 
@@ -351,14 +351,14 @@ impl ModuleDiff {
     ) -> anyhow::Result<Self> {
         let mut added_functions = Vec::with_capacity(added_module_paths.len());
         for m in added_module_paths {
-            if m.is_deps() || m.is_system() {
+            if m.is_deps() || m.is_system() || m.is_wasm() {
                 continue;
             }
             added_functions.push(m.as_str().to_string());
         }
         let mut removed_functions = Vec::with_capacity(removed_module_paths.len());
         for m in removed_module_paths {
-            if m.is_deps() || m.is_system() {
+            if m.is_deps() || m.is_system() || m.is_wasm() {
                 continue;
             }
             removed_functions.push(m.as_str().to_string());
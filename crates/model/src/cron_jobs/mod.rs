@@ -18,12 +18,14 @@ use common::{
         Query,
     },
     runtime::Runtime,
+    types::Timestamp,
 };
 use database::{
     ResolvedQuery,
     SystemMetadataModel,
     Transaction,
 };
+use errors::ErrorMetadata;
 use futures_async_stream::try_stream;
 use sync_types::CanonicalizedModulePath;
 use types::CronJobMetadata;
@@ -60,6 +62,10 @@ use crate::{
 pub mod next_ts;
 pub mod types;
 
+/// Paused crons park their next run here so they sort after every runnable
+/// cron in the `by_next_ts` index. Resuming recomputes the real next run.
+pub const PAUSED_NEXT_TS: Timestamp = Timestamp::MAX;
+
 pub static CRON_JOBS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
     "_cron_jobs"
         .parse()
@@ -224,7 +230,11 @@ impl<'a, RT: Runtime> CronModel<'a, RT> {
     ) -> anyhow::Result<()> {
         let now = self.runtime().generate_timestamp()?;
         let next_ts = compute_next_ts(&cron_spec, None, now)?;
-        let cron = CronJobMetadata { name, cron_spec };
+        let cron = CronJobMetadata {
+            name,
+            cron_spec,
+            paused: false,
+        };
 
         let cron_job_id = SystemMetadataModel::new(self.tx, self.component.into())
             .insert(&CRON_JOBS_TABLE, cron.try_into()?)
@@ -270,7 +280,9 @@ impl<'a, RT: Runtime> CronModel<'a, RT> {
         mut cron_job: ParsedDocument<CronJobMetadata>,
         new_cron_spec: CronSpec,
     ) -> anyhow::Result<()> {
-        if new_cron_spec.cron_schedule != cron_job.cron_spec.cron_schedule {
+        // Paused crons stay parked; resuming computes the next run from the new
+        // schedule.
+        if new_cron_spec.cron_schedule != cron_job.cron_spec.cron_schedule && !cron_job.paused {
             // Skip updating the next run ts, if the runs are close together on the old
             // schedule. This is a heuristic to avoid OCC with existing cron
             // jobs running/changing state. True solution would be to move this
@@ -300,6 +312,95 @@ impl<'a, RT: Runtime> CronModel<'a, RT> {
         Ok(())
     }
 
+    pub async fn get_by_name(
+        &mut self,
+        name: &CronIdentifier,
+    ) -> anyhow::Result<Option<ParsedDocument<CronJobMetadata>>> {
+        let query = Query::index_range(IndexRange {
+            index_name: CRON_JOBS_INDEX_BY_NAME.name(),
+            range: vec![IndexRangeExpression::Eq(
+                CRON_JOBS_NAME_FIELD.clone(),
+                ConvexValue::try_from(name.to_string())?.into(),
+            )],
+            order: Order::Asc,
+        });
+        let mut query_stream = ResolvedQuery::new(self.tx, self.component.into(), query)?;
+        query_stream
+            .expect_at_most_one(self.tx)
+            .await?
+            .map(|v| v.parse())
+            .transpose()
+    }
+
+    async fn must_get_by_name(
+        &mut self,
+        name: &CronIdentifier,
+    ) -> anyhow::Result<ParsedDocument<CronJobMetadata>> {
+        self.get_by_name(name).await?.ok_or_else(|| {
+            ErrorMetadata::bad_request("CronJobNotFound", format!("Cron job {name} not found"))
+                .into()
+        })
+    }
+
+    /// Pause or resume a cron. Paused crons keep their spec up to date on
+    /// pushes, but their next run is parked at [`PAUSED_NEXT_TS`] until they
+    /// are resumed.
+    ///
+    /// Pausing a cron mid-run abandons the run's bookkeeping, as when a push
+    /// changes the cron: the run itself isn't interrupted, but its completion
+    /// isn't recorded.
+    pub async fn set_paused(&mut self, name: &CronIdentifier, paused: bool) -> anyhow::Result<()> {
+        let mut cron_job = self.must_get_by_name(name).await?;
+        if cron_job.paused == paused {
+            return Ok(());
+        }
+        let mut next_run = self
+            .next_run(cron_job.id().developer_id)
+            .await?
+            .context("No next run found")?
+            .into_value();
+        next_run.state = CronJobState::Pending;
+        next_run.next_ts = if paused {
+            PAUSED_NEXT_TS
+        } else {
+            // Don't fire immediately for runs that were due while paused.
+            let now = self.runtime().generate_timestamp()?;
+            compute_next_ts(&cron_job.cron_spec, next_run.prev_ts, now)?.max(now)
+        };
+        self.update_job_state(next_run).await?;
+        cron_job.paused = paused;
+        SystemMetadataModel::new(self.tx, self.component.into())
+            .replace(cron_job.id(), cron_job.into_value().try_into()?)
+            .await?;
+        Ok(())
+    }
+
+    /// Schedule an out-of-band run of a cron as soon as possible. The regular
+    /// schedule resumes after it.
+    pub async fn run_now(&mut self, name: &CronIdentifier) -> anyhow::Result<()> {
+        let cron_job = self.must_get_by_name(name).await?;
+        if cron_job.paused {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "CronJobPaused",
+                format!("Cron job {name} is paused. Resume it before running it."),
+            ));
+        }
+        let mut next_run = self
+            .next_run(cron_job.id().developer_id)
+            .await?
+            .context("No next run found")?
+            .into_value();
+        if next_run.state != CronJobState::Pending {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "CronJobAlreadyRunning",
+                format!("Cron job {name} is already running"),
+            ));
+        }
+        next_run.next_ts = self.runtime().generate_timestamp()?;
+        self.update_job_state(next_run).await?;
+        Ok(())
+    }
+
     pub async fn delete(
         &mut self,
         cron_job: ParsedDocument<CronJobMetadata>,
@@ -2,11 +2,21 @@ use std::time::Duration;
 
 use anyhow::Context;
 use chrono::{
+    DateTime,
+    LocalResult,
+    NaiveDateTime,
+    Offset,
+    TimeDelta,
     TimeZone,
     Utc,
 };
+use chrono_tz::Tz;
 use saffron::Cron;
 use sync_types::Timestamp;
+use value::{
+    sha256::Sha256,
+    DeveloperDocumentId,
+};
 
 use super::types::{
     CronSchedule,
@@ -18,47 +28,19 @@ pub fn compute_next_ts(
     prev_ts: Option<Timestamp>,
     now: Timestamp,
 ) -> anyhow::Result<Timestamp> {
-    let cron: Cron = match cron_spec.cron_schedule.clone() {
-        CronSchedule::Interval { seconds } => {
-            let next_ts = match prev_ts {
-                Some(prev_ts) => prev_ts.add(Duration::from_secs(seconds as u64))?,
-                None => now,
-            };
-            return Ok(next_ts);
-        },
-        CronSchedule::Hourly { minute_utc } => format!("{minute_utc} * * * *")
-            .parse()
-            .context("Hourly Schedule: Cron parsing from Saffron failed")?,
-        CronSchedule::Daily {
-            hour_utc,
-            minute_utc,
-        } => format!("{minute_utc} {hour_utc} * * *")
-            .parse()
-            .context("Daily Schedule: Cron parsing from Saffron failed")?,
-        CronSchedule::Weekly {
-            day_of_week,
-            hour_utc,
-            minute_utc,
-        } => format!("{minute_utc} {hour_utc} * * {day_of_week}")
-            .parse()
-            .context("Weekly Schedule: Cron parsing from Saffron failed")?,
-        CronSchedule::Monthly {
-            day,
-            hour_utc,
-            minute_utc,
-        } => format!("{minute_utc} {hour_utc} {day} * *")
-            .parse()
-            .context("Monthly Schedule: Cron parsing from Saffron failed")?,
-        CronSchedule::Cron { cron_expr } => cron_expr
-            .parse()
-            .context("Cron Schedule: Cron parsing from Saffron failed")?,
-    };
+    let schedule = &cron_spec.cron_schedule;
+    if let CronSchedule::Interval { seconds } = schedule {
+        let next_ts = match prev_ts {
+            Some(prev_ts) => prev_ts.add(Duration::from_secs(*seconds as u64))?,
+            None => now,
+        };
+        return Ok(next_ts);
+    }
+    let cron = schedule.cron()?;
+    let tz = schedule.timezone();
     let now_nanos: i64 = now.into();
     let now_utc = Utc.timestamp_nanos(now_nanos);
-    let next_ts_utc = match cron.next_after(now_utc) {
-        Some(next_ts_utc) => next_ts_utc,
-        None => return Err(anyhow::anyhow!("Could not compute next timestamp for cron")),
-    };
+    let next_ts_utc = next_after_in_timezone(&cron, tz, schedule.runs_every_hour(), now_utc)?;
     let next_ts_nanos = next_ts_utc
         .timestamp_nanos_opt()
         .context("Unable to get nanos from UTC")?;
@@ -66,6 +48,222 @@ pub fn compute_next_ts(
     Ok(next_ts)
 }
 
+/// The run scheduled right after `ts`, even if that's already in the past.
+pub fn following_ts(cron_spec: &CronSpec, ts: Timestamp) -> anyhow::Result<Timestamp> {
+    compute_next_ts(cron_spec, Some(ts), ts)
+}
+
+/// When a run scheduled for `next_ts` may start, after applying the spec's
+/// jitter. The delay is derived from the job and the scheduled time, so it's
+/// stable across executor restarts but differs between jobs and between runs
+/// of the same job.
+pub fn compute_ready_ts(
+    cron_spec: &CronSpec,
+    cron_job_id: DeveloperDocumentId,
+    next_ts: Timestamp,
+) -> anyhow::Result<Timestamp> {
+    let Some(jitter_seconds) = cron_spec.jitter_seconds.filter(|s| *s > 0) else {
+        return Ok(next_ts);
+    };
+    // Use a fixed hash rather than `DefaultHasher`, whose output may change
+    // between Rust releases and would reshuffle every job's delay on upgrade.
+    let mut hasher = Sha256::new();
+    hasher.update(&u32::from(cron_job_id.table()).to_le_bytes());
+    hasher.update(&cron_job_id.internal_id());
+    hasher.update(&i64::from(next_ts).to_le_bytes());
+    let digest = hasher.finalize();
+    let hash = u64::from_le_bytes(digest[..8].try_into()?);
+    let window_millis = u64::from(jitter_seconds) * 1000;
+    next_ts.add(Duration::from_millis(hash % window_millis))
+}
+
+/// Counting skipped runs of calendar schedules means walking them one by one,
+/// so stop counting after this many.
+pub const MAX_SKIPPED_RUNS_COUNTED: u64 = 10_000;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SkippedRuns {
+    /// Saturates at `MAX_SKIPPED_RUNS_COUNTED` for calendar schedules.
+    pub num_skipped: u64,
+    /// When the first run that wasn't skipped is scheduled.
+    pub next_ts: Timestamp,
+}
+
+/// Skips the runs scheduled from `first_ts` up to `now`, keeping only the most
+/// recent `max_runs`. Unlike walking the schedule from `first_ts`, this takes
+/// time proportional to `max_runs` rather than to the length of the outage.
+pub fn skip_missed_runs(
+    cron_spec: &CronSpec,
+    first_ts: Timestamp,
+    now: Timestamp,
+    max_runs: u64,
+) -> anyhow::Result<SkippedRuns> {
+    anyhow::ensure!(first_ts <= now, "No runs were missed");
+    if let CronSchedule::Interval { seconds } = cron_spec.cron_schedule {
+        let period = i128::from(seconds) * 1_000_000_000;
+        anyhow::ensure!(period > 0, "Invalid interval {seconds}s");
+        let elapsed = i128::from(i64::from(now)) - i128::from(i64::from(first_ts));
+        let num_missed = u64::try_from(elapsed / period + 1)?;
+        let num_skipped = num_missed.saturating_sub(max_runs);
+        let next_ts = i128::from(i64::from(first_ts)) + i128::from(num_skipped) * period;
+        return Ok(SkippedRuns {
+            num_skipped,
+            next_ts: Timestamp::try_from(i64::try_from(next_ts)?)?,
+        });
+    }
+    let next_ts = if max_runs == 0 {
+        compute_next_ts(cron_spec, None, now)?
+    } else {
+        first_kept_calendar_run(cron_spec, first_ts, now, max_runs)?
+    };
+    let mut num_skipped = 0;
+    let mut ts = first_ts;
+    while ts < next_ts && num_skipped < MAX_SKIPPED_RUNS_COUNTED {
+        num_skipped += 1;
+        ts = following_ts(cron_spec, ts)?;
+    }
+    Ok(SkippedRuns {
+        num_skipped,
+        next_ts,
+    })
+}
+
+/// The earliest of the last `max_runs` runs scheduled at or before `now`.
+/// Calendar schedules can't be walked backwards, so binary search for the
+/// latest start time that still has `max_runs` runs up to `now`. Each probe
+/// walks at most `max_runs` runs.
+fn first_kept_calendar_run(
+    cron_spec: &CronSpec,
+    first_ts: Timestamp,
+    now: Timestamp,
+    max_runs: u64,
+) -> anyhow::Result<Timestamp> {
+    let run_at_or_after =
+        |start: i64| compute_next_ts(cron_spec, None, Timestamp::try_from(start - 1)?);
+    let count_runs_from = |start: i64| -> anyhow::Result<u64> {
+        let mut count = 0;
+        let mut ts = run_at_or_after(start)?;
+        while ts <= now && count < max_runs {
+            count += 1;
+            ts = following_ts(cron_spec, ts)?;
+        }
+        Ok(count)
+    };
+    // Invariant: `lo` has `max_runs` runs up to `now` and `hi` has fewer.
+    let mut lo = i64::from(first_ts);
+    let mut hi = i64::from(now) + 1;
+    anyhow::ensure!(
+        count_runs_from(lo)? == max_runs,
+        "Fewer than {max_runs} runs were missed"
+    );
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if count_runs_from(mid)? == max_runs {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    run_at_or_after(lo)
+}
+
+/// Saffron only understands UTC, so we evaluate the cron against wall-clock
+/// time in `tz` (pretending it's UTC) and then map each candidate back to a
+/// real instant:
+///
+/// - Candidates that fall in a DST gap (e.g. 2:30am when clocks spring forward
+///   from 2am to 3am) run at the first valid instant after the gap. Several
+///   candidates in the same gap collapse into one run.
+/// - Candidates that fall in a DST overlap (e.g. 1:30am when clocks fall back
+///   from 2am to 1am) run once, at the earlier instant, unless the schedule
+///   fires every hour, in which case it runs during both passes.
+fn next_after_in_timezone(
+    cron: &Cron,
+    tz: Tz,
+    runs_every_hour: bool,
+    now: DateTime<Utc>,
+) -> anyhow::Result<DateTime<Utc>> {
+    let wall_clock = now.with_timezone(&tz).naive_local();
+    let next = next_after_wall_clock(cron, tz, runs_every_hour, now, wall_clock)?;
+    if runs_every_hour && utc_offset(tz, next) < utc_offset(tz, now) {
+        // Clocks fall back before `next`, so the repeated hour comes first:
+        // search it again from the start of its second pass.
+        let fall_back = offset_transition(tz, now, next);
+        let wall_clock = fall_back.with_timezone(&tz).naive_local() - TimeDelta::seconds(1);
+        let repeated = next_after_wall_clock(cron, tz, true, now, wall_clock)?;
+        return Ok(repeated.min(next));
+    }
+    Ok(next)
+}
+
+fn next_after_wall_clock(
+    cron: &Cron,
+    tz: Tz,
+    runs_every_hour: bool,
+    now: DateTime<Utc>,
+    mut wall_clock: NaiveDateTime,
+) -> anyhow::Result<DateTime<Utc>> {
+    // Candidates only map to instants at or before `now` inside a repeated
+    // hour, so this skips at most an hour's worth of them.
+    loop {
+        let candidate = cron
+            .next_after(Utc.from_utc_datetime(&wall_clock))
+            .context("Could not compute next timestamp for cron")?
+            .naive_utc();
+        let instant = match tz.from_local_datetime(&candidate) {
+            LocalResult::Single(t) => t,
+            LocalResult::Ambiguous(earliest, latest) => {
+                if earliest.with_timezone(&Utc) > now || !runs_every_hour {
+                    earliest
+                } else {
+                    latest
+                }
+            },
+            LocalResult::None => end_of_gap(tz, candidate)?,
+        };
+        let instant = instant.with_timezone(&Utc);
+        if instant > now {
+            return Ok(instant);
+        }
+        wall_clock = candidate;
+    }
+}
+
+fn utc_offset(tz: Tz, t: DateTime<Utc>) -> i32 {
+    tz.offset_from_utc_datetime(&t.naive_utc())
+        .fix()
+        .local_minus_utc()
+}
+
+/// The first instant in `(start, end]` with `end`'s UTC offset.
+fn offset_transition(tz: Tz, start: DateTime<Utc>, end: DateTime<Utc>) -> DateTime<Utc> {
+    let offset = utc_offset(tz, end);
+    let (mut lo, mut hi) = (start, end);
+    while hi - lo > TimeDelta::seconds(1) {
+        let mid = lo + (hi - lo) / 2;
+        if utc_offset(tz, mid) == offset {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    hi
+}
+
+/// The first valid instant after the DST gap containing `wall_clock`.
+fn end_of_gap(tz: Tz, wall_clock: NaiveDateTime) -> anyhow::Result<DateTime<Tz>> {
+    // Gaps are at most a few hours and transitions happen on minute
+    // boundaries, so walk forward a minute at a time.
+    let mut t = wall_clock;
+    for _ in 0..(24 * 60) {
+        t += TimeDelta::minutes(1);
+        if let Some(instant) = tz.from_local_datetime(&t).earliest() {
+            return Ok(instant);
+        }
+    }
+    anyhow::bail!("No valid time in {tz} after {wall_clock}")
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -74,16 +272,40 @@ mod tests {
         Timestamp,
         UdfPath,
     };
-    use value::ConvexArray;
+    use value::{
+        ConvexArray,
+        DeveloperDocumentId,
+    };
 
     use crate::cron_jobs::{
-        next_ts::compute_next_ts,
+        next_ts::{
+            compute_next_ts,
+            compute_ready_ts,
+            following_ts,
+            skip_missed_runs,
+            SkippedRuns,
+        },
         types::{
+            CronCatchUpPolicy,
             CronSchedule,
             CronSpec,
         },
     };
 
+    fn new_cron_spec(cron_schedule: CronSchedule) -> CronSpec {
+        CronSpec {
+            udf_path: UdfPath::from_str("test").unwrap().canonicalize(),
+            udf_args: ConvexArray::try_from(vec![]).unwrap(),
+            cron_schedule,
+            catch_up: CronCatchUpPolicy::RunOnce,
+            jitter_seconds: None,
+        }
+    }
+
+    fn ts(secs: i64) -> Timestamp {
+        Timestamp::try_from(i64::pow(10, 9) * secs).unwrap()
+    }
+
     #[test]
     fn test_compute_next_ts_interval() {
         // Every minute
@@ -91,6 +313,8 @@ mod tests {
             udf_path: UdfPath::from_str("test").unwrap().canonicalize(),
             udf_args: ConvexArray::try_from(vec![]).unwrap(),
             cron_schedule: CronSchedule::Interval { seconds: 60 },
+            catch_up: CronCatchUpPolicy::RunOnce,
+            jitter_seconds: None,
         };
 
         // Mar 01 2023 08:35:00 UTC
@@ -112,7 +336,12 @@ mod tests {
         let cron_spec = CronSpec {
             udf_path: UdfPath::from_str("test").unwrap().canonicalize(),
             udf_args: ConvexArray::try_from(vec![]).unwrap(),
-            cron_schedule: CronSchedule::Hourly { minute_utc: 5 },
+            cron_schedule: CronSchedule::Hourly {
+                minute_utc: 5,
+                timezone: None,
+            },
+            catch_up: CronCatchUpPolicy::RunOnce,
+            jitter_seconds: None,
         };
 
         // Mar 01 2023 08:35:00 UTC
@@ -133,7 +362,10 @@ mod tests {
             cron_schedule: CronSchedule::Daily {
                 hour_utc: 8,
                 minute_utc: 30,
+                timezone: None,
             },
+            catch_up: CronCatchUpPolicy::RunOnce,
+            jitter_seconds: None,
         };
 
         // Feb 28 2023 08:35:00 UTC
@@ -155,7 +387,10 @@ mod tests {
                 day_of_week: 2,
                 hour_utc: 12,
                 minute_utc: 30,
+                timezone: None,
             },
+            catch_up: CronCatchUpPolicy::RunOnce,
+            jitter_seconds: None,
         };
 
         // Feb 28 2023 08:35:00 UTC
@@ -177,7 +412,10 @@ mod tests {
                 day: 1,
                 hour_utc: 12,
                 minute_utc: 30,
+                timezone: None,
             },
+            catch_up: CronCatchUpPolicy::RunOnce,
+            jitter_seconds: None,
         };
 
         // Feb 28 2023 08:35:00 UTC
@@ -199,7 +437,10 @@ mod tests {
             udf_args: ConvexArray::try_from(vec![]).unwrap(),
             cron_schedule: CronSchedule::Cron {
                 cron_expr: "0 12 * * 1,5".to_string(),
+                timezone: None,
             },
+            catch_up: CronCatchUpPolicy::RunOnce,
+            jitter_seconds: None,
         };
 
         // Feb 28 2023 08:35:00 UTC
@@ -216,11 +457,208 @@ mod tests {
             udf_args: ConvexArray::try_from(vec![]).unwrap(),
             cron_schedule: CronSchedule::Cron {
                 cron_expr: "0 12 * * 7".to_string(),
+                timezone: None,
             },
+            catch_up: CronCatchUpPolicy::RunOnce,
+            jitter_seconds: None,
         };
         result = compute_next_ts(&cron_spec, prev_ts, now);
         assert!(result.is_err());
         assert!(format!("{:?}", result.unwrap_err())
             .contains("Cron Schedule: Cron parsing from Saffron failed"));
     }
+
+    #[test]
+    fn test_compute_next_ts_timezone() -> anyhow::Result<()> {
+        // Every weekday at 9:00 in New York
+        let cron_spec = new_cron_spec(CronSchedule::Cron {
+            cron_expr: "0 9 * * 1-5".to_string(),
+            timezone: Some("America/New_York".parse()?),
+        });
+
+        // Fri Mar 10 2023 15:00:00 UTC (EST)
+        let next = compute_next_ts(&cron_spec, None, ts(1678460400))?;
+        // Mon Mar 13 2023 13:00:00 UTC (9:00 EDT)
+        assert_eq!(next, ts(1678712400));
+
+        // Thu Mar 09 2023 12:00:00 UTC (EST)
+        let next = compute_next_ts(&cron_spec, None, ts(1678363200))?;
+        // Thu Mar 09 2023 14:00:00 UTC (9:00 EST)
+        assert_eq!(next, ts(1678370400));
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_next_ts_dst_gap() -> anyhow::Result<()> {
+        // Every day at 2:30 in New York, which doesn't exist on Mar 12 2023
+        let cron_spec = new_cron_spec(CronSchedule::Daily {
+            hour_utc: 2,
+            minute_utc: 30,
+            timezone: Some("America/New_York".parse()?),
+        });
+
+        // Mar 12 2023 05:00:00 UTC (00:00 EST)
+        let next = compute_next_ts(&cron_spec, None, ts(1678597200))?;
+        // Mar 12 2023 07:00:00 UTC (03:00 EDT), the end of the gap
+        assert_eq!(next, ts(1678604400));
+
+        let next = compute_next_ts(&cron_spec, Some(next), next)?;
+        // Mar 13 2023 06:30:00 UTC (02:30 EDT)
+        assert_eq!(next, ts(1678689000));
+
+        // Every 15 minutes only runs once for the whole gap.
+        let cron_spec = new_cron_spec(CronSchedule::Cron {
+            cron_expr: "*/15 * * * *".to_string(),
+            timezone: Some("America/New_York".parse()?),
+        });
+        // Mar 12 2023 06:50:00 UTC (01:50 EST)
+        let next = compute_next_ts(&cron_spec, None, ts(1678603800))?;
+        // Mar 12 2023 07:00:00 UTC (03:00 EDT)
+        assert_eq!(next, ts(1678604400));
+        let next = compute_next_ts(&cron_spec, Some(next), next)?;
+        // Mar 12 2023 07:15:00 UTC (03:15 EDT)
+        assert_eq!(next, ts(1678605300));
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_next_ts_dst_overlap() -> anyhow::Result<()> {
+        // Every day at 1:30 in New York, which happens twice on Nov 5 2023
+        let cron_spec = new_cron_spec(CronSchedule::Daily {
+            hour_utc: 1,
+            minute_utc: 30,
+            timezone: Some("America/New_York".parse()?),
+        });
+
+        // Nov 05 2023 04:00:00 UTC (00:00 EDT)
+        let next = compute_next_ts(&cron_spec, None, ts(1699156800))?;
+        // Nov 05 2023 05:30:00 UTC (01:30 EDT), the first 1:30
+        assert_eq!(next, ts(1699162200));
+
+        // The second 1:30 is skipped.
+        let next = compute_next_ts(&cron_spec, Some(next), next)?;
+        // Nov 06 2023 06:30:00 UTC (01:30 EST)
+        assert_eq!(next, ts(1699252200));
+
+        // Hourly schedules run during both passes of the repeated hour.
+        let cron_spec = new_cron_spec(CronSchedule::Hourly {
+            minute_utc: 30,
+            timezone: Some("America/New_York".parse()?),
+        });
+        // Nov 05 2023 05:45:00 UTC (01:45 EDT)
+        let next = compute_next_ts(&cron_spec, None, ts(1699163100))?;
+        // Nov 05 2023 06:30:00 UTC (01:30 EST)
+        assert_eq!(next, ts(1699165800));
+        let next = compute_next_ts(&cron_spec, Some(next), next)?;
+        // Nov 05 2023 07:30:00 UTC (02:30 EST)
+        assert_eq!(next, ts(1699169400));
+        Ok(())
+    }
+
+    #[test]
+    fn test_following_ts() -> anyhow::Result<()> {
+        // Unlike `compute_next_ts`, wall-clock schedules don't skip ahead to now.
+        let cron_spec = new_cron_spec(CronSchedule::Hourly {
+            minute_utc: 0,
+            timezone: None,
+        });
+        // Mar 01 2023 08:00:00 UTC
+        let next = following_ts(&cron_spec, ts(1677657600))?;
+        // Mar 01 2023 09:00:00 UTC
+        assert_eq!(next, ts(1677661200));
+
+        let cron_spec = new_cron_spec(CronSchedule::Interval { seconds: 60 });
+        assert_eq!(following_ts(&cron_spec, ts(1677657600))?, ts(1677657660));
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_ready_ts() -> anyhow::Result<()> {
+        let mut cron_spec = new_cron_spec(CronSchedule::Interval { seconds: 60 });
+        let next_ts = ts(1677657600);
+        assert_eq!(
+            compute_ready_ts(&cron_spec, DeveloperDocumentId::MIN, next_ts)?,
+            next_ts
+        );
+
+        cron_spec.jitter_seconds = Some(30);
+        let ready_ts = compute_ready_ts(&cron_spec, DeveloperDocumentId::MIN, next_ts)?;
+        assert!(ready_ts >= next_ts);
+        assert!(ready_ts < ts(1677657630));
+        // The delay is stable for the same job and scheduled time.
+        assert_eq!(
+            compute_ready_ts(&cron_spec, DeveloperDocumentId::MIN, next_ts)?,
+            ready_ts
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_ready_ts_is_fixed() -> anyhow::Result<()> {
+        // The delay must not change across releases, or every job's runs would
+        // shift on upgrade.
+        let mut cron_spec = new_cron_spec(CronSchedule::Interval { seconds: 60 });
+        cron_spec.jitter_seconds = Some(30);
+        let next_ts = ts(1677657600);
+        let ready_ts = compute_ready_ts(&cron_spec, DeveloperDocumentId::MIN, next_ts)?;
+        assert_eq!(ready_ts, Timestamp::try_from(1677657608248000000)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_skip_missed_runs_interval() -> anyhow::Result<()> {
+        let cron_spec = new_cron_spec(CronSchedule::Interval { seconds: 60 });
+        // A year of missed runs, keeping the last 3.
+        let first_ts = ts(1677657600);
+        let now = ts(1677657600 + 365 * 24 * 60 * 60 + 30);
+        let skipped = skip_missed_runs(&cron_spec, first_ts, now, 3)?;
+        assert_eq!(
+            skipped,
+            SkippedRuns {
+                num_skipped: 365 * 24 * 60 + 1 - 3,
+                next_ts: ts(1677657600 + 365 * 24 * 60 * 60 - 120),
+            }
+        );
+
+        // Skipping everything resumes with the first run after `now`.
+        let skipped = skip_missed_runs(&cron_spec, first_ts, now, 0)?;
+        assert_eq!(skipped.next_ts, ts(1677657600 + 365 * 24 * 60 * 60 + 60));
+        Ok(())
+    }
+
+    #[test]
+    fn test_skip_missed_runs_calendar() -> anyhow::Result<()> {
+        let cron_spec = new_cron_spec(CronSchedule::Hourly {
+            minute_utc: 0,
+            timezone: None,
+        });
+        // Wed Mar 01 2023 08:00:00 UTC through 10 days later at 08:30.
+        let first_ts = ts(1677657600);
+        let now = ts(1677657600 + 10 * 24 * 60 * 60 + 30 * 60);
+        let skipped = skip_missed_runs(&cron_spec, first_ts, now, 2)?;
+        assert_eq!(
+            skipped,
+            SkippedRuns {
+                num_skipped: 10 * 24 + 1 - 2,
+                // Keep the runs at 07:00 and 08:00 on the last day.
+                next_ts: ts(1677657600 + 10 * 24 * 60 * 60 - 60 * 60),
+            }
+        );
+
+        let skipped = skip_missed_runs(&cron_spec, first_ts, now, 0)?;
+        assert_eq!(
+            skipped,
+            SkippedRuns {
+                num_skipped: 10 * 24 + 1,
+                next_ts: ts(1677657600 + 10 * 24 * 60 * 60 + 60 * 60),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_timezone() {
+        let result = "Mars/Olympus_Mons".parse::<crate::cron_jobs::types::CronTimezone>();
+        assert!(result.is_err());
+    }
 }
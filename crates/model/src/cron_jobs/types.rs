@@ -10,6 +10,7 @@ use anyhow::{
     bail,
     Context,
 };
+use chrono_tz::Tz;
 use common::{
     components::ComponentId,
     document::ParsedDocument,
@@ -18,6 +19,7 @@ use common::{
     types::Timestamp,
     RequestId,
 };
+use errors::ErrorMetadata;
 use saffron::Cron;
 use serde::{
     Deserialize,
@@ -48,8 +50,18 @@ pub enum CronValidationError {
     SecondsMinutesHours,
     #[error("Interval must be an integer greater than 0")]
     InvalidIntervalValue,
+    #[error("catchUp max must be an integer between 1 and {MAX_CRON_CATCH_UP_RUNS}")]
+    InvalidCatchUpMax,
+    #[error("jitterSeconds must be an integer between 0 and {MAX_CRON_JITTER_SECONDS}")]
+    InvalidJitterSeconds,
 }
 
+/// Upper bound on `CronCatchUpPolicy::RunAll { max }`.
+pub const MAX_CRON_CATCH_UP_RUNS: u32 = 1000;
+
+/// Upper bound on `CronSpec::jitter_seconds`.
+pub const MAX_CRON_JITTER_SECONDS: u32 = 60 * 60;
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub struct CronJob {
@@ -65,6 +77,9 @@ pub struct CronJob {
     // Cron-related metadata specified by the user and updated on pushes
     pub cron_spec: CronSpec,
 
+    // Set by admins to stop the cron from running. Preserved across pushes.
+    pub paused: bool,
+
     // Internally tracked metadata to execute the current run of the cron
     pub state: CronJobState,
     pub prev_ts: Option<Timestamp>,
@@ -83,6 +98,7 @@ impl CronJob {
             component,
             name: cron.name,
             cron_spec: cron.cron_spec,
+            paused: cron.paused,
             state: next_run.state,
             prev_ts: next_run.prev_ts,
             next_ts: next_run.next_ts,
@@ -93,6 +109,7 @@ impl CronJob {
         CronJobMetadata {
             name: self.name.clone(),
             cron_spec: self.cron_spec.clone(),
+            paused: self.paused,
         }
     }
 
@@ -114,6 +131,9 @@ pub struct CronJobMetadata {
 
     // Cron-related metadata specified by the user and updated on pushes
     pub cron_spec: CronSpec,
+
+    // Set by admins to stop the cron from running. Preserved across pushes.
+    pub paused: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -121,6 +141,8 @@ pub struct CronJobMetadata {
 pub struct SerializedCronJobMetadata {
     name: String,
     cron_spec: SerializedCronSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paused: Option<bool>,
 }
 
 impl TryFrom<CronJobMetadata> for SerializedCronJobMetadata {
@@ -130,6 +152,7 @@ impl TryFrom<CronJobMetadata> for SerializedCronJobMetadata {
         Ok(Self {
             name: job.name.to_string(),
             cron_spec: job.cron_spec.try_into()?,
+            paused: job.paused.then_some(true),
         })
     }
 }
@@ -141,6 +164,7 @@ impl TryFrom<SerializedCronJobMetadata> for CronJobMetadata {
         Ok(Self {
             name: value.name.parse()?,
             cron_spec: value.cron_spec.try_into()?,
+            paused: value.paused.unwrap_or(false),
         })
     }
 }
@@ -219,6 +243,10 @@ pub struct CronSpec {
     )]
     pub udf_args: ConvexArray,
     pub cron_schedule: CronSchedule,
+    pub catch_up: CronCatchUpPolicy,
+    // Runs are delayed by a random-looking but stable offset within this
+    // window so that crons sharing a schedule don't all start at once.
+    pub jitter_seconds: Option<u32>,
 }
 
 impl HeapSize for CronSpec {
@@ -234,6 +262,10 @@ pub struct SerializedCronSpec {
     #[serde(with = "serde_bytes")]
     udf_args: Option<Vec<u8>>,
     cron_schedule: SerializedCronSchedule,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    catch_up: Option<SerializedCronCatchUpPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    jitter_seconds: Option<i64>,
 }
 
 impl TryFrom<CronSpec> for SerializedCronSpec {
@@ -247,6 +279,8 @@ impl TryFrom<CronSpec> for SerializedCronSpec {
             udf_path: String::from(spec.udf_path),
             udf_args: Some(udf_args_bytes),
             cron_schedule: spec.cron_schedule.try_into()?,
+            catch_up: (spec.catch_up != CronCatchUpPolicy::default()).then(|| spec.catch_up.into()),
+            jitter_seconds: spec.jitter_seconds.map(i64::from),
         })
     }
 }
@@ -264,10 +298,18 @@ impl TryFrom<SerializedCronSpec> for CronSpec {
             None => ConvexArray::try_from(vec![])?,
         };
         let cron_schedule = value.cron_schedule.try_into()?;
+        let catch_up = value
+            .catch_up
+            .map(CronCatchUpPolicy::try_from)
+            .transpose()?
+            .unwrap_or_default();
+        let jitter_seconds = value.jitter_seconds.map(u32::try_from).transpose()?;
         Ok(Self {
             udf_path,
             udf_args,
             cron_schedule,
+            catch_up,
+            jitter_seconds,
         })
     }
 }
@@ -312,6 +354,7 @@ impl TryFrom<JsonValue> for CronSpec {
             Hourly {
                 #[serde(rename = "minuteUTC")]
                 minute_utc: i64,
+                timezone: Option<String>,
             },
             #[serde(rename = "daily")]
             Daily {
@@ -319,6 +362,7 @@ impl TryFrom<JsonValue> for CronSpec {
                 minute_utc: i64,
                 #[serde(rename = "hourUTC")]
                 hour_utc: i64,
+                timezone: Option<String>,
             },
             #[serde(rename_all = "camelCase")]
             #[serde(rename = "weekly")]
@@ -328,6 +372,7 @@ impl TryFrom<JsonValue> for CronSpec {
                 #[serde(rename = "hourUTC")]
                 hour_utc: i64,
                 day_of_week: DayOfWeek,
+                timezone: Option<String>,
             },
             #[serde(rename_all = "camelCase")]
             #[serde(rename = "monthly")]
@@ -337,10 +382,22 @@ impl TryFrom<JsonValue> for CronSpec {
                 #[serde(rename = "hourUTC")]
                 hour_utc: i64,
                 day: i64,
+                timezone: Option<String>,
             },
             #[serde(rename_all = "camelCase")]
             #[serde(rename = "cron")]
-            Cron { cron: String },
+            Cron {
+                cron: String,
+                timezone: Option<String>,
+            },
+        }
+
+        #[derive(Deserialize)]
+        #[serde(tag = "type", rename_all = "camelCase")]
+        enum CatchUpJson {
+            Skip,
+            RunOnce,
+            RunAll { max: i64 },
         }
 
         // The JavaScript object produced by crons.export() uses different names:
@@ -351,6 +408,8 @@ impl TryFrom<JsonValue> for CronSpec {
             name: String,
             args: JsonValue,
             schedule: ScheduleJson,
+            catch_up: Option<CatchUpJson>,
+            jitter_seconds: Option<i64>,
         }
         let j: CronSpecJson = serde_json::from_value(value.clone())
             .with_context(|| CronValidationError::InvalidJson)?;
@@ -386,18 +445,25 @@ impl TryFrom<JsonValue> for CronSpec {
 
                 CronSchedule::Interval { seconds }
             },
-            ScheduleJson::Hourly { minute_utc } => {
+            ScheduleJson::Hourly {
+                minute_utc,
+                timezone,
+            } => {
                 if !(0..=59).contains(&minute_utc) {
                     anyhow::bail!(
                         "minuteUTC must be 0-59 in {}",
                         serde_json::to_string_pretty(&value).unwrap()
                     );
                 }
-                CronSchedule::Hourly { minute_utc }
+                CronSchedule::Hourly {
+                    minute_utc,
+                    timezone: timezone.as_deref().map(str::parse).transpose()?,
+                }
             },
             ScheduleJson::Daily {
                 minute_utc,
                 hour_utc,
+                timezone,
             } => {
                 if !(0..=59).contains(&minute_utc) {
                     anyhow::bail!(
@@ -414,12 +480,14 @@ impl TryFrom<JsonValue> for CronSpec {
                 CronSchedule::Daily {
                     minute_utc,
                     hour_utc,
+                    timezone: timezone.as_deref().map(str::parse).transpose()?,
                 }
             },
             ScheduleJson::Weekly {
                 minute_utc,
                 hour_utc,
                 day_of_week,
+                timezone,
             } => {
                 if !(0..=59).contains(&minute_utc) {
                     anyhow::bail!(
@@ -445,12 +513,14 @@ impl TryFrom<JsonValue> for CronSpec {
                         DayOfWeek::Friday => 5,
                         DayOfWeek::Saturday => 6,
                     },
+                    timezone: timezone.as_deref().map(str::parse).transpose()?,
                 }
             },
             ScheduleJson::Monthly {
                 minute_utc,
                 hour_utc,
                 day,
+                timezone,
             } => {
                 if !(0..=59).contains(&minute_utc) {
                     anyhow::bail!(
@@ -474,13 +544,39 @@ impl TryFrom<JsonValue> for CronSpec {
                     day,
                     hour_utc,
                     minute_utc,
+                    timezone: timezone.as_deref().map(str::parse).transpose()?,
                 }
             },
-            ScheduleJson::Cron { cron } => {
+            ScheduleJson::Cron { cron, timezone } => {
                 cron.parse::<saffron::Cron>()?;
-                CronSchedule::Cron { cron_expr: cron }
+                CronSchedule::Cron {
+                    cron_expr: cron,
+                    timezone: timezone.as_deref().map(str::parse).transpose()?,
+                }
+            },
+        };
+
+        let catch_up = match j.catch_up {
+            None | Some(CatchUpJson::RunOnce) => CronCatchUpPolicy::RunOnce,
+            Some(CatchUpJson::Skip) => CronCatchUpPolicy::Skip,
+            Some(CatchUpJson::RunAll { max }) => {
+                let max = u32::try_from(max)
+                    .ok()
+                    .filter(|max| (1..=MAX_CRON_CATCH_UP_RUNS).contains(max))
+                    .ok_or(CronValidationError::InvalidCatchUpMax)?;
+                CronCatchUpPolicy::RunAll { max }
             },
         };
+        let jitter_seconds = j
+            .jitter_seconds
+            .map(|jitter| {
+                u32::try_from(jitter)
+                    .ok()
+                    .filter(|jitter| *jitter <= MAX_CRON_JITTER_SECONDS)
+                    .ok_or(CronValidationError::InvalidJitterSeconds)
+            })
+            .transpose()?
+            .filter(|jitter| *jitter > 0);
 
         let udf_path: UdfPath = j.name.parse()?;
         let udf_path_canonicalized = udf_path.canonicalize();
@@ -488,6 +584,8 @@ impl TryFrom<JsonValue> for CronSpec {
             udf_path: udf_path_canonicalized,
             udf_args: ConvexArray::try_from(j.args)?,
             cron_schedule: schedule,
+            catch_up,
+            jitter_seconds,
         })
     }
 }
@@ -508,6 +606,102 @@ pub enum CronJobState {
     },
 }
 
+/// What to do with scheduled runs that were missed while the cron couldn't
+/// execute, e.g. during a backend outage or while the deployment was paused.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum CronCatchUpPolicy {
+    /// Drop every missed run and wait for the next scheduled time.
+    Skip,
+    /// Run once to cover all the missed runs, then resume the schedule.
+    #[default]
+    RunOnce,
+    /// Run each missed run back to back, keeping at most the `max` most recent.
+    RunAll {
+        #[cfg_attr(
+            any(test, feature = "testing"),
+            proptest(strategy = "1..=MAX_CRON_CATCH_UP_RUNS")
+        )]
+        max: u32,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum SerializedCronCatchUpPolicy {
+    Skip,
+    RunOnce,
+    RunAll { max: i64 },
+}
+
+impl From<CronCatchUpPolicy> for SerializedCronCatchUpPolicy {
+    fn from(policy: CronCatchUpPolicy) -> Self {
+        match policy {
+            CronCatchUpPolicy::Skip => Self::Skip,
+            CronCatchUpPolicy::RunOnce => Self::RunOnce,
+            CronCatchUpPolicy::RunAll { max } => Self::RunAll { max: max.into() },
+        }
+    }
+}
+
+impl TryFrom<SerializedCronCatchUpPolicy> for CronCatchUpPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedCronCatchUpPolicy) -> anyhow::Result<Self, Self::Error> {
+        match value {
+            SerializedCronCatchUpPolicy::Skip => Ok(Self::Skip),
+            SerializedCronCatchUpPolicy::RunOnce => Ok(Self::RunOnce),
+            SerializedCronCatchUpPolicy::RunAll { max } => Ok(Self::RunAll {
+                max: max.try_into()?,
+            }),
+        }
+    }
+}
+
+/// An IANA timezone (e.g. "America/New_York") that a cron schedule's
+/// wall-clock fields are interpreted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CronTimezone(Tz);
+
+impl CronTimezone {
+    pub fn tz(&self) -> Tz {
+        self.0
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl FromStr for CronTimezone {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tz = s.parse::<Tz>().map_err(|_| {
+            anyhow::anyhow!(ErrorMetadata::bad_request(
+                "InvalidCronTimezone",
+                format!("{s:?} is not a valid IANA timezone name, e.g. \"America/New_York\"."),
+            ))
+        })?;
+        Ok(Self(tz))
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl proptest::arbitrary::Arbitrary for CronTimezone {
+    type Parameters = ();
+
+    type Strategy = impl proptest::strategy::Strategy<Value = CronTimezone>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+        proptest::sample::select(chrono_tz::TZ_VARIANTS.to_vec()).prop_map(CronTimezone)
+    }
+}
+
+/// When `timezone` is set, the `_utc` fields are wall-clock times in that
+/// timezone rather than in UTC. The field names are kept for compatibility
+/// with stored schedules.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 pub enum CronSchedule {
@@ -516,23 +710,28 @@ pub enum CronSchedule {
     },
     Hourly {
         minute_utc: i64,
+        timezone: Option<CronTimezone>,
     },
     Daily {
         hour_utc: i64,
         minute_utc: i64,
+        timezone: Option<CronTimezone>,
     },
     Weekly {
         day_of_week: i64,
         hour_utc: i64,
         minute_utc: i64,
+        timezone: Option<CronTimezone>,
     },
     Monthly {
         day: i64,
         hour_utc: i64,
         minute_utc: i64,
+        timezone: Option<CronTimezone>,
     },
     Cron {
         cron_expr: String,
+        timezone: Option<CronTimezone>,
     },
 }
 
@@ -543,7 +742,7 @@ impl HeapSize for CronSchedule {
             CronSchedule::Hourly { .. } => mem::size_of::<i64>(),
             CronSchedule::Daily { .. } => 2 * mem::size_of::<i64>(),
             CronSchedule::Monthly { .. } | CronSchedule::Weekly { .. } => 3 * mem::size_of::<i64>(),
-            CronSchedule::Cron { cron_expr } => cron_expr.heap_size(),
+            CronSchedule::Cron { cron_expr, .. } => cron_expr.heap_size(),
         }
     }
 }
@@ -557,12 +756,16 @@ pub enum SerializedCronSchedule {
     Hourly {
         #[serde(rename = "minuteUTC")]
         minute_utc: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timezone: Option<String>,
     },
     Daily {
         #[serde(rename = "hourUTC")]
         hour_utc: i64,
         #[serde(rename = "minuteUTC")]
         minute_utc: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timezone: Option<String>,
     },
     Weekly {
         #[serde(rename = "dayOfWeek")]
@@ -571,6 +774,8 @@ pub enum SerializedCronSchedule {
         hour_utc: i64,
         #[serde(rename = "minuteUTC")]
         minute_utc: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timezone: Option<String>,
     },
     Monthly {
         day: i64,
@@ -578,10 +783,14 @@ pub enum SerializedCronSchedule {
         hour_utc: i64,
         #[serde(rename = "minuteUTC")]
         minute_utc: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timezone: Option<String>,
     },
     #[serde(rename_all = "camelCase")]
     Cron {
         cron_expr: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timezone: Option<String>,
     },
 }
 
@@ -591,33 +800,51 @@ impl TryFrom<CronSchedule> for SerializedCronSchedule {
     fn try_from(schedule: CronSchedule) -> anyhow::Result<Self, Self::Error> {
         match schedule {
             CronSchedule::Interval { seconds } => Ok(Self::Interval { seconds }),
-            CronSchedule::Hourly { minute_utc } => Ok(Self::Hourly { minute_utc }),
+            CronSchedule::Hourly {
+                minute_utc,
+                timezone,
+            } => Ok(Self::Hourly {
+                minute_utc,
+                timezone: timezone.map(|tz| tz.name().to_string()),
+            }),
             CronSchedule::Daily {
                 hour_utc,
                 minute_utc,
+                timezone,
             } => Ok(Self::Daily {
                 hour_utc,
                 minute_utc,
+                timezone: timezone.map(|tz| tz.name().to_string()),
             }),
             CronSchedule::Weekly {
                 day_of_week,
                 hour_utc,
                 minute_utc,
+                timezone,
             } => Ok(Self::Weekly {
                 day_of_week,
                 hour_utc,
                 minute_utc,
+                timezone: timezone.map(|tz| tz.name().to_string()),
             }),
             CronSchedule::Monthly {
                 day,
                 hour_utc,
                 minute_utc,
+                timezone,
             } => Ok(Self::Monthly {
                 day,
                 hour_utc,
                 minute_utc,
+                timezone: timezone.map(|tz| tz.name().to_string()),
+            }),
+            CronSchedule::Cron {
+                cron_expr,
+                timezone,
+            } => Ok(Self::Cron {
+                cron_expr,
+                timezone: timezone.map(|tz| tz.name().to_string()),
             }),
-            CronSchedule::Cron { cron_expr } => Ok(Self::Cron { cron_expr }),
         }
     }
 }
@@ -628,35 +855,51 @@ impl TryFrom<SerializedCronSchedule> for CronSchedule {
     fn try_from(value: SerializedCronSchedule) -> anyhow::Result<Self, Self::Error> {
         match value {
             SerializedCronSchedule::Interval { seconds } => Ok(CronSchedule::Interval { seconds }),
-            SerializedCronSchedule::Hourly { minute_utc } => {
-                Ok(CronSchedule::Hourly { minute_utc })
-            },
+            SerializedCronSchedule::Hourly {
+                minute_utc,
+                timezone,
+            } => Ok(CronSchedule::Hourly {
+                minute_utc,
+                timezone: timezone.as_deref().map(str::parse).transpose()?,
+            }),
             SerializedCronSchedule::Daily {
                 hour_utc,
                 minute_utc,
+                timezone,
             } => Ok(CronSchedule::Daily {
                 hour_utc,
                 minute_utc,
+                timezone: timezone.as_deref().map(str::parse).transpose()?,
             }),
             SerializedCronSchedule::Weekly {
                 day_of_week,
                 hour_utc,
                 minute_utc,
+                timezone,
             } => Ok(CronSchedule::Weekly {
                 day_of_week,
                 hour_utc,
                 minute_utc,
+                timezone: timezone.as_deref().map(str::parse).transpose()?,
             }),
             SerializedCronSchedule::Monthly {
                 day,
                 hour_utc,
                 minute_utc,
+                timezone,
             } => Ok(CronSchedule::Monthly {
                 day,
                 hour_utc,
                 minute_utc,
+                timezone: timezone.as_deref().map(str::parse).transpose()?,
+            }),
+            SerializedCronSchedule::Cron {
+                cron_expr,
+                timezone,
+            } => Ok(CronSchedule::Cron {
+                cron_expr,
+                timezone: timezone.as_deref().map(str::parse).transpose()?,
             }),
-            SerializedCronSchedule::Cron { cron_expr } => Ok(CronSchedule::Cron { cron_expr }),
         }
     }
 }
@@ -679,23 +922,28 @@ pub enum CronScheduleProductAnalysis {
     },
     Hourly {
         minute_utc: i64,
+        timezone: Option<String>,
     },
     Daily {
         hour_utc: i64,
         minute_utc: i64,
+        timezone: Option<String>,
     },
     Weekly {
         day_of_week: i64,
         hour_utc: i64,
         minute_utc: i64,
+        timezone: Option<String>,
     },
     Monthly {
         day: i64,
         hour_utc: i64,
         minute_utc: i64,
+        timezone: Option<String>,
     },
     Cron {
         cron_expr: String,
+        timezone: Option<String>,
     },
 }
 
@@ -703,52 +951,105 @@ impl From<CronSchedule> for CronScheduleProductAnalysis {
     fn from(schedule: CronSchedule) -> Self {
         match schedule {
             CronSchedule::Interval { seconds } => Self::Interval { seconds },
-            CronSchedule::Hourly { minute_utc } => Self::Hourly { minute_utc },
+            CronSchedule::Hourly {
+                minute_utc,
+                timezone,
+            } => Self::Hourly {
+                minute_utc,
+                timezone: timezone.map(|tz| tz.name().to_string()),
+            },
             CronSchedule::Daily {
                 hour_utc,
                 minute_utc,
+                timezone,
             } => Self::Daily {
                 hour_utc,
                 minute_utc,
+                timezone: timezone.map(|tz| tz.name().to_string()),
             },
             CronSchedule::Weekly {
                 day_of_week,
                 hour_utc,
                 minute_utc,
+                timezone,
             } => Self::Weekly {
                 day_of_week,
                 hour_utc,
                 minute_utc,
+                timezone: timezone.map(|tz| tz.name().to_string()),
             },
             CronSchedule::Monthly {
                 day,
                 hour_utc,
                 minute_utc,
+                timezone,
             } => Self::Monthly {
                 day,
                 hour_utc,
                 minute_utc,
+                timezone: timezone.map(|tz| tz.name().to_string()),
+            },
+            CronSchedule::Cron {
+                cron_expr,
+                timezone,
+            } => Self::Cron {
+                cron_expr,
+                timezone: timezone.map(|tz| tz.name().to_string()),
             },
-            CronSchedule::Cron { cron_expr } => Self::Cron { cron_expr },
         }
     }
 }
 
 impl CronSchedule {
     pub fn validate_format(&self) -> anyhow::Result<()> {
-        let _: Cron = match self.clone() {
-            CronSchedule::Interval { seconds } => {
-                if seconds <= 0 {
-                    bail!("CronSchedule intervals must have a positive duration.");
-                }
-                return Ok(());
+        if let CronSchedule::Interval { seconds } = self {
+            if *seconds <= 0 {
+                bail!("CronSchedule intervals must have a positive duration.");
+            }
+            return Ok(());
+        }
+        self.cron()?;
+        Ok(())
+    }
+
+    /// The timezone wall-clock schedules are evaluated in, defaulting to UTC.
+    pub fn timezone(&self) -> Tz {
+        let timezone = match self {
+            CronSchedule::Interval { .. } => None,
+            CronSchedule::Hourly { timezone, .. }
+            | CronSchedule::Daily { timezone, .. }
+            | CronSchedule::Weekly { timezone, .. }
+            | CronSchedule::Monthly { timezone, .. }
+            | CronSchedule::Cron { timezone, .. } => *timezone,
+        };
+        timezone.map(|tz| tz.tz()).unwrap_or(Tz::UTC)
+    }
+
+    /// Whether the schedule fires at every hour of the day. Such schedules
+    /// run during both passes of a repeated hour when clocks fall back.
+    pub(crate) fn runs_every_hour(&self) -> bool {
+        match self {
+            CronSchedule::Hourly { .. } => true,
+            CronSchedule::Cron { cron_expr, .. } => {
+                cron_expr.split_whitespace().nth(1) == Some("*")
+            },
+            _ => false,
+        }
+    }
+
+    /// The wall-clock cron expression for non-interval schedules.
+    pub(crate) fn cron(&self) -> anyhow::Result<Cron> {
+        let cron = match self {
+            CronSchedule::Interval { .. } => {
+                bail!("Interval schedules don't have a cron expression")
             },
-            CronSchedule::Hourly { minute_utc } => format!("{minute_utc} * * * *")
+            CronSchedule::Hourly { minute_utc, .. } => format!("{minute_utc} * * * *")
                 .parse()
                 .context("Hourly Schedule: Cron parsing from Saffron failed")?,
             CronSchedule::Daily {
                 hour_utc,
                 minute_utc,
+                ..
             } => format!("{minute_utc} {hour_utc} * * *")
                 .parse()
                 .context("Daily Schedule: Cron parsing from Saffron failed")?,
@@ -756,6 +1057,7 @@ impl CronSchedule {
                 day_of_week,
                 hour_utc,
                 minute_utc,
+                ..
             } => format!("{minute_utc} {hour_utc} * * {day_of_week}")
                 .parse()
                 .context("Weekly Schedule: Cron parsing from Saffron failed")?,
@@ -763,14 +1065,15 @@ impl CronSchedule {
                 day,
                 hour_utc,
                 minute_utc,
+                ..
             } => format!("{minute_utc} {hour_utc} {day} * *")
                 .parse()
                 .context("Monthly Schedule: Cron parsing from Saffron failed")?,
-            CronSchedule::Cron { cron_expr } => cron_expr
+            CronSchedule::Cron { cron_expr, .. } => cron_expr
                 .parse()
                 .context("Cron Schedule: Cron parsing from Saffron failed")?,
         };
-        Ok(())
+        Ok(cron)
     }
 }
 
@@ -1079,6 +1382,7 @@ impl TryFrom<ConvexObject> for CronJobLogLines {
 mod tests {
     use cmd_util::env::env_config;
     use proptest::prelude::*;
+    use serde_json::json;
     use sync_types::testing::assert_roundtrips;
     use value::{
         assert_obj,
@@ -1087,11 +1391,13 @@ mod tests {
     };
 
     use crate::cron_jobs::types::{
+        CronCatchUpPolicy,
         CronJobLog,
         CronJobLogLines,
         CronJobMetadata,
         CronJobResult,
         CronJobStatus,
+        CronSpec,
     };
 
     proptest! {
@@ -1134,6 +1440,39 @@ mod tests {
         );
         assert_roundtrips::<_, CronJobMetadata>(cron_job_obj);
     }
+
+    #[test]
+    fn test_cron_spec_catch_up_and_jitter() -> anyhow::Result<()> {
+        let spec_json = |catch_up: serde_json::Value, jitter_seconds: serde_json::Value| {
+            json!({
+                "name": "crons.js:vacuumOldEntries",
+                "args": [{}],
+                "schedule": {"type": "interval", "minutes": 5},
+                "catchUp": catch_up,
+                "jitterSeconds": jitter_seconds,
+            })
+        };
+
+        let spec = CronSpec::try_from(spec_json(json!(null), json!(null)))?;
+        assert_eq!(spec.catch_up, CronCatchUpPolicy::RunOnce);
+        assert_eq!(spec.jitter_seconds, None);
+
+        let spec = CronSpec::try_from(spec_json(json!({"type": "runAll", "max": 10}), json!(30)))?;
+        assert_eq!(spec.catch_up, CronCatchUpPolicy::RunAll { max: 10 });
+        assert_eq!(spec.jitter_seconds, Some(30));
+        assert_roundtrips::<_, ConvexObject>(spec);
+
+        let spec = CronSpec::try_from(spec_json(json!({"type": "skip"}), json!(0)))?;
+        assert_eq!(spec.catch_up, CronCatchUpPolicy::Skip);
+        assert_eq!(spec.jitter_seconds, None);
+
+        assert!(
+            CronSpec::try_from(spec_json(json!({"type": "runAll", "max": 0}), json!(null)))
+                .is_err()
+        );
+        assert!(CronSpec::try_from(spec_json(json!(null), json!(-1))).is_err());
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
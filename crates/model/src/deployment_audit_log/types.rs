@@ -12,7 +12,10 @@ use common::{
         SerializedNamedDeveloperIndexConfig,
     },
     components::ComponentPath,
-    http::RequestDestination,
+    http::{
+        fetch::EgressPolicy,
+        RequestDestination,
+    },
     log_streaming::{
         LogEvent,
         StructuredLogEvent,
@@ -44,6 +47,7 @@ use value::{
     val,
     ConvexObject,
     ConvexValue,
+    DeveloperDocumentId,
     TableName,
 };
 
@@ -55,6 +59,8 @@ use crate::{
         SerializedComponentDiff,
     },
     config::types::ConfigDiff,
+    cron_jobs::types::CronIdentifier,
+    egress_policy::types::PersistedEgressPolicy,
     environment_variables::types::EnvVarName,
     snapshot_imports::types::{
         ImportFormat,
@@ -133,6 +139,9 @@ pub enum DeploymentAuditLogEvent {
     DeleteCanonicalUrl {
         request_destination: RequestDestination,
     },
+    UpdateEgressPolicy {
+        policy: EgressPolicy,
+    },
     PushConfig {
         config_diff: ConfigDiff,
     },
@@ -169,6 +178,22 @@ pub enum DeploymentAuditLogEvent {
         table_names_deleted: BTreeMap<ComponentPath, Vec<TableName>>,
         table_count_deleted: u64,
     },
+    PauseCronJob {
+        component: ComponentPath,
+        name: CronIdentifier,
+    },
+    ResumeCronJob {
+        component: ComponentPath,
+        name: CronIdentifier,
+    },
+    RunCronJob {
+        component: ComponentPath,
+        name: CronIdentifier,
+    },
+    CancelWorkflow {
+        component: ComponentPath,
+        workflow_id: DeveloperDocumentId,
+    },
 }
 
 impl From<LegacyIndexDiff> for DeploymentAuditLogEvent {
@@ -214,6 +239,7 @@ impl DeploymentAuditLogEvent {
             },
             DeploymentAuditLogEvent::UpdateCanonicalUrl { .. } => "update_canonical_url",
             DeploymentAuditLogEvent::DeleteCanonicalUrl { .. } => "delete_canonical_url",
+            DeploymentAuditLogEvent::UpdateEgressPolicy { .. } => "update_egress_policy",
             DeploymentAuditLogEvent::PushConfig { .. } => "push_config",
             DeploymentAuditLogEvent::PushConfigWithComponents { .. } => {
                 "push_config_with_components"
@@ -222,6 +248,10 @@ impl DeploymentAuditLogEvent {
             DeploymentAuditLogEvent::ChangeDeploymentState { .. } => "change_deployment_state",
             DeploymentAuditLogEvent::SnapshotImport { .. } => "snapshot_import",
             DeploymentAuditLogEvent::ClearTables => "clear_tables",
+            DeploymentAuditLogEvent::PauseCronJob { .. } => "pause_cron_job",
+            DeploymentAuditLogEvent::ResumeCronJob { .. } => "resume_cron_job",
+            DeploymentAuditLogEvent::RunCronJob { .. } => "run_cron_job",
+            DeploymentAuditLogEvent::CancelWorkflow { .. } => "cancel_workflow",
         }
     }
 
@@ -249,6 +279,9 @@ impl DeploymentAuditLogEvent {
             } => {
                 obj!("request_destination" => request_destination.to_string())
             },
+            DeploymentAuditLogEvent::UpdateEgressPolicy { policy } => {
+                ConvexObject::try_from(PersistedEgressPolicy(policy))
+            },
             DeploymentAuditLogEvent::PushConfig { config_diff } => {
                 ConvexObject::try_from(config_diff)
             },
@@ -350,6 +383,19 @@ impl DeploymentAuditLogEvent {
                 )
             },
             DeploymentAuditLogEvent::ClearTables => obj!(),
+            DeploymentAuditLogEvent::PauseCronJob { component, name }
+            | DeploymentAuditLogEvent::ResumeCronJob { component, name }
+            | DeploymentAuditLogEvent::RunCronJob { component, name } => {
+                let component: ConvexValue = component.serialize().try_into()?;
+                obj!("component" => component, "cron_name" => name.to_string())
+            },
+            DeploymentAuditLogEvent::CancelWorkflow {
+                component,
+                workflow_id,
+            } => {
+                let component: ConvexValue = component.serialize().try_into()?;
+                obj!("component" => component, "workflow_id" => workflow_id.to_string())
+            },
         }
     }
 
@@ -419,6 +465,9 @@ impl TryFrom<ConvexObject> for DeploymentAuditLogEvent {
             "delete_canonical_url" => DeploymentAuditLogEvent::DeleteCanonicalUrl {
                 request_destination: remove_string(&mut fields, "request_destination")?.parse()?,
             },
+            "update_egress_policy" => DeploymentAuditLogEvent::UpdateEgressPolicy {
+                policy: PersistedEgressPolicy::try_from(ConvexObject::try_from(fields)?)?.0,
+            },
             "push_config" => DeploymentAuditLogEvent::PushConfig {
                 config_diff: ConvexObject::try_from(fields)?.try_into()?,
             },
@@ -444,6 +493,30 @@ impl TryFrom<ConvexObject> for DeploymentAuditLogEvent {
                 new_state: remove_string(&mut fields, "new_state")?.parse()?,
             },
             "clear_tables" => DeploymentAuditLogEvent::ClearTables,
+            "pause_cron_job" => DeploymentAuditLogEvent::PauseCronJob {
+                component: ComponentPath::deserialize(
+                    remove_nullable_string(&mut fields, "component")?.as_deref(),
+                )?,
+                name: remove_string(&mut fields, "cron_name")?.parse()?,
+            },
+            "resume_cron_job" => DeploymentAuditLogEvent::ResumeCronJob {
+                component: ComponentPath::deserialize(
+                    remove_nullable_string(&mut fields, "component")?.as_deref(),
+                )?,
+                name: remove_string(&mut fields, "cron_name")?.parse()?,
+            },
+            "run_cron_job" => DeploymentAuditLogEvent::RunCronJob {
+                component: ComponentPath::deserialize(
+                    remove_nullable_string(&mut fields, "component")?.as_deref(),
+                )?,
+                name: remove_string(&mut fields, "cron_name")?.parse()?,
+            },
+            "cancel_workflow" => DeploymentAuditLogEvent::CancelWorkflow {
+                component: ComponentPath::deserialize(
+                    remove_nullable_string(&mut fields, "component")?.as_deref(),
+                )?,
+                workflow_id: remove_string(&mut fields, "workflow_id")?.parse()?,
+            },
             "snapshot_import" => {
                 let table_names: BTreeMap<_, _> = remove_vec(&mut fields, "table_names")?
                     .into_iter()
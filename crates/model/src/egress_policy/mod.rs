@@ -0,0 +1,104 @@
+use std::sync::LazyLock;
+
+use common::{
+    document::ParsedDocument,
+    http::fetch::EgressPolicy,
+    runtime::Runtime,
+};
+use database::{
+    SystemMetadataModel,
+    Transaction,
+};
+use value::{
+    TableName,
+    TableNamespace,
+};
+
+use self::types::PersistedEgressPolicy;
+use crate::{
+    SystemIndex,
+    SystemTable,
+};
+
+pub mod types;
+
+pub static EGRESS_POLICY_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_egress_policy"
+        .parse()
+        .expect("Invalid built-in egress_policy table")
+});
+
+/// Holds at most one document: the deployment's policy for outbound `fetch`
+/// requests from actions.
+pub struct EgressPolicyTable;
+
+impl SystemTable for EgressPolicyTable {
+    type Metadata = PersistedEgressPolicy;
+
+    fn table_name() -> &'static TableName {
+        &EGRESS_POLICY_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![]
+    }
+}
+
+pub struct EgressPolicyModel<'a, RT: Runtime> {
+    tx: &'a mut Transaction<RT>,
+}
+
+impl<'a, RT: Runtime> EgressPolicyModel<'a, RT> {
+    pub fn new(tx: &'a mut Transaction<RT>) -> Self {
+        Self { tx }
+    }
+
+    /// Returns the deployment's egress policy, which is empty (allowing all
+    /// requests) if one hasn't been set.
+    pub async fn get(&mut self) -> anyhow::Result<EgressPolicy> {
+        Ok(self
+            .get_inner()
+            .await?
+            .map(|doc| doc.into_value().0)
+            .unwrap_or_default())
+    }
+
+    /// Replaces the deployment's egress policy. Setting an empty policy
+    /// removes it.
+    pub async fn set(&mut self, policy: EgressPolicy) -> anyhow::Result<()> {
+        let existing = self.get_inner().await?;
+        let mut model = SystemMetadataModel::new_global(self.tx);
+        match existing {
+            Some(doc) if policy == EgressPolicy::default() => {
+                model.delete(doc.id()).await?;
+            },
+            Some(doc) => {
+                model
+                    .replace(doc.id(), PersistedEgressPolicy(policy).try_into()?)
+                    .await?;
+            },
+            None if policy == EgressPolicy::default() => {},
+            None => {
+                model
+                    .insert(
+                        &EGRESS_POLICY_TABLE,
+                        PersistedEgressPolicy(policy).try_into()?,
+                    )
+                    .await?;
+            },
+        }
+        Ok(())
+    }
+
+    async fn get_inner(&mut self) -> anyhow::Result<Option<ParsedDocument<PersistedEgressPolicy>>> {
+        let policy = self
+            .tx
+            .query_system(
+                TableNamespace::Global,
+                &SystemIndex::<EgressPolicyTable>::by_id(),
+            )?
+            .unique()
+            .await?;
+        Ok(policy.map(|doc| (*doc).clone()))
+    }
+}
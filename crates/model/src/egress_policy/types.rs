@@ -0,0 +1,84 @@
+use common::http::fetch::{
+    EgressPolicy,
+    EgressRule,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use value::codegen_convex_serialization;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct PersistedEgressPolicy(pub EgressPolicy);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedEgressPolicy {
+    allow: Vec<SerializedEgressRule>,
+    deny: Vec<SerializedEgressRule>,
+    log_requests: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedEgressRule {
+    destination: String,
+    ports: Vec<i64>,
+}
+
+impl From<EgressRule> for SerializedEgressRule {
+    fn from(rule: EgressRule) -> Self {
+        Self {
+            destination: rule.destination.to_string(),
+            ports: rule.ports.into_iter().map(i64::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<SerializedEgressRule> for EgressRule {
+    type Error = anyhow::Error;
+
+    fn try_from(rule: SerializedEgressRule) -> anyhow::Result<Self> {
+        Ok(Self {
+            destination: rule.destination.parse()?,
+            ports: rule
+                .ports
+                .into_iter()
+                .map(u16::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<PersistedEgressPolicy> for SerializedEgressPolicy {
+    fn from(PersistedEgressPolicy(policy): PersistedEgressPolicy) -> Self {
+        Self {
+            allow: policy.allow.into_iter().map(Into::into).collect(),
+            deny: policy.deny.into_iter().map(Into::into).collect(),
+            log_requests: policy.log_requests,
+        }
+    }
+}
+
+impl TryFrom<SerializedEgressPolicy> for PersistedEgressPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(policy: SerializedEgressPolicy) -> anyhow::Result<Self> {
+        Ok(Self(EgressPolicy {
+            allow: policy
+                .allow
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<anyhow::Result<_>>()?,
+            deny: policy
+                .deny
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<anyhow::Result<_>>()?,
+            log_requests: policy.log_requests,
+        }))
+    }
+}
+
+codegen_convex_serialization!(PersistedEgressPolicy, SerializedEgressPolicy);
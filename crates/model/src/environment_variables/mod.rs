@@ -102,6 +102,19 @@ impl PreloadedEnvironmentVariables {
         anyhow::ensure!(var.name() == name, "Invalid environment variable");
         Ok(Some(var.into_value()))
     }
+
+    /// Returns all environment variables without recording any reads. The
+    /// result must not affect the transaction's outcome: use `get` for each
+    /// variable that does.
+    pub fn all_unrecorded(&self) -> anyhow::Result<BTreeMap<EnvVarName, EnvVarValue>> {
+        let mut environment_variables = BTreeMap::new();
+        for doc in self.range.documents_unrecorded() {
+            let doc: ParsedDocument<PersistedEnvironmentVariable> = doc.clone().parse()?;
+            let var = doc.into_value().0;
+            environment_variables.insert(var.name().to_owned(), var.into_value());
+        }
+        Ok(environment_variables)
+    }
 }
 
 impl<'a, RT: Runtime> EnvironmentVariablesModel<'a, RT> {
@@ -342,6 +342,7 @@ pub async fn get_total_file_storage_size<RT: Runtime>(
         let mut tx = db.begin_tx(
             identity.clone(),
             Arc::new(SearchNotEnabled),
+            Arc::new(SearchNotEnabled),
             FunctionUsageTracker::new(),
             virtual_system_mapping().clone(),
         )?;
@@ -150,10 +150,19 @@ use modules::{
     MODULE_INDEX_BY_DELETED,
     MODULE_INDEX_BY_PATH,
 };
+use rate_limits::{
+    RateLimitsTable,
+    RATE_LIMITS_INDEX_BY_NAME_KEY_AND_SHARD,
+    RATE_LIMITS_TABLE,
+};
 use scheduled_jobs::{
+    ScheduledJobQueuesTable,
     ScheduledJobsTable,
+    SCHEDULED_JOB_QUEUES_INDEX_BY_NAME,
+    SCHEDULED_JOB_QUEUES_TABLE,
     SCHEDULED_JOBS_INDEX,
     SCHEDULED_JOBS_INDEX_BY_COMPLETED_TS,
+    SCHEDULED_JOBS_INDEX_BY_QUEUE,
     SCHEDULED_JOBS_INDEX_BY_UDF_PATH,
     SCHEDULED_JOBS_TABLE,
 };
@@ -181,6 +190,21 @@ use value::{
     TableNamespace,
     TableNumber,
 };
+use work_queues::{
+    WorkQueueMessagesTable,
+    WORK_QUEUE_MESSAGES_INDEX_BY_QUEUE_AND_VISIBLE_TS,
+    WORK_QUEUE_MESSAGES_TABLE,
+};
+use workflows::{
+    WorkflowEventsTable,
+    WorkflowStepsTable,
+    WorkflowsTable,
+    WORKFLOWS_TABLE,
+    WORKFLOW_EVENTS_INDEX_BY_WORKFLOW_ID,
+    WORKFLOW_EVENTS_TABLE,
+    WORKFLOW_STEPS_INDEX_BY_WORKFLOW_ID,
+    WORKFLOW_STEPS_TABLE,
+};
 
 use crate::{
     auth::AuthTable,
@@ -195,6 +219,10 @@ use crate::{
         DeploymentAuditLogsTable,
         DEPLOYMENT_AUDIT_LOG_TABLE,
     },
+    egress_policy::{
+        EgressPolicyTable,
+        EGRESS_POLICY_TABLE,
+    },
     environment_variables::EnvironmentVariablesTable,
     exports::ExportsTable,
     external_packages::EXTERNAL_PACKAGES_TABLE,
@@ -212,6 +240,7 @@ pub mod config;
 pub mod cron_jobs;
 pub mod database_globals;
 pub mod deployment_audit_log;
+pub mod egress_policy;
 pub mod environment_variables;
 pub mod exports;
 pub mod external_packages;
@@ -221,11 +250,15 @@ pub mod log_sinks;
 mod metrics;
 pub mod migrations;
 pub mod modules;
+pub mod rate_limits;
 pub mod scheduled_jobs;
 pub mod session_requests;
 pub mod snapshot_imports;
 pub mod source_packages;
 pub mod udf_config;
+pub mod utils;
+pub mod work_queues;
+pub mod workflows;
 
 #[cfg(any(test, feature = "testing"))]
 pub mod test_helpers;
@@ -265,9 +298,16 @@ enum DefaultTableNumber {
     CanonicalUrls = 34,
     CronNextRun = 35,
     IndexBackfills = 36,
+    Workflows = 37,
+    WorkflowSteps = 38,
+    WorkflowEvents = 39,
+    WorkQueueMessages = 40,
+    RateLimits = 41,
+    EgressPolicy = 42,
+    ScheduledJobQueues = 43,
     // Keep this number and your user name up to date. The number makes it easy to know
     // what to use next. The username on the same line detects merge conflicts
-    // Next Number - 37 - emma
+    // Next Number - 44 - emma
 }
 
 impl From<DefaultTableNumber> for TableNumber {
@@ -310,6 +350,13 @@ impl From<DefaultTableNumber> for &'static dyn ErasedSystemTable {
             DefaultTableNumber::CanonicalUrls => &CanonicalUrlsTable,
             DefaultTableNumber::CronNextRun => &CronNextRunTable,
             DefaultTableNumber::IndexBackfills => &IndexBackfillTable,
+            DefaultTableNumber::Workflows => &WorkflowsTable,
+            DefaultTableNumber::WorkflowSteps => &WorkflowStepsTable,
+            DefaultTableNumber::WorkflowEvents => &WorkflowEventsTable,
+            DefaultTableNumber::WorkQueueMessages => &WorkQueueMessagesTable,
+            DefaultTableNumber::RateLimits => &RateLimitsTable,
+            DefaultTableNumber::EgressPolicy => &EgressPolicyTable,
+            DefaultTableNumber::ScheduledJobQueues => &ScheduledJobQueuesTable,
         }
     }
 }
@@ -536,6 +583,7 @@ pub fn app_system_tables() -> Vec<&'static dyn ErasedSystemTable> {
         &SnapshotImportsTable,
         &FunctionHandlesTable,
         &CanonicalUrlsTable,
+        &EgressPolicyTable,
         &LogSinksTable,
         &AwsLambdaVersionsTable,
         &BackendInfoTable,
@@ -551,9 +599,15 @@ pub fn component_system_tables() -> Vec<&'static dyn ErasedSystemTable> {
     vec![
         &FileStorageTable,
         &ScheduledJobsTable,
+        &ScheduledJobQueuesTable,
         &CronJobsTable,
         &CronJobLogsTable,
         &CronNextRunTable,
+        &WorkflowsTable,
+        &WorkflowStepsTable,
+        &WorkflowEventsTable,
+        &WorkQueueMessagesTable,
+        &RateLimitsTable,
         &ModulesTable,
         &UdfConfigTable,
         &SourcePackagesTable,
@@ -571,6 +625,7 @@ static APP_TABLES_TO_LOAD_IN_MEMORY: LazyLock<BTreeSet<TableName>> = LazyLock::n
         CRON_NEXT_RUN_TABLE.clone(),
         BACKEND_STATE_TABLE.clone(),
         CANONICAL_URLS_TABLE.clone(),
+        EGRESS_POLICY_TABLE.clone(),
         BACKEND_INFO_TABLE.clone(),
         AWS_LAMBDA_VERSIONS_TABLE.clone(),
         SOURCE_PACKAGES_TABLE.clone(),
@@ -626,6 +681,13 @@ pub static FIRST_SEEN_TABLE: LazyLock<BTreeMap<TableName, DatabaseVersion>> = La
         FUNCTION_HANDLES_TABLE.clone() => 102,
         CANONICAL_URLS_TABLE.clone() => 116,
         INDEX_BACKFILLS_TABLE.clone() => 120,
+        WORKFLOWS_TABLE.clone() => 123,
+        WORKFLOW_STEPS_TABLE.clone() => 123,
+        WORKFLOW_EVENTS_TABLE.clone() => 123,
+        WORK_QUEUE_MESSAGES_TABLE.clone() => 124,
+        RATE_LIMITS_TABLE.clone() => 125,
+        EGRESS_POLICY_TABLE.clone() => 126,
+        SCHEDULED_JOB_QUEUES_TABLE.clone() => 127,
     }
 });
 
@@ -635,6 +697,7 @@ pub static FIRST_SEEN_INDEX: LazyLock<BTreeMap<IndexName, DatabaseVersion>> = La
         SCHEDULED_JOBS_INDEX_BY_COMPLETED_TS.name() => 74,
         SCHEDULED_JOBS_INDEX.name() => 45,
         SCHEDULED_JOBS_INDEX_BY_UDF_PATH.name() => 44,
+        SCHEDULED_JOBS_INDEX_BY_QUEUE.name() => 127,
         SESSION_REQUESTS_INDEX.name() => 44,
         FILE_STORAGE_ID_INDEX.name() => 44,
         DEPRECATED_CRON_JOBS_INDEX_BY_NEXT_TS.name() => 47,
@@ -652,6 +715,11 @@ pub static FIRST_SEEN_INDEX: LazyLock<BTreeMap<IndexName, DatabaseVersion>> = La
         BY_COMPONENT_PATH_INDEX.name() => 102,
         EXPORTS_BY_REQUESTOR.name() => 110,
         INDEX_BACKFILLS_BY_INDEX_ID.name() => 120,
+        WORKFLOW_STEPS_INDEX_BY_WORKFLOW_ID.name() => 123,
+        WORKFLOW_EVENTS_INDEX_BY_WORKFLOW_ID.name() => 123,
+        WORK_QUEUE_MESSAGES_INDEX_BY_QUEUE_AND_VISIBLE_TS.name() => 124,
+        RATE_LIMITS_INDEX_BY_NAME_KEY_AND_SHARD.name() => 125,
+        SCHEDULED_JOB_QUEUES_INDEX_BY_NAME.name() => 127,
     }
 });
 
@@ -71,6 +71,7 @@ mod metrics;
 pub mod module_versions;
 pub mod types;
 pub mod user_error;
+pub mod wasm;
 
 /// Table name for user modules.
 pub static MODULES_TABLE: LazyLock<TableName> =
@@ -143,7 +144,7 @@ impl<'a, RT: Runtime> ModuleModel<'a, RT> {
             if existing_module_id.is_none() {
                 added_modules.insert(path.clone());
             }
-            let analyze_result = if !path.is_deps() {
+            let analyze_result = if !path.is_deps() && !path.is_wasm() {
                 // We expect AnalyzeResult to always be set for non-dependency modules.
                 let analyze_result = analyze_results.remove(&path).context(format!(
                     "Missing analyze result for module {}",
@@ -151,7 +152,7 @@ impl<'a, RT: Runtime> ModuleModel<'a, RT> {
                 ))?;
                 Some(analyze_result)
             } else {
-                // We don't analyze dependencies.
+                // We don't analyze dependencies or WebAssembly modules.
                 None
             };
             self.put(
@@ -303,7 +304,7 @@ impl<'a, RT: Runtime> ModuleModel<'a, RT> {
             anyhow::bail!("You cannot push a function under '_system/'");
         }
         anyhow::ensure!(
-            path.module_path.is_deps() || analyze_result.is_some(),
+            path.module_path.is_deps() || path.module_path.is_wasm() || analyze_result.is_some(),
             "AnalyzedModule is required for non-dependency modules"
         );
         let sha256 = hash_module_source(&source, source_map.as_ref());
@@ -11,6 +11,7 @@ use common::{
     http::RoutedHttpPath,
     json::JsonSerializable,
     types::{
+        FunctionLimits,
         HttpActionRoute,
         RoutableMethod,
         UdfType,
@@ -308,6 +309,8 @@ pub struct AnalyzedFunction {
     pub args_str: Option<String>,
     // JSON-serialized ReturnsValidator
     pub returns_str: Option<String>,
+
+    pub limits: FunctionLimits,
 }
 
 impl AnalyzedFunction {
@@ -328,6 +331,7 @@ impl AnalyzedFunction {
             visibility,
             args_str: Some(args_json),
             returns_str: Some(returns_json),
+            limits: FunctionLimits::default(),
         })
     }
 
@@ -365,6 +369,8 @@ pub struct SerializedAnalyzedFunction {
     visibility: Option<Visibility>,
     args: Option<String>,
     returns: Option<String>,
+    // JSON-serialized FunctionLimits, unset if the function has no limits.
+    limits: Option<String>,
 }
 
 impl TryFrom<AnalyzedFunction> for SerializedAnalyzedFunction {
@@ -378,6 +384,11 @@ impl TryFrom<AnalyzedFunction> for SerializedAnalyzedFunction {
             visibility: f.visibility,
             args: f.args_str,
             returns: f.returns_str,
+            limits: if f.limits.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(&f.limits)?)
+            },
         })
     }
 }
@@ -393,6 +404,11 @@ impl TryFrom<SerializedAnalyzedFunction> for AnalyzedFunction {
             visibility: f.visibility,
             args_str: f.args,
             returns_str: f.returns,
+            limits: f
+                .limits
+                .map(|limits| serde_json::from_str(&limits))
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 }
@@ -0,0 +1,216 @@
+//! WebAssembly modules pushed alongside JavaScript modules. Their source is
+//! the base64-encoded binary, so they're stored and packaged like any other
+//! module, and importing one evaluates to a compiled `WebAssembly.Module`.
+use common::knobs::ISOLATE_MAX_ARRAY_BUFFER_TOTAL_SIZE;
+use errors::ErrorMetadata;
+
+use super::module_versions::ModuleSource;
+
+const WASM_MAGIC: &[u8] = b"\0asm";
+const WASM_VERSION: &[u8] = &[1, 0, 0, 0];
+const WASM_PAGE_SIZE: u64 = 1 << 16;
+const MEMORY_SECTION_ID: u8 = 5;
+
+const LIMITS_HAS_MAXIMUM: u8 = 0x01;
+const LIMITS_SHARED: u8 = 0x02;
+const LIMITS_MEMORY64: u8 = 0x04;
+
+/// The most pages a single WebAssembly memory may grow to. Wasm memories
+/// share their budget with ArrayBuffers, so no single memory can use more
+/// than that budget.
+pub fn max_wasm_memory_pages() -> u64 {
+    *ISOLATE_MAX_ARRAY_BUFFER_TOTAL_SIZE as u64 / WASM_PAGE_SIZE
+}
+
+/// Decodes the binary for a `.wasm` module and checks that it's something we
+/// can instantiate. V8 does the full validation when the module is compiled.
+pub fn decode_wasm_module(source: &ModuleSource) -> anyhow::Result<Vec<u8>> {
+    let bytes = base64::decode(&**source).map_err(|e| {
+        invalid_wasm_module(format!(
+            "WebAssembly module source must be base64 encoded: {e}"
+        ))
+    })?;
+    validate_wasm_module(&bytes)?;
+    Ok(bytes)
+}
+
+/// Checks that a WebAssembly binary is something we can instantiate. Every
+/// module is checked before V8 compiles it, whether it was pushed as a `.wasm`
+/// module or compiled at runtime.
+pub fn validate_wasm_module(bytes: &[u8]) -> anyhow::Result<()> {
+    let mut reader = WasmReader { bytes, offset: 0 };
+    if reader.read_bytes(WASM_MAGIC.len())? != WASM_MAGIC {
+        anyhow::bail!(invalid_wasm_module(
+            "Module is not a WebAssembly binary".to_string()
+        ));
+    }
+    if reader.read_bytes(WASM_VERSION.len())? != WASM_VERSION {
+        anyhow::bail!(invalid_wasm_module(
+            "Only version 1 WebAssembly binaries are supported".to_string()
+        ));
+    }
+    while !reader.is_empty() {
+        let section_id = reader.read_byte()?;
+        let section_len = reader.read_leb128()? as usize;
+        let section = reader.read_bytes(section_len)?;
+        if section_id == MEMORY_SECTION_ID {
+            validate_memory_section(section)?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_memory_section(section: &[u8]) -> anyhow::Result<()> {
+    let mut reader = WasmReader {
+        bytes: section,
+        offset: 0,
+    };
+    let num_memories = reader.read_leb128()?;
+    for _ in 0..num_memories {
+        let flags = reader.read_byte()?;
+        // Shared memories only make sense with threads, and their contents
+        // can change underneath a function, so we don't allow them.
+        if flags & LIMITS_SHARED != 0 {
+            anyhow::bail!(invalid_wasm_module(
+                "Shared WebAssembly memories are not supported".to_string()
+            ));
+        }
+        let min_pages = reader.read_leb128()?;
+        if flags & LIMITS_HAS_MAXIMUM != 0 {
+            reader.read_leb128()?;
+        }
+        if flags & LIMITS_MEMORY64 == 0 && min_pages > u32::MAX as u64 {
+            anyhow::bail!(invalid_wasm_module(
+                "Malformed WebAssembly memory limits".to_string()
+            ));
+        }
+        let max_pages = max_wasm_memory_pages();
+        if min_pages > max_pages {
+            anyhow::bail!(invalid_wasm_module(format!(
+                "WebAssembly memory needs at least {min_pages} pages, but memories may only have \
+                 {max_pages} pages ({} bytes)",
+                max_pages * WASM_PAGE_SIZE
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn invalid_wasm_module(msg: String) -> ErrorMetadata {
+    ErrorMetadata::bad_request("InvalidWasmModule", msg)
+}
+
+struct WasmReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> WasmReader<'a> {
+    fn is_empty(&self) -> bool {
+        self.offset >= self.bytes.len()
+    }
+
+    fn read_bytes(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| {
+                invalid_wasm_module("Unexpected end of WebAssembly binary".to_string())
+            })?;
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn read_byte(&mut self) -> anyhow::Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    /// Reads an unsigned LEB128 integer of up to 64 bits.
+    fn read_leb128(&mut self) -> anyhow::Result<u64> {
+        let mut result: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            result |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        anyhow::bail!(invalid_wasm_module(
+            "Malformed integer in WebAssembly binary".to_string()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use errors::ErrorMetadataAnyhowExt;
+
+    use super::{
+        decode_wasm_module,
+        max_wasm_memory_pages,
+    };
+    use crate::modules::module_versions::ModuleSource;
+
+    const EMPTY_MODULE: &[u8] = b"\0asm\x01\0\0\0";
+
+    fn with_memory(flags: u8, min_pages: u8) -> Vec<u8> {
+        let mut bytes = EMPTY_MODULE.to_vec();
+        bytes.extend([5, 3, 1, flags, min_pages]);
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        decode_wasm_module(&ModuleSource::new(&base64::encode(bytes)))
+    }
+
+    #[test]
+    fn test_decode_wasm_module() -> anyhow::Result<()> {
+        assert_eq!(decode(EMPTY_MODULE)?, EMPTY_MODULE);
+        assert_eq!(decode(&with_memory(0, 1))?, with_memory(0, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_wasm_modules() {
+        let not_base64 = decode_wasm_module(&ModuleSource::new("export default 1;"));
+        let shared_memory = {
+            // Shared memories must also declare a maximum.
+            let mut bytes = EMPTY_MODULE.to_vec();
+            bytes.extend([5, 4, 1, 0x03, 1, 1]);
+            decode(&bytes)
+        };
+        let too_large = {
+            let pages = max_wasm_memory_pages() + 1;
+            let mut bytes = EMPTY_MODULE.to_vec();
+            let mut limits = vec![0];
+            let mut remaining = pages;
+            loop {
+                let byte = (remaining & 0x7f) as u8;
+                remaining >>= 7;
+                if remaining == 0 {
+                    limits.push(byte);
+                    break;
+                }
+                limits.push(byte | 0x80);
+            }
+            bytes.extend([5, limits.len() as u8 + 1, 1]);
+            bytes.extend(limits);
+            decode(&bytes)
+        };
+        for result in [
+            not_base64,
+            decode(b"not wasm"),
+            decode(b"\0asm\x02\0\0\0"),
+            decode(&EMPTY_MODULE[..6]),
+            // A section that runs past the end of the binary.
+            decode(&[EMPTY_MODULE, &[1, 10, 0]].concat()),
+            shared_memory,
+            too_large,
+        ] {
+            let err = result.unwrap_err();
+            assert_eq!(err.short_msg(), "InvalidWasmModule", "{err:?}");
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc,
+        LazyLock,
+    },
+};
+
+use common::{
+    document::{
+        ParseDocument,
+        ParsedDocument,
+        ResolvedDocument,
+        CREATION_TIME_FIELD_PATH,
+    },
+    interval::{
+        BinaryKey,
+        Interval,
+    },
+    query::{
+        IndexRange,
+        IndexRangeExpression,
+        Order,
+        Query,
+    },
+    runtime::Runtime,
+    types::WriteTimestamp,
+};
+use database::{
+    DocumentMerge,
+    ResolvedQuery,
+    SystemMetadataModel,
+    Transaction,
+};
+use errors::ErrorMetadata;
+use rand::Rng;
+use value::{
+    values_to_bytes,
+    ConvexObject,
+    ConvexValue,
+    FieldPath,
+    TableName,
+    TableNamespace,
+};
+
+use self::types::{
+    validate_name_and_key,
+    RateLimitConfig,
+    RateLimitRequest,
+    RateLimitResult,
+    RateLimitState,
+};
+use crate::{
+    SystemIndex,
+    SystemTable,
+};
+
+pub mod types;
+
+pub static RATE_LIMITS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_rate_limits"
+        .parse()
+        .expect("_rate_limits is not a valid system table name")
+});
+
+pub static RATE_LIMITS_INDEX_BY_NAME_KEY_AND_SHARD: LazyLock<SystemIndex<RateLimitsTable>> =
+    LazyLock::new(|| {
+        SystemIndex::new(
+            "by_name_key_and_shard",
+            [
+                &NAME_FIELD,
+                &KEY_FIELD,
+                &SHARD_FIELD,
+                &CREATION_TIME_FIELD_PATH,
+            ],
+        )
+        .unwrap()
+    });
+static NAME_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "name".parse().expect("invalid name field"));
+static KEY_FIELD: LazyLock<FieldPath> = LazyLock::new(|| "key".parse().expect("invalid key field"));
+static SHARD_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "shard".parse().expect("invalid shard field"));
+
+pub struct RateLimitsTable;
+impl SystemTable for RateLimitsTable {
+    type Metadata = RateLimitState;
+
+    fn table_name() -> &'static TableName {
+        &RATE_LIMITS_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![RATE_LIMITS_INDEX_BY_NAME_KEY_AND_SHARD.clone()]
+    }
+}
+
+// Rate limits whose state is split across shards. Each call reads every shard
+// without taking a read dependency on them, and its consumption is merged into
+// the shards' latest state when the transaction commits, so concurrent
+// consumers don't conflict with each other. Consuming from different shards
+// also keeps concurrent consumers from overwriting the same documents.
+pub struct RateLimitModel<'a, RT: Runtime> {
+    tx: &'a mut Transaction<RT>,
+    namespace: TableNamespace,
+}
+
+impl<'a, RT: Runtime> RateLimitModel<'a, RT> {
+    pub fn new(tx: &'a mut Transaction<RT>, namespace: TableNamespace) -> Self {
+        Self { tx, namespace }
+    }
+
+    /// Checks whether the limit has room for `request.count`, and consumes it
+    /// if `request.consume` is set.
+    ///
+    /// The decision is made against all of the limit's shards as of the
+    /// transaction's snapshot, and the call is only denied if they don't have
+    /// room between them. The read doesn't conflict with concurrent writes,
+    /// and consumption is applied to the shards' latest state at commit time
+    /// (see [`Transaction::merge_document`]), so concurrent consumers commit
+    /// without OCC errors. If concurrent consumers commit first and take the
+    /// room this call counted on, the commit fails with an OCC error and the
+    /// transaction is retried. Calls that create missing shards take a read
+    /// dependency on the whole limit.
+    pub async fn limit(&mut self, request: RateLimitRequest) -> anyhow::Result<RateLimitResult> {
+        let now_ms: i64 = self
+            .tx
+            .runtime()
+            .unix_timestamp()
+            .as_ms_since_epoch()?
+            .try_into()?;
+        let config = &request.config;
+        let shards = config.shards();
+        let mut existing = self
+            .read_shards(&request.name, request.key.as_deref())
+            .await?;
+        let mut balances = Vec::with_capacity(shards as usize);
+        let mut total_available = 0.0;
+        for shard in 0..shards {
+            let state = existing.get(&shard).map(|(doc, _)| &**doc);
+            let (available, ts_ms) = shard_available(config, state, now_ms);
+            total_available += available;
+            balances.push((shard, available, ts_ms));
+        }
+        if total_available < request.count {
+            return Ok(RateLimitResult {
+                ok: false,
+                retry_after: Some(retry_after(config, request.count - total_available, now_ms)),
+            });
+        }
+        if request.consume {
+            if (0..shards).any(|shard| !existing.contains_key(&shard)) {
+                // Creating shards has to conflict with concurrent calls
+                // creating them too.
+                self.record_shards_read(&request.name, request.key.as_deref())
+                    .await?;
+            }
+            // Start at a random shard so concurrent consumers spread out.
+            let start = self.tx.runtime().rng().random_range(0..shards);
+            let mut remaining = request.count;
+            for offset in 0..shards {
+                let (shard, available, ts_ms) = balances[((start + offset) % shards) as usize];
+                let taken = available.min(remaining).max(0.0);
+                remaining -= taken;
+                match existing.remove(&shard) {
+                    None => {
+                        let state = Self::state(&request, shard, config, available - taken, ts_ms);
+                        SystemMetadataModel::new(self.tx, self.namespace)
+                            .insert_metadata(&RATE_LIMITS_TABLE, state.try_into()?)
+                            .await?;
+                    },
+                    Some(_) if taken <= 0.0 => {},
+                    // The shard was written earlier in this transaction, so
+                    // nothing else can write to it before this one commits.
+                    Some((doc, WriteTimestamp::Pending)) => {
+                        let state = Self::state(&request, shard, config, available - taken, ts_ms);
+                        SystemMetadataModel::new(self.tx, self.namespace)
+                            .replace(doc.id(), state.try_into()?)
+                            .await?;
+                    },
+                    Some((doc, WriteTimestamp::Committed(_))) => {
+                        self.tx.merge_document(
+                            doc.id(),
+                            Arc::new(ConsumeFromShard {
+                                config: *config,
+                                now_ms,
+                                count: taken,
+                            }),
+                        )?;
+                    },
+                }
+            }
+        }
+        Ok(RateLimitResult {
+            ok: true,
+            retry_after: None,
+        })
+    }
+
+    /// Resets a rate limit to its initial state by deleting all of its shards.
+    pub async fn reset(&mut self, name: &str, key: Option<&str>) -> anyhow::Result<()> {
+        validate_name_and_key(name, key)?;
+        let query = Self::query(name, key)?;
+        let mut query_stream = ResolvedQuery::new(self.tx, self.namespace, query)?;
+        while let Some(doc) = query_stream.next(self.tx, None).await? {
+            SystemMetadataModel::new(self.tx, self.namespace)
+                .delete(doc.id())
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn state(
+        request: &RateLimitRequest,
+        shard: u32,
+        config: &RateLimitConfig,
+        remaining: f64,
+        ts_ms: i64,
+    ) -> RateLimitState {
+        RateLimitState {
+            name: request.name.clone(),
+            key: request.key.clone(),
+            shard,
+            value: stored_value(config, remaining),
+            ts_ms,
+        }
+    }
+
+    /// Reads every shard of a limit, including consumption merged into them
+    /// earlier in this transaction, without taking a read dependency on them.
+    async fn read_shards(
+        &mut self,
+        name: &str,
+        key: Option<&str>,
+    ) -> anyhow::Result<BTreeMap<u32, (ParsedDocument<RateLimitState>, WriteTimestamp)>> {
+        let prefix = values_to_bytes(&[
+            Some(ConvexValue::try_from(name.to_string())?),
+            Some(Self::key_value(key)?),
+        ]);
+        let documents = self
+            .tx
+            .index_range_for_merge(
+                self.namespace,
+                &RATE_LIMITS_INDEX_BY_NAME_KEY_AND_SHARD.name(),
+                &Interval::prefix(BinaryKey::from(prefix)),
+            )
+            .await?;
+        let mut shards = BTreeMap::new();
+        for (document, ts) in documents {
+            let doc: ParsedDocument<RateLimitState> = document.parse()?;
+            shards.entry(doc.shard).or_insert((doc, ts));
+        }
+        Ok(shards)
+    }
+
+    async fn record_shards_read(&mut self, name: &str, key: Option<&str>) -> anyhow::Result<()> {
+        let query = Self::query(name, key)?;
+        let mut query_stream = ResolvedQuery::new(self.tx, self.namespace, query)?;
+        while query_stream.next(self.tx, None).await?.is_some() {}
+        Ok(())
+    }
+
+    fn key_value(key: Option<&str>) -> anyhow::Result<ConvexValue> {
+        Ok(match key {
+            Some(key) => ConvexValue::try_from(key.to_string())?,
+            None => ConvexValue::Null,
+        })
+    }
+
+    fn query(name: &str, key: Option<&str>) -> anyhow::Result<Query> {
+        Ok(Query::index_range(IndexRange {
+            index_name: RATE_LIMITS_INDEX_BY_NAME_KEY_AND_SHARD.name(),
+            range: vec![
+                IndexRangeExpression::Eq(
+                    NAME_FIELD.clone(),
+                    ConvexValue::try_from(name.to_string())?.into(),
+                ),
+                IndexRangeExpression::Eq(KEY_FIELD.clone(), Self::key_value(key)?.into()),
+            ],
+            order: Order::Asc,
+        }))
+    }
+}
+
+/// Consumes `count` from a shard's latest state when the transaction commits.
+#[derive(Debug)]
+struct ConsumeFromShard {
+    config: RateLimitConfig,
+    now_ms: i64,
+    count: f64,
+}
+
+impl DocumentMerge for ConsumeFromShard {
+    fn merge(&self, latest: &ResolvedDocument) -> anyhow::Result<ConvexObject> {
+        let state: ParsedDocument<RateLimitState> = latest.parse()?;
+        let state = state.into_value();
+        // A consumer that ran later may have already committed, and the shard
+        // shouldn't move back in time.
+        let now_ms = self.now_ms.max(state.ts_ms);
+        let (available, ts_ms) = shard_available(&self.config, Some(&state), now_ms);
+        // Concurrent consumers took the room this transaction counted on, so
+        // retry it against their writes.
+        anyhow::ensure!(available >= self.count, ErrorMetadata::system_occ());
+        RateLimitState {
+            value: stored_value(&self.config, available - self.count),
+            ts_ms,
+            ..state
+        }
+        .try_into()
+    }
+}
+
+/// How much one shard has available at `now_ms`, and the `ts_ms` to store
+/// with its state if it's updated.
+fn shard_available(
+    config: &RateLimitConfig,
+    state: Option<&RateLimitState>,
+    now_ms: i64,
+) -> (f64, i64) {
+    let shards = config.shards() as f64;
+    match *config {
+        RateLimitConfig::FixedWindow {
+            rate, period_ms, ..
+        } => {
+            let period_ms = period_ms as i64;
+            let window_start = now_ms - now_ms.rem_euclid(period_ms);
+            let used = match state {
+                Some(state) if state.ts_ms == window_start => state.value,
+                _ => 0.0,
+            };
+            ((rate / shards - used).max(0.0), window_start)
+        },
+        RateLimitConfig::TokenBucket {
+            rate,
+            period_ms,
+            capacity,
+            ..
+        } => {
+            let capacity = capacity / shards;
+            let rate_per_ms = rate / shards / period_ms as f64;
+            let tokens = match state {
+                Some(state) => {
+                    let elapsed = (now_ms - state.ts_ms).max(0) as f64;
+                    (state.value + elapsed * rate_per_ms).min(capacity)
+                },
+                None => capacity,
+            };
+            (tokens, now_ms)
+        },
+    }
+}
+
+/// The `value` to store for a shard that has `remaining` left.
+fn stored_value(config: &RateLimitConfig, remaining: f64) -> f64 {
+    match *config {
+        RateLimitConfig::FixedWindow { rate, .. } => rate / config.shards() as f64 - remaining,
+        RateLimitConfig::TokenBucket { .. } => remaining,
+    }
+}
+
+/// How many milliseconds until the limit as a whole has `missing` more
+/// available.
+fn retry_after(config: &RateLimitConfig, missing: f64, now_ms: i64) -> f64 {
+    match *config {
+        RateLimitConfig::FixedWindow { period_ms, .. } => {
+            let period_ms = period_ms as i64;
+            (period_ms - now_ms.rem_euclid(period_ms)) as f64
+        },
+        RateLimitConfig::TokenBucket {
+            rate, period_ms, ..
+        } => missing / (rate / period_ms as f64),
+    }
+}
@@ -0,0 +1,298 @@
+use errors::ErrorMetadata;
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use value::codegen_convex_serialization;
+
+/// Rate limits can be split across at most this many shards.
+pub const MAX_RATE_LIMIT_SHARDS: u32 = 64;
+
+/// Names and keys are stored in the index on `_rate_limits`, so bound their
+/// size well below the limit on values.
+pub const MAX_RATE_LIMIT_NAME_LENGTH: usize = 256;
+pub const MAX_RATE_LIMIT_KEY_LENGTH: usize = 1024;
+
+/// Limits that don't pick a shard count get one shard per this much capacity,
+/// so busy limits spread their writes without starving small ones.
+const CAPACITY_PER_DEFAULT_SHARD: f64 = 10.0;
+
+/// Limits that don't pick a shard count get at most this many shards.
+const MAX_DEFAULT_RATE_LIMIT_SHARDS: u32 = 16;
+
+fn default_shards(capacity: f64) -> u32 {
+    let shards = (capacity / CAPACITY_PER_DEFAULT_SHARD).floor();
+    if shards.is_nan() || shards < 1.0 {
+        return 1;
+    }
+    (shards as u32).min(MAX_DEFAULT_RATE_LIMIT_SHARDS)
+}
+
+/// One shard of a rate limit's state. A limit with `n` shards gives each
+/// shard `1/n` of its rate and capacity, so consumers that land on different
+/// shards never touch the same document.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct RateLimitState {
+    pub name: String,
+    pub key: Option<String>,
+    pub shard: u32,
+    /// Tokens left for a token bucket, or units used in the current window
+    /// for a fixed window.
+    #[cfg_attr(any(test, feature = "testing"), proptest(strategy = "0f64..1e9"))]
+    pub value: f64,
+    /// When `value` was last refilled for a token bucket, or when the current
+    /// window started for a fixed window, in milliseconds since the epoch.
+    pub ts_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedRateLimitState {
+    name: String,
+    key: Option<String>,
+    shard: i64,
+    value: f64,
+    ts: i64,
+}
+
+impl From<RateLimitState> for SerializedRateLimitState {
+    fn from(state: RateLimitState) -> Self {
+        SerializedRateLimitState {
+            name: state.name,
+            key: state.key,
+            shard: state.shard.into(),
+            value: state.value,
+            ts: state.ts_ms,
+        }
+    }
+}
+
+impl TryFrom<SerializedRateLimitState> for RateLimitState {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedRateLimitState) -> anyhow::Result<Self> {
+        Ok(RateLimitState {
+            name: value.name,
+            key: value.key,
+            shard: value.shard.try_into()?,
+            value: value.value,
+            ts_ms: value.ts,
+        })
+    }
+}
+
+codegen_convex_serialization!(RateLimitState, SerializedRateLimitState);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RateLimitConfig {
+    /// Allows `rate` units per `period_ms`, resetting at the start of each
+    /// period.
+    FixedWindow {
+        rate: f64,
+        period_ms: u64,
+        shards: u32,
+    },
+    /// Refills `rate` tokens per `period_ms`, up to `capacity`, so bursts of
+    /// up to `capacity` are allowed.
+    TokenBucket {
+        rate: f64,
+        period_ms: u64,
+        capacity: f64,
+        shards: u32,
+    },
+}
+
+impl RateLimitConfig {
+    pub fn shards(&self) -> u32 {
+        match self {
+            RateLimitConfig::FixedWindow { shards, .. }
+            | RateLimitConfig::TokenBucket { shards, .. } => *shards,
+        }
+    }
+
+    /// The most a single call can consume, across all of its shards.
+    pub fn max_count(&self) -> f64 {
+        match self {
+            RateLimitConfig::FixedWindow { rate, .. } => *rate,
+            RateLimitConfig::TokenBucket { capacity, .. } => *capacity,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum RateLimitConfigJson {
+    #[serde(rename_all = "camelCase")]
+    FixedWindow {
+        rate: f64,
+        period: u64,
+        shards: Option<u32>,
+    },
+    #[serde(rename_all = "camelCase")]
+    TokenBucket {
+        rate: f64,
+        period: u64,
+        capacity: Option<f64>,
+        shards: Option<u32>,
+    },
+}
+
+impl TryFrom<RateLimitConfigJson> for RateLimitConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(json: RateLimitConfigJson) -> anyhow::Result<Self> {
+        let config = match json {
+            RateLimitConfigJson::FixedWindow {
+                rate,
+                period,
+                shards,
+            } => RateLimitConfig::FixedWindow {
+                rate,
+                period_ms: period,
+                shards: shards.unwrap_or_else(|| default_shards(rate)),
+            },
+            RateLimitConfigJson::TokenBucket {
+                rate,
+                period,
+                capacity,
+                shards,
+            } => {
+                let capacity = capacity.unwrap_or(rate);
+                RateLimitConfig::TokenBucket {
+                    rate,
+                    period_ms: period,
+                    capacity,
+                    shards: shards.unwrap_or_else(|| default_shards(capacity)),
+                }
+            },
+        };
+        let (rate, period_ms, capacity) = match config {
+            RateLimitConfig::FixedWindow {
+                rate, period_ms, ..
+            } => (rate, period_ms, rate),
+            RateLimitConfig::TokenBucket {
+                rate,
+                period_ms,
+                capacity,
+                ..
+            } => (rate, period_ms, capacity),
+        };
+        anyhow::ensure!(
+            rate.is_finite() && rate > 0.0 && capacity.is_finite() && capacity > 0.0,
+            ErrorMetadata::bad_request(
+                "InvalidRateLimitConfig",
+                "Rate limit rate and capacity must be positive numbers",
+            )
+        );
+        anyhow::ensure!(
+            period_ms > 0,
+            ErrorMetadata::bad_request(
+                "InvalidRateLimitConfig",
+                "Rate limit period must be at least 1 millisecond",
+            )
+        );
+        anyhow::ensure!(
+            (1..=MAX_RATE_LIMIT_SHARDS).contains(&config.shards()),
+            ErrorMetadata::bad_request(
+                "InvalidRateLimitConfig",
+                format!("Rate limit shards must be between 1 and {MAX_RATE_LIMIT_SHARDS}"),
+            )
+        );
+        Ok(config)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitRequestJson {
+    pub name: String,
+    pub key: Option<String>,
+    pub config: RateLimitConfigJson,
+    pub count: Option<f64>,
+    /// Only check whether the request would be allowed, without consuming
+    /// anything.
+    pub check: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetRateLimitJson {
+    pub name: String,
+    pub key: Option<String>,
+}
+
+pub fn validate_name_and_key(name: &str, key: Option<&str>) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        !name.is_empty() && name.len() <= MAX_RATE_LIMIT_NAME_LENGTH,
+        ErrorMetadata::bad_request(
+            "InvalidRateLimitName",
+            format!("Rate limit names must be 1 to {MAX_RATE_LIMIT_NAME_LENGTH} characters"),
+        )
+    );
+    if let Some(key) = key {
+        anyhow::ensure!(
+            key.len() <= MAX_RATE_LIMIT_KEY_LENGTH,
+            ErrorMetadata::bad_request(
+                "InvalidRateLimitKey",
+                format!("Rate limit keys must be at most {MAX_RATE_LIMIT_KEY_LENGTH} characters"),
+            )
+        );
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+pub struct RateLimitRequest {
+    pub name: String,
+    pub key: Option<String>,
+    pub config: RateLimitConfig,
+    pub count: f64,
+    pub consume: bool,
+}
+
+impl TryFrom<RateLimitRequestJson> for RateLimitRequest {
+    type Error = anyhow::Error;
+
+    fn try_from(json: RateLimitRequestJson) -> anyhow::Result<Self> {
+        validate_name_and_key(&json.name, json.key.as_deref())?;
+        let config = RateLimitConfig::try_from(json.config)?;
+        let count = json.count.unwrap_or(1.0);
+        anyhow::ensure!(
+            count.is_finite() && count > 0.0,
+            ErrorMetadata::bad_request(
+                "InvalidRateLimitCount",
+                "Rate limit count must be a positive number",
+            )
+        );
+        anyhow::ensure!(
+            count <= config.max_count(),
+            ErrorMetadata::bad_request(
+                "RateLimitCountTooLarge",
+                format!(
+                    "Cannot consume {count} from rate limit {} since it only allows {}",
+                    json.name,
+                    config.max_count()
+                ),
+            )
+        );
+        Ok(RateLimitRequest {
+            name: json.name,
+            key: json.key,
+            config,
+            count,
+            consume: !json.check.unwrap_or(false),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitResult {
+    pub ok: bool,
+    /// How long to wait before retrying, in milliseconds, if not `ok`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<f64>,
+}
@@ -11,6 +11,7 @@ use common::{
     document::{
         ParseDocument,
         ParsedDocument,
+        CREATION_TIME_FIELD_PATH,
     },
     execution_context::ExecutionContext,
     knobs::{
@@ -59,11 +60,14 @@ use self::{
     types::{
         ScheduledJob,
         ScheduledJobAttempts,
+        ScheduledJobQueue,
+        ScheduledJobRetryPolicy,
         ScheduledJobState,
     },
     virtual_table::ScheduledJobsDocMapper,
 };
 use crate::{
+    workflows::WorkflowModel,
     SystemIndex,
     SystemTable,
 };
@@ -106,6 +110,22 @@ pub static SCHEDULED_JOBS_INDEX_BY_UDF_PATH: LazyLock<SystemIndex<ScheduledJobsT
         )
         .unwrap()
     });
+/// By pending queue name and next ts. Used to run jobs from each queue in
+/// order without scanning past jobs of queues that are at their concurrency
+/// limit. Completed jobs don't have a pending queue, so listing the queues
+/// with pending jobs doesn't read them.
+pub static SCHEDULED_JOBS_INDEX_BY_QUEUE: LazyLock<SystemIndex<ScheduledJobsTable>> =
+    LazyLock::new(|| {
+        SystemIndex::new(
+            "by_pending_queue_and_next_ts",
+            [
+                &PENDING_QUEUE_FIELD,
+                &NEXT_TS_FIELD,
+                &CREATION_TIME_FIELD_PATH,
+            ],
+        )
+        .unwrap()
+    });
 /// By completed ts. Used to efficiently find jobs to garbage collect.
 pub static SCHEDULED_JOBS_INDEX_BY_COMPLETED_TS: LazyLock<SystemIndex<ScheduledJobsTable>> =
     LazyLock::new(|| SystemIndex::new("by_completed_ts", [&COMPLETED_TS_FIELD]).unwrap());
@@ -113,6 +133,8 @@ pub static NEXT_TS_FIELD: LazyLock<FieldPath> =
     LazyLock::new(|| "nextTs".parse().expect("invalid nextTs field"));
 pub static COMPLETED_TS_FIELD: LazyLock<FieldPath> =
     LazyLock::new(|| "completedTs".parse().expect("invalid completedTs field"));
+pub static PENDING_QUEUE_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "pendingQueue".parse().expect("invalid pendingQueue field"));
 static UDF_PATH_FIELD: LazyLock<FieldPath> =
     LazyLock::new(|| "udfPath".parse().expect("invalid udfPath field"));
 static COMPONENT_PATH_FIELD: LazyLock<FieldPath> =
@@ -131,6 +153,7 @@ impl SystemTable for ScheduledJobsTable {
             SCHEDULED_JOBS_INDEX_BY_COMPLETED_TS.clone(),
             SCHEDULED_JOBS_INDEX.clone(),
             SCHEDULED_JOBS_INDEX_BY_UDF_PATH.clone(),
+            SCHEDULED_JOBS_INDEX_BY_QUEUE.clone(),
         ]
     }
 
@@ -152,6 +175,29 @@ impl SystemTable for ScheduledJobsTable {
     }
 }
 
+pub static SCHEDULED_JOB_QUEUES_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_scheduled_job_queues"
+        .parse()
+        .expect("_scheduled_job_queues is not a valid system table name")
+});
+pub static SCHEDULED_JOB_QUEUES_INDEX_BY_NAME: LazyLock<SystemIndex<ScheduledJobQueuesTable>> =
+    LazyLock::new(|| SystemIndex::new("by_name", [&QUEUE_NAME_FIELD]).unwrap());
+static QUEUE_NAME_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "name".parse().expect("invalid name field"));
+
+pub struct ScheduledJobQueuesTable;
+impl SystemTable for ScheduledJobQueuesTable {
+    type Metadata = ScheduledJobQueue;
+
+    fn table_name() -> &'static TableName {
+        &SCHEDULED_JOB_QUEUES_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![SCHEDULED_JOB_QUEUES_INDEX_BY_NAME.clone()]
+    }
+}
+
 // Maintains state for scheduling asynchronous functions (scheduled jobs).
 pub struct SchedulerModel<'a, RT: Runtime> {
     tx: &'a mut Transaction<RT>,
@@ -198,6 +244,8 @@ impl<'a, RT: Runtime> SchedulerModel<'a, RT> {
         args: ConvexArray,
         ts: UnixTimestamp,
         context: ExecutionContext,
+        retry_policy: Option<ScheduledJobRetryPolicy>,
+        queue: Option<ScheduledJobQueue>,
     ) -> anyhow::Result<ResolvedDocumentId> {
         if path.udf_path.is_system()
             && !(self.tx.identity().is_admin() || self.tx.identity().is_system())
@@ -206,6 +254,18 @@ impl<'a, RT: Runtime> SchedulerModel<'a, RT> {
         }
 
         self.check_scheduling_limits(&args)?;
+        if let Some(retry_policy) = &retry_policy {
+            retry_policy.validate()?;
+        }
+        let queue = match queue {
+            Some(queue) => {
+                queue.validate()?;
+                let name = queue.name.clone();
+                self.save_queue(queue).await?;
+                Some(name)
+            },
+            None => None,
+        };
 
         let now: Timestamp = self.tx.runtime().generate_timestamp()?;
         let original_scheduled_ts: Timestamp = ts.as_system_time().try_into()?;
@@ -220,6 +280,8 @@ impl<'a, RT: Runtime> SchedulerModel<'a, RT> {
             None,
             original_scheduled_ts,
             ScheduledJobAttempts::default(),
+            retry_policy.clone(),
+            queue.clone(),
         )?;
         let job = if let Some((parent_component_id, parent_scheduled_job)) =
             context.parent_scheduled_job
@@ -259,6 +321,8 @@ impl<'a, RT: Runtime> SchedulerModel<'a, RT> {
                             Some(*scheduled_ts),
                             *scheduled_ts,
                             ScheduledJobAttempts::default(),
+                            retry_policy,
+                            queue,
                         )?
                     },
                 }
@@ -275,6 +339,47 @@ impl<'a, RT: Runtime> SchedulerModel<'a, RT> {
         Ok(id)
     }
 
+    /// Stores the settings of a queue, replacing any stored by earlier jobs.
+    /// Queues whose settings don't change aren't written, so scheduling onto
+    /// the same queue from concurrent mutations doesn't conflict.
+    async fn save_queue(&mut self, queue: ScheduledJobQueue) -> anyhow::Result<()> {
+        let existing = self.get_queue(&queue.name).await?;
+        let mut model = SystemMetadataModel::new(self.tx, self.namespace);
+        match existing {
+            Some(doc) if *doc == queue => (),
+            Some(doc) => {
+                model.replace(doc.id(), queue.try_into()?).await?;
+            },
+            None => {
+                model
+                    .insert_metadata(&SCHEDULED_JOB_QUEUES_TABLE, queue.try_into()?)
+                    .await?;
+            },
+        }
+        Ok(())
+    }
+
+    /// The settings of the named queue, if a job has been scheduled onto it.
+    pub async fn get_queue(
+        &mut self,
+        name: &str,
+    ) -> anyhow::Result<Option<ParsedDocument<ScheduledJobQueue>>> {
+        let query = Query::index_range(IndexRange {
+            index_name: SCHEDULED_JOB_QUEUES_INDEX_BY_NAME.name(),
+            range: vec![IndexRangeExpression::Eq(
+                QUEUE_NAME_FIELD.clone(),
+                ConvexValue::try_from(name.to_string())?.into(),
+            )],
+            order: Order::Asc,
+        });
+        let mut query_stream = ResolvedQuery::new(self.tx, self.namespace, query)?;
+        query_stream
+            .expect_at_most_one(self.tx)
+            .await?
+            .map(|doc| doc.parse())
+            .transpose()
+    }
+
     pub async fn replace(
         &mut self,
         id: ResolvedDocumentId,
@@ -291,10 +396,37 @@ impl<'a, RT: Runtime> SchedulerModel<'a, RT> {
         Ok(())
     }
 
+    /// Marks the job as running a step of a durable workflow, so completing
+    /// the job completes the step.
+    pub async fn set_workflow_step(
+        &mut self,
+        id: ResolvedDocumentId,
+        workflow_step_id: DeveloperDocumentId,
+    ) -> anyhow::Result<()> {
+        let Some(job) = self.tx.get(id).await? else {
+            anyhow::bail!("scheduled job not found")
+        };
+        let job: ParsedDocument<ScheduledJob> = job.parse()?;
+        let mut job = job.into_value();
+        job.workflow_step_id = Some(workflow_step_id);
+        self.replace(id, job).await
+    }
+
     pub async fn complete(
         &mut self,
         id: ResolvedDocumentId,
         state: ScheduledJobState,
+    ) -> anyhow::Result<()> {
+        self.complete_with_result(id, state, None).await
+    }
+
+    /// Like `complete`, but also hands the function's return value to the
+    /// workflow step the job runs, if any.
+    pub async fn complete_with_result(
+        &mut self,
+        id: ResolvedDocumentId,
+        state: ScheduledJobState,
+        result: Option<ConvexValue>,
     ) -> anyhow::Result<()> {
         match state {
             ScheduledJobState::InProgress { .. } | ScheduledJobState::Pending => {
@@ -324,14 +456,20 @@ impl<'a, RT: Runtime> SchedulerModel<'a, RT> {
         }
 
         let mut job: ScheduledJob = job.into_value();
-        job.state = state;
+        job.state = state.clone();
         // Remove next_ts and set completed_ts so the scheduler knows that the
         // job has already been processed
         job.next_ts = None;
         job.completed_ts = Some(*self.tx.begin_timestamp());
+        let workflow_step_id = job.workflow_step_id;
         SystemMetadataModel::new(self.tx, self.namespace)
             .replace(id, job.try_into()?)
             .await?;
+        if let Some(workflow_step_id) = workflow_step_id {
+            WorkflowModel::new(self.tx, self.namespace)
+                .complete_scheduled_step(workflow_step_id, &state, result)
+                .await?;
+        }
 
         Ok(())
     }
@@ -486,9 +624,11 @@ impl<'a, RT: Runtime> VirtualSchedulerModel<'a, RT> {
         args: ConvexArray,
         ts: UnixTimestamp,
         context: ExecutionContext,
+        retry_policy: Option<ScheduledJobRetryPolicy>,
+        queue: Option<ScheduledJobQueue>,
     ) -> anyhow::Result<DeveloperDocumentId> {
         let system_id = SchedulerModel::new(self.tx, self.namespace)
-            .schedule(path, args, ts, context)
+            .schedule(path, args, ts, context, retry_policy, queue)
             .await?;
         self.tx
             .virtual_system_mapping()
@@ -1,14 +1,23 @@
+use std::{
+    cmp,
+    collections::BTreeSet,
+    time::Duration,
+};
+
 use common::{
     components::{
         CanonicalizedComponentFunctionPath,
         ComponentPath,
     },
     execution_context::ExecutionId,
+    knobs::SCHEDULED_JOB_MAX_BACKOFF,
     types::Timestamp,
     RequestId,
 };
+use errors::ErrorMetadata;
 #[cfg(any(test, feature = "testing"))]
 use proptest::prelude::*;
+use rand::Rng;
 use serde::{
     Deserialize,
     Serialize,
@@ -18,6 +27,7 @@ use serde_json::Value as JsonValue;
 use value::{
     codegen_convex_serialization,
     ConvexArray,
+    DeveloperDocumentId,
 };
 
 #[derive(Clone, Debug, PartialEq)]
@@ -50,6 +60,23 @@ pub struct ScheduledJob {
     pub original_scheduled_ts: Timestamp,
 
     pub attempts: ScheduledJobAttempts,
+
+    /// Set when the job was scheduled with retries. Without a policy, a job
+    /// that fails is marked `Failed` and never rerun.
+    pub retry_policy: Option<ScheduledJobRetryPolicy>,
+
+    /// The name of the queue the job was scheduled onto, whose settings live
+    /// in `_scheduled_job_queues`. Jobs without a queue share the default
+    /// queue, which is only bounded by the scheduler's overall parallelism.
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "proptest::option::of(\"[a-zA-Z0-9_]{1,16}\")")
+    )]
+    pub queue: Option<String>,
+
+    /// Set when the job runs a step of a durable workflow. The step in
+    /// `_workflow_steps` is completed with the job's outcome.
+    pub workflow_step_id: Option<DeveloperDocumentId>,
 }
 
 fn args_to_bytes(args: ConvexArray) -> anyhow::Result<ByteBuf> {
@@ -66,6 +93,8 @@ impl ScheduledJob {
         completed_ts: Option<Timestamp>,
         original_scheduled_ts: Timestamp,
         attempts: ScheduledJobAttempts,
+        retry_policy: Option<ScheduledJobRetryPolicy>,
+        queue: Option<String>,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             path,
@@ -75,6 +104,9 @@ impl ScheduledJob {
             completed_ts,
             original_scheduled_ts,
             attempts,
+            retry_policy,
+            queue,
+            workflow_step_id: None,
         })
     }
 
@@ -98,6 +130,16 @@ pub struct SerializedScheduledJob {
     completed_ts: Option<i64>,
     original_scheduled_ts: Option<i64>,
     attempts: Option<ScheduledJobAttempts>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry_policy: Option<ScheduledJobRetryPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    queue: Option<String>,
+    // The queue again, but only while the job is pending, so the scheduler
+    // can find the queues with pending jobs without reading completed ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pending_queue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workflow_step_id: Option<String>,
 }
 
 impl TryFrom<ScheduledJob> for SerializedScheduledJob {
@@ -113,6 +155,10 @@ impl TryFrom<ScheduledJob> for SerializedScheduledJob {
             completed_ts: job.completed_ts.map(|ts| ts.into()),
             original_scheduled_ts: Some(job.original_scheduled_ts.into()),
             attempts: Some(job.attempts),
+            retry_policy: job.retry_policy,
+            pending_queue: job.queue.clone().filter(|_| job.next_ts.is_some()),
+            queue: job.queue,
+            workflow_step_id: job.workflow_step_id.map(|id| id.encode()),
         })
     }
 }
@@ -155,6 +201,12 @@ impl TryFrom<SerializedScheduledJob> for ScheduledJob {
             completed_ts,
             original_scheduled_ts,
             attempts: value.attempts.unwrap_or_default(),
+            retry_policy: value.retry_policy,
+            queue: value.queue,
+            workflow_step_id: value
+                .workflow_step_id
+                .map(|id| DeveloperDocumentId::decode(&id))
+                .transpose()?,
         })
     }
 }
@@ -165,6 +217,9 @@ impl TryFrom<SerializedScheduledJob> for ScheduledJob {
 pub struct ScheduledJobAttempts {
     pub system_errors: u32,
     pub occ_errors: u32,
+    /// Reruns after failures allowed by the job's retry policy.
+    #[serde(default)]
+    pub retries: u32,
 }
 
 impl ScheduledJobAttempts {
@@ -173,6 +228,212 @@ impl ScheduledJobAttempts {
     }
 }
 
+/// The most times a job with a retry policy can run, including the first run.
+pub const MAX_SCHEDULED_JOB_ATTEMPTS: u32 = 20;
+
+/// The kinds of failures a retry policy can rerun a job after.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub enum ScheduledJobRetryClass {
+    /// The function threw, e.g. because a `fetch` in an action failed.
+    DeveloperError,
+    /// The action was interrupted by a system error or restart before it
+    /// finished. Retrying these gives up at-most-once execution.
+    Interrupted,
+}
+
+/// Exponential backoff for rerunning a failed scheduled job, set when the
+/// job is scheduled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledJobRetryPolicy {
+    /// The most times the job runs, including the first run.
+    pub max_attempts: u32,
+    /// The delay before the first retry, doubled for each later retry.
+    pub base_delay_ms: u32,
+    /// The fraction of each delay, between 0 and 1, that's randomized.
+    #[cfg_attr(any(test, feature = "testing"), proptest(strategy = "0.0..=1.0f64"))]
+    pub jitter: f64,
+    pub retry_on: BTreeSet<ScheduledJobRetryClass>,
+}
+
+impl ScheduledJobRetryPolicy {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            (1..=MAX_SCHEDULED_JOB_ATTEMPTS).contains(&self.max_attempts),
+            ErrorMetadata::bad_request(
+                "InvalidRetryPolicy",
+                format!(
+                    "Retry policy maxAttempts must be between 1 and {MAX_SCHEDULED_JOB_ATTEMPTS}, \
+                     got {}",
+                    self.max_attempts
+                ),
+            )
+        );
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.jitter),
+            ErrorMetadata::bad_request(
+                "InvalidRetryPolicy",
+                format!(
+                    "Retry policy jitter must be between 0 and 1, got {}",
+                    self.jitter
+                ),
+            )
+        );
+        Ok(())
+    }
+
+    /// Whether a job that has already been retried `retries` times should run
+    /// again after a failure of kind `class`.
+    pub fn should_retry(&self, class: ScheduledJobRetryClass, retries: u32) -> bool {
+        self.retry_on.contains(&class) && retries + 1 < self.max_attempts
+    }
+
+    /// The delay before the job's next run, after `retries` earlier retries.
+    pub fn retry_delay(&self, retries: u32, rng: &mut impl Rng) -> Duration {
+        let base = Duration::from_millis(self.base_delay_ms as u64);
+        let backoff = 2u32
+            .checked_pow(retries)
+            .and_then(|p| base.checked_mul(p))
+            .unwrap_or(*SCHEDULED_JOB_MAX_BACKOFF);
+        let backoff = cmp::min(backoff, *SCHEDULED_JOB_MAX_BACKOFF);
+        backoff.mul_f64(1.0 - self.jitter * rng.random::<f64>())
+    }
+}
+
+/// The retry policy as passed to `scheduler.runAfter` and `scheduler.runAt`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledJobRetryPolicyJson {
+    max_attempts: u32,
+    base_delay_ms: Option<u32>,
+    jitter: Option<f64>,
+    retry_on: Option<Vec<ScheduledJobRetryClass>>,
+}
+
+impl TryFrom<ScheduledJobRetryPolicyJson> for ScheduledJobRetryPolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ScheduledJobRetryPolicyJson) -> anyhow::Result<Self> {
+        let policy = Self {
+            max_attempts: value.max_attempts,
+            base_delay_ms: value.base_delay_ms.unwrap_or(1000),
+            jitter: value.jitter.unwrap_or(0.5),
+            retry_on: match value.retry_on {
+                Some(retry_on) => retry_on.into_iter().collect(),
+                None => BTreeSet::from([ScheduledJobRetryClass::DeveloperError]),
+            },
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
+}
+
+pub const MAX_SCHEDULED_JOB_QUEUE_NAME_LENGTH: usize = 64;
+
+/// A named queue of scheduled jobs. At most `max_concurrency` of the queue's
+/// jobs run at once, and queues with a higher `priority` get free execution
+/// slots first. Each queue's settings are stored once in
+/// `_scheduled_job_queues`, and the last job scheduled onto the queue sets
+/// them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct ScheduledJobQueue {
+    #[cfg_attr(any(test, feature = "testing"), proptest(regex = "[a-zA-Z0-9_]{1,16}"))]
+    pub name: String,
+    pub max_concurrency: u32,
+    pub priority: u32,
+}
+
+impl ScheduledJobQueue {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !self.name.is_empty()
+                && self.name.len() <= MAX_SCHEDULED_JOB_QUEUE_NAME_LENGTH
+                && self
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+            ErrorMetadata::bad_request(
+                "InvalidSchedulerQueue",
+                format!(
+                    "Scheduler queue names must be 1 to {MAX_SCHEDULED_JOB_QUEUE_NAME_LENGTH} \
+                     letters, digits, underscores or dashes, got {:?}",
+                    self.name
+                ),
+            )
+        );
+        anyhow::ensure!(
+            self.max_concurrency >= 1,
+            ErrorMetadata::bad_request(
+                "InvalidSchedulerQueue",
+                format!(
+                    "Scheduler queue {} must have a maxConcurrency of at least 1",
+                    self.name
+                ),
+            )
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedScheduledJobQueue {
+    name: String,
+    max_concurrency: i64,
+    priority: i64,
+}
+
+impl From<ScheduledJobQueue> for SerializedScheduledJobQueue {
+    fn from(queue: ScheduledJobQueue) -> Self {
+        SerializedScheduledJobQueue {
+            name: queue.name,
+            max_concurrency: queue.max_concurrency.into(),
+            priority: queue.priority.into(),
+        }
+    }
+}
+
+impl TryFrom<SerializedScheduledJobQueue> for ScheduledJobQueue {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedScheduledJobQueue) -> anyhow::Result<Self> {
+        Ok(ScheduledJobQueue {
+            name: value.name,
+            max_concurrency: value.max_concurrency.try_into()?,
+            priority: value.priority.try_into()?,
+        })
+    }
+}
+
+codegen_convex_serialization!(ScheduledJobQueue, SerializedScheduledJobQueue);
+
+/// The queue as passed to `scheduler.withQueue`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledJobQueueJson {
+    name: String,
+    max_concurrency: u32,
+    priority: Option<u32>,
+}
+
+impl TryFrom<ScheduledJobQueueJson> for ScheduledJobQueue {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ScheduledJobQueueJson) -> anyhow::Result<Self> {
+        let queue = Self {
+            name: value.name,
+            max_concurrency: value.max_concurrency,
+            priority: value.priority.unwrap_or(0),
+        };
+        queue.validate()?;
+        Ok(queue)
+    }
+}
+
 /// The state machine for scheduled jobs. Note that only actions go through the
 /// InProgress state. Mutations jump straight from Pending to one of the
 /// completion states.
@@ -72,6 +72,7 @@ impl VirtualSystemDocMapper for ScheduledJobsDocMapper {
                 Some(ts) => Some(timestamp_to_ms(ts)?),
                 None => None,
             },
+            retries: job.retry_policy.map(|_| job.attempts.retries),
         };
         let mut public_job_resolved: ConvexObject = public_job.try_into()?;
 
@@ -103,6 +104,9 @@ pub struct PublicScheduledJob {
     pub state: ScheduledJobState,
     pub scheduled_time: f64,
     pub completed_time: Option<f64>,
+    /// How many times the job has been rerun after failing, if it was
+    /// scheduled with a retry policy.
+    pub retries: Option<u32>,
 }
 
 impl TryFrom<PublicScheduledJob> for ConvexObject {
@@ -136,6 +140,9 @@ impl TryFrom<PublicScheduledJob> for ConvexObject {
                 ConvexValue::Float64(completed_time),
             );
         }
+        if let Some(retries) = job.retries {
+            obj.insert("retries".parse()?, ConvexValue::Float64(retries.into()));
+        }
         ConvexObject::try_from(obj)
     }
 }
@@ -185,12 +192,24 @@ impl TryFrom<ConvexObject> for PublicScheduledJob {
                 "Invalid `completedTime` field for PublicScheduledJob: {completed_time:?}"
             ),
         };
+        let retries = match fields.remove("retries") {
+            None => None,
+            Some(ConvexValue::Float64(retries))
+                if retries.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(&retries) =>
+            {
+                Some(retries as u32)
+            },
+            retries => {
+                anyhow::bail!("Invalid `retries` field for PublicScheduledJob: {retries:?}")
+            },
+        };
         Ok(PublicScheduledJob {
             name,
             args,
             state,
             scheduled_time,
             completed_time,
+            retries,
         })
     }
 }
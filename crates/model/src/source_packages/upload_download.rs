@@ -97,8 +97,8 @@ async fn write_package(
         let mut source_map_checksum = None;
         if let Some(ref source_map) = module.source_map {
             let source_map = source_map.as_bytes();
-            // NB: All modules' canonicalized paths have a ".js" extension, so it's safe to
-            // suffix this with ".map".
+            // NB: All modules with source maps have canonicalized paths with a ".js"
+            // extension, so it's safe to suffix this with ".map".
             let source_map_path = format!("modules/{}.map", String::from(path.clone()));
             let builder = ZipEntryBuilder::new(source_map_path.clone(), Compression::Deflate)
                 .unix_permissions(0o644);
@@ -188,7 +188,7 @@ pub async fn download_package(
         let path = path
             .strip_prefix("modules/")
             .context("Path does not start with modules/?")?;
-        let (module_path, is_source_map) = if path.ends_with(".js") {
+        let (module_path, is_source_map) = if path.ends_with(".js") || path.ends_with(".wasm") {
             (path.parse::<CanonicalizedModulePath>()?, false)
         } else if path.ends_with(".js.map") {
             (path.trim_end_matches(".map").parse()?, true)
@@ -0,0 +1,14 @@
+//! Helpers shared by system tables that store developer values as JSON bytes.
+//! Values are kept as bytes because a `Document`'s top-level object restricts
+//! which field names can be used.
+use serde_bytes::ByteBuf;
+use serde_json::Value as JsonValue;
+use value::ConvexValue;
+
+pub fn value_to_bytes(value: ConvexValue) -> anyhow::Result<ByteBuf> {
+    Ok(ByteBuf::from(value.json_serialize()?.into_bytes()))
+}
+
+pub fn bytes_to_json(bytes: &ByteBuf) -> anyhow::Result<JsonValue> {
+    Ok(serde_json::from_slice(bytes)?)
+}
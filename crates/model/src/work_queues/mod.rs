@@ -0,0 +1,418 @@
+use std::{
+    sync::LazyLock,
+    time::Duration,
+};
+
+use common::{
+    document::{
+        ParseDocument,
+        ParsedDocument,
+        CREATION_TIME_FIELD_PATH,
+    },
+    query::{
+        IndexRange,
+        IndexRangeExpression,
+        Order,
+        Query,
+    },
+    runtime::Runtime,
+};
+use database::{
+    ResolvedQuery,
+    SystemMetadataModel,
+    Transaction,
+};
+use errors::ErrorMetadata;
+use rand::Rng;
+use sync_types::Timestamp;
+use value::{
+    ConvexValue,
+    DeveloperDocumentId,
+    FieldPath,
+    ResolvedDocumentId,
+    TableName,
+    TableNamespace,
+};
+
+use self::types::{
+    WorkQueueLeaseJson,
+    WorkQueueMessage,
+};
+use crate::{
+    utils::value_to_bytes,
+    SystemIndex,
+    SystemTable,
+};
+
+pub mod types;
+
+pub static WORK_QUEUE_MESSAGES_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_work_queue_messages"
+        .parse()
+        .expect("_work_queue_messages is not a valid system table name")
+});
+
+/// By queue, shard and visibility. Consumers pick a random shard to start
+/// reading from so they don't all conflict on the same messages.
+pub static WORK_QUEUE_MESSAGES_INDEX_BY_QUEUE_AND_VISIBLE_TS: LazyLock<
+    SystemIndex<WorkQueueMessagesTable>,
+> = LazyLock::new(|| {
+    SystemIndex::new(
+        "by_queue_and_visible_ts",
+        [
+            &QUEUE_FIELD,
+            &DEAD_LETTERED_FIELD,
+            &SHARD_FIELD,
+            &VISIBLE_TS_FIELD,
+            &CREATION_TIME_FIELD_PATH,
+        ],
+    )
+    .unwrap()
+});
+static QUEUE_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "queue".parse().expect("invalid queue field"));
+static DEAD_LETTERED_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "deadLettered".parse().expect("invalid deadLettered field"));
+static SHARD_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "shard".parse().expect("invalid shard field"));
+static VISIBLE_TS_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "visibleTs".parse().expect("invalid visibleTs field"));
+
+/// Number of shards each queue is spread across.
+pub const WORK_QUEUE_NUM_SHARDS: u32 = 16;
+pub const MAX_WORK_QUEUE_NAME_LENGTH: usize = 64;
+pub const MAX_DEQUEUE_BATCH_SIZE: usize = 100;
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+pub const MAX_MAX_ATTEMPTS: u32 = 100;
+pub const MAX_VISIBILITY_TIMEOUT: Duration = Duration::from_secs(12 * 60 * 60);
+
+pub struct WorkQueueMessagesTable;
+impl SystemTable for WorkQueueMessagesTable {
+    type Metadata = WorkQueueMessage;
+
+    fn table_name() -> &'static TableName {
+        &WORK_QUEUE_MESSAGES_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![WORK_QUEUE_MESSAGES_INDEX_BY_QUEUE_AND_VISIBLE_TS.clone()]
+    }
+}
+
+pub fn validate_queue_name(queue: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        !queue.is_empty()
+            && queue.len() <= MAX_WORK_QUEUE_NAME_LENGTH
+            && queue
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+        ErrorMetadata::bad_request(
+            "InvalidWorkQueueName",
+            format!(
+                "Invalid work queue name {queue:?}. Queue names must be 1 to \
+                 {MAX_WORK_QUEUE_NAME_LENGTH} characters of letters, digits, `_` or `-`"
+            ),
+        )
+    );
+    Ok(())
+}
+
+// Transactional queues with SQS-style leases. Messages enqueued in a mutation
+// become visible when it commits, and dequeueing a message hides it for a
+// visibility timeout instead of removing it, so a consumer that dies without
+// acking it lets another one pick it up.
+pub struct WorkQueueModel<'a, RT: Runtime> {
+    tx: &'a mut Transaction<RT>,
+    namespace: TableNamespace,
+}
+
+impl<'a, RT: Runtime> WorkQueueModel<'a, RT> {
+    pub fn new(tx: &'a mut Transaction<RT>, namespace: TableNamespace) -> Self {
+        Self { tx, namespace }
+    }
+
+    pub async fn enqueue(
+        &mut self,
+        queue: String,
+        body: ConvexValue,
+        delay: Duration,
+        max_attempts: Option<u32>,
+    ) -> anyhow::Result<DeveloperDocumentId> {
+        validate_queue_name(&queue)?;
+        let max_attempts = max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+        anyhow::ensure!(
+            (1..=MAX_MAX_ATTEMPTS).contains(&max_attempts),
+            ErrorMetadata::bad_request(
+                "InvalidMaxAttempts",
+                format!("maxAttempts must be between 1 and {MAX_MAX_ATTEMPTS}"),
+            )
+        );
+        let now = self.tx.runtime().generate_timestamp()?;
+        let message = WorkQueueMessage {
+            queue,
+            shard: self
+                .tx
+                .runtime()
+                .rng()
+                .random_range(0..WORK_QUEUE_NUM_SHARDS),
+            body_bytes: value_to_bytes(body)?,
+            visible_ts: now.add(delay)?,
+            attempts: 0,
+            max_attempts,
+            dead_lettered: false,
+            last_error: None,
+        };
+        let id = SystemMetadataModel::new(self.tx, self.namespace)
+            .insert_metadata(&WORK_QUEUE_MESSAGES_TABLE, message.try_into()?)
+            .await?;
+        Ok(id.developer_id)
+    }
+
+    /// Leases up to `max_messages` visible messages, hiding each one for
+    /// `visibility_timeout`. Messages whose last lease expired after their
+    /// final attempt are dead-lettered instead of being handed out again.
+    pub async fn dequeue(
+        &mut self,
+        queue: &str,
+        max_messages: usize,
+        visibility_timeout: Duration,
+    ) -> anyhow::Result<Vec<WorkQueueLeaseJson>> {
+        validate_queue_name(queue)?;
+        anyhow::ensure!(
+            (1..=MAX_DEQUEUE_BATCH_SIZE).contains(&max_messages),
+            ErrorMetadata::bad_request(
+                "InvalidDequeueBatchSize",
+                format!("maxMessages must be between 1 and {MAX_DEQUEUE_BATCH_SIZE}"),
+            )
+        );
+        anyhow::ensure!(
+            visibility_timeout <= MAX_VISIBILITY_TIMEOUT,
+            ErrorMetadata::bad_request(
+                "InvalidVisibilityTimeout",
+                format!(
+                    "The visibility timeout can be at most {} seconds",
+                    MAX_VISIBILITY_TIMEOUT.as_secs()
+                ),
+            )
+        );
+        let now = self.tx.runtime().generate_timestamp()?;
+        let lease_until = now.add(visibility_timeout)?;
+        let first_shard = self
+            .tx
+            .runtime()
+            .rng()
+            .random_range(0..WORK_QUEUE_NUM_SHARDS);
+        let mut leases = vec![];
+        for i in 0..WORK_QUEUE_NUM_SHARDS {
+            if leases.len() >= max_messages {
+                break;
+            }
+            let shard = (first_shard + i) % WORK_QUEUE_NUM_SHARDS;
+            let query = Self::shard_query(queue, shard, now);
+            let mut query_stream = ResolvedQuery::new(self.tx, self.namespace, query)?;
+            let mut visible = vec![];
+            while visible.len() < max_messages - leases.len()
+                && let Some(doc) = query_stream.next(self.tx, None).await?
+            {
+                let message: ParsedDocument<WorkQueueMessage> = doc.parse()?;
+                visible.push(message);
+            }
+            for message in visible {
+                let (id, mut message) = message.into_id_and_value();
+                if message.attempts_exhausted() {
+                    message.dead_lettered = true;
+                    message.last_error.get_or_insert_with(|| {
+                        "Visibility timeout expired on the final attempt".to_string()
+                    });
+                    self.replace(id, message).await?;
+                    continue;
+                }
+                message.attempts += 1;
+                message.visible_ts = lease_until;
+                leases.push(WorkQueueLeaseJson {
+                    id: id.developer_id.encode(),
+                    body: message.body()?,
+                    attempt: message.attempts,
+                    last_error: message.last_error.clone(),
+                });
+                self.replace(id, message).await?;
+            }
+        }
+        Ok(leases)
+    }
+
+    /// Removes a leased message from the queue.
+    pub async fn ack(
+        &mut self,
+        message_id: DeveloperDocumentId,
+        attempt: u32,
+    ) -> anyhow::Result<()> {
+        let message = self.leased_message(message_id, attempt).await?;
+        SystemMetadataModel::new(self.tx, self.namespace)
+            .delete(message.id())
+            .await?;
+        Ok(())
+    }
+
+    /// Returns a leased message to the queue after `delay`, or dead-letters it
+    /// if it has used up its attempts.
+    pub async fn nack(
+        &mut self,
+        message_id: DeveloperDocumentId,
+        attempt: u32,
+        delay: Duration,
+        error: Option<String>,
+    ) -> anyhow::Result<()> {
+        let message = self.leased_message(message_id, attempt).await?;
+        let (id, mut message) = message.into_id_and_value();
+        if error.is_some() {
+            message.last_error = error;
+        }
+        if message.attempts_exhausted() {
+            message.dead_lettered = true;
+        } else {
+            message.visible_ts = self.tx.runtime().generate_timestamp()?.add(delay)?;
+        }
+        self.replace(id, message).await
+    }
+
+    /// Lists the oldest dead-lettered messages of a queue.
+    pub async fn dead_letters(
+        &mut self,
+        queue: &str,
+        limit: usize,
+    ) -> anyhow::Result<Vec<WorkQueueLeaseJson>> {
+        validate_queue_name(queue)?;
+        let limit = limit.min(MAX_DEQUEUE_BATCH_SIZE);
+        let query = Query::index_range(IndexRange {
+            index_name: WORK_QUEUE_MESSAGES_INDEX_BY_QUEUE_AND_VISIBLE_TS.name(),
+            range: vec![
+                IndexRangeExpression::Eq(
+                    QUEUE_FIELD.clone(),
+                    ConvexValue::try_from(queue.to_string())?.into(),
+                ),
+                IndexRangeExpression::Eq(
+                    DEAD_LETTERED_FIELD.clone(),
+                    ConvexValue::from(true).into(),
+                ),
+            ],
+            order: Order::Asc,
+        });
+        let mut query_stream = ResolvedQuery::new(self.tx, self.namespace, query)?;
+        let mut messages = vec![];
+        while messages.len() < limit
+            && let Some(doc) = query_stream.next(self.tx, None).await?
+        {
+            let message: ParsedDocument<WorkQueueMessage> = doc.parse()?;
+            messages.push(WorkQueueLeaseJson {
+                id: message.id().developer_id.encode(),
+                body: message.body()?,
+                attempt: message.attempts,
+                last_error: message.last_error.clone(),
+            });
+        }
+        Ok(messages)
+    }
+
+    /// Moves a dead-lettered message back onto its queue with a fresh set of
+    /// attempts.
+    pub async fn redrive(&mut self, message_id: DeveloperDocumentId) -> anyhow::Result<()> {
+        let (id, mut message) = self.get(message_id).await?.into_id_and_value();
+        anyhow::ensure!(
+            message.dead_lettered,
+            ErrorMetadata::bad_request(
+                "WorkQueueMessageNotDeadLettered",
+                format!("Message {message_id} is not dead-lettered"),
+            )
+        );
+        message.dead_lettered = false;
+        message.attempts = 0;
+        message.visible_ts = self.tx.runtime().generate_timestamp()?;
+        self.replace(id, message).await
+    }
+
+    fn shard_query(queue: &str, shard: u32, now: Timestamp) -> Query {
+        Query::index_range(IndexRange {
+            index_name: WORK_QUEUE_MESSAGES_INDEX_BY_QUEUE_AND_VISIBLE_TS.name(),
+            range: vec![
+                IndexRangeExpression::Eq(
+                    QUEUE_FIELD.clone(),
+                    ConvexValue::try_from(queue.to_string())
+                        .expect("queue names are valid strings")
+                        .into(),
+                ),
+                IndexRangeExpression::Eq(
+                    DEAD_LETTERED_FIELD.clone(),
+                    ConvexValue::from(false).into(),
+                ),
+                IndexRangeExpression::Eq(
+                    SHARD_FIELD.clone(),
+                    ConvexValue::from(i64::from(shard)).into(),
+                ),
+                IndexRangeExpression::Lte(
+                    VISIBLE_TS_FIELD.clone(),
+                    ConvexValue::from(i64::from(now)).into(),
+                ),
+            ],
+            order: Order::Asc,
+        })
+    }
+
+    async fn get(
+        &mut self,
+        message_id: DeveloperDocumentId,
+    ) -> anyhow::Result<ParsedDocument<WorkQueueMessage>> {
+        let table_mapping = self.tx.table_mapping().namespace(self.namespace);
+        let id = message_id
+            .to_resolved(table_mapping.number_to_tablet())
+            .ok()
+            .filter(|id| {
+                table_mapping.tablet_matches_name(id.tablet_id, &WORK_QUEUE_MESSAGES_TABLE)
+            })
+            .ok_or_else(|| {
+                ErrorMetadata::bad_request(
+                    "InvalidWorkQueueMessageId",
+                    format!("{message_id} is not a work queue message id"),
+                )
+            })?;
+        let Some(message) = self.tx.get(id).await? else {
+            anyhow::bail!(ErrorMetadata::not_found(
+                "WorkQueueMessageNotFound",
+                format!(
+                    "Work queue message {message_id} not found. It may have been acked already"
+                ),
+            ));
+        };
+        message.parse()
+    }
+
+    async fn leased_message(
+        &mut self,
+        message_id: DeveloperDocumentId,
+        attempt: u32,
+    ) -> anyhow::Result<ParsedDocument<WorkQueueMessage>> {
+        let message = self.get(message_id).await?;
+        anyhow::ensure!(
+            !message.dead_lettered && message.attempts == attempt,
+            ErrorMetadata::bad_request(
+                "WorkQueueLeaseLost",
+                format!(
+                    "The lease on message {message_id} from attempt {attempt} was lost. Its \
+                     visibility timeout expired and it was dequeued again"
+                ),
+            )
+        );
+        Ok(message)
+    }
+
+    async fn replace(
+        &mut self,
+        id: ResolvedDocumentId,
+        message: WorkQueueMessage,
+    ) -> anyhow::Result<()> {
+        SystemMetadataModel::new(self.tx, self.namespace)
+            .replace(id, message.try_into()?)
+            .await?;
+        Ok(())
+    }
+}
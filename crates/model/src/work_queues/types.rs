@@ -0,0 +1,110 @@
+use common::types::Timestamp;
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_bytes::ByteBuf;
+use serde_json::Value as JsonValue;
+use value::codegen_convex_serialization;
+
+use crate::utils::bytes_to_json;
+
+/// A message in a work queue. Messages are spread across `shard`s so that
+/// concurrent consumers start reading at different points of the queue
+/// instead of all conflicting on its head.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct WorkQueueMessage {
+    pub queue: String,
+    pub shard: u32,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "any::<Vec<u8>>().prop_map(ByteBuf::from)")
+    )]
+    pub body_bytes: ByteBuf,
+    /// The message can't be dequeued before this time. Leasing a message
+    /// pushes it forward by the visibility timeout.
+    pub visible_ts: Timestamp,
+    /// How many times the message has been leased. Doubles as the lease
+    /// token, since it changes every time the message is handed out.
+    pub attempts: u32,
+    pub max_attempts: u32,
+    /// Set once the message has failed `max_attempts` times. Dead-lettered
+    /// messages are never dequeued.
+    pub dead_lettered: bool,
+    pub last_error: Option<String>,
+}
+
+impl WorkQueueMessage {
+    pub fn body(&self) -> anyhow::Result<JsonValue> {
+        bytes_to_json(&self.body_bytes)
+    }
+
+    pub fn attempts_exhausted(&self) -> bool {
+        self.attempts >= self.max_attempts
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedWorkQueueMessage {
+    queue: String,
+    shard: i64,
+    // Serialized as binary for the same reason as scheduled job arguments.
+    body: ByteBuf,
+    visible_ts: i64,
+    attempts: i64,
+    max_attempts: i64,
+    dead_lettered: bool,
+    last_error: Option<String>,
+}
+
+impl From<WorkQueueMessage> for SerializedWorkQueueMessage {
+    fn from(message: WorkQueueMessage) -> Self {
+        SerializedWorkQueueMessage {
+            queue: message.queue,
+            shard: message.shard.into(),
+            body: message.body_bytes,
+            visible_ts: message.visible_ts.into(),
+            attempts: message.attempts.into(),
+            max_attempts: message.max_attempts.into(),
+            dead_lettered: message.dead_lettered,
+            last_error: message.last_error,
+        }
+    }
+}
+
+impl TryFrom<SerializedWorkQueueMessage> for WorkQueueMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedWorkQueueMessage) -> anyhow::Result<Self> {
+        Ok(WorkQueueMessage {
+            queue: value.queue,
+            shard: value.shard.try_into()?,
+            body_bytes: value.body,
+            visible_ts: value.visible_ts.try_into()?,
+            attempts: value.attempts.try_into()?,
+            max_attempts: value.max_attempts.try_into()?,
+            dead_lettered: value.dead_lettered,
+            last_error: value.last_error,
+        })
+    }
+}
+
+codegen_convex_serialization!(WorkQueueMessage, SerializedWorkQueueMessage);
+
+/// A message handed out by `dequeue` or `deadLetters`. Acking or nacking a
+/// leased message requires the `attempt` it was leased at, so a consumer
+/// whose lease expired can't settle a message that was handed to someone
+/// else.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkQueueLeaseJson {
+    pub id: String,
+    pub body: JsonValue,
+    pub attempt: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
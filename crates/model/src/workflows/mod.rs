@@ -0,0 +1,696 @@
+use std::{
+    sync::LazyLock,
+    time::Duration,
+};
+
+use common::{
+    components::CanonicalizedComponentFunctionPath,
+    document::{
+        ParseDocument,
+        ParsedDocument,
+        CREATION_TIME_FIELD_PATH,
+    },
+    execution_context::{
+        ExecutionContext,
+        ExecutionId,
+    },
+    query::{
+        IndexRange,
+        IndexRangeExpression,
+        Order,
+        Query,
+    },
+    runtime::{
+        Runtime,
+        UnixTimestamp,
+    },
+    RequestId,
+};
+use database::{
+    ResolvedQuery,
+    SystemMetadataModel,
+    Transaction,
+};
+use errors::ErrorMetadata;
+use serde_bytes::ByteBuf;
+use sync_types::Timestamp;
+use value::{
+    assert_obj,
+    ConvexArray,
+    ConvexObject,
+    ConvexValue,
+    DeveloperDocumentId,
+    FieldPath,
+    ResolvedDocumentId,
+    TableName,
+    TableNamespace,
+};
+
+use self::types::{
+    Workflow,
+    WorkflowEvent,
+    WorkflowJournalJson,
+    WorkflowState,
+    WorkflowStatusJson,
+    WorkflowStep,
+    WorkflowStepJson,
+    WorkflowStepKind,
+    WorkflowStepState,
+};
+use crate::{
+    scheduled_jobs::{
+        types::ScheduledJobState,
+        SchedulerModel,
+    },
+    utils::{
+        bytes_to_json,
+        value_to_bytes,
+    },
+    SystemIndex,
+    SystemTable,
+};
+
+#[cfg(test)]
+mod tests;
+pub mod types;
+
+pub static WORKFLOWS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_workflows"
+        .parse()
+        .expect("_workflows is not a valid system table name")
+});
+
+pub static WORKFLOW_STEPS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_workflow_steps"
+        .parse()
+        .expect("_workflow_steps is not a valid system table name")
+});
+
+pub static WORKFLOW_EVENTS_TABLE: LazyLock<TableName> = LazyLock::new(|| {
+    "_workflow_events"
+        .parse()
+        .expect("_workflow_events is not a valid system table name")
+});
+
+/// By workflow and step number. Used to load the journal in order.
+pub static WORKFLOW_STEPS_INDEX_BY_WORKFLOW_ID: LazyLock<SystemIndex<WorkflowStepsTable>> =
+    LazyLock::new(|| {
+        SystemIndex::new(
+            "by_workflow_id_and_step_number",
+            [
+                &WORKFLOW_ID_FIELD,
+                &STEP_NUMBER_FIELD,
+                &CREATION_TIME_FIELD_PATH,
+            ],
+        )
+        .unwrap()
+    });
+/// By workflow and event name. Used to hand buffered events out in the order
+/// they were sent.
+pub static WORKFLOW_EVENTS_INDEX_BY_WORKFLOW_ID: LazyLock<SystemIndex<WorkflowEventsTable>> =
+    LazyLock::new(|| {
+        SystemIndex::new(
+            "by_workflow_id_and_event",
+            [&WORKFLOW_ID_FIELD, &EVENT_FIELD, &CREATION_TIME_FIELD_PATH],
+        )
+        .unwrap()
+    });
+static WORKFLOW_ID_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "workflowId".parse().expect("invalid workflowId field"));
+static STEP_NUMBER_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "stepNumber".parse().expect("invalid stepNumber field"));
+static EVENT_FIELD: LazyLock<FieldPath> =
+    LazyLock::new(|| "event".parse().expect("invalid event field"));
+
+/// Each replay reads the whole journal, so bound how long it can get.
+pub const MAX_WORKFLOW_STEPS: u32 = 1000;
+pub const MAX_WORKFLOW_EVENT_NAME_LENGTH: usize = 256;
+
+pub fn validate_event_name(event: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        !event.is_empty() && event.len() <= MAX_WORKFLOW_EVENT_NAME_LENGTH,
+        ErrorMetadata::bad_request(
+            "InvalidWorkflowEventName",
+            format!(
+                "Invalid workflow event name {event:?}. Event names must be 1 to \
+                 {MAX_WORKFLOW_EVENT_NAME_LENGTH} characters"
+            ),
+        )
+    );
+    Ok(())
+}
+
+pub struct WorkflowsTable;
+impl SystemTable for WorkflowsTable {
+    type Metadata = Workflow;
+
+    fn table_name() -> &'static TableName {
+        &WORKFLOWS_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![]
+    }
+}
+
+pub struct WorkflowStepsTable;
+impl SystemTable for WorkflowStepsTable {
+    type Metadata = WorkflowStep;
+
+    fn table_name() -> &'static TableName {
+        &WORKFLOW_STEPS_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![WORKFLOW_STEPS_INDEX_BY_WORKFLOW_ID.clone()]
+    }
+}
+
+pub struct WorkflowEventsTable;
+impl SystemTable for WorkflowEventsTable {
+    type Metadata = WorkflowEvent;
+
+    fn table_name() -> &'static TableName {
+        &WORKFLOW_EVENTS_TABLE
+    }
+
+    fn indexes() -> Vec<SystemIndex<Self>> {
+        vec![WORKFLOW_EVENTS_INDEX_BY_WORKFLOW_ID.clone()]
+    }
+}
+
+/// A step the workflow handler wants to record. Queries and mutations have
+/// already run inside the replaying mutation by the time they are recorded.
+pub enum WorkflowStepRequest {
+    Query(Result<ConvexValue, String>),
+    Mutation(Result<ConvexValue, String>),
+    Action {
+        path: CanonicalizedComponentFunctionPath,
+        args: ConvexArray,
+    },
+    Sleep(Duration),
+    WaitForEvent(String),
+}
+
+// Maintains the journals of durable workflows. A workflow's handler is a
+// mutation that is rerun by the scheduler whenever a step finishes, replaying
+// journaled results until it reaches a step that hasn't run yet.
+pub struct WorkflowModel<'a, RT: Runtime> {
+    tx: &'a mut Transaction<RT>,
+    namespace: TableNamespace,
+}
+
+impl<'a, RT: Runtime> WorkflowModel<'a, RT> {
+    pub fn new(tx: &'a mut Transaction<RT>, namespace: TableNamespace) -> Self {
+        Self { tx, namespace }
+    }
+
+    /// Starts a workflow by scheduling the first run of its handler.
+    pub async fn start(
+        &mut self,
+        path: CanonicalizedComponentFunctionPath,
+        args: ConvexObject,
+    ) -> anyhow::Result<DeveloperDocumentId> {
+        let workflow = Workflow {
+            path: path.clone(),
+            args_bytes: value_to_bytes(ConvexValue::Object(args))?,
+            state: WorkflowState::Running,
+            start_ts: *self.tx.begin_timestamp(),
+            completed_ts: None,
+            replay_job_id: None,
+        };
+        let id = SystemMetadataModel::new(self.tx, self.namespace)
+            .insert_metadata(&WORKFLOWS_TABLE, workflow.try_into()?)
+            .await?;
+        let workflow = self.get(id.developer_id).await?;
+        let now = self.tx.runtime().unix_timestamp();
+        self.schedule_replay(workflow, now).await?;
+        Ok(id.developer_id)
+    }
+
+    pub async fn get(
+        &mut self,
+        workflow_id: DeveloperDocumentId,
+    ) -> anyhow::Result<ParsedDocument<Workflow>> {
+        let id = self.resolve(workflow_id)?;
+        let Some(workflow) = self.tx.get(id).await? else {
+            anyhow::bail!(ErrorMetadata::not_found(
+                "WorkflowNotFound",
+                format!("Workflow {workflow_id} not found"),
+            ));
+        };
+        workflow.parse()
+    }
+
+    /// Returns the journal for a replay of the workflow handler, or None if
+    /// the workflow has already finished. Sleeps that are due are completed
+    /// first so the handler can move past them.
+    pub async fn load(
+        &mut self,
+        workflow_id: DeveloperDocumentId,
+    ) -> anyhow::Result<Option<WorkflowJournalJson>> {
+        let workflow = self.get(workflow_id).await?;
+        if !workflow.state.is_running() {
+            return Ok(None);
+        }
+        let mut steps = self.steps(workflow_id).await?;
+        if let Some(last) = steps.last_mut()
+            && let WorkflowStepKind::Sleep { wake_ts } = last.kind
+            && last.state == WorkflowStepState::Pending
+            && wake_ts <= self.tx.runtime().generate_timestamp()?
+        {
+            let mut step = last.clone().into_value();
+            step.state = WorkflowStepState::completed(ConvexValue::Null)?;
+            step.completed_ts = Some(*self.tx.begin_timestamp());
+            let doc = SystemMetadataModel::new(self.tx, self.namespace)
+                .replace(last.id(), step.try_into()?)
+                .await?;
+            *last = doc.parse()?;
+        }
+        Ok(Some(WorkflowJournalJson {
+            args: workflow.args()?,
+            steps: steps
+                .into_iter()
+                .map(|step| step.into_value().try_into())
+                .collect::<anyhow::Result<_>>()?,
+        }))
+    }
+
+    /// Appends the next step to the journal. Steps that can't complete within
+    /// the replaying mutation are journaled as pending, and the step is
+    /// completed later by the scheduler, a timer or an event.
+    pub async fn record_step(
+        &mut self,
+        workflow_id: DeveloperDocumentId,
+        step_number: u32,
+        name: String,
+        request: WorkflowStepRequest,
+    ) -> anyhow::Result<WorkflowStepJson> {
+        let workflow = self.get(workflow_id).await?;
+        anyhow::ensure!(
+            workflow.state.is_running(),
+            ErrorMetadata::bad_request(
+                "WorkflowNotRunning",
+                format!("Workflow {workflow_id} is no longer running"),
+            )
+        );
+        let num_steps = self.steps(workflow_id).await?.len();
+        anyhow::ensure!(
+            step_number as usize == num_steps,
+            ErrorMetadata::bad_request(
+                "WorkflowStepOutOfOrder",
+                format!(
+                    "Workflow {workflow_id} recorded step {step_number} but has {num_steps} \
+                     steps. Workflow handlers must run their steps one at a time."
+                ),
+            )
+        );
+        anyhow::ensure!(
+            step_number < MAX_WORKFLOW_STEPS,
+            ErrorMetadata::bad_request(
+                "TooManyWorkflowSteps",
+                format!("Workflow {workflow_id} has more than {MAX_WORKFLOW_STEPS} steps"),
+            )
+        );
+
+        let now = *self.tx.begin_timestamp();
+        let finished = |result: Result<ConvexValue, String>| match result {
+            Ok(value) => WorkflowStepState::completed(value),
+            Err(error) => Ok(WorkflowStepState::Failed(error)),
+        };
+        let mut action_job_id = None;
+        let (kind, state) = match request {
+            WorkflowStepRequest::Query(result) => (WorkflowStepKind::Query, finished(result)?),
+            WorkflowStepRequest::Mutation(result) => {
+                (WorkflowStepKind::Mutation, finished(result)?)
+            },
+            WorkflowStepRequest::Action { path, args } => {
+                let scheduled_job_id = SchedulerModel::new(self.tx, self.namespace)
+                    .schedule(
+                        path,
+                        args,
+                        self.tx.runtime().unix_timestamp(),
+                        workflow_context(),
+                        None,
+                        None,
+                    )
+                    .await?;
+                action_job_id = Some(scheduled_job_id);
+                (
+                    WorkflowStepKind::Action {
+                        scheduled_job_id: scheduled_job_id.developer_id,
+                    },
+                    WorkflowStepState::Pending,
+                )
+            },
+            WorkflowStepRequest::Sleep(duration) => {
+                let wake_at = self.tx.runtime().unix_timestamp() + duration;
+                self.schedule_replay(workflow, wake_at).await?;
+                (
+                    WorkflowStepKind::Sleep {
+                        wake_ts: wake_at.as_system_time().try_into()?,
+                    },
+                    WorkflowStepState::Pending,
+                )
+            },
+            WorkflowStepRequest::WaitForEvent(event) => {
+                validate_event_name(&event)?;
+                let state = match self.take_buffered_event(workflow_id, &event).await? {
+                    Some(value_bytes) => WorkflowStepState::Completed {
+                        result_bytes: value_bytes,
+                    },
+                    None => WorkflowStepState::Pending,
+                };
+                (WorkflowStepKind::WaitForEvent { event }, state)
+            },
+        };
+        let completed_ts = (state != WorkflowStepState::Pending).then_some(now);
+        let step = WorkflowStep {
+            workflow_id,
+            step_number,
+            name,
+            kind,
+            state,
+            start_ts: now,
+            completed_ts,
+        };
+        let step_id = SystemMetadataModel::new(self.tx, self.namespace)
+            .insert_metadata(&WORKFLOW_STEPS_TABLE, step.clone().try_into()?)
+            .await?;
+        if let Some(job_id) = action_job_id {
+            SchedulerModel::new(self.tx, self.namespace)
+                .set_workflow_step(job_id, step_id.developer_id)
+                .await?;
+        }
+        step.try_into()
+    }
+
+    /// Records the outcome of the workflow handler.
+    pub async fn finish(
+        &mut self,
+        workflow_id: DeveloperDocumentId,
+        result: Result<ConvexValue, String>,
+    ) -> anyhow::Result<()> {
+        let workflow = self.get(workflow_id).await?;
+        if !workflow.state.is_running() {
+            return Ok(());
+        }
+        let (id, mut workflow) = workflow.into_id_and_value();
+        workflow.state = match result {
+            Ok(value) => WorkflowState::Completed {
+                result_bytes: value_to_bytes(value)?,
+            },
+            Err(error) => WorkflowState::Failed(error),
+        };
+        workflow.completed_ts = Some(*self.tx.begin_timestamp());
+        SystemMetadataModel::new(self.tx, self.namespace)
+            .replace(id, workflow.try_into()?)
+            .await?;
+        self.delete_buffered_events(workflow_id).await
+    }
+
+    /// Delivers an event to the workflow. If the workflow is waiting for it,
+    /// the step completes and the handler is replayed. Otherwise the event is
+    /// kept until the workflow waits for it.
+    pub async fn send_event(
+        &mut self,
+        workflow_id: DeveloperDocumentId,
+        event: String,
+        value: ConvexValue,
+    ) -> anyhow::Result<()> {
+        validate_event_name(&event)?;
+        let workflow = self.get(workflow_id).await?;
+        anyhow::ensure!(
+            workflow.state.is_running(),
+            ErrorMetadata::bad_request(
+                "WorkflowNotRunning",
+                format!("Cannot send event to workflow {workflow_id} since it is not running"),
+            )
+        );
+        if let Some(last) = self.steps(workflow_id).await?.pop()
+            && last.state == WorkflowStepState::Pending
+            && matches!(&last.kind, WorkflowStepKind::WaitForEvent { event: e } if *e == event)
+        {
+            self.complete_step(last, WorkflowStepState::completed(value)?)
+                .await?;
+            let now = self.tx.runtime().unix_timestamp();
+            return self.schedule_replay(workflow, now).await;
+        }
+        let buffered = WorkflowEvent {
+            workflow_id,
+            event,
+            value_bytes: value_to_bytes(value)?,
+        };
+        SystemMetadataModel::new(self.tx, self.namespace)
+            .insert_metadata(&WORKFLOW_EVENTS_TABLE, buffered.try_into()?)
+            .await?;
+        Ok(())
+    }
+
+    /// Cancels a running workflow along with the action it is waiting on and
+    /// any pending run of its handler, including one waiting for a sleep to
+    /// end. Canceling a workflow that already finished is a no-op.
+    pub async fn cancel(&mut self, workflow_id: DeveloperDocumentId) -> anyhow::Result<()> {
+        let workflow = self.get(workflow_id).await?;
+        if !workflow.state.is_running() {
+            return Ok(());
+        }
+        let (id, mut workflow) = workflow.into_id_and_value();
+        workflow.state = WorkflowState::Canceled;
+        workflow.completed_ts = Some(*self.tx.begin_timestamp());
+        let replay_job_id = workflow.replay_job_id;
+        SystemMetadataModel::new(self.tx, self.namespace)
+            .replace(id, workflow.try_into()?)
+            .await?;
+        if let Some(replay_job_id) = replay_job_id {
+            self.cancel_job(replay_job_id).await?;
+        }
+        if let Some(last) = self.steps(workflow_id).await?.pop()
+            && last.state == WorkflowStepState::Pending
+            && let WorkflowStepKind::Action { scheduled_job_id } = last.kind
+        {
+            self.cancel_job(scheduled_job_id).await?;
+        }
+        self.delete_buffered_events(workflow_id).await
+    }
+
+    pub async fn status(
+        &mut self,
+        workflow_id: DeveloperDocumentId,
+    ) -> anyhow::Result<WorkflowStatusJson> {
+        let workflow = self.get(workflow_id).await?.into_value();
+        let (state, result, error) = match workflow.state {
+            WorkflowState::Running => ("running", None, None),
+            WorkflowState::Completed { result_bytes } => {
+                ("completed", Some(bytes_to_json(&result_bytes)?), None)
+            },
+            WorkflowState::Failed(error) => ("failed", None, Some(error)),
+            WorkflowState::Canceled => ("canceled", None, None),
+        };
+        let steps = self
+            .steps(workflow_id)
+            .await?
+            .into_iter()
+            .map(|step| step.into_value().try_into())
+            .collect::<anyhow::Result<_>>()?;
+        Ok(WorkflowStatusJson {
+            state,
+            result,
+            error,
+            start_time: ts_to_ms(workflow.start_ts),
+            completed_time: workflow.completed_ts.map(ts_to_ms),
+            steps,
+        })
+    }
+
+    /// Called when a scheduled job that runs a workflow step finishes.
+    /// Records the job's outcome and replays the workflow.
+    pub async fn complete_scheduled_step(
+        &mut self,
+        workflow_step_id: DeveloperDocumentId,
+        job_state: &ScheduledJobState,
+        result: Option<ConvexValue>,
+    ) -> anyhow::Result<()> {
+        let step_id = self
+            .tx
+            .resolve_developer_id(&workflow_step_id, self.namespace)?;
+        let Some(step) = self.tx.get(step_id).await? else {
+            return Ok(());
+        };
+        let step: ParsedDocument<WorkflowStep> = step.parse()?;
+        if step.state != WorkflowStepState::Pending {
+            return Ok(());
+        }
+        let workflow = self.get(step.workflow_id).await?;
+        if !workflow.state.is_running() {
+            return Ok(());
+        }
+        let state = match job_state {
+            ScheduledJobState::Success => {
+                WorkflowStepState::completed(result.unwrap_or(ConvexValue::Null))?
+            },
+            ScheduledJobState::Failed(error) => WorkflowStepState::Failed(error.clone()),
+            ScheduledJobState::Canceled => {
+                WorkflowStepState::Failed("Action was canceled".to_string())
+            },
+            ScheduledJobState::Pending | ScheduledJobState::InProgress { .. } => {
+                anyhow::bail!("invalid state for completing a workflow step")
+            },
+        };
+        self.complete_step(step, state).await?;
+        let now = self.tx.runtime().unix_timestamp();
+        self.schedule_replay(workflow, now).await
+    }
+
+    fn resolve(&mut self, workflow_id: DeveloperDocumentId) -> anyhow::Result<ResolvedDocumentId> {
+        let table_mapping = self.tx.table_mapping().namespace(self.namespace);
+        let id = workflow_id
+            .to_resolved(table_mapping.number_to_tablet())
+            .ok()
+            .filter(|id| table_mapping.tablet_matches_name(id.tablet_id, &WORKFLOWS_TABLE));
+        id.ok_or_else(|| {
+            ErrorMetadata::bad_request(
+                "InvalidWorkflowId",
+                format!("{workflow_id} is not a workflow id"),
+            )
+            .into()
+        })
+    }
+
+    async fn steps(
+        &mut self,
+        workflow_id: DeveloperDocumentId,
+    ) -> anyhow::Result<Vec<ParsedDocument<WorkflowStep>>> {
+        let query = Query::index_range(IndexRange {
+            index_name: WORKFLOW_STEPS_INDEX_BY_WORKFLOW_ID.name(),
+            range: vec![IndexRangeExpression::Eq(
+                WORKFLOW_ID_FIELD.clone(),
+                ConvexValue::from(workflow_id).into(),
+            )],
+            order: Order::Asc,
+        });
+        let mut query_stream = ResolvedQuery::new(self.tx, self.namespace, query)?;
+        let mut steps = vec![];
+        while let Some(step) = query_stream.next(self.tx, None).await? {
+            steps.push(step.parse()?);
+        }
+        Ok(steps)
+    }
+
+    async fn complete_step(
+        &mut self,
+        step: ParsedDocument<WorkflowStep>,
+        state: WorkflowStepState,
+    ) -> anyhow::Result<()> {
+        let (id, mut step) = step.into_id_and_value();
+        step.state = state;
+        step.completed_ts = Some(*self.tx.begin_timestamp());
+        SystemMetadataModel::new(self.tx, self.namespace)
+            .replace(id, step.try_into()?)
+            .await?;
+        Ok(())
+    }
+
+    fn events_query(
+        workflow_id: DeveloperDocumentId,
+        event: Option<&str>,
+    ) -> anyhow::Result<Query> {
+        let mut range = vec![IndexRangeExpression::Eq(
+            WORKFLOW_ID_FIELD.clone(),
+            ConvexValue::from(workflow_id).into(),
+        )];
+        if let Some(event) = event {
+            range.push(IndexRangeExpression::Eq(
+                EVENT_FIELD.clone(),
+                ConvexValue::try_from(event.to_string())?.into(),
+            ));
+        }
+        Ok(Query::index_range(IndexRange {
+            index_name: WORKFLOW_EVENTS_INDEX_BY_WORKFLOW_ID.name(),
+            range,
+            order: Order::Asc,
+        }))
+    }
+
+    async fn take_buffered_event(
+        &mut self,
+        workflow_id: DeveloperDocumentId,
+        event: &str,
+    ) -> anyhow::Result<Option<ByteBuf>> {
+        let query = Self::events_query(workflow_id, Some(event))?;
+        let mut query_stream = ResolvedQuery::new(self.tx, self.namespace, query)?;
+        let Some(doc) = query_stream.next(self.tx, None).await? else {
+            return Ok(None);
+        };
+        let event: ParsedDocument<WorkflowEvent> = doc.parse()?;
+        SystemMetadataModel::new(self.tx, self.namespace)
+            .delete(event.id())
+            .await?;
+        Ok(Some(event.into_value().value_bytes))
+    }
+
+    async fn delete_buffered_events(
+        &mut self,
+        workflow_id: DeveloperDocumentId,
+    ) -> anyhow::Result<()> {
+        let query = Self::events_query(workflow_id, None)?;
+        let mut query_stream = ResolvedQuery::new(self.tx, self.namespace, query)?;
+        while let Some(doc) = query_stream.next(self.tx, None).await? {
+            SystemMetadataModel::new(self.tx, self.namespace)
+                .delete(doc.id())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Schedules a run of the workflow's handler and remembers it on the
+    /// workflow so that canceling the workflow can cancel the run. A run is
+    /// only scheduled while the handler is blocked on a step, so at most one
+    /// is pending at a time.
+    async fn schedule_replay(
+        &mut self,
+        workflow: ParsedDocument<Workflow>,
+        ts: UnixTimestamp,
+    ) -> anyhow::Result<()> {
+        let (id, mut workflow) = workflow.into_id_and_value();
+        let args = ConvexArray::try_from(vec![ConvexValue::Object(
+            assert_obj!("workflowId" => id.developer_id.encode()),
+        )])?;
+        let job_id = SchedulerModel::new(self.tx, self.namespace)
+            .schedule(
+                workflow.path.clone(),
+                args,
+                ts,
+                workflow_context(),
+                None,
+                None,
+            )
+            .await?;
+        workflow.replay_job_id = Some(job_id.developer_id);
+        SystemMetadataModel::new(self.tx, self.namespace)
+            .replace(id, workflow.try_into()?)
+            .await?;
+        Ok(())
+    }
+
+    async fn cancel_job(&mut self, job_id: DeveloperDocumentId) -> anyhow::Result<()> {
+        let job_id = self.tx.resolve_developer_id(&job_id, self.namespace)?;
+        let mut model = SchedulerModel::new(self.tx, self.namespace);
+        // Finished jobs may already have been garbage collected.
+        if model.check_status(job_id).await?.is_some() {
+            model.cancel(job_id).await?;
+        }
+        Ok(())
+    }
+}
+
+fn ts_to_ms(ts: Timestamp) -> f64 {
+    i64::from(ts) as f64 / 1_000_000.0
+}
+
+// Jobs scheduled on behalf of a workflow are owned by the workflow rather than
+// the replay that happened to schedule them, so they don't inherit its
+// cancellation.
+fn workflow_context() -> ExecutionContext {
+    ExecutionContext::new_from_parts(RequestId::new(), ExecutionId::new(), None, true)
+}
@@ -0,0 +1,257 @@
+use std::{
+    str::FromStr,
+    time::Duration,
+};
+
+use common::components::{
+    CanonicalizedComponentFunctionPath,
+    ComponentPath,
+};
+use database::test_helpers::DbFixtures;
+use errors::ErrorMetadataAnyhowExt;
+use runtime::testing::TestRuntime;
+use sync_types::CanonicalizedUdfPath;
+use value::{
+    assert_obj,
+    ConvexArray,
+    ConvexValue,
+    TableNamespace,
+};
+
+use crate::{
+    scheduled_jobs::{
+        types::ScheduledJobState,
+        SchedulerModel,
+    },
+    test_helpers::DbFixturesWithModel,
+    workflows::{
+        WorkflowModel,
+        WorkflowStepRequest,
+        MAX_WORKFLOW_EVENT_NAME_LENGTH,
+    },
+};
+
+fn workflow_path() -> CanonicalizedComponentFunctionPath {
+    CanonicalizedComponentFunctionPath {
+        component: ComponentPath::test_user(),
+        udf_path: CanonicalizedUdfPath::from_str("workflows:doubleThenIncrement").unwrap(),
+    }
+}
+
+fn action_path() -> CanonicalizedComponentFunctionPath {
+    CanonicalizedComponentFunctionPath {
+        component: ComponentPath::test_user(),
+        udf_path: CanonicalizedUdfPath::from_str("workflows:double").unwrap(),
+    }
+}
+
+#[convex_macro::test_runtime]
+async fn test_workflow_journals_steps(rt: TestRuntime) -> anyhow::Result<()> {
+    let db = DbFixtures::new_with_model(&rt).await?.db;
+    let mut tx = db.begin_system().await?;
+    let mut model = WorkflowModel::new(&mut tx, TableNamespace::test_user());
+    let workflow_id = model
+        .start(workflow_path(), assert_obj!("orderId" => "abc"))
+        .await?;
+
+    let step = model
+        .record_step(
+            workflow_id,
+            0,
+            "orders:get".to_string(),
+            WorkflowStepRequest::Query(Ok(ConvexValue::from(1.0))),
+        )
+        .await?;
+    assert_eq!(step.state, "completed");
+
+    // Steps must be recorded in order.
+    let err = model
+        .record_step(
+            workflow_id,
+            2,
+            "shipped".to_string(),
+            WorkflowStepRequest::WaitForEvent("shipped".to_string()),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.short_msg(), "WorkflowStepOutOfOrder");
+
+    let step = model
+        .record_step(
+            workflow_id,
+            1,
+            "shipped".to_string(),
+            WorkflowStepRequest::WaitForEvent("shipped".to_string()),
+        )
+        .await?;
+    assert_eq!(step.state, "pending");
+
+    model
+        .send_event(workflow_id, "shipped".to_string(), ConvexValue::from(2.0))
+        .await?;
+    let journal = model.load(workflow_id).await?.unwrap();
+    assert_eq!(journal.steps.len(), 2);
+    assert_eq!(journal.steps[1].state, "completed");
+
+    model.finish(workflow_id, Ok(ConvexValue::Null)).await?;
+    assert!(model.load(workflow_id).await?.is_none());
+    let status = model.status(workflow_id).await?;
+    assert_eq!(status.state, "completed");
+    assert!(status.completed_time.is_some());
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_workflow_buffers_events(rt: TestRuntime) -> anyhow::Result<()> {
+    let db = DbFixtures::new_with_model(&rt).await?.db;
+    let mut tx = db.begin_system().await?;
+    let mut model = WorkflowModel::new(&mut tx, TableNamespace::test_user());
+    let workflow_id = model.start(workflow_path(), assert_obj!()).await?;
+
+    // An event sent before the workflow waits for it completes the step
+    // as soon as it's recorded.
+    model
+        .send_event(workflow_id, "approved".to_string(), ConvexValue::Null)
+        .await?;
+    let step = model
+        .record_step(
+            workflow_id,
+            0,
+            "approved".to_string(),
+            WorkflowStepRequest::WaitForEvent("approved".to_string()),
+        )
+        .await?;
+    assert_eq!(step.state, "completed");
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_workflow_rejects_invalid_event_names(rt: TestRuntime) -> anyhow::Result<()> {
+    let db = DbFixtures::new_with_model(&rt).await?.db;
+    let mut tx = db.begin_system().await?;
+    let mut model = WorkflowModel::new(&mut tx, TableNamespace::test_user());
+    let workflow_id = model.start(workflow_path(), assert_obj!()).await?;
+
+    let too_long = "a".repeat(MAX_WORKFLOW_EVENT_NAME_LENGTH + 1);
+    let err = model
+        .send_event(workflow_id, too_long.clone(), ConvexValue::Null)
+        .await
+        .unwrap_err();
+    assert_eq!(err.short_msg(), "InvalidWorkflowEventName");
+    let err = model
+        .record_step(
+            workflow_id,
+            0,
+            "wait".to_string(),
+            WorkflowStepRequest::WaitForEvent(too_long),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(err.short_msg(), "InvalidWorkflowEventName");
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_action_step_completes_with_job_result(rt: TestRuntime) -> anyhow::Result<()> {
+    let db = DbFixtures::new_with_model(&rt).await?.db;
+    let mut tx = db.begin_system().await?;
+    let workflow_id = WorkflowModel::new(&mut tx, TableNamespace::test_user())
+        .start(workflow_path(), assert_obj!())
+        .await?;
+    WorkflowModel::new(&mut tx, TableNamespace::test_user())
+        .record_step(
+            workflow_id,
+            0,
+            "workflows:double".to_string(),
+            WorkflowStepRequest::Action {
+                path: action_path(),
+                args: ConvexArray::try_from(vec![ConvexValue::Object(assert_obj!("x" => 2.0))])?,
+            },
+        )
+        .await?;
+
+    let mut scheduler = SchedulerModel::new(&mut tx, TableNamespace::test_user());
+    let action_job = scheduler
+        .list()
+        .await?
+        .into_iter()
+        .find(|job| job.path == action_path())
+        .unwrap();
+    assert!(action_job.workflow_step_id.is_some());
+    scheduler
+        .complete_with_result(
+            action_job.id(),
+            ScheduledJobState::Success,
+            Some(ConvexValue::from(4.0)),
+        )
+        .await?;
+
+    let journal = WorkflowModel::new(&mut tx, TableNamespace::test_user())
+        .load(workflow_id)
+        .await?
+        .unwrap();
+    assert_eq!(journal.steps[0].state, "completed");
+    assert_eq!(journal.steps[0].result, Some(serde_json::json!(4.0)));
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_cancel_workflow(rt: TestRuntime) -> anyhow::Result<()> {
+    let db = DbFixtures::new_with_model(&rt).await?.db;
+    let mut tx = db.begin_system().await?;
+    let mut model = WorkflowModel::new(&mut tx, TableNamespace::test_user());
+    let workflow_id = model.start(workflow_path(), assert_obj!()).await?;
+    model.cancel(workflow_id).await?;
+
+    assert_eq!(model.status(workflow_id).await?.state, "canceled");
+    assert!(model.load(workflow_id).await?.is_none());
+    let err = model
+        .send_event(workflow_id, "approved".to_string(), ConvexValue::Null)
+        .await
+        .unwrap_err();
+    assert_eq!(err.short_msg(), "WorkflowNotRunning");
+
+    // The first run of the handler was canceled along with the workflow.
+    let jobs = SchedulerModel::new(&mut tx, TableNamespace::test_user())
+        .list()
+        .await?;
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].state, ScheduledJobState::Canceled);
+    Ok(())
+}
+
+#[convex_macro::test_runtime]
+async fn test_cancel_sleeping_workflow(rt: TestRuntime) -> anyhow::Result<()> {
+    let db = DbFixtures::new_with_model(&rt).await?.db;
+    let mut tx = db.begin_system().await?;
+    let mut model = WorkflowModel::new(&mut tx, TableNamespace::test_user());
+    let workflow_id = model.start(workflow_path(), assert_obj!()).await?;
+    // The first run of the handler is the one that records the sleep.
+    let mut scheduler = SchedulerModel::new(&mut tx, TableNamespace::test_user());
+    let first_run = scheduler.list().await?.pop().unwrap();
+    scheduler
+        .complete(first_run.id(), ScheduledJobState::Success)
+        .await?;
+    let mut model = WorkflowModel::new(&mut tx, TableNamespace::test_user());
+    model
+        .record_step(
+            workflow_id,
+            0,
+            "sleep".to_string(),
+            WorkflowStepRequest::Sleep(Duration::from_secs(60 * 60)),
+        )
+        .await?;
+    model.cancel(workflow_id).await?;
+
+    // The run that would have woken the workflow up is canceled.
+    let jobs = SchedulerModel::new(&mut tx, TableNamespace::test_user())
+        .list()
+        .await?;
+    let mut states: Vec<_> = jobs.into_iter().map(|job| job.into_value().state).collect();
+    states.sort_by_key(|state| state == &ScheduledJobState::Canceled);
+    assert_eq!(
+        states,
+        vec![ScheduledJobState::Success, ScheduledJobState::Canceled]
+    );
+    Ok(())
+}
@@ -0,0 +1,436 @@
+use common::{
+    components::{
+        CanonicalizedComponentFunctionPath,
+        ComponentPath,
+    },
+    types::Timestamp,
+};
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_bytes::ByteBuf;
+use serde_json::Value as JsonValue;
+use value::{
+    codegen_convex_serialization,
+    ConvexValue,
+    DeveloperDocumentId,
+};
+
+use crate::utils::{
+    bytes_to_json,
+    value_to_bytes,
+};
+
+/// A durable workflow. The workflow's handler is a mutation that is replayed
+/// against the journal in `_workflow_steps` each time one of its steps
+/// completes, until the handler returns or throws.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct Workflow {
+    /// The mutation that implements the workflow. It always lives in the same
+    /// component as the `_workflows` table that tracks it.
+    pub path: CanonicalizedComponentFunctionPath,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "any::<Vec<u8>>().prop_map(ByteBuf::from)")
+    )]
+    pub args_bytes: ByteBuf,
+    pub state: WorkflowState,
+    pub start_ts: Timestamp,
+    pub completed_ts: Option<Timestamp>,
+    /// The most recently scheduled run of the handler, which canceling the
+    /// workflow cancels if it hasn't run yet.
+    pub replay_job_id: Option<DeveloperDocumentId>,
+}
+
+impl Workflow {
+    pub fn args(&self) -> anyhow::Result<JsonValue> {
+        Ok(serde_json::from_slice(&self.args_bytes)?)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum WorkflowState {
+    Running,
+    Completed {
+        #[cfg_attr(
+            any(test, feature = "testing"),
+            proptest(strategy = "any::<Vec<u8>>().prop_map(ByteBuf::from)")
+        )]
+        result_bytes: ByteBuf,
+    },
+    Failed(String),
+    Canceled,
+}
+
+impl WorkflowState {
+    pub fn is_running(&self) -> bool {
+        matches!(self, WorkflowState::Running)
+    }
+}
+
+/// One journaled step of a workflow. Steps are append-only and numbered from
+/// zero in the order the handler performed them.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct WorkflowStep {
+    pub workflow_id: DeveloperDocumentId,
+    pub step_number: u32,
+    /// Label used to detect a handler whose steps changed between replays.
+    /// Defaults to the function name or event name on the client.
+    pub name: String,
+    pub kind: WorkflowStepKind,
+    pub state: WorkflowStepState,
+    pub start_ts: Timestamp,
+    pub completed_ts: Option<Timestamp>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum WorkflowStepKind {
+    /// Queries and mutations run inside the replaying mutation, so they are
+    /// journaled already completed.
+    Query,
+    Mutation,
+    /// Actions run as scheduled jobs. The step completes when the job does.
+    Action {
+        scheduled_job_id: DeveloperDocumentId,
+    },
+    Sleep {
+        wake_ts: Timestamp,
+    },
+    WaitForEvent {
+        event: String,
+    },
+}
+
+impl WorkflowStepKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WorkflowStepKind::Query => "query",
+            WorkflowStepKind::Mutation => "mutation",
+            WorkflowStepKind::Action { .. } => "action",
+            WorkflowStepKind::Sleep { .. } => "sleep",
+            WorkflowStepKind::WaitForEvent { .. } => "waitForEvent",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub enum WorkflowStepState {
+    Pending,
+    Completed {
+        #[cfg_attr(
+            any(test, feature = "testing"),
+            proptest(strategy = "any::<Vec<u8>>().prop_map(ByteBuf::from)")
+        )]
+        result_bytes: ByteBuf,
+    },
+    Failed(String),
+}
+
+impl WorkflowStepState {
+    pub fn completed(result: ConvexValue) -> anyhow::Result<Self> {
+        Ok(WorkflowStepState::Completed {
+            result_bytes: value_to_bytes(result)?,
+        })
+    }
+}
+
+/// An event sent to a workflow before it started waiting for it.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct WorkflowEvent {
+    pub workflow_id: DeveloperDocumentId,
+    pub event: String,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "any::<Vec<u8>>().prop_map(ByteBuf::from)")
+    )]
+    pub value_bytes: ByteBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedWorkflow {
+    component: String,
+    udf_path: String,
+    // Serialized as binary for the same reason as scheduled job arguments.
+    args: ByteBuf,
+    state: SerializedWorkflowState,
+    start_ts: i64,
+    completed_ts: Option<i64>,
+    replay_job_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum SerializedWorkflowState {
+    Running,
+    Completed { result: ByteBuf },
+    Failed { error: String },
+    Canceled,
+}
+
+impl TryFrom<Workflow> for SerializedWorkflow {
+    type Error = anyhow::Error;
+
+    fn try_from(workflow: Workflow) -> anyhow::Result<Self> {
+        Ok(SerializedWorkflow {
+            component: String::from(workflow.path.component),
+            udf_path: String::from(workflow.path.udf_path),
+            args: workflow.args_bytes,
+            state: match workflow.state {
+                WorkflowState::Running => SerializedWorkflowState::Running,
+                WorkflowState::Completed { result_bytes } => SerializedWorkflowState::Completed {
+                    result: result_bytes,
+                },
+                WorkflowState::Failed(error) => SerializedWorkflowState::Failed { error },
+                WorkflowState::Canceled => SerializedWorkflowState::Canceled,
+            },
+            start_ts: workflow.start_ts.into(),
+            completed_ts: workflow.completed_ts.map(|ts| ts.into()),
+            replay_job_id: workflow.replay_job_id.map(|id| id.encode()),
+        })
+    }
+}
+
+impl TryFrom<SerializedWorkflow> for Workflow {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedWorkflow) -> anyhow::Result<Self> {
+        let component: ComponentPath = value.component.parse()?;
+        Ok(Workflow {
+            path: CanonicalizedComponentFunctionPath {
+                component,
+                udf_path: value.udf_path.parse()?,
+            },
+            args_bytes: value.args,
+            state: match value.state {
+                SerializedWorkflowState::Running => WorkflowState::Running,
+                SerializedWorkflowState::Completed { result } => WorkflowState::Completed {
+                    result_bytes: result,
+                },
+                SerializedWorkflowState::Failed { error } => WorkflowState::Failed(error),
+                SerializedWorkflowState::Canceled => WorkflowState::Canceled,
+            },
+            start_ts: value.start_ts.try_into()?,
+            completed_ts: value.completed_ts.map(|ts| ts.try_into()).transpose()?,
+            replay_job_id: value
+                .replay_job_id
+                .map(|id| DeveloperDocumentId::decode(&id))
+                .transpose()?,
+        })
+    }
+}
+
+codegen_convex_serialization!(Workflow, SerializedWorkflow);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedWorkflowStep {
+    workflow_id: String,
+    step_number: i64,
+    name: String,
+    kind: SerializedWorkflowStepKind,
+    state: SerializedWorkflowStepState,
+    start_ts: i64,
+    completed_ts: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum SerializedWorkflowStepKind {
+    Query,
+    Mutation,
+    #[serde(rename_all = "camelCase")]
+    Action {
+        scheduled_job_id: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Sleep {
+        wake_ts: i64,
+    },
+    WaitForEvent {
+        event: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum SerializedWorkflowStepState {
+    Pending,
+    Completed { result: ByteBuf },
+    Failed { error: String },
+}
+
+impl TryFrom<WorkflowStep> for SerializedWorkflowStep {
+    type Error = anyhow::Error;
+
+    fn try_from(step: WorkflowStep) -> anyhow::Result<Self> {
+        Ok(SerializedWorkflowStep {
+            workflow_id: step.workflow_id.encode(),
+            step_number: step.step_number.into(),
+            name: step.name,
+            kind: match step.kind {
+                WorkflowStepKind::Query => SerializedWorkflowStepKind::Query,
+                WorkflowStepKind::Mutation => SerializedWorkflowStepKind::Mutation,
+                WorkflowStepKind::Action { scheduled_job_id } => {
+                    SerializedWorkflowStepKind::Action {
+                        scheduled_job_id: scheduled_job_id.encode(),
+                    }
+                },
+                WorkflowStepKind::Sleep { wake_ts } => SerializedWorkflowStepKind::Sleep {
+                    wake_ts: wake_ts.into(),
+                },
+                WorkflowStepKind::WaitForEvent { event } => {
+                    SerializedWorkflowStepKind::WaitForEvent { event }
+                },
+            },
+            state: match step.state {
+                WorkflowStepState::Pending => SerializedWorkflowStepState::Pending,
+                WorkflowStepState::Completed { result_bytes } => {
+                    SerializedWorkflowStepState::Completed {
+                        result: result_bytes,
+                    }
+                },
+                WorkflowStepState::Failed(error) => SerializedWorkflowStepState::Failed { error },
+            },
+            start_ts: step.start_ts.into(),
+            completed_ts: step.completed_ts.map(|ts| ts.into()),
+        })
+    }
+}
+
+impl TryFrom<SerializedWorkflowStep> for WorkflowStep {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedWorkflowStep) -> anyhow::Result<Self> {
+        Ok(WorkflowStep {
+            workflow_id: DeveloperDocumentId::decode(&value.workflow_id)?,
+            step_number: value.step_number.try_into()?,
+            name: value.name,
+            kind: match value.kind {
+                SerializedWorkflowStepKind::Query => WorkflowStepKind::Query,
+                SerializedWorkflowStepKind::Mutation => WorkflowStepKind::Mutation,
+                SerializedWorkflowStepKind::Action { scheduled_job_id } => {
+                    WorkflowStepKind::Action {
+                        scheduled_job_id: DeveloperDocumentId::decode(&scheduled_job_id)?,
+                    }
+                },
+                SerializedWorkflowStepKind::Sleep { wake_ts } => WorkflowStepKind::Sleep {
+                    wake_ts: wake_ts.try_into()?,
+                },
+                SerializedWorkflowStepKind::WaitForEvent { event } => {
+                    WorkflowStepKind::WaitForEvent { event }
+                },
+            },
+            state: match value.state {
+                SerializedWorkflowStepState::Pending => WorkflowStepState::Pending,
+                SerializedWorkflowStepState::Completed { result } => WorkflowStepState::Completed {
+                    result_bytes: result,
+                },
+                SerializedWorkflowStepState::Failed { error } => WorkflowStepState::Failed(error),
+            },
+            start_ts: value.start_ts.try_into()?,
+            completed_ts: value.completed_ts.map(|ts| ts.try_into()).transpose()?,
+        })
+    }
+}
+
+codegen_convex_serialization!(WorkflowStep, SerializedWorkflowStep);
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedWorkflowEvent {
+    workflow_id: String,
+    event: String,
+    value: ByteBuf,
+}
+
+impl From<WorkflowEvent> for SerializedWorkflowEvent {
+    fn from(event: WorkflowEvent) -> Self {
+        SerializedWorkflowEvent {
+            workflow_id: event.workflow_id.encode(),
+            event: event.event,
+            value: event.value_bytes,
+        }
+    }
+}
+
+impl TryFrom<SerializedWorkflowEvent> for WorkflowEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SerializedWorkflowEvent) -> anyhow::Result<Self> {
+        Ok(WorkflowEvent {
+            workflow_id: DeveloperDocumentId::decode(&value.workflow_id)?,
+            event: value.event,
+            value_bytes: value.value,
+        })
+    }
+}
+
+codegen_convex_serialization!(WorkflowEvent, SerializedWorkflowEvent);
+
+/// The journal handed to the workflow handler when it replays.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowJournalJson {
+    pub args: JsonValue,
+    pub steps: Vec<WorkflowStepJson>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowStepJson {
+    pub step_number: u32,
+    pub name: String,
+    pub kind: &'static str,
+    pub state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TryFrom<WorkflowStep> for WorkflowStepJson {
+    type Error = anyhow::Error;
+
+    fn try_from(step: WorkflowStep) -> anyhow::Result<Self> {
+        let (state, result, error) = match step.state {
+            WorkflowStepState::Pending => ("pending", None, None),
+            WorkflowStepState::Completed { result_bytes } => {
+                ("completed", Some(bytes_to_json(&result_bytes)?), None)
+            },
+            WorkflowStepState::Failed(error) => ("failed", None, Some(error)),
+        };
+        Ok(WorkflowStepJson {
+            step_number: step.step_number,
+            name: step.name,
+            kind: step.kind.name(),
+            state,
+            result,
+            error,
+        })
+    }
+}
+
+/// Status returned by `getWorkflowStatus` and the admin API.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowStatusJson {
+    pub state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Milliseconds since the epoch, like `_creationTime`.
+    pub start_time: f64,
+    pub completed_time: Option<f64>,
+    pub steps: Vec<WorkflowStepJson>,
+}
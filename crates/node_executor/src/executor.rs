@@ -18,7 +18,10 @@ use common::{
         JsError,
     },
     execution_context::ExecutionContext,
-    knobs::NODE_ANALYZE_MAX_RETRIES,
+    knobs::{
+        ACTION_MAX_USER_TIMEOUT,
+        NODE_ANALYZE_MAX_RETRIES,
+    },
     log_lines::{
         LogLine,
         LogLineStructured,
@@ -28,6 +31,7 @@ use common::{
     types::{
         ActionCallbackToken,
         ConvexOrigin,
+        FunctionLimits,
         NodeDependency,
         ObjectKey,
         UdfType,
@@ -231,13 +235,17 @@ impl<RT: Runtime> Actions<RT> {
     ) -> anyhow::Result<NodeActionOutcome> {
         let path = request.path_and_args.path().clone();
         let timer = node_executor("execute");
+        // Use the user facing timeout here, which should be less than the
+        // total Node timeout. This allows us to preempt early and give
+        // better error message and logs in the common case.
+        let timeout = match request.path_and_args.limits().timeout_ms {
+            Some(timeout_ms) => Duration::from_millis(timeout_ms).min(*ACTION_MAX_USER_TIMEOUT),
+            None => self.user_timeout,
+        };
         let request = ExecutorRequest::Execute {
             request,
             backend_address: self.convex_origin.clone(),
-            // Use the user facing timeout here, which should be less than the
-            // total Node timeout. This allows us to preempt early and give
-            // better error message and logs in the common case.
-            timeout: self.user_timeout,
+            timeout,
         };
         let InvokeResponse {
             response,
@@ -488,6 +496,21 @@ impl<RT: Runtime> Actions<RT> {
                     },
                     None => ReturnsValidator::Unvalidated,
                 };
+                let limits = f.limits.unwrap_or_default();
+                if let Err(e) = limits.validate(udf_type) {
+                    let message = format!(
+                        "Invalid limits for `{}` defined in `{:?}`: {e}",
+                        f.name, path
+                    );
+                    return Ok(Err(JsError::from_message(message)));
+                }
+                if limits.memory_mb.is_some() {
+                    return Ok(Err(JsError::from_message(format!(
+                        "`{}` defined in `{:?}` sets `memoryMb`, which isn't supported for \
+                         Node.js actions.",
+                        f.name, path,
+                    ))));
+                }
                 let visibility = f.visibility.map(Visibility::from);
 
                 // Extract source position
@@ -512,14 +535,17 @@ impl<RT: Runtime> Actions<RT> {
                     .name
                     .parse()
                     .map_err(|e| invalid_function_name_error(&path, &e))?;
-                functions.push(AnalyzedFunction::new(
-                    function_name,
-                    pos,
-                    udf_type,
-                    visibility,
-                    args,
-                    returns,
-                )?);
+                functions.push(AnalyzedFunction {
+                    limits,
+                    ..AnalyzedFunction::new(
+                        function_name,
+                        pos,
+                        udf_type,
+                        visibility,
+                        args,
+                        returns,
+                    )?
+                });
             }
 
             // Sort by line number where source position of None compares least
@@ -870,6 +896,7 @@ pub struct AnalyzedNodeFunction {
     visibility: Option<VisibilityJson>,
     args: Option<ArgsValidatorJson>,
     returns: Option<ReturnsValidatorJson>,
+    limits: Option<FunctionLimits>,
 }
 
 #[derive(Debug)]
@@ -95,6 +95,7 @@ impl Dataset {
         let config = DeveloperTextIndexConfig {
             search_field: "body".parse()?,
             filter_fields: BTreeSet::new(),
+            synonyms: vec![],
         };
 
         let schema = TantivySearchIndexSchema::new(&config);
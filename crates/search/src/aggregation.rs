@@ -7,11 +7,23 @@ use std::{
     },
 };
 
+use itertools::Itertools;
 use tantivy::Term;
+use value::{
+    ConvexValue,
+    FieldPath,
+};
 
-use crate::searcher::{
-    PostingListMatch,
-    TokenMatch,
+use crate::{
+    query::{
+        FacetCount,
+        FacetCounts,
+    },
+    searcher::{
+        PostingListMatch,
+        TermSuggestion,
+        TokenMatch,
+    },
 };
 
 // Aggregate the top `max_results` posting list matches, sorted by BM25 score,
@@ -119,3 +131,129 @@ impl TokenMatchAggregator {
         self.matches.into_sorted_vec()
     }
 }
+
+// Aggregate the top `max_results` term suggestions, sorted by document
+// frequency in descending order and then by term. Since the best suggestion
+// compares as the smallest, this uses a max-heap so we can efficiently pop the
+// worst suggestion.
+pub struct TermSuggestionAggregator {
+    max_results: usize,
+    suggestions: BinaryHeap<TermSuggestion>,
+}
+
+impl TermSuggestionAggregator {
+    pub fn new(max_results: usize) -> Self {
+        Self {
+            max_results,
+            suggestions: BinaryHeap::with_capacity(max_results),
+        }
+    }
+
+    // Returns whether the suggestion was inserted. If false, the heap was full
+    // and the suggestion was worse than the current worst one.
+    pub fn insert(&mut self, suggestion: TermSuggestion) -> bool {
+        if self.max_results == 0 {
+            return false;
+        }
+        if self.suggestions.len() >= self.max_results {
+            assert_eq!(self.suggestions.len(), self.max_results);
+            let worst = self.suggestions.peek_mut().expect("Empty suggestions?");
+            if *worst < suggestion {
+                return false;
+            }
+            PeekMut::pop(worst);
+        }
+        self.suggestions.push(suggestion);
+        true
+    }
+
+    pub fn into_results(self) -> Vec<TermSuggestion> {
+        self.suggestions.into_sorted_vec()
+    }
+}
+
+// Count the values of a filter field across the documents matching a search
+// query. Unlike the other aggregators, every value needs to be counted before
+// we know which ones are the most common, so the limit is only applied when
+// producing results.
+pub struct FacetCountAggregator {
+    field_path: FieldPath,
+    counts: BTreeMap<Option<ConvexValue>, u64>,
+}
+
+impl FacetCountAggregator {
+    pub fn new(field_path: FieldPath) -> Self {
+        Self {
+            field_path,
+            counts: BTreeMap::new(),
+        }
+    }
+
+    pub fn field_path(&self) -> &FieldPath {
+        &self.field_path
+    }
+
+    pub fn insert(&mut self, value: Option<&ConvexValue>) {
+        *self.counts.entry(value.cloned()).or_insert(0) += 1;
+    }
+
+    pub fn into_results(self, max_results: usize) -> FacetCounts {
+        let num_values = self.counts.len();
+        let counts: Vec<_> = self
+            .counts
+            .into_iter()
+            .map(|(value, count)| (Reverse(count), value))
+            .sorted()
+            .take(max_results)
+            .map(|(Reverse(count), value)| FacetCount { value, count })
+            .collect();
+        FacetCounts {
+            field_path: self.field_path,
+            num_omitted_values: num_values - counts.len(),
+            counts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use value::{
+        ConvexValue,
+        FieldPath,
+    };
+
+    use super::FacetCountAggregator;
+    use crate::query::FacetCount;
+
+    #[test]
+    fn test_facet_count_aggregator() -> anyhow::Result<()> {
+        let field_path: FieldPath = "category".parse()?;
+        let books = ConvexValue::try_from("books".to_string())?;
+        let music = ConvexValue::try_from("music".to_string())?;
+        let movies = ConvexValue::try_from("movies".to_string())?;
+
+        let mut aggregator = FacetCountAggregator::new(field_path.clone());
+        for value in [&books, &music, &books, &movies, &music, &books] {
+            aggregator.insert(Some(value));
+        }
+        aggregator.insert(None);
+
+        let results = aggregator.into_results(2);
+        assert_eq!(results.field_path, field_path);
+        assert_eq!(
+            results.counts,
+            vec![
+                FacetCount {
+                    value: Some(books),
+                    count: 3,
+                },
+                FacetCount {
+                    value: Some(music),
+                    count: 2,
+                },
+            ]
+        );
+        assert_eq!(results.num_omitted_values, 2);
+        Ok(())
+    }
+}
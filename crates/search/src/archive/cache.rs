@@ -393,6 +393,7 @@ fn is_immutable(search_file_type: SearchFileType) -> bool {
         SearchFileType::FragmentedVectorSegment => true,
         SearchFileType::VectorDeletedBitset => true,
         SearchFileType::VectorIdTracker => true,
+        SearchFileType::SparseVectorSegment => true,
         // Text indexes do not appear to be read in readonly mode.
         SearchFileType::Text => false,
         SearchFileType::TextIdTracker => true,
@@ -8,6 +8,14 @@ use tantivy::tokenizer::{
 /// How many words (after stemming) can be in a text query?
 pub const MAX_QUERY_TERMS: usize = 16;
 
+/// How many additional words can synonym expansion add to a text query?
+pub const MAX_SYNONYM_QUERY_TERMS: usize = 16;
+
+/// How many multi-word synonyms can a text query expand to? Each one runs as
+/// its own conjunctive query, so this bounds how many times we search the
+/// index's segments.
+pub const MAX_SYNONYM_PHRASES: usize = 4;
+
 /// What is the maximum length of a single text term? We will silently drop
 /// terms that exceed this length.
 ///
@@ -41,8 +49,34 @@ pub const MAX_EDIT_DISTANCE: u32 = 2;
 /// maximum number of posting lists we'll want to consider in a single query.
 pub const MAX_UNIQUE_QUERY_TERMS: usize = 64;
 
+/// How many completions can a single suggest query return?
+pub const MAX_SUGGESTIONS: usize = 32;
+
+/// How many prefix matches can a single index segment return when computing
+/// suggestions? Segments return their most frequent live terms, which are
+/// merged across all segments before picking candidates.
+pub const MAX_SUGGEST_SEGMENT_TERMS: usize = 4096;
+
+/// How many of the merged prefix matches are kept as candidates for a suggest
+/// query? Segments that hit `MAX_SUGGEST_SEGMENT_TERMS` are then asked for the
+/// exact document frequencies of the candidates they didn't return.
+pub const MAX_SUGGEST_CANDIDATE_TERMS: usize = 256;
+
+/// How many filter fields can be faceted in a single search query?
+pub const MAX_FACET_FIELDS: usize = MAX_FILTER_CONDITIONS;
+
+/// How many values can a search query return counts for per faceted field?
+pub const MAX_FACET_VALUES: usize = 100;
+
 pub fn convex_en() -> TextAnalyzer {
     TextAnalyzer::from(SimpleTokenizer)
         .filter(RemoveLongFilter::limit(MAX_TEXT_TERM_LENGTH))
         .filter(LowerCaser)
 }
+
+/// The default smoothing constant for reciprocal rank fusion, from the
+/// original RRF paper.
+pub const DEFAULT_RRF_K: f64 = 60.0;
+
+/// How many results can a hybrid text and vector search return?
+pub const MAX_HYBRID_SEARCH_RESULTS: usize = 256;
@@ -249,6 +249,43 @@ pub async fn upload_vector_segment<RT: Runtime>(
     })
 }
 
+/// Sparse vector segments share the id tracker and deleted bitset formats of
+/// dense segments, so only the segment file itself differs.
+pub async fn upload_sparse_vector_segment<RT: Runtime>(
+    rt: &RT,
+    storage: Arc<dyn Storage>,
+    new_segment: VectorDiskSegmentValues,
+) -> anyhow::Result<FragmentedVectorSegment> {
+    let VectorDiskSegmentPaths {
+        segment,
+        uuids,
+        deleted_bitset,
+    } = new_segment.paths;
+    let upload_segment = upload_single_file_from_path(
+        segment,
+        storage.clone(),
+        SearchFileType::SparseVectorSegment,
+    );
+    let upload_id_tracker =
+        upload_single_file_from_path(uuids, storage.clone(), SearchFileType::VectorIdTracker);
+    let upload_bitset = upload_single_file_from_path(
+        deleted_bitset,
+        storage.clone(),
+        SearchFileType::VectorDeletedBitset,
+    );
+    let (segment_key, id_tracker_key, deleted_bitset_key) =
+        futures::try_join!(upload_segment, upload_id_tracker, upload_bitset)?;
+
+    Ok(FragmentedVectorSegment {
+        segment_key,
+        id_tracker_key,
+        deleted_bitset_key,
+        num_vectors: new_segment.num_vectors,
+        num_deleted: new_segment.num_deleted,
+        id: rt.new_uuid_v4().to_string(),
+    })
+}
+
 pub async fn upload_single_file_from_path<P: AsRef<Path>>(
     path: P,
     storage: Arc<dyn Storage>,
@@ -40,6 +40,11 @@ use vector::{
         load_disk_segment,
         merge_disk_segments_hnsw,
         UntarredVectorDiskSegmentPaths,
+        VectorDiskSegmentPaths,
+    },
+    sparse_segment::{
+        merge_sparse_segments,
+        SparseVectorSegment,
     },
     PreviousVectorSegmentsHack,
     QdrantExternalId,
@@ -50,6 +55,7 @@ use crate::{
     disk_index::{
         download_single_file_zip,
         upload_single_file,
+        upload_sparse_vector_segment,
         upload_vector_segment,
     },
     metrics::{
@@ -139,6 +145,65 @@ impl<RT: Runtime> FragmentedSegmentFetcher<RT> {
             segment, id_tracker, bitset,
         ))
     }
+
+    /// Fetch all parts of a sparse vector segment. Unlike dense segments, the
+    /// segment itself is a single file.
+    pub async fn fetch_sparse_segment<T: TryInto<FragmentedSegmentStorageKeys>>(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        fragment: T,
+    ) -> anyhow::Result<VectorDiskSegmentPaths>
+    where
+        anyhow::Error: From<T::Error>,
+    {
+        let paths: FragmentedSegmentStorageKeys = fragment.try_into()?;
+        let fetch_segment = self.archive_cache.get_single_file(
+            search_storage.clone(),
+            &paths.segment,
+            SearchFileType::SparseVectorSegment,
+        );
+        let fetch_id_tracker = self.archive_cache.get_single_file(
+            search_storage.clone(),
+            &paths.id_tracker,
+            SearchFileType::VectorIdTracker,
+        );
+        let fetch_bitset = self.archive_cache.get_single_file(
+            search_storage.clone(),
+            &paths.deleted_bitset,
+            SearchFileType::VectorDeletedBitset,
+        );
+        let (segment, uuids, deleted_bitset) =
+            futures::try_join!(fetch_segment, fetch_id_tracker, fetch_bitset)?;
+        Ok(VectorDiskSegmentPaths {
+            segment,
+            uuids,
+            deleted_bitset,
+        })
+    }
+
+    /// Fetch and load sparse vector segments with limited concurrency.
+    pub async fn load_sparse_segments<T: TryInto<FragmentedSegmentStorageKeys> + Send>(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        fragments: Vec<T>,
+        blocking_thread_pool: &BoundedThreadPool<RT>,
+    ) -> anyhow::Result<Vec<SparseVectorSegment>>
+    where
+        anyhow::Error: From<T::Error>,
+    {
+        stream::iter(fragments.into_iter().map(|fragment| {
+            let search_storage = search_storage.clone();
+            async move {
+                let paths = self.fetch_sparse_segment(search_storage, fragment).await?;
+                blocking_thread_pool
+                    .execute(move || SparseVectorSegment::load(&paths))
+                    .await?
+            }
+        }))
+        .buffer_unordered(4)
+        .try_collect()
+        .await
+    }
 }
 
 pub(crate) struct FragmentedSegmentCompactor<RT: Runtime> {
@@ -229,6 +294,48 @@ impl<RT: Runtime> FragmentedSegmentCompactor<RT> {
         log_vectors_in_compacted_segment_total(result.num_vectors);
         Ok(result)
     }
+
+    pub async fn compact_sparse<T: TryInto<FragmentedSegmentStorageKeys> + Send>(
+        &self,
+        segments: Vec<T>,
+        search_storage: Arc<dyn Storage>,
+    ) -> anyhow::Result<FragmentedVectorSegment>
+    where
+        anyhow::Error: From<T::Error>,
+    {
+        let timer = vector_compact_seconds_timer();
+        let fetch_timer = vector_compact_fetch_segments_seconds_timer();
+        let segments = self
+            .segment_fetcher
+            .load_sparse_segments(search_storage.clone(), segments, &self.blocking_thread_pool)
+            .await?;
+        fetch_timer.finish();
+        let total_segments = segments.len();
+        tracing::info!("Compacting {total_segments} sparse segments");
+
+        let tmp_dir = TempDir::new()?;
+        let target_path = tmp_dir.path().join("segment");
+        fs::create_dir(&target_path).await?;
+        let new_segment = self
+            .blocking_thread_pool
+            .execute(move || {
+                let timer = vector_compact_construct_segment_seconds_timer();
+                let result = merge_sparse_segments(segments, &target_path)?;
+                let segment_size = result.paths.segment.metadata()?.len();
+                log_compacted_segment_size_bytes(segment_size, SearchType::SparseVector);
+                timer.finish();
+                anyhow::Ok(result)
+            })
+            .await??;
+
+        let result = upload_sparse_vector_segment(&self.rt, search_storage, new_segment).await?;
+        // Ensure we own the temp dir through the entire upload
+        drop(tmp_dir);
+        tracing::debug!("Compacted {} sparse segments", total_segments);
+        timer.finish();
+        log_vectors_in_compacted_segment_total(result.num_vectors);
+        Ok(result)
+    }
 }
 
 pub struct PreviousVectorSegments(pub Vec<MutableFragmentedSegmentMetadata>);
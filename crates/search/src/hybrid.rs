@@ -0,0 +1,182 @@
+//! Fusing the results of a text query and a vector query into a single
+//! ranking with reciprocal rank fusion (RRF).
+//!
+//! RRF only looks at each result's rank within its own result list, so it
+//! doesn't need BM25 scores and vector similarities to be on comparable
+//! scales. A result's fused score is the sum of `weight / (k + rank)` across
+//! the lists it appears in, where `rank` starts at 1.
+
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+};
+
+use errors::ErrorMetadata;
+
+use crate::constants::DEFAULT_RRF_K;
+
+/// The parameters for fusing text and vector results.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReciprocalRankFusion {
+    /// Smoothing constant added to each rank. Larger values flatten the
+    /// difference between the top results and the rest of the list.
+    k: f64,
+    text_weight: f64,
+    vector_weight: f64,
+}
+
+impl Default for ReciprocalRankFusion {
+    fn default() -> Self {
+        Self {
+            k: DEFAULT_RRF_K,
+            text_weight: 1.0,
+            vector_weight: 1.0,
+        }
+    }
+}
+
+/// A result's position and original score within one of the fused lists.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComponentScore {
+    /// The 1-based rank of the result in its list.
+    pub rank: usize,
+    /// The BM25 score for text results or the similarity for vector results.
+    pub score: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FusedResult<Id> {
+    pub id: Id,
+    pub score: f64,
+    pub text: Option<ComponentScore>,
+    pub vector: Option<ComponentScore>,
+}
+
+impl ReciprocalRankFusion {
+    pub fn new(k: f64, text_weight: f64, vector_weight: f64) -> anyhow::Result<Self> {
+        if !k.is_finite() || k < 0.0 {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidHybridSearchFusion",
+                format!("Reciprocal rank fusion `k` must be a non-negative number. Actual: {k}"),
+            ));
+        }
+        for (name, weight) in [("textWeight", text_weight), ("vectorWeight", vector_weight)] {
+            if !weight.is_finite() || weight < 0.0 {
+                anyhow::bail!(ErrorMetadata::bad_request(
+                    "InvalidHybridSearchFusion",
+                    format!(
+                        "Hybrid search `{name}` must be a non-negative number. Actual: {weight}"
+                    ),
+                ));
+            }
+        }
+        if text_weight == 0.0 && vector_weight == 0.0 {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidHybridSearchFusion",
+                "At least one of `textWeight` and `vectorWeight` must be positive.",
+            ));
+        }
+        Ok(Self {
+            k,
+            text_weight,
+            vector_weight,
+        })
+    }
+
+    /// Fuse two result lists, each sorted from best to worst, and return the
+    /// best `limit` results. Ties are broken by ID so the output is
+    /// deterministic.
+    pub fn fuse<Id: Ord + Clone>(
+        &self,
+        text_results: impl IntoIterator<Item = (Id, f32)>,
+        vector_results: impl IntoIterator<Item = (Id, f32)>,
+        limit: usize,
+    ) -> Vec<FusedResult<Id>> {
+        let mut fused: BTreeMap<Id, FusedResult<Id>> = BTreeMap::new();
+        for (i, (id, score)) in text_results.into_iter().enumerate() {
+            let rank = i + 1;
+            let result = fused.entry(id.clone()).or_insert_with(|| FusedResult {
+                id,
+                score: 0.0,
+                text: None,
+                vector: None,
+            });
+            // Only count the first occurrence of an ID in each list.
+            if result.text.is_none() {
+                result.score += self.text_weight / (self.k + rank as f64);
+                result.text = Some(ComponentScore { rank, score });
+            }
+        }
+        for (i, (id, score)) in vector_results.into_iter().enumerate() {
+            let rank = i + 1;
+            let result = fused.entry(id.clone()).or_insert_with(|| FusedResult {
+                id,
+                score: 0.0,
+                text: None,
+                vector: None,
+            });
+            if result.vector.is_none() {
+                result.score += self.vector_weight / (self.k + rank as f64);
+                result.vector = Some(ComponentScore { rank, score });
+            }
+        }
+        let mut results: Vec<_> = fused.into_values().collect();
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        results.truncate(limit);
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ComponentScore,
+        ReciprocalRankFusion,
+    };
+
+    #[test]
+    fn test_rrf_prefers_results_in_both_lists() -> anyhow::Result<()> {
+        let fusion = ReciprocalRankFusion::new(60.0, 1.0, 1.0)?;
+        let results = fusion.fuse(
+            vec![("a", 3.0), ("b", 2.0), ("c", 1.0)],
+            vec![("c", 0.9), ("d", 0.8)],
+            3,
+        );
+        let ids: Vec<_> = results.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["c", "a", "d"]);
+        assert_eq!(
+            results[0].text,
+            Some(ComponentScore {
+                rank: 3,
+                score: 1.0
+            })
+        );
+        assert_eq!(
+            results[0].vector,
+            Some(ComponentScore {
+                rank: 1,
+                score: 0.9
+            })
+        );
+        assert_eq!(results[1].vector, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rrf_weights() -> anyhow::Result<()> {
+        let fusion = ReciprocalRankFusion::new(60.0, 0.0, 1.0)?;
+        let results = fusion.fuse(vec![("a", 3.0)], vec![("b", 0.5)], 10);
+        let ids: Vec<_> = results.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["b", "a"]);
+        assert_eq!(results[1].score, 0.0);
+
+        assert!(ReciprocalRankFusion::new(60.0, 0.0, 0.0).is_err());
+        assert!(ReciprocalRankFusion::new(-1.0, 1.0, 1.0).is_err());
+        Ok(())
+    }
+}
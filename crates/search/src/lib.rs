@@ -13,6 +13,7 @@ mod constants;
 mod convex_query;
 pub mod disk_index;
 pub mod fragmented_segment;
+pub mod hybrid;
 mod incremental_index;
 mod intersection;
 mod levenshtein_dfa;
@@ -33,6 +34,7 @@ use std::{
     sync::Arc,
 };
 
+pub use aggregation::FacetCountAggregator;
 use aggregation::PostingListMatchAggregator;
 use anyhow::Context;
 use common::{
@@ -46,6 +48,7 @@ use common::{
         search_value_to_bytes,
         InternalSearch,
         InternalSearchFilterExpression,
+        InternalSearchSuggest,
         SearchVersion,
     },
     runtime::{
@@ -60,10 +63,15 @@ use common::{
 use constants::CONVEX_EN_TOKENIZER;
 pub use constants::{
     convex_en,
+    DEFAULT_RRF_K,
     EXACT_SEARCH_MAX_WORD_LENGTH,
     MAX_CANDIDATE_REVISIONS,
+    MAX_FACET_FIELDS,
+    MAX_FACET_VALUES,
     MAX_FILTER_CONDITIONS,
+    MAX_HYBRID_SEARCH_RESULTS,
     MAX_QUERY_TERMS,
+    MAX_SUGGESTIONS,
     SINGLE_TYPO_SEARCH_MAX_WORD_LENGTH,
 };
 use convex_query::OrTerm;
@@ -73,12 +81,18 @@ use itertools::Itertools;
 use metrics::log_search_token_limit_exceeded;
 pub use query::{
     CandidateRevision,
+    FacetCount,
+    FacetCounts,
     FilterConditionRead,
     QueryReads,
     QueryResults,
+    SearchSuggestion,
+    SuggestResults,
     TextQueryTermRead,
 };
 use query::{
+    CompiledSuggestQuery,
+    FuzzyDistance,
     RevisionWithKeys,
     TextQueryTerm,
 };
@@ -140,12 +154,22 @@ pub use self::{
     },
 };
 use crate::{
-    aggregation::TokenMatchAggregator,
-    constants::MAX_UNIQUE_QUERY_TERMS,
+    aggregation::{
+        TermSuggestionAggregator,
+        TokenMatchAggregator,
+    },
+    constants::{
+        MAX_SUGGEST_CANDIDATE_TERMS,
+        MAX_SUGGEST_SEGMENT_TERMS,
+        MAX_SYNONYM_PHRASES,
+        MAX_SYNONYM_QUERY_TERMS,
+        MAX_UNIQUE_QUERY_TERMS,
+    },
     metrics::log_num_segments_searched_total,
     searcher::{
         Bm25Stats,
         PostingListQuery,
+        TermSuggestion,
         TokenQuery,
     },
 };
@@ -239,6 +263,8 @@ pub struct TantivySearchIndexSchema {
 
     pub filter_fields: BTreeMap<FieldPath, Field>,
 
+    synonyms: Vec<BTreeSet<String>>,
+
     pub(crate) schema: Schema,
 }
 
@@ -252,6 +278,13 @@ impl From<&TantivySearchIndexSchema> for pb::searchlight::SearchIndexConfig {
                 .cloned()
                 .map(|p| p.into())
                 .collect::<Vec<_>>(),
+            synonyms: schema
+                .synonyms
+                .iter()
+                .map(|synonym_set| pb::searchlight::SynonymSet {
+                    entries: synonym_set.iter().cloned().collect(),
+                })
+                .collect(),
         }
     }
 }
@@ -297,6 +330,7 @@ impl TantivySearchIndexSchema {
             search_field,
 
             filter_fields,
+            synonyms: index_config.synonyms.clone(),
             schema,
         }
     }
@@ -322,6 +356,7 @@ impl TantivySearchIndexSchema {
         DeveloperTextIndexConfig {
             search_field: self.search_field_path.clone(),
             filter_fields: self.filter_fields.keys().cloned().collect(),
+            synonyms: self.synonyms.clone(),
         }
     }
 
@@ -437,10 +472,98 @@ impl TantivySearchIndexSchema {
     ) -> anyhow::Result<RevisionWithKeys> {
         log_num_segments_searched_total(segments.len());
 
-        // Step 1: Map the old `CompiledQuery` struct onto `TokenQuery`s.
+        let Some(ResolvedQueryTerms {
+            or_terms,
+            and_terms,
+            phrases,
+            bm25_stats,
+        }) = self
+            .resolve_query_terms(
+                compiled_query,
+                memory_index,
+                search_storage.clone(),
+                &segments,
+                disk_index_ts,
+                searcher.clone(),
+            )
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+
+        // Multi-word synonyms only match documents containing all of their words,
+        // so each phrase gets its own posting list query that also requires its
+        // words. Every query shares the same BM25 statistics and scores the
+        // query's own terms along with the phrase's words, so a document's score
+        // doesn't depend on which query found it and the results can be merged
+        // by score.
+        let mut posting_list_queries = vec![];
+        if !or_terms.is_empty() {
+            posting_list_queries.push((or_terms.clone(), and_terms.clone()));
+        }
+        for phrase in phrases {
+            let mut phrase_or_terms = or_terms.clone();
+            let mut phrase_and_terms = and_terms.clone();
+            for or_term in phrase {
+                phrase_and_terms.push(or_term.term.clone());
+                if !or_terms.iter().any(|t| t.term == or_term.term) {
+                    phrase_or_terms.push(or_term);
+                }
+            }
+            posting_list_queries.push((phrase_or_terms, phrase_and_terms));
+        }
+        let num_queries = posting_list_queries.len();
+        let mut results = vec![];
+        for (or_terms, and_terms) in posting_list_queries {
+            let query_results = self
+                .query_posting_lists(
+                    or_terms,
+                    and_terms,
+                    &bm25_stats,
+                    memory_index,
+                    search_storage.clone(),
+                    &segments,
+                    disk_index_ts,
+                    searcher.clone(),
+                )
+                .await?;
+            results.extend(query_results);
+        }
+        if num_queries > 1 {
+            // Index keys sort by descending score, so keep each document's first
+            // (best) match.
+            results.sort_by(|(_, a), (_, b)| a.cmp(b));
+            let mut seen_ids = BTreeSet::new();
+            results.retain(|(candidate, _)| seen_ids.insert(candidate.id));
+            results.truncate(MAX_CANDIDATE_REVISIONS);
+        }
+        Ok(results)
+    }
+
+    /// Find the index terms matching a compiled query and their BM25
+    /// statistics across the memory and disk indexes. Returns `None` if the
+    /// query can't match any documents.
+    async fn resolve_query_terms(
+        &self,
+        compiled_query: CompiledQuery,
+        memory_index: &MemoryTextIndex,
+        search_storage: Arc<dyn Storage>,
+        segments: &[FragmentedTextStorageKeys],
+        disk_index_ts: Timestamp,
+        searcher: Arc<dyn Searcher>,
+    ) -> anyhow::Result<Option<ResolvedQueryTerms>> {
+        let CompiledQuery {
+            text_query,
+            filter_conditions,
+            synonym_phrases,
+        } = compiled_query;
+
+        // Step 1: Map the old `CompiledQuery` struct onto `TokenQuery`s. The words of
+        // multi-word synonyms are matched exactly, after the query's own terms and
+        // before its filter conditions.
         let mut token_queries = vec![];
-        let num_text_query_terms = compiled_query.text_query.len() as u32;
-        for query_term in compiled_query.text_query {
+        let num_text_query_terms = text_query.len() as u32;
+        for query_term in text_query {
             let query = TokenQuery {
                 max_distance: query_term.max_distance(),
                 prefix: query_term.prefix(),
@@ -448,9 +571,19 @@ impl TantivySearchIndexSchema {
             };
             token_queries.push(query);
         }
-        let mut exist_filter_conditions = false;
-        for CompiledFilterCondition::Must(term) in compiled_query.filter_conditions {
-            exist_filter_conditions = true;
+        let phrase_terms: BTreeSet<Term> = synonym_phrases.iter().flatten().cloned().collect();
+        let num_phrase_terms = phrase_terms.len() as u32;
+        for term in phrase_terms {
+            let query = TokenQuery {
+                term,
+                max_distance: 0,
+                prefix: false,
+            };
+            token_queries.push(query);
+        }
+        let mut filter_terms = BTreeSet::new();
+        for CompiledFilterCondition::Must(term) in filter_conditions {
+            filter_terms.insert(term.clone());
             let query = TokenQuery {
                 term,
                 max_distance: 0,
@@ -464,7 +597,7 @@ impl TantivySearchIndexSchema {
         // into the joinset *before* calling `block_in_place` so we can make progress
         // while this thread gets transitioned to being a blocking thread.
         let mut token_query_futures = JoinSet::new();
-        for segment in &segments {
+        for segment in segments {
             let searcher = searcher.clone();
             let search_storage = search_storage.clone();
             let segment = segment.clone();
@@ -495,43 +628,44 @@ impl TantivySearchIndexSchema {
             }
         }
 
-        // Deduplicate terms, using the best distance for each term.
+        // Deduplicate terms, using the best distance for each term. A term can be
+        // a query term, a synonym phrase's word and a filter term at the same time,
+        // so we deduplicate each kind separately to avoid losing intersection
+        // conditions.
         let mut results_by_term = BTreeMap::new();
         for token_match in match_aggregator.into_results() {
-            let sort_key = (
-                token_match.distance,
-                token_match.prefix,
-                token_match.token_ord,
-            );
-            let existing_key = results_by_term.entry(token_match.term).or_insert(sort_key);
-
-            // NB: Since OR and AND queries are on different fields, we can assume their
-            // terms are disjoint. Assert this condition here since we're deduplicating
-            // terms and taking their minimum `token_ord`, which could potentially lose
-            // intersection conditions otherwise.
-            let (_, _, existing_token_ord) = *existing_key;
-            let existing_is_intersection = existing_token_ord >= num_text_query_terms;
-            let is_intersection = token_match.token_ord >= num_text_query_terms;
-            anyhow::ensure!(existing_is_intersection == is_intersection);
-
+            let kind = if token_match.token_ord < num_text_query_terms {
+                QueryTermKind::Text
+            } else if token_match.token_ord < num_text_query_terms + num_phrase_terms {
+                QueryTermKind::Phrase
+            } else {
+                QueryTermKind::Filter
+            };
+            let sort_key = (token_match.distance, token_match.prefix);
+            let existing_key = results_by_term
+                .entry((token_match.term, kind))
+                .or_insert(sort_key);
             *existing_key = cmp::min(*existing_key, sort_key);
         }
-        let terms = results_by_term.keys().cloned().collect_vec();
-        // If there are no matches, short-circuit and return an empty result.
-        let no_and_tokens_present = results_by_term
-            .iter()
-            .filter(|(_, (_, _, token_ord))| *token_ord >= num_text_query_terms)
-            .count()
-            == 0;
-        let no_filter_matches = exist_filter_conditions && no_and_tokens_present;
-        if terms.is_empty() || no_filter_matches {
-            return Ok(vec![]);
+        let terms = results_by_term
+            .keys()
+            .map(|(term, _)| term.clone())
+            .dedup()
+            .collect_vec();
+        // If there are no matches or one of the filter terms doesn't appear in
+        // the index, short-circuit and return an empty result.
+        let num_filter_terms_present = results_by_term
+            .keys()
+            .filter(|(_, kind)| *kind == QueryTermKind::Filter)
+            .count();
+        if terms.is_empty() || num_filter_terms_present < filter_terms.len() {
+            return Ok(None);
         }
 
         // Step 3: Given the terms we decided on, query BM25 statistics across all of
         // the indexes and merge their results.
         let mut bm25_futures = JoinSet::new();
-        for segment in &segments {
+        for segment in segments {
             let searcher = searcher.clone();
             let search_storage = search_storage.clone();
             let segment = segment.clone();
@@ -550,48 +684,87 @@ impl TantivySearchIndexSchema {
         let bm25_stats =
             block_in_place(|| memory_index.update_bm25_stats(disk_index_ts, &terms, bm25_stats))?;
 
-        // Step 4: Decide on our posting list queries given the previous results.
+        // Step 4: Decide on our posting list terms given the previous results.
         let mut or_terms = vec![];
         let mut and_terms = vec![];
-        for (term, (distance, prefix, token_ord)) in results_by_term {
-            if token_ord >= num_text_query_terms {
+        let mut phrase_or_terms = BTreeMap::new();
+        for ((term, kind), (distance, prefix)) in results_by_term {
+            if kind == QueryTermKind::Filter {
                 anyhow::ensure!(distance == 0 && !prefix);
                 and_terms.push(term);
-            } else {
-                let doc_frequency = *bm25_stats
-                    .doc_frequencies
-                    .get(&term)
-                    .context("Missing term frequency")?;
-                // TODO: Come up with a smarter way to boost scores based on edit distance.
-                // Eventually this will be in user space so developers can tweak
-                // it as they desire.
-                let mut boost = 1. / (1. + distance as f32);
-                if prefix {
-                    boost *= 0.5;
-                }
+                continue;
+            }
+            let doc_frequency = *bm25_stats
+                .doc_frequencies
+                .get(&term)
+                .context("Missing term frequency")?;
+            if kind == QueryTermKind::Phrase {
+                anyhow::ensure!(distance == 0 && !prefix);
                 let or_term = OrTerm {
-                    term,
+                    term: term.clone(),
                     doc_frequency,
-                    bm25_boost: boost,
+                    bm25_boost: 1.,
                 };
-                metrics::log_search_term_edit_distance(distance, prefix);
-                or_terms.push(or_term);
+                phrase_or_terms.insert(term, or_term);
+                continue;
+            }
+            // TODO: Come up with a smarter way to boost scores based on edit distance.
+            // Eventually this will be in user space so developers can tweak
+            // it as they desire.
+            let mut boost = 1. / (1. + distance as f32);
+            if prefix {
+                boost *= 0.5;
             }
+            let or_term = OrTerm {
+                term,
+                doc_frequency,
+                bm25_boost: boost,
+            };
+            metrics::log_search_term_edit_distance(distance, prefix);
+            or_terms.push(or_term);
         }
+        // A synonym phrase can only match if all of its words are in the index.
+        let phrases = synonym_phrases
+            .into_iter()
+            .filter_map(|phrase| {
+                phrase
+                    .iter()
+                    .map(|term| phrase_or_terms.get(term).cloned())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .collect_vec();
 
         // or_terms is the set of text tokens that matches our query. and_terms only
-        // filters these terms further. So if we have no or_terms, our result is
-        // empty regardless of any matching and_terms.
-        if or_terms.is_empty() {
-            return Ok(vec![]);
+        // filters these terms further. So if we have no or_terms or phrases, our
+        // result is empty regardless of any matching and_terms.
+        if or_terms.is_empty() && phrases.is_empty() {
+            return Ok(None);
         }
+        Ok(Some(ResolvedQueryTerms {
+            or_terms,
+            and_terms,
+            phrases,
+            bm25_stats,
+        }))
+    }
 
+    async fn query_posting_lists(
+        &self,
+        or_terms: Vec<OrTerm>,
+        and_terms: Vec<Term>,
+        bm25_stats: &Bm25Stats,
+        memory_index: &MemoryTextIndex,
+        search_storage: Arc<dyn Storage>,
+        segments: &[FragmentedTextStorageKeys],
+        disk_index_ts: Timestamp,
+        searcher: Arc<dyn Searcher>,
+    ) -> anyhow::Result<RevisionWithKeys> {
         // Step 5: Execute the posting list query against the memory index's tombstones
         // to know which `InternalId`s to exclude when querying the disk
         // indexes.
         let (prepared_memory_query, query) = block_in_place(|| {
             let prepared_memory_query =
-                memory_index.prepare_posting_list_query(&and_terms, &or_terms, &bm25_stats)?;
+                memory_index.prepare_posting_list_query(&and_terms, &or_terms, bm25_stats)?;
             let mut deleted_internal_ids = BTreeSet::new();
             if let Some(ref prepared_query) = prepared_memory_query {
                 deleted_internal_ids =
@@ -599,7 +772,7 @@ impl TantivySearchIndexSchema {
             }
             let query = PostingListQuery {
                 deleted_internal_ids,
-                num_terms_by_field: bm25_stats.num_terms_by_field,
+                num_terms_by_field: bm25_stats.num_terms_by_field.clone(),
                 num_documents: bm25_stats.num_documents,
                 or_terms,
                 and_terms,
@@ -611,7 +784,7 @@ impl TantivySearchIndexSchema {
         // Step 6: Query the posting lists across the indexes and take the best
         // results.
         let mut posting_list_futures = JoinSet::new();
-        for segment in &segments {
+        for segment in segments {
             let searcher = searcher.clone();
             let search_storage = search_storage.clone();
             let segment = segment.clone();
@@ -669,6 +842,197 @@ impl TantivySearchIndexSchema {
         Ok(result)
     }
 
+    #[fastrace::trace]
+    pub async fn suggest(
+        &self,
+        query: CompiledSuggestQuery,
+        memory_index: &MemoryTextIndex,
+        search_storage: Arc<dyn Storage>,
+        segments: Vec<FragmentedTextStorageKeys>,
+        disk_index_ts: Timestamp,
+        searcher: Arc<dyn Searcher>,
+    ) -> anyhow::Result<Vec<SearchSuggestion>> {
+        log_num_segments_searched_total(segments.len());
+
+        // Step 1: Find the terms matching the prefix in each segment and sum their
+        // document frequencies. Segments only count documents that haven't been
+        // deleted as of the disk snapshot.
+        let mut suggestion_futures = JoinSet::new();
+        for segment in &segments {
+            let searcher = searcher.clone();
+            let search_storage = search_storage.clone();
+            let segment = segment.clone();
+            let prefix = query.prefix.clone();
+            suggestion_futures.spawn("query_term_suggestions", async move {
+                let suggestions = searcher
+                    .query_term_suggestions(
+                        search_storage,
+                        segment.clone(),
+                        prefix,
+                        MAX_SUGGEST_SEGMENT_TERMS,
+                    )
+                    .await?;
+                anyhow::Ok((segment, suggestions))
+            });
+        }
+        let mut doc_frequencies = BTreeMap::new();
+        let mut truncated_segments = vec![];
+        while let Some(result) = suggestion_futures.join_next().await {
+            let (segment, suggestions) = result??;
+            let mut segment_terms = BTreeSet::new();
+            let truncated = suggestions.len() >= MAX_SUGGEST_SEGMENT_TERMS;
+            for suggestion in suggestions {
+                *doc_frequencies
+                    .entry(suggestion.term.clone())
+                    .or_insert(0i64) += i64::try_from(suggestion.doc_frequency)?;
+                segment_terms.insert(suggestion.term);
+            }
+            if truncated {
+                truncated_segments.push((segment, segment_terms));
+            }
+        }
+
+        // Step 2: Apply the writes in the memory index since the disk snapshot.
+        block_in_place(|| {
+            memory_index.update_term_suggestions(disk_index_ts, &query.prefix, &mut doc_frequencies)
+        })?;
+
+        // Step 3: Only now that every segment's terms have been merged, keep the most
+        // frequent ones as candidates. Terms whose documents were all deleted after
+        // the disk snapshot no longer have any matches.
+        let mut candidate_aggregator = TermSuggestionAggregator::new(MAX_SUGGEST_CANDIDATE_TERMS);
+        for (term, doc_frequency) in doc_frequencies {
+            if doc_frequency <= 0 {
+                continue;
+            }
+            candidate_aggregator.insert(TermSuggestion {
+                term,
+                doc_frequency: doc_frequency as u64,
+            });
+        }
+        let mut candidates: BTreeMap<Term, u64> = candidate_aggregator
+            .into_results()
+            .into_iter()
+            .map(|suggestion| (suggestion.term, suggestion.doc_frequency))
+            .collect();
+
+        // Step 4: A segment that hit its term limit may still contain candidates that
+        // didn't make its own most frequent terms. Look up their exact document
+        // frequencies so the ranking isn't skewed towards terms that happen to be
+        // concentrated in a few segments.
+        let mut stats_futures = JoinSet::new();
+        for (segment, segment_terms) in truncated_segments {
+            let missing_terms: Vec<_> = candidates
+                .keys()
+                .filter(|term| !segment_terms.contains(*term))
+                .cloned()
+                .collect();
+            if missing_terms.is_empty() {
+                continue;
+            }
+            let searcher = searcher.clone();
+            let search_storage = search_storage.clone();
+            stats_futures.spawn("query_bm25_stats", async move {
+                searcher
+                    .query_bm25_stats(search_storage, segment, missing_terms)
+                    .await
+            });
+        }
+        while let Some(result) = stats_futures.join_next().await {
+            for (term, doc_frequency) in result??.doc_frequencies {
+                if let Some(candidate_frequency) = candidates.get_mut(&term) {
+                    *candidate_frequency += doc_frequency;
+                }
+            }
+        }
+
+        // Step 5: Take the most frequent terms and complete the query text with them.
+        let mut aggregator = TermSuggestionAggregator::new(query.limit);
+        for (term, doc_frequency) in candidates {
+            aggregator.insert(TermSuggestion {
+                term,
+                doc_frequency,
+            });
+        }
+        aggregator
+            .into_results()
+            .into_iter()
+            .map(|suggestion| {
+                let completion = suggestion
+                    .term
+                    .as_str()
+                    .context("Suggested term was not valid UTF8")?;
+                let text = query
+                    .context_tokens
+                    .iter()
+                    .map(|token| token.as_str())
+                    .chain(std::iter::once(completion))
+                    .join(" ");
+                Ok(SearchSuggestion {
+                    text,
+                    doc_frequency: suggestion.doc_frequency,
+                })
+            })
+            .collect()
+    }
+
+    /// Find the words and phrases to add to a query for each synonym set with
+    /// an entry that appears in the query's tokens. Entries can be phrases, so
+    /// they're tokenized with the index's analyzer and matched as contiguous
+    /// runs of tokens.
+    fn expand_synonyms(
+        &self,
+        tokens: &[String],
+        synonyms: &[BTreeSet<String>],
+    ) -> SynonymExpansion {
+        let tokenize = |text: &str| {
+            let mut token_stream = self.analyzer.token_stream(text);
+            let mut tokens = vec![];
+            while let Some(token) = token_stream.next() {
+                tokens.push(token.text.clone());
+            }
+            tokens
+        };
+        let in_query = |entry: &[String]| tokens.windows(entry.len()).any(|window| window == entry);
+        let mut seen_tokens: BTreeSet<String> = tokens.iter().cloned().collect();
+        let mut seen_phrases = BTreeSet::new();
+        let mut num_terms = 0;
+        let mut expansion = SynonymExpansion::default();
+        for synonym_set in synonyms {
+            let entries = synonym_set
+                .iter()
+                .map(|entry| tokenize(entry))
+                .filter(|entry| !entry.is_empty())
+                .collect_vec();
+            if !entries.iter().any(|entry| in_query(entry)) {
+                continue;
+            }
+            for entry in entries {
+                let is_new = if entry.len() == 1 {
+                    seen_tokens.insert(entry[0].clone())
+                } else {
+                    !in_query(&entry) && seen_phrases.insert(entry.clone())
+                };
+                if !is_new {
+                    continue;
+                }
+                if num_terms + entry.len() > MAX_SYNONYM_QUERY_TERMS
+                    || (entry.len() > 1 && expansion.phrases.len() >= MAX_SYNONYM_PHRASES)
+                {
+                    metrics::log_search_synonym_limit_exceeded();
+                    return expansion;
+                }
+                num_terms += entry.len();
+                if entry.len() == 1 {
+                    expansion.tokens.extend(entry);
+                } else {
+                    expansion.phrases.push(entry);
+                }
+            }
+        }
+        expansion
+    }
+
     fn compile_tokens_with_typo_tolerance(
         search_field: Field,
         tokens: &Vec<String>,
@@ -766,7 +1130,12 @@ impl TantivySearchIndexSchema {
             log_search_token_limit_exceeded();
         }
 
-        let text_query = match version {
+        let SynonymExpansion {
+            tokens: synonym_tokens,
+            phrases: synonym_phrases,
+        } = self.expand_synonyms(&tokens, &self.synonyms);
+
+        let mut text_query = match version {
             SearchVersion::V1 => tokens
                 .iter()
                 .map(|text| {
@@ -780,10 +1149,36 @@ impl TantivySearchIndexSchema {
                 Self::compile_tokens_with_typo_tolerance(self.search_field, &tokens)?
             },
         };
+        // Synonyms are matched exactly, even if the word they're expanding was
+        // matched as a prefix or with typos.
+        for text in synonym_tokens {
+            let term = Term::from_field_text(self.search_field, &text);
+            anyhow::ensure!(term.as_str().is_some(), "Term was not valid UTF8");
+            text_query.push(QueryTerm::new(term, false));
+        }
+        let synonym_phrases = synonym_phrases
+            .into_iter()
+            .map(|phrase| {
+                phrase
+                    .iter()
+                    .map(|text| {
+                        let term = Term::from_field_text(self.search_field, text);
+                        anyhow::ensure!(term.as_str().is_some(), "Term was not valid UTF8");
+                        Ok(term)
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
         let text_reads = text_query
-            .clone()
-            .into_iter()
+            .iter()
+            .cloned()
+            .chain(
+                synonym_phrases
+                    .iter()
+                    .flatten()
+                    .map(|term| QueryTerm::new(term.clone(), false)),
+            )
             .map(|t| {
                 anyhow::Ok(TextQueryTermRead::new(
                     self.search_field_path.clone(),
@@ -806,6 +1201,7 @@ impl TantivySearchIndexSchema {
         let query = CompiledQuery {
             text_query,
             filter_conditions,
+            synonym_phrases,
         };
         let reads = QueryReads::new(text_reads, filter_reads.into());
         metrics::log_compiled_query(&query);
@@ -813,6 +1209,87 @@ impl TantivySearchIndexSchema {
         timer.finish();
         Ok((query, reads))
     }
+
+    pub fn compile_suggest(
+        &self,
+        query: &InternalSearchSuggest,
+    ) -> anyhow::Result<(Option<CompiledSuggestQuery>, QueryReads)> {
+        if query.limit == 0 || query.limit > MAX_SUGGESTIONS {
+            anyhow::bail!(ErrorMetadata::bad_request(
+                "InvalidSuggestLimitError",
+                format!(
+                    "Suggest query against {} must request between 1 and {} suggestions. Actual: \
+                     {}",
+                    query.printable_index_name()?,
+                    MAX_SUGGESTIONS,
+                    query.limit,
+                )
+            ))
+        }
+        let mut token_stream = self.analyzer.token_stream(&query.prefix);
+        let mut tokens = vec![];
+        while tokens.len() < MAX_QUERY_TERMS
+            && let Some(token) = token_stream.next()
+        {
+            tokens.push(token.text.clone());
+        }
+        if tokens.len() == MAX_QUERY_TERMS && token_stream.next().is_some() {
+            log_search_token_limit_exceeded();
+        }
+        let Some(last_token) = tokens.pop() else {
+            return Ok((None, QueryReads::empty()));
+        };
+        let prefix = Term::from_field_text(self.search_field, &last_token);
+        anyhow::ensure!(prefix.as_str().is_some(), "Term was not valid UTF8");
+
+        // Any write that adds or removes a term starting with the prefix can change
+        // the suggestions, which is exactly the set of writes that overlap a prefix
+        // search read.
+        let text_read = TextQueryTermRead::new(
+            self.search_field_path.clone(),
+            TextQueryTerm::Fuzzy {
+                token: last_token,
+                max_distance: FuzzyDistance::Zero,
+                prefix: true,
+            },
+        );
+        let reads = QueryReads::new(vec![text_read].into(), Default::default());
+        let compiled = CompiledSuggestQuery {
+            context_tokens: tokens,
+            prefix,
+            limit: query.limit,
+        };
+        Ok((Some(compiled), reads))
+    }
+}
+
+/// The terms a compiled text query resolved to in the index, along with the
+/// BM25 statistics used to score all of them.
+struct ResolvedQueryTerms {
+    /// The query's own terms, any of which can match a document.
+    or_terms: Vec<OrTerm>,
+    /// Filter terms that every matching document must contain.
+    and_terms: Vec<Term>,
+    /// The words of each multi-word synonym whose words are all in the index.
+    phrases: Vec<Vec<OrTerm>>,
+    bm25_stats: Bm25Stats,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum QueryTermKind {
+    Text,
+    Phrase,
+    Filter,
+}
+
+/// The words and phrases a text query's synonyms expand to.
+#[derive(Debug, Default, PartialEq)]
+struct SynonymExpansion {
+    /// Single-word synonyms, which are OR'd into the query like its own words.
+    tokens: Vec<String>,
+    /// Multi-word synonyms, each of which only matches documents containing
+    /// all of its words.
+    phrases: Vec<Vec<String>>,
 }
 
 pub struct DocumentLengths {
@@ -826,6 +1303,7 @@ pub enum SearchFileType {
     FragmentedVectorSegment,
     VectorDeletedBitset,
     VectorIdTracker,
+    SparseVectorSegment,
     Text,
     TextIdTracker,
     TextAliveBitset,
@@ -834,11 +1312,45 @@ pub enum SearchFileType {
 
 #[cfg(test)]
 mod test {
-    use std::collections::BTreeSet;
+    use std::{
+        collections::{
+            BTreeMap,
+            BTreeSet,
+        },
+        sync::Arc,
+    };
 
-    use common::bootstrap_model::index::text_index::DeveloperTextIndexConfig;
+    use common::{
+        bootstrap_model::index::text_index::DeveloperTextIndexConfig,
+        document::{
+            CreationTime,
+            ResolvedDocument,
+        },
+        query::{
+            InternalSearch,
+            InternalSearchFilterExpression,
+            SearchVersion,
+        },
+        testing::TestIdGenerator,
+        types::{
+            IndexDescriptor,
+            TabletIndexName,
+            Timestamp,
+            WriteTimestamp,
+        },
+    };
+    use runtime::testing::TestRuntime;
+    use storage::LocalDirStorage;
+    use value::{
+        assert_obj,
+        DeveloperDocumentId,
+        ResolvedDocumentId,
+    };
 
     use crate::{
+        searcher::SearcherStub,
+        MemoryTextIndex,
+        SynonymExpansion,
         TantivySearchIndexSchema,
         SEARCH_FIELD_ID,
     };
@@ -851,6 +1363,7 @@ mod test {
         let schema = TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
             search_field: "mySearchField".parse()?,
             filter_fields: BTreeSet::new(),
+            synonyms: vec![],
         });
         assert_eq!(schema.internal_id_field.field_id(), 0);
         assert_eq!(schema.ts_field.field_id(), 1);
@@ -858,4 +1371,104 @@ mod test {
         assert_eq!(schema.search_field.field_id(), SEARCH_FIELD_ID);
         Ok(())
     }
+    #[test]
+    fn test_synonym_tokens() -> anyhow::Result<()> {
+        let schema = TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
+            search_field: "mySearchField".parse()?,
+            filter_fields: BTreeSet::new(),
+            synonyms: vec![],
+        });
+        let synonyms = vec![
+            BTreeSet::from(["tv".to_string(), "Flat Screen".to_string()]),
+            BTreeSet::from(["television".to_string(), "tv".to_string()]),
+            BTreeSet::from(["radio".to_string(), "wireless".to_string()]),
+        ];
+        let tokens = |words: &[&str]| words.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+
+        // Multi-word synonyms are kept together as phrases.
+        assert_eq!(
+            schema.expand_synonyms(&tokens(&["cheap", "tv"]), &synonyms),
+            SynonymExpansion {
+                tokens: tokens(&["television"]),
+                phrases: vec![tokens(&["flat", "screen"])],
+            },
+        );
+        // Phrases only match as contiguous tokens.
+        assert_eq!(
+            schema.expand_synonyms(&tokens(&["flat", "screen"]), &synonyms),
+            SynonymExpansion {
+                tokens: tokens(&["tv"]),
+                phrases: vec![],
+            },
+        );
+        assert_eq!(
+            schema.expand_synonyms(&tokens(&["screen", "flat"]), &synonyms),
+            SynonymExpansion::default(),
+        );
+        Ok(())
+    }
+
+    #[convex_macro::test_runtime]
+    async fn test_multi_word_synonyms_require_every_word(rt: TestRuntime) -> anyhow::Result<()> {
+        let schema = TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
+            search_field: "mySearchField".parse()?,
+            filter_fields: BTreeSet::new(),
+            synonyms: vec![BTreeSet::from([
+                "tv".to_string(),
+                "flat screen".to_string(),
+            ])],
+        });
+        let mut id_generator = TestIdGenerator::new();
+        let table = id_generator.user_table_id(&"test".parse()?);
+        let mut memory_index = MemoryTextIndex::new(WriteTimestamp::Committed(Timestamp::MIN));
+        let mut texts_by_id = BTreeMap::new();
+        for text in [
+            "cheap tv",
+            "cheap flat screen",
+            "flat pack furniture",
+            "screen door",
+        ] {
+            let id = ResolvedDocumentId::new(
+                table.tablet_id,
+                DeveloperDocumentId::new(table.table_number, id_generator.generate_internal()),
+            );
+            let creation_time = CreationTime::try_from(10.)?;
+            let document =
+                ResolvedDocument::new(id, creation_time, assert_obj!("mySearchField" => text))?;
+            memory_index.update(
+                id.internal_id(),
+                WriteTimestamp::Pending,
+                None,
+                Some((schema.index_into_terms(&document)?, creation_time)),
+            )?;
+            texts_by_id.insert(id.internal_id(), text);
+        }
+
+        let search = InternalSearch {
+            index_name: TabletIndexName::new(table.tablet_id, IndexDescriptor::new("by_text")?)?,
+            table_name: "test".parse()?,
+            filters: vec![InternalSearchFilterExpression::Search(
+                "mySearchField".parse()?,
+                "tv".to_string(),
+            )],
+        };
+        let (compiled_query, _) = schema.compile(&search, SearchVersion::V1)?;
+        let results = schema
+            .search(
+                compiled_query,
+                &memory_index,
+                Arc::new(LocalDirStorage::new(rt)?),
+                vec![],
+                Timestamp::MIN,
+                Arc::new(SearcherStub),
+            )
+            .await?;
+        let texts: BTreeSet<_> = results
+            .iter()
+            .map(|(candidate, _)| texts_by_id[&candidate.id])
+            .collect();
+        // Documents with only one of "flat" or "screen" don't match.
+        assert_eq!(texts, BTreeSet::from(["cheap tv", "cheap flat screen"]));
+        Ok(())
+    }
 }
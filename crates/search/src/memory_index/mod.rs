@@ -499,6 +499,47 @@ impl MemoryTextIndex {
         Ok(stats)
     }
 
+    /// Adjust the document frequencies of terms starting with `prefix` for
+    /// writes after `snapshot_ts`. Terms that only exist in the memory index
+    /// are added with just the memory index's contribution.
+    #[fastrace::trace]
+    pub fn update_term_suggestions(
+        &self,
+        snapshot_ts: Timestamp,
+        prefix: &Term,
+        doc_frequencies: &mut BTreeMap<Term, i64>,
+    ) -> anyhow::Result<()> {
+        let _timer = metrics::index_update_term_suggestions_timer();
+        anyhow::ensure!(
+            self.min_ts <= WriteTimestamp::Committed(snapshot_ts.succ()?),
+            "Timestamps are out of order!  min ts:{:?} snapshot_ts:{snapshot_ts}",
+            self.min_ts,
+        );
+        let matching_terms: BTreeMap<TermId, Term> = self
+            .term_table
+            .get_fuzzy(prefix, 0, true)
+            .filter(|(_, _, term)| term.field() == prefix.field())
+            .map(|(term_id, _, term)| (term_id, term))
+            .collect();
+        let mut increments = BTreeMap::new();
+        let commit_iter = self.statistics.range((
+            Bound::Excluded(WriteTimestamp::Committed(snapshot_ts)),
+            Bound::Unbounded,
+        ));
+        for (_, commit_stats) in commit_iter {
+            for (term_id, &increment) in &commit_stats.term_freq_diffs {
+                if matching_terms.contains_key(term_id) {
+                    *increments.entry(*term_id).or_insert(0i64) += increment as i64;
+                }
+            }
+        }
+        for (term_id, increment) in increments {
+            let term = matching_terms[&term_id].clone();
+            *doc_frequencies.entry(term).or_insert(0) += increment;
+        }
+        Ok(())
+    }
+
     #[fastrace::trace]
     pub fn prepare_posting_list_query(
         &self,
@@ -955,6 +996,8 @@ impl PreparedMemoryPostingListQuery {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use common::{
         document::CreationTime,
         types::Timestamp,
@@ -1020,4 +1063,57 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_update_term_suggestions() -> anyhow::Result<()> {
+        let ts0 = Timestamp::MIN;
+        let mut index = MemoryTextIndex::new(WriteTimestamp::Committed(ts0));
+        let field = Field::from_field_id(0);
+        let search_terms = |words: &[&str]| {
+            words
+                .iter()
+                .map(|word| DocumentTerm::Search {
+                    term: Term::from_field_text(field, word),
+                    pos: FieldPosition::default(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let ts1 = ts0.succ()?;
+        index.update(
+            InternalId::MIN,
+            WriteTimestamp::Committed(ts1),
+            None,
+            Some((search_terms(&["apple", "apricot"]), CreationTime::ONE)),
+        )?;
+        let ts2 = ts1.succ()?;
+        index.update(
+            InternalId::MAX,
+            WriteTimestamp::Committed(ts2),
+            None,
+            Some((search_terms(&["apple", "banana"]), CreationTime::ONE)),
+        )?;
+
+        let prefix = Term::from_field_text(field, "ap");
+        let mut doc_frequencies = BTreeMap::new();
+        index.update_term_suggestions(ts0, &prefix, &mut doc_frequencies)?;
+        assert_eq!(
+            doc_frequencies,
+            BTreeMap::from([
+                (Term::from_field_text(field, "apple"), 2),
+                (Term::from_field_text(field, "apricot"), 1),
+            ])
+        );
+
+        // Only changes after the snapshot timestamp are applied on top of the
+        // existing frequencies.
+        let mut doc_frequencies = BTreeMap::from([(Term::from_field_text(field, "apple"), 1)]);
+        index.update_term_suggestions(ts1, &prefix, &mut doc_frequencies)?;
+        assert_eq!(
+            doc_frequencies,
+            BTreeMap::from([(Term::from_field_text(field, "apple"), 2)])
+        );
+
+        Ok(())
+    }
 }
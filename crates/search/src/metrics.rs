@@ -21,6 +21,7 @@ use crate::{
     query::{
         CompiledQuery,
         RevisionWithKeys,
+        SearchSuggestion,
     },
     scoring::Bm25StatisticsDiff,
     tantivy_query::SearchQueryResult,
@@ -71,6 +72,14 @@ pub fn index_update_bm25_stats_timer() -> Timer<VMHistogram> {
     Timer::new(&SEARCH_INDEX_UPDATE_BM25_STATS_SECONDS)
 }
 
+register_convex_histogram!(
+    SEARCH_INDEX_UPDATE_TERM_SUGGESTIONS_SECONDS,
+    "Duration of updating term suggestions in memory index",
+);
+pub fn index_update_term_suggestions_timer() -> Timer<VMHistogram> {
+    Timer::new(&SEARCH_INDEX_UPDATE_TERM_SUGGESTIONS_SECONDS)
+}
+
 register_convex_histogram!(
     SEARCH_INDEX_PREPARE_POSTING_LIST_QUERY_SECONDS,
     "Duration of preparing posting list query in memory index",
@@ -168,6 +177,29 @@ pub fn finish_search(timer: StatusTimer, revisions_with_keys: &RevisionWithKeys)
     timer.finish();
 }
 
+register_convex_histogram!(
+    SEARCH_INDEX_MANAGER_SUGGEST_SECONDS,
+    "Total suggest duration",
+    &[STATUS_LABEL[0], CLUSTER_LABEL],
+);
+pub fn suggest_timer(cluster: &'static str) -> StatusTimer {
+    let mut timer = StatusTimer::new(&SEARCH_INDEX_MANAGER_SUGGEST_SECONDS);
+    timer.add_label(cluster_label(cluster));
+    timer
+}
+
+register_convex_histogram!(
+    SEARCH_INDEX_MANAGER_SUGGESTIONS_TOTAL,
+    "Number of suggestions from the search index manager"
+);
+pub fn finish_suggest(timer: StatusTimer, suggestions: &[SearchSuggestion]) {
+    log_distribution(
+        &SEARCH_INDEX_MANAGER_SUGGESTIONS_TOTAL,
+        suggestions.len() as f64,
+    );
+    timer.finish();
+}
+
 register_convex_histogram!(
     SEARCH_SCHEMA_COMPILE_SECONDS,
     "Time to compile a search schema",
@@ -204,6 +236,14 @@ pub fn log_search_token_limit_exceeded() {
     log_counter(&SEARCH_EXCEEDED_TOKEN_LIMIT_TOTAL, 1)
 }
 
+register_convex_counter!(
+    SEARCH_EXCEEDED_SYNONYM_LIMIT_TOTAL,
+    "The number of times synonym expansion added more terms to a search query than our limit"
+);
+pub fn log_search_synonym_limit_exceeded() {
+    log_counter(&SEARCH_EXCEEDED_SYNONYM_LIMIT_TOTAL, 1)
+}
+
 register_convex_histogram!(
     SEARCH_BM25_STATISTICS_DIFF_SECONDS,
     "Time to compute a BM25 diff",
@@ -500,6 +540,7 @@ impl SearchFileType {
             SearchFileType::VectorSegment => "vector_segment",
             SearchFileType::VectorDeletedBitset => "vector_deleted_bitset",
             SearchFileType::VectorIdTracker => "vector_id_tracker",
+            SearchFileType::SparseVectorSegment => "sparse_vector_segment",
             SearchFileType::Text => "text",
             SearchFileType::TextIdTracker => "text_id_tracker",
             SearchFileType::TextAliveBitset => "text_alive_bitset",
@@ -546,6 +587,7 @@ pub fn log_text_document_indexed(schema: &TantivySearchIndexSchema, document: &T
 #[derive(Clone, Copy, Debug, strum::AsRefStr)]
 pub enum SearchType {
     Vector,
+    SparseVector,
     Text,
 }
 
@@ -559,6 +601,7 @@ pub const SEARCH_TYPE_LABEL: &str = "search_type";
 pub fn search_type_label(search_type: SearchType) -> StaticMetricLabel {
     let type_str = match search_type {
         SearchType::Vector => "vector",
+        SearchType::SparseVector => "sparse_vector",
         SearchType::Text => "text",
     };
     StaticMetricLabel::new("search_type", type_str)
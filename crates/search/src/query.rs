@@ -74,6 +74,9 @@ use crate::{
 pub struct CompiledQuery {
     pub text_query: Vec<QueryTerm>,
     pub filter_conditions: Vec<CompiledFilterCondition>,
+    /// Multi-word synonyms of the query's words. A document containing every
+    /// term of one of these phrases matches the query too.
+    pub synonym_phrases: Vec<Vec<Term>>,
 }
 
 impl CompiledQuery {
@@ -102,6 +105,11 @@ impl CompiledQuery {
                 // TODO(CX-5481): get rid of this `Term::wrap` call. Need to propagate the Field for these.
                 .map(|bytes| CompiledFilterCondition::Must(Term::wrap(bytes)))
                 .collect_vec(),
+            synonym_phrases: value
+                .synonym_phrases
+                .into_iter()
+                .map(|phrase| phrase.terms.into_iter().map(Term::wrap).collect_vec())
+                .collect_vec(),
         })
     }
 }
@@ -119,10 +127,31 @@ impl From<CompiledQuery> for pb::searchlight::TextQuery {
                 .into_iter()
                 .map(|CompiledFilterCondition::Must(term)| term.as_slice().to_vec())
                 .collect_vec(),
+            synonym_phrases: value
+                .synonym_phrases
+                .into_iter()
+                .map(|phrase| pb::searchlight::SynonymPhrase {
+                    terms: phrase
+                        .into_iter()
+                        .map(|term| term.as_slice().to_vec())
+                        .collect_vec(),
+                })
+                .collect_vec(),
         }
     }
 }
 
+/// A suggest query compiled against a particular `SearchIndexSchema`.
+#[derive(Debug, Clone)]
+pub struct CompiledSuggestQuery {
+    /// Tokens preceding the partial token, which are prepended to each
+    /// completion to form a phrase.
+    pub context_tokens: Vec<String>,
+    /// The partial token being completed.
+    pub prefix: Term,
+    pub limit: usize,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct QueryTerm {
     term: Term,
@@ -598,6 +627,47 @@ impl QueryResults {
     }
 }
 
+/// A completion for the last token of a suggest query, along with the number of
+/// documents containing the completed term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchSuggestion {
+    pub text: String,
+    pub doc_frequency: u64,
+}
+
+pub struct SuggestResults {
+    pub suggestions: Vec<SearchSuggestion>,
+    pub reads: QueryReads,
+}
+
+impl SuggestResults {
+    pub fn empty() -> Self {
+        Self {
+            suggestions: vec![],
+            reads: QueryReads::empty(),
+        }
+    }
+}
+
+/// The number of documents matching a search query that have a particular
+/// value for a filter field. A `value` of `None` counts documents where the
+/// field is missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetCount {
+    pub value: Option<ConvexValue>,
+    pub count: u64,
+}
+
+/// The most common values of a filter field across a search query's matches,
+/// sorted by count in descending order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetCounts {
+    pub field_path: FieldPath,
+    pub counts: Vec<FacetCount>,
+    /// The number of distinct values that didn't fit within the facet limit.
+    pub num_omitted_values: usize,
+}
+
 /// A read based on a single token extracted from a text query search.
 ///
 /// A single text query will be split into many parts (tokenized), each part
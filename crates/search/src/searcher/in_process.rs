@@ -22,8 +22,10 @@ use tantivy::{
 };
 use tempfile::TempDir;
 use vector::{
+    CompiledSparseVectorSearch,
     CompiledVectorSearch,
     QdrantSchema,
+    SparseVectorSchema,
     VectorSearchQueryResult,
     VectorSearcher,
 };
@@ -33,6 +35,7 @@ use super::{
         Bm25Stats,
         PostingListMatch,
         PostingListQuery,
+        TermSuggestion,
         TokenMatch,
         TokenQuery,
     },
@@ -60,6 +63,16 @@ impl Searcher for SearcherStub {
         Ok(vec![])
     }
 
+    async fn query_term_suggestions(
+        &self,
+        _search_storage: Arc<dyn Storage>,
+        _storage_keys: FragmentedTextStorageKeys,
+        _prefix: Term,
+        _max_results: usize,
+    ) -> anyhow::Result<Vec<TermSuggestion>> {
+        Ok(vec![])
+    }
+
     async fn query_bm25_stats(
         &self,
         _search_storage: Arc<dyn Storage>,
@@ -108,6 +121,25 @@ impl VectorSearcher for SearcherStub {
     ) -> anyhow::Result<FragmentedVectorSegment> {
         anyhow::bail!("Not implemented!");
     }
+
+    async fn execute_multi_segment_sparse_vector_query(
+        &self,
+        _search_storage: Arc<dyn Storage>,
+        _segments: Vec<FragmentedVectorSegmentPaths>,
+        _schema: SparseVectorSchema,
+        _search: CompiledSparseVectorSearch,
+        _overfetch_delta: u32,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        Ok(vec![])
+    }
+
+    async fn execute_sparse_vector_compaction(
+        &self,
+        _search_storage: Arc<dyn Storage>,
+        _segments: Vec<FragmentedVectorSegmentPaths>,
+    ) -> anyhow::Result<FragmentedVectorSegment> {
+        anyhow::bail!("Not implemented!");
+    }
 }
 
 #[async_trait]
@@ -169,6 +201,18 @@ impl<RT: Runtime> Searcher for InProcessSearcher<RT> {
             .await
     }
 
+    async fn query_term_suggestions(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        storage_keys: FragmentedTextStorageKeys,
+        prefix: Term,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<TermSuggestion>> {
+        self.searcher
+            .query_term_suggestions(search_storage, storage_keys, prefix, max_results)
+            .await
+    }
+
     async fn query_bm25_stats(
         &self,
         search_storage: Arc<dyn Storage>,
@@ -233,4 +277,33 @@ impl<RT: Runtime> VectorSearcher for InProcessSearcher<RT> {
             .execute_vector_compaction(search_storage, segments, dimension)
             .await
     }
+
+    async fn execute_multi_segment_sparse_vector_query(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        segments: Vec<FragmentedVectorSegmentPaths>,
+        schema: SparseVectorSchema,
+        search: CompiledSparseVectorSearch,
+        overfetch_delta: u32,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        self.searcher
+            .execute_multi_segment_sparse_vector_query(
+                search_storage,
+                segments,
+                schema,
+                search,
+                overfetch_delta,
+            )
+            .await
+    }
+
+    async fn execute_sparse_vector_compaction(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        segments: Vec<FragmentedVectorSegmentPaths>,
+    ) -> anyhow::Result<FragmentedVectorSegment> {
+        self.searcher
+            .execute_sparse_vector_compaction(search_storage, segments)
+            .await
+    }
 }
@@ -19,6 +19,15 @@ pub(crate) fn text_query_tokens_searcher_latency_seconds() -> StatusTimer {
     StatusTimer::new(&TEXT_QUERY_TOKENS_SEARCHER_LATENCY_SECONDS)
 }
 
+register_convex_histogram!(
+    TEXT_QUERY_TERM_SUGGESTIONS_SEARCHER_LATENCY_SECONDS,
+    "The amount of time it took to query for term suggestions on searchlight (in Searcher)",
+    &STATUS_LABEL,
+);
+pub(crate) fn text_query_term_suggestions_searcher_latency_seconds() -> StatusTimer {
+    StatusTimer::new(&TEXT_QUERY_TERM_SUGGESTIONS_SEARCHER_LATENCY_SECONDS)
+}
+
 register_convex_histogram!(
     TEXT_QUERY_BM25_SEARCHER_LATENCY_SECONDS,
     "The amount of time it took to query for bm25 stats from searcher (searchlight)",
@@ -20,6 +20,7 @@ pub use searcher::{
     SegmentTermMetadataFetcher,
     Term,
     TermDeletionsByField,
+    TermSuggestion,
     TermValue,
     TokenMatch,
     TokenQuery,
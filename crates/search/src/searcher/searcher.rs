@@ -24,6 +24,7 @@ use common::{
     bootstrap_model::index::text_index::FragmentedTextSegment,
     bounded_thread_pool::BoundedThreadPool,
     document::CreationTime,
+    knobs::VECTOR_QUANTIZATION_RECALL_SAMPLE_RATE,
     runtime::Runtime,
     types::{
         ObjectKey,
@@ -46,6 +47,7 @@ use pb::searchlight::{
     QueryBm25StatsResponse,
     StorageKey,
 };
+use rand::Rng;
 use storage::Storage;
 pub use tantivy::Term;
 use tantivy::{
@@ -67,8 +69,10 @@ use text_search::tracker::StaticDeletionTracker;
 use value::InternalId;
 use vector::{
     qdrant_segments::UntarredVectorDiskSegmentPaths,
+    CompiledSparseVectorSearch,
     CompiledVectorSearch,
     QdrantSchema,
+    SparseVectorSchema,
     VectorIndexType,
     VectorSearchQueryResult,
     VectorSearcher,
@@ -88,10 +92,14 @@ use super::{
     },
 };
 use crate::{
-    aggregation::TokenMatchAggregator,
+    aggregation::{
+        TermSuggestionAggregator,
+        TokenMatchAggregator,
+    },
     archive::cache::ArchiveCacheManager,
     constants::{
         MAX_EDIT_DISTANCE,
+        MAX_SUGGEST_SEGMENT_TERMS,
         MAX_UNIQUE_QUERY_TERMS,
     },
     convex_query::{
@@ -117,6 +125,7 @@ use crate::{
             text_query_bm25_searcher_latency_seconds,
             text_query_posting_lists_searcher_latency_seconds,
             text_query_term_ordinals_searcher_timer,
+            text_query_term_suggestions_searcher_latency_seconds,
             text_query_tokens_searcher_latency_seconds,
         },
         searchlight_knobs::{
@@ -146,6 +155,14 @@ pub trait Searcher: VectorSearcher + Send + Sync + 'static {
         max_results: usize,
     ) -> anyhow::Result<Vec<TokenMatch>>;
 
+    async fn query_term_suggestions(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        storage_keys: FragmentedTextStorageKeys,
+        prefix: Term,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<TermSuggestion>>;
+
     async fn query_bm25_stats(
         &self,
         search_storage: Arc<dyn Storage>,
@@ -364,6 +381,7 @@ impl<RT: Runtime> SearcherImpl<RT> {
     ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
         let slow_query_threshold = self.slow_vector_query_threshold_millis;
         let require_exact = self.require_exact_vector_search;
+        let sample_recall = self.rt.rng().random::<f64>() < *VECTOR_QUANTIZATION_RECALL_SAMPLE_RATE;
         let search = move || {
             let timer = metrics::vector_schema_query_timer();
             let start = Instant::now();
@@ -373,6 +391,7 @@ impl<RT: Runtime> SearcherImpl<RT> {
                 overfetch_delta,
                 slow_query_threshold,
                 require_exact,
+                sample_recall,
             );
             let query_duration = Instant::now().duration_since(start);
             if query_duration > Duration::from_millis(slow_query_threshold) {
@@ -457,6 +476,22 @@ impl<RT: Runtime> Searcher for SearcherImpl<RT> {
         Ok(resp)
     }
 
+    #[fastrace::trace]
+    async fn query_term_suggestions(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        storage_keys: FragmentedTextStorageKeys,
+        prefix: Term,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<TermSuggestion>> {
+        let timer = text_query_term_suggestions_searcher_latency_seconds();
+        let text_segment = self.load_text_segment(search_storage, storage_keys).await?;
+        let query = move || Self::query_term_suggestions_impl(text_segment, prefix, max_results);
+        let resp = self.text_search_pool.execute(query).await??;
+        timer.finish();
+        Ok(resp)
+    }
+
     #[fastrace::trace]
     async fn query_bm25_stats(
         &self,
@@ -633,6 +668,47 @@ impl<RT: Runtime> VectorSearcher for SearcherImpl<RT> {
             .await?;
         Ok(segment)
     }
+
+    async fn execute_multi_segment_sparse_vector_query(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        fragments: Vec<FragmentedVectorSegmentPaths>,
+        _schema: SparseVectorSchema,
+        query: CompiledSparseVectorSearch,
+        overfetch_delta: u32,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        let timer = metrics::vector_query_timer(VectorIndexType::MultiSegment);
+        let results: anyhow::Result<Vec<VectorSearchQueryResult>> = try {
+            let segments = self
+                .fragmented_segment_fetcher
+                .load_sparse_segments(search_storage, fragments, &self.vector_search_pool)
+                .await?;
+            let query_capacity = (query.limit + overfetch_delta) as usize;
+            self.vector_search_pool
+                .execute(move || {
+                    let mut results = vec![];
+                    for segment in &segments {
+                        results.extend(segment.search(&query, overfetch_delta)?);
+                    }
+                    results.sort_by(|a, b| a.cmp(b).reverse());
+                    results.truncate(query_capacity);
+                    anyhow::Ok(results)
+                })
+                .await??
+        };
+        timer.finish(results.is_ok());
+        results
+    }
+
+    async fn execute_sparse_vector_compaction(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        segments: Vec<FragmentedVectorSegmentPaths>,
+    ) -> anyhow::Result<common::bootstrap_model::index::vector_index::FragmentedVectorSegment> {
+        self.fragmented_segment_compactor
+            .compact_sparse(segments, search_storage)
+            .await
+    }
 }
 
 impl<RT: Runtime> SearcherImpl<RT> {
@@ -802,6 +878,53 @@ impl<RT: Runtime> SearcherImpl<RT> {
         Ok(())
     }
 
+    #[fastrace::trace]
+    fn query_term_suggestions_impl(
+        text_segment: Arc<TextSegment>,
+        prefix: Term,
+        max_results: usize,
+    ) -> anyhow::Result<Vec<TermSuggestion>> {
+        match text_segment.as_ref() {
+            TextSegment::Empty => Ok(vec![]),
+            TextSegment::Segment {
+                searcher,
+                deletion_tracker,
+                id_tracker: _,
+                segment_ord,
+            } => {
+                anyhow::ensure!(max_results <= MAX_SUGGEST_SEGMENT_TERMS);
+                let segment = searcher.segment_reader(*segment_ord);
+                let field = prefix.field();
+                let inverted_index = segment.inverted_index(field)?;
+                let term_dict = inverted_index.terms();
+
+                // Unlike `visit_top_terms_for_query`, we can't stop early here since the
+                // term dictionary is in lexicographic order rather than frequency order.
+                let prefix_str = prefix
+                    .as_str()
+                    .context("Suggest prefix for non-string field")?;
+                let dfa = build_fuzzy_dfa(prefix_str, 0, true);
+                let dfa_compat = LevenshteinDfaWrapper(&dfa);
+                let mut term_stream = term_dict.search(dfa_compat).into_stream()?;
+                let mut aggregator = TermSuggestionAggregator::new(max_results);
+                while term_stream.advance() {
+                    let term_ord = term_stream.term_ord();
+                    let doc_frequency =
+                        deletion_tracker.doc_frequency(field, term_dict, term_ord)?;
+                    if doc_frequency == 0 {
+                        continue;
+                    }
+                    let match_str = std::str::from_utf8(term_stream.key())?;
+                    aggregator.insert(TermSuggestion {
+                        term: Term::from_field_text(field, match_str),
+                        doc_frequency,
+                    });
+                }
+                Ok(aggregator.into_results())
+            },
+        }
+    }
+
     #[fastrace::trace]
     fn query_bm25_stats_impl(
         text_segment: Arc<TextSegment>,
@@ -1049,6 +1172,32 @@ impl TryFrom<TokenMatch> for pb::searchlight::TokenMatch {
     }
 }
 
+/// A term from a segment's dictionary that starts with a suggest query's
+/// prefix, along with the number of live documents in the segment that contain
+/// it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TermSuggestion {
+    pub term: Term,
+    pub doc_frequency: u64,
+}
+
+// Suggestions are sorted by document frequency in descending order, breaking
+// ties by the term itself, so the best suggestion compares as the smallest.
+impl Ord for TermSuggestion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .doc_frequency
+            .cmp(&self.doc_frequency)
+            .then_with(|| self.term.cmp(&other.term))
+    }
+}
+
+impl PartialOrd for TermSuggestion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FragmentedTextStorageKeys {
     pub segment: ObjectKey,
@@ -1527,6 +1676,7 @@ mod tests {
         let schema = TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
             search_field: field_path.clone(),
             filter_fields: BTreeSet::new(),
+            synonyms: vec![],
         });
 
         #[derive(serde::Deserialize)]
@@ -1703,6 +1853,7 @@ mod tests {
         TantivySearchIndexSchema::new(&DeveloperTextIndexConfig {
             search_field: field_path,
             filter_fields: BTreeSet::new(),
+            synonyms: vec![],
         })
     }
 
@@ -20,6 +20,7 @@ use common::{
     knobs::SEARCHLIGHT_CLUSTER_NAME,
     query::{
         InternalSearch,
+        InternalSearchSuggest,
         SearchVersion,
     },
     types::{
@@ -52,6 +53,7 @@ use crate::{
     searcher::FragmentedTextStorageKeys,
     QueryResults,
     Searcher,
+    SuggestResults,
     TantivySearchIndexSchema,
 };
 
@@ -212,6 +214,50 @@ impl TextIndexManager {
         Ok(results)
     }
 
+    pub async fn suggest(
+        &self,
+        index: &Index,
+        suggest: &InternalSearchSuggest,
+        searcher: Arc<dyn Searcher>,
+        search_storage: Arc<dyn Storage>,
+    ) -> anyhow::Result<SuggestResults> {
+        let timer = metrics::suggest_timer(&SEARCHLIGHT_CLUSTER_NAME);
+        let printable_index_name = suggest.printable_index_name()?;
+        let tantivy_schema = TantivySearchIndexSchema::new_for_index(index, &printable_index_name)?;
+        let (compiled_query, reads) = tantivy_schema.compile_suggest(suggest)?;
+
+        // Like empty searches, a suggest query without any tokens doesn't need to
+        // touch the index.
+        let suggestions = match compiled_query {
+            Some(compiled_query) => {
+                let SnapshotInfo {
+                    disk_index,
+                    disk_index_ts,
+                    memory_index,
+                    ..
+                } = self.get_snapshot_info(index, &printable_index_name)?;
+                tantivy_schema
+                    .suggest(
+                        compiled_query,
+                        memory_index,
+                        search_storage,
+                        disk_index
+                            .0
+                            .iter()
+                            .cloned()
+                            .map(FragmentedTextStorageKeys::from)
+                            .collect(),
+                        *disk_index_ts,
+                        searcher,
+                    )
+                    .await?
+            },
+            None => vec![],
+        };
+        metrics::finish_suggest(timer, &suggestions);
+        Ok(SuggestResults { suggestions, reads })
+    }
+
     pub async fn search_with_compiled_query(
         &self,
         index: &Index,
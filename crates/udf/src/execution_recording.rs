@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use common::{
+    errors::JsError,
+    identity::InertIdentity,
+    runtime::UnixTimestamp,
+    types::{
+        Timestamp,
+        UdfType,
+    },
+    value::ConvexValue,
+};
+use errors::ErrorMetadata;
+use keybroker::KeyBroker;
+use pb::{
+    common::UdfType as UdfTypeProto,
+    outcome::{
+        recorded_syscall::Result as RecordedSyscallResultProto,
+        ExecutionRecording as ExecutionRecordingProto,
+        RecordedModule as RecordedModuleProto,
+        RecordedSyscall as RecordedSyscallProto,
+        RecordedSyscallError as RecordedSyscallErrorProto,
+    },
+};
+use serde_json::Value as JsonValue;
+use value::sha256::Sha256Digest;
+
+use crate::validation::ValidatedPathAndArgs;
+
+/// Everything a query or mutation observed while it executed: its inputs,
+/// the seeds for its nondeterministic globals, the hash of every module it
+/// loaded, and the result of every sync and async syscall in the order they
+/// were issued. Replaying a recording serves syscalls from `syscalls` instead
+/// of the database, so the function sees exactly what it saw the first time.
+#[derive(Clone)]
+#[cfg_attr(any(test, feature = "testing"), derive(Debug, PartialEq))]
+pub struct ExecutionRecording {
+    pub udf_type: UdfType,
+    pub path_and_args: ValidatedPathAndArgs,
+    pub identity: InertIdentity,
+    pub snapshot_ts: Timestamp,
+    pub rng_seed: [u8; 32],
+    pub unix_timestamp: UnixTimestamp,
+    pub syscalls: Vec<RecordedSyscall>,
+    /// The hash of each module's source, keyed by module path.
+    pub modules: BTreeMap<String, Sha256Digest>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(any(test, feature = "testing"), derive(PartialEq))]
+pub struct RecordedSyscall {
+    pub name: String,
+    pub args: JsonValue,
+    pub result: Result<String, RecordedSyscallError>,
+}
+
+/// An error returned from a syscall. Only the parts that are visible to
+/// JavaScript are kept.
+#[derive(Clone, Debug)]
+#[cfg_attr(any(test, feature = "testing"), derive(PartialEq))]
+pub struct RecordedSyscallError {
+    pub metadata: ErrorMetadata,
+    pub custom_data: Option<ConvexValue>,
+}
+
+impl RecordedSyscallError {
+    pub fn from_error(e: &anyhow::Error) -> Self {
+        let metadata = e
+            .downcast_ref::<ErrorMetadata>()
+            .cloned()
+            .unwrap_or_else(ErrorMetadata::operational_internal_server_error);
+        let custom_data = e
+            .downcast_ref::<JsError>()
+            .and_then(|js_error| js_error.custom_data.clone());
+        Self {
+            metadata,
+            custom_data,
+        }
+    }
+
+    pub fn into_error(self) -> anyhow::Error {
+        let message = self.metadata.msg.to_string();
+        let error = anyhow::Error::from(self.metadata);
+        match self.custom_data {
+            Some(custom_data) => error.context(JsError {
+                message,
+                custom_data: Some(custom_data),
+                frames: None,
+            }),
+            None => error,
+        }
+    }
+}
+
+impl ExecutionRecording {
+    /// Recordings contain the function's arguments, identity and every value
+    /// it read, so they're only ever stored encrypted with the instance's key.
+    pub fn encrypt(self, key_broker: &KeyBroker) -> anyhow::Result<String> {
+        let proto = ExecutionRecordingProto::try_from(self)?;
+        Ok(key_broker.encrypt_execution_recording(&proto))
+    }
+
+    pub fn decrypt(key_broker: &KeyBroker, encrypted: &str) -> anyhow::Result<Self> {
+        key_broker
+            .decrypt_execution_recording(encrypted.trim())?
+            .try_into()
+    }
+}
+
+impl TryFrom<ExecutionRecording> for ExecutionRecordingProto {
+    type Error = anyhow::Error;
+
+    fn try_from(
+        ExecutionRecording {
+            udf_type,
+            path_and_args,
+            identity,
+            snapshot_ts,
+            rng_seed,
+            unix_timestamp,
+            syscalls,
+            modules,
+        }: ExecutionRecording,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            udf_type: UdfTypeProto::from(udf_type).into(),
+            path_and_args: Some(path_and_args.try_into()?),
+            identity: Some(identity.to_string()),
+            snapshot_ts: Some(snapshot_ts.into()),
+            rng_seed: Some(rng_seed.to_vec()),
+            unix_timestamp: Some(unix_timestamp.into()),
+            syscalls: syscalls
+                .into_iter()
+                .map(RecordedSyscallProto::try_from)
+                .try_collect()?,
+            modules: modules
+                .into_iter()
+                .map(|(path, sha256)| RecordedModuleProto {
+                    path: Some(path),
+                    sha256: Some(sha256.to_vec()),
+                })
+                .collect(),
+        })
+    }
+}
+
+impl TryFrom<ExecutionRecordingProto> for ExecutionRecording {
+    type Error = anyhow::Error;
+
+    fn try_from(proto: ExecutionRecordingProto) -> anyhow::Result<Self> {
+        let udf_type = UdfType::from(proto.udf_type());
+        let ExecutionRecordingProto {
+            udf_type: _,
+            path_and_args,
+            identity,
+            snapshot_ts,
+            rng_seed,
+            unix_timestamp,
+            syscalls,
+            modules,
+        } = proto;
+        let rng_seed = rng_seed.context("Missing rng_seed")?;
+        Ok(Self {
+            udf_type,
+            path_and_args: ValidatedPathAndArgs::from_proto(
+                path_and_args.context("Missing path_and_args")?,
+            )?,
+            identity: identity.context("Missing identity")?.parse()?,
+            snapshot_ts: snapshot_ts.context("Missing snapshot_ts")?.try_into()?,
+            rng_seed: rng_seed
+                .as_slice()
+                .try_into()
+                .context("Invalid rng_seed length")?,
+            unix_timestamp: unix_timestamp
+                .context("Missing unix_timestamp")?
+                .try_into()?,
+            syscalls: syscalls
+                .into_iter()
+                .map(RecordedSyscall::try_from)
+                .try_collect()?,
+            modules: modules
+                .into_iter()
+                .map(|RecordedModuleProto { path, sha256 }| {
+                    anyhow::Ok((
+                        path.context("Missing path")?,
+                        sha256.context("Missing sha256")?.try_into()?,
+                    ))
+                })
+                .try_collect()?,
+        })
+    }
+}
+
+impl TryFrom<RecordedSyscall> for RecordedSyscallProto {
+    type Error = anyhow::Error;
+
+    fn try_from(RecordedSyscall { name, args, result }: RecordedSyscall) -> anyhow::Result<Self> {
+        let result = match result {
+            Ok(value) => RecordedSyscallResultProto::Value(value),
+            Err(RecordedSyscallError {
+                metadata,
+                custom_data,
+            }) => RecordedSyscallResultProto::Error(RecordedSyscallErrorProto {
+                metadata: Some(metadata.into()),
+                custom_data: custom_data
+                    .map(|value| serde_json::to_string(&value.to_internal_json()))
+                    .transpose()?,
+            }),
+        };
+        Ok(Self {
+            name: Some(name),
+            args: Some(serde_json::to_string(&args)?),
+            result: Some(result),
+        })
+    }
+}
+
+impl TryFrom<RecordedSyscallProto> for RecordedSyscall {
+    type Error = anyhow::Error;
+
+    fn try_from(
+        RecordedSyscallProto { name, args, result }: RecordedSyscallProto,
+    ) -> anyhow::Result<Self> {
+        let result = match result.context("Missing result")? {
+            RecordedSyscallResultProto::Value(value) => Ok(value),
+            RecordedSyscallResultProto::Error(RecordedSyscallErrorProto {
+                metadata,
+                custom_data,
+            }) => Err(RecordedSyscallError {
+                metadata: metadata.context("Missing metadata")?.try_into()?,
+                custom_data: custom_data
+                    .map(|json| ConvexValue::try_from(serde_json::from_str::<JsonValue>(&json)?))
+                    .transpose()?,
+            }),
+        };
+        Ok(Self {
+            name: name.context("Missing name")?,
+            args: serde_json::from_str(&args.context("Missing args")?)?,
+            result,
+        })
+    }
+}
@@ -4,6 +4,7 @@
 mod action_outcome;
 mod client;
 pub mod environment;
+mod execution_recording;
 mod function_outcome;
 pub mod helpers;
 mod http_action;
@@ -24,6 +25,11 @@ pub use crate::{
         EvaluateAppDefinitionsResult,
         FunctionResult,
     },
+    execution_recording::{
+        ExecutionRecording,
+        RecordedSyscall,
+        RecordedSyscallError,
+    },
     function_outcome::FunctionOutcome,
     http_action::{
         HttpActionRequest,
@@ -18,6 +18,7 @@ use common::{
     types::{
         AllowedVisibility,
         BackendState,
+        FunctionLimits,
         UdfType,
     },
     version::{
@@ -267,6 +268,7 @@ pub struct ValidatedPathAndArgs {
     args: ConvexArray,
     // Not set for system modules.
     npm_version: Option<Version>,
+    limits: FunctionLimits,
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -283,8 +285,9 @@ impl Arbitrary for ValidatedPathAndArgs {
             ConvexArray,
             ComponentId,
             ComponentPath,
+            FunctionLimits,
         )>()
-        .prop_map(|(udf_path, args, component_id, component_path)| {
+        .prop_map(|(udf_path, args, component_id, component_path, limits)| {
             ValidatedPathAndArgs {
                 path: ResolvedComponentFunctionPath {
                     component: component_id,
@@ -293,6 +296,7 @@ impl Arbitrary for ValidatedPathAndArgs {
                 },
                 args,
                 npm_version: None,
+                limits,
             }
         })
     }
@@ -354,6 +358,7 @@ impl ValidatedPathAndArgs {
                         path,
                         args,
                         npm_version: None,
+                        limits: FunctionLimits::default(),
                     },
                     ReturnsValidator::Unvalidated,
                 ))
@@ -502,6 +507,7 @@ impl ValidatedPathAndArgs {
             path,
             args,
             npm_version: Some(version),
+            limits: analyzed_function.limits,
         }))
     }
 
@@ -539,6 +545,7 @@ impl ValidatedPathAndArgs {
             },
             args,
             npm_version,
+            limits: FunctionLimits::default(),
         }
     }
 
@@ -546,6 +553,11 @@ impl ValidatedPathAndArgs {
         &self.path
     }
 
+    /// The resource limits from the function's config.
+    pub fn limits(&self) -> &FunctionLimits {
+        &self.limits
+    }
+
     pub fn consume(self) -> (ResolvedComponentFunctionPath, ConvexArray, Option<Version>) {
         (self.path, self.args, self.npm_version)
     }
@@ -561,6 +573,7 @@ impl ValidatedPathAndArgs {
             npm_version,
             component_path,
             component_id,
+            limits,
         }: pb::common::ValidatedPathAndArgs,
     ) -> anyhow::Result<Self> {
         let args_json: JsonValue =
@@ -579,6 +592,7 @@ impl ValidatedPathAndArgs {
             },
             args,
             npm_version: npm_version.map(|v| Version::parse(&v)).transpose()?,
+            limits: limits.map(FunctionLimits::from).unwrap_or_default(),
         })
     }
 }
@@ -591,6 +605,7 @@ impl TryFrom<ValidatedPathAndArgs> for pb::common::ValidatedPathAndArgs {
             path,
             args,
             npm_version,
+            limits,
         }: ValidatedPathAndArgs,
     ) -> anyhow::Result<Self> {
         let args = args.json_serialize()?.into_bytes();
@@ -603,6 +618,7 @@ impl TryFrom<ValidatedPathAndArgs> for pb::common::ValidatedPathAndArgs {
             npm_version: npm_version.map(|v| v.to_string()),
             component_path,
             component_id: path.component.serialize_to_string(),
+            limits: Some(limits.into()),
         })
     }
 }
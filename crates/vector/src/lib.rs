@@ -27,6 +27,9 @@ mod qdrant_index;
 pub mod qdrant_segments;
 mod query;
 mod searcher;
+mod sparse_index;
+mod sparse_memory_index;
+pub mod sparse_segment;
 mod vector_index_manager;
 
 #[cfg(any(test, feature = "testing"))]
@@ -49,21 +52,39 @@ pub use self::{
         CompiledVectorSearch,
         InternalVectorSearch,
         PublicVectorSearchQueryResult,
+        VectorQueryRead,
+        VectorQueryReads,
         VectorSearch,
+        VectorSearchCursor,
         VectorSearchExpression,
         VectorSearchJson,
+        VectorSearchPage,
         VectorSearchQueryResult,
         VectorSearchRequest,
+        VectorSearchSubscriptions,
     },
     searcher::VectorSearcher,
+    sparse_index::{
+        CompiledSparseVectorSearch,
+        InternalSparseVectorSearch,
+        SparseVector,
+        SparseVectorDocument,
+        SparseVectorSchema,
+        SparseVectorSearch,
+        SparseVectorSearchJson,
+    },
+    sparse_memory_index::MemorySparseVectorIndex,
     vector_index_manager::{
         IndexState,
+        MemoryIndex,
         VectorIndexManager,
     },
 };
 
 pub const MAX_VECTOR_RESULTS: usize = 256;
 pub const DEFAULT_VECTOR_LIMIT: u32 = 10;
+/// Paginated searches refetch every earlier page, so bound how deep they go.
+pub const MAX_VECTOR_PAGINATION_DEPTH: usize = 4096;
 pub const MAX_FILTER_LENGTH: usize = 64;
 
 #[derive(Clone, Debug)]
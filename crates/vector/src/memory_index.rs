@@ -3,23 +3,24 @@ use std::{
     mem,
 };
 
-use common::types::{
-    Timestamp,
-    WriteTimestamp,
+use common::{
+    bootstrap_model::index::vector_index::VectorDistanceMetric,
+    types::{
+        Timestamp,
+        WriteTimestamp,
+    },
 };
 use imbl::{
     OrdMap,
     OrdSet,
     Vector,
 };
-use qdrant_segment::spaces::{
-    metric::Metric,
-    simple::CosineMetric,
-};
 use value::InternalId;
 
 use crate::{
     qdrant_index::{
+        preprocess_vector,
+        preprocessed_similarity,
         NormalizedQdrantDocument,
         QdrantDocument,
     },
@@ -32,6 +33,8 @@ use crate::{
 
 #[derive(Clone)]
 pub struct MemoryVectorIndex {
+    distance_metric: VectorDistanceMetric,
+
     min_ts: WriteTimestamp,
     max_ts: WriteTimestamp,
 
@@ -45,8 +48,10 @@ pub struct MemoryVectorIndex {
 }
 
 impl MemoryVectorIndex {
-    pub fn new(base_ts: WriteTimestamp) -> Self {
+    pub fn new(base_ts: WriteTimestamp, distance_metric: VectorDistanceMetric) -> Self {
         Self {
+            distance_metric,
+
             min_ts: base_ts,
             max_ts: base_ts,
 
@@ -110,7 +115,7 @@ impl MemoryVectorIndex {
             }
         }
         if let Some(old_value) = old_value {
-            let normalized = NormalizedQdrantDocument::from(old_value);
+            let normalized = NormalizedQdrantDocument::new(old_value, self.distance_metric);
             self.tombstones_size += normalized.size();
             self.tombstones.push_back((ts, normalized));
         }
@@ -119,7 +124,7 @@ impl MemoryVectorIndex {
             self.documents_size -= old_value.document.size();
         }
         if let Some(new_value) = new_value {
-            let normalized = NormalizedQdrantDocument::from(new_value);
+            let normalized = NormalizedQdrantDocument::new(new_value, self.distance_metric);
             self.documents_size += normalized.size();
             let revision = Revision {
                 ts,
@@ -203,12 +208,16 @@ impl MemoryVectorIndex {
             self.min_ts,
         );
         let query_vector = Vec::from(query.vector.clone());
-        let query_vector = CosineMetric::preprocess(query_vector);
+        let query_vector = preprocess_vector(self.distance_metric, query_vector);
         let mut candidates = vec![];
 
         for (&id, revision) in &self.documents {
             if revision.document.matches(query) {
-                let distance = CosineMetric::similarity(&query_vector, &revision.document.vector);
+                let distance = preprocessed_similarity(
+                    self.distance_metric,
+                    &query_vector,
+                    &revision.document.vector,
+                );
                 candidates.push(VectorSearchQueryResult {
                     score: distance,
                     id,
@@ -1,8 +1,10 @@
+use common::bootstrap_model::index::vector_index::VectorQuantizationType;
 use metrics::{
     cluster_label,
     log_counter,
     log_counter_with_labels,
     log_distribution,
+    log_distribution_with_labels,
     register_convex_counter,
     register_convex_histogram,
     MetricLabel,
@@ -256,6 +258,65 @@ pub fn qdrant_segment_disk_build_timer(disk_index_type: QdrantVectorIndexType) -
     timer
 }
 
+const QUANTIZATION_TYPE_LABEL: &str = "quantization_type";
+
+fn quantization_type_label(quantization_type: VectorQuantizationType) -> StaticMetricLabel {
+    let type_str = match quantization_type {
+        VectorQuantizationType::Scalar => "scalar",
+        VectorQuantizationType::Binary => "binary",
+    };
+    StaticMetricLabel::new(QUANTIZATION_TYPE_LABEL, type_str)
+}
+
+register_convex_histogram!(
+    VECTOR_QUANTIZED_SEGMENT_SIZE_BYTES,
+    "Estimated size of the quantized vectors in a newly built disk segment",
+    &[QUANTIZATION_TYPE_LABEL],
+);
+register_convex_histogram!(
+    VECTOR_QUANTIZED_SEGMENT_SAVED_BYTES,
+    "Estimated memory saved by quantizing the vectors in a newly built disk segment",
+    &[QUANTIZATION_TYPE_LABEL],
+);
+pub fn log_quantized_segment_size(
+    quantization_type: VectorQuantizationType,
+    num_vectors: usize,
+    dimensions: usize,
+) {
+    let full_precision_bytes = num_vectors * dimensions * std::mem::size_of::<f32>();
+    let quantized_bytes = num_vectors * quantization_type.vector_size_bytes(dimensions);
+    log_distribution_with_labels(
+        &VECTOR_QUANTIZED_SEGMENT_SIZE_BYTES,
+        quantized_bytes as f64,
+        vec![quantization_type_label(quantization_type)],
+    );
+    log_distribution_with_labels(
+        &VECTOR_QUANTIZED_SEGMENT_SAVED_BYTES,
+        full_precision_bytes.saturating_sub(quantized_bytes) as f64,
+        vec![quantization_type_label(quantization_type)],
+    );
+}
+
+register_convex_histogram!(
+    VECTOR_QUANTIZATION_RECALL,
+    "Fraction of the exact nearest neighbors found by a sampled quantized vector search",
+    &[QUANTIZATION_TYPE_LABEL, "rescore"],
+);
+pub fn log_quantization_recall(
+    quantization_type: VectorQuantizationType,
+    rescore: bool,
+    recall: f64,
+) {
+    log_distribution_with_labels(
+        &VECTOR_QUANTIZATION_RECALL,
+        recall,
+        vec![
+            quantization_type_label(quantization_type),
+            StaticMetricLabel::new("rescore", if rescore { "true" } else { "false" }),
+        ],
+    );
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum VectorIndexType {
     MultiSegment,
@@ -2,6 +2,7 @@ use std::{
     collections::{
         BTreeMap,
         BTreeSet,
+        HashSet,
     },
     fs,
     mem,
@@ -23,12 +24,17 @@ use std::{
 
 use atomic_refcell::AtomicRefCell;
 use common::{
-    bootstrap_model::index::vector_index::DeveloperVectorIndexConfig,
+    bootstrap_model::index::vector_index::{
+        DeveloperVectorIndexConfig,
+        VectorDistanceMetric,
+        VectorQuantization,
+    },
     document::ResolvedDocument,
     knobs::VECTOR_INDEX_THREADS,
     persistence::DocumentStream,
     query::search_value_to_bytes,
     types::{
+        IndexName,
         Timestamp,
         WriteTimestamp,
     },
@@ -53,11 +59,16 @@ use qdrant_segment::{
     segment::Segment,
     spaces::{
         metric::Metric,
-        simple::CosineMetric,
+        simple::{
+            CosineMetric,
+            DotProductMetric,
+            EuclidMetric,
+        },
     },
     types::{
         AnyVariants,
         Condition,
+        Distance,
         ExtendedPointId,
         FieldCondition,
         Filter,
@@ -69,6 +80,7 @@ use qdrant_segment::{
         PayloadSelector,
         PayloadSelectorInclude,
         PointIdType,
+        QuantizationSearchParams,
         SearchParams,
         ValueVariants,
         WithPayload,
@@ -97,6 +109,7 @@ use crate::{
     qdrant_segments::{
         build_disk_segment,
         create_mutable_segment,
+        qdrant_quantization_config,
         segment_config,
         snapshot_segment,
         VectorDiskSegmentValues,
@@ -113,6 +126,7 @@ use crate::{
     VectorSearchQueryResult,
     DEFAULT_VECTOR_LIMIT,
     MAX_FILTER_LENGTH,
+    MAX_VECTOR_PAGINATION_DEPTH,
     MAX_VECTOR_RESULTS,
 };
 
@@ -123,6 +137,8 @@ pub struct QdrantSchema {
     dimension: usize,
     vector_field: FieldPath,
     filter_fields: BTreeSet<FieldPath>,
+    distance_metric: VectorDistanceMetric,
+    quantization: Option<VectorQuantization>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -137,9 +153,15 @@ impl QdrantSchema {
             dimension: u32::from(index_config.dimensions) as usize,
             vector_field: index_config.vector_field.clone(),
             filter_fields: index_config.filter_fields.clone(),
+            distance_metric: index_config.distance_metric,
+            quantization: index_config.quantization,
         }
     }
 
+    pub fn distance_metric(&self) -> VectorDistanceMetric {
+        self.distance_metric
+    }
+
     pub fn index(&self, document: &ResolvedDocument) -> Option<QdrantDocument> {
         let object = document.value();
         let Some(ConvexValue::Array(ref array)) = object.get_path(&self.vector_field) else {
@@ -193,63 +215,29 @@ impl QdrantSchema {
                 )
             )
         );
-        let mut filter_conditions = BTreeMap::new();
-        // Each equality expression contributes to this, so an `In` with N elements
-        // increments this by N
-        let mut filter_length = 0;
-
-        for expresion in query.expressions {
-            match expresion {
-                VectorSearchExpression::Eq(field_path, value) => {
-                    if !self.filter_fields.contains(&field_path) {
-                        anyhow::bail!(incorrect_vector_filter_field_error(
-                            &index_name,
-                            &field_path
-                        ))
-                    }
-                    let value_bytes = search_value_to_bytes(value.as_ref());
-                    if filter_conditions.contains_key(&field_path) {
-                        anyhow::bail!("Found multiple filters for the same field?")
-                    }
-                    filter_conditions.insert(field_path, CompiledVectorFilter::Eq(value_bytes));
-                    filter_length += 1;
-                },
-                VectorSearchExpression::In(field_path, values) => {
-                    if !self.filter_fields.contains(&field_path) {
-                        anyhow::bail!(incorrect_vector_filter_field_error(
-                            &index_name,
-                            &field_path
-                        ))
-                    }
-                    let values_bytes: Vec<_> = values
-                        .into_iter()
-                        .map(|v| search_value_to_bytes(v.as_ref()))
-                        .collect();
-                    if filter_conditions.contains_key(&field_path) {
-                        anyhow::bail!("Found multiple filters for the same field?")
-                    }
-                    filter_length += values_bytes.len();
-                    filter_conditions.insert(field_path, CompiledVectorFilter::In(values_bytes));
-                },
-            }
-        }
+        // Later pages are found by fetching everything up to and including the
+        // page and then skipping past the cursor.
+        let offset = query.cursor.map_or(0, |c| c.offset);
+        let fetch_limit = offset.saturating_add(query_limit);
         anyhow::ensure!(
-            filter_length <= MAX_FILTER_LENGTH,
+            fetch_limit as usize <= MAX_VECTOR_PAGINATION_DEPTH,
             ErrorMetadata::bad_request(
-                "TooManyElementsInVectorQueryError",
+                "VectorPaginationTooDeepError",
                 format!(
-                    "Vector query against {index_name} has too many conditions. Max: {} Actual: {}",
-                    MAX_FILTER_LENGTH, filter_length
+                    "Paginated vector queries can fetch at most {} results in total, requested {}.",
+                    MAX_VECTOR_PAGINATION_DEPTH, fetch_limit as usize,
                 )
             )
         );
+        let filter_conditions =
+            compile_vector_filters(&index_name, &self.filter_fields, query.expressions)?;
         anyhow::ensure!(
             query_vector.len() == self.dimension,
             vector_dimensions_mismatch_error(query_vector.len() as u32, self.dimension as u32)
         );
         let result = CompiledVectorSearch {
             vector: query_vector,
-            limit: query_limit,
+            limit: fetch_limit,
             filter_conditions,
         };
         metrics::log_compiled_query(&result);
@@ -264,6 +252,7 @@ impl QdrantSchema {
         overfetch_delta: u32,
         slow_vector_query_threshold_millis: u64,
         require_exact: bool,
+        sample_recall: bool,
     ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
         let qdrant_conditions = query
             .filter_conditions
@@ -285,27 +274,73 @@ impl QdrantSchema {
         let search_params = SearchParams {
             hnsw_ef: None,
             exact: require_exact,
-            quantization: None,
+            quantization: self.quantization.map(|q| QuantizationSearchParams {
+                ignore: false,
+                rescore: Some(q.rescore),
+                oversampling: None,
+            }),
             indexed_only: false,
         };
         let payload_selector = PayloadSelectorInclude {
             include: vec![json_path_from_str(TIMESTAMP_FIELD)?],
         };
+        let with_payload = WithPayload {
+            enable: true,
+            payload_selector: Some(PayloadSelector::Include(payload_selector)),
+        };
+        let top = (query.limit + overfetch_delta) as usize;
+        let query_vector = query.vector.into();
         let start = Instant::now();
         let qdrant_results = segment.search(
             DEFAULT_VECTOR_NAME,
-            &query.vector.into(),
-            &WithPayload {
-                enable: true,
-                payload_selector: Some(PayloadSelector::Include(payload_selector)),
-            },
+            &query_vector,
+            &with_payload,
             &WithVector::Bool(false),
             Some(&qdrant_filter),
-            (query.limit + overfetch_delta) as usize,
+            top,
             Some(&search_params),
             &AtomicBool::new(false),
         )?;
         let duration = Instant::now().duration_since(start);
+        if let Some(quantization) = self.quantization
+            && !require_exact
+            && sample_recall
+        {
+            // Measure how many of the exact nearest neighbors the quantized search
+            // found by repeating it against the full precision vectors.
+            let exact_params = SearchParams {
+                hnsw_ef: None,
+                exact: true,
+                quantization: Some(QuantizationSearchParams {
+                    ignore: true,
+                    rescore: None,
+                    oversampling: None,
+                }),
+                indexed_only: false,
+            };
+            let exact_results = segment.search(
+                DEFAULT_VECTOR_NAME,
+                &query_vector,
+                &WithPayload {
+                    enable: false,
+                    payload_selector: None,
+                },
+                &WithVector::Bool(false),
+                Some(&qdrant_filter),
+                top,
+                Some(&exact_params),
+                &AtomicBool::new(false),
+            )?;
+            let approximate_ids: Vec<_> = qdrant_results.iter().map(|point| point.id).collect();
+            let exact_ids: Vec<_> = exact_results.iter().map(|point| point.id).collect();
+            if let Some(recall) = quantized_recall(&approximate_ids, &exact_ids) {
+                metrics::log_quantization_recall(
+                    quantization.quantization_type,
+                    quantization.rescore,
+                    recall,
+                );
+            }
+        }
         if duration > Duration::from_millis(slow_vector_query_threshold_millis) {
             let detail = TelemetryDetail {
                 level: DetailsLevel::Level2,
@@ -357,7 +392,13 @@ impl QdrantSchema {
         // upfront, always set up the more complex directory.
         let memory_dir: PathBuf = tmpdir.path().join("memory");
         let id_tracker = Arc::new(AtomicRefCell::new(VectorMemoryIdTracker::new()));
-        let mutable_config = segment_config(self.dimension, true, *VECTOR_INDEX_THREADS);
+        let mutable_config = segment_config(
+            self.dimension,
+            qdrant_distance(self.distance_metric),
+            self.quantization.map(qdrant_quantization_config),
+            true,
+            *VECTOR_INDEX_THREADS,
+        );
         let mut memory_segment = create_mutable_segment(
             &memory_dir,
             id_tracker.clone(),
@@ -463,7 +504,13 @@ impl QdrantSchema {
                 fs::create_dir_all(&indexing_path)?;
                 let disk_path = index_path.join("disk");
                 fs::create_dir_all(&disk_path)?;
-                let disk_config = segment_config(self.dimension, false, *VECTOR_INDEX_THREADS);
+                let disk_config = segment_config(
+                    self.dimension,
+                    qdrant_distance(self.distance_metric),
+                    self.quantization.map(qdrant_quantization_config),
+                    false,
+                    *VECTOR_INDEX_THREADS,
+                );
                 build_disk_segment(&memory_segment, &indexing_path, &disk_path, disk_config)
             },
         }?;
@@ -522,11 +569,95 @@ impl QdrantDocument {
     }
 }
 
-#[cfg(any(test, feature = "testing"))]
 pub fn cosine_similarity(v1: &[f32], v2: &[f32]) -> f32 {
-    let v1 = CosineMetric::preprocess(v1.to_vec());
-    let v2 = CosineMetric::preprocess(v2.to_vec());
-    CosineMetric::similarity(&v1, &v2)
+    vector_similarity(VectorDistanceMetric::Cosine, v1, v2)
+}
+
+pub(crate) fn qdrant_distance(metric: VectorDistanceMetric) -> Distance {
+    match metric {
+        VectorDistanceMetric::Cosine => Distance::Cosine,
+        VectorDistanceMetric::DotProduct => Distance::Dot,
+        VectorDistanceMetric::Euclidean => Distance::Euclid,
+    }
+}
+
+/// Prepares a vector for scoring with `metric`, matching what qdrant does
+/// when inserting into or querying a segment.
+pub(crate) fn preprocess_vector(metric: VectorDistanceMetric, vector: Vec<f32>) -> Vec<f32> {
+    match metric {
+        VectorDistanceMetric::Cosine => CosineMetric::preprocess(vector),
+        VectorDistanceMetric::DotProduct => DotProductMetric::preprocess(vector),
+        VectorDistanceMetric::Euclidean => EuclidMetric::preprocess(vector),
+    }
+}
+
+/// Scores two preprocessed vectors the same way qdrant segments do. Higher
+/// scores are always better, so for Euclidean distance this is the negated
+/// squared distance.
+pub(crate) fn preprocessed_similarity(metric: VectorDistanceMetric, v1: &[f32], v2: &[f32]) -> f32 {
+    match metric {
+        VectorDistanceMetric::Cosine => CosineMetric::similarity(v1, v2),
+        VectorDistanceMetric::DotProduct => DotProductMetric::similarity(v1, v2),
+        VectorDistanceMetric::Euclidean => EuclidMetric::similarity(v1, v2),
+    }
+}
+
+pub(crate) fn vector_similarity(metric: VectorDistanceMetric, v1: &[f32], v2: &[f32]) -> f32 {
+    let v1 = preprocess_vector(metric, v1.to_vec());
+    let v2 = preprocess_vector(metric, v2.to_vec());
+    preprocessed_similarity(metric, &v1, &v2)
+}
+
+/// An upper bound on the similarity between `query` and any vector within
+/// Euclidean distance `radius` of `approximate`.
+pub(crate) fn max_vector_similarity(
+    metric: VectorDistanceMetric,
+    query: &[f32],
+    approximate: &[f32],
+    radius: f32,
+) -> f32 {
+    let similarity = vector_similarity(metric, query, approximate);
+    if radius <= 0. {
+        return similarity;
+    }
+    match metric {
+        VectorDistanceMetric::Cosine => {
+            // Moving `approximate` by `radius` rotates it by at most
+            // `asin(radius / |approximate|)`.
+            let norm = approximate.iter().map(|x| x * x).sum::<f32>().sqrt();
+            if norm <= radius {
+                return 1.;
+            }
+            let angle = similarity.clamp(-1., 1.).acos();
+            (angle - (radius / norm).asin()).max(0.).cos()
+        },
+        VectorDistanceMetric::DotProduct => {
+            let query_norm = query.iter().map(|x| x * x).sum::<f32>().sqrt();
+            similarity + query_norm * radius
+        },
+        VectorDistanceMetric::Euclidean => {
+            let distance = (-similarity).max(0.).sqrt();
+            -(distance - radius).max(0.).powi(2)
+        },
+    }
+}
+
+/// Converts an internal score into the score returned to developers. This is
+/// the identity except for Euclidean distance, where we return the distance
+/// itself.
+pub(crate) fn public_vector_score(metric: VectorDistanceMetric, score: f32) -> f32 {
+    match metric {
+        VectorDistanceMetric::Cosine | VectorDistanceMetric::DotProduct => score,
+        VectorDistanceMetric::Euclidean => score.abs().sqrt(),
+    }
+}
+
+/// The inverse of [`public_vector_score`].
+pub(crate) fn internal_vector_score(metric: VectorDistanceMetric, score: f32) -> f32 {
+    match metric {
+        VectorDistanceMetric::Cosine | VectorDistanceMetric::DotProduct => score,
+        VectorDistanceMetric::Euclidean => -(score * score),
+    }
 }
 
 // NB: For cosine similarity, we need to normalize vectors before indexing them.
@@ -537,19 +668,17 @@ pub struct NormalizedQdrantDocument {
     pub filter_fields: BTreeMap<FieldPath, Vec<u8>>,
 }
 
-impl From<QdrantDocument> for NormalizedQdrantDocument {
-    fn from(value: QdrantDocument) -> Self {
+impl NormalizedQdrantDocument {
+    pub fn new(value: QdrantDocument, distance_metric: VectorDistanceMetric) -> Self {
         let vector = Vec::from(value.vector);
-        let vector = CosineMetric::preprocess(vector);
+        let vector = preprocess_vector(distance_metric, vector);
         Self {
             internal_id: value.internal_id,
             vector,
             filter_fields: value.filter_fields,
         }
     }
-}
 
-impl NormalizedQdrantDocument {
     pub fn size(&self) -> usize {
         let mut size = 0;
         size += self.vector.len() * mem::size_of::<f32>();
@@ -562,6 +691,71 @@ impl NormalizedQdrantDocument {
     }
 }
 
+/// The fraction of `exact` results that also appear in `approximate`, or
+/// `None` if there weren't any exact results to find.
+fn quantized_recall(approximate: &[ExtendedPointId], exact: &[ExtendedPointId]) -> Option<f64> {
+    if exact.is_empty() {
+        return None;
+    }
+    let approximate: HashSet<_> = approximate.iter().collect();
+    let found = exact.iter().filter(|id| approximate.contains(id)).count();
+    Some(found as f64 / exact.len() as f64)
+}
+
+/// Compiles the filter expressions of a vector query, checking them against
+/// the index's `filterFields`. Shared by dense and sparse vector indexes.
+pub(crate) fn compile_vector_filters(
+    index_name: &IndexName,
+    filter_fields: &BTreeSet<FieldPath>,
+    expressions: Vec<VectorSearchExpression>,
+) -> anyhow::Result<BTreeMap<FieldPath, CompiledVectorFilter>> {
+    let mut filter_conditions = BTreeMap::new();
+    // Each equality expression contributes to this, so an `In` with N elements
+    // increments this by N
+    let mut filter_length = 0;
+
+    for expresion in expressions {
+        match expresion {
+            VectorSearchExpression::Eq(field_path, value) => {
+                if !filter_fields.contains(&field_path) {
+                    anyhow::bail!(incorrect_vector_filter_field_error(index_name, &field_path))
+                }
+                let value_bytes = search_value_to_bytes(value.as_ref());
+                if filter_conditions.contains_key(&field_path) {
+                    anyhow::bail!("Found multiple filters for the same field?")
+                }
+                filter_conditions.insert(field_path, CompiledVectorFilter::Eq(value_bytes));
+                filter_length += 1;
+            },
+            VectorSearchExpression::In(field_path, values) => {
+                if !filter_fields.contains(&field_path) {
+                    anyhow::bail!(incorrect_vector_filter_field_error(index_name, &field_path))
+                }
+                let values_bytes: Vec<_> = values
+                    .into_iter()
+                    .map(|v| search_value_to_bytes(v.as_ref()))
+                    .collect();
+                if filter_conditions.contains_key(&field_path) {
+                    anyhow::bail!("Found multiple filters for the same field?")
+                }
+                filter_length += values_bytes.len();
+                filter_conditions.insert(field_path, CompiledVectorFilter::In(values_bytes));
+            },
+        }
+    }
+    anyhow::ensure!(
+        filter_length <= MAX_FILTER_LENGTH,
+        ErrorMetadata::bad_request(
+            "TooManyElementsInVectorQueryError",
+            format!(
+                "Vector query against {index_name} has too many conditions. Max: {} Actual: {}",
+                MAX_FILTER_LENGTH, filter_length
+            )
+        )
+    );
+    Ok(filter_conditions)
+}
+
 fn encode_user_field_path(field_path: &FieldPath) -> anyhow::Result<JsonPath> {
     let key = String::from(field_path.clone());
     json_path_from_str(key.as_str())
@@ -595,6 +789,8 @@ impl From<QdrantSchema> for proto::VectorIndexConfig {
             dimension: value.dimension as u32,
             vector_field_path: Some(value.vector_field.into()),
             filter_fields: value.filter_fields.into_iter().map(|f| f.into()).collect(),
+            distance_metric: Some(proto::VectorDistanceMetric::from(value.distance_metric) as i32),
+            quantization: value.quantization.map(|q| q.into()),
         }
     }
 }
@@ -603,6 +799,8 @@ impl TryFrom<proto::VectorIndexConfig> for QdrantSchema {
     type Error = anyhow::Error;
 
     fn try_from(value: proto::VectorIndexConfig) -> Result<Self, Self::Error> {
+        let distance_metric = value.distance_metric().into();
+        let quantization = value.quantization.map(VectorQuantization::from);
         let vector_field = value
             .vector_field_path
             .ok_or_else(|| anyhow::anyhow!("Missing vector field path in VectorIndexConfigProto"))?
@@ -616,6 +814,8 @@ impl TryFrom<proto::VectorIndexConfig> for QdrantSchema {
             dimension: value.dimension as usize,
             vector_field,
             filter_fields,
+            distance_metric,
+            quantization,
         })
     }
 }
@@ -678,10 +878,13 @@ fn json_path_from_str(s: &str) -> anyhow::Result<JsonPath> {
 mod tests {
     use common::types::Timestamp;
     use maplit::btreemap;
+    use qdrant_segment::types::ExtendedPointId;
     use rand::Rng;
     use serde_json::json;
+    use uuid::Uuid;
     use value::InternalId;
 
+    use super::quantized_recall;
     use crate::QdrantDocument;
 
     #[test]
@@ -735,4 +938,18 @@ mod tests {
         assert_eq!(payload, json!({ "zzz": "YQ", "_ts": "AAAAAAAAAAA"}));
         Ok(())
     }
+
+    #[test]
+    fn test_quantized_recall() {
+        let ids: Vec<_> = (0..4)
+            .map(|_| ExtendedPointId::Uuid(Uuid::new_v4()))
+            .collect();
+        assert_eq!(quantized_recall(&ids, &[]), None);
+        assert_eq!(quantized_recall(&ids, &ids), Some(1.0));
+        // Order doesn't matter, only which of the exact results were found.
+        let reversed: Vec<_> = ids.iter().rev().copied().collect();
+        assert_eq!(quantized_recall(&reversed, &ids), Some(1.0));
+        assert_eq!(quantized_recall(&ids[..1], &ids), Some(0.25));
+        assert_eq!(quantized_recall(&[], &ids), Some(0.0));
+    }
 }
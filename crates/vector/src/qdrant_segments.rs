@@ -17,6 +17,10 @@ use std::{
 
 use atomic_refcell::AtomicRefCell;
 use common::{
+    bootstrap_model::index::vector_index::{
+        VectorQuantization,
+        VectorQuantizationType,
+    },
     deleted_bitset::DeletedBitset,
     id_tracker::StaticIdTracker,
     runtime::tokio_spawn_blocking,
@@ -66,10 +70,16 @@ use qdrant_segment::{
         PAYLOAD_INDEX_PATH,
     },
     types::{
+        BinaryQuantization,
+        BinaryQuantizationConfig,
         Distance,
         HnswConfig,
         Indexes,
         PayloadStorageType,
+        QuantizationConfig,
+        ScalarQuantization,
+        ScalarQuantizationConfig,
+        ScalarType,
         SegmentConfig,
         SegmentType,
         VectorDataConfig,
@@ -77,21 +87,57 @@ use qdrant_segment::{
         DEFAULT_FULL_SCAN_THRESHOLD,
         DEFAULT_HNSW_EF_CONSTRUCT,
     },
-    vector_storage::VectorStorage,
+    vector_storage::{
+        quantized::quantized_vectors::QuantizedVectors,
+        VectorStorage,
+    },
 };
 use rocksdb::DB;
 
-use crate::id_tracker::{
-    VectorMemoryIdTracker,
-    VectorStaticIdTracker,
+use crate::{
+    id_tracker::{
+        VectorMemoryIdTracker,
+        VectorStaticIdTracker,
+    },
+    metrics,
 };
 
 const UUID_TABLE_FILENAME: &str = "uuids.table";
 const DELETED_BITSET_FILENAME: &str = "deleted.bitset";
 pub(crate) const DEFAULT_VECTOR_NAME: &str = "default_vector";
 
+/// Quantized vectors are always kept in RAM, since saving memory while still
+/// searching them quickly is the point of quantizing.
+pub(crate) fn qdrant_quantization_config(quantization: VectorQuantization) -> QuantizationConfig {
+    match quantization.quantization_type {
+        VectorQuantizationType::Scalar => QuantizationConfig::Scalar(ScalarQuantization {
+            scalar: ScalarQuantizationConfig {
+                r#type: ScalarType::Int8,
+                // Clip outliers so they don't stretch the int8 range.
+                quantile: Some(0.99),
+                always_ram: Some(true),
+            },
+        }),
+        VectorQuantizationType::Binary => QuantizationConfig::Binary(BinaryQuantization {
+            binary: BinaryQuantizationConfig {
+                always_ram: Some(true),
+            },
+        }),
+    }
+}
+
+fn quantization_type(config: &QuantizationConfig) -> Option<VectorQuantizationType> {
+    match config {
+        QuantizationConfig::Scalar(_) => Some(VectorQuantizationType::Scalar),
+        QuantizationConfig::Binary(_) => Some(VectorQuantizationType::Binary),
+        _ => None,
+    }
+}
+
 pub(crate) fn segment_config(
     dimension: usize,
+    distance: Distance,
+    quantization_config: Option<QuantizationConfig>,
     mutable: bool,
     max_indexing_threads: usize,
 ) -> SegmentConfig {
@@ -128,10 +174,12 @@ pub(crate) fn segment_config(
     };
     let vector_data_config = VectorDataConfig {
         size: dimension,
-        distance: Distance::Cosine,
+        distance,
         storage_type: vector_storage_type,
         index,
-        quantization_config: None,
+        // Mutable segments never build quantized vectors, but we keep the
+        // config so that segments built from them pick it up.
+        quantization_config,
     };
     SegmentConfig {
         vector_data: HashMap::from([(DEFAULT_VECTOR_NAME.to_string(), vector_data_config)]),
@@ -166,7 +214,7 @@ pub fn create_mutable_segment(
     let vector_storage = open_appendable_memmap_vector_storage(
         &vector_storage_path,
         dimension,
-        Distance::Cosine,
+        default_vector_config(&segment_config)?.distance,
         &stopped,
     )?;
     let point_count = id_tracker.borrow().total_point_count();
@@ -261,10 +309,44 @@ pub fn merge_disk_segments_hnsw(
     tmp_path: &Path,
     disk_path: &Path,
 ) -> anyhow::Result<VectorDiskSegmentValues> {
-    let segment_config = segment_config(dimension, false, 4);
+    // Segments are only ever merged with other segments from the same index, so
+    // they share a distance metric and quantization config.
+    let (distance, quantization_config) = match segments.first() {
+        Some((_, segment)) => {
+            let vector_config = default_vector_config(&segment.segment_config)?;
+            (
+                vector_config.distance,
+                vector_config.quantization_config.clone(),
+            )
+        },
+        None => (Distance::Cosine, None),
+    };
+    for (_, segment) in &segments {
+        let vector_config = default_vector_config(&segment.segment_config)?;
+        anyhow::ensure!(
+            vector_config.distance == distance,
+            "Can't merge segments with different distance metrics"
+        );
+        // Changing an index's quantization creates a new index, so a mismatch
+        // here means segments from different indexes got mixed up.
+        anyhow::ensure!(
+            vector_config.quantization_config == quantization_config,
+            "Can't merge segments with different quantization configs: {:?} and {:?}",
+            vector_config.quantization_config,
+            quantization_config,
+        );
+    }
+    let segment_config = segment_config(dimension, distance, quantization_config, false, 4);
     merge_disk_segments(segments, tmp_path, disk_path, segment_config)
 }
 
+fn default_vector_config(segment_config: &SegmentConfig) -> anyhow::Result<&VectorDataConfig> {
+    segment_config
+        .vector_data
+        .get(DEFAULT_VECTOR_NAME)
+        .ok_or_else(|| anyhow::anyhow!("Missing {DEFAULT_VECTOR_NAME} in segment config"))
+}
+
 pub fn merge_disk_segments(
     segments: Vec<(Option<UntarredVectorDiskSegmentPaths>, &Segment)>,
     tmp_path: &Path,
@@ -327,6 +409,20 @@ pub fn merge_disk_segments(
     anyhow::ensure!(vector_count == total_point_count);
     // Writing the new segment should have removed all deletes.
     anyhow::ensure!(num_deleted == 0);
+    if vector_data.quantized_vectors.borrow().is_some() {
+        let vector_config = default_vector_config(&segment_config)?;
+        if let Some(quantization_type) = vector_config
+            .quantization_config
+            .as_ref()
+            .and_then(quantization_type)
+        {
+            metrics::log_quantized_segment_size(
+                quantization_type,
+                total_point_count,
+                vector_config.size,
+            );
+        }
+    }
 
     let memory_tracker = Arc::new(AtomicRefCell::new(memory_tracker));
     let segment = snapshot_segment(
@@ -487,6 +583,20 @@ pub fn load_disk_segment(paths: UntarredVectorDiskSegmentPaths) -> anyhow::Resul
     let vector_count = vector_storage.borrow().total_vector_count();
     anyhow::ensure!(vector_count == point_count);
 
+    // Segments snapshotted directly from a mutable segment keep its quantization
+    // config but never built quantized vectors.
+    let quantized_vectors = if vector_config.quantization_config.is_some()
+        && QuantizedVectors::config_exists(&vector_storage_path)
+    {
+        Some(QuantizedVectors::load(
+            &vector_storage.borrow(),
+            &vector_storage_path,
+        )?)
+    } else {
+        None
+    };
+    let quantized_vectors = Arc::new(AtomicRefCell::new(quantized_vectors));
+
     let vector_index = match vector_config.index {
         qdrant_segment::types::Indexes::Plain {} => VectorIndexEnum::Plain(PlainIndex::new(
             id_tracker.clone(),
@@ -502,7 +612,7 @@ pub fn load_disk_segment(paths: UntarredVectorDiskSegmentPaths) -> anyhow::Resul
                 &vector_index_path,
                 id_tracker.clone(),
                 vector_storage.clone(),
-                Arc::new(AtomicRefCell::new(None)),
+                quantized_vectors.clone(),
                 payload_index.clone(),
                 hnsw_config.clone(),
             )?)
@@ -513,7 +623,7 @@ pub fn load_disk_segment(paths: UntarredVectorDiskSegmentPaths) -> anyhow::Resul
     let vector_data = VectorData {
         vector_storage,
         vector_index,
-        quantized_vectors: Arc::new(AtomicRefCell::new(None)),
+        quantized_vectors,
     };
     let segment = Segment {
         version: segment_state.version,
@@ -561,6 +671,10 @@ mod tests {
     use anyhow::Context;
     use atomic_refcell::AtomicRefCell;
     use common::{
+        bootstrap_model::index::vector_index::{
+            VectorQuantization,
+            VectorQuantizationType,
+        },
         deleted_bitset::DeletedBitset,
         id_tracker::StaticIdTracker,
     };
@@ -580,6 +694,7 @@ mod tests {
         segment::Segment,
         types::{
             Condition,
+            Distance,
             ExtendedPointId,
             FieldCondition,
             Filter,
@@ -615,6 +730,8 @@ mod tests {
             build_disk_segment,
             create_mutable_segment,
             merge_disk_segments,
+            merge_disk_segments_hnsw,
+            qdrant_quantization_config,
             segment_config,
             snapshot_segment,
             unsafe_load_disk_segment,
@@ -657,7 +774,7 @@ mod tests {
     ) -> anyhow::Result<(Segment, Arc<AtomicRefCell<VectorMemoryIdTracker>>)> {
         let memory_path = test_dir.path().join("memory");
         let id_tracker = Arc::new(AtomicRefCell::new(VectorMemoryIdTracker::new()));
-        let mutable_config = segment_config(dimensions, true, 4);
+        let mutable_config = segment_config(dimensions, Distance::Cosine, None, true, 4);
         let mut memory_segment =
             create_mutable_segment(&memory_path, id_tracker.clone(), dimensions, mutable_config)?;
 
@@ -677,7 +794,7 @@ mod tests {
     ) -> anyhow::Result<(Segment, Arc<AtomicRefCell<VectorMemoryIdTracker>>)> {
         let memory_path = test_dir.path().join("memory");
         let id_tracker = Arc::new(AtomicRefCell::new(VectorMemoryIdTracker::new()));
-        let mutable_config = segment_config(dimensions, true, 4);
+        let mutable_config = segment_config(dimensions, Distance::Cosine, None, true, 4);
         let mut memory_segment =
             create_mutable_segment(&memory_path, id_tracker.clone(), dimensions, mutable_config)?;
 
@@ -695,6 +812,15 @@ mod tests {
         dimensions: usize,
         test_dir: &TempDir,
         vectors: impl Iterator<Item = (ExtendedPointId, Vec<f32>)>,
+    ) -> anyhow::Result<VectorDiskSegmentPaths> {
+        create_test_quantized_disk_segment(dimensions, test_dir, vectors, None)
+    }
+
+    fn create_test_quantized_disk_segment(
+        dimensions: usize,
+        test_dir: &TempDir,
+        vectors: impl Iterator<Item = (ExtendedPointId, Vec<f32>)>,
+        quantization: Option<VectorQuantization>,
     ) -> anyhow::Result<VectorDiskSegmentPaths> {
         // Generate the memory segment
         let (memory_segment, _) = create_test_memory_segment(dimensions, test_dir, vectors)?;
@@ -705,7 +831,13 @@ mod tests {
         let disk_path = test_dir.path().join("disk");
         fs::create_dir_all(&disk_path)?;
 
-        let disk_config = segment_config(dimensions, false, 4);
+        let disk_config = segment_config(
+            dimensions,
+            Distance::Cosine,
+            quantization.map(qdrant_quantization_config),
+            false,
+            4,
+        );
         Ok(build_disk_segment(&memory_segment, &indexing_path, &disk_path, disk_config)?.paths)
     }
 
@@ -773,7 +905,7 @@ mod tests {
         let disk_path = test_dir.path().join("disk");
         fs::create_dir_all(&disk_path)?;
 
-        let disk_config = segment_config(DIMENSIONS, false, 4);
+        let disk_config = segment_config(DIMENSIONS, Distance::Cosine, None, false, 4);
         Ok(build_disk_segment(memory_segment, &indexing_path, &disk_path, disk_config)?.paths)
     }
 
@@ -1349,7 +1481,7 @@ mod tests {
         let new_paths = create_test_disk_segment(DIMENSIONS, &new_dir, vector.into_iter())?;
         let new_segment = unsafe_load_disk_segment(&new_paths).await?;
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, Distance::Cosine, None, false, 4);
         let merged_dir = tempfile::tempdir()?;
         let result =
             merge_disk_segments_tmpdir(vec![&initial_segment, &new_segment], &merged_dir, config)
@@ -1375,7 +1507,7 @@ mod tests {
         let new_paths = create_test_disk_segment(DIMENSIONS, &new_dir, vectors.into_iter())?;
         let new_segment = unsafe_load_disk_segment(&new_paths).await?;
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, Distance::Cosine, None, false, 4);
         let merged_dir = tempfile::tempdir()?;
         let VectorDiskSegmentValues { paths, .. } =
             merge_disk_segments_tmpdir(vec![&initial_segment, &new_segment], &merged_dir, config)?;
@@ -1388,6 +1520,146 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn merge_segments_with_quantization_loads_quantized_vectors() -> anyhow::Result<()> {
+        let vectors: Vec<_> = stream_vectors(10).collect();
+
+        let initial_dir = tempfile::tempdir()?;
+        let initial_paths =
+            create_test_disk_segment(DIMENSIONS, &initial_dir, vectors.into_iter())?;
+        let initial_segment = unsafe_load_disk_segment(&initial_paths).await?;
+
+        let quantization = VectorQuantization {
+            quantization_type: VectorQuantizationType::Scalar,
+            rescore: true,
+        };
+        let config = segment_config(
+            DIMENSIONS,
+            Distance::Cosine,
+            Some(qdrant_quantization_config(quantization)),
+            false,
+            4,
+        );
+        let merged_dir = tempfile::tempdir()?;
+        let VectorDiskSegmentValues { paths, .. } =
+            merge_disk_segments_tmpdir(vec![&initial_segment], &merged_dir, config)?;
+        let merged_segment = unsafe_load_disk_segment(&paths).await?;
+        assert!(merged_segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .borrow()
+            .is_some());
+        // Segments built without quantization don't load any.
+        assert!(initial_segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .borrow()
+            .is_none());
+        Ok(())
+    }
+
+    fn merge_disk_segments_hnsw_tmpdir(
+        segments: Vec<&Segment>,
+        tmp_dir: &TempDir,
+    ) -> anyhow::Result<VectorDiskSegmentValues> {
+        let indexing_path = tmp_dir.path().join("indexing");
+        fs::create_dir_all(&indexing_path)?;
+        let disk_path = tmp_dir.path().join("disk");
+        fs::create_dir_all(&disk_path)?;
+        merge_disk_segments_hnsw(
+            segments.into_iter().map(|s| (None, s)).collect(),
+            DIMENSIONS,
+            &indexing_path,
+            &disk_path,
+        )
+    }
+
+    #[tokio::test]
+    async fn merge_segments_hnsw_keeps_quantization() -> anyhow::Result<()> {
+        let quantization = VectorQuantization {
+            quantization_type: VectorQuantizationType::Scalar,
+            rescore: true,
+        };
+        let mut segments = vec![];
+        let mut dirs = vec![];
+        let mut vectors = vec![];
+        for _ in 0..2 {
+            let dir = tempfile::tempdir()?;
+            let segment_vectors: Vec<_> = stream_vectors(10).collect();
+            let paths = create_test_quantized_disk_segment(
+                DIMENSIONS,
+                &dir,
+                segment_vectors.clone().into_iter(),
+                Some(quantization),
+            )?;
+            segments.push(unsafe_load_disk_segment(&paths).await?);
+            vectors.extend(segment_vectors);
+            dirs.push(dir);
+        }
+
+        let merged_dir = tempfile::tempdir()?;
+        let VectorDiskSegmentValues { paths, .. } =
+            merge_disk_segments_hnsw_tmpdir(segments.iter().collect(), &merged_dir)?;
+        let merged_segment = unsafe_load_disk_segment(&paths).await?;
+        let vector_data_config = &merged_segment.segment_config.vector_data[DEFAULT_VECTOR_NAME];
+        assert_eq!(
+            vector_data_config.quantization_config,
+            Some(qdrant_quantization_config(quantization))
+        );
+        assert!(merged_segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .borrow()
+            .is_some());
+        // Every vector is still its own nearest neighbor.
+        for (point_id, vector) in vectors {
+            let results = search(&merged_segment, vector)?;
+            assert_eq!(results.first(), Some(&point_id));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn merge_segments_hnsw_with_different_quantization_fails() -> anyhow::Result<()> {
+        let scalar = VectorQuantization {
+            quantization_type: VectorQuantizationType::Scalar,
+            rescore: true,
+        };
+        let binary = VectorQuantization {
+            quantization_type: VectorQuantizationType::Binary,
+            rescore: true,
+        };
+        for (first, second) in [
+            (None, Some(scalar)),
+            (Some(scalar), None),
+            (Some(scalar), Some(binary)),
+        ] {
+            let first_dir = tempfile::tempdir()?;
+            let first_paths = create_test_quantized_disk_segment(
+                DIMENSIONS,
+                &first_dir,
+                stream_vectors(5),
+                first,
+            )?;
+            let first_segment = unsafe_load_disk_segment(&first_paths).await?;
+            let second_dir = tempfile::tempdir()?;
+            let second_paths = create_test_quantized_disk_segment(
+                DIMENSIONS,
+                &second_dir,
+                stream_vectors(5),
+                second,
+            )?;
+            let second_segment = unsafe_load_disk_segment(&second_paths).await?;
+
+            let merged_dir = tempfile::tempdir()?;
+            let err =
+                merge_disk_segments_hnsw_tmpdir(vec![&first_segment, &second_segment], &merged_dir)
+                    .unwrap_err();
+            assert!(
+                err.to_string().contains("different quantization configs"),
+                "{err:?}"
+            );
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn merge_segments_with_same_vector_where_one_copy_is_deleted_includes_the_vector(
     ) -> anyhow::Result<()> {
@@ -1414,7 +1686,7 @@ mod tests {
         let new_paths = create_test_disk_segment(DIMENSIONS, &new_dir, vector.clone().into_iter())?;
         let new_segment = unsafe_load_disk_segment(&new_paths).await?;
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, Distance::Cosine, None, false, 4);
         let merged_dir = tempfile::tempdir()?;
         let VectorDiskSegmentValues {
             paths: merged_paths,
@@ -1453,7 +1725,7 @@ mod tests {
             .map(|(segment, ..)| segment)
             .collect();
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, Distance::Cosine, None, false, 4);
         let merged_dir = tempfile::tempdir()?;
         let VectorDiskSegmentValues {
             paths: merged_paths,
@@ -1496,7 +1768,7 @@ mod tests {
             create_test_disk_segment(DIMENSIONS, &other_dir, other_vectors.clone().into_iter())?;
         let other_segment = unsafe_load_disk_segment(&other_paths).await?;
 
-        let config = segment_config(DIMENSIONS, false, 4);
+        let config = segment_config(DIMENSIONS, Distance::Cosine, None, false, 4);
         let merged_dir = tempfile::tempdir()?;
         let VectorDiskSegmentValues {
             paths: merged_paths,
@@ -5,19 +5,34 @@ use std::{
         BTreeSet,
     },
     fmt::{
+        self,
         Debug,
         Formatter,
     },
+    str::FromStr,
 };
 
 use common::{
+    bootstrap_model::index::vector_index::VectorDistanceMetric,
     components::ComponentId,
+    document::PackedDocument,
+    document_index_keys::{
+        DocumentIndexKeyValue,
+        DocumentIndexKeys,
+        VectorIndexKeyValue,
+    },
     json::JsonExpression,
-    query::Expression,
+    query::{
+        Expression,
+        FilterValue,
+    },
     types::{
         GenericIndexName,
         IndexName,
         MaybeValue,
+        SubscriberId,
+        TabletIndexName,
+        Timestamp,
         WriteTimestamp,
     },
 };
@@ -34,11 +49,16 @@ use serde_json::{
     Value as JsonValue,
 };
 use value::{
+    heap_size::{
+        HeapSize,
+        WithHeapSize,
+    },
     id_v6::DeveloperDocumentId,
     ConvexValue,
     FieldPath,
     InternalId,
     NamespacedTableMapping,
+    ResolvedDocumentId,
     Size,
     TableName,
     TableNamespace,
@@ -46,7 +66,15 @@ use value::{
     TabletId,
 };
 
-use crate::IndexedVector;
+use crate::{
+    qdrant_index::{
+        internal_vector_score,
+        max_vector_similarity,
+        public_vector_score,
+    },
+    IndexedVector,
+    DEFAULT_VECTOR_LIMIT,
+};
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -61,6 +89,99 @@ pub struct VectorSearch {
     pub limit: Option<u32>,
     pub vector: Vec<f32>,
     pub expressions: BTreeSet<VectorSearchExpression>,
+    /// Continue a previous search from where its last page ended.
+    pub cursor: Option<VectorSearchCursor>,
+    /// Only return results scoring at least this well. For Euclidean distance
+    /// this is a maximum distance.
+    pub score_threshold: Option<f32>,
+}
+
+/// The position of the last result returned by a page of vector search.
+///
+/// Every page of a paginated search is read at the timestamp of the first
+/// page, so results are neither skipped nor repeated as the table changes.
+/// `score` is the internal, higher-is-better score of the last result.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+pub struct VectorSearchCursor {
+    pub ts: Timestamp,
+    /// The number of results returned by previous pages.
+    pub offset: u32,
+    #[cfg_attr(any(test, feature = "testing"), proptest(strategy = "-1e6f32..1e6f32"))]
+    pub score: f32,
+    pub id: InternalId,
+}
+
+impl VectorSearchCursor {
+    pub fn after(ts: Timestamp, offset: u32, last: &VectorSearchQueryResult) -> Self {
+        Self {
+            ts,
+            offset,
+            score: last.score,
+            id: last.id,
+        }
+    }
+
+    /// Whether `result` sorts after this cursor in (descending score, id)
+    /// order and should therefore be on a later page.
+    pub fn precedes(&self, result: &VectorSearchQueryResult) -> bool {
+        result
+            .score
+            .total_cmp(&self.score)
+            .then(result.id.cmp(&self.id))
+            .is_lt()
+    }
+}
+
+impl fmt::Display for VectorSearchCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{:08x}:{}",
+            u64::from(self.ts),
+            self.offset,
+            self.score.to_bits(),
+            self.id,
+        )
+    }
+}
+
+impl FromStr for VectorSearchCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            ErrorMetadata::bad_request(
+                "InvalidVectorSearchCursor",
+                "Vector search cursor is invalid. Pass the `continueCursor` returned by a \
+                 previous page.",
+            )
+        };
+        let parts: Vec<_> = s.split(':').collect();
+        let [ts, offset, score, id] = parts[..] else {
+            anyhow::bail!(invalid());
+        };
+        let cursor: anyhow::Result<Self> = try {
+            Self {
+                ts: Timestamp::try_from(ts.parse::<u64>()?)?,
+                offset: offset.parse()?,
+                score: f32::from_bits(u32::from_str_radix(score, 16)?),
+                id: id.parse()?,
+            }
+        };
+        let cursor = cursor.map_err(|e| e.context(invalid()))?;
+        // Scores of real results are always finite.
+        anyhow::ensure!(cursor.score.is_finite(), invalid());
+        Ok(cursor)
+    }
+}
+
+/// A page of vector search results along with the cursor for the next page,
+/// which is `None` once the search is exhausted.
+#[derive(Clone, Debug)]
+pub struct VectorSearchPage {
+    pub results: Vec<PublicVectorSearchQueryResult>,
+    pub continue_cursor: Option<VectorSearchCursor>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -82,6 +203,8 @@ impl Arbitrary for VectorSearch {
             any::<ComponentId>(),
             any::<Option<u32>>(),
             any::<Vec<f32>>(),
+            any::<Option<VectorSearchCursor>>(),
+            any::<Option<f32>>(),
             // There's an invariant that there's at most one `VectorSearchExpression` for a given
             // field. To ensure this, generate a map from FieldPath to filtered values
             // and construct the `VectorSearchExpression` from that.
@@ -91,15 +214,19 @@ impl Arbitrary for VectorSearch {
                 1..5,
             ),
         )
-            .prop_map(|(index_name, component_id, limit, vector, field_map)| {
-                VectorSearch {
-                    index_name,
-                    component_id,
-                    limit,
-                    vector,
-                    expressions: VectorSearchExpression::from_field_map(field_map),
-                }
-            })
+            .prop_map(
+                |(index_name, component_id, limit, vector, cursor, score_threshold, field_map)| {
+                    VectorSearch {
+                        index_name,
+                        component_id,
+                        limit,
+                        vector,
+                        expressions: VectorSearchExpression::from_field_map(field_map),
+                        cursor,
+                        score_threshold,
+                    }
+                },
+            )
     }
 }
 
@@ -190,7 +317,7 @@ impl VectorSearchExpression {
         }
     }
 
-    fn from_expression(expression: Expression) -> anyhow::Result<BTreeSet<Self>> {
+    pub(crate) fn from_expression(expression: Expression) -> anyhow::Result<BTreeSet<Self>> {
         let field_map = Self::assemble_filter_map(expression)?;
         Ok(Self::from_field_map(field_map))
     }
@@ -246,6 +373,8 @@ pub struct VectorSearchJson {
     limit: Option<u32>,
     vector: Vec<f32>,
     expressions: Option<JsonExpression>,
+    cursor: Option<String>,
+    score_threshold: Option<f32>,
 }
 
 impl VectorSearchJson {
@@ -277,6 +406,14 @@ impl TryFrom<JsonValue> for VectorSearch {
 
     fn try_from(value: JsonValue) -> Result<Self, Self::Error> {
         let search: VectorSearchJson = serde_json::from_value(value)?;
+        search.try_into()
+    }
+}
+
+impl TryFrom<VectorSearchJson> for VectorSearch {
+    type Error = anyhow::Error;
+
+    fn try_from(search: VectorSearchJson) -> Result<Self, Self::Error> {
         let index_name: GenericIndexName<TableName> = search.index_name.parse()?;
         let component_id = ComponentId::deserialize_from_string(search.component_id.as_deref())?;
         let expressions = search
@@ -292,6 +429,8 @@ impl TryFrom<JsonValue> for VectorSearch {
             expressions,
             limit: search.limit,
             vector: search.vector,
+            cursor: search.cursor.map(|c| c.parse()).transpose()?,
+            score_threshold: search.score_threshold,
         };
         Ok(result)
     }
@@ -314,6 +453,8 @@ impl TryFrom<VectorSearch> for JsonValue {
             expressions: expression_json,
             limit: value.limit,
             vector: value.vector,
+            cursor: value.cursor.map(|c| c.to_string()),
+            score_threshold: value.score_threshold,
         };
         Ok(serde_json::to_value(search)?)
     }
@@ -382,17 +523,22 @@ impl VectorSearch {
             vector: self.vector,
             limit: self.limit,
             expressions: self.expressions.into_iter().collect(),
+            cursor: self.cursor,
+            score_threshold: self.score_threshold,
             original_table_name,
         };
         Ok(result)
     }
 }
 
+#[derive(Clone)]
 pub struct InternalVectorSearch {
     pub index_name: GenericIndexName<TabletId>,
     pub limit: Option<u32>,
     pub vector: Vec<f32>,
     pub expressions: Vec<VectorSearchExpression>,
+    pub cursor: Option<VectorSearchCursor>,
+    pub score_threshold: Option<f32>,
     pub original_table_name: TableName,
 }
 
@@ -462,10 +608,14 @@ impl PartialEq for VectorSearchQueryResult {
 }
 
 impl VectorSearchQueryResult {
-    pub fn to_public(self, table_number: TableNumber) -> PublicVectorSearchQueryResult {
+    pub fn to_public(
+        self,
+        table_number: TableNumber,
+        distance_metric: VectorDistanceMetric,
+    ) -> PublicVectorSearchQueryResult {
         PublicVectorSearchQueryResult {
             id: DeveloperDocumentId::new(table_number, self.id),
-            score: self.score,
+            score: public_vector_score(distance_metric, self.score),
         }
     }
 }
@@ -609,6 +759,17 @@ impl From<PublicVectorSearchQueryResult> for JsonValue {
     }
 }
 
+impl From<VectorSearchPage> for JsonValue {
+    fn from(page: VectorSearchPage) -> Self {
+        let results: Vec<_> = page.results.into_iter().map(JsonValue::from).collect();
+        json!({
+            "results": results,
+            "continueCursor": page.continue_cursor.map(|c| c.to_string()),
+            "isDone": page.continue_cursor.is_none(),
+        })
+    }
+}
+
 impl Ord for PublicVectorSearchQueryResult {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
         self.score
@@ -623,13 +784,294 @@ impl PartialOrd for PublicVectorSearchQueryResult {
     }
 }
 
+/// Slack when comparing a written document's score against the K-th score of
+/// a search. Erring towards an overlap only costs an extra rerun, while float
+/// differences between the index and this check must not hide a real change.
+const SCORE_TOLERANCE: f32 = 1e-5;
+
+/// The read dependency of a single vector search: a write overlaps it if the
+/// written document could enter (or leave) the top-K results.
+#[derive(Clone, Debug)]
+pub struct VectorQueryRead {
+    pub vector_field: FieldPath,
+    pub distance_metric: VectorDistanceMetric,
+    pub vector: Box<[f32]>,
+    /// A document must match at least one of these to be in the results. An
+    /// empty list matches every document.
+    pub filter_conditions: Vec<(FieldPath, FilterValue)>,
+    /// The lowest score in the results if the search returned its full limit,
+    /// otherwise the query's score threshold. `None` if neither applies, in
+    /// which case any matching document with a vector overlaps.
+    pub min_score: Option<f32>,
+}
+
+impl VectorQueryRead {
+    pub fn new(
+        vector_field: FieldPath,
+        distance_metric: VectorDistanceMetric,
+        query: &InternalVectorSearch,
+        results: &[VectorSearchQueryResult],
+    ) -> Self {
+        let filter_conditions = query
+            .expressions
+            .iter()
+            .flat_map(|expression| match expression {
+                VectorSearchExpression::Eq(field_path, value) => {
+                    vec![(
+                        field_path.clone(),
+                        FilterValue::from_search_value(value.as_ref()),
+                    )]
+                },
+                VectorSearchExpression::In(field_path, values) => values
+                    .iter()
+                    .map(|value| {
+                        (
+                            field_path.clone(),
+                            FilterValue::from_search_value(value.as_ref()),
+                        )
+                    })
+                    .collect(),
+            })
+            .collect();
+        let limit = query.limit.unwrap_or(DEFAULT_VECTOR_LIMIT) as usize;
+        // Documents scoring below the threshold can't enter the results even
+        // if the search returned fewer than its limit.
+        let min_score = (results.len() >= limit)
+            .then(|| results.iter().map(|r| r.score).min_by(f32::total_cmp))
+            .flatten()
+            .or_else(|| {
+                query
+                    .score_threshold
+                    .map(|t| internal_vector_score(distance_metric, t))
+            });
+        Self {
+            vector_field,
+            distance_metric,
+            vector: query.vector.clone().into(),
+            filter_conditions,
+            min_score,
+        }
+    }
+
+    /// `vector` may be an approximation, within Euclidean distance `radius`
+    /// of the document's vector, in which case this errs towards overlapping.
+    fn overlaps(
+        &self,
+        filter_value: impl Fn(&FieldPath) -> Option<FilterValue>,
+        vector: Option<&[f32]>,
+        radius: f32,
+    ) -> bool {
+        // Documents without a vector of the right dimensions aren't indexed.
+        let Some(vector) = vector else {
+            return false;
+        };
+        if vector.len() != self.vector.len() {
+            return false;
+        }
+        if !self.filter_conditions.is_empty()
+            && !self
+                .filter_conditions
+                .iter()
+                .any(|(field_path, value)| filter_value(field_path).as_ref() == Some(value))
+        {
+            return false;
+        }
+        match self.min_score {
+            None => true,
+            Some(min_score) => {
+                max_vector_similarity(self.distance_metric, &self.vector, vector, radius)
+                    >= min_score - SCORE_TOLERANCE
+            },
+        }
+    }
+
+    pub fn overlaps_document(&self, document: &PackedDocument) -> bool {
+        let vector = match document.value().get_path(&self.vector_field) {
+            Some(ConvexValue::Array(array)) => array
+                .iter()
+                .map(|value| match value {
+                    ConvexValue::Float64(f) => Some(*f as f32),
+                    _ => None,
+                })
+                .collect::<Option<Vec<f32>>>(),
+            _ => None,
+        };
+        self.overlaps(
+            |field_path| {
+                Some(FilterValue::from_search_value(
+                    document.value().get_path(field_path).as_ref(),
+                ))
+            },
+            vector.as_deref(),
+            0.,
+        )
+    }
+
+    pub fn overlaps_vector_index_key_value(&self, index_key_value: &VectorIndexKeyValue) -> bool {
+        let vector = index_key_value.vector.as_ref();
+        self.overlaps(
+            |field_path| index_key_value.filter_values.get(field_path).cloned(),
+            vector.map(|v| v.to_vec()).as_deref(),
+            vector.map_or(0., |v| v.max_error()),
+        )
+    }
+}
+
+impl PartialEq for VectorQueryRead {
+    fn eq(&self, other: &Self) -> bool {
+        self.vector_field == other.vector_field
+            && self.distance_metric == other.distance_metric
+            && self.vector.len() == other.vector.len()
+            && self
+                .vector
+                .iter()
+                .zip(other.vector.iter())
+                .all(|(a, b)| a.total_cmp(b).is_eq())
+            && self.filter_conditions == other.filter_conditions
+            && match (self.min_score, other.min_score) {
+                (Some(a), Some(b)) => a.total_cmp(&b).is_eq(),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+impl Eq for VectorQueryRead {}
+
+impl HeapSize for VectorQueryRead {
+    fn heap_size(&self) -> usize {
+        self.vector_field.heap_size()
+            + std::mem::size_of_val::<[f32]>(&self.vector)
+            + self
+                .filter_conditions
+                .iter()
+                .map(|(field_path, value)| {
+                    std::mem::size_of::<(FieldPath, FilterValue)>()
+                        + field_path.heap_size()
+                        + value.heap_size()
+                })
+                .sum::<usize>()
+    }
+}
+
+/// All of the vector searches a transaction ran against one index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VectorQueryReads {
+    pub queries: WithHeapSize<Vec<VectorQueryRead>>,
+}
+
+impl VectorQueryReads {
+    pub fn empty() -> Self {
+        Self {
+            queries: WithHeapSize::default(),
+        }
+    }
+
+    pub fn new(query: VectorQueryRead) -> Self {
+        Self {
+            queries: vec![query].into(),
+        }
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.queries.extend(other.queries);
+    }
+
+    pub fn overlaps_document(&self, document: &PackedDocument) -> bool {
+        self.queries
+            .iter()
+            .any(|query| query.overlaps_document(document))
+    }
+
+    pub fn overlaps_vector_index_key_value(&self, index_key_value: &VectorIndexKeyValue) -> bool {
+        self.queries
+            .iter()
+            .any(|query| query.overlaps_vector_index_key_value(index_key_value))
+    }
+}
+
+impl HeapSize for VectorQueryReads {
+    fn heap_size(&self) -> usize {
+        self.queries.heap_size()
+    }
+}
+
+/// Vector search reads of every subscription, by index.
+///
+/// Unlike text search there's no inverted structure to look up matching
+/// subscriptions, so each write is scored against every vector subscription
+/// on its table.
+pub struct VectorSearchSubscriptions {
+    queries: BTreeMap<TabletIndexName, BTreeMap<SubscriberId, Vec<VectorQueryRead>>>,
+}
+
+impl VectorSearchSubscriptions {
+    pub fn new() -> Self {
+        Self {
+            queries: BTreeMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queries.values().map(|m| m.len()).sum()
+    }
+
+    pub fn insert(&mut self, id: SubscriberId, index: &TabletIndexName, reads: &VectorQueryReads) {
+        self.queries
+            .entry(index.clone())
+            .or_default()
+            .entry(id)
+            .or_default()
+            .extend(reads.queries.iter().cloned());
+    }
+
+    pub fn remove(&mut self, id: SubscriberId, index: &TabletIndexName) {
+        let queries = self
+            .queries
+            .get_mut(index)
+            .unwrap_or_else(|| panic!("Missing vector query index entry for {}", index));
+        assert!(queries.remove(&id).is_some());
+        if queries.is_empty() {
+            self.queries.remove(index);
+        }
+    }
+
+    pub fn add_matches(
+        &self,
+        document_id: &ResolvedDocumentId,
+        document_index_keys: &DocumentIndexKeys,
+        notify: &mut impl FnMut(SubscriberId),
+    ) {
+        for (index, queries) in &self.queries {
+            if *index.table() != document_id.tablet_id {
+                continue;
+            }
+            let Some(DocumentIndexKeyValue::Vector(index_key_value)) =
+                document_index_keys.get(index)
+            else {
+                continue;
+            };
+            for (subscriber_id, queries) in queries {
+                if queries
+                    .iter()
+                    .any(|query| query.overlaps_vector_index_key_value(index_key_value))
+                {
+                    notify(*subscriber_id);
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use cmd_util::env::env_config;
+    use common::document_index_keys::CompactVector;
     use proptest::prelude::*;
     use value::testing::assert_roundtrips;
 
     use super::*;
+    use crate::qdrant_index::vector_similarity;
 
     proptest! {
         #![proptest_config(
@@ -649,5 +1091,129 @@ mod tests {
         ) {
             assert_roundtrips::<VectorSearchQueryResult, proto::VectorQueryResult>(result)
         }
+
+        #[test]
+        fn test_vector_search_cursor_roundtrips(cursor in any::<VectorSearchCursor>()) {
+            prop_assert_eq!(cursor.to_string().parse::<VectorSearchCursor>().unwrap(), cursor);
+        }
+    }
+
+    #[test]
+    fn test_vector_search_cursor_precedes() {
+        let id = |b: u8| InternalId::from([b; 16]);
+        let result = |score: f32, b: u8| VectorSearchQueryResult {
+            score,
+            id: id(b),
+            ts: WriteTimestamp::Pending,
+        };
+        let cursor = VectorSearchCursor::after(Timestamp::MIN, 2, &result(0.5, 1));
+        assert!(cursor.precedes(&result(0.4, 9)));
+        // Ties on score are broken by id, matching the order results are sorted in.
+        assert!(cursor.precedes(&result(0.5, 0)));
+        assert!(!cursor.precedes(&result(0.5, 1)));
+        assert!(!cursor.precedes(&result(0.5, 2)));
+        assert!(!cursor.precedes(&result(0.6, 0)));
+
+        assert!("not a cursor".parse::<VectorSearchCursor>().is_err());
+        let nan_cursor = VectorSearchCursor {
+            score: f32::NAN,
+            ..cursor
+        };
+        assert!(nan_cursor
+            .to_string()
+            .parse::<VectorSearchCursor>()
+            .is_err());
+    }
+
+    fn query_read(
+        min_score: Option<f32>,
+        filter_conditions: Vec<(FieldPath, FilterValue)>,
+    ) -> VectorQueryRead {
+        VectorQueryRead {
+            vector_field: "embedding".parse().unwrap(),
+            distance_metric: VectorDistanceMetric::Cosine,
+            vector: vec![1.0, 0.0].into(),
+            filter_conditions,
+            min_score,
+        }
+    }
+
+    fn key_value(vector: Option<Vec<f32>>, author: &str) -> VectorIndexKeyValue {
+        let author_value = ConvexValue::try_from(author.to_string()).unwrap();
+        VectorIndexKeyValue {
+            filter_values: BTreeMap::from([(
+                "author".parse().unwrap(),
+                FilterValue::from_search_value(Some(&author_value)),
+            )])
+            .into(),
+            vector: vector.map(|v| CompactVector::new(&v)),
+        }
+    }
+
+    #[test]
+    fn test_vector_query_read_min_score() {
+        let read = query_read(Some(0.9), vec![]);
+        assert!(read.overlaps_vector_index_key_value(&key_value(Some(vec![1.0, 0.1]), "a")));
+        assert!(!read.overlaps_vector_index_key_value(&key_value(Some(vec![0.0, 1.0]), "a")));
+        assert!(!read.overlaps_vector_index_key_value(&key_value(None, "a")));
+        // Vectors with other dimensions are never in the index.
+        assert!(!read.overlaps_vector_index_key_value(&key_value(Some(vec![1.0, 0.0, 0.0]), "a")));
+
+        // Searches that returned fewer than their limit overlap any new vector.
+        let read = query_read(None, vec![]);
+        assert!(read.overlaps_vector_index_key_value(&key_value(Some(vec![0.0, 1.0]), "a")));
+    }
+
+    #[test]
+    fn test_vector_query_read_filters() {
+        let author = ConvexValue::try_from("a".to_string()).unwrap();
+        let read = query_read(
+            None,
+            vec![(
+                "author".parse().unwrap(),
+                FilterValue::from_search_value(Some(&author)),
+            )],
+        );
+        assert!(read.overlaps_vector_index_key_value(&key_value(Some(vec![1.0, 0.0]), "a")));
+        assert!(!read.overlaps_vector_index_key_value(&key_value(Some(vec![1.0, 0.0]), "b")));
+    }
+
+    #[test]
+    fn test_vector_query_read_euclidean() {
+        // Euclidean scores are negated squared distances internally, so a
+        // result at distance 0.5 has score -0.25.
+        let read = VectorQueryRead {
+            distance_metric: VectorDistanceMetric::Euclidean,
+            ..query_read(Some(-0.25), vec![])
+        };
+        assert!(read.overlaps_vector_index_key_value(&key_value(Some(vec![1.3, 0.0]), "a")));
+        assert!(!read.overlaps_vector_index_key_value(&key_value(Some(vec![2.0, 0.0]), "a")));
+        // Cosine similarity would consider this vector identical to the query.
+        assert!(!read.overlaps_vector_index_key_value(&key_value(Some(vec![10.0, 0.0]), "a")));
+    }
+
+    #[test]
+    fn test_vector_query_read_compact_vector_is_conservative() {
+        // The stored vector is rounded, so a document just above the K-th
+        // score must still overlap even if its rounded vector scores lower.
+        let vector = vec![1.0, 0.305, 0.0];
+        let exact = vector_similarity(VectorDistanceMetric::Cosine, &[1.0, 0.0, 0.0], &vector);
+        let read = VectorQueryRead {
+            vector: vec![1.0, 0.0, 0.0].into(),
+            ..query_read(Some(exact), vec![])
+        };
+        assert!(read.overlaps_vector_index_key_value(&key_value(Some(vector), "a")));
+        assert!(!read.overlaps_vector_index_key_value(&key_value(Some(vec![0.0, 1.0, 0.0]), "a")));
+    }
+
+    #[test]
+    fn test_public_euclidean_score_is_distance() {
+        let result = VectorSearchQueryResult {
+            score: -0.25,
+            id: InternalId::MIN,
+            ts: WriteTimestamp::Pending,
+        };
+        let public = result.to_public(TableNumber::MIN, VectorDistanceMetric::Euclidean);
+        assert_eq!(public.score, 0.5);
     }
 }
@@ -10,6 +10,10 @@ use crate::{
         CompiledVectorSearch,
         VectorSearchQueryResult,
     },
+    sparse_index::{
+        CompiledSparseVectorSearch,
+        SparseVectorSchema,
+    },
 };
 
 #[async_trait]
@@ -29,4 +33,19 @@ pub trait VectorSearcher: Send + Sync + 'static {
         segments: Vec<pb::searchlight::FragmentedVectorSegmentPaths>,
         dimension: usize,
     ) -> anyhow::Result<FragmentedVectorSegment>;
+
+    async fn execute_multi_segment_sparse_vector_query(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        segments: Vec<pb::searchlight::FragmentedVectorSegmentPaths>,
+        schema: SparseVectorSchema,
+        search: CompiledSparseVectorSearch,
+        overfetch_delta: u32,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>>;
+
+    async fn execute_sparse_vector_compaction(
+        &self,
+        search_storage: Arc<dyn Storage>,
+        segments: Vec<pb::searchlight::FragmentedVectorSegmentPaths>,
+    ) -> anyhow::Result<FragmentedVectorSegment>;
 }
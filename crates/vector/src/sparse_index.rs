@@ -0,0 +1,476 @@
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    mem,
+    path::Path,
+};
+
+use common::{
+    bootstrap_model::index::sparse_vector_index::{
+        DeveloperSparseVectorIndexConfig,
+        MAX_SPARSE_VECTOR_LENGTH,
+    },
+    components::ComponentId,
+    document::ResolvedDocument,
+    json::JsonExpression,
+    persistence::DocumentStream,
+    query::{
+        search_value_to_bytes,
+        Expression,
+    },
+    types::{
+        GenericIndexName,
+        IndexName,
+    },
+};
+use errors::ErrorMetadata;
+use futures::TryStreamExt;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::Value as JsonValue;
+use value::{
+    ConvexValue,
+    FieldPath,
+    InternalId,
+    NamespacedTableMapping,
+    TableName,
+    TableNamespace,
+    TabletId,
+};
+
+use crate::{
+    qdrant_index::{
+        compile_vector_filters,
+        PreviousVectorSegmentsHack,
+        QdrantExternalId,
+    },
+    qdrant_segments::VectorDiskSegmentValues,
+    query::{
+        CompiledVectorFilter,
+        VectorSearchExpression,
+    },
+    sparse_segment::{
+        write_sparse_segment,
+        SparsePoint,
+    },
+    DEFAULT_VECTOR_LIMIT,
+    MAX_VECTOR_RESULTS,
+};
+
+/// A sparse vector, stored as its nonzero entries sorted by dimension index.
+///
+/// Documents store sparse vectors as objects mapping the decimal dimension
+/// index to a `float64` weight, e.g. `{ "3": 0.5, "1024": 1.25 }`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SparseVector {
+    indices: Vec<u32>,
+    values: Vec<f32>,
+}
+
+impl SparseVector {
+    pub fn new(mut entries: Vec<(u32, f32)>) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            entries.len() <= MAX_SPARSE_VECTOR_LENGTH,
+            invalid_sparse_vector_error(format!(
+                "Sparse vectors can have at most {MAX_SPARSE_VECTOR_LENGTH} entries, received {}.",
+                entries.len()
+            ))
+        );
+        entries.sort_by_key(|(index, _)| *index);
+        for window in entries.windows(2) {
+            anyhow::ensure!(
+                window[0].0 != window[1].0,
+                invalid_sparse_vector_error(format!(
+                    "Sparse vector has multiple entries for index {}.",
+                    window[0].0
+                ))
+            );
+        }
+        for (index, value) in &entries {
+            anyhow::ensure!(
+                value.is_finite(),
+                invalid_sparse_vector_error(format!(
+                    "Sparse vector has a non-finite weight at index {index}."
+                ))
+            );
+        }
+        // Explicit zeros never contribute to a dot product, so don't index them.
+        entries.retain(|(_, value)| *value != 0.0);
+        let (indices, values) = entries.into_iter().unzip();
+        Ok(Self { indices, values })
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u32, f32)> + '_ {
+        self.indices
+            .iter()
+            .copied()
+            .zip(self.values.iter().copied())
+    }
+
+    /// The dot product of two sparse vectors, or `None` if they don't share
+    /// any nonzero dimensions. Inverted segments only ever see vectors that
+    /// share a dimension with the query, so the memory index uses this to
+    /// return the same set of results.
+    pub fn overlapping_dot(&self, other: &SparseVector) -> Option<f32> {
+        let (mut i, mut j) = (0, 0);
+        let mut result = None;
+        while i < self.indices.len() && j < other.indices.len() {
+            match self.indices[i].cmp(&other.indices[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    *result.get_or_insert(0.0) += self.values[i] * other.values[j];
+                    i += 1;
+                    j += 1;
+                },
+            }
+        }
+        result
+    }
+
+    pub fn size(&self) -> usize {
+        self.len() * (mem::size_of::<u32>() + mem::size_of::<f32>())
+    }
+}
+
+impl TryFrom<&ConvexValue> for SparseVector {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &ConvexValue) -> anyhow::Result<Self> {
+        let ConvexValue::Object(ref object) = value else {
+            anyhow::bail!(invalid_sparse_vector_error(
+                "Sparse vectors must be objects mapping dimension indexes to weights.".to_string()
+            ));
+        };
+        let entries = object
+            .iter()
+            .map(|(field, value)| {
+                let index: u32 = field.parse().map_err(|_| {
+                    invalid_sparse_vector_error(format!(
+                        "Sparse vector has invalid index {:?}. Indexes must be nonnegative 32-bit \
+                         integers.",
+                        &field[..]
+                    ))
+                })?;
+                let ConvexValue::Float64(weight) = value else {
+                    anyhow::bail!(invalid_sparse_vector_error(format!(
+                        "Sparse vector has a non-float64 weight at index {index}."
+                    )));
+                };
+                Ok((index, *weight as f32))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        SparseVector::new(entries)
+    }
+}
+
+fn invalid_sparse_vector_error(msg: String) -> ErrorMetadata {
+    ErrorMetadata::bad_request("InvalidSparseVector", msg)
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseVectorDocument {
+    pub internal_id: InternalId,
+    pub vector: SparseVector,
+    pub filter_fields: BTreeMap<FieldPath, Vec<u8>>,
+}
+
+impl SparseVectorDocument {
+    pub fn size(&self) -> usize {
+        let mut size = self.vector.size();
+        size += self.filter_fields.len() * mem::size_of::<(FieldPath, Vec<u8>)>();
+        for (field_path, value) in &self.filter_fields {
+            size += field_path.fields().iter().map(|f| f.len()).sum::<usize>();
+            size += value.len();
+        }
+        size
+    }
+
+    pub(crate) fn matches(&self, query: &CompiledSparseVectorSearch) -> bool {
+        matches_filter_conditions(&query.filter_conditions, |field_path| {
+            self.filter_fields.get(field_path).map(|v| &v[..])
+        })
+    }
+}
+
+/// Filters on a vector query match if any of their conditions match, the same
+/// as in dense vector indexes.
+pub(crate) fn matches_filter_conditions<'a>(
+    filter_conditions: &BTreeMap<FieldPath, CompiledVectorFilter>,
+    filter_value: impl Fn(&FieldPath) -> Option<&'a [u8]>,
+) -> bool {
+    if filter_conditions.is_empty() {
+        return true;
+    }
+    filter_conditions
+        .iter()
+        .any(|(field_path, condition)| match filter_value(field_path) {
+            None => false,
+            Some(value) => match condition {
+                CompiledVectorFilter::Eq(term) => term[..] == *value,
+                CompiledVectorFilter::In(terms) => terms.iter().any(|t| t[..] == *value),
+            },
+        })
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseVectorSchema {
+    vector_field: FieldPath,
+    filter_fields: BTreeSet<FieldPath>,
+}
+
+impl SparseVectorSchema {
+    pub fn new(index_config: &DeveloperSparseVectorIndexConfig) -> Self {
+        Self {
+            vector_field: index_config.vector_field.clone(),
+            filter_fields: index_config.filter_fields.clone(),
+        }
+    }
+
+    pub fn index(&self, document: &ResolvedDocument) -> Option<SparseVectorDocument> {
+        let object = document.value();
+        let value = object.get_path(&self.vector_field)?;
+        let vector = match SparseVector::try_from(value) {
+            Ok(vector) => vector,
+            Err(e) => {
+                tracing::debug!("Ignoring invalid sparse vector: {e}");
+                return None;
+            },
+        };
+        if vector.is_empty() {
+            return None;
+        }
+        Some(SparseVectorDocument {
+            internal_id: document.internal_id(),
+            vector,
+            filter_fields: self
+                .filter_fields
+                .iter()
+                .map(|f| (f.clone(), search_value_to_bytes(object.get_path(f))))
+                .collect(),
+        })
+    }
+
+    pub fn estimate_document_size(&self, document: &ResolvedDocument) -> usize {
+        self.index(document).map(|d| d.size()).unwrap_or(0)
+    }
+
+    pub fn compile(
+        &self,
+        query: InternalSparseVectorSearch,
+    ) -> anyhow::Result<CompiledSparseVectorSearch> {
+        let index_name = query.printable_index_name()?;
+        let limit = query.limit.unwrap_or(DEFAULT_VECTOR_LIMIT);
+        anyhow::ensure!(
+            limit as usize <= MAX_VECTOR_RESULTS,
+            ErrorMetadata::bad_request(
+                "VectorLimitTooLargeError",
+                format!(
+                    "Vector queries can fetch at most {} results, requested {}.",
+                    MAX_VECTOR_RESULTS, limit as usize,
+                )
+            )
+        );
+        let filter_conditions =
+            compile_vector_filters(&index_name, &self.filter_fields, query.expressions)?;
+        Ok(CompiledSparseVectorSearch {
+            vector: query.vector,
+            limit,
+            filter_conditions,
+        })
+    }
+
+    /// Builds an inverted segment from `revision_stream`, deleting each
+    /// revision's previous version from `previous_segments`.
+    pub async fn build_disk_index<T: PreviousVectorSegmentsHack>(
+        &self,
+        index_path: &Path,
+        revision_stream: DocumentStream<'_>,
+        previous_segments: &mut T,
+    ) -> anyhow::Result<Option<VectorDiskSegmentValues>> {
+        // Revisions are in timestamp order, so later revisions of a document
+        // replace earlier ones from the same batch.
+        let mut points = BTreeMap::new();
+        futures::pin_mut!(revision_stream);
+        while let Some(entry) = revision_stream.try_next().await? {
+            let internal_id = entry.id.internal_id();
+            match entry.value.as_ref().and_then(|d| self.index(d)) {
+                Some(document) => {
+                    points.insert(
+                        internal_id,
+                        SparsePoint {
+                            internal_id,
+                            ts: entry.ts,
+                            vector: document.vector,
+                            filter_values: document.filter_fields.into_values().collect(),
+                        },
+                    );
+                },
+                None => {
+                    points.remove(&internal_id);
+                },
+            }
+            let point_id = QdrantExternalId::try_from(internal_id)?;
+            previous_segments.maybe_delete_qdrant(*point_id)?;
+        }
+        if points.is_empty() {
+            tracing::debug!("Skipping an empty sparse vector index for {index_path:?}");
+            return Ok(None);
+        }
+        let filter_fields: Vec<_> = self.filter_fields.iter().cloned().collect();
+        let result = write_sparse_segment(index_path, &filter_fields, points.into_values())?;
+        Ok(Some(result))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseVectorSearch {
+    pub index_name: IndexName,
+    pub component_id: ComponentId,
+    pub limit: Option<u32>,
+    pub vector: SparseVector,
+    pub expressions: BTreeSet<VectorSearchExpression>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SparseVectorSearchJson {
+    index_name: String,
+    component_id: Option<String>,
+    limit: Option<u32>,
+    /// An object mapping decimal dimension indexes to weights, in the same
+    /// format as indexed documents.
+    vector: JsonValue,
+    expressions: Option<JsonExpression>,
+}
+
+impl SparseVectorSearchJson {
+    /// See [`crate::VectorSearchJson::insert_component_id`].
+    pub fn insert_component_id(&mut self, component_id: ComponentId) {
+        self.component_id = component_id.serialize_to_string();
+    }
+}
+
+impl TryFrom<JsonValue> for SparseVectorSearch {
+    type Error = anyhow::Error;
+
+    fn try_from(value: JsonValue) -> anyhow::Result<Self> {
+        let search: SparseVectorSearchJson = serde_json::from_value(value)?;
+        let index_name: IndexName = search.index_name.parse()?;
+        let component_id = ComponentId::deserialize_from_string(search.component_id.as_deref())?;
+        let expressions = search
+            .expressions
+            .map_or(anyhow::Ok(BTreeSet::new()), |e| {
+                let expression: Expression = e.try_into()?;
+                VectorSearchExpression::from_expression(expression)
+            })?;
+        let vector = SparseVector::try_from(&ConvexValue::try_from(search.vector)?)?;
+        Ok(Self {
+            index_name,
+            component_id,
+            limit: search.limit,
+            vector,
+            expressions,
+        })
+    }
+}
+
+impl SparseVectorSearch {
+    pub fn resolve(
+        self,
+        table_mapping: &NamespacedTableMapping,
+    ) -> anyhow::Result<InternalSparseVectorSearch> {
+        anyhow::ensure!(
+            table_mapping.namespace() == TableNamespace::from(self.component_id),
+            format!(
+                "Component id {:?} does not match the table namespace {:?}",
+                self.component_id,
+                table_mapping.namespace()
+            )
+        );
+        let original_table_name = self.index_name.table().clone();
+        let index_name = self
+            .index_name
+            .to_resolved(table_mapping.name_to_tablet())?;
+        Ok(InternalSparseVectorSearch {
+            index_name,
+            limit: self.limit,
+            vector: self.vector,
+            expressions: self.expressions.into_iter().collect(),
+            original_table_name,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InternalSparseVectorSearch {
+    pub index_name: GenericIndexName<TabletId>,
+    pub limit: Option<u32>,
+    pub vector: SparseVector,
+    pub expressions: Vec<VectorSearchExpression>,
+    pub original_table_name: TableName,
+}
+
+impl InternalSparseVectorSearch {
+    pub fn printable_index_name(&self) -> anyhow::Result<IndexName> {
+        IndexName::new(
+            self.original_table_name.clone(),
+            self.index_name.descriptor().clone(),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CompiledSparseVectorSearch {
+    pub vector: SparseVector,
+    pub limit: u32,
+    pub filter_conditions: BTreeMap<FieldPath, CompiledVectorFilter>,
+}
+
+#[cfg(test)]
+mod tests {
+    use value::{
+        assert_obj,
+        ConvexValue,
+    };
+
+    use super::SparseVector;
+
+    #[test]
+    fn test_parse_sparse_vector() -> anyhow::Result<()> {
+        let value = ConvexValue::Object(assert_obj!("7" => 1.5, "2" => 0.5, "9" => 0.0));
+        let vector = SparseVector::try_from(&value)?;
+        assert_eq!(vector.iter().collect::<Vec<_>>(), vec![(2, 0.5), (7, 1.5)]);
+
+        let bad_index = ConvexValue::Object(assert_obj!("-1" => 1.0));
+        assert!(SparseVector::try_from(&bad_index).is_err());
+        let bad_weight = ConvexValue::Object(assert_obj!("1" => "heavy"));
+        assert!(SparseVector::try_from(&bad_weight).is_err());
+        assert!(SparseVector::try_from(&ConvexValue::Float64(1.0)).is_err());
+        assert!(SparseVector::new(vec![(1, f32::NAN)]).is_err());
+        assert!(SparseVector::new(vec![(1, 1.0), (1, 2.0)]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_overlapping_dot() -> anyhow::Result<()> {
+        let a = SparseVector::new(vec![(1, 2.0), (3, 1.0), (8, -1.0)])?;
+        let b = SparseVector::new(vec![(3, 4.0), (8, 2.0), (10, 7.0)])?;
+        assert_eq!(a.overlapping_dot(&b), Some(2.0));
+        let c = SparseVector::new(vec![(2, 1.0)])?;
+        assert_eq!(a.overlapping_dot(&c), None);
+        Ok(())
+    }
+}
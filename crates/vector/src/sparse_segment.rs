@@ -0,0 +1,437 @@
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    fs::File,
+    io::{
+        BufReader,
+        BufWriter,
+        Read,
+        Write,
+    },
+    path::Path,
+};
+
+use byteorder::{
+    LittleEndian,
+    ReadBytesExt,
+    WriteBytesExt,
+};
+use common::{
+    deleted_bitset::DeletedBitset,
+    id_tracker::{
+        MemoryIdTracker,
+        StaticIdTracker,
+    },
+    types::{
+        Timestamp,
+        WriteTimestamp,
+    },
+};
+use value::{
+    FieldPath,
+    InternalId,
+};
+
+use crate::{
+    qdrant_segments::{
+        VectorDiskSegmentPaths,
+        VectorDiskSegmentValues,
+    },
+    query::VectorSearchQueryResult,
+    sparse_index::{
+        matches_filter_conditions,
+        CompiledSparseVectorSearch,
+        SparseVector,
+    },
+};
+
+/// Version 1 of the sparse segment file has the following format:
+/// ```
+/// [ version ] [ num_points ] [ num_filter_fields ] [ filter_field ]*
+/// [ point ]* [ num_posting_lists ] [ posting_list ]*
+/// ```
+/// - version (u8): version number for the file format
+/// - num_points (little-endian u32): number of points in the segment
+/// - num_filter_fields (little-endian u32): number of filter fields
+/// - filter_field: [ len (little-endian u32) ] [ field path (UTF-8) ]
+/// - point: [ ts (little-endian u64) ] [ filter_value ]*, with one `[ len
+///   (little-endian u32) ] [ bytes ]` filter value per filter field, in point
+///   offset order
+/// - num_posting_lists (little-endian u32): number of nonempty dimensions
+/// - posting_list: [ dimension (little-endian u32) ] [ len (little-endian u32)
+///   ] [ offset (little-endian u32) weight (little-endian f32) ]*, sorted by
+///   dimension and then by offset
+///
+/// Point offsets are the same offsets used by the segment's id tracker and
+/// deleted bitset, which have the same format as dense vector segments.
+pub const SPARSE_SEGMENT_VERSION: u8 = 1;
+
+const SEGMENT_FILENAME: &str = "sparse_segment";
+const ID_TRACKER_FILENAME: &str = "id_tracker";
+const DELETED_BITSET_FILENAME: &str = "deleted_bitset";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparsePoint {
+    pub internal_id: InternalId,
+    pub ts: Timestamp,
+    pub vector: SparseVector,
+    /// Filter values in the order of the segment's filter fields.
+    pub filter_values: Vec<Vec<u8>>,
+}
+
+/// Writes `points` to a new segment in `index_path`. All of the points are
+/// live, so the new segment's deleted bitset is empty.
+pub fn write_sparse_segment(
+    index_path: &Path,
+    filter_fields: &[FieldPath],
+    points: impl IntoIterator<Item = SparsePoint>,
+) -> anyhow::Result<VectorDiskSegmentValues> {
+    let mut id_tracker = MemoryIdTracker::default();
+    let mut timestamps = vec![];
+    let mut filter_values = vec![];
+    let mut postings: BTreeMap<u32, Vec<(u32, f32)>> = BTreeMap::new();
+    for (offset, point) in points.into_iter().enumerate() {
+        let offset = u32::try_from(offset)?;
+        anyhow::ensure!(point.filter_values.len() == filter_fields.len());
+        id_tracker.insert(offset, point.internal_id.0);
+        timestamps.push(point.ts);
+        filter_values.push(point.filter_values);
+        for (dimension, weight) in point.vector.iter() {
+            postings
+                .entry(dimension)
+                .or_default()
+                .push((offset, weight));
+        }
+    }
+    let num_vectors = u32::try_from(timestamps.len())?;
+
+    let segment = index_path.join(SEGMENT_FILENAME);
+    let mut out = BufWriter::new(File::create(&segment)?);
+    out.write_u8(SPARSE_SEGMENT_VERSION)?;
+    out.write_u32::<LittleEndian>(num_vectors)?;
+    out.write_u32::<LittleEndian>(filter_fields.len().try_into()?)?;
+    for field in filter_fields {
+        write_bytes(&mut out, String::from(field.clone()).as_bytes())?;
+    }
+    for (ts, values) in timestamps.iter().zip(&filter_values) {
+        out.write_u64::<LittleEndian>((*ts).into())?;
+        for value in values {
+            write_bytes(&mut out, value)?;
+        }
+    }
+    out.write_u32::<LittleEndian>(postings.len().try_into()?)?;
+    for (dimension, posting_list) in &postings {
+        out.write_u32::<LittleEndian>(*dimension)?;
+        out.write_u32::<LittleEndian>(posting_list.len().try_into()?)?;
+        for (offset, weight) in posting_list {
+            out.write_u32::<LittleEndian>(*offset)?;
+            out.write_f32::<LittleEndian>(*weight)?;
+        }
+    }
+    out.into_inner()?.sync_all()?;
+
+    let uuids = index_path.join(ID_TRACKER_FILENAME);
+    let mut out = BufWriter::new(File::create(&uuids)?);
+    id_tracker.write_id_tracker(&mut out)?;
+    out.into_inner()?.sync_all()?;
+
+    let deleted_bitset = index_path.join(DELETED_BITSET_FILENAME);
+    DeletedBitset::new(num_vectors as usize).write_to_path(deleted_bitset.clone())?;
+
+    Ok(VectorDiskSegmentValues {
+        paths: VectorDiskSegmentPaths {
+            segment,
+            uuids,
+            deleted_bitset,
+        },
+        num_vectors,
+        num_deleted: 0,
+    })
+}
+
+fn write_bytes(out: &mut impl Write, bytes: &[u8]) -> anyhow::Result<()> {
+    out.write_u32::<LittleEndian>(bytes.len().try_into()?)?;
+    out.write_all(bytes)?;
+    Ok(())
+}
+
+fn read_bytes(reader: &mut impl Read) -> anyhow::Result<Vec<u8>> {
+    let len = reader.read_u32::<LittleEndian>()? as usize;
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// An inverted sparse vector segment loaded fully into memory.
+pub struct SparseVectorSegment {
+    filter_fields: Vec<FieldPath>,
+    timestamps: Vec<Timestamp>,
+    filter_values: Vec<Vec<Vec<u8>>>,
+    postings: BTreeMap<u32, Vec<(u32, f32)>>,
+    id_tracker: StaticIdTracker,
+    deleted_bitset: DeletedBitset,
+}
+
+impl SparseVectorSegment {
+    pub fn load(paths: &VectorDiskSegmentPaths) -> anyhow::Result<Self> {
+        let mut reader = BufReader::new(File::open(&paths.segment)?);
+        anyhow::ensure!(reader.read_u8()? == SPARSE_SEGMENT_VERSION);
+        let num_points = reader.read_u32::<LittleEndian>()? as usize;
+        let num_filter_fields = reader.read_u32::<LittleEndian>()? as usize;
+        let filter_fields = (0..num_filter_fields)
+            .map(|_| String::from_utf8(read_bytes(&mut reader)?)?.parse())
+            .collect::<anyhow::Result<Vec<FieldPath>>>()?;
+        let mut timestamps = Vec::with_capacity(num_points);
+        let mut filter_values = Vec::with_capacity(num_points);
+        for _ in 0..num_points {
+            timestamps.push(Timestamp::try_from(reader.read_u64::<LittleEndian>()?)?);
+            filter_values.push(
+                (0..num_filter_fields)
+                    .map(|_| read_bytes(&mut reader))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+            );
+        }
+        let num_posting_lists = reader.read_u32::<LittleEndian>()?;
+        let mut postings = BTreeMap::new();
+        for _ in 0..num_posting_lists {
+            let dimension = reader.read_u32::<LittleEndian>()?;
+            let len = reader.read_u32::<LittleEndian>()? as usize;
+            let mut posting_list = Vec::with_capacity(len);
+            for _ in 0..len {
+                let offset = reader.read_u32::<LittleEndian>()?;
+                anyhow::ensure!((offset as usize) < num_points);
+                posting_list.push((offset, reader.read_f32::<LittleEndian>()?));
+            }
+            postings.insert(dimension, posting_list);
+        }
+        // Be defensive against truncated or appended files.
+        anyhow::ensure!(reader.read_u8().is_err(), "Trailing data in sparse segment");
+
+        let id_tracker = StaticIdTracker::load_from_path(&paths.uuids)?;
+        let deleted_bitset = DeletedBitset::load_from_path(&paths.deleted_bitset)?;
+        anyhow::ensure!(id_tracker.count() == num_points);
+        anyhow::ensure!(deleted_bitset.len() == num_points);
+        Ok(Self {
+            filter_fields,
+            timestamps,
+            filter_values,
+            postings,
+            id_tracker,
+            deleted_bitset,
+        })
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    pub fn num_deleted(&self) -> usize {
+        self.deleted_bitset.num_deleted()
+    }
+
+    /// Scores every live point that shares a dimension with the query by dot
+    /// product, returning the best `query.limit + overfetch_delta` results.
+    pub fn search(
+        &self,
+        query: &CompiledSparseVectorSearch,
+        overfetch_delta: u32,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        let mut scores: HashMap<u32, f32> = HashMap::new();
+        for (dimension, query_weight) in query.vector.iter() {
+            let Some(posting_list) = self.postings.get(&dimension) else {
+                continue;
+            };
+            for (offset, weight) in posting_list {
+                if self.deleted_bitset.is_deleted(*offset) {
+                    continue;
+                }
+                *scores.entry(*offset).or_default() += query_weight * weight;
+            }
+        }
+        let mut results = vec![];
+        for (offset, score) in scores {
+            if !self.matches(offset, query) {
+                continue;
+            }
+            let id = self
+                .id_tracker
+                .get_convex_id(offset as usize)
+                .ok_or_else(|| anyhow::anyhow!("Missing id for offset {offset}"))?;
+            results.push(VectorSearchQueryResult {
+                score,
+                id: InternalId::from(id),
+                ts: WriteTimestamp::Committed(self.timestamps[offset as usize]),
+            });
+        }
+        results.sort_by(|a, b| a.cmp(b).reverse());
+        results.truncate((query.limit + overfetch_delta) as usize);
+        Ok(results)
+    }
+
+    fn matches(&self, offset: u32, query: &CompiledSparseVectorSearch) -> bool {
+        let values = &self.filter_values[offset as usize];
+        matches_filter_conditions(&query.filter_conditions, |field_path| {
+            self.filter_fields
+                .iter()
+                .position(|f| f == field_path)
+                .map(|i| &values[i][..])
+        })
+    }
+
+    /// Reconstructs the points in this segment that haven't been deleted.
+    fn live_points(&self) -> anyhow::Result<Vec<SparsePoint>> {
+        let mut entries: Vec<Vec<(u32, f32)>> = vec![vec![]; self.num_points()];
+        for (dimension, posting_list) in &self.postings {
+            for (offset, weight) in posting_list {
+                entries[*offset as usize].push((*dimension, *weight));
+            }
+        }
+        let mut points = vec![];
+        for (offset, entries) in entries.into_iter().enumerate() {
+            if self.deleted_bitset.is_deleted(offset as u32) {
+                continue;
+            }
+            let id = self
+                .id_tracker
+                .get_convex_id(offset)
+                .ok_or_else(|| anyhow::anyhow!("Missing id for offset {offset}"))?;
+            points.push(SparsePoint {
+                internal_id: InternalId::from(id),
+                ts: self.timestamps[offset],
+                vector: SparseVector::new(entries)?,
+                filter_values: self.filter_values[offset].clone(),
+            });
+        }
+        Ok(points)
+    }
+}
+
+/// Merges the live points of `segments` into a single new segment in
+/// `index_path`, dropping everything that's been deleted.
+pub fn merge_sparse_segments(
+    segments: Vec<SparseVectorSegment>,
+    index_path: &Path,
+) -> anyhow::Result<VectorDiskSegmentValues> {
+    let filter_fields = segments
+        .first()
+        .map(|segment| segment.filter_fields.clone())
+        .unwrap_or_default();
+    let mut points = vec![];
+    for segment in &segments {
+        anyhow::ensure!(
+            segment.filter_fields == filter_fields,
+            "Can't merge sparse segments with different filter fields"
+        );
+        points.extend(segment.live_points()?);
+    }
+    write_sparse_segment(index_path, &filter_fields, points)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use common::{
+        deleted_bitset::DeletedBitset,
+        types::Timestamp,
+    };
+    use tempfile::TempDir;
+    use value::{
+        FieldPath,
+        InternalId,
+    };
+
+    use super::{
+        merge_sparse_segments,
+        write_sparse_segment,
+        SparsePoint,
+        SparseVectorSegment,
+    };
+    use crate::{
+        query::CompiledVectorFilter,
+        sparse_index::{
+            CompiledSparseVectorSearch,
+            SparseVector,
+        },
+    };
+
+    fn point(id: u128, entries: Vec<(u32, f32)>, filter: u8) -> anyhow::Result<SparsePoint> {
+        Ok(SparsePoint {
+            internal_id: InternalId(id.to_le_bytes()),
+            ts: Timestamp::try_from(id as u64)?,
+            vector: SparseVector::new(entries)?,
+            filter_values: vec![vec![filter]],
+        })
+    }
+
+    fn query(
+        entries: Vec<(u32, f32)>,
+        filter: Option<u8>,
+    ) -> anyhow::Result<CompiledSparseVectorSearch> {
+        let field: FieldPath = "category".parse()?;
+        Ok(CompiledSparseVectorSearch {
+            vector: SparseVector::new(entries)?,
+            limit: 10,
+            filter_conditions: filter
+                .map(|f| BTreeMap::from([(field, CompiledVectorFilter::Eq(vec![f]))]))
+                .unwrap_or_default(),
+        })
+    }
+
+    #[test]
+    fn test_sparse_segment_search_and_merge() -> anyhow::Result<()> {
+        let filter_fields: Vec<FieldPath> = vec!["category".parse()?];
+        let first_dir = TempDir::new()?;
+        let first = write_sparse_segment(
+            first_dir.path(),
+            &filter_fields,
+            vec![
+                point(1, vec![(1, 1.0), (5, 2.0)], 0)?,
+                point(2, vec![(5, 1.0)], 1)?,
+                point(3, vec![(9, 3.0)], 0)?,
+            ],
+        )?;
+        assert_eq!(first.num_vectors, 3);
+        let segment = SparseVectorSegment::load(&first.paths)?;
+        let results = segment.search(&query(vec![(5, 1.0), (9, 1.0)], None)?, 0)?;
+        let scores: Vec<_> = results.iter().map(|r| r.score).collect();
+        assert_eq!(scores, vec![3.0, 2.0, 1.0]);
+        assert_eq!(results[0].id, InternalId(3u128.to_le_bytes()));
+
+        let results = segment.search(&query(vec![(5, 1.0)], Some(1))?, 0)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, InternalId(2u128.to_le_bytes()));
+
+        // Points that don't share a dimension with the query aren't returned.
+        assert!(segment.search(&query(vec![(7, 1.0)], None)?, 0)?.is_empty());
+
+        // Delete the first point and merge with a second segment.
+        let mut deleted = DeletedBitset::new(3);
+        deleted.delete(0)?;
+        deleted.write_to_path(first.paths.deleted_bitset.clone())?;
+        let second_dir = TempDir::new()?;
+        let second = write_sparse_segment(
+            second_dir.path(),
+            &filter_fields,
+            vec![point(4, vec![(1, 4.0)], 0)?],
+        )?;
+        let merged_dir = TempDir::new()?;
+        let merged = merge_sparse_segments(
+            vec![
+                SparseVectorSegment::load(&first.paths)?,
+                SparseVectorSegment::load(&second.paths)?,
+            ],
+            merged_dir.path(),
+        )?;
+        assert_eq!(merged.num_vectors, 3);
+        assert_eq!(merged.num_deleted, 0);
+        let merged = SparseVectorSegment::load(&merged.paths)?;
+        let results = merged.search(&query(vec![(1, 1.0)], None)?, 0)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, InternalId(4u128.to_le_bytes()));
+        assert_eq!(results[0].score, 4.0);
+        Ok(())
+    }
+}
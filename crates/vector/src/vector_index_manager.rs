@@ -54,14 +54,23 @@ use crate::{
         finish_index_manager_update_timer,
         VectorIndexType,
     },
-    qdrant_index::QdrantSchema,
+    qdrant_index::{
+        internal_vector_score,
+        QdrantSchema,
+    },
     query::{
         InternalVectorSearch,
         VectorSearchQueryResult,
     },
     searcher::VectorSearcher,
+    sparse_index::{
+        InternalSparseVectorSearch,
+        SparseVectorSchema,
+    },
+    sparse_memory_index::MemorySparseVectorIndex,
     CompiledVectorSearch,
     DocInVectorIndex,
+    DEFAULT_VECTOR_LIMIT,
 };
 
 #[derive(Clone)]
@@ -72,16 +81,54 @@ pub struct VectorIndexManager {
 #[derive(Clone)]
 pub enum IndexState {
     Bootstrapping(OrdMap<IndexId, VectorIndexState>),
-    Ready(OrdMap<IndexId, (VectorIndexState, MemoryVectorIndex)>),
+    Ready(OrdMap<IndexId, (VectorIndexState, MemoryIndex)>),
+}
+
+/// The in-memory revisions of either a dense or a sparse vector index. Both
+/// kinds share the on-disk state machine, so they're tracked together.
+#[derive(Clone)]
+pub enum MemoryIndex {
+    Dense(MemoryVectorIndex),
+    Sparse(MemorySparseVectorIndex),
+}
+
+impl MemoryIndex {
+    pub fn size(&self) -> usize {
+        match self {
+            MemoryIndex::Dense(index) => index.size(),
+            MemoryIndex::Sparse(index) => index.size(),
+        }
+    }
+
+    pub fn num_transactions(&self) -> usize {
+        match self {
+            MemoryIndex::Dense(index) => index.num_transactions(),
+            MemoryIndex::Sparse(index) => index.num_transactions(),
+        }
+    }
+
+    pub fn truncate(&mut self, new_min_ts: Timestamp) -> anyhow::Result<()> {
+        match self {
+            MemoryIndex::Dense(index) => index.truncate(new_min_ts),
+            MemoryIndex::Sparse(index) => index.truncate(new_min_ts),
+        }
+    }
+}
+
+impl From<MemoryVectorIndex> for MemoryIndex {
+    fn from(index: MemoryVectorIndex) -> Self {
+        MemoryIndex::Dense(index)
+    }
+}
+
+impl From<MemorySparseVectorIndex> for MemoryIndex {
+    fn from(index: MemorySparseVectorIndex) -> Self {
+        MemoryIndex::Sparse(index)
+    }
 }
 
 impl IndexState {
-    pub fn insert(
-        &mut self,
-        id: InternalId,
-        state: VectorIndexState,
-        memory_index: MemoryVectorIndex,
-    ) {
+    pub fn insert(&mut self, id: InternalId, state: VectorIndexState, memory_index: MemoryIndex) {
         match self {
             IndexState::Bootstrapping(ref mut indexes) => {
                 indexes.insert(id, state);
@@ -96,7 +143,7 @@ impl IndexState {
         &mut self,
         id: &IndexId,
         new_vector_index_state: Option<VectorIndexState>,
-        mutate_memory: impl FnOnce(&mut MemoryVectorIndex) -> anyhow::Result<()>,
+        mutate_memory: impl FnOnce(&mut MemoryIndex) -> anyhow::Result<()>,
     ) -> anyhow::Result<()> {
         match self {
             IndexState::Bootstrapping(indexes) => {
@@ -137,12 +184,12 @@ fn get_vector_index_states(
 ) -> anyhow::Result<OrdMap<InternalId, VectorIndexState>> {
     let mut indexes = OrdMap::new();
 
-    for index in registry.all_vector_indexes() {
-        let IndexConfig::Vector {
-            developer_config: _,
-            ref on_disk_state,
-        } = index.config
-        else {
+    for index in registry
+        .all_vector_indexes()
+        .into_iter()
+        .chain(registry.all_sparse_vector_indexes())
+    {
+        let Some(on_disk_state) = vector_index_state(&index.config) else {
             continue;
         };
         indexes.insert(index.id().internal_id(), on_disk_state.clone());
@@ -150,6 +197,16 @@ fn get_vector_index_states(
     Ok(indexes)
 }
 
+/// The on disk state of dense and sparse vector indexes, which share the same
+/// segment lifecycle.
+fn vector_index_state(config: &IndexConfig) -> Option<&VectorIndexState> {
+    match config {
+        IndexConfig::Vector { on_disk_state, .. }
+        | IndexConfig::SparseVector { on_disk_state, .. } => Some(on_disk_state),
+        _ => None,
+    }
+}
+
 impl VectorIndexManager {
     pub fn is_bootstrapping(&self) -> bool {
         matches!(self.indexes, IndexState::Bootstrapping(..))
@@ -179,7 +236,7 @@ impl VectorIndexManager {
 
     fn require_ready_indexes(
         &self,
-    ) -> anyhow::Result<&OrdMap<InternalId, (VectorIndexState, MemoryVectorIndex)>> {
+    ) -> anyhow::Result<&OrdMap<InternalId, (VectorIndexState, MemoryIndex)>> {
         if let IndexState::Ready(ref indexes) = self.indexes {
             Ok(indexes)
         } else {
@@ -193,7 +250,7 @@ impl VectorIndexManager {
     fn require_ready_index(
         &self,
         id: &InternalId,
-    ) -> anyhow::Result<Option<&(VectorIndexState, MemoryVectorIndex)>> {
+    ) -> anyhow::Result<Option<&(VectorIndexState, MemoryIndex)>> {
         Ok(self.require_ready_indexes()?.get(id))
     }
 
@@ -247,6 +304,29 @@ impl VectorIndexManager {
             at_least_one_matching_index =
                 at_least_one_matching_index || old_value.is_some() || new_value.is_some();
             self.indexes.update(&index.id, None, |memory_index| {
+                let MemoryIndex::Dense(memory_index) = memory_index else {
+                    anyhow::bail!("Expected a dense memory index for {}", index.id);
+                };
+                memory_index.update(id.internal_id(), ts, old_value, new_value)
+            })?;
+        }
+        for index in index_registry.sparse_vector_indexes_by_table(id.tablet_id) {
+            let IndexConfig::SparseVector {
+                ref developer_config,
+                ..
+            } = index.metadata.config
+            else {
+                continue;
+            };
+            let schema = SparseVectorSchema::new(developer_config);
+            let old_value = deletion.as_ref().and_then(|d| schema.index(d));
+            let new_value = insertion.as_ref().and_then(|d| schema.index(d));
+            at_least_one_matching_index =
+                at_least_one_matching_index || old_value.is_some() || new_value.is_some();
+            self.indexes.update(&index.id, None, |memory_index| {
+                let MemoryIndex::Sparse(memory_index) = memory_index else {
+                    anyhow::bail!("Expected a sparse memory index for {}", index.id);
+                };
                 memory_index.update(id.internal_id(), ts, old_value, new_value)
             })?;
         }
@@ -267,21 +347,27 @@ impl VectorIndexManager {
         match (deletion, insertion) {
             (None, Some(insertion)) => {
                 let metadata = IndexMetadata::try_from(insertion.value().clone().0)?;
-                if let IndexConfig::Vector {
-                    ref on_disk_state, ..
-                } = metadata.config
-                {
-                    let VectorIndexState::Backfilling(state) = on_disk_state else {
+                let memory_index = match metadata.config {
+                    IndexConfig::Vector {
+                        ref developer_config,
+                        ..
+                    } => Some(MemoryVectorIndex::new(ts, developer_config.distance_metric).into()),
+                    IndexConfig::SparseVector { .. } => {
+                        Some(MemorySparseVectorIndex::new(ts).into())
+                    },
+                    _ => None,
+                };
+                if let Some(memory_index) = memory_index {
+                    let Some(VectorIndexState::Backfilling(state)) =
+                        vector_index_state(&metadata.config)
+                    else {
                         anyhow::bail!(
                             "Inserted new search index that wasn't backfilling: {metadata:?}"
                         );
                     };
                     let index = VectorIndexState::Backfilling(state.clone());
-                    self.indexes.insert(
-                        insertion.id().internal_id(),
-                        index,
-                        MemoryVectorIndex::new(ts),
-                    );
+                    self.indexes
+                        .insert(insertion.id().internal_id(), index, memory_index);
 
                     metrics::log_index_created()
                 }
@@ -289,83 +375,55 @@ impl VectorIndexManager {
             (Some(prev_version), Some(next_version)) => {
                 let prev_metadata: ParsedDocument<IndexMetadata<_>> = prev_version.parse()?;
                 let next_metadata: ParsedDocument<IndexMetadata<_>> = next_version.parse()?;
-                let (old_snapshot, new_snapshot, staged) =
-                    match (&prev_metadata.config, &next_metadata.config) {
-                        (
-                            IndexConfig::Vector {
-                                on_disk_state:
-                                    VectorIndexState::Backfilling(VectorIndexBackfillState { .. }),
-                                ..
-                            },
-                            IndexConfig::Vector {
-                                on_disk_state:
-                                    VectorIndexState::Backfilling(VectorIndexBackfillState {
-                                        staged,
-                                        ..
-                                    }),
-                                ..
-                            },
-                        ) => (None, None, *staged),
-                        (
-                            IndexConfig::Vector {
-                                on_disk_state: VectorIndexState::Backfilling { .. },
-                                ..
-                            },
-                            IndexConfig::Vector {
-                                on_disk_state: VectorIndexState::Backfilled { snapshot, staged },
-                                ..
-                            },
-                        ) => (None, Some(snapshot), *staged),
-                        (
-                            IndexConfig::Vector {
-                                on_disk_state:
-                                    VectorIndexState::Backfilled {
-                                        snapshot: old_snapshot,
-                                        ..
-                                    },
-                                ..
-                            },
-                            IndexConfig::Vector {
-                                on_disk_state: VectorIndexState::SnapshottedAt(new_snapshot),
-                                ..
-                            },
-                        ) => (Some(old_snapshot), Some(new_snapshot), false),
-                        (
-                            IndexConfig::Vector {
-                                on_disk_state:
-                                    VectorIndexState::Backfilled {
-                                        snapshot: old_snapshot,
-                                        ..
-                                    },
-                                ..
-                            },
-                            IndexConfig::Vector {
-                                on_disk_state:
-                                    VectorIndexState::Backfilled {
-                                        snapshot: new_snapshot,
-                                        staged,
-                                    },
-                                ..
-                            },
-                        ) => (Some(old_snapshot), Some(new_snapshot), *staged),
-                        (
-                            IndexConfig::Vector {
-                                on_disk_state: VectorIndexState::SnapshottedAt(old_snapshot),
-                                ..
-                            },
-                            IndexConfig::Vector {
-                                on_disk_state: VectorIndexState::SnapshottedAt(new_snapshot),
-                                ..
-                            },
-                        ) => (Some(old_snapshot), Some(new_snapshot), false),
-                        (IndexConfig::Vector { .. }, _) | (_, IndexConfig::Vector { .. }) => {
-                            anyhow::bail!(
-                                "Invalid index type transition: {prev_metadata:?} to \
-                                 {next_metadata:?}"
-                            );
-                        },
-                        _ => (None, None, false),
-                    };
+                let invalid_transition = || {
+                    anyhow::anyhow!(
+                        "Invalid index type transition: {prev_metadata:?} to {next_metadata:?}"
+                    )
+                };
+                let prev_state = vector_index_state(&prev_metadata.config);
+                let next_state = vector_index_state(&next_metadata.config);
+                if prev_state.is_some()
+                    && next_state.is_some()
+                    && prev_metadata.is_vector_index() != next_metadata.is_vector_index()
+                {
+                    return Err(invalid_transition());
+                }
+                let (old_snapshot, new_snapshot, staged) = match (prev_state, next_state) {
+                    (
+                        Some(VectorIndexState::Backfilling(VectorIndexBackfillState { .. })),
+                        Some(VectorIndexState::Backfilling(VectorIndexBackfillState {
+                            staged,
+                            ..
+                        })),
+                    ) => (None, None, *staged),
+                    (
+                        Some(VectorIndexState::Backfilling { .. }),
+                        Some(VectorIndexState::Backfilled { snapshot, staged }),
+                    ) => (None, Some(snapshot), *staged),
+                    (
+                        Some(VectorIndexState::Backfilled {
+                            snapshot: old_snapshot,
+                            ..
+                        }),
+                        Some(VectorIndexState::SnapshottedAt(new_snapshot)),
+                    ) => (Some(old_snapshot), Some(new_snapshot), false),
+                    (
+                        Some(VectorIndexState::Backfilled {
+                            snapshot: old_snapshot,
+                            ..
+                        }),
+                        Some(VectorIndexState::Backfilled {
+                            snapshot: new_snapshot,
+                            staged,
+                        }),
+                    ) => (Some(old_snapshot), Some(new_snapshot), *staged),
+                    (
+                        Some(VectorIndexState::SnapshottedAt(old_snapshot)),
+                        Some(VectorIndexState::SnapshottedAt(new_snapshot)),
+                    ) => (Some(old_snapshot), Some(new_snapshot), false),
+                    (Some(_), _) | (_, Some(_)) => return Err(invalid_transition()),
+                    (None, None) => (None, None, false),
+                };
                 if let Some(new_snapshot) = new_snapshot {
                     let is_newly_enabled =
                         !prev_metadata.config.is_enabled() && next_metadata.config.is_enabled();
@@ -403,7 +461,7 @@ impl VectorIndexManager {
             },
             (Some(deletion), None) => {
                 let metadata: ParsedDocument<IndexMetadata<_>> = deletion.parse()?;
-                if metadata.is_vector_index() {
+                if metadata.is_vector_index() || metadata.is_sparse_vector_index() {
                     self.indexes.delete(&deletion.id().internal_id());
                     metrics::log_index_deleted();
                 }
@@ -435,7 +493,9 @@ impl VectorIndexManager {
                     )
                 ));
             };
-            let Some((vector_index, memory_index)) = self.require_ready_index(&index.id())? else {
+            let Some((vector_index, MemoryIndex::Dense(memory_index))) =
+                self.require_ready_index(&index.id())?
+            else {
                 anyhow::bail!("Vector index {:?} not available", index.id());
             };
             let qdrant_schema = QdrantSchema::new(developer_config);
@@ -534,6 +594,11 @@ impl VectorIndexManager {
         )
             -> BoxFuture<'a, anyhow::Result<Vec<VectorSearchQueryResult>>>,
     ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        let page_limit = query.limit.unwrap_or(DEFAULT_VECTOR_LIMIT) as usize;
+        let cursor = query.cursor;
+        let score_threshold = query
+            .score_threshold
+            .map(|t| internal_vector_score(qdrant_schema.distance_metric(), t));
         let compiled_query = qdrant_schema.compile(query)?;
         let updated_matches = memory_index.updated_matches(ts, &compiled_query)?;
         let overfetch_delta = updated_matches.len();
@@ -550,7 +615,13 @@ impl VectorIndexManager {
             disk_revisions.extend(memory_revisions);
             let original_len = disk_revisions.len();
             disk_revisions.sort_by(|a, b| a.cmp(b).reverse());
-            disk_revisions.truncate(compiled_query.limit as usize);
+            if let Some(ref cursor) = cursor {
+                disk_revisions.retain(|r| cursor.precedes(r));
+            }
+            if let Some(score_threshold) = score_threshold {
+                disk_revisions.retain(|r| r.score >= score_threshold);
+            }
+            disk_revisions.truncate(page_limit);
             metrics::log_num_discarded_revisions(original_len - disk_revisions.len());
 
             anyhow::Ok(())
@@ -559,6 +630,91 @@ impl VectorIndexManager {
         Ok(disk_revisions)
     }
 
+    pub async fn sparse_vector_search(
+        &self,
+        index: &Index,
+        query: InternalSparseVectorSearch,
+        searcher: Arc<dyn VectorSearcher>,
+        search_storage: Arc<dyn Storage>,
+    ) -> anyhow::Result<Vec<VectorSearchQueryResult>> {
+        let timer = metrics::search_timer(&SEARCHLIGHT_CLUSTER_NAME);
+        let result: anyhow::Result<_> = try {
+            let IndexConfig::SparseVector {
+                ref developer_config,
+                ..
+            } = index.metadata.config
+            else {
+                anyhow::bail!(ErrorMetadata::bad_request(
+                    "IndexNotASparseVectorIndexError",
+                    format!(
+                        "Index {} is not a sparse vector index",
+                        query.printable_index_name()?
+                    )
+                ));
+            };
+            let Some((vector_index, MemoryIndex::Sparse(memory_index))) =
+                self.require_ready_index(&index.id())?
+            else {
+                anyhow::bail!("Sparse vector index {:?} not available", index.id());
+            };
+            let VectorIndexState::SnapshottedAt(ref snapshot) = vector_index else {
+                anyhow::bail!(index_backfilling_error(&query.printable_index_name()?));
+            };
+            let VectorIndexSnapshotData::MultiSegment(ref segments) = snapshot.data else {
+                anyhow::bail!(index_backfilling_error(&query.printable_index_name()?));
+            };
+            let schema = SparseVectorSchema::new(developer_config);
+            let compiled_query = schema.compile(query)?;
+            let updated_matches = memory_index.updated_matches(snapshot.ts, &compiled_query)?;
+            let overfetch_delta = updated_matches.len();
+            metrics::log_searchlight_overfetch_delta(overfetch_delta);
+
+            let client_timer = metrics::searchlight_client_execute_timer(
+                VectorIndexType::MultiSegment,
+                &SEARCHLIGHT_CLUSTER_NAME,
+            );
+            let mut disk_revisions = searcher
+                .execute_multi_segment_sparse_vector_query(
+                    search_storage,
+                    segments
+                        .iter()
+                        .cloned()
+                        .map(|segment| segment.to_paths_proto())
+                        .try_collect()?,
+                    schema,
+                    compiled_query.clone(),
+                    overfetch_delta as u32,
+                )
+                .await?;
+            metrics::log_num_segments_searched_total(segments.len());
+            metrics::finish_searchlight_client_execute(client_timer, &disk_revisions);
+
+            block_in_place(|| {
+                // Filter out revisions that are no longer latest.
+                disk_revisions.retain(|r| !updated_matches.contains(&r.id));
+                disk_revisions.extend(memory_index.query(snapshot.ts, &compiled_query)?);
+                let original_len = disk_revisions.len();
+                disk_revisions.sort_by(|a, b| a.cmp(b).reverse());
+                disk_revisions.truncate(compiled_query.limit as usize);
+                metrics::log_num_discarded_revisions(original_len - disk_revisions.len());
+                anyhow::Ok(())
+            })?;
+            disk_revisions
+        };
+        match result {
+            Ok(disk_revisions) => {
+                metrics::finish_search(timer, &disk_revisions, VectorIndexType::MultiSegment);
+                Ok(disk_revisions)
+            },
+            Err(e) => {
+                if e.is_bad_request() {
+                    timer.finish_developer_error();
+                }
+                Err(e)
+            },
+        }
+    }
+
     pub fn total_in_memory_size(&self) -> usize {
         if let IndexState::Ready(ref indexes) = self.indexes {
             indexes
@@ -5,7 +5,10 @@ import {
   GenericDataModel,
   GenericDatabaseWriter,
   GenericMutationCtx,
+  NamedTableInfo,
   QueryInitializer,
+  SearchIndexNames,
+  SearchSuggestion,
   TableNamesInDataModel,
   createFunctionHandle,
 } from "convex/server";
@@ -129,6 +132,14 @@ class WrapWriter<DataModel extends GenericDataModel> {
   ): GenericId<TableName> | null {
     return this.ctx.db.normalizeId(tableName, id);
   }
+  suggest<TableName extends TableNamesInDataModel<DataModel>>(
+    tableName: TableName,
+    indexName: SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+    prefix: string,
+    options?: { limit?: number },
+  ): Promise<SearchSuggestion[]> {
+    return this.ctx.db.suggest(tableName, indexName, prefix, options);
+  }
   async insert<TableName extends string>(
     table: TableName,
     value: any,
@@ -19,7 +19,8 @@ const CustomJwt = z
     type: z.literal("customJwt"),
     applicationID: z.string().nullable(),
     issuer: z.string(),
-    jwks: z.string(),
+    jwks: z.string().optional(),
+    secretEnvVar: z.string().optional(),
     algorithm: z.string(),
   })
   .passthrough();
@@ -6,6 +6,7 @@ import { SchedulableFunctionReference } from "./scheduler.js";
 type CronSchedule = {
   type: "cron";
   cron: string;
+  timezone?: string;
 };
 /** @public */
 export type IntervalSchedule =
@@ -16,12 +17,14 @@ export type IntervalSchedule =
 export type HourlySchedule = {
   type: "hourly";
   minuteUTC: number;
+  timezone?: string;
 };
 /** @public */
 export type DailySchedule = {
   type: "daily";
   hourUTC: number;
   minuteUTC: number;
+  timezone?: string;
 };
 const DAYS_OF_WEEK = [
   "sunday",
@@ -39,6 +42,7 @@ export type WeeklySchedule = {
   dayOfWeek: DayOfWeek;
   hourUTC: number;
   minuteUTC: number;
+  timezone?: string;
 };
 /** @public */
 export type MonthlySchedule = {
@@ -46,6 +50,7 @@ export type MonthlySchedule = {
   day: number;
   hourUTC: number;
   minuteUTC: number;
+  timezone?: string;
 };
 
 // Duplicating types so docstrings are visible in signatures:
@@ -88,6 +93,12 @@ export type Hourly = {
    * Minutes past the hour, 0-59.
    */
   minuteUTC: number;
+  /**
+   * An IANA timezone like `"America/New_York"`. When set, the hour and
+   * minute are wall-clock times in this timezone instead of UTC, and the
+   * schedule follows daylight saving time changes.
+   */
+  timezone?: string;
 };
 
 /** @public */
@@ -100,6 +111,12 @@ export type Daily = {
    * 0-59, minute of hour. Remember, this is UTC.
    */
   minuteUTC: number;
+  /**
+   * An IANA timezone like `"America/New_York"`. When set, the hour and
+   * minute are wall-clock times in this timezone instead of UTC, and the
+   * schedule follows daylight saving time changes.
+   */
+  timezone?: string;
 };
 
 /** @public */
@@ -116,6 +133,12 @@ export type Monthly = {
    * 0-59, minute of hour. Remember to convert from your own time zone to UTC.
    */
   minuteUTC: number;
+  /**
+   * An IANA timezone like `"America/New_York"`. When set, the hour and
+   * minute are wall-clock times in this timezone instead of UTC, and the
+   * schedule follows daylight saving time changes.
+   */
+  timezone?: string;
 };
 /** @public */
 export type Weekly = {
@@ -131,6 +154,12 @@ export type Weekly = {
    * 0-59, minute of hour. Remember to convert from your own time zone to UTC.
    */
   minuteUTC: number;
+  /**
+   * An IANA timezone like `"America/New_York"`. When set, the hour and
+   * minute are wall-clock times in this timezone instead of UTC, and the
+   * schedule follows daylight saving time changes.
+   */
+  timezone?: string;
 };
 
 /** @public */
@@ -153,8 +182,45 @@ export interface CronJob {
   name: string;
   args: JSONValue;
   schedule: Schedule;
+  catchUp?: CatchUpPolicy;
+  jitterSeconds?: number;
 }
 
+/**
+ * What to do with runs that were missed while they couldn't execute, e.g.
+ * during an outage or while the deployment was paused.
+ *
+ * - `{ type: "skip" }` drops the missed runs and waits for the next
+ *   scheduled time.
+ * - `{ type: "runOnce" }` runs once to cover all the missed runs. This is the
+ *   default.
+ * - `{ type: "runAll", max }` runs each missed run back to back, keeping at
+ *   most the `max` most recent ones.
+ *
+ * @public
+ */
+export type CatchUpPolicy =
+  | { type: "skip" }
+  | { type: "runOnce" }
+  | { type: "runAll"; max: number };
+
+/**
+ * Options accepted alongside any cron schedule.
+ *
+ * @public
+ */
+export type CronOptions = {
+  /**
+   * What to do with missed runs. Defaults to `{ type: "runOnce" }`.
+   */
+  catchUp?: CatchUpPolicy;
+  /**
+   * Delay each run by up to this many seconds (at most 3600), so jobs on the
+   * same schedule don't all start at once.
+   */
+  jitterSeconds?: number;
+};
+
 /**
  * Create a CronJobs object to schedule recurring tasks.
  *
@@ -186,6 +252,17 @@ export const cronJobs = () => new Crons();
  */
 type CronString = string;
 
+/**
+ * @public
+ *
+ * A cron string evaluated in an IANA timezone like `"America/New_York"`
+ * instead of UTC.
+ */
+export type CronWithTimezone = {
+  cron: CronString;
+  timezone?: string;
+};
+
 function validateIntervalNumber(n: number) {
   if (!Number.isInteger(n) || n <= 0) {
     throw new Error("Interval must be an integer greater than 0");
@@ -224,6 +301,41 @@ function validatedCronString(s: string) {
   return s;
 }
 
+function validatedTimezone(s: string | undefined) {
+  if (s !== undefined && (typeof s !== "string" || s.length === 0)) {
+    throw new Error(
+      'Timezone must be an IANA timezone name like "America/New_York"',
+    );
+  }
+  return s;
+}
+
+function validatedCronOptions(options: CronOptions): CronOptions {
+  const { catchUp, jitterSeconds } = options;
+  if (catchUp !== undefined) {
+    if (catchUp.type === "runAll") {
+      if (
+        !Number.isInteger(catchUp.max) ||
+        catchUp.max < 1 ||
+        catchUp.max > 1000
+      ) {
+        throw new Error("catchUp max must be an integer from 1 to 1000");
+      }
+    } else if (catchUp.type !== "skip" && catchUp.type !== "runOnce") {
+      throw new Error('catchUp type must be "skip", "runOnce" or "runAll"');
+    }
+  }
+  if (
+    jitterSeconds !== undefined &&
+    (!Number.isInteger(jitterSeconds) ||
+      jitterSeconds < 0 ||
+      jitterSeconds > 3600)
+  ) {
+    throw new Error("jitterSeconds must be an integer from 0 to 3600");
+  }
+  return { catchUp, jitterSeconds };
+}
+
 function validatedCronIdentifier(s: string) {
   if (!s.match(/^[ -~]*$/)) {
     throw new Error(
@@ -252,6 +364,7 @@ export class Crons {
   schedule(
     cronIdentifier: string,
     schedule: Schedule,
+    options: CronOptions,
     functionReference: SchedulableFunctionReference,
     args?: Record<string, Value>,
   ) {
@@ -264,6 +377,7 @@ export class Crons {
       name: getFunctionName(functionReference),
       args: [convexToJson(cronArgs)],
       schedule: schedule,
+      ...validatedCronOptions(options),
     };
   }
 
@@ -282,11 +396,11 @@ export class Crons {
    */
   interval<FuncRef extends SchedulableFunctionReference>(
     cronIdentifier: string,
-    schedule: Interval,
+    schedule: Interval & CronOptions,
     functionReference: FuncRef,
     ...args: OptionalRestArgs<FuncRef>
   ) {
-    const s = schedule;
+    const { catchUp, jitterSeconds, ...s } = schedule;
     const hasSeconds = +("seconds" in s && s.seconds !== undefined);
     const hasMinutes = +("minutes" in s && s.minutes !== undefined);
     const hasHours = +("hours" in s && s.hours !== undefined);
@@ -303,7 +417,8 @@ export class Crons {
     }
     this.schedule(
       cronIdentifier,
-      { ...schedule, type: "interval" },
+      { ...s, type: "interval" },
+      { catchUp, jitterSeconds },
       functionReference,
       ...args,
     );
@@ -330,14 +445,16 @@ export class Crons {
    */
   hourly<FuncRef extends SchedulableFunctionReference>(
     cronIdentifier: string,
-    schedule: Hourly,
+    schedule: Hourly & CronOptions,
     functionReference: FuncRef,
     ...args: OptionalRestArgs<FuncRef>
   ) {
     const minuteUTC = validatedMinuteOfHour(schedule.minuteUTC);
+    const timezone = validatedTimezone(schedule.timezone);
     this.schedule(
       cronIdentifier,
-      { minuteUTC, type: "hourly" },
+      { minuteUTC, timezone, type: "hourly" },
+      schedule,
       functionReference,
       ...args,
     );
@@ -365,15 +482,17 @@ export class Crons {
    */
   daily<FuncRef extends SchedulableFunctionReference>(
     cronIdentifier: string,
-    schedule: Daily,
+    schedule: Daily & CronOptions,
     functionReference: FuncRef,
     ...args: OptionalRestArgs<FuncRef>
   ) {
     const hourUTC = validatedHourOfDay(schedule.hourUTC);
     const minuteUTC = validatedMinuteOfHour(schedule.minuteUTC);
+    const timezone = validatedTimezone(schedule.timezone);
     this.schedule(
       cronIdentifier,
-      { hourUTC, minuteUTC, type: "daily" },
+      { hourUTC, minuteUTC, timezone, type: "daily" },
+      schedule,
       functionReference,
       ...args,
     );
@@ -401,16 +520,18 @@ export class Crons {
    */
   weekly<FuncRef extends SchedulableFunctionReference>(
     cronIdentifier: string,
-    schedule: Weekly,
+    schedule: Weekly & CronOptions,
     functionReference: FuncRef,
     ...args: OptionalRestArgs<FuncRef>
   ) {
     const dayOfWeek = validatedDayOfWeek(schedule.dayOfWeek);
     const hourUTC = validatedHourOfDay(schedule.hourUTC);
     const minuteUTC = validatedMinuteOfHour(schedule.minuteUTC);
+    const timezone = validatedTimezone(schedule.timezone);
     this.schedule(
       cronIdentifier,
-      { dayOfWeek, hourUTC, minuteUTC, type: "weekly" },
+      { dayOfWeek, hourUTC, minuteUTC, timezone, type: "weekly" },
+      schedule,
       functionReference,
       ...args,
     );
@@ -442,16 +563,18 @@ export class Crons {
    */
   monthly<FuncRef extends SchedulableFunctionReference>(
     cronIdentifier: string,
-    schedule: Monthly,
+    schedule: Monthly & CronOptions,
     functionReference: FuncRef,
     ...args: OptionalRestArgs<FuncRef>
   ) {
     const day = validatedDayOfMonth(schedule.day);
     const hourUTC = validatedHourOfDay(schedule.hourUTC);
     const minuteUTC = validatedMinuteOfHour(schedule.minuteUTC);
+    const timezone = validatedTimezone(schedule.timezone);
     this.schedule(
       cronIdentifier,
-      { day, hourUTC, minuteUTC, type: "monthly" },
+      { day, hourUTC, minuteUTC, timezone, type: "monthly" },
+      schedule,
       functionReference,
       ...args,
     );
@@ -472,21 +595,28 @@ export class Crons {
    * ```
    *
    * @param cronIdentifier - A unique name for this scheduled job.
-   * @param cron - Cron string like `"15 7 * * *"` (Every day at 7:15 UTC)
+   * @param cron - Cron string like `"15 7 * * *"` (Every day at 7:15 UTC), or
+   * `{ cron: "15 7 * * *", timezone: "America/New_York" }` to evaluate it in
+   * a timezone other than UTC. The object form also accepts
+   * {@link CronOptions}.
    * @param functionReference - A {@link FunctionReference} for the function
    * to schedule.
    * @param args - The arguments to the function.
    */
   cron<FuncRef extends SchedulableFunctionReference>(
     cronIdentifier: string,
-    cron: CronString,
+    cron: CronString | (CronWithTimezone & CronOptions),
     functionReference: FuncRef,
     ...args: OptionalRestArgs<FuncRef>
   ) {
-    const c = validatedCronString(cron);
+    const c = validatedCronString(typeof cron === "string" ? cron : cron.cron);
+    const timezone = validatedTimezone(
+      typeof cron === "string" ? undefined : cron.timezone,
+    );
     this.schedule(
       cronIdentifier,
-      { cron: c, type: "cron" },
+      { cron: c, timezone, type: "cron" },
+      typeof cron === "string" ? {} : cron,
       functionReference,
       ...args,
     );
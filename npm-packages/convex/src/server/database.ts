@@ -3,6 +3,7 @@ import {
   DocumentByName,
   GenericDataModel,
   NamedTableInfo,
+  SearchIndexNames,
   TableNamesInDataModel,
 } from "./data_model.js";
 import { QueryInitializer } from "./query.js";
@@ -66,6 +67,41 @@ interface BaseDatabaseReader<DataModel extends GenericDataModel> {
     tableName: TableName,
     id: string,
   ): GenericId<TableName> | null;
+
+  /**
+   * Suggest completions for the last word of `prefix` from the terms in a
+   * search index's `searchField`, most common first.
+   *
+   * Like search queries, suggestions are reactive: a query that calls this is
+   * rerun when documents containing a matching term are written.
+   *
+   * @param tableName - The name of the table with the search index.
+   * @param indexName - The name of the search index.
+   * @param prefix - The partially typed search text. Every word but the last
+   * is kept as-is, and the last word is completed.
+   * @param options - `limit` is the maximum number of suggestions to return
+   * (default 10, at most 32).
+   * @returns - The completed search texts with the number of documents that
+   * contain the completed word.
+   */
+  suggest<TableName extends TableNamesInDataModel<DataModel>>(
+    tableName: TableName,
+    indexName: SearchIndexNames<NamedTableInfo<DataModel, TableName>>,
+    prefix: string,
+    options?: { limit?: number },
+  ): Promise<SearchSuggestion[]>;
+}
+
+/**
+ * A completion returned by {@link GenericDatabaseReader.suggest}.
+ *
+ * @public
+ */
+export interface SearchSuggestion {
+  /** The search text with its last word completed. */
+  text: string;
+  /** The number of documents containing the completed word. */
+  docFrequency: number;
 }
 
 interface BaseDatabaseReaderWithTable<DataModel extends GenericDataModel> {
@@ -0,0 +1,131 @@
+import {
+  DocumentByInfo,
+  GenericTableInfo,
+  NamedSearchIndex,
+  NamedVectorIndex,
+  SearchIndexNames,
+  VectorIndexNames,
+} from "./data_model.js";
+import { SearchFilter, SearchFilterBuilder } from "./search_filter_builder.js";
+import { FilterExpression, VectorFilterBuilder } from "./vector_search.js";
+
+/**
+ * An object with parameters for running a hybrid search, which combines a
+ * full text search and a vector search over the same table and fuses their
+ * rankings.
+ * @public
+ */
+export interface HybridSearchQuery<
+  TableInfo extends GenericTableInfo,
+  TextIndexName extends SearchIndexNames<TableInfo>,
+  VectorIndexName extends VectorIndexNames<TableInfo>,
+> {
+  /**
+   * The full text half of the search.
+   */
+  text: {
+    /**
+     * The name of the search index on the table.
+     */
+    indexName: TextIndexName;
+    /**
+     * The search filter, e.g. `q => q.search("body", "hello")`.
+     */
+    search: (
+      q: SearchFilterBuilder<
+        DocumentByInfo<TableInfo>,
+        NamedSearchIndex<TableInfo, TextIndexName>
+      >,
+    ) => SearchFilter;
+  };
+  /**
+   * The vector half of the search.
+   */
+  vector: {
+    /**
+     * The name of the vector index on the table.
+     */
+    indexName: VectorIndexName;
+    /**
+     * The query vector. This must have the same length as the `dimensions` of
+     * the index.
+     */
+    vector: number[];
+    /**
+     * The number of vector results to fuse, between 1 and 256 inclusive.
+     *
+     * @default 10
+     */
+    limit?: number;
+    /**
+     * Optional filter expression made up of `q.or` and `q.eq` operating
+     * over the filter fields of the vector index.
+     */
+    filter?: (
+      q: VectorFilterBuilder<
+        DocumentByInfo<TableInfo>,
+        NamedVectorIndex<TableInfo, VectorIndexName>
+      >,
+    ) => FilterExpression<boolean>;
+  };
+  /**
+   * Parameters for reciprocal rank fusion. Each result scores
+   * `weight / (k + rank)` for every list it appears in.
+   */
+  fusion?: {
+    /**
+     * @default 60
+     */
+    k?: number;
+    /**
+     * @default 1
+     */
+    textWeight?: number;
+    /**
+     * @default 1
+     */
+    vectorWeight?: number;
+  };
+  /**
+   * The number of fused results to return, between 1 and 256 inclusive.
+   *
+   * @default 10
+   */
+  limit?: number;
+}
+
+/**
+ * The rank and score of a hybrid search result within the text or vector
+ * results.
+ * @public
+ */
+export interface HybridSearchComponentScore {
+  /**
+   * The 1-based rank of the document in this half of the search.
+   */
+  rank: number;
+  score: number;
+}
+
+/**
+ * A document returned by a hybrid search.
+ * @public
+ */
+export interface HybridSearchResult<TableInfo extends GenericTableInfo> {
+  _id: DocumentByInfo<TableInfo>["_id"];
+  /**
+   * The fused score of the document. Higher is better.
+   */
+  _score: number;
+  /**
+   * The document's rank and score in the text results, or `null` if it only
+   * matched the vector search.
+   */
+  textScore: HybridSearchComponentScore | null;
+  /**
+   * The document's rank and score in the vector results, or `null` if it
+   * only matched the text search.
+   */
+  vectorScore: HybridSearchComponentScore | null;
+  document: DocumentByInfo<TableInfo>;
+}
@@ -10,6 +10,7 @@ import {
   GenericDatabaseReaderWithTable,
   GenericDatabaseWriter,
   GenericDatabaseWriterWithTable,
+  SearchSuggestion,
 } from "../database.js";
 import { QueryInitializerImpl } from "./query_impl.js";
 import { GenericDataModel, GenericDocument } from "../data_model.js";
@@ -43,6 +44,37 @@ async function get(
   return jsonToConvex(syscallJSON) as GenericDocument;
 }
 
+async function suggest(
+  tableName: string,
+  indexName: string,
+  prefix: string,
+  options: { limit?: number } | undefined,
+  isSystem: boolean,
+): Promise<SearchSuggestion[]> {
+  validateArg(tableName, 1, "suggest", "tableName");
+  validateArg(indexName, 2, "suggest", "indexName");
+  if (typeof prefix !== "string") {
+    throw new Error(
+      `Invalid argument \`prefix\` for \`db.suggest\`, expected string but got '${typeof prefix}'`,
+    );
+  }
+  if (tableName.startsWith("_") !== isSystem) {
+    throw new Error(
+      `${isSystem ? "User" : "System"} tables can only be accessed from db.${
+        isSystem ? "" : "system."
+      }suggest().`,
+    );
+  }
+  const syscallJSON = await performAsyncSyscall("1.0/searchSuggest", {
+    indexName: tableName + "." + indexName,
+    prefix,
+    limit: options?.limit ?? DEFAULT_SUGGEST_LIMIT,
+  });
+  return syscallJSON as SearchSuggestion[];
+}
+
+const DEFAULT_SUGGEST_LIMIT = 10;
+
 export function setupReader(): GenericDatabaseReader<GenericDataModel> {
   const reader = (
     isSystem = false,
@@ -80,6 +112,14 @@ export function setupReader(): GenericDatabaseReader<GenericDataModel> {
         const syscallResult = jsonToConvex(syscallJSON) as any;
         return syscallResult.id;
       },
+      suggest: async (
+        tableName: string,
+        indexName: string,
+        prefix: string,
+        options?: { limit?: number },
+      ) => {
+        return await suggest(tableName, indexName, prefix, options, isSystem);
+      },
       // We set the system reader on the next line
       system: null as any,
       table: (tableName) => {
@@ -142,6 +182,7 @@ export function setupWriter(): GenericDatabaseWriter<GenericDataModel> &
     get: reader.get,
     query: reader.query,
     normalizeId: reader.normalizeId,
+    suggest: reader.suggest,
     system: reader.system as any,
     insert: async (table, value) => {
       return await insert(table, value);
@@ -0,0 +1,57 @@
+import { jsonToConvex } from "../../values/index.js";
+import { version } from "../../index.js";
+import { performAsyncSyscall } from "./syscall.js";
+import { validateArg } from "./validate.js";
+import { GenericTableInfo } from "../data_model.js";
+import { HybridSearchQuery, HybridSearchResult } from "../hybrid_search.js";
+import { SearchFilterBuilderImpl } from "./search_filter_builder_impl.js";
+import {
+  filterBuilderImpl,
+  serializeExpression,
+} from "./vector_search_impl.js";
+
+export function setupActionHybridSearch(requestId: string) {
+  return async (
+    tableName: string,
+    query: HybridSearchQuery<GenericTableInfo, string, string>,
+  ): Promise<Array<HybridSearchResult<GenericTableInfo>>> => {
+    validateArg(tableName, 1, "hybridSearch", "tableName");
+    validateArg(query, 2, "hybridSearch", "query");
+    validateArg(query.text, 2, "hybridSearch", "query.text");
+    validateArg(query.vector, 2, "hybridSearch", "query.vector");
+    const { vector } = query;
+    if (
+      !vector.vector ||
+      !Array.isArray(vector.vector) ||
+      vector.vector.length === 0
+    ) {
+      throw Error("`vector.vector` must be a non-empty Array in hybridSearch");
+    }
+    const searchFilter = query.text.search(SearchFilterBuilderImpl.new());
+
+    const { results } = await performAsyncSyscall("1.0/actions/hybridSearch", {
+      requestId,
+      version,
+      query: {
+        text: {
+          indexName: tableName + "." + query.text.indexName,
+          filters: (searchFilter as SearchFilterBuilderImpl).export(),
+        },
+        vector: {
+          indexName: tableName + "." + vector.indexName,
+          limit: vector.limit,
+          vector: vector.vector,
+          expressions: vector.filter
+            ? serializeExpression(vector.filter(filterBuilderImpl))
+            : null,
+        },
+        fusion: query.fusion,
+        limit: query.limit,
+      },
+    });
+    return results.map((result: any) => ({
+      ...result,
+      document: jsonToConvex(result.document),
+    }));
+  };
+}
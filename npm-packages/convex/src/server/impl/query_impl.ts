@@ -20,6 +20,7 @@ import { validateArg, validateArgIsNonNegativeInteger } from "./validate.js";
 import { version } from "../../index.js";
 
 const MAX_QUERY_OPERATORS = 256;
+const DEFAULT_FACET_LIMIT = 10;
 
 type QueryOperator = { filter: JSONValue } | { limit: number };
 type Source =
@@ -328,6 +329,50 @@ export class QueryImpl implements Query<GenericTableInfo> {
     return this.limit(n).collect();
   }
 
+  async takeWithFacets(
+    n: number,
+    facets: { fields: string[]; limit?: number },
+  ): Promise<any> {
+    validateArg(n, 1, "takeWithFacets", "n");
+    validateArgIsNonNegativeInteger(n, 1, "takeWithFacets", "n");
+    validateArg(facets, 2, "takeWithFacets", "facets");
+    const query = this.takeQuery();
+    if (query.source.type !== "Search") {
+      throw new Error(
+        "takeWithFacets can only be called on queries using withSearchIndex.",
+      );
+    }
+    if (query.operators.length > 0) {
+      throw new Error("takeWithFacets can't be combined with .filter(...).");
+    }
+    const { results, facets: facetCounts, isTruncated } =
+      await performAsyncSyscall("1.0/searchWithFacets", {
+        query,
+        fields: facets.fields,
+        limit: facets.limit ?? DEFAULT_FACET_LIMIT,
+        numResults: n,
+        version,
+      });
+    return {
+      results: results.map((json: JSONValue) => jsonToConvex(json)),
+      facets: facetCounts.map(
+        (facet: {
+          field: string;
+          counts: { value?: JSONValue; count: number }[];
+          numOmittedValues: number;
+        }) => ({
+          field: facet.field,
+          counts: facet.counts.map(({ value, count }) => ({
+            value: value === undefined ? undefined : jsonToConvex(value),
+            count,
+          })),
+          numOmittedValues: facet.numOmittedValues,
+        }),
+      ),
+      isTruncated,
+    };
+  }
+
   async first(): Promise<any | null> {
     const first_array = await this.take(1);
     return first_array.length === 0 ? null : first_array[0];
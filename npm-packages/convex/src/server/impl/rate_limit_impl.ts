@@ -0,0 +1,99 @@
+import {
+  GenericActionCtx,
+  GenericMutationCtx,
+  GenericQueryCtx,
+} from "../registration.js";
+import { RateLimitOptions, RateLimitStatus } from "../rate_limit.js";
+import { performAsyncSyscall } from "./syscall.js";
+import { validateArg } from "./validate.js";
+
+type RateLimitCtx =
+  | GenericQueryCtx<any>
+  | GenericMutationCtx<any>
+  | GenericActionCtx<any>;
+
+function isActionCtx(ctx: RateLimitCtx): boolean {
+  return !("db" in ctx);
+}
+
+async function performRateLimit(
+  ctx: RateLimitCtx,
+  name: string,
+  options: RateLimitOptions,
+  check: boolean,
+): Promise<RateLimitStatus> {
+  return await performAsyncSyscall(
+    isActionCtx(ctx) ? "1.0/actions/rateLimit" : "1.0/rateLimit",
+    {
+      name,
+      key: options.key,
+      config: options.config,
+      count: options.count,
+      check,
+    },
+  );
+}
+
+/**
+ * Consume from a rate limit.
+ *
+ * The limit's state is stored by Convex, so every function calling
+ * `rateLimit` with the same `name` and `key` shares it. Nothing is consumed if
+ * the limit is exceeded.
+ *
+ * ```js
+ * const status = await rateLimit(ctx, "sendMessage", {
+ *   key: userId,
+ *   config: { kind: "tokenBucket", rate: 10, period: MINUTE, capacity: 3 },
+ * });
+ * if (!status.ok) {
+ *   throw new Error(`Rate limited, retry in ${status.retryAfter}ms`);
+ * }
+ * ```
+ *
+ * In a mutation, the consumption commits with the mutation. In an action, it
+ * commits immediately.
+ *
+ * @public
+ */
+export async function rateLimit(
+  ctx: GenericMutationCtx<any> | GenericActionCtx<any>,
+  name: string,
+  options: RateLimitOptions,
+): Promise<RateLimitStatus> {
+  validateArg(name, 1, "rateLimit", "name");
+  validateArg(options, 2, "rateLimit", "options");
+  return await performRateLimit(ctx, name, options, false);
+}
+
+/**
+ * Check whether {@link rateLimit} would succeed, without consuming anything.
+ *
+ * @public
+ */
+export async function checkRateLimit(
+  ctx: RateLimitCtx,
+  name: string,
+  options: RateLimitOptions,
+): Promise<RateLimitStatus> {
+  validateArg(name, 1, "checkRateLimit", "name");
+  validateArg(options, 2, "checkRateLimit", "options");
+  return await performRateLimit(ctx, name, options, true);
+}
+
+/**
+ * Reset a rate limit, as if it had never been used.
+ *
+ * @public
+ */
+export async function resetRateLimit(
+  ctx: GenericMutationCtx<any> | GenericActionCtx<any>,
+  name: string,
+  key?: string,
+): Promise<void> {
+  validateArg(name, 1, "resetRateLimit", "name");
+  await performAsyncSyscall(
+    isActionCtx(ctx) ? "1.0/actions/resetRateLimit" : "1.0/rateLimit/reset",
+    { name, key },
+  );
+}
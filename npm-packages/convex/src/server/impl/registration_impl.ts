@@ -11,6 +11,7 @@ import { GenericDataModel } from "../data_model.js";
 import {
   ActionBuilder,
   DefaultFunctionArgs,
+  FunctionLimits,
   GenericActionCtx,
   GenericMutationCtx,
   GenericQueryCtx,
@@ -22,7 +23,13 @@ import {
   RegisteredQuery,
 } from "../registration.js";
 import { setupActionCalls } from "./actions_impl.js";
-import { setupActionVectorSearch } from "./vector_search_impl.js";
+import {
+  setupActionVectorSearch,
+  setupActionVectorSearchPage,
+  setupVectorSearch,
+} from "./vector_search_impl.js";
+import { setupActionHybridSearch } from "./hybrid_search_impl.js";
+import { setupActionSparseVectorSearch } from "./sparse_vector_search_impl.js";
 import { setupAuth } from "./authentication_impl.js";
 import { setupReader, setupWriter } from "./database_impl.js";
 import { QueryImpl, QueryInitializerImpl } from "./query_impl.js";
@@ -52,6 +59,7 @@ async function invokeMutation<
     auth: setupAuth(requestId),
     storage: setupStorageWriter(requestId),
     scheduler: setupMutationScheduler(),
+    vectorSearch: setupVectorSearch() as any,
 
     runQuery: (reference: any, args?: any) => runUdf("query", reference, args),
     runMutation: (reference: any, args?: any) =>
@@ -146,6 +154,7 @@ type FunctionDefinition =
   | {
       args?: GenericValidator | Record<string, GenericValidator>;
       returns?: GenericValidator | Record<string, GenericValidator>;
+      limits?: FunctionLimits;
       handler: (ctx: any, args: DefaultFunctionArgs) => any;
     };
 
@@ -183,6 +192,19 @@ function exportReturns(functionDefinition: FunctionDefinition) {
   };
 }
 
+function exportLimits(functionDefinition: FunctionDefinition) {
+  return () => {
+    let limits: FunctionLimits | null = null;
+    if (
+      typeof functionDefinition === "object" &&
+      functionDefinition.limits !== undefined
+    ) {
+      limits = functionDefinition.limits;
+    }
+    return JSON.stringify(limits);
+  };
+}
+
 /**
  * Define a mutation in this Convex app's public API.
  *
@@ -216,6 +238,7 @@ export const mutationGeneric: MutationBuilder<any, "public"> = ((
   func.invokeMutation = (argsStr) => invokeMutation(handler, argsStr);
   func.exportArgs = exportArgs(functionDefinition);
   func.exportReturns = exportReturns(functionDefinition);
+  func.exportLimits = exportLimits(functionDefinition);
   func._handler = handler;
   return func;
 }) as MutationBuilder<any, "public">;
@@ -252,6 +275,7 @@ export const internalMutationGeneric: MutationBuilder<any, "internal"> = ((
   func.invokeMutation = (argsStr) => invokeMutation(handler, argsStr);
   func.exportArgs = exportArgs(functionDefinition);
   func.exportReturns = exportReturns(functionDefinition);
+  func.exportLimits = exportLimits(functionDefinition);
   func._handler = handler;
   return func;
 }) as MutationBuilder<any, "internal">;
@@ -267,6 +291,7 @@ async function invokeQuery<
     db: setupReader(),
     auth: setupAuth(requestId),
     storage: setupStorageReader(requestId),
+    vectorSearch: setupVectorSearch() as any,
     runQuery: (reference: any, args?: any) => runUdf("query", reference, args),
   };
   const result = await invokeFunction(func, queryCtx, args as any);
@@ -307,6 +332,7 @@ export const queryGeneric: QueryBuilder<any, "public"> = ((
   func.invokeQuery = (argsStr) => invokeQuery(handler, argsStr);
   func.exportArgs = exportArgs(functionDefinition);
   func.exportReturns = exportReturns(functionDefinition);
+  func.exportLimits = exportLimits(functionDefinition);
   func._handler = handler;
   return func;
 }) as QueryBuilder<any, "public">;
@@ -344,6 +370,7 @@ export const internalQueryGeneric: QueryBuilder<any, "internal"> = ((
   func.invokeQuery = (argsStr) => invokeQuery(handler as any, argsStr);
   func.exportArgs = exportArgs(functionDefinition);
   func.exportReturns = exportReturns(functionDefinition);
+  func.exportLimits = exportLimits(functionDefinition);
   func._handler = handler;
   return func;
 }) as QueryBuilder<any, "internal">;
@@ -359,6 +386,9 @@ async function invokeAction<
     scheduler: setupActionScheduler(requestId),
     storage: setupStorageActionWriter(requestId),
     vectorSearch: setupActionVectorSearch(requestId) as any,
+    vectorSearchPage: setupActionVectorSearchPage(requestId) as any,
+    hybridSearch: setupActionHybridSearch(requestId) as any,
+    sparseVectorSearch: setupActionSparseVectorSearch(requestId) as any,
   };
   const result = await invokeFunction(func, ctx, args as any);
   return JSON.stringify(convexToJson(result === undefined ? null : result));
@@ -396,6 +426,7 @@ export const actionGeneric: ActionBuilder<any, "public"> = ((
     invokeAction(handler, requestId, argsStr);
   func.exportArgs = exportArgs(functionDefinition);
   func.exportReturns = exportReturns(functionDefinition);
+  func.exportLimits = exportLimits(functionDefinition);
   func._handler = handler;
   return func;
 }) as ActionBuilder<any, "public">;
@@ -432,6 +463,7 @@ export const internalActionGeneric: ActionBuilder<any, "internal"> = ((
     invokeAction(handler, requestId, argsStr);
   func.exportArgs = exportArgs(functionDefinition);
   func.exportReturns = exportReturns(functionDefinition);
+  func.exportLimits = exportLimits(functionDefinition);
   func._handler = handler;
   return func;
 }) as ActionBuilder<any, "internal">;
@@ -449,6 +481,9 @@ async function invokeHttpAction<
     storage: setupStorageActionWriter(requestId),
     scheduler: setupActionScheduler(requestId),
     vectorSearch: setupActionVectorSearch(requestId) as any,
+    vectorSearchPage: setupActionVectorSearchPage(requestId) as any,
+    hybridSearch: setupActionHybridSearch(requestId) as any,
+    sparseVectorSearch: setupActionSparseVectorSearch(requestId) as any,
   };
   return await invokeFunction(func, ctx, [request]);
 }
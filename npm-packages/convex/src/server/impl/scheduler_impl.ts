@@ -2,19 +2,34 @@ import { convexToJson, Value } from "../../values/index.js";
 import { version } from "../../index.js";
 import { performAsyncSyscall } from "./syscall.js";
 import { parseArgs } from "../../common/index.js";
-import { SchedulableFunctionReference, Scheduler } from "../scheduler.js";
+import {
+  SchedulableFunctionReference,
+  ScheduledFunctionQueue,
+  ScheduledFunctionRetryPolicy,
+  Scheduler,
+} from "../scheduler.js";
 import { Id } from "../../values/value.js";
 import { validateArg } from "./validate.js";
 import { getFunctionAddress } from "../components/paths.js";
 
-export function setupMutationScheduler(): Scheduler {
+type ScheduleOptions = {
+  retry?: ScheduledFunctionRetryPolicy;
+  queue?: ScheduledFunctionQueue;
+};
+
+export function setupMutationScheduler(
+  options: ScheduleOptions = {},
+): Scheduler {
   return {
     runAfter: async (
       delayMs: number,
       functionReference: SchedulableFunctionReference,
       args?: Record<string, Value>,
     ) => {
-      const syscallArgs = runAfterSyscallArgs(delayMs, functionReference, args);
+      const syscallArgs = {
+        ...runAfterSyscallArgs(delayMs, functionReference, args),
+        ...options,
+      };
       return await performAsyncSyscall("1.0/schedule", syscallArgs);
     },
     runAt: async (
@@ -22,11 +37,10 @@ export function setupMutationScheduler(): Scheduler {
       functionReference: SchedulableFunctionReference,
       args?: Record<string, Value>,
     ) => {
-      const syscallArgs = runAtSyscallArgs(
-        ms_since_epoch_or_date,
-        functionReference,
-        args,
-      );
+      const syscallArgs = {
+        ...runAtSyscallArgs(ms_since_epoch_or_date, functionReference, args),
+        ...options,
+      };
       return await performAsyncSyscall("1.0/schedule", syscallArgs);
     },
     cancel: async (id: Id<"_scheduled_functions">) => {
@@ -34,10 +48,20 @@ export function setupMutationScheduler(): Scheduler {
       const args = { id: convexToJson(id) };
       await performAsyncSyscall("1.0/cancel_job", args);
     },
+    withRetry: (retry: ScheduledFunctionRetryPolicy) =>
+      setupMutationScheduler({
+        ...options,
+        retry: validateRetryPolicy(retry),
+      }),
+    withQueue: (queue: ScheduledFunctionQueue) =>
+      setupMutationScheduler({ ...options, queue: validateQueue(queue) }),
   };
 }
 
-export function setupActionScheduler(requestId: string): Scheduler {
+export function setupActionScheduler(
+  requestId: string,
+  options: ScheduleOptions = {},
+): Scheduler {
   return {
     runAfter: async (
       delayMs: number,
@@ -47,6 +71,7 @@ export function setupActionScheduler(requestId: string): Scheduler {
       const syscallArgs = {
         requestId,
         ...runAfterSyscallArgs(delayMs, functionReference, args),
+        ...options,
       };
       return await performAsyncSyscall("1.0/actions/schedule", syscallArgs);
     },
@@ -58,6 +83,7 @@ export function setupActionScheduler(requestId: string): Scheduler {
       const syscallArgs = {
         requestId,
         ...runAtSyscallArgs(ms_since_epoch_or_date, functionReference, args),
+        ...options,
       };
       return await performAsyncSyscall("1.0/actions/schedule", syscallArgs);
     },
@@ -66,9 +92,44 @@ export function setupActionScheduler(requestId: string): Scheduler {
       const syscallArgs = { id: convexToJson(id) };
       return await performAsyncSyscall("1.0/actions/cancel_job", syscallArgs);
     },
+    withRetry: (retry: ScheduledFunctionRetryPolicy) =>
+      setupActionScheduler(requestId, {
+        ...options,
+        retry: validateRetryPolicy(retry),
+      }),
+    withQueue: (queue: ScheduledFunctionQueue) =>
+      setupActionScheduler(requestId, {
+        ...options,
+        queue: validateQueue(queue),
+      }),
   };
 }
 
+function validateRetryPolicy(
+  retry: ScheduledFunctionRetryPolicy,
+): ScheduledFunctionRetryPolicy {
+  if (typeof retry !== "object" || retry === null) {
+    throw new Error("`retry` must be an object");
+  }
+  if (!Number.isInteger(retry.maxAttempts) || retry.maxAttempts < 1) {
+    throw new Error("`retry.maxAttempts` must be a positive integer");
+  }
+  return retry;
+}
+
+function validateQueue(queue: ScheduledFunctionQueue): ScheduledFunctionQueue {
+  if (typeof queue !== "object" || queue === null) {
+    throw new Error("`queue` must be an object");
+  }
+  if (typeof queue.name !== "string") {
+    throw new Error("`queue.name` must be a string");
+  }
+  if (!Number.isInteger(queue.maxConcurrency) || queue.maxConcurrency < 1) {
+    throw new Error("`queue.maxConcurrency` must be a positive integer");
+  }
+  return queue;
+}
+
 function runAfterSyscallArgs(
   delayMs: number,
   functionReference: SchedulableFunctionReference,
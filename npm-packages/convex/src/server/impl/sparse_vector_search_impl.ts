@@ -0,0 +1,52 @@
+import { version } from "../../index.js";
+import { performAsyncSyscall } from "./syscall.js";
+import { validateArg } from "./validate.js";
+import { GenericTableInfo } from "../data_model.js";
+import {
+  SparseVectorSearchQuery,
+  SparseVectorSearchResult,
+} from "../sparse_vector_search.js";
+import {
+  filterBuilderImpl,
+  serializeExpression,
+} from "./vector_search_impl.js";
+
+export function setupActionSparseVectorSearch(requestId: string) {
+  return async (
+    tableName: string,
+    indexName: string,
+    query: SparseVectorSearchQuery<GenericTableInfo>,
+  ): Promise<Array<SparseVectorSearchResult<string>>> => {
+    validateArg(tableName, 1, "sparseVectorSearch", "tableName");
+    validateArg(indexName, 2, "sparseVectorSearch", "indexName");
+    validateArg(query, 3, "sparseVectorSearch", "query");
+    if (
+      !query.vector ||
+      typeof query.vector !== "object" ||
+      Array.isArray(query.vector) ||
+      Object.keys(query.vector).length === 0
+    ) {
+      throw Error(
+        "`vector` must be a non-empty object mapping dimension indexes to " +
+          "weights in sparseVectorSearch",
+      );
+    }
+
+    const { results } = await performAsyncSyscall(
+      "1.0/actions/sparseVectorSearch",
+      {
+        requestId,
+        version,
+        query: {
+          indexName: tableName + "." + indexName,
+          limit: query.limit,
+          vector: query.vector,
+          expressions: query.filter
+            ? serializeExpression(query.filter(filterBuilderImpl))
+            : null,
+        },
+      },
+    );
+    return results;
+  };
+}
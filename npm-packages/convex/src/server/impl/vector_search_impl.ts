@@ -5,6 +5,8 @@ import {
   FilterExpression,
   VectorFilterBuilder,
   VectorSearch,
+  VectorSearchPage,
+  VectorSearchPageResult,
   VectorSearchQuery,
 } from "../vector_search.js";
 import {
@@ -44,6 +46,68 @@ export function setupActionVectorSearch(
   };
 }
 
+export function setupActionVectorSearchPage(
+  requestId: string,
+): VectorSearchPage<GenericDataModel, string, string> {
+  return async (
+    tableName: string,
+    indexName: string,
+    query: VectorSearchQuery<GenericTableInfo, string>,
+  ) => {
+    validateArg(tableName, 1, "vectorSearchPage", "tableName");
+    validateArg(indexName, 2, "vectorSearchPage", "indexName");
+    validateArg(query, 3, "vectorSearchPage", "query");
+    if (
+      !query.vector ||
+      !Array.isArray(query.vector) ||
+      query.vector.length === 0
+    ) {
+      throw Error("`vector` must be a non-empty Array in vectorSearchPage");
+    }
+
+    return await new VectorQueryImpl(
+      requestId,
+      tableName + "." + indexName,
+      query,
+    ).paginate();
+  };
+}
+
+export function setupVectorSearch(): VectorSearch<
+  GenericDataModel,
+  string,
+  string
+> {
+  return async (
+    tableName: string,
+    indexName: string,
+    query: VectorSearchQuery<GenericTableInfo, string>,
+  ) => {
+    validateArg(tableName, 1, "vectorSearch", "tableName");
+    validateArg(indexName, 2, "vectorSearch", "indexName");
+    validateArg(query, 3, "vectorSearch", "query");
+    if (
+      !query.vector ||
+      !Array.isArray(query.vector) ||
+      query.vector.length === 0
+    ) {
+      throw Error("`vector` must be a non-empty Array in vectorSearch");
+    }
+    if (query.cursor !== undefined) {
+      throw Error(
+        "`cursor` is only supported by `vectorSearchPage` in actions. Queries " +
+          "and mutations return the top results at their snapshot.",
+      );
+    }
+
+    const { results } = await performAsyncSyscall("1.0/vectorSearch", {
+      version,
+      query: serializeVectorQuery(tableName + "." + indexName, query),
+    });
+    return results;
+  };
+}
+
 export class VectorQueryImpl {
   private requestId: string;
   private state:
@@ -56,34 +120,33 @@ export class VectorQueryImpl {
     query: VectorSearchQuery<GenericTableInfo, string>,
   ) {
     this.requestId = requestId;
-    const filters = query.filter
-      ? serializeExpression(query.filter(filterBuilderImpl))
-      : null;
-
     this.state = {
       type: "preparing",
-      query: {
-        indexName,
-        limit: query.limit,
-        vector: query.vector,
-        expressions: filters,
-      },
+      query: serializeVectorQuery(indexName, query),
     };
   }
 
   async collect(): Promise<Array<any>> {
+    const { page } = await this.paginate();
+    return page;
+  }
+
+  async paginate(): Promise<VectorSearchPageResult<string>> {
     if (this.state.type === "consumed") {
       throw new Error("This query is closed and can't emit any more values.");
     }
     const query = this.state.query;
     this.state = { type: "consumed" };
 
-    const { results } = await performAsyncSyscall("1.0/actions/vectorSearch", {
-      requestId: this.requestId,
-      version,
-      query,
-    });
-    return results;
+    const { results, continueCursor, isDone } = await performAsyncSyscall(
+      "1.0/actions/vectorSearch",
+      {
+        requestId: this.requestId,
+        version,
+        query,
+      },
+    );
+    return { page: results, continueCursor, isDone };
   }
 }
 
@@ -92,8 +155,27 @@ type SerializedVectorQuery = {
   limit?: number;
   vector: Array<number>;
   expressions: JSONValue;
+  scoreThreshold?: number;
+  cursor?: string;
 };
 
+function serializeVectorQuery(
+  indexName: string,
+  query: VectorSearchQuery<GenericTableInfo, string>,
+): SerializedVectorQuery {
+  const filters = query.filter
+    ? serializeExpression(query.filter(filterBuilderImpl))
+    : null;
+  return {
+    indexName,
+    limit: query.limit,
+    vector: query.vector,
+    expressions: filters,
+    scoreThreshold: query.scoreThreshold,
+    cursor: query.cursor,
+  };
+}
+
 type ExpressionOrValue<T extends Value | undefined> = FilterExpression<T> | T;
 
 // The `any` type parameter in `Expression<any>` allows us to use this class
@@ -0,0 +1,146 @@
+import { convexToJson, jsonToConvex, Value } from "../../values/index.js";
+import { GenericMutationCtx, GenericQueryCtx } from "../registration.js";
+import {
+  DequeueOptions,
+  EnqueueOptions,
+  LeasedMessage,
+  NackOptions,
+} from "../work_queue.js";
+import { performAsyncSyscall } from "./syscall.js";
+import { validateArg } from "./validate.js";
+
+function parseLeases<T extends Value>(leases: any[]): LeasedMessage<T>[] {
+  return leases.map((lease) => ({
+    ...lease,
+    body: jsonToConvex(lease.body) as T,
+  }));
+}
+
+/**
+ * Add a message to a work queue. The message can be dequeued once the
+ * calling mutation commits.
+ *
+ * Queues are created on first use and are scoped to the current component.
+ * Queue names can contain letters, digits, `_` and `-`.
+ *
+ * @returns The id of the message.
+ *
+ * @public
+ */
+export async function enqueueMessage(
+  _ctx: GenericMutationCtx<any>,
+  queue: string,
+  body: Value,
+  options?: EnqueueOptions,
+): Promise<string> {
+  validateArg(queue, 1, "enqueueMessage", "queue");
+  return await performAsyncSyscall("1.0/queue/enqueue", {
+    queue,
+    body: convexToJson(body),
+    delayMs: options?.delayMs,
+    maxAttempts: options?.maxAttempts,
+  });
+}
+
+/**
+ * Lease messages from a work queue.
+ *
+ * Leased messages are hidden from other consumers for
+ * `visibilityTimeoutMs`. Settle each one with {@link ackMessage} once it's
+ * processed, or {@link nackMessage} to retry it. Messages that aren't settled
+ * in time can be dequeued again, and messages that run out of attempts are
+ * dead-lettered.
+ *
+ * Concurrent consumers read from different parts of the queue, so dequeueing
+ * from many mutations at once doesn't cause write conflicts.
+ *
+ * @public
+ */
+export async function dequeueMessages<T extends Value = Value>(
+  _ctx: GenericMutationCtx<any>,
+  queue: string,
+  options: DequeueOptions,
+): Promise<LeasedMessage<T>[]> {
+  validateArg(queue, 1, "dequeueMessages", "queue");
+  validateArg(options, 2, "dequeueMessages", "options");
+  const leases = await performAsyncSyscall("1.0/queue/dequeue", {
+    queue,
+    visibilityTimeoutMs: options.visibilityTimeoutMs,
+    maxMessages: options.maxMessages,
+  });
+  return parseLeases<T>(leases);
+}
+
+/**
+ * Remove a processed message from its queue.
+ *
+ * Throws if the message's lease expired and it was dequeued again.
+ *
+ * @public
+ */
+export async function ackMessage(
+  _ctx: GenericMutationCtx<any>,
+  message: Pick<LeasedMessage, "id" | "attempt">,
+): Promise<void> {
+  validateArg(message, 1, "ackMessage", "message");
+  await performAsyncSyscall("1.0/queue/ack", {
+    id: message.id,
+    attempt: message.attempt,
+  });
+}
+
+/**
+ * Return a message to its queue so it can be retried, or dead-letter it if
+ * it has used up its attempts.
+ *
+ * Throws if the message's lease expired and it was dequeued again.
+ *
+ * @public
+ */
+export async function nackMessage(
+  _ctx: GenericMutationCtx<any>,
+  message: Pick<LeasedMessage, "id" | "attempt">,
+  options?: NackOptions,
+): Promise<void> {
+  validateArg(message, 1, "nackMessage", "message");
+  await performAsyncSyscall("1.0/queue/nack", {
+    id: message.id,
+    attempt: message.attempt,
+    delayMs: options?.delayMs,
+    error: options?.error,
+  });
+}
+
+/**
+ * List the oldest dead-lettered messages of a queue.
+ *
+ * @param limit - The maximum number of messages to return, at most 100.
+ *
+ * @public
+ */
+export async function listDeadLetters<T extends Value = Value>(
+  _ctx: GenericQueryCtx<any>,
+  queue: string,
+  limit?: number,
+): Promise<LeasedMessage<T>[]> {
+  validateArg(queue, 1, "listDeadLetters", "queue");
+  const messages = await performAsyncSyscall("1.0/queue/deadLetters", {
+    queue,
+    limit,
+  });
+  return parseLeases<T>(messages);
+}
+
+/**
+ * Move a dead-lettered message back onto its queue with a fresh set of
+ * attempts.
+ *
+ * @public
+ */
+export async function redriveMessage(
+  _ctx: GenericMutationCtx<any>,
+  id: string,
+): Promise<void> {
+  validateArg(id, 1, "redriveMessage", "id");
+  await performAsyncSyscall("1.0/queue/redrive", { id });
+}
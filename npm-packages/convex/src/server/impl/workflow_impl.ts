@@ -0,0 +1,281 @@
+import {
+  convexToJson,
+  jsonToConvex,
+  JSONValue,
+  v,
+  Value,
+} from "../../values/index.js";
+import { Id } from "../../values/value.js";
+import { parseArgs } from "../../common/index.js";
+import { GenericDataModel } from "../data_model.js";
+import {
+  GenericMutationCtx,
+  GenericQueryCtx,
+  RegisteredMutation,
+} from "../registration.js";
+import {
+  WorkflowReference,
+  WorkflowStatus,
+  WorkflowStep,
+  WorkflowStepStatus,
+} from "../workflow.js";
+import { getFunctionAddress } from "../components/paths.js";
+import { internalMutationGeneric } from "./registration_impl.js";
+import { performAsyncSyscall } from "./syscall.js";
+import { validateArg } from "./validate.js";
+
+type StepKind = WorkflowStepStatus["kind"];
+
+// A journaled step as returned by the workflow syscalls, with its result
+// still encoded as JSON.
+type JournalEntry = Omit<WorkflowStepStatus, "result"> & { result?: JSONValue };
+
+// Thrown to unwind the handler when it reaches a step that hasn't finished.
+class WorkflowSuspended extends Error {
+  constructor() {
+    super("Workflow suspended until its current step finishes");
+  }
+}
+
+function errorMessage(e: unknown): string {
+  return e instanceof Error ? e.message : String(e);
+}
+
+function functionLabel(functionReference: any): string {
+  const address = getFunctionAddress(functionReference);
+  return address.name ?? address.reference ?? address.functionHandle;
+}
+
+class WorkflowStepImpl implements WorkflowStep {
+  private nextStepNumber = 0;
+  suspended = false;
+
+  constructor(
+    private ctx: GenericMutationCtx<GenericDataModel>,
+    private workflowId: string,
+    private journal: JournalEntry[],
+  ) {}
+
+  private async step(
+    kind: StepKind,
+    name: string,
+    run: () => Promise<Record<string, any>>,
+  ): Promise<any> {
+    if (this.suspended) {
+      throw new WorkflowSuspended();
+    }
+    const stepNumber = this.nextStepNumber++;
+    let entry = this.journal[stepNumber];
+    if (entry !== undefined) {
+      if (entry.kind !== kind || entry.name !== name) {
+        throw new Error(
+          `Workflow is not deterministic: step ${stepNumber} was ${entry.kind} "${entry.name}" but is now ${kind} "${name}"`,
+        );
+      }
+    } else {
+      entry = await performAsyncSyscall("1.0/workflow/step", {
+        workflowId: this.workflowId,
+        stepNumber,
+        name,
+        kind: { type: kind, ...(await run()) },
+      });
+    }
+    switch (entry.state) {
+      case "completed":
+        return jsonToConvex(entry.result!);
+      case "failed":
+        throw new Error(entry.error);
+      default:
+        this.suspended = true;
+        throw new WorkflowSuspended();
+    }
+  }
+
+  async runQuery(query: any, args?: any) {
+    return await this.step("query", functionLabel(query), async () => {
+      try {
+        const result = await this.ctx.runQuery(query, args);
+        return { result: convexToJson(result === undefined ? null : result) };
+      } catch (e: unknown) {
+        return { error: errorMessage(e) };
+      }
+    });
+  }
+
+  async runMutation(mutation: any, args?: any) {
+    return await this.step("mutation", functionLabel(mutation), async () => {
+      try {
+        const result = await this.ctx.runMutation(mutation, args);
+        return { result: convexToJson(result === undefined ? null : result) };
+      } catch (e: unknown) {
+        return { error: errorMessage(e) };
+      }
+    });
+  }
+
+  async runAction(action: any, args?: any) {
+    return await this.step("action", functionLabel(action), async () => ({
+      ...getFunctionAddress(action),
+      args: convexToJson(parseArgs(args)),
+    }));
+  }
+
+  async sleep(ms: number) {
+    if (typeof ms !== "number" || !isFinite(ms) || ms < 0) {
+      throw new Error("`ms` must be a non-negative finite number");
+    }
+    await this.step("sleep", "sleep", async () => ({ ms }));
+  }
+
+  async waitForEvent<T extends Value = Value>(event: string): Promise<T> {
+    validateArg(event, 1, "waitForEvent", "event");
+    return await this.step("waitForEvent", event, async () => ({ event }));
+  }
+}
+
+/**
+ * Define a durable workflow.
+ *
+ * The workflow is an internal mutation that the scheduler reruns each time
+ * one of its steps finishes. Start it with {@link startWorkflow}.
+ *
+ * ```js
+ * export const fulfillOrder = workflowGeneric({
+ *   handler: async (step, { orderId }) => {
+ *     await step.runAction(internal.payments.charge, { orderId });
+ *     await step.waitForEvent("shipped");
+ *     await step.runAction(internal.emails.sendReceipt, { orderId });
+ *   },
+ * });
+ * ```
+ *
+ * @param workflow - The workflow's handler, which receives a
+ * {@link WorkflowStep} to run its steps and the arguments the workflow was
+ * started with.
+ * @returns The wrapped workflow. Export it to register it.
+ *
+ * @public
+ */
+export function workflowGeneric<
+  Args extends Record<string, Value> = Record<string, Value>,
+  Returns extends Value | void = Value | void,
+>(workflow: {
+  handler: (step: WorkflowStep, args: Args) => Promise<Returns>;
+}): RegisteredMutation<"internal", { workflowId: string }, null> {
+  return internalMutationGeneric({
+    args: { workflowId: v.string() },
+    handler: async (
+      ctx: GenericMutationCtx<GenericDataModel>,
+      { workflowId }: { workflowId: string },
+    ) => {
+      const journal = await performAsyncSyscall("1.0/workflow/load", {
+        workflowId,
+      });
+      if (journal === null) {
+        // The workflow already finished or was canceled.
+        return null;
+      }
+      const step = new WorkflowStepImpl(ctx, workflowId, journal.steps);
+      let result;
+      try {
+        result = await workflow.handler(
+          step,
+          jsonToConvex(journal.args) as Args,
+        );
+      } catch (e: unknown) {
+        if (!step.suspended) {
+          await performAsyncSyscall("1.0/workflow/finish", {
+            workflowId,
+            error: errorMessage(e),
+          });
+        }
+        return null;
+      }
+      if (!step.suspended) {
+        await performAsyncSyscall("1.0/workflow/finish", {
+          workflowId,
+          result: convexToJson(result === undefined ? null : (result as Value)),
+        });
+      }
+      return null;
+    },
+  }) as RegisteredMutation<"internal", { workflowId: string }, null>;
+}
+
+/**
+ * Start a workflow defined with {@link workflowGeneric}.
+ *
+ * The workflow's first run is scheduled when the calling mutation commits.
+ *
+ * @returns The id of the workflow, for use with {@link getWorkflowStatus},
+ * {@link sendWorkflowEvent} and {@link cancelWorkflow}.
+ *
+ * @public
+ */
+export async function startWorkflow(
+  _ctx: GenericMutationCtx<any>,
+  workflow: WorkflowReference,
+  args?: Record<string, Value>,
+): Promise<Id<"_workflows">> {
+  return await performAsyncSyscall("1.0/workflow/start", {
+    ...getFunctionAddress(workflow),
+    args: convexToJson(parseArgs(args)),
+  });
+}
+
+/**
+ * Get the status of a workflow and its journaled steps.
+ *
+ * @public
+ */
+export async function getWorkflowStatus(
+  _ctx: GenericQueryCtx<any>,
+  workflowId: Id<"_workflows">,
+): Promise<WorkflowStatus> {
+  validateArg(workflowId, 1, "getWorkflowStatus", "workflowId");
+  const status = await performAsyncSyscall("1.0/workflow/status", {
+    workflowId,
+  });
+  return {
+    ...status,
+    result:
+      status.result === undefined ? undefined : jsonToConvex(status.result),
+    steps: status.steps.map((step: any) => ({
+      ...step,
+      result: step.result === undefined ? undefined : jsonToConvex(step.result),
+    })),
+  };
+}
+
+/**
+ * Send an event to a workflow waiting for it with `step.waitForEvent`.
+ *
+ * @public
+ */
+export async function sendWorkflowEvent(
+  _ctx: GenericMutationCtx<any>,
+  workflowId: Id<"_workflows">,
+  event: string,
+  value?: Value,
+): Promise<void> {
+  validateArg(workflowId, 1, "sendWorkflowEvent", "workflowId");
+  validateArg(event, 2, "sendWorkflowEvent", "event");
+  await performAsyncSyscall("1.0/workflow/sendEvent", {
+    workflowId,
+    event,
+    value: convexToJson(value === undefined ? null : value),
+  });
+}
+
+/**
+ * Cancel a running workflow, along with the action it is waiting on.
+ *
+ * @public
+ */
+export async function cancelWorkflow(
+  _ctx: GenericMutationCtx<any>,
+  workflowId: Id<"_workflows">,
+): Promise<void> {
+  validateArg(workflowId, 1, "cancelWorkflow", "workflowId");
+  await performAsyncSyscall("1.0/workflow/cancel", { workflowId });
+}
@@ -103,10 +103,18 @@ export {
 } from "./impl/registration_impl.js";
 export type { IndexRange, IndexRangeBuilder } from "./index_range_builder.js";
 export * from "./pagination.js";
-export type { OrderedQuery, Query, QueryInitializer } from "./query.js";
+export type {
+  OrderedQuery,
+  Query,
+  QueryInitializer,
+  SearchFacet,
+  SearchQuery,
+  SearchResultsWithFacets,
+} from "./query.js";
 export type {
   ArgsArray,
   DefaultFunctionArgs,
+  FunctionLimits,
   FunctionVisibility,
   ActionBuilder,
   MutationBuilder,
@@ -132,9 +140,51 @@ export type {
 } from "./registration.js";
 export * from "./search_filter_builder.js";
 export * from "./storage.js";
-export type { Scheduler, SchedulableFunctionReference } from "./scheduler.js";
+export type {
+  Scheduler,
+  SchedulableFunctionReference,
+  ScheduledFunctionQueue,
+  ScheduledFunctionRetryPolicy,
+} from "./scheduler.js";
 export { cronJobs } from "./cron.js";
-export type { CronJob, Crons } from "./cron.js";
+export type { CatchUpPolicy, CronJob, CronOptions, Crons } from "./cron.js";
+export {
+  workflowGeneric,
+  startWorkflow,
+  getWorkflowStatus,
+  sendWorkflowEvent,
+  cancelWorkflow,
+} from "./impl/workflow_impl.js";
+export type {
+  WorkflowReference,
+  WorkflowStatus,
+  WorkflowStep,
+  WorkflowStepStatus,
+} from "./workflow.js";
+export {
+  enqueueMessage,
+  dequeueMessages,
+  ackMessage,
+  nackMessage,
+  listDeadLetters,
+  redriveMessage,
+} from "./impl/work_queue_impl.js";
+export type {
+  DequeueOptions,
+  EnqueueOptions,
+  LeasedMessage,
+  NackOptions,
+} from "./work_queue.js";
+export {
+  rateLimit,
+  checkRateLimit,
+  resetRateLimit,
+} from "./impl/rate_limit_impl.js";
+export type {
+  RateLimitConfig,
+  RateLimitOptions,
+  RateLimitStatus,
+} from "./rate_limit.js";
 export type {
   SystemFields,
   IdField,
@@ -189,11 +239,19 @@ export type {
 /**
  * @internal
  */
-export type { Index, SearchIndex, VectorIndex } from "./schema.js";
+export type {
+  Index,
+  SearchIndex,
+  SparseVectorIndex,
+  VectorIndex,
+} from "./schema.js";
 
 export type {
   SearchIndexConfig,
+  SparseVectorIndexConfig,
   VectorIndexConfig,
+  VectorDistanceMetric,
+  VectorQuantizationConfig,
   TableDefinition,
   SchemaDefinition,
   DefineSchemaOptions,
@@ -206,11 +264,24 @@ export { defineTable, defineSchema } from "./schema.js";
 
 export type {
   VectorSearch,
+  VectorSearchPage,
+  VectorSearchPageResult,
   VectorSearchQuery,
   VectorFilterBuilder,
   FilterExpression,
 } from "./vector_search.js";
 
+export type {
+  SparseVectorSearchQuery,
+  SparseVectorSearchResult,
+} from "./sparse_vector_search.js";
+
+export type {
+  HybridSearchQuery,
+  HybridSearchResult,
+  HybridSearchComponentScore,
+} from "./hybrid_search.js";
+
 /**
  * @public
  */
@@ -1,3 +1,4 @@
+import { Value } from "../values/index.js";
 import {
   DocumentByInfo,
   GenericTableInfo,
@@ -93,7 +94,7 @@ export interface QueryInitializer<TableInfo extends GenericTableInfo>
         NamedSearchIndex<TableInfo, IndexName>
       >,
     ) => SearchFilter,
-  ): OrderedQuery<TableInfo>;
+  ): SearchQuery<TableInfo, IndexName>;
 
   /**
    * The number of documents in the table.
@@ -229,3 +230,76 @@ export interface OrderedQuery<TableInfo extends GenericTableInfo>
    */
   unique(): Promise<DocumentByInfo<TableInfo> | null>;
 }
+
+/**
+ * A {@link Query} against a search index, returning documents in relevance
+ * order.
+ *
+ * @public
+ */
+export interface SearchQuery<
+  TableInfo extends GenericTableInfo,
+  IndexName extends SearchIndexNames<TableInfo>,
+> extends OrderedQuery<TableInfo> {
+  /**
+   * Execute the search, returning the first `n` results along with counts of
+   * the most common values of some of the index's `filterFields` across all
+   * of the search's matches.
+   *
+   * Facets can't be combined with `.filter(...)`.
+   *
+   * @param n - The number of results to return.
+   * @param facets - The filter fields to count values for, and the maximum
+   * number of distinct values to return per field (10 by default).
+   * @returns - The first `n` results of the search and its facet counts.
+   */
+  takeWithFacets<
+    FacetField extends NamedSearchIndex<TableInfo, IndexName>["filterFields"],
+  >(
+    n: number,
+    facets: { fields: FacetField[]; limit?: number },
+  ): Promise<SearchResultsWithFacets<DocumentByInfo<TableInfo>, FacetField>>;
+}
+
+/**
+ * The results of {@link SearchQuery.takeWithFacets}.
+ *
+ * @public
+ */
+export interface SearchResultsWithFacets<
+  Document,
+  FacetField extends string = string,
+> {
+  /**
+   * The most relevant results of the search.
+   */
+  results: Document[];
+  /**
+   * The counts for each requested field, in the order they were requested.
+   */
+  facets: SearchFacet<FacetField>[];
+  /**
+   * Search queries only consider their 1024 most relevant matches. If the
+   * search matched more documents than that, the facet counts only cover
+   * the most relevant ones and this is `true`.
+   */
+  isTruncated: boolean;
+}
+
+/**
+ * The most common values of a filter field across a search's matches.
+ *
+ * @public
+ */
+export interface SearchFacet<FacetField extends string = string> {
+  field: FacetField;
+  /**
+   * Counts sorted from most to least common. A `value` of `undefined` counts
+   * the documents that don't have the field.
+   */
+  counts: { value: Value | undefined; count: number }[];
+  /**
+   * The number of distinct values that didn't fit within the facet limit.
+   */
+  numOmittedValues: number;
+}
@@ -0,0 +1,64 @@
+/**
+ * How a rate limit is enforced.
+ *
+ * - `fixedWindow` allows `rate` units per `period`, resetting at the start of
+ *   each period.
+ * - `tokenBucket` refills `rate` tokens per `period`, up to `capacity`
+ *   (defaulting to `rate`), so bursts of up to `capacity` are allowed.
+ *
+ * `period` is in milliseconds. A limit's state is split across `shards`
+ * documents, each allowing `1/shards` of the rate. Calls are only denied when
+ * all of the shards together don't have room, and concurrent callers don't
+ * conflict with each other unless they take the last of the room. Spreading
+ * callers across shards keeps them from updating the same document. By
+ * default limits get one shard per 10 units of capacity, up to 16.
+ *
+ * @public
+ */
+export type RateLimitConfig =
+  | {
+      kind: "fixedWindow";
+      rate: number;
+      period: number;
+      shards?: number;
+    }
+  | {
+      kind: "tokenBucket";
+      rate: number;
+      period: number;
+      capacity?: number;
+      shards?: number;
+    };
+
+/**
+ * Options for {@link rateLimit} and {@link checkRateLimit}.
+ *
+ * @public
+ */
+export type RateLimitOptions = {
+  config: RateLimitConfig;
+  /**
+   * Limits with the same name but different keys are tracked separately,
+   * for example to limit each user on their own.
+   */
+  key?: string;
+  /**
+   * How many units to consume. Defaults to 1.
+   */
+  count?: number;
+};
+
+/**
+ * The result of {@link rateLimit} and {@link checkRateLimit}.
+ *
+ * @public
+ */
+export type RateLimitStatus =
+  | { ok: true; retryAfter?: undefined }
+  | {
+      ok: false;
+      /**
+       * How long to wait before retrying, in milliseconds.
+       */
+      retryAfter: number;
+    };
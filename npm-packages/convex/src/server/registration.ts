@@ -24,11 +24,20 @@ import { Id } from "../values/value.js";
 import {
   GenericDataModel,
   NamedTableInfo,
+  SearchIndexNames,
   TableNamesInDataModel,
   VectorIndexNames,
 } from "./data_model.js";
+import { HybridSearchQuery, HybridSearchResult } from "./hybrid_search.js";
+import {
+  SparseVectorSearchQuery,
+  SparseVectorSearchResult,
+} from "./sparse_vector_search.js";
 import { Scheduler } from "./scheduler.js";
-import { VectorSearchQuery } from "./vector_search.js";
+import {
+  VectorSearchPageResult,
+  VectorSearchQuery,
+} from "./vector_search.js";
 import { Expand } from "../type_utils.js";
 import { Validator } from "../values/validators.js";
 
@@ -64,6 +73,32 @@ export interface GenericMutationCtx<DataModel extends GenericDataModel> {
    */
   scheduler: Scheduler;
 
+  /**
+   * Run a vector search on the given table and index.
+   *
+   * The search runs at the mutation's snapshot, including its own writes.
+   * A concurrent write that could change the results, like inserting a
+   * document with a closer vector, conflicts with the mutation.
+   *
+   * @param tableName - The name of the table to query.
+   * @param indexName - The name of the vector index on the table to query.
+   * @param query - A {@link VectorSearchQuery} containing the vector to query,
+   * the number of results to return, and any filters. `cursor` is not
+   * supported; use `vectorSearchPage` from an action to paginate.
+   * @returns A promise of IDs and scores for the documents with the nearest
+   * vectors
+   */
+  vectorSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    IndexName extends VectorIndexNames<NamedTableInfo<DataModel, TableName>>,
+  >(
+    tableName: TableName,
+    indexName: IndexName,
+    query: Expand<
+      VectorSearchQuery<NamedTableInfo<DataModel, TableName>, IndexName>
+    >,
+  ): Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
   /**
    * Call a query function within the same transaction.
    *
@@ -139,6 +174,31 @@ export interface GenericQueryCtx<DataModel extends GenericDataModel> {
    */
   storage: StorageReader;
 
+  /**
+   * Run a vector search on the given table and index.
+   *
+   * The results are reactive: any later write that could change them, like
+   * inserting a document with a closer vector, reruns the query.
+   *
+   * @param tableName - The name of the table to query.
+   * @param indexName - The name of the vector index on the table to query.
+   * @param query - A {@link VectorSearchQuery} containing the vector to query,
+   * the number of results to return, and any filters. `cursor` is not
+   * supported; use `vectorSearchPage` from an action to paginate.
+   * @returns A promise of IDs and scores for the documents with the nearest
+   * vectors
+   */
+  vectorSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    IndexName extends VectorIndexNames<NamedTableInfo<DataModel, TableName>>,
+  >(
+    tableName: TableName,
+    indexName: IndexName,
+    query: Expand<
+      VectorSearchQuery<NamedTableInfo<DataModel, TableName>, IndexName>
+    >,
+  ): Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
   /**
    * Call a query function within the same transaction.
    *
@@ -265,6 +325,72 @@ export interface GenericActionCtx<DataModel extends GenericDataModel> {
       VectorSearchQuery<NamedTableInfo<DataModel, TableName>, IndexName>
     >,
   ): Promise<Array<{ _id: Id<TableName>; _score: number }>>;
+
+  /**
+   * Run a vector search and return one page of results along with a cursor
+   * for the next page. Pass the cursor back as `query.cursor` to continue.
+   *
+   * @param tableName - The name of the table to query.
+   * @param indexName - The name of the vector index on the table to query.
+   * @param query - A {@link VectorSearchQuery} containing the vector to query,
+   * the page size, and any filters.
+   * @returns A promise of a page of IDs and scores along with the cursor to
+   * continue from.
+   */
+  vectorSearchPage<
+    TableName extends TableNamesInDataModel<DataModel>,
+    IndexName extends VectorIndexNames<NamedTableInfo<DataModel, TableName>>,
+  >(
+    tableName: TableName,
+    indexName: IndexName,
+    query: Expand<
+      VectorSearchQuery<NamedTableInfo<DataModel, TableName>, IndexName>
+    >,
+  ): Promise<VectorSearchPageResult<TableName>>;
+
+  /**
+   * Run a text search and a vector search over the same table and fuse their
+   * rankings with reciprocal rank fusion.
+   *
+   * @param tableName - The name of the table to query.
+   * @param query - A {@link HybridSearchQuery} with the text and vector
+   * halves of the search and how to weigh them.
+   * @returns A promise of the fused results, best first, along with each
+   * document's rank and score in the text and vector results.
+   */
+  hybridSearch<
+    TableName extends TableNamesInDataModel<DataModel>,
+    TextIndexName extends SearchIndexNames<
+      NamedTableInfo<DataModel, TableName>
+    >,
+    VectorIndexName extends VectorIndexNames<
+      NamedTableInfo<DataModel, TableName>
+    >,
+  >(
+    tableName: TableName,
+    query: HybridSearchQuery<
+      NamedTableInfo<DataModel, TableName>,
+      TextIndexName,
+      VectorIndexName
+    >,
+  ): Promise<Array<HybridSearchResult<NamedTableInfo<DataModel, TableName>>>>;
+
+  /**
+   * Run a search against a sparse vector index defined with
+   * `defineTable().sparseVectorIndex`.
+   *
+   * @param tableName - The name of the table to query.
+   * @param indexName - The name of the sparse vector index on the table.
+   * @param query - A {@link SparseVectorSearchQuery} containing the sparse
+   * vector to query, the number of results to return, and any filters.
+   * @returns A promise of IDs and dot product scores for the best matching
+   * documents, best first.
+   */
+  sparseVectorSearch<TableName extends TableNamesInDataModel<DataModel>>(
+    tableName: TableName,
+    indexName: string,
+    query: SparseVectorSearchQuery<NamedTableInfo<DataModel, TableName>>,
+  ): Promise<Array<SparseVectorSearchResult<TableName>>>;
 }
 
 /**
@@ -361,6 +487,9 @@ export type RegisteredMutation<
   /** @internal */
   exportReturns(): string;
 
+  /** @internal */
+  exportLimits(): string;
+
   /** @internal */
   _handler: (ctx: GenericMutationCtx<any>, args: Args) => Returns;
 } & VisibilityProperties<Visibility>;
@@ -390,6 +519,9 @@ export type RegisteredQuery<
   /** @internal */
   exportReturns(): string;
 
+  /** @internal */
+  exportLimits(): string;
+
   /** @internal */
   _handler: (ctx: GenericQueryCtx<any>, args: Args) => Returns;
 } & VisibilityProperties<Visibility>;
@@ -419,6 +551,9 @@ export type RegisteredAction<
   /** @internal */
   exportReturns(): string;
 
+  /** @internal */
+  exportLimits(): string;
+
   /** @internal */
   _handler: (ctx: GenericActionCtx<any>, args: Args) => Returns;
 } & VisibilityProperties<Visibility>;
@@ -595,6 +730,43 @@ export type DefaultArgsForOptionalValidator<
     ? [ObjectType<ArgsValidator>]
     : OneArgArray;
 
+/**
+ * Resource limits for a single function, overriding the deployment's
+ * defaults.
+ *
+ * Limits can be tighter or looser than the defaults but can't go over the
+ * deployment's maximums. Read and write limits only apply to queries and
+ * mutations, and limits aren't applied to actions that run in Node.js.
+ *
+ * @public
+ */
+export type FunctionLimits = {
+  /**
+   * How long the function's JavaScript can run, in milliseconds.
+   */
+  timeoutMs?: number;
+  /**
+   * How much JavaScript memory the function can use, in megabytes.
+   */
+  memoryMb?: number;
+  /**
+   * How many documents the function can read.
+   */
+  maxDocumentsRead?: number;
+  /**
+   * How many bytes of documents the function can read.
+   */
+  maxBytesRead?: number;
+  /**
+   * How many documents the function can write.
+   */
+  maxDocumentsWritten?: number;
+  /**
+   * How many bytes of documents the function can write.
+   */
+  maxBytesWritten?: number;
+};
+
 /**
  * Internal type helper used by Convex code generation.
  *
@@ -646,6 +818,17 @@ export type MutationBuilder<
            * ```
            */
           returns?: ReturnsValidator;
+          /**
+           * Resource limits for this function.
+           *
+           * Examples:
+           *
+           * ```
+           * limits: { timeoutMs: 500 }
+           * limits: { maxDocumentsRead: 100, maxDocumentsWritten: 10 }
+           * ```
+           */
+          limits?: FunctionLimits;
           /**
            * The implementation of this function.
            *
@@ -739,6 +922,17 @@ export type MutationBuilderWithTable<
            * ```
            */
           returns?: ReturnsValidator;
+          /**
+           * Resource limits for this function.
+           *
+           * Examples:
+           *
+           * ```
+           * limits: { timeoutMs: 500 }
+           * limits: { maxDocumentsRead: 100, maxDocumentsWritten: 10 }
+           * ```
+           */
+          limits?: FunctionLimits;
           /**
            * The implementation of this function.
            *
@@ -832,6 +1026,17 @@ export type QueryBuilder<
            * ```
            */
           returns?: ReturnsValidator;
+          /**
+           * Resource limits for this function.
+           *
+           * Examples:
+           *
+           * ```
+           * limits: { timeoutMs: 500 }
+           * limits: { maxDocumentsRead: 100, maxDocumentsWritten: 10 }
+           * ```
+           */
+          limits?: FunctionLimits;
           /**
            * The implementation of this function.
            *
@@ -921,6 +1126,17 @@ export type QueryBuilderWithTable<
            * ```
            */
           returns?: ReturnsValidator;
+          /**
+           * Resource limits for this function.
+           *
+           * Examples:
+           *
+           * ```
+           * limits: { timeoutMs: 500 }
+           * limits: { maxDocumentsRead: 100, maxDocumentsWritten: 10 }
+           * ```
+           */
+          limits?: FunctionLimits;
           /**
            * The implementation of this function.
            *
@@ -1011,6 +1227,17 @@ export type ActionBuilder<
            * ```
            */
           returns?: ReturnsValidator;
+          /**
+           * Resource limits for this function.
+           *
+           * Examples:
+           *
+           * ```
+           * limits: { timeoutMs: 5000 }
+           * limits: { memoryMb: 32 }
+           * ```
+           */
+          limits?: Pick<FunctionLimits, "timeoutMs" | "memoryMb">;
           /**
            * The implementation of this function.
            *
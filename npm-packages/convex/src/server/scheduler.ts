@@ -13,6 +13,66 @@ export type SchedulableFunctionReference = FunctionReference<
   "public" | "internal"
 >;
 
+/**
+ * How to rerun a scheduled function that fails.
+ *
+ * Retries are scheduled with exponential backoff: the n-th retry waits
+ * `baseDelayMs * 2^(n-1)` milliseconds, with a random `jitter` fraction of that
+ * delay taken off.
+ *
+ * @public
+ */
+export type ScheduledFunctionRetryPolicy = {
+  /**
+   * The most times the function runs, including the first run. Must be
+   * between 1 and 20.
+   */
+  maxAttempts: number;
+  /**
+   * The delay before the first retry in milliseconds. Defaults to 1000.
+   */
+  baseDelayMs?: number;
+  /**
+   * The fraction of each delay, between 0 and 1, that's randomized. Defaults
+   * to 0.5.
+   */
+  jitter?: number;
+  /**
+   * Which failures to retry. `"developerError"` retries functions that throw,
+   * and `"interrupted"` reruns actions that were interrupted by a transient
+   * error, giving up at-most-once execution. Defaults to `["developerError"]`.
+   */
+  retryOn?: ("developerError" | "interrupted")[];
+};
+
+/**
+ * A named queue for scheduled functions.
+ *
+ * At most `maxConcurrency` functions from the queue run at once, so a burst
+ * of scheduled functions in one queue can't starve other scheduled functions.
+ * Functions scheduled without a queue share a default queue.
+ *
+ * @public
+ */
+export type ScheduledFunctionQueue = {
+  /**
+   * The queue's name: letters, digits, underscores and dashes. Queues are
+   * scoped to the component that schedules onto them.
+   */
+  name: string;
+  /**
+   * The most functions from this queue that run at once.
+   */
+  maxConcurrency: number;
+  /**
+   * Queues with a higher priority get free execution slots first. The default
+   * queue has priority 0, which is also the default. A function that has been
+   * ready to run for over a minute goes ahead of higher priority queues, so
+   * busy queues can't hold back lower priority ones forever.
+   */
+  priority?: number;
+};
+
 /**
  * An interface to schedule Convex functions.
  *
@@ -69,4 +129,37 @@ export interface Scheduler {
    * @param id
    */
   cancel(id: Id<"_scheduled_functions">): Promise<void>;
+
+  /**
+   * Returns a scheduler whose functions are rerun on failure according to
+   * `retry`.
+   *
+   * ```ts
+   * await ctx.scheduler
+   *   .withRetry({ maxAttempts: 5 })
+   *   .runAfter(0, internal.emails.send, { to });
+   * ```
+   *
+   * The number of retries so far is in the `retries` field of the function's
+   * `_scheduled_functions` document.
+   *
+   * @param retry - The {@link ScheduledFunctionRetryPolicy} to use.
+   */
+  withRetry(retry: ScheduledFunctionRetryPolicy): Scheduler;
+
+  /**
+   * Returns a scheduler whose functions run on the named `queue`.
+   *
+   * ```ts
+   * await ctx.scheduler
+   *   .withQueue({ name: "emails", maxConcurrency: 10 })
+   *   .runAfter(0, internal.emails.send, { to });
+   * ```
+   *
+   * The queue's settings are stored once per queue, and scheduling onto the
+   * queue with different settings replaces them for all of its functions.
+   *
+   * @param queue - The {@link ScheduledFunctionQueue} to use.
+   */
+  withQueue(queue: ScheduledFunctionQueue): Scheduler;
 }
@@ -587,6 +587,28 @@ test("defineTable collects indexes", () => {
   ]);
 });
 
+test("defineTable collects sparse vector indexes", () => {
+  const table = defineTable({
+    embedding: v.record(v.string(), v.float64()),
+    genre: v.string(),
+  }).sparseVectorIndex("by_embedding", {
+    vectorField: "embedding",
+    filterFields: ["genre"],
+  });
+
+  expect(table.export().sparseVectorIndexes).toEqual([
+    {
+      indexDescriptor: "by_embedding",
+      vectorField: "embedding",
+      filterFields: ["genre"],
+    },
+  ]);
+  const schema = defineSchema({ table, other: defineTable({ a: v.string() }) });
+  const [exported, other] = JSON.parse(schema.export()).tables;
+  expect(exported.sparseVectorIndexes).toHaveLength(1);
+  expect(other).not.toHaveProperty("sparseVectorIndexes");
+});
+
 test("Experimental API table.[' indexes']() returns indexes", () => {
   const table = defineTable({
     a: v.string(),
@@ -94,6 +94,17 @@ export interface SearchIndexConfig<
    * Additional fields to index for fast filtering when running search queries.
    */
   filterFields?: FilterFields[];
+
+  /**
+   * Groups of words or phrases that search queries should treat as
+   * equivalent, like `["tv", "television"]`.
+   *
+   * Multi-word synonyms like `["tv", "flat screen"]` only match documents
+   * containing every word of the phrase.
+   *
+   * Synonyms are stored with the index, so changing them rebuilds it.
+   */
+  synonyms?: string[][];
 }
 
 /**
@@ -119,8 +130,65 @@ export interface VectorIndexConfig<
    * Additional fields to index for fast filtering when running vector searches.
    */
   filterFields?: FilterFields[];
+  /**
+   * How vectors are compared to the query vector. Defaults to `"cosine"`.
+   *
+   * With `"euclidean"`, the `_score` of each result is the distance to the
+   * query vector, so lower scores are closer matches.
+   */
+  distanceMetric?: VectorDistanceMetric;
+  /**
+   * Compress the vectors in this index to reduce its memory footprint, at some
+   * cost to search accuracy.
+   *
+   * `"scalar"` stores each component in one byte and `"binary"` in one bit.
+   * By default results are rescored against the full precision vectors;
+   * set `rescore: false` to skip this.
+   */
+  quantization?: VectorQuantizationConfig;
+}
+
+/**
+ * The configuration for a sparse vector index.
+ *
+ * @public
+ */
+export interface SparseVectorIndexConfig<
+  VectorField extends string,
+  FilterFields extends string,
+> {
+  /**
+   * The field to index for sparse vector search.
+   *
+   * Sparse vectors are objects mapping a dimension index to its weight, e.g.
+   * `{ "3": 0.5, "1024": 1.25 }`, so this should be a field of type
+   * `v.record(v.string(), v.float64())`.
+   */
+  vectorField: VectorField;
+  /**
+   * Additional fields to index for fast filtering when running sparse vector
+   * searches.
+   */
+  filterFields?: FilterFields[];
 }
 
+/**
+ * The distance metric used by a vector index.
+ *
+ * @public
+ */
+export type VectorDistanceMetric = "cosine" | "dotProduct" | "euclidean";
+
+/**
+ * The quantization configuration for a vector index.
+ *
+ * @public
+ */
+export type VectorQuantizationConfig = {
+  type: "scalar" | "binary";
+  rescore?: boolean;
+};
+
 /**
  * @internal
  */
@@ -129,6 +197,17 @@ export type VectorIndex = {
   vectorField: string;
   dimensions: number;
   filterFields: string[];
+  distanceMetric?: VectorDistanceMetric;
+  quantization?: VectorQuantizationConfig;
+};
+
+/**
+ * @internal
+ */
+export type SparseVectorIndex = {
+  indexDescriptor: string;
+  vectorField: string;
+  filterFields: string[];
 };
 
 /**
@@ -146,6 +225,7 @@ export type SearchIndex = {
   indexDescriptor: string;
   searchField: string;
   filterFields: string[];
+  synonyms?: string[][];
 };
 /**
  * The definition of a table within a schema.
@@ -165,6 +245,7 @@ export class TableDefinition<
   private stagedSearchIndexes: SearchIndex[];
   private vectorIndexes: VectorIndex[];
   private stagedVectorIndexes: VectorIndex[];
+  private sparseVectorIndexes: SparseVectorIndex[];
   // The type of documents stored in this table.
   validator: DocumentType;
 
@@ -178,6 +259,7 @@ export class TableDefinition<
     this.stagedSearchIndexes = [];
     this.vectorIndexes = [];
     this.stagedVectorIndexes = [];
+    this.sparseVectorIndexes = [];
     this.validator = documentType;
   }
 
@@ -285,6 +367,7 @@ export class TableDefinition<
       indexDescriptor: name,
       searchField: indexConfig.searchField,
       filterFields: indexConfig.filterFields || [],
+      synonyms: indexConfig.synonyms,
     });
     return this;
   }
@@ -311,6 +394,7 @@ export class TableDefinition<
       indexDescriptor: name,
       searchField: indexConfig.searchField,
       filterFields: indexConfig.filterFields || [],
+      synonyms: indexConfig.synonyms,
     });
     return this;
   }
@@ -352,6 +436,8 @@ export class TableDefinition<
       vectorField: indexConfig.vectorField,
       dimensions: indexConfig.dimensions,
       filterFields: indexConfig.filterFields || [],
+      distanceMetric: indexConfig.distanceMetric,
+      quantization: indexConfig.quantization,
     });
     return this;
   }
@@ -379,6 +465,35 @@ export class TableDefinition<
       vectorField: indexConfig.vectorField,
       dimensions: indexConfig.dimensions,
       filterFields: indexConfig.filterFields || [],
+      distanceMetric: indexConfig.distanceMetric,
+      quantization: indexConfig.quantization,
+    });
+    return this;
+  }
+
+  /**
+   * Define a sparse vector index on this table.
+   *
+   * Sparse vector indexes score documents by the dot product of their sparse
+   * vector with the query's, which suits keyword-weighted embeddings like
+   * SPLADE. Search them with `ctx.sparseVectorSearch` from an action.
+   *
+   * @param name - The name of the index.
+   * @param indexConfig - The sparse vector index configuration object.
+   * @returns A {@link TableDefinition} with this sparse vector index included.
+   */
+  sparseVectorIndex<
+    IndexName extends string,
+    VectorField extends ExtractFieldPaths<DocumentType>,
+    FilterFields extends ExtractFieldPaths<DocumentType> = never,
+  >(
+    name: IndexName,
+    indexConfig: Expand<SparseVectorIndexConfig<VectorField, FilterFields>>,
+  ): TableDefinition<DocumentType, Indexes, SearchIndexes, VectorIndexes> {
+    this.sparseVectorIndexes.push({
+      indexDescriptor: name,
+      vectorField: indexConfig.vectorField,
+      filterFields: indexConfig.filterFields || [],
     });
     return this;
   }
@@ -415,6 +530,7 @@ export class TableDefinition<
       stagedSearchIndexes: this.stagedSearchIndexes,
       vectorIndexes: this.vectorIndexes,
       stagedVectorIndexes: this.stagedVectorIndexes,
+      sparseVectorIndexes: this.sparseVectorIndexes,
       documentType,
     };
   }
@@ -539,6 +655,7 @@ export class SchemaDefinition<
           stagedSearchIndexes,
           vectorIndexes,
           stagedVectorIndexes,
+          sparseVectorIndexes,
           documentType,
         } = definition.export();
         return {
@@ -549,6 +666,9 @@ export class SchemaDefinition<
           stagedSearchIndexes,
           vectorIndexes,
           stagedVectorIndexes,
+          // Only send sparse vector indexes when there are some so that the
+          // exported schema is unchanged for tables without them.
+          ...(sparseVectorIndexes.length > 0 ? { sparseVectorIndexes } : {}),
           documentType,
         };
       }),
@@ -678,6 +798,7 @@ const _systemSchema = defineSchema({
     args: v.array(v.any()),
     scheduledTime: v.float64(),
     completedTime: v.optional(v.float64()),
+    retries: v.optional(v.float64()),
     state: v.union(
       v.object({ kind: v.literal("pending") }),
       v.object({ kind: v.literal("inProgress") }),
@@ -0,0 +1,46 @@
+import { Id } from "../values/value.js";
+import {
+  DocumentByInfo,
+  GenericTableInfo,
+  GenericVectorIndexConfig,
+} from "./data_model.js";
+import { FilterExpression, VectorFilterBuilder } from "./vector_search.js";
+
+/**
+ * An object with parameters for performing a search against a sparse vector
+ * index.
+ * @public
+ */
+export interface SparseVectorSearchQuery<TableInfo extends GenericTableInfo> {
+  /**
+   * The query vector, as an object mapping dimension indexes to weights.
+   *
+   * Documents are scored by the dot product of their vector with this one,
+   * and only documents sharing a nonzero dimension with it are returned.
+   */
+  vector: Record<number, number>;
+  /**
+   * The number of results to return. If specified, must be between 1 and 256
+   * inclusive.
+   *
+   * @default 10
+   */
+  limit?: number;
+  /**
+   * Optional filter expression made up of `q.or` and `q.eq` operating
+   * over the filter fields of the index.
+   */
+  filter?: (
+    q: VectorFilterBuilder<DocumentByInfo<TableInfo>, GenericVectorIndexConfig>,
+  ) => FilterExpression<boolean>;
+}
+
+/**
+ * A result of a sparse vector search. `_score` is the dot product of the
+ * document's vector with the query vector, so higher scores are better.
+ * @public
+ */
+export type SparseVectorSearchResult<TableName extends string> = {
+  _id: Id<TableName>;
+  _score: number;
+};
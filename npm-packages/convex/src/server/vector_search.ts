@@ -50,8 +50,50 @@ export interface VectorSearchQuery<
       NamedVectorIndex<TableInfo, IndexName>
     >,
   ) => FilterExpression<boolean>;
+  /**
+   * Only return results scoring at least this well. For indexes using
+   * `"euclidean"` distance this is the maximum distance to return.
+   */
+  scoreThreshold?: number;
+  /**
+   * The `continueCursor` of a previous page, to fetch the results after it.
+   *
+   * Every page of a search is read at the same snapshot as its first page.
+   * That snapshot is only kept for about 10 seconds, so a cursor used later
+   * fails with a `VectorSearchCursorExpired` error and the search has to be
+   * restarted without one. A search can page through at most 4096 results in
+   * total; going deeper fails with a `VectorPaginationTooDeepError` error.
+   * Only supported from actions.
+   */
+  cursor?: string;
+}
+
+/**
+ * A page of results from {@link VectorSearchPage}.
+ * @public
+ */
+export interface VectorSearchPageResult<TableName extends string> {
+  page: Array<{ _id: Id<TableName>; _score: number }>;
+  /**
+   * Have we reached the end of the results?
+   */
+  isDone: boolean;
+  /**
+   * Pass this as the `cursor` of the same query to fetch the next page.
+   */
+  continueCursor: string | null;
 }
 
+export type VectorSearchPage<
+  DataModel extends GenericDataModel,
+  TableName extends TableNamesInDataModel<DataModel>,
+  IndexName extends VectorIndexNames<NamedTableInfo<DataModel, TableName>>,
+> = (
+  tableName: TableName,
+  indexName: IndexName,
+  query: VectorSearchQuery<NamedTableInfo<DataModel, TableName>, IndexName>,
+) => Promise<VectorSearchPageResult<TableName>>;
+
 export type VectorSearch<
   DataModel extends GenericDataModel,
   TableName extends TableNamesInDataModel<DataModel>,
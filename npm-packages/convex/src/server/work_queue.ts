@@ -0,0 +1,73 @@
+import { Value } from "../values/value.js";
+
+/**
+ * Options for {@link enqueueMessage}.
+ *
+ * @public
+ */
+export type EnqueueOptions = {
+  /**
+   * How long to wait before the message can be dequeued, in milliseconds.
+   * Defaults to 0.
+   */
+  delayMs?: number;
+  /**
+   * How many times the message can be dequeued before it's dead-lettered.
+   * Defaults to 5.
+   */
+  maxAttempts?: number;
+};
+
+/**
+ * Options for {@link dequeueMessages}.
+ *
+ * @public
+ */
+export type DequeueOptions = {
+  /**
+   * How long leased messages stay hidden from other consumers, in
+   * milliseconds. If a message isn't acked or nacked before then, it can be
+   * dequeued again.
+   */
+  visibilityTimeoutMs: number;
+  /**
+   * The maximum number of messages to lease, between 1 and 100. Defaults to 1.
+   */
+  maxMessages?: number;
+};
+
+/**
+ * Options for {@link nackMessage}.
+ *
+ * @public
+ */
+export type NackOptions = {
+  /**
+   * How long to wait before the message can be dequeued again, in
+   * milliseconds. Defaults to 0.
+   */
+  delayMs?: number;
+  /**
+   * Why processing failed. Kept on the message and shown for dead letters.
+   */
+  error?: string;
+};
+
+/**
+ * A message leased from a work queue.
+ *
+ * @public
+ */
+export type LeasedMessage<T extends Value = Value> = {
+  id: string;
+  body: T;
+  /**
+   * Which attempt this lease is for, starting at 1. Pass it back to
+   * {@link ackMessage} or {@link nackMessage} to settle the message.
+   */
+  attempt: number;
+  /**
+   * The error recorded by the last {@link nackMessage}, if any.
+   */
+  lastError?: string;
+};
@@ -0,0 +1,115 @@
+import {
+  FunctionReference,
+  FunctionReturnType,
+  OptionalRestArgs,
+} from "./api.js";
+import { Value } from "../values/value.js";
+
+/**
+ * The interface a workflow handler uses to run its steps.
+ *
+ * Each step is journaled. When a step can't finish right away (an action, a
+ * sleep or waiting for an event), the handler is suspended and rerun from the
+ * start once the step completes, with the journaled results of earlier steps
+ * returned immediately. Handlers must therefore be deterministic: they should
+ * only make decisions based on their arguments and the results of their steps,
+ * and await each step before starting the next one.
+ *
+ * @public
+ */
+export interface WorkflowStep {
+  /**
+   * Run a query and journal its result.
+   */
+  runQuery<
+    Query extends FunctionReference<"query", "public" | "internal">,
+  >(
+    query: Query,
+    ...args: OptionalRestArgs<Query>
+  ): Promise<FunctionReturnType<Query>>;
+
+  /**
+   * Run a mutation and journal its result. The mutation's writes commit
+   * together with the journal entry.
+   */
+  runMutation<
+    Mutation extends FunctionReference<"mutation", "public" | "internal">,
+  >(
+    mutation: Mutation,
+    ...args: OptionalRestArgs<Mutation>
+  ): Promise<FunctionReturnType<Mutation>>;
+
+  /**
+   * Schedule an action and resume the workflow with its result once it
+   * finishes. If the action throws, so does this step.
+   */
+  runAction<
+    Action extends FunctionReference<"action", "public" | "internal">,
+  >(
+    action: Action,
+    ...args: OptionalRestArgs<Action>
+  ): Promise<FunctionReturnType<Action>>;
+
+  /**
+   * Suspend the workflow for `ms` milliseconds.
+   */
+  sleep(ms: number): Promise<void>;
+
+  /**
+   * Suspend the workflow until `event` is sent to it with
+   * {@link sendWorkflowEvent}, and return the event's value. Events sent
+   * before the workflow waits for them are buffered.
+   */
+  waitForEvent<T extends Value = Value>(event: string): Promise<T>;
+}
+
+/**
+ * A reference to a workflow defined with `workflowGeneric`.
+ *
+ * @public
+ */
+export type WorkflowReference = FunctionReference<
+  "mutation",
+  "internal",
+  { workflowId: string }
+>;
+
+/**
+ * The status of one journaled step of a workflow.
+ *
+ * @public
+ */
+export type WorkflowStepStatus = {
+  stepNumber: number;
+  name: string;
+  kind: "query" | "mutation" | "action" | "sleep" | "waitForEvent";
+  state: "pending" | "completed" | "failed";
+  result?: Value;
+  error?: string;
+};
+
+/**
+ * The status of a workflow, as returned by {@link getWorkflowStatus}.
+ *
+ * @public
+ */
+export type WorkflowStatus = {
+  state: "running" | "completed" | "failed" | "canceled";
+  /**
+   * The value the handler returned, if it completed.
+   */
+  result?: Value;
+  /**
+   * The error the handler threw, if it failed.
+   */
+  error?: string;
+  /**
+   * When the workflow started, in milliseconds since the epoch.
+   */
+  startTime: number;
+  /**
+   * When the workflow finished, in milliseconds since the epoch.
+   */
+  completedTime: number | null;
+  steps: WorkflowStepStatus[];
+};
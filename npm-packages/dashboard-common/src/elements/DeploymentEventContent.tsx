@@ -79,6 +79,7 @@ export function DeploymentEventContent({
     case "replace_environment_variable":
     case "update_canonical_url":
     case "delete_canonical_url":
+    case "update_egress_policy":
     case "change_deployment_state":
     case "clear_tables":
     default:
@@ -169,6 +170,9 @@ export function ActionText({ event }: { event: DeploymentAuditLogEvent }) {
         </>
       );
 
+    case "update_egress_policy":
+      return <span>updated the outbound request policy</span>;
+
     case "build_indexes":
       return <span>updated indexes</span>;
 
@@ -195,6 +199,35 @@ export function ActionText({ event }: { event: DeploymentAuditLogEvent }) {
     case "clear_tables":
       return <span>cleared tables</span>;
 
+    case "pause_cron_job":
+    case "resume_cron_job":
+    case "run_cron_job":
+      return (
+        <>
+          <span>
+            {event.action === "pause_cron_job"
+              ? "paused"
+              : event.action === "resume_cron_job"
+                ? "resumed"
+                : "ran"}{" "}
+            the cron job{" "}
+          </span>
+          <span className="font-mono font-semibold">
+            {event.metadata.cron_name}
+          </span>
+        </>
+      );
+
+    case "cancel_workflow":
+      return (
+        <>
+          <span>canceled the workflow </span>
+          <span className="font-mono font-semibold">
+            {event.metadata.workflow_id}
+          </span>
+        </>
+      );
+
     case "snapshot_import": {
       if (event.metadata.requestor.type === "cloudRestore") {
         return (
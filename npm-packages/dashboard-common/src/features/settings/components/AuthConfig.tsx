@@ -40,10 +40,18 @@ export function AuthConfig() {
                         label="Issuer"
                         value={provider.issuer}
                       />
-                      <ProviderAttribute
-                        label="JWKS URL"
-                        value={provider.jwks}
-                      />
+                      {provider.jwks && (
+                        <ProviderAttribute
+                          label="JWKS URL"
+                          value={provider.jwks}
+                        />
+                      )}
+                      {provider.secretEnvVar && (
+                        <ProviderAttribute
+                          label="Secret Environment Variable"
+                          value={provider.secretEnvVar}
+                        />
+                      )}
                       <ProviderAttribute
                         label="Algorithm"
                         value={provider.algorithm.replace(/^"(.*)"$/, "$1")}
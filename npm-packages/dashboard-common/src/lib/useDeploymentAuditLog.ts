@@ -43,12 +43,17 @@ function processDeploymentAuditLogEvent(
     case "replace_environment_variable":
     case "update_canonical_url":
     case "delete_canonical_url":
+    case "update_egress_policy":
     case "push_config":
     case "push_config_with_components":
     case "change_deployment_state":
     case "build_indexes":
     case "clear_tables":
     case "snapshot_import":
+    case "pause_cron_job":
+    case "resume_cron_job":
+    case "run_cron_job":
+    case "cancel_workflow":
       break;
     default:
       return null;
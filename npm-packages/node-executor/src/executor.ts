@@ -479,6 +479,7 @@ export type AnalyzedFunctions = Array<{
   visibility: Visibility | null;
   args: JSONValue | null;
   returns: JSONValue | null;
+  limits: JSONValue | null;
 }>;
 
 async function analyzeModule(filePath: string): Promise<AnalyzedFunctions> {
@@ -493,6 +494,7 @@ async function analyzeModule(filePath: string): Promise<AnalyzedFunctions> {
       visibility: Visibility | null;
       args: JSONValue | null;
       returns: JSONValue | null;
+      limits: JSONValue | null;
     }
   > = new Map();
   for (const [name, value] of Object.entries(module)) {
@@ -554,6 +556,16 @@ async function analyzeModule(filePath: string): Promise<AnalyzedFunctions> {
         returns = JSON.parse(exportedReturns);
       }
     }
+    let limits: string | null = null;
+    if (
+      Object.prototype.hasOwnProperty.call(value, "exportLimits") &&
+      typeof (value as any).exportLimits === "function"
+    ) {
+      const exportedLimits = (value as any).exportLimits();
+      if (typeof exportedLimits === "string") {
+        limits = JSON.parse(exportedLimits);
+      }
+    }
 
     if (isPublic && isInternal) {
       logDebug(`Skipping function marked as both public and internal: ${name}`);
@@ -564,6 +576,7 @@ async function analyzeModule(filePath: string): Promise<AnalyzedFunctions> {
         visibility: { kind: "public" },
         args,
         returns,
+        limits,
       });
     } else if (isInternal) {
       functions.set(name, {
@@ -571,9 +584,16 @@ async function analyzeModule(filePath: string): Promise<AnalyzedFunctions> {
         visibility: { kind: "internal" },
         args,
         returns,
+        limits,
       });
     } else {
-      functions.set(name, { udfType, visibility: null, args, returns });
+      functions.set(name, {
+        udfType,
+        visibility: null,
+        args,
+        returns,
+        limits,
+      });
     }
   }
   // Do an awful, regex based line match that assumes that moduleConfig.source originates from
@@ -46,6 +46,8 @@ const scheduleSchema = z.object({
   functionHandle: z.optional(z.string()),
   ts: z.number(),
   args: z.any(),
+  retry: z.optional(z.any()),
+  queue: z.optional(z.any()),
   version: z.string(),
 });
 
@@ -311,6 +313,10 @@ export class SyscallsImpl {
           return JSON.stringify(await this.syscallSchedule(jsonArgs));
         case "1.0/actions/cancel_job":
           return JSON.stringify(await this.syscallCancelJob(jsonArgs));
+        case "1.0/actions/rateLimit":
+          return JSON.stringify(await this.syscallRateLimit(jsonArgs));
+        case "1.0/actions/resetRateLimit":
+          return JSON.stringify(await this.syscallResetRateLimit(jsonArgs));
         case "1.0/getUserIdentity":
           return JSON.stringify(this.syscallGetUserIdentity(jsonArgs));
         case "1.0/storageGenerateUploadUrl": {
@@ -530,6 +536,8 @@ export class SyscallsImpl {
         udfPath: scheduleArgs.name,
         udfArgs: scheduleArgs.args,
         scheduledTs: scheduleArgs.ts,
+        retry: scheduleArgs.retry,
+        queue: scheduleArgs.queue,
       },
       path: "/api/actions/schedule_job",
       operationName,
@@ -557,6 +565,55 @@ export class SyscallsImpl {
     return null;
   }
 
+  async syscallRateLimit(rawArgs: string): Promise<JSONValue> {
+    const rateLimitSchema = z.object({
+      name: z.string(),
+      key: z.optional(z.string()),
+      config: z.any(),
+      count: z.optional(z.number()),
+      check: z.optional(z.boolean()),
+      version: z.string(),
+    });
+    const operationName = "rate limit";
+    const { version, ...body } = this.validateArgs(
+      rawArgs,
+      rateLimitSchema,
+      operationName,
+    );
+    return await this.actionCallback({
+      version,
+      body,
+      path: "/api/actions/rate_limit",
+      operationName,
+      responseValidator: z.object({
+        ok: z.boolean(),
+        retryAfter: z.optional(z.number()),
+      }),
+    });
+  }
+
+  async syscallResetRateLimit(rawArgs: string): Promise<JSONValue> {
+    const resetRateLimitSchema = z.object({
+      name: z.string(),
+      key: z.optional(z.string()),
+      version: z.string(),
+    });
+    const operationName = "reset rate limit";
+    const { version, ...body } = this.validateArgs(
+      rawArgs,
+      resetRateLimitSchema,
+      operationName,
+    );
+    await this.actionCallback({
+      version,
+      body,
+      path: "/api/actions/reset_rate_limit",
+      operationName,
+      responseValidator: z.any(),
+    });
+    return null;
+  }
+
   syscallGetUserIdentity(rawArgs: string): JSONValue {
     this.validateArgs(rawArgs, z.any(), "get user identity");
     return this.userIdentity as JSONValue;
@@ -23,6 +23,8 @@ import {
   SearchFilter,
   SearchFilterBuilder,
   SearchIndexes,
+  SearchQuery,
+  SearchSuggestion,
   TableNamesInDataModel,
   WithoutSystemFields,
   DefaultFunctionArgs,
@@ -184,6 +186,9 @@ class WrapQuery<T extends GenericTableInfo> implements Query<T> {
     }
     return results;
   }
+  async takeWithFacets(): Promise<never> {
+    throw new Error("takeWithFacets isn't supported with row level security");
+  }
   async first(): Promise<DocumentByInfo<T> | null> {
     for await (const result of this) {
       return result;
@@ -256,7 +261,7 @@ class WrapQueryInitializer<T extends GenericTableInfo>
     searchFilter: (
       q: SearchFilterBuilder<DocumentByInfo<T>, NamedSearchIndex<T, IndexName>>,
     ) => SearchFilter,
-  ): OrderedQuery<T> {
+  ): SearchQuery<T, IndexName> {
     return new WrapQuery(
       this.q.withSearchIndex(indexName, searchFilter),
       this.p,
@@ -345,6 +350,12 @@ class WrapReader<Ctx, DataModel extends GenericDataModel>
     return this.db.normalizeId(tableName, id);
   }
 
+  suggest(): Promise<SearchSuggestion[]> {
+    // Suggestions are built from every document's terms, so they can't be
+    // filtered by the read rules.
+    throw new Error("db.suggest isn't supported with row level security");
+  }
+
   private _tableName<TableName extends string>(
     id: GenericId<TableName>,
   ): TableName | null {
@@ -438,6 +449,9 @@ class WrapWriter<Ctx, DataModel extends GenericDataModel>
   query<TableName extends string>(tableName: TableName): QueryInitializer<any> {
     return this.reader.query(tableName);
   }
+  suggest(): Promise<SearchSuggestion[]> {
+    return this.reader.suggest();
+  }
 
   normalizeId<TableName extends TableNamesInDataModel<DataModel>>(
     tableName: TableName,
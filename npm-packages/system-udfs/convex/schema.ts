@@ -33,6 +33,12 @@ export const udfVisibility = v.union(
   v.object({ kind: v.literal("internal") }),
 );
 
+const egressRule = v.object({
+  // A host pattern like "*.example.com" or a CIDR block like "10.0.0.0/8".
+  destination: v.string(),
+  ports: v.array(v.int64()),
+});
+
 const analyzedSourcePosition = v.object({
   path: v.string(),
   start_lineno: v.int64(),
@@ -68,34 +74,47 @@ const CronSchedule = v.union(
   v.object({
     type: v.literal("hourly"),
     minuteUTC: v.int64(),
+    timezone: v.optional(v.string()),
   }),
   v.object({
     type: v.literal("daily"),
     minuteUTC: v.int64(),
     hourUTC: v.int64(),
+    timezone: v.optional(v.string()),
   }),
   v.object({
     type: v.literal("weekly"),
     dayOfWeek: v.int64(),
     hourUTC: v.int64(),
     minuteUTC: v.int64(),
+    timezone: v.optional(v.string()),
   }),
   v.object({
     type: v.literal("monthly"),
     day: v.int64(),
     minuteUTC: v.int64(),
     hourUTC: v.int64(),
+    timezone: v.optional(v.string()),
   }),
   v.object({
     type: v.literal("cron"),
     cronExpr: v.string(),
+    timezone: v.optional(v.string()),
   }),
 );
 
+const CronCatchUpPolicy = v.union(
+  v.object({ type: v.literal("skip") }),
+  v.object({ type: v.literal("runOnce") }),
+  v.object({ type: v.literal("runAll"), max: v.int64() }),
+);
+
 const analyzedCronSpec = v.object({
   udfPath: v.string(),
   udfArgs: v.bytes(),
   cronSchedule: CronSchedule,
+  catchUp: v.optional(CronCatchUpPolicy),
+  jitterSeconds: v.optional(v.int64()),
 });
 
 const mappedModule = v.object({
@@ -352,7 +371,9 @@ export default defineSchema({
         type: v.literal("customJwt"),
         applicationID: v.union(v.string(), v.null()),
         issuer: v.string(),
-        jwks: v.string(),
+        // Null for HS256 providers, which use `secretEnvVar` instead.
+        jwks: v.union(v.string(), v.null()),
+        secretEnvVar: v.optional(v.union(v.string(), v.null())),
         // This is serialized *wrapped with double quotes* (!)
         algorithm: v.string(),
       }),
@@ -401,12 +422,21 @@ export default defineSchema({
     ),
     udfArgs: v.bytes(),
     component: v.optional(v.string()),
+    queue: v.optional(v.string()),
+    pendingQueue: v.optional(v.string()),
   })
     .index("by_udf_path_and_next_event_ts", ["udfPath", "nextTs"])
-    .index("by_next_ts", ["nextTs"]),
+    .index("by_next_ts", ["nextTs"])
+    .index("by_pending_queue_and_next_ts", ["pendingQueue", "nextTs"]),
+  _scheduled_job_queues: defineTable({
+    name: v.string(),
+    maxConcurrency: v.int64(),
+    priority: v.int64(),
+  }).index("by_name", ["name"]),
   _cron_jobs: defineTable({
     name: v.string(),
     cronSpec: analyzedCronSpec,
+    paused: v.optional(v.boolean()),
   }).index("by_name", ["name"]),
   _cron_next_run: defineTable({
     cronJobId: v.id("_cron_jobs"),
@@ -428,9 +458,77 @@ export default defineSchema({
     }),
     executionTime: v.number(),
   }).index("by_name_and_ts", ["name", "ts"]),
+  _workflows: defineTable({
+    component: v.string(),
+    udfPath: v.string(),
+    args: v.bytes(),
+    state: v.union(
+      v.object({ type: v.literal("running") }),
+      v.object({ type: v.literal("completed"), result: v.bytes() }),
+      v.object({ type: v.literal("failed"), error: v.string() }),
+      v.object({ type: v.literal("canceled") }),
+    ),
+    startTs: v.int64(),
+    completedTs: v.union(v.int64(), v.null()),
+    replayJobId: v.union(v.id("_scheduled_jobs"), v.null()),
+  }),
+  _workflow_steps: defineTable({
+    workflowId: v.id("_workflows"),
+    stepNumber: v.int64(),
+    name: v.string(),
+    kind: v.union(
+      v.object({ type: v.literal("query") }),
+      v.object({ type: v.literal("mutation") }),
+      v.object({
+        type: v.literal("action"),
+        scheduledJobId: v.id("_scheduled_jobs"),
+      }),
+      v.object({ type: v.literal("sleep"), wakeTs: v.int64() }),
+      v.object({ type: v.literal("waitForEvent"), event: v.string() }),
+    ),
+    state: v.union(
+      v.object({ type: v.literal("pending") }),
+      v.object({ type: v.literal("completed"), result: v.bytes() }),
+      v.object({ type: v.literal("failed"), error: v.string() }),
+    ),
+    startTs: v.int64(),
+    completedTs: v.union(v.int64(), v.null()),
+  }).index("by_workflow_id_and_step_number", ["workflowId", "stepNumber"]),
+  _workflow_events: defineTable({
+    workflowId: v.id("_workflows"),
+    event: v.string(),
+    value: v.bytes(),
+  }).index("by_workflow_id_and_event", ["workflowId", "event"]),
+  _work_queue_messages: defineTable({
+    queue: v.string(),
+    shard: v.int64(),
+    body: v.bytes(),
+    visibleTs: v.int64(),
+    attempts: v.int64(),
+    maxAttempts: v.int64(),
+    deadLettered: v.boolean(),
+    lastError: v.union(v.string(), v.null()),
+  }).index("by_queue_and_visible_ts", [
+    "queue",
+    "deadLettered",
+    "shard",
+    "visibleTs",
+  ]),
+  _rate_limits: defineTable({
+    name: v.string(),
+    key: v.union(v.string(), v.null()),
+    shard: v.int64(),
+    value: v.float64(),
+    ts: v.int64(),
+  }).index("by_name_key_and_shard", ["name", "key", "shard"]),
   _udf_config: defineTable({ serverVersion: v.string() }),
   _schemas: defineTable(schemaMetadata).index("by_state", ["state"]),
   _log_sinks: logSinksTable,
+  _egress_policy: defineTable({
+    allow: v.array(egressRule),
+    deny: v.array(egressRule),
+    logRequests: v.boolean(),
+  }),
   _backend_state: backendStateTable,
   _snapshot_imports: snapshotImportsTable,
 });
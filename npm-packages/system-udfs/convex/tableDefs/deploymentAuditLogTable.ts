@@ -56,6 +56,21 @@ const deleteCanonicalUrl = v.object({
   }),
 });
 
+const egressRule = v.object({
+  destination: v.string(),
+  ports: v.array(v.int64()),
+});
+
+const updateEgressPolicy = v.object({
+  action: v.literal("update_egress_policy"),
+  member_id: v.string(),
+  metadata: v.object({
+    allow: v.array(egressRule),
+    deny: v.array(egressRule),
+    logRequests: v.boolean(),
+  }),
+});
+
 const databaseIndex = v.object({
   name: v.optional(v.string()),
   type: v.literal("database"),
@@ -216,6 +231,42 @@ export const snapshotImport = v.object({
   }),
 });
 
+export const pauseCronJob = v.object({
+  action: v.literal("pause_cron_job"),
+  member_id: v.union(v.int64(), v.null()),
+  metadata: v.object({
+    component: v.union(v.null(), v.string()),
+    cron_name: v.string(),
+  }),
+});
+
+export const resumeCronJob = v.object({
+  action: v.literal("resume_cron_job"),
+  member_id: v.union(v.int64(), v.null()),
+  metadata: v.object({
+    component: v.union(v.null(), v.string()),
+    cron_name: v.string(),
+  }),
+});
+
+export const runCronJob = v.object({
+  action: v.literal("run_cron_job"),
+  member_id: v.union(v.int64(), v.null()),
+  metadata: v.object({
+    component: v.union(v.null(), v.string()),
+    cron_name: v.string(),
+  }),
+});
+
+export const cancelWorkflow = v.object({
+  action: v.literal("cancel_workflow"),
+  member_id: v.union(v.int64(), v.null()),
+  metadata: v.object({
+    component: v.union(v.null(), v.string()),
+    workflow_id: v.string(),
+  }),
+});
+
 const deploymentAuditLogTable = defineTable(
   v.union(
     createEnvironmentVariable,
@@ -224,12 +275,17 @@ const deploymentAuditLogTable = defineTable(
     replaceEnvironmentVariable,
     updateCanonicalUrl,
     deleteCanonicalUrl,
+    updateEgressPolicy,
     buildIndexes,
     pushConfig,
     pushConfigWithComponents,
     changeDeploymentState,
     clearTables,
     snapshotImport,
+    pauseCronJob,
+    resumeCronJob,
+    runCronJob,
+    cancelWorkflow,
   ),
 );
 
@@ -32,6 +32,7 @@ export function setup(global: any) {
   // since those may call into 3rd party libraries we bundle, which may then
   // retain references to globals we modify, like `Date` or `FinalizationRegistry`.
   setupMisc(global);
+  setupWebAssembly(global);
 
   // These need to be set up in order of the numbers in their filenames (taken
   // from Deno) since later ones depend on the earlier ones.
@@ -146,6 +147,52 @@ function setupMisc(global) {
   global.FinalizationRegistry = FinalizationRegistry;
 }
 
+function setupWebAssembly(global) {
+  // V8 refuses to compile WebAssembly from bytes unless it goes through the
+  // `wasm/compile` op, which checks the module is safe to run first. Patch the
+  // APIs that compile bytes to use it. Modules imported from `.wasm` files are
+  // checked when they're loaded.
+  const WebAssembly = global.WebAssembly;
+  const OriginalModule = WebAssembly.Module;
+  const originalInstantiate = WebAssembly.instantiate;
+
+  const compile = (source: any) => {
+    let bytes: Uint8Array;
+    if (source instanceof ArrayBuffer) {
+      bytes = new Uint8Array(source);
+    } else if (ArrayBuffer.isView(source)) {
+      bytes = new Uint8Array(
+        source.buffer,
+        source.byteOffset,
+        source.byteLength,
+      );
+    } else {
+      throw new TypeError(
+        "WebAssembly source must be an ArrayBuffer or a typed array",
+      );
+    }
+    return performOp("wasm/compile", bytes);
+  };
+
+  // Keep `WebAssembly.Module`'s static methods and `instanceof` behavior.
+  WebAssembly.Module = new Proxy(OriginalModule, {
+    construct(_target, args) {
+      return compile(args[0]);
+    },
+  });
+  WebAssembly.compile = async function (source: any) {
+    return compile(source);
+  };
+  WebAssembly.instantiate = async function (source: any, importObject?: any) {
+    if (source instanceof OriginalModule) {
+      return originalInstantiate(source, importObject);
+    }
+    const module = compile(source);
+    const instance = await originalInstantiate(module, importObject);
+    return { module, instance };
+  };
+}
+
 // No-op implementation of https://tc39.es/ecma262/multipage/managing-memory.html#sec-finalization-registry.prototype.register
 class FinalizationRegistry {
   constructor(callbackFn: (heldValue: any) => void) {
@@ -65,7 +65,9 @@ import type * as load_failure from "../load_failure.js";
 import type * as logging from "../logging.js";
 import type * as name from "../name.js";
 import type * as node_actions from "../node_actions.js";
+import type * as profiling from "../profiling.js";
 import type * as query from "../query.js";
+import type * as replay from "../replay.js";
 import type * as returns_validation from "../returns_validation.js";
 import type * as scheduler from "../scheduler.js";
 import type * as search from "../search.js";
@@ -79,6 +81,7 @@ import type * as userError from "../userError.js";
 import type * as values from "../values.js";
 import type * as vector_search from "../vector_search.js";
 import type * as wasmTests from "../wasmTests.js";
+import type * as workflows from "../workflows.js";
 
 /**
  * A utility for referencing Convex functions in your app's API.
@@ -141,7 +144,9 @@ declare const fullApi: ApiFromModules<{
   logging: typeof logging;
   name: typeof name;
   node_actions: typeof node_actions;
+  profiling: typeof profiling;
   query: typeof query;
+  replay: typeof replay;
   returns_validation: typeof returns_validation;
   scheduler: typeof scheduler;
   search: typeof search;
@@ -155,6 +160,7 @@ declare const fullApi: ApiFromModules<{
   values: typeof values;
   vector_search: typeof vector_search;
   wasmTests: typeof wasmTests;
+  workflows: typeof workflows;
 }>;
 export declare const api: FilterApi<
   typeof fullApi,
@@ -76,6 +76,30 @@ export const queryLeak = query(async ({ db }) => {
   }
 });
 
+export const queryWithReadLimit = query({
+  limits: { maxDocumentsRead: 10 },
+  handler: async ({ db }) => {
+    return (await db.query("test").collect()).length;
+  },
+});
+
+export const memoryLimitWithoutYielding = query({
+  limits: { memoryMb: 16 },
+  handler: async () => {
+    // Go over the limit and then keep running without yielding to the
+    // event loop.
+    const parts = [];
+    for (let i = 0; i < 6000; i++) {
+      parts.push(new Array(1000).fill(i));
+    }
+    let sum = 0;
+    for (let i = 0; i < 100_000_000; i++) {
+      sum += i;
+    }
+    return parts.length + sum;
+  },
+});
+
 export const queryATon = query(async ({ db }) => {
   for (let i = 0; i < 30000; i++) {
     for await (const _row of db.query("test")) {
@@ -3,6 +3,7 @@
 // https://github.com/denoland/deno/blob/main/LICENSE.md
 import { wrapInTests } from "./js_builtins/testHelpers";
 import { assert, expect } from "chai";
+import { v } from "convex/values";
 import { action, ActionCtx, query } from "./_generated/server";
 import { api } from "./_generated/api";
 
@@ -1782,6 +1783,18 @@ export const fetchUnendingRequest = action(async () => {
   throw new Error(`fetch should not complete`);
 });
 
+export const fetchUrl = action({
+  args: { url: v.string() },
+  handler: async (_ctx, { url }) => {
+    try {
+      const response = await fetch(url);
+      return `${response.status} ${await response.text()}`;
+    } catch (e: any) {
+      return e.message;
+    }
+  },
+});
+
 // Regression test for https://webtechsurvey.com/response-header/x-olaf
 async function fetchOlaf() {
   const response = await fetch("http://localhost:4545/echo_server", {
@@ -0,0 +1,13 @@
+import { query } from "./_generated/server";
+
+function spin(iterations: number) {
+  let total = 0;
+  for (let i = 0; i < iterations; i++) {
+    total += Math.sqrt(i);
+  }
+  return total;
+}
+
+export const busy = query(async () => {
+  return spin(20_000_000);
+});
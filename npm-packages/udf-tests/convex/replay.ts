@@ -0,0 +1,15 @@
+import { mutation } from "./_generated/server";
+
+export const insertRandomObject = mutation(async ({ db }) => {
+  const existing = await db.query("objects").collect();
+  const id = await db.insert("objects", {
+    random: Math.random(),
+    now: Date.now(),
+  });
+  return {
+    count: existing.length,
+    id,
+    random: Math.random(),
+    now: Date.now(),
+  };
+});
@@ -28,6 +28,19 @@ export const paginatedSearch = query(
   },
 );
 
+export const populateSuggest = mutation(async ({ db }) => {
+  const messages = ["apple pie", "apple tart", "apricot jam", "banana"];
+  for (const message of messages) {
+    await db.insert("messages", { body: message });
+  }
+});
+
+export const suggest = query(
+  async ({ db }, { prefix }: { prefix: string }) => {
+    return db.suggest("messages", "by_body", prefix, { limit: 5 });
+  },
+);
+
 export const createDocumentAndSearchForIt = mutation(async ({ db }) => {
   await db.insert("messages", {
     body: "a",
@@ -130,3 +130,26 @@ export const noFilter = action({
     return "success";
   },
 });
+
+export const nearestIds = query({
+  args: { vector: v.array(v.number()), limit: v.number() },
+  handler: async (ctx, { vector, limit }) => {
+    const results = await ctx.vectorSearch("vectorTable", "vector", {
+      vector,
+      limit,
+    });
+    return results.map((r) => r._id);
+  },
+});
+
+export const insertVector = mutation({
+  args: { vector: v.array(v.number()) },
+  handler: async (ctx, { vector }) => {
+    return await ctx.db.insert("vectorTable", {
+      vector,
+      filterA: "Z",
+      filterB: false,
+      id: "inserted",
+    });
+  },
+});
@@ -0,0 +1,22 @@
+import { workflowGeneric } from "convex/server";
+import { v } from "convex/values";
+import { api } from "./_generated/api";
+import { action, mutation } from "./_generated/server";
+
+export const double = action({
+  args: { x: v.number() },
+  handler: async (_ctx, { x }) => x * 2,
+});
+
+export const increment = mutation({
+  args: { x: v.number() },
+  handler: async (_ctx, { x }) => x + 1,
+});
+
+export const doubleThenIncrement = workflowGeneric({
+  handler: async (step, { x }: { x: number }) => {
+    const doubled = await step.runAction(api.workflows.double, { x });
+    await step.sleep(1000);
+    return await step.runMutation(api.workflows.increment, { x: doubled });
+  },
+});